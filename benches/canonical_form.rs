@@ -0,0 +1,79 @@
+//! Measure simplification of canonical forms by summing values of Domineering positions, and
+//! computation of their temperatures and stops, with and without caches.
+//!
+//! Run with `cargo bench --bench canonical_form`.
+
+use cgt::{
+    grid::small_bit_grid::SmallBitGrid,
+    short::partizan::{
+        canonical_form::{comparison_cache, thermograph_cache, CanonicalForm},
+        games::domineering::Domineering,
+        partizan_game::PartizanGame,
+        transposition_table::ParallelTranspositionTable,
+    },
+};
+use std::{hint::black_box, str::FromStr, time::Instant};
+
+const ITERATIONS: usize = 5;
+
+/// Values of all positions reachable from an empty `width`x`height` board
+fn domineering_values(width: u8, height: u8) -> Vec<CanonicalForm> {
+    let grid = SmallBitGrid::from_str(&vec![".".repeat(width as usize); height as usize].join("|"))
+        .unwrap();
+    let transposition_table = ParallelTranspositionTable::new();
+    Domineering::new(grid).canonical_form(&transposition_table);
+    transposition_table.snapshot().values
+}
+
+fn bench_sums(name: &str, values: &[CanonicalForm], cache: bool) {
+    comparison_cache::set_enabled(cache);
+    comparison_cache::reset_statistics();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for g in values {
+            for h in values {
+                black_box(g + h);
+            }
+        }
+    }
+    println!(
+        "{name} ({} values, cache {}): {:?}, comparison cache: {}",
+        values.len(),
+        if cache { "on" } else { "off" },
+        start.elapsed() / ITERATIONS as u32,
+        comparison_cache::statistics()
+    );
+}
+
+fn bench_temperatures(name: &str, values: &[CanonicalForm], cache: bool) {
+    thermograph_cache::set_enabled(cache);
+    thermograph_cache::reset_statistics();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for g in values {
+            black_box((g.temperature(), g.left_stop(), g.right_stop()));
+        }
+    }
+    println!(
+        "{name} ({} values, cache {}): {:?}, thermograph cache: {}",
+        values.len(),
+        if cache { "on" } else { "off" },
+        start.elapsed() / ITERATIONS as u32,
+        thermograph_cache::statistics()
+    );
+}
+
+fn main() {
+    for (width, height) in [(3, 4), (4, 4)] {
+        let values = domineering_values(width, height);
+        let name = format!("Domineering {width}x{height} sums");
+        bench_sums(&name, &values, false);
+        bench_sums(&name, &values, true);
+    }
+    for (width, height) in [(4, 4), (4, 5)] {
+        let values = domineering_values(width, height);
+        let name = format!("Domineering {width}x{height} temperatures");
+        bench_temperatures(&name, &values, false);
+        bench_temperatures(&name, &values, true);
+    }
+}
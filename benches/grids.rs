@@ -0,0 +1,56 @@
+//! Compare grid backends on Domineering, both on full search and on move generation alone.
+//!
+//! Run with `cargo bench --bench grids`.
+
+use cgt::{
+    grid::{fixed_grid::FixedGrid, small_bit_grid::SmallBitGrid, FiniteGrid, Grid},
+    short::partizan::{
+        games::domineering::{Domineering, Tile},
+        partizan_game::PartizanGame,
+        transposition_table::ParallelTranspositionTable,
+    },
+};
+use std::{hint::black_box, time::Instant};
+
+const ITERATIONS: usize = 5;
+
+fn bench<G>(name: &str, width: u8, height: u8)
+where
+    G: Grid<Item = Tile> + FiniteGrid + Clone + std::hash::Hash + Send + Sync + Ord,
+{
+    let grid = G::filled(width, height, Tile::Empty).unwrap();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let transposition_table = ParallelTranspositionTable::new();
+        black_box(Domineering::new(grid.clone()).canonical_form(&transposition_table));
+    }
+    println!(
+        "{name} {width}x{height}: {:?}",
+        start.elapsed() / ITERATIONS as u32
+    );
+}
+
+fn bench_perft<G>(name: &str, width: u8, height: u8, depth: u32)
+where
+    G: Grid<Item = Tile> + FiniteGrid + Clone + std::hash::Hash + Send + Sync + Ord,
+{
+    let position = Domineering::new(G::filled(width, height, Tile::Empty).unwrap());
+    let start = Instant::now();
+    let mut leaves = 0;
+    for _ in 0..ITERATIONS {
+        leaves = black_box(position.perft(depth));
+    }
+    println!(
+        "{name} {width}x{height} perft({depth}) = {leaves}: {:?}",
+        start.elapsed() / ITERATIONS as u32
+    );
+}
+
+fn main() {
+    bench_perft::<SmallBitGrid<Tile>>("SmallBitGrid", 5, 5, 3);
+    bench_perft::<FixedGrid<Tile, 5, 5>>("FixedGrid", 5, 5, 3);
+    bench::<SmallBitGrid<Tile>>("SmallBitGrid", 4, 4);
+    bench::<FixedGrid<Tile, 4, 4>>("FixedGrid", 4, 4);
+    bench::<SmallBitGrid<Tile>>("SmallBitGrid", 5, 5);
+    bench::<FixedGrid<Tile, 5, 5>>("FixedGrid", 5, 5);
+}
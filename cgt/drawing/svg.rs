@@ -1,6 +1,7 @@
 //! Simple SVG immediate drawing utilities
 #![allow(clippy::missing_errors_doc, clippy::new_ret_no_self)]
 
+use alloc::string::String;
 use std::fmt::{self, Write};
 
 /// Object that can be rendered as SVG
@@ -105,7 +106,7 @@ pub struct Grid {
 }
 
 impl ImmSvg {
-    /// Create new SVG
+    /// Create new standalone SVG
     pub fn new<W>(
         w: &mut W,
         width: u32,
@@ -115,7 +116,11 @@ impl ImmSvg {
     where
         W: Write,
     {
-        write!(w, "<svg width=\"{}\" height=\"{}\">", width, height)?;
+        write!(
+            w,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">",
+            width, height
+        )?;
         cont(w)?;
         write!(w, "</svg>")
     }
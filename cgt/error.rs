@@ -0,0 +1,59 @@
+//! Errors reported by the library
+//!
+//! Functions return the most specific error type, e.g. grid parsers return [`ParseGridError`],
+//! and all of them convert into [`Error`], so they can be mixed with `?`.
+
+use crate::{
+    graph::InvalidMatrixSize,
+    grid::ParseGridError,
+    short::partizan::{
+        cancellation::Cancelled,
+        games::{
+            amazons::ParseAmazonsError, domineering::ParseDomineeringError, snort::InvalidPosition,
+        },
+    },
+};
+
+/// Any error reported by the library
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// Grid could not be parsed
+    #[error(transparent)]
+    Grid(#[from] ParseGridError),
+
+    /// Amazons position could not be parsed
+    #[error(transparent)]
+    Amazons(#[from] ParseAmazonsError),
+
+    /// Adjacency matrix has wrong number of entries
+    #[error(transparent)]
+    MatrixSize(#[from] InvalidMatrixSize),
+
+    /// Position breaks rules of the game
+    #[error(transparent)]
+    Position(#[from] InvalidPosition),
+
+    /// Input does not follow the expected notation
+    #[error("{0}")]
+    Syntax(String),
+
+    /// Computation was aborted
+    #[error(transparent)]
+    Cancelled(#[from] Cancelled),
+}
+
+/// Invalid boards are reported as [`Error::Grid`] like for other grid games, and invalid
+/// topologies as [`Error::Syntax`]
+impl From<ParseDomineeringError> for Error {
+    #[inline]
+    fn from(error: ParseDomineeringError) -> Self {
+        match error {
+            ParseDomineeringError::Grid(error) => Self::Grid(error),
+            ParseDomineeringError::InvalidTopology(_) => Self::Syntax(error.to_string()),
+        }
+    }
+}
+
+/// Result with [`Error`] as the default error type
+pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -0,0 +1,284 @@
+//! Genetic search for positions of games
+//!
+//! Positions evolve as [`Chromosome`]s that can be mutated and crossed, and are scored by a
+//! [`Fitness`], usually computed from their canonical forms with [`evaluate_all`]. [`Search`]
+//! combines both into an [`Algorithm`] run by [`GeneticAlgorithm`], so programs can embed the
+//! search instead of running `cgt-cli`. Search for hot Snort positions is in [`snort`], and
+//! [`islands`] runs many populations in parallel.
+//!
+//! ```
+//! use cgt::{
+//!     genetic::{Evaluated, GeneticAlgorithm, Search},
+//!     short::partizan::{
+//!         batch::Evaluation,
+//!         games::domineering::Domineering,
+//!         random_position::{GridParameters, RandomPosition},
+//!     },
+//! };
+//! use rand::rngs::StdRng;
+//! use std::num::NonZeroUsize;
+//!
+//! let parameters = GridParameters {
+//!     width: 3,
+//!     height: 3,
+//!     pieces: 0,
+//!     density: 0.3,
+//! };
+//! let search = Search::new(
+//!     Evaluated::new(|_: &Domineering, evaluation: &Evaluation| {
+//!         evaluation.canonical_form.temperature().to_rational()
+//!     }),
+//!     |rng: &mut StdRng| Domineering::random_position(&parameters, rng).unwrap(),
+//!     0.1,
+//! );
+//! let mut alg = GeneticAlgorithm::with_specimen_seeded(
+//!     Vec::new(),
+//!     NonZeroUsize::new(8).unwrap(),
+//!     search,
+//!     42,
+//! );
+//! for _ in 0..3 {
+//!     alg.step_generation();
+//! }
+//! assert!(alg.highest_score().score >= alg.specimen()[0].score);
+//! ```
+
+use crate::{
+    grid::FiniteGrid,
+    numeric::rational::Rational,
+    short::partizan::{
+        batch::{evaluate_all, Evaluation},
+        partizan_game::PartizanGame,
+        transposition_table::ParallelTranspositionTable,
+    },
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+use std::hash::Hash;
+
+pub use crate::genetic_algorithm::{
+    Algorithm, Checkpoint, GeneticAlgorithm, ParentSelection, Scored, Selection,
+};
+
+pub mod islands;
+pub mod snort;
+
+/// Objects that can evolve in a genetic search, usually positions of games
+pub trait Chromosome: Clone {
+    /// Randomly change parts of the object, each with `mutation_rate` probability
+    fn mutate<R>(&mut self, mutation_rate: f32, rng: &mut R)
+    where
+        R: Rng;
+
+    /// Combine two objects into one
+    #[must_use]
+    fn crossover<R>(&self, other: &Self, rng: &mut R) -> Self
+    where
+        R: Rng;
+}
+
+/// Evaluation function of a genetic search
+pub trait Fitness<C> {
+    /// Score of objects. Search tries to maximize it according to [`Ord`]
+    type Score: Clone + Ord;
+
+    /// Lowest possible score, given to new objects before they are scored
+    fn lowest_score(&self) -> Self::Score;
+
+    /// Score of a single object
+    fn fitness(&self, chromosome: &C) -> Self::Score;
+
+    /// Scores of all objects of a generation. Override to evaluate objects together, e.g. with
+    /// [`evaluate_all`]
+    fn fitness_all(&self, chromosomes: &[C]) -> Vec<Self::Score> {
+        chromosomes
+            .iter()
+            .map(|chromosome| self.fitness(chromosome))
+            .collect()
+    }
+}
+
+/// Fitness computed from canonical forms of positions, evaluated with [`evaluate_all`] and
+/// sharing one transposition table between all generations
+pub struct Evaluated<G, F> {
+    transposition_table: ParallelTranspositionTable<G>,
+    score: F,
+}
+
+impl<G, F> Evaluated<G, F>
+where
+    G: Hash + Eq,
+{
+    /// Create fitness where `score` computes score of a position from its evaluation
+    pub fn new(score: F) -> Self {
+        Self {
+            transposition_table: ParallelTranspositionTable::new(),
+            score,
+        }
+    }
+
+    /// Get transposition table shared by all evaluations
+    pub const fn transposition_table(&self) -> &ParallelTranspositionTable<G> {
+        &self.transposition_table
+    }
+}
+
+impl<G, F> Fitness<G> for Evaluated<G, F>
+where
+    G: PartizanGame + Hash + Eq + Send + Sync,
+    F: Fn(&G, &Evaluation) -> Rational,
+{
+    type Score = Rational;
+
+    fn lowest_score(&self) -> Rational {
+        Rational::NegativeInfinity
+    }
+
+    fn fitness(&self, position: &G) -> Rational {
+        self.fitness_all(std::slice::from_ref(position))[0]
+    }
+
+    fn fitness_all(&self, positions: &[G]) -> Vec<Rational> {
+        positions
+            .iter()
+            .zip(evaluate_all(positions, &self.transposition_table))
+            .map(|(position, evaluation)| (self.score)(position, &evaluation))
+            .collect()
+    }
+}
+
+/// Genetic search of [`Chromosome`]s scored by a [`Fitness`]
+pub struct Search<F, S> {
+    fitness: F,
+    spawn: S,
+    mutation_rate: f32,
+}
+
+impl<F, S> Search<F, S> {
+    /// Create search where `spawn` creates random objects filling the first generation, e.g.
+    /// with [`RandomPosition`](crate::short::partizan::random_position::RandomPosition)
+    pub const fn new(fitness: F, spawn: S, mutation_rate: f32) -> Self {
+        Self {
+            fitness,
+            spawn,
+            mutation_rate,
+        }
+    }
+
+    /// Get fitness of the search
+    pub const fn fitness(&self) -> &F {
+        &self.fitness
+    }
+}
+
+impl<C, F, S> Algorithm<C, F::Score> for Search<F, S>
+where
+    C: Chromosome,
+    F: Fitness<C>,
+    S: Fn(&mut StdRng) -> C,
+{
+    fn mutate(&self, chromosome: &mut C, rng: &mut StdRng) {
+        chromosome.mutate(self.mutation_rate, rng);
+    }
+
+    fn cross(&self, lhs: &C, rhs: &C, rng: &mut StdRng) -> C {
+        lhs.crossover(rhs, rng)
+    }
+
+    fn lowest_score(&self) -> F::Score {
+        self.fitness.lowest_score()
+    }
+
+    fn score(&self, chromosome: &C) -> F::Score {
+        self.fitness.fitness(chromosome)
+    }
+
+    fn score_all(&self, chromosomes: &[C]) -> Vec<F::Score> {
+        self.fitness.fitness_all(chromosomes)
+    }
+
+    fn random(&self, rng: &mut StdRng) -> C {
+        (self.spawn)(rng)
+    }
+}
+
+/// Replace every tile of the grid, with `mutation_rate` probability, with one of `tiles`
+pub fn mutate_grid<G, R>(grid: &mut G, tiles: &[G::Item], mutation_rate: f32, rng: &mut R)
+where
+    G: FiniteGrid,
+    G::Item: Copy,
+    R: Rng,
+{
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            if rng.gen::<f32>() < mutation_rate {
+                grid.set(x, y, *tiles.choose(rng).unwrap());
+            }
+        }
+    }
+}
+
+/// Take rows above a random row from `lhs` and the rest from `rhs`. Grids of different sizes
+/// cannot be crossed, then `lhs` is returned.
+pub fn cross_grids<G, R>(lhs: &G, rhs: &G, rng: &mut R) -> G
+where
+    G: FiniteGrid + Clone,
+    R: Rng,
+{
+    let mut grid = lhs.clone();
+    if lhs.width() != rhs.width() || lhs.height() != rhs.height() {
+        return grid;
+    }
+
+    let split = rng.gen_range(0..=lhs.height());
+    for y in split..lhs.height() {
+        for x in 0..lhs.width() {
+            grid.set(x, y, rhs.get(x, y));
+        }
+    }
+    grid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::games::toads_and_frogs::{Tile, ToadsAndFrogs};
+    use rand::SeedableRng;
+
+    #[test]
+    fn crossover_takes_tail_of_other_parent() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let lhs = ToadsAndFrogs::new(vec![Tile::Toad; 4]);
+        let rhs = ToadsAndFrogs::new(vec![Tile::Frog; 6]);
+        for _ in 0..16 {
+            let child = lhs.crossover(&rhs, &mut rng);
+            assert_eq!(child.tiles().len(), 6);
+            assert!(child.tiles()[..4].iter().all(|tile| *tile != Tile::Empty));
+        }
+    }
+
+    #[test]
+    fn search_keeps_best_position() {
+        let search = Search::new(
+            Evaluated::new(|_: &ToadsAndFrogs, evaluation: &Evaluation| {
+                evaluation.canonical_form.temperature().to_rational()
+            }),
+            |rng: &mut StdRng| {
+                let mut position = ToadsAndFrogs::new(vec![Tile::Empty; 6]);
+                position.mutate(0.5, rng);
+                position
+            },
+            0.2,
+        );
+        let mut alg = GeneticAlgorithm::with_specimen_seeded(
+            Vec::new(),
+            std::num::NonZeroUsize::new(10).unwrap(),
+            search,
+            7,
+        );
+        let initial = alg.highest_score().score;
+        for _ in 0..5 {
+            alg.step_generation();
+        }
+        assert!(alg.highest_score().score >= initial);
+    }
+}
@@ -0,0 +1,251 @@
+//! Island model of genetic search
+//!
+//! Population is split into islands, each evolving separately on its own thread, so the search
+//! uses many cores and islands explore different regions of the search space. Every few
+//! generations best specimen of every island migrate to the next one, in a ring.
+
+use crate::genetic::{Algorithm, GeneticAlgorithm, Scored, Selection};
+use std::{num::NonZeroUsize, sync::Arc, thread};
+
+/// Migration between islands of [`IslandModel`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Migration {
+    /// Number of generations between migrations
+    pub interval: NonZeroUsize,
+
+    /// Number of best specimen copied from every island to the next one, replacing its worst
+    /// specimen
+    pub migrants: usize,
+}
+
+/// Genetic algorithm runner with many populations, see the [module documentation](self)
+///
+/// All islands share one algorithm, e.g. with a single transposition table. Island `i` draws
+/// random choices from a generator seeded with `seed + i`, so runs with the same seed are
+/// identical regardless of scheduling of threads, and a model with one island runs exactly like
+/// [`GeneticAlgorithm`] with the same seed.
+pub struct IslandModel<Alg, Object, Score> {
+    islands: Vec<GeneticAlgorithm<Arc<Alg>, Object, Score>>,
+    migration: Migration,
+    algorithm: Arc<Alg>,
+}
+
+impl<Alg, Object, Score> IslandModel<Alg, Object, Score>
+where
+    Alg: Algorithm<Object, Score> + Send + Sync,
+    Score: Clone + Ord + Send,
+    Object: Clone + Send,
+{
+    /// Create `islands` islands of `size` specimen each. Initial population is dealt between
+    /// islands, and the rest of every island is filled with random objects
+    pub fn with_specimen_seeded(
+        specimen: Vec<Object>,
+        islands: NonZeroUsize,
+        size: NonZeroUsize,
+        migration: Migration,
+        algorithm: Alg,
+        seed: u64,
+    ) -> Self {
+        let algorithm = Arc::new(algorithm);
+        let mut dealt = vec![Vec::new(); islands.get()];
+        for (idx, object) in specimen.into_iter().enumerate() {
+            dealt[idx % islands.get()].push(object);
+        }
+        let islands = dealt
+            .into_iter()
+            .zip(0..)
+            .map(|(specimen, idx)| {
+                GeneticAlgorithm::with_specimen_seeded(
+                    specimen,
+                    size,
+                    algorithm.clone(),
+                    seed.wrapping_add(idx),
+                )
+            })
+            .collect();
+        Self {
+            islands,
+            migration,
+            algorithm,
+        }
+    }
+
+    /// Perform one generation step on every island in parallel, followed by a migration every
+    /// [`Migration::interval`] generations
+    pub fn step_generation(&mut self) {
+        thread::scope(|scope| {
+            for island in &mut self.islands {
+                scope.spawn(|| island.step_generation());
+            }
+        });
+
+        if self.islands.len() > 1
+            && self
+                .generation()
+                .is_multiple_of(self.migration.interval.get())
+        {
+            self.migrate();
+        }
+    }
+
+    fn migrate(&mut self) {
+        let mut emigrants = self
+            .islands
+            .iter()
+            .map(|island| {
+                let specimen = island.specimen();
+                specimen[specimen.len().saturating_sub(self.migration.migrants)..].to_vec()
+            })
+            .collect::<Vec<_>>();
+        // Migrants of island `i` arrive at island `i + 1`
+        emigrants.rotate_right(1);
+        for (island, immigrants) in self.islands.iter_mut().zip(emigrants) {
+            island.immigrate(immigrants);
+        }
+    }
+
+    /// Change choice of surviving specimen and parents on every island
+    pub fn set_selection(&mut self, selection: Selection) {
+        for island in &mut self.islands {
+            island.set_selection(selection);
+        }
+    }
+
+    /// Get number of finished (scored) generations
+    pub fn generation(&self) -> usize {
+        self.islands[0].generation()
+    }
+
+    /// Get object with highest fitness on any island
+    pub fn highest_score(&self) -> &Scored<Object, Score> {
+        self.islands
+            .iter()
+            .map(GeneticAlgorithm::highest_score)
+            .max_by(|lhs, rhs| Ord::cmp(&lhs.score, &rhs.score))
+            .expect("unreachable: there is at least one island")
+    }
+
+    /// Get scored specimen of all islands, each island ordered by their score
+    pub fn specimen(&self) -> impl Iterator<Item = &Scored<Object, Score>> {
+        self.islands.iter().flat_map(GeneticAlgorithm::specimen)
+    }
+
+    /// Get islands
+    pub fn islands(&self) -> &[GeneticAlgorithm<Arc<Alg>, Object, Score>] {
+        &self.islands
+    }
+
+    /// Get underlying algorithm
+    pub fn algorithm(&self) -> &Alg {
+        &self.algorithm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng};
+
+    /// Maximize a number, mutated by adding a random number
+    struct Count;
+
+    impl Algorithm<u32, u32> for Count {
+        fn mutate(&self, object: &mut u32, rng: &mut StdRng) {
+            *object += rng.gen_range(0..3);
+        }
+
+        fn cross(&self, lhs: &u32, rhs: &u32, _rng: &mut StdRng) -> u32 {
+            *lhs.max(rhs)
+        }
+
+        fn lowest_score(&self) -> u32 {
+            0
+        }
+
+        fn score(&self, object: &u32) -> u32 {
+            *object
+        }
+
+        fn random(&self, rng: &mut StdRng) -> u32 {
+            rng.gen_range(0..10)
+        }
+    }
+
+    fn migration(interval: usize, migrants: usize) -> Migration {
+        Migration {
+            interval: NonZeroUsize::new(interval).unwrap(),
+            migrants,
+        }
+    }
+
+    #[test]
+    fn single_island_matches_genetic_algorithm() {
+        let size = NonZeroUsize::new(6).unwrap();
+        let mut islands = IslandModel::with_specimen_seeded(
+            vec![5],
+            NonZeroUsize::new(1).unwrap(),
+            size,
+            migration(1, 2),
+            Count,
+            9,
+        );
+        let mut alg = GeneticAlgorithm::with_specimen_seeded(vec![5], size, Count, 9);
+        for _ in 0..5 {
+            islands.step_generation();
+            alg.step_generation();
+        }
+        assert_eq!(
+            islands
+                .specimen()
+                .map(|spec| spec.object)
+                .collect::<Vec<_>>(),
+            alg.specimen()
+                .iter()
+                .map(|spec| spec.object)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn best_specimen_migrate() {
+        let mut islands = IslandModel::with_specimen_seeded(
+            vec![1000],
+            NonZeroUsize::new(3).unwrap(),
+            NonZeroUsize::new(4).unwrap(),
+            migration(1, 1),
+            Count,
+            1,
+        );
+        assert_eq!(islands.islands().len(), 3);
+        assert_eq!(islands.specimen().count(), 12);
+        islands.step_generation();
+        islands.step_generation();
+        for island in islands.islands() {
+            assert!(island.highest_score().score >= 1000);
+        }
+        assert_eq!(islands.generation(), 2);
+    }
+
+    #[test]
+    fn runs_are_repeatable() {
+        let run = || {
+            let mut islands = IslandModel::with_specimen_seeded(
+                Vec::new(),
+                NonZeroUsize::new(4).unwrap(),
+                NonZeroUsize::new(5).unwrap(),
+                migration(2, 2),
+                Count,
+                17,
+            );
+            for _ in 0..6 {
+                islands.step_generation();
+            }
+            islands
+                .specimen()
+                .map(|spec| spec.object)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(run(), run());
+    }
+}
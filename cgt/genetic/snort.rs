@@ -0,0 +1,513 @@
+//! Search for Snort positions with temperature much higher than degree of their graphs
+
+use crate::{
+    genetic::{Algorithm, Fitness},
+    graph::{
+        adjacency_matrix::undirected::UndirectedGraph,
+        classes::{self, GraphClass},
+        Graph, VertexIndex,
+    },
+    numeric::{dyadic_rational_number::DyadicRationalNumber, rational::Rational},
+    short::partizan::{
+        batch::evaluate_all,
+        cancellation::{Cancellation, Cancelled},
+        games::snort::{Snort, VertexColor, VertexKind},
+        partizan_game::PartizanGame,
+        transposition_table::ParallelTranspositionTable,
+    },
+    zobrist::Zobrist,
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+use std::{cmp::min, time::Duration};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+
+/// Mutation operators of Snort positions, with probabilities of applying them
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mutation {
+    /// Probability of removing a random vertex
+    pub vertex_remove: f32,
+
+    /// Probability of adding a vertex connected to a random vertex
+    pub vertex_add: f32,
+
+    /// Probability of toggling every edge
+    pub edge_flip: f32,
+
+    /// Probability of changing color of every vertex to a random one
+    pub color_flip: f32,
+}
+
+impl Mutation {
+    /// All operators with the same rate, like in mutations of [`Snort`] by default
+    pub const fn uniform(rate: f32) -> Self {
+        Self {
+            vertex_remove: rate,
+            vertex_add: rate,
+            edge_flip: rate,
+            color_flip: rate,
+        }
+    }
+
+    /// Apply operators in order of vertex removal, vertex addition, edge flips and color flips
+    pub fn apply<R>(&self, position: &mut Snort, rng: &mut R)
+    where
+        R: Rng,
+    {
+        // Mutate vertices
+        if position.graph.size() > 1 {
+            let mutation_roll: f32 = rng.gen();
+            if mutation_roll < self.vertex_remove {
+                let to_remove = VertexIndex {
+                    index: rng.gen_range(0..position.graph.size()),
+                };
+                position.graph.remove_vertex(to_remove);
+                position.vertices.inner.remove(to_remove.index);
+            }
+        }
+        let mutation_roll: f32 = rng.gen();
+        if mutation_roll < self.vertex_add {
+            position.graph.add_vertex();
+            position
+                .vertices
+                .inner
+                .push(VertexKind::Single(VertexColor::Empty));
+            let another_vertex = VertexIndex {
+                index: rng.gen_range(0..position.graph.size() - 1),
+            };
+            position.graph.connect(
+                VertexIndex {
+                    index: position.graph.size() - 1,
+                },
+                another_vertex,
+                true,
+            );
+        }
+
+        // Mutate edges
+        for v in position.graph.vertices() {
+            for u in position.graph.vertices() {
+                if v == u {
+                    continue;
+                }
+
+                let mutation_roll: f32 = rng.gen();
+                if mutation_roll < self.edge_flip {
+                    position
+                        .graph
+                        .connect(v, u, !position.graph.are_adjacent(v, u));
+                }
+            }
+        }
+
+        // Mutate colors
+        let available_colors = [
+            VertexColor::Empty,
+            VertexColor::TintLeft,
+            VertexColor::TintRight,
+        ];
+        for index in 0..position.vertices.inner.len() {
+            let mutation_roll: f32 = rng.gen();
+            if mutation_roll < self.color_flip {
+                position.vertices[VertexIndex { index }] =
+                    VertexKind::Single(*available_colors.choose(rng).unwrap());
+            }
+        }
+    }
+}
+
+/// Crossover schemes of Snort positions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Crossover {
+    /// Take a random number of vertices, with edges and colors of the first vertices from the
+    /// smaller graph and of the rest from the larger one. Edges between both parts are lost
+    #[default]
+    Prefix,
+
+    /// Take a random number of vertices between sizes of both graphs, with every color and
+    /// every edge from a random parent having its vertices
+    Uniform,
+}
+
+impl Crossover {
+    /// Combine two positions into one
+    pub fn apply<R>(self, lhs: &Snort, rhs: &Snort, rng: &mut R) -> Snort
+    where
+        R: Rng,
+    {
+        let mut positions = [lhs, rhs];
+        positions.sort_by_key(|pos| pos.graph.size());
+        let [smaller, larger] = positions;
+
+        match self {
+            Self::Prefix => Self::prefix(smaller, larger, rng),
+            Self::Uniform => Self::uniform(smaller, larger, rng),
+        }
+    }
+
+    fn prefix<R>(smaller: &Snort, larger: &Snort, rng: &mut R) -> Snort
+    where
+        R: Rng,
+    {
+        let new_size = rng.gen_range(1..=larger.graph.size());
+        let mut new_graph = UndirectedGraph::empty(new_size);
+
+        for v in 0..(min(new_size, smaller.graph.size())) {
+            for u in 0..(min(new_size, smaller.graph.size())) {
+                let v = VertexIndex { index: v };
+                let u = VertexIndex { index: u };
+                new_graph.connect(v, u, smaller.graph.are_adjacent(v, u));
+            }
+        }
+        for v in (min(new_size, smaller.graph.size()))..(min(new_size, larger.graph.size())) {
+            for u in (min(new_size, smaller.graph.size()))..(min(new_size, larger.graph.size())) {
+                let v = VertexIndex { index: v };
+                let u = VertexIndex { index: u };
+                new_graph.connect(v, u, larger.graph.are_adjacent(v, u));
+            }
+        }
+
+        let mut colors = smaller.vertices.inner[0..(min(new_size, smaller.graph.size()))].to_vec();
+        colors.extend(
+            &larger.vertices.inner
+                [(min(new_size, smaller.graph.size()))..(min(new_size, larger.graph.size()))],
+        );
+
+        Snort::with_colors(colors, new_graph).unwrap()
+    }
+
+    fn uniform<R>(smaller: &Snort, larger: &Snort, rng: &mut R) -> Snort
+    where
+        R: Rng,
+    {
+        let new_size = rng.gen_range(smaller.graph.size().max(1)..=larger.graph.size());
+        let shared = min(new_size, smaller.graph.size());
+        let mut new_graph = UndirectedGraph::empty(new_size);
+
+        for v in 0..new_size {
+            for u in (v + 1)..new_size {
+                let parent = if u < shared && rng.gen_bool(0.5) {
+                    smaller
+                } else {
+                    larger
+                };
+                let v = VertexIndex { index: v };
+                let u = VertexIndex { index: u };
+                new_graph.connect(v, u, parent.graph.are_adjacent(v, u));
+            }
+        }
+
+        let colors = (0..new_size)
+            .map(|index| {
+                let parent = if index < shared && rng.gen_bool(0.5) {
+                    smaller
+                } else {
+                    larger
+                };
+                parent.vertices.inner[index]
+            })
+            .collect();
+
+        Snort::with_colors(colors, new_graph).unwrap()
+    }
+}
+
+/// Difference between temperature of a position and degree of its graph
+///
+/// Positions without moves or on disconnected graphs are not evaluated and get the lowest
+/// score. Other positions are evaluated together with [`evaluate_all`], unless
+/// [`temperature_only`](Self::temperature_only) or
+/// [`evaluation_timeout`](Self::evaluation_timeout) is set.
+pub struct TemperatureDegreeDifference {
+    transposition_table: ParallelTranspositionTable<Zobrist<Snort>>,
+
+    /// Compute temperature from the thermograph of the whole game tree, without constructing
+    /// canonical forms. Positions are not split into components and nothing is cached between
+    /// them, see [`PartizanGame::thermograph_direct`].
+    pub temperature_only: bool,
+
+    /// Give up on positions that take longer to evaluate, scoring them as lowest possible
+    pub evaluation_timeout: Option<Duration>,
+}
+
+impl TemperatureDegreeDifference {
+    /// Create fitness constructing canonical forms without a timeout
+    pub fn new() -> Self {
+        Self {
+            transposition_table: ParallelTranspositionTable::new(),
+            temperature_only: false,
+            evaluation_timeout: None,
+        }
+    }
+
+    /// Get transposition table shared by all evaluations
+    pub const fn transposition_table(&self) -> &ParallelTranspositionTable<Zobrist<Snort>> {
+        &self.transposition_table
+    }
+
+    /// Check if position is worth evaluating
+    pub fn is_scored(position: &Snort) -> bool {
+        let degree_sum = position.graph.degrees().sum::<usize>();
+        !position.vertices.inner.is_empty() && degree_sum != 0 && position.graph.is_connected()
+    }
+
+    fn score_with_temperature(position: &Snort, temperature: DyadicRationalNumber) -> Rational {
+        temperature.to_rational() - Rational::from(position.degree() as i64)
+    }
+}
+
+impl Fitness<Snort> for TemperatureDegreeDifference {
+    type Score = Rational;
+
+    fn lowest_score(&self) -> Rational {
+        Rational::NegativeInfinity
+    }
+
+    fn fitness(&self, position: &Snort) -> Rational {
+        if !Self::is_scored(position) {
+            return Rational::NegativeInfinity;
+        }
+
+        let temperature = if self.temperature_only {
+            position.temperature_direct()
+        } else if let Some(timeout) = self.evaluation_timeout {
+            match Zobrist::new(position.clone()).canonical_form_cancellable(
+                &self.transposition_table,
+                &Cancellation::with_timeout(timeout),
+            ) {
+                Ok(canonical_form) => canonical_form.temperature(),
+                Err(Cancelled) => return Rational::NegativeInfinity,
+            }
+        } else {
+            Zobrist::new(position.clone())
+                .canonical_form(&self.transposition_table)
+                .temperature()
+        };
+        Self::score_with_temperature(position, temperature)
+    }
+
+    fn fitness_all(&self, positions: &[Snort]) -> Vec<Rational> {
+        if self.temperature_only || self.evaluation_timeout.is_some() {
+            #[cfg(feature = "rayon")]
+            let scores = positions
+                .par_iter()
+                .map(|position| self.fitness(position))
+                .collect();
+            #[cfg(not(feature = "rayon"))]
+            let scores = positions
+                .iter()
+                .map(|position| self.fitness(position))
+                .collect();
+            return scores;
+        }
+
+        let scored = positions
+            .iter()
+            .filter(|position| Self::is_scored(position))
+            .map(|position| Zobrist::new(position.clone()))
+            .collect::<Vec<_>>();
+        let mut evaluations = evaluate_all(&scored, &self.transposition_table).into_iter();
+        positions
+            .iter()
+            .map(|position| {
+                if Self::is_scored(position) {
+                    let evaluation = evaluations.next().unwrap();
+                    Self::score_with_temperature(position, evaluation.canonical_form.temperature())
+                } else {
+                    Rational::NegativeInfinity
+                }
+            })
+            .collect()
+    }
+}
+
+/// Genetic search maximizing [`TemperatureDegreeDifference`], in graphs of given classes
+pub struct TemperatureDegreeSearch {
+    /// Fitness of positions
+    pub fitness: TemperatureDegreeDifference,
+
+    /// Largest number of vertices of graphs in the first generation
+    pub max_graph_vertices: usize,
+
+    /// Mutation operators applied to new positions
+    pub mutation: Mutation,
+
+    /// Crossover scheme of parents of new positions
+    pub crossover: Crossover,
+
+    /// Classes of graphs to search in. Mutated, crossed and random positions are repaired to
+    /// belong to all of them
+    pub graph_classes: Vec<GraphClass>,
+}
+
+impl TemperatureDegreeSearch {
+    /// Create search in all graphs, applying all mutation operators with the same rate and
+    /// crossing positions with [`Crossover::Prefix`]
+    pub fn new(max_graph_vertices: usize, mutation_rate: f32) -> Self {
+        Self {
+            fitness: TemperatureDegreeDifference::new(),
+            max_graph_vertices,
+            mutation: Mutation::uniform(mutation_rate),
+            crossover: Crossover::Prefix,
+            graph_classes: Vec::new(),
+        }
+    }
+
+    /// Change graph of the position to belong to searched classes, e.g. of a position from
+    /// a search in other classes
+    pub fn repair<R>(&self, position: &mut Snort, rng: &mut R)
+    where
+        R: Rng,
+    {
+        if !self.graph_classes.is_empty() {
+            classes::repair(&mut position.graph, &self.graph_classes, rng);
+        }
+    }
+}
+
+impl Algorithm<Snort, Rational> for TemperatureDegreeSearch {
+    fn mutate(&self, position: &mut Snort, rng: &mut StdRng) {
+        self.mutation.apply(position, rng);
+        self.repair(position, rng);
+    }
+
+    fn cross(&self, lhs: &Snort, rhs: &Snort, rng: &mut StdRng) -> Snort {
+        let mut position = self.crossover.apply(lhs, rhs, rng);
+        self.repair(&mut position, rng);
+        position
+    }
+
+    fn lowest_score(&self) -> Rational {
+        self.fitness.lowest_score()
+    }
+
+    fn score(&self, position: &Snort) -> Rational {
+        self.fitness.fitness(position)
+    }
+
+    fn score_all(&self, positions: &[Snort]) -> Vec<Rational> {
+        self.fitness.fitness_all(positions)
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Snort {
+        let graph_size = rng.gen_range(1..=self.max_graph_vertices);
+        let mut position = Snort::new(UndirectedGraph::empty(graph_size));
+        Mutation::uniform(1.0).apply(&mut position, rng);
+        self.repair(&mut position, rng);
+        position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genetic::GeneticAlgorithm;
+    use rand::SeedableRng;
+    use std::num::NonZeroUsize;
+
+    #[test]
+    fn scores_caterpillar() {
+        // 0   3
+        //  \ /
+        //   2
+        //  / \
+        // 1   4
+        let position = Snort::new(UndirectedGraph::from_edges(
+            5,
+            &[
+                (VertexIndex { index: 0 }, VertexIndex { index: 2 }),
+                (VertexIndex { index: 1 }, VertexIndex { index: 2 }),
+                (VertexIndex { index: 2 }, VertexIndex { index: 3 }),
+                (VertexIndex { index: 2 }, VertexIndex { index: 4 }),
+            ],
+        ));
+        let fitness = TemperatureDegreeDifference::new();
+        let temperature = Zobrist::new(position.clone())
+            .canonical_form(fitness.transposition_table())
+            .temperature();
+        assert_eq!(
+            fitness.fitness_all(&[position.clone(), Snort::new(UndirectedGraph::empty(3))]),
+            vec![
+                temperature.to_rational() - Rational::from(4),
+                Rational::NegativeInfinity
+            ]
+        );
+        assert_eq!(
+            fitness.fitness(&position),
+            temperature.to_rational() - Rational::from(4)
+        );
+    }
+
+    #[test]
+    fn disabled_operators_keep_position() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut position = Snort::new(UndirectedGraph::from_edges(
+            3,
+            &[
+                (VertexIndex { index: 0 }, VertexIndex { index: 1 }),
+                (VertexIndex { index: 1 }, VertexIndex { index: 2 }),
+            ],
+        ));
+        let original = position.clone();
+        Mutation::uniform(0.0).apply(&mut position, &mut rng);
+        assert_eq!(position, original);
+
+        Mutation {
+            color_flip: 1.0,
+            ..Mutation::uniform(0.0)
+        }
+        .apply(&mut position, &mut rng);
+        assert_eq!(position.graph, original.graph);
+    }
+
+    #[test]
+    fn uniform_crossover_takes_edges_of_parents() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let lhs = Snort::new(UndirectedGraph::empty(3));
+        let rhs = Snort::new(UndirectedGraph::from_edges(
+            5,
+            &[
+                (VertexIndex { index: 3 }, VertexIndex { index: 4 }),
+                (VertexIndex { index: 0 }, VertexIndex { index: 4 }),
+            ],
+        ));
+        for _ in 0..16 {
+            let child = Crossover::Uniform.apply(&lhs, &rhs, &mut rng);
+            assert!((3..=5).contains(&child.graph.size()));
+            for v in child.graph.vertices() {
+                for u in child.graph.vertices() {
+                    if child.graph.are_adjacent(v, u) {
+                        assert!(rhs.graph.are_adjacent(v, u));
+                    }
+                }
+            }
+            if child.graph.size() == 5 {
+                let (three, four) = (VertexIndex { index: 3 }, VertexIndex { index: 4 });
+                assert!(child.graph.are_adjacent(three, four));
+            }
+        }
+    }
+
+    #[test]
+    fn searches_trees() {
+        let mut search = TemperatureDegreeSearch::new(6, 0.2);
+        search.graph_classes = vec![GraphClass::Tree];
+        let mut alg = GeneticAlgorithm::with_specimen_seeded(
+            Vec::new(),
+            NonZeroUsize::new(6).unwrap(),
+            search,
+            3,
+        );
+        alg.step_generation();
+        alg.step_generation();
+        for specimen in alg.specimen() {
+            let graph = &specimen.object.graph;
+            assert!(graph.is_connected());
+            assert_eq!(graph.degrees().sum::<usize>() / 2 + 1, graph.size());
+        }
+    }
+}
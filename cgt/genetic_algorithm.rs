@@ -1,7 +1,7 @@
 //! Utilities for genetic search
 
-use rand::{rngs::ThreadRng, seq::SliceRandom};
-use std::num::NonZeroUsize;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::{num::NonZeroUsize, sync::Arc};
 
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -17,10 +17,10 @@ pub struct Scored<Object, Score> {
 /// Definition of a genetic algorithm
 pub trait Algorithm<Object, Score> {
     /// Mutate object in place
-    fn mutate(&self, object: &mut Object, rng: &mut ThreadRng);
+    fn mutate(&self, object: &mut Object, rng: &mut StdRng);
 
     /// Combine two objects into one
-    fn cross(&self, lhs: &Object, rhs: &Object, rng: &mut ThreadRng) -> Object;
+    fn cross(&self, lhs: &Object, rhs: &Object, rng: &mut StdRng) -> Object;
 
     /// Get the lowest possible score, used for initial setup
     fn lowest_score(&self) -> Score;
@@ -28,14 +28,144 @@ pub trait Algorithm<Object, Score> {
     /// Evaluate fitness of an object. Algorithm will try to maximize this value according to [`Ord`]
     fn score(&self, object: &Object) -> Score;
 
+    /// Evaluate fitness of all objects in a generation. Override to evaluate objects together,
+    /// e.g. in parallel.
+    fn score_all(&self, objects: &[Object]) -> Vec<Score> {
+        objects.iter().map(|object| self.score(object)).collect()
+    }
+
     /// Create a totally random object, used for initial population
-    fn random(&self, rng: &mut ThreadRng) -> Object;
+    fn random(&self, rng: &mut StdRng) -> Object;
+}
+
+/// Algorithm shared between many runners, e.g. islands of
+/// [`IslandModel`](crate::genetic::islands::IslandModel)
+impl<Alg, Object, Score> Algorithm<Object, Score> for Arc<Alg>
+where
+    Alg: Algorithm<Object, Score>,
+{
+    fn mutate(&self, object: &mut Object, rng: &mut StdRng) {
+        Alg::mutate(self, object, rng);
+    }
+
+    fn cross(&self, lhs: &Object, rhs: &Object, rng: &mut StdRng) -> Object {
+        Alg::cross(self, lhs, rhs, rng)
+    }
+
+    fn lowest_score(&self) -> Score {
+        Alg::lowest_score(self)
+    }
+
+    fn score(&self, object: &Object) -> Score {
+        Alg::score(self, object)
+    }
+
+    fn score_all(&self, objects: &[Object]) -> Vec<Score> {
+        Alg::score_all(self, objects)
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Object {
+        Alg::random(self, rng)
+    }
+}
+
+/// Choice of parents of new specimen, see [`Selection`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum ParentSelection {
+    /// Any specimen, with equal probability
+    Uniform,
+
+    /// Best of `size` specimen drawn with equal probability. Larger tournaments favour better
+    /// specimen more
+    Tournament {
+        /// Number of specimen competing for each parent
+        size: NonZeroUsize,
+    },
+
+    /// Roulette wheel where chance of a specimen is proportional to its rank, so only the order
+    /// of scores matters
+    Roulette,
+}
+
+impl ParentSelection {
+    /// Choose a parent out of `specimen` ordered by their score
+    fn choose<'a, Object, Score>(
+        self,
+        specimen: &'a [Scored<Object, Score>],
+        rng: &mut StdRng,
+    ) -> &'a Scored<Object, Score>
+    where
+        Score: Ord,
+    {
+        match self {
+            Self::Uniform => specimen.choose(rng).unwrap(),
+            Self::Tournament { size } => (0..size.get())
+                .map(|_| specimen.choose(rng).unwrap())
+                .max_by(|lhs, rhs| Ord::cmp(&lhs.score, &rhs.score))
+                .unwrap(),
+            Self::Roulette => {
+                let total = specimen.len() * (specimen.len() + 1) / 2;
+                let mut ticket = rng.gen_range(0..total);
+                for (idx, spec) in specimen.iter().enumerate() {
+                    if ticket <= idx {
+                        return spec;
+                    }
+                    ticket -= idx + 1;
+                }
+                unreachable!("tickets cover all specimen")
+            }
+        }
+    }
+}
+
+/// Choice of specimen surviving to the next generation and of parents of the rest
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Selection {
+    /// Fraction of best specimen copied to the next generation unchanged, clamped between 0
+    /// and 1. Rest of the generation is bred from parents chosen from all specimen
+    pub elitism: f64,
+
+    /// Choice of parents
+    pub parents: ParentSelection,
+}
+
+impl Default for Selection {
+    /// Best half survives, parents are chosen uniformly
+    fn default() -> Self {
+        Self {
+            elitism: 0.5,
+            parents: ParentSelection::Uniform,
+        }
+    }
+}
+
+/// State of [`GeneticAlgorithm`] that can be saved and later resumed with
+/// [`GeneticAlgorithm::from_checkpoint`]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Checkpoint<Object, Score> {
+    /// Scored specimen, ordered by their score
+    pub specimen: Vec<Scored<Object, Score>>,
+
+    /// Number of finished generations
+    pub generation: usize,
+
+    /// Seed of the random number generator used in the next generation
+    pub seed: u64,
 }
 
 /// Genetic algorithm runner
+///
+/// Every generation uses a random number generator seeded with a seed drawn in the previous
+/// generation, so the whole state of a run is captured by a [`Checkpoint`].
 pub struct GeneticAlgorithm<Alg, Object, Score> {
     specimen: Vec<Scored<Object, Score>>,
     generation: usize,
+    seed: u64,
+    selection: Selection,
     algorithm: Alg,
 }
 
@@ -47,18 +177,24 @@ where
 {
     /// Create new instance with given population size and random population
     pub fn new(size: NonZeroUsize, algorithm: Alg) -> Self {
-        let mut rng = rand::thread_rng();
-        let specimen = (0..size.get())
-            .map(|_| algorithm.random(&mut rng))
-            .collect::<Vec<_>>();
-
-        Self::with_specimen(specimen, size, algorithm)
+        Self::with_specimen(Vec::new(), size, algorithm)
     }
 
     /// Like [`Self::new`] but will use initial populaiton. If initial population is smaller than
     /// generation size rest will be filled with random objects
-    pub fn with_specimen(mut specimen: Vec<Object>, size: NonZeroUsize, algorithm: Alg) -> Self {
-        let mut rng = rand::thread_rng();
+    pub fn with_specimen(specimen: Vec<Object>, size: NonZeroUsize, algorithm: Alg) -> Self {
+        Self::with_specimen_seeded(specimen, size, algorithm, StdRng::from_entropy().gen())
+    }
+
+    /// Like [`Self::with_specimen`] but all random choices are derived from `seed`, so runs with
+    /// the same seed, initial population and algorithm are identical
+    pub fn with_specimen_seeded(
+        mut specimen: Vec<Object>,
+        size: NonZeroUsize,
+        algorithm: Alg,
+        seed: u64,
+    ) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
         let to_generate = size.get().saturating_sub(specimen.len());
         specimen.extend((0..to_generate).map(|_| algorithm.random(&mut rng)));
         let specimen = specimen
@@ -72,38 +208,66 @@ where
         let mut s = Self {
             specimen,
             generation: 0,
+            seed: rng.gen(),
+            selection: Selection::default(),
             algorithm,
         };
         s.score();
         s
     }
 
+    /// Resume algorithm from a [`Checkpoint`]. Specimen are not scored again, so `algorithm`
+    /// should score objects the same way as the algorithm that created the checkpoint.
+    /// [`Selection`] is not a part of the checkpoint and has to be set again.
+    pub fn from_checkpoint(checkpoint: Checkpoint<Object, Score>, algorithm: Alg) -> Self {
+        Self {
+            specimen: checkpoint.specimen,
+            generation: checkpoint.generation,
+            seed: checkpoint.seed,
+            selection: Selection::default(),
+            algorithm,
+        }
+    }
+
+    /// Save current state of the algorithm
+    pub fn checkpoint(&self) -> Checkpoint<Object, Score> {
+        Checkpoint {
+            specimen: self.specimen.clone(),
+            generation: self.generation,
+            seed: self.seed,
+        }
+    }
+
     /// Get object with highest fitness
     pub fn highest_score(&self) -> &Scored<Object, Score> {
         self.specimen.last().expect("unreachable")
     }
 
     fn score(&mut self) {
-        self.specimen
-            .iter_mut()
-            .for_each(|spec| spec.score = self.algorithm.score(&spec.object));
+        let objects = self
+            .specimen
+            .iter()
+            .map(|spec| spec.object.clone())
+            .collect::<Vec<_>>();
+        let scores = self.algorithm.score_all(&objects);
+        for (spec, score) in self.specimen.iter_mut().zip(scores) {
+            spec.score = score;
+        }
 
         self.specimen
             .sort_unstable_by(|lhs, rhs| Ord::cmp(&lhs.score, &rhs.score));
     }
 
-    fn cross(&mut self) {
-        let mut rng = rand::thread_rng();
+    fn cross(&mut self, rng: &mut StdRng) {
         let generation_size = self.specimen.len();
-        let mid_point = generation_size / 2;
+        let elite = (generation_size as f64 * self.selection.elitism.clamp(0.0, 1.0)).ceil();
         let mut new_specimen = Vec::with_capacity(generation_size);
-        let top_half = &self.specimen[mid_point..];
-        new_specimen.extend_from_slice(top_half);
+        new_specimen.extend_from_slice(&self.specimen[generation_size - elite as usize..]);
         for _ in new_specimen.len()..generation_size {
-            let lhs = self.specimen.choose(&mut rng).unwrap();
-            let rhs = self.specimen.choose(&mut rng).unwrap();
-            let mut object = self.algorithm.cross(&lhs.object, &rhs.object, &mut rng);
-            self.algorithm.mutate(&mut object, &mut rng);
+            let lhs = self.selection.parents.choose(&self.specimen, rng);
+            let rhs = self.selection.parents.choose(&self.specimen, rng);
+            let mut object = self.algorithm.cross(&lhs.object, &rhs.object, rng);
+            self.algorithm.mutate(&mut object, rng);
             new_specimen.push(Scored {
                 object,
                 score: self.algorithm.lowest_score(),
@@ -114,16 +278,39 @@ where
 
     /// Perform one generation step
     pub fn step_generation(&mut self) {
-        self.cross();
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        self.cross(&mut rng);
+        self.seed = rng.gen();
         self.score();
         self.generation += 1;
     }
 
+    /// Replace specimen with the lowest scores with `migrants`, e.g. coming from another
+    /// population. Migrants are not scored again, so they should come from an algorithm scoring
+    /// objects the same way. Migrants above the population size are dropped.
+    pub fn immigrate(&mut self, migrants: Vec<Scored<Object, Score>>) {
+        let replaced = migrants.len().min(self.specimen.len());
+        self.specimen
+            .splice(..replaced, migrants.into_iter().take(replaced));
+        self.specimen
+            .sort_unstable_by(|lhs, rhs| Ord::cmp(&lhs.score, &rhs.score));
+    }
+
     /// Get number of finished (scored) generations
     pub const fn generation(&self) -> usize {
         self.generation
     }
 
+    /// Get choice of surviving specimen and parents
+    pub const fn selection(&self) -> &Selection {
+        &self.selection
+    }
+
+    /// Change choice of surviving specimen and parents, used from the next generation
+    pub const fn set_selection(&mut self, selection: Selection) {
+        self.selection = selection;
+    }
+
     /// Get underlying algorithm
     pub const fn algorithm(&self) -> &Alg {
         &self.algorithm
@@ -134,3 +321,100 @@ where
         &self.specimen
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Maximize a number, mutated by adding one
+    struct Count;
+
+    impl Algorithm<u32, u32> for Count {
+        fn mutate(&self, object: &mut u32, _rng: &mut StdRng) {
+            *object += 1;
+        }
+
+        fn cross(&self, lhs: &u32, rhs: &u32, _rng: &mut StdRng) -> u32 {
+            *lhs.min(rhs)
+        }
+
+        fn lowest_score(&self) -> u32 {
+            0
+        }
+
+        fn score(&self, object: &u32) -> u32 {
+            *object
+        }
+
+        fn random(&self, rng: &mut StdRng) -> u32 {
+            rng.gen_range(0..100)
+        }
+    }
+
+    fn specimen(scores: &[u32]) -> Vec<Scored<u32, u32>> {
+        scores
+            .iter()
+            .map(|&score| Scored {
+                object: score,
+                score,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn elitism_keeps_best() {
+        let mut alg = GeneticAlgorithm::with_specimen_seeded(
+            (0..8).collect(),
+            NonZeroUsize::new(8).unwrap(),
+            Count,
+            1,
+        );
+        alg.set_selection(Selection {
+            elitism: 0.25,
+            parents: ParentSelection::Uniform,
+        });
+        alg.step_generation();
+        let objects = alg
+            .specimen()
+            .iter()
+            .map(|spec| spec.object)
+            .collect::<Vec<_>>();
+        assert!(objects.contains(&6) && objects.contains(&7));
+
+        alg.set_selection(Selection {
+            elitism: 1.0,
+            parents: ParentSelection::Roulette,
+        });
+        alg.step_generation();
+        let unchanged = alg
+            .specimen()
+            .iter()
+            .map(|spec| spec.object)
+            .collect::<Vec<_>>();
+        assert_eq!(unchanged, objects);
+    }
+
+    #[test]
+    fn tournament_of_many_chooses_best() {
+        let specimen = specimen(&[1, 2, 3, 4]);
+        let mut rng = StdRng::seed_from_u64(3);
+        let tournament = ParentSelection::Tournament {
+            size: NonZeroUsize::new(64).unwrap(),
+        };
+        for _ in 0..16 {
+            assert_eq!(tournament.choose(&specimen, &mut rng).score, 4);
+        }
+    }
+
+    #[test]
+    fn roulette_favours_better() {
+        let specimen = specimen(&[1, 2, 3, 4]);
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut counts = [0; 4];
+        for _ in 0..1000 {
+            counts[ParentSelection::Roulette.choose(&specimen, &mut rng).score as usize - 1] += 1;
+        }
+        assert!(counts[0] > 0);
+        assert!(counts[0] < counts[1] && counts[1] < counts[2] && counts[2] < counts[3]);
+    }
+}
@@ -1,17 +1,54 @@
 //! Simple graph implementation
 
-use std::collections::VecDeque;
+use crate::graph::{
+    canonical_labeling::CanonicalLabeling,
+    planarity::PlanarEmbedding,
+    traversal::{Bfs, Dfs},
+};
 
+pub mod adjacency_list;
 pub mod adjacency_matrix;
+pub mod canonical_labeling;
+pub mod classes;
+pub mod dot;
+pub mod generators;
+pub mod graph6;
+pub mod graphml;
+pub mod labeled;
 pub mod layout;
+pub mod planarity;
+pub mod traversal;
+pub mod tree_code;
 
 /// Graph vertex. We assume that all graphs that we implement use 0-based indexing for their vertices
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexIndex {
     /// 0-based index of a vertex in the graph
     pub index: usize,
 }
 
+/// Error returned when adjacency matrix does not have `size^2` entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+#[error("adjacency matrix of graph with {size} vertices has {entries} entries")]
+pub struct InvalidMatrixSize {
+    /// Number of graph vertices
+    pub size: usize,
+
+    /// Number of matrix entries
+    pub entries: usize,
+}
+
+impl InvalidMatrixSize {
+    pub(crate) const fn check(size: usize, entries: usize) -> Result<(), Self> {
+        if entries == size * size {
+            Ok(())
+        } else {
+            Err(Self { size, entries })
+        }
+    }
+}
+
 /// Graph
 pub trait Graph: Sized {
     /// Iterator over vertices
@@ -32,6 +69,10 @@ pub trait Graph: Sized {
     where
         Self: 'g;
 
+    /// Whether edges have a direction, i.e. edge from `u` to `v` does not imply edge from `v`
+    /// to `u`
+    const DIRECTED: bool = false;
+
     /// Create an empty graph without any edges between vertices
     fn empty(size: usize) -> Self;
 
@@ -50,6 +91,18 @@ pub trait Graph: Sized {
     /// Add or remove edge between vertices
     fn connect(&mut self, lhs_vertex: VertexIndex, rhs_vertex: VertexIndex, connect: bool);
 
+    /// Remove all edges incident to a given vertex, in both directions
+    fn disconnect(&mut self, vertex: VertexIndex) {
+        let incident: Vec<VertexIndex> = self
+            .vertices()
+            .filter(|&u| self.are_adjacent(u, vertex) || self.are_adjacent(vertex, u))
+            .collect();
+        for u in incident {
+            self.connect(u, vertex, false);
+            self.connect(vertex, u, false);
+        }
+    }
+
     /// Get iterator over vertices adjacent to given vertex
     fn adjacent_to<'g>(&'g self, vertex: VertexIndex) -> Self::AdjacentIter<'g>;
 
@@ -59,10 +112,34 @@ pub trait Graph: Sized {
             .any(|adjacent| adjacent == rhs_vertex)
     }
 
+    /// Get vertices adjacent to a given vertex as a bitmask, where bit `i` is set iff vertex `i`
+    /// is adjacent. Returns `None` if graph has more than 128 vertices.
+    fn neighbors_mask(&self, vertex: VertexIndex) -> Option<u128> {
+        if self.size() > 128 {
+            return None;
+        }
+        Some(
+            self.adjacent_to(vertex)
+                .fold(0, |mask, u| mask | (1 << u.index)),
+        )
+    }
+
+    /// Add or remove edges between a vertex and every vertex in a bitmask, in the same format as
+    /// [`Graph::neighbors_mask`]
+    ///
+    /// # Panics
+    /// - `mask` has bits set for vertices not in the graph
+    fn connect_mask(&mut self, vertex: VertexIndex, mask: u128, connect: bool) {
+        for u in mask_vertices(mask) {
+            self.connect(vertex, u, connect);
+        }
+    }
+
     /// Get iterator over edges
     fn edges<'g>(&'g self) -> Self::EdgesIter<'g>;
 
-    /// Get iterator over vertex degrees, in order
+    /// Get iterator over vertex degrees, in order. Loops are not counted. In directed graphs
+    /// degree of a vertex is the sum of its in-degree and out-degree.
     fn degrees<'g>(&'g self) -> Self::DegreeIter<'g>;
 
     /// Create nw graph from "flat" adjacency matrix.
@@ -70,10 +147,8 @@ pub trait Graph: Sized {
     /// # Errors
     /// - if `matrix.len() != size^2`
     #[inline]
-    fn from_flat_matrix(size: usize, matrix: &[bool]) -> Option<Self> {
-        if matrix.len() != size * size {
-            return None;
-        }
+    fn from_flat_matrix(size: usize, matrix: &[bool]) -> Result<Self, InvalidMatrixSize> {
+        InvalidMatrixSize::check(size, matrix.len())?;
 
         let mut g = Self::empty(size);
         for u in g.vertices() {
@@ -82,12 +157,15 @@ pub trait Graph: Sized {
             }
         }
 
-        Some(g)
+        Ok(g)
     }
 
     /// Create nw graph from adjacency matrix.
+    ///
+    /// # Errors
+    /// - if matrix does not have `size^2` entries
     #[inline]
-    fn from_matrix(size: usize, matrix: &[&[bool]]) -> Option<Self> {
+    fn from_matrix(size: usize, matrix: &[&[bool]]) -> Result<Self, InvalidMatrixSize> {
         let vec: Vec<bool> = matrix.iter().map(|r| r.iter()).flatten().copied().collect();
         Self::from_flat_matrix(size, &vec)
     }
@@ -105,22 +183,123 @@ pub trait Graph: Sized {
             return true;
         }
 
-        let mut seen = vec![false; self.size()];
-        let mut queue: VecDeque<VertexIndex> = VecDeque::with_capacity(self.size());
+        self.bfs_from(VertexIndex { index: 0 }).count() == self.size()
+    }
+
+    /// Iterate over vertices reachable from `start` in breadth-first order, together with their
+    /// distance from `start`
+    fn bfs_from(&self, start: VertexIndex) -> Bfs<'_, Self> {
+        Bfs::new(self, start)
+    }
 
-        seen[0] = true;
-        queue.push_back(VertexIndex { index: 0 });
+    /// Iterate over vertices reachable from `start` in depth-first preorder
+    fn dfs_from(&self, start: VertexIndex) -> Dfs<'_, Self> {
+        Dfs::new(self, start)
+    }
 
-        while let Some(v) = queue.pop_front() {
-            for u in self.vertices() {
-                if self.are_adjacent(v, u) && v != u && !seen[u.index] {
-                    seen[u.index] = true;
-                    queue.push_back(u);
+    /// Get lengths of shortest paths between all pairs of vertices, where `matrix[u][v]` is the
+    /// distance from `u` to `v`, or `None` if `v` is not reachable from `u`
+    fn distance_matrix(&self) -> Vec<Vec<Option<usize>>> {
+        self.vertices()
+            .map(|u| {
+                let mut row = vec![None; self.size()];
+                for (v, distance) in self.bfs_from(u) {
+                    row[v.index] = Some(distance);
+                }
+                row
+            })
+            .collect()
+    }
+
+    /// Get greatest distance from `vertex` to any other vertex, or `None` if some vertex is not
+    /// reachable
+    fn eccentricity(&self, vertex: VertexIndex) -> Option<usize> {
+        let mut reached = 0;
+        let mut eccentricity = 0;
+        for (_, distance) in self.bfs_from(vertex) {
+            reached += 1;
+            eccentricity = distance;
+        }
+        (reached == self.size()).then_some(eccentricity)
+    }
+
+    /// Get greatest distance between any two vertices, or `None` if graph is not (strongly)
+    /// connected
+    fn diameter(&self) -> Option<usize> {
+        self.vertices()
+            .try_fold(0, |diameter, v| Some(diameter.max(self.eccentricity(v)?)))
+    }
+
+    /// Relabel vertices, so that isomorphic graphs have identical representations.
+    /// See [`CanonicalLabeling`].
+    #[must_use]
+    fn canonical_form(&self) -> Self {
+        CanonicalLabeling::new(self, &vec![0; self.size()]).apply(self)
+    }
+
+    /// Check if two graphs are isomorphic
+    fn is_isomorphic_to(&self, other: &Self) -> bool {
+        if self.size() != other.size() {
+            return false;
+        }
+
+        let lhs = self.canonical_form();
+        let rhs = other.canonical_form();
+        lhs.vertices().all(|u| {
+            lhs.vertices()
+                .all(|v| lhs.are_adjacent(u, v) == rhs.are_adjacent(u, v))
+        })
+    }
+
+    /// Check if graph can be drawn on a plane without crossing edges.
+    /// See [`PlanarEmbedding`] to get the embedding.
+    fn is_planar(&self) -> bool {
+        PlanarEmbedding::new(self).is_some()
+    }
+
+    /// Split vertices into two sides, such that every edge connects vertices from different
+    /// sides. Edge directions are ignored. Returns side of each vertex (`false` for the side of
+    /// vertex `0`), or `None` if graph is not bipartite.
+    fn bipartition(&self) -> Option<Vec<bool>> {
+        let mut neighbors = vec![Vec::new(); self.size()];
+        for (u, v) in self.edges() {
+            if u == v {
+                return None;
+            }
+            neighbors[u.index].push(v);
+            neighbors[v.index].push(u);
+        }
+
+        let mut sides: Vec<Option<bool>> = vec![None; self.size()];
+        let mut stack = Vec::new();
+        for start in self.vertices() {
+            if sides[start.index].is_some() {
+                continue;
+            }
+            sides[start.index] = Some(false);
+            stack.push(start);
+            while let Some(v) = stack.pop() {
+                let side = sides[v.index]?;
+                for u in &neighbors[v.index] {
+                    match sides[u.index] {
+                        None => {
+                            sides[u.index] = Some(!side);
+                            stack.push(*u);
+                        }
+                        Some(u_side) if u_side == side => return None,
+                        Some(_) => {}
+                    }
                 }
             }
         }
 
-        seen.into_iter().all(|b| b)
+        sides.into_iter().collect()
+    }
+
+    /// Check if vertices can be split into two sides, such that every edge connects vertices from
+    /// different sides. See [`Graph::bipartition`].
+    fn is_bipartite(&self) -> bool {
+        self.bipartition().is_some()
     }
 
     /// Create a graph from list of edges
@@ -132,4 +311,248 @@ pub trait Graph: Sized {
         }
         graph
     }
+
+    /// Create a subgraph induced by given vertices. Vertex `vertices[i]` becomes vertex `i` of
+    /// the new graph.
+    #[must_use]
+    fn induced_subgraph(&self, vertices: &[VertexIndex]) -> Self {
+        let mut new_indices = vec![None; self.size()];
+        for (index, v) in vertices.iter().enumerate() {
+            new_indices[v.index] = Some(VertexIndex { index });
+        }
+
+        let mut graph = Self::empty(vertices.len());
+        for (index, v) in vertices.iter().enumerate() {
+            for u in self.adjacent_to(*v) {
+                if let Some(new_u) = new_indices[u.index] {
+                    graph.connect(VertexIndex { index }, new_u, true);
+                }
+            }
+        }
+        graph
+    }
+
+    /// Create a subgraph induced by vertices that satisfy the predicate, in the same order
+    #[must_use]
+    fn filter_vertices<P>(&self, mut predicate: P) -> Self
+    where
+        P: FnMut(VertexIndex) -> bool,
+    {
+        let vertices: Vec<VertexIndex> = self.vertices().filter(|v| predicate(*v)).collect();
+        self.induced_subgraph(&vertices)
+    }
+
+    /// Merge `rhs_vertex` into `lhs_vertex`. Edges of `rhs_vertex` are moved to `lhs_vertex`,
+    /// except edges between the two, and `rhs_vertex` is removed, so indices of vertices after it
+    /// are shifted down.
+    ///
+    /// # Panics
+    /// - `lhs_vertex` and `rhs_vertex` are the same vertex
+    fn contract_edge(&mut self, lhs_vertex: VertexIndex, rhs_vertex: VertexIndex) {
+        assert_ne!(lhs_vertex, rhs_vertex, "Cannot contract a loop");
+
+        let is_other = |u: &VertexIndex| *u != lhs_vertex && *u != rhs_vertex;
+        let outgoing: Vec<VertexIndex> = self.adjacent_to(rhs_vertex).filter(is_other).collect();
+        let incoming: Vec<VertexIndex> = self
+            .vertices()
+            .filter(|u| is_other(u) && self.are_adjacent(*u, rhs_vertex))
+            .collect();
+        for u in outgoing {
+            self.connect(lhs_vertex, u, true);
+        }
+        for u in incoming {
+            self.connect(u, lhs_vertex, true);
+        }
+        self.remove_vertex(rhs_vertex);
+    }
+
+    /// Create a graph consisting of both graphs without any edges between them. Vertices of
+    /// `other` are placed after vertices of `self`.
+    #[must_use]
+    fn disjoint_union(&self, other: &Self) -> Self {
+        let offset = self.size();
+        let mut graph = Self::empty(offset + other.size());
+        for u in self.vertices() {
+            for v in self.adjacent_to(u) {
+                graph.connect(u, v, true);
+            }
+        }
+        for u in other.vertices() {
+            for v in other.adjacent_to(u) {
+                graph.connect(
+                    VertexIndex {
+                        index: offset + u.index,
+                    },
+                    VertexIndex {
+                        index: offset + v.index,
+                    },
+                    true,
+                );
+            }
+        }
+        graph
+    }
+
+    /// Create a disjoint union of both graphs, where additionally every vertex of `self` is
+    /// connected to every vertex of `other` in both directions.
+    #[must_use]
+    fn join(&self, other: &Self) -> Self {
+        let mut graph = self.disjoint_union(other);
+        for u in self.vertices() {
+            for v in other.vertices() {
+                let v = VertexIndex {
+                    index: self.size() + v.index,
+                };
+                graph.connect(u, v, true);
+                graph.connect(v, u, true);
+            }
+        }
+        graph
+    }
+}
+
+/// Iterate over vertices with bits set in a mask, see [`Graph::neighbors_mask`]
+pub(crate) fn mask_vertices(mut mask: u128) -> impl Iterator<Item = VertexIndex> {
+    std::iter::from_fn(move || {
+        if mask == 0 {
+            return None;
+        }
+        let index = mask.trailing_zeros() as usize;
+        // Clear lowest set bit
+        mask &= mask - 1;
+        Some(VertexIndex { index })
+    })
+}
+
+/// Implement `+` and `+=` as [`Graph::disjoint_union`] for a graph type
+macro_rules! impl_disjoint_union_ops {
+    ($t: ty) => {
+        auto_ops::impl_op_ex!(+|lhs: &$t, rhs: &$t| -> $t { $crate::graph::Graph::disjoint_union(lhs, rhs) });
+        auto_ops::impl_op_ex!(+=|lhs: &mut $t, rhs: &$t| { *lhs = $crate::graph::Graph::disjoint_union(lhs, rhs) });
+    };
+}
+use impl_disjoint_union_ops;
+
+#[test]
+fn induced_subgraph() {
+    use crate::graph::adjacency_list::undirected::UndirectedGraph;
+
+    let cycle: UndirectedGraph = generators::cycle(5);
+    let path = cycle.induced_subgraph(&[
+        VertexIndex { index: 3 },
+        VertexIndex { index: 4 },
+        VertexIndex { index: 0 },
+        VertexIndex { index: 1 },
+    ]);
+    assert_eq!(path, generators::path(4));
+
+    let even = cycle.filter_vertices(|v| v.index % 2 == 0);
+    assert_eq!(even.size(), 3);
+    assert_eq!(
+        even.edges().collect::<Vec<_>>(),
+        vec![(VertexIndex { index: 0 }, VertexIndex { index: 2 })]
+    );
+}
+
+#[test]
+fn contract_edge() {
+    use crate::graph::adjacency_matrix::{directed::DirectedGraph, undirected::UndirectedGraph};
+
+    let mut cycle: UndirectedGraph = generators::cycle(5);
+    cycle.contract_edge(VertexIndex { index: 1 }, VertexIndex { index: 2 });
+    assert_eq!(cycle, generators::cycle(4));
+
+    // 0 -> 1 -> 2, 3 -> 1
+    let mut directed = DirectedGraph::from_edges(
+        4,
+        &[
+            (VertexIndex { index: 0 }, VertexIndex { index: 1 }),
+            (VertexIndex { index: 1 }, VertexIndex { index: 2 }),
+            (VertexIndex { index: 3 }, VertexIndex { index: 1 }),
+        ],
+    );
+    directed.contract_edge(VertexIndex { index: 0 }, VertexIndex { index: 1 });
+    assert_eq!(
+        directed,
+        DirectedGraph::from_edges(
+            3,
+            &[
+                (VertexIndex { index: 0 }, VertexIndex { index: 1 }),
+                (VertexIndex { index: 2 }, VertexIndex { index: 0 }),
+            ]
+        )
+    );
+}
+
+#[test]
+fn disjoint_union_and_join() {
+    use crate::graph::adjacency_list::undirected::UndirectedGraph;
+
+    let path: UndirectedGraph = generators::path(2);
+    let mut union = &path + &path;
+    assert_eq!(union.size(), 4);
+    assert_eq!(union.edges().count(), 2);
+    assert!(!union.is_connected());
+
+    union += &generators::path(1);
+    assert_eq!(union.size(), 5);
+
+    let empty: UndirectedGraph = UndirectedGraph::empty(2);
+    assert_eq!(
+        empty.join(&UndirectedGraph::empty(3)),
+        generators::complete_bipartite(2, 3)
+    );
+    assert!(generators::complete::<UndirectedGraph>(3)
+        .join(&generators::complete(2))
+        .is_isomorphic_to(&generators::complete(5)));
+}
+
+#[test]
+fn bipartition() {
+    use crate::graph::adjacency_list::{directed::DirectedGraph, undirected::UndirectedGraph};
+
+    let grid: UndirectedGraph = generators::grid(3, 2);
+    assert_eq!(
+        grid.bipartition(),
+        Some(vec![false, true, false, true, false, true])
+    );
+    assert!(generators::cycle::<UndirectedGraph>(6).is_bipartite());
+    assert!(!generators::cycle::<UndirectedGraph>(5).is_bipartite());
+    assert!(generators::path::<UndirectedGraph>(3)
+        .disjoint_union(&generators::complete_bipartite(2, 3))
+        .is_bipartite());
+
+    // Directed triangle 0 -> 1 -> 2 -> 0
+    let triangle = DirectedGraph::from_edges(
+        3,
+        &[
+            (VertexIndex { index: 0 }, VertexIndex { index: 1 }),
+            (VertexIndex { index: 1 }, VertexIndex { index: 2 }),
+            (VertexIndex { index: 2 }, VertexIndex { index: 0 }),
+        ],
+    );
+    assert_eq!(triangle.bipartition(), None);
+}
+
+#[test]
+fn neighbors_mask() {
+    use crate::graph::adjacency_list::undirected::UndirectedGraph;
+
+    let mut star: UndirectedGraph = generators::star(4);
+    assert_eq!(star.neighbors_mask(VertexIndex { index: 0 }), Some(0b11110));
+    star.connect_mask(VertexIndex { index: 0 }, 0b01010, false);
+    assert_eq!(star.neighbors_mask(VertexIndex { index: 0 }), Some(0b10100));
+    assert_eq!(star.neighbors_mask(VertexIndex { index: 1 }), Some(0));
+    assert_eq!(
+        UndirectedGraph::empty(129).neighbors_mask(VertexIndex { index: 0 }),
+        None
+    );
+
+    let mut matrix: adjacency_matrix::undirected::UndirectedGraph = generators::path(100);
+    matrix.connect_mask(VertexIndex { index: 99 }, 1 << 70 | 1, true);
+    assert_eq!(
+        matrix.neighbors_mask(VertexIndex { index: 99 }),
+        Some(1 << 98 | 1 << 70 | 1)
+    );
+    assert!(matrix.are_adjacent(VertexIndex { index: 70 }, VertexIndex { index: 99 }));
 }
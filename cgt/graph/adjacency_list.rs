@@ -0,0 +1,4 @@
+//! Graphs implemented as adjacency lists, suitable for large sparse graphs
+
+pub mod directed;
+pub mod undirected;
@@ -0,0 +1,277 @@
+//! Directed graph
+
+use std::{fmt::Display, iter::FusedIterator};
+
+use crate::graph::{impl_disjoint_union_ops, Graph, VertexIndex};
+
+/// Directed graph, implements [`Graph`] trait
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirectedGraph {
+    /// Sorted lists of out-neighbours of each vertex
+    adjacency_lists: Vec<Vec<VertexIndex>>,
+
+    /// Sorted lists of in-neighbours of each vertex
+    predecessor_lists: Vec<Vec<VertexIndex>>,
+}
+
+impl DirectedGraph {
+    /// Number of edges going out of the vertex, not counting loops
+    pub fn out_degree(&self, vertex: VertexIndex) -> usize {
+        let adjacent = &self.adjacency_lists[vertex.index];
+        adjacent.len() - usize::from(adjacent.binary_search(&vertex).is_ok())
+    }
+
+    /// Number of edges going into the vertex, not counting loops
+    pub fn in_degree(&self, vertex: VertexIndex) -> usize {
+        let predecessors = &self.predecessor_lists[vertex.index];
+        predecessors.len() - usize::from(predecessors.binary_search(&vertex).is_ok())
+    }
+
+    /// Get iterator over vertices with an edge going into the vertex
+    pub fn predecessors(
+        &self,
+        vertex: VertexIndex,
+    ) -> std::iter::Copied<std::slice::Iter<'_, VertexIndex>> {
+        self.predecessor_lists[vertex.index].iter().copied()
+    }
+}
+
+impl Display for DirectedGraph {
+    #[allow(clippy::missing_inline_in_public_items)]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, adjacent) in self.adjacency_lists.iter().enumerate() {
+            write!(f, "{}:", idx)?;
+            for vertex in adjacent {
+                write!(f, " {}", vertex.index)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Graph for DirectedGraph {
+    type VertexIter = std::iter::Map<std::ops::Range<usize>, fn(usize) -> VertexIndex>;
+
+    type AdjacentIter<'g> = std::iter::Copied<std::slice::Iter<'g, VertexIndex>>;
+
+    type DegreeIter<'g> = DegreeIter<'g>;
+
+    type EdgesIter<'g> = EdgesIter<'g>;
+
+    const DIRECTED: bool = true;
+
+    #[inline]
+    fn empty(size: usize) -> Self {
+        Self {
+            adjacency_lists: vec![Vec::new(); size],
+            predecessor_lists: vec![Vec::new(); size],
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.adjacency_lists.len()
+    }
+
+    fn vertices(&self) -> Self::VertexIter {
+        (0..self.size()).map(|index| VertexIndex { index })
+    }
+
+    #[inline]
+    fn add_vertex(&mut self) -> VertexIndex {
+        self.adjacency_lists.push(Vec::new());
+        self.predecessor_lists.push(Vec::new());
+        VertexIndex {
+            index: self.size() - 1,
+        }
+    }
+
+    fn remove_vertex(&mut self, vertex_to_remove: VertexIndex) {
+        debug_assert!(self.size() > 0, "Graph has no vertices");
+        self.adjacency_lists.remove(vertex_to_remove.index);
+        self.predecessor_lists.remove(vertex_to_remove.index);
+        for adjacent in self
+            .adjacency_lists
+            .iter_mut()
+            .chain(self.predecessor_lists.iter_mut())
+        {
+            adjacent.retain(|vertex| *vertex != vertex_to_remove);
+            for vertex in adjacent.iter_mut() {
+                if *vertex > vertex_to_remove {
+                    vertex.index -= 1;
+                }
+            }
+        }
+    }
+
+    fn connect(&mut self, lhs_vertex: VertexIndex, rhs_vertex: VertexIndex, connect: bool) {
+        fn update(list: &mut Vec<VertexIndex>, vertex: VertexIndex, connect: bool) {
+            match (list.binary_search(&vertex), connect) {
+                (Err(position), true) => list.insert(position, vertex),
+                (Ok(position), false) => {
+                    list.remove(position);
+                }
+                (Ok(_), true) | (Err(_), false) => {}
+            }
+        }
+
+        update(
+            &mut self.adjacency_lists[lhs_vertex.index],
+            rhs_vertex,
+            connect,
+        );
+        update(
+            &mut self.predecessor_lists[rhs_vertex.index],
+            lhs_vertex,
+            connect,
+        );
+    }
+
+    fn adjacent_to(&self, vertex: VertexIndex) -> Self::AdjacentIter<'_> {
+        self.adjacency_lists[vertex.index].iter().copied()
+    }
+
+    fn are_adjacent(&self, lhs_vertex: VertexIndex, rhs_vertex: VertexIndex) -> bool {
+        self.adjacency_lists[lhs_vertex.index]
+            .binary_search(&rhs_vertex)
+            .is_ok()
+    }
+
+    fn edges(&self) -> Self::EdgesIter<'_> {
+        EdgesIter {
+            u: VertexIndex { index: 0 },
+            adjacent_idx: 0,
+            graph: self,
+        }
+    }
+
+    fn degrees(&self) -> Self::DegreeIter<'_> {
+        DegreeIter {
+            idx: VertexIndex { index: 0 },
+            graph: self,
+        }
+    }
+}
+
+impl_disjoint_union_ops!(DirectedGraph);
+
+/// Iterator over graph edges, constructed with [`Graph::edges`].
+pub struct EdgesIter<'graph> {
+    u: VertexIndex,
+    adjacent_idx: usize,
+    graph: &'graph DirectedGraph,
+}
+
+impl Iterator for EdgesIter<'_> {
+    type Item = (VertexIndex, VertexIndex);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let adjacent = self.graph.adjacency_lists.get(self.u.index)?;
+            if let Some(v) = adjacent.get(self.adjacent_idx) {
+                self.adjacent_idx += 1;
+                return Some((self.u, *v));
+            }
+            self.u.index += 1;
+            self.adjacent_idx = 0;
+        }
+    }
+}
+
+impl FusedIterator for EdgesIter<'_> {}
+
+/// Iterator over degrees of vertices in a graph, i.e. sums of their in-degree and out-degree.
+/// Obtained with [`Graph::degrees`]
+#[derive(Debug)]
+pub struct DegreeIter<'graph> {
+    idx: VertexIndex,
+    graph: &'graph DirectedGraph,
+}
+
+impl Iterator for DegreeIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx.index >= self.graph.size() {
+            return None;
+        }
+        let res = self.graph.out_degree(self.idx) + self.graph.in_degree(self.idx);
+        self.idx.index += 1;
+        Some(res)
+    }
+}
+
+impl FusedIterator for DegreeIter<'_> {}
+
+/// ```text
+/// 1 -> 3 -> 2
+///  \   |
+///   \  v
+///    > 0
+/// ```
+#[cfg(test)]
+fn test_graph() -> DirectedGraph {
+    let mut g = DirectedGraph::empty(4);
+    g.connect(VertexIndex { index: 3 }, VertexIndex { index: 0 }, true);
+    g.connect(VertexIndex { index: 3 }, VertexIndex { index: 2 }, true);
+    g.connect(VertexIndex { index: 1 }, VertexIndex { index: 3 }, true);
+    g.connect(VertexIndex { index: 1 }, VertexIndex { index: 0 }, true);
+    g
+}
+
+#[test]
+fn adds_and_removes_vertices() {
+    let mut g = test_graph();
+    assert_eq!(&format!("{g}"), "0:\n1: 0 3\n2:\n3: 0 2\n");
+
+    g.add_vertex();
+    assert_eq!(&format!("{g}"), "0:\n1: 0 3\n2:\n3: 0 2\n4:\n");
+
+    g.remove_vertex(VertexIndex { index: 1 });
+    assert_eq!(&format!("{g}"), "0:\n1:\n2: 0 1\n3:\n");
+}
+
+#[test]
+fn same_as_adjacency_matrix() {
+    use crate::graph::adjacency_matrix;
+
+    let g = test_graph();
+    let matrix =
+        adjacency_matrix::directed::DirectedGraph::from_edges(4, &g.edges().collect::<Vec<_>>());
+    for v in g.vertices() {
+        assert_eq!(
+            g.adjacent_to(v).collect::<Vec<_>>(),
+            matrix.adjacent_to(v).collect::<Vec<_>>()
+        );
+    }
+    assert_eq!(
+        g.degrees().collect::<Vec<_>>(),
+        matrix.degrees().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn in_and_out_degrees() {
+    let mut g = test_graph();
+    g.connect(VertexIndex { index: 0 }, VertexIndex { index: 0 }, true);
+    assert_eq!(
+        g.predecessors(VertexIndex { index: 0 }).collect::<Vec<_>>(),
+        vec![
+            VertexIndex { index: 0 },
+            VertexIndex { index: 1 },
+            VertexIndex { index: 3 }
+        ]
+    );
+    assert_eq!(g.in_degree(VertexIndex { index: 0 }), 2);
+    assert_eq!(g.out_degree(VertexIndex { index: 0 }), 0);
+    assert_eq!(g.degrees().collect::<Vec<_>>(), vec![2, 2, 1, 3]);
+
+    g.remove_vertex(VertexIndex { index: 1 });
+    assert_eq!(
+        g.predecessors(VertexIndex { index: 1 }).collect::<Vec<_>>(),
+        vec![VertexIndex { index: 2 }]
+    );
+}
@@ -0,0 +1,166 @@
+//! Undirected graph
+
+use std::{fmt::Display, iter::FusedIterator};
+
+use crate::graph::{adjacency_list::directed, impl_disjoint_union_ops, Graph, VertexIndex};
+
+/// Undirected graph, implements [`Graph`] trait
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UndirectedGraph(directed::DirectedGraph);
+
+impl Display for UndirectedGraph {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Graph for UndirectedGraph {
+    type VertexIter = std::iter::Map<std::ops::Range<usize>, fn(usize) -> VertexIndex>;
+
+    type AdjacentIter<'g> = std::iter::Copied<std::slice::Iter<'g, VertexIndex>>;
+
+    type DegreeIter<'g> = DegreeIter<'g>;
+
+    type EdgesIter<'g> = EdgesIter<'g>;
+
+    fn empty(size: usize) -> Self {
+        Self(directed::DirectedGraph::empty(size))
+    }
+
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+
+    fn vertices(&self) -> Self::VertexIter {
+        self.0.vertices()
+    }
+
+    fn add_vertex(&mut self) -> VertexIndex {
+        self.0.add_vertex()
+    }
+
+    fn remove_vertex(&mut self, vertex_to_remove: VertexIndex) {
+        self.0.remove_vertex(vertex_to_remove);
+    }
+
+    fn connect(&mut self, lhs_vertex: VertexIndex, rhs_vertex: VertexIndex, connect: bool) {
+        self.0.connect(lhs_vertex, rhs_vertex, connect);
+        self.0.connect(rhs_vertex, lhs_vertex, connect);
+    }
+
+    fn adjacent_to(&self, vertex: VertexIndex) -> Self::AdjacentIter<'_> {
+        self.0.adjacent_to(vertex)
+    }
+
+    fn are_adjacent(&self, lhs_vertex: VertexIndex, rhs_vertex: VertexIndex) -> bool {
+        self.0.are_adjacent(lhs_vertex, rhs_vertex)
+    }
+
+    fn edges(&self) -> Self::EdgesIter<'_> {
+        EdgesIter {
+            inner: self.0.edges(),
+        }
+    }
+
+    fn degrees(&self) -> Self::DegreeIter<'_> {
+        DegreeIter {
+            idx: VertexIndex { index: 0 },
+            graph: &self.0,
+        }
+    }
+}
+
+impl_disjoint_union_ops!(UndirectedGraph);
+
+/// Iterator over graph edges, constructed with [`Graph::edges`].
+pub struct EdgesIter<'graph> {
+    inner: directed::EdgesIter<'graph>,
+}
+
+impl Iterator for EdgesIter<'_> {
+    type Item = (VertexIndex, VertexIndex);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // graph is undirected so we need to iterate only the triangle
+        self.inner.find(|(u, v)| u <= v)
+    }
+}
+
+impl FusedIterator for EdgesIter<'_> {}
+
+/// Iterator over degrees of vertices in a graph. Obtained with [`Graph::degrees`]
+#[derive(Debug)]
+pub struct DegreeIter<'graph> {
+    idx: VertexIndex,
+    graph: &'graph directed::DirectedGraph,
+}
+
+impl Iterator for DegreeIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx.index >= self.graph.size() {
+            return None;
+        }
+        let res = self.graph.out_degree(self.idx);
+        self.idx.index += 1;
+        Some(res)
+    }
+}
+
+impl FusedIterator for DegreeIter<'_> {}
+
+/// ```text
+/// 1 - 3 - 2
+///  \  |
+///   \ |
+///     0
+/// ```
+#[cfg(test)]
+fn test_graph() -> UndirectedGraph {
+    let mut g = UndirectedGraph::empty(4);
+    g.connect(VertexIndex { index: 3 }, VertexIndex { index: 0 }, true);
+    g.connect(VertexIndex { index: 3 }, VertexIndex { index: 2 }, true);
+    g.connect(VertexIndex { index: 1 }, VertexIndex { index: 3 }, true);
+    g.connect(VertexIndex { index: 1 }, VertexIndex { index: 0 }, true);
+    g
+}
+
+#[test]
+fn adjacency() {
+    let g = test_graph();
+    assert_eq!(
+        g.adjacent_to(VertexIndex { index: 3 }).collect::<Vec<_>>(),
+        vec![
+            VertexIndex { index: 0 },
+            VertexIndex { index: 1 },
+            VertexIndex { index: 2 }
+        ]
+    );
+    assert!(g.are_adjacent(VertexIndex { index: 0 }, VertexIndex { index: 1 }));
+    assert!(!g.are_adjacent(VertexIndex { index: 0 }, VertexIndex { index: 2 }));
+    assert!(g.is_connected());
+}
+
+#[test]
+fn degrees() {
+    let g = test_graph();
+    assert_eq!(g.degrees().collect::<Vec<_>>(), vec![2, 2, 1, 3]);
+    assert_eq!(g.degree(), 3);
+}
+
+#[test]
+fn edges() {
+    let g = test_graph();
+    assert_eq!(
+        g.edges().collect::<Vec<_>>(),
+        vec![
+            (VertexIndex { index: 0 }, VertexIndex { index: 1 }),
+            (VertexIndex { index: 0 }, VertexIndex { index: 3 }),
+            (VertexIndex { index: 1 }, VertexIndex { index: 3 }),
+            (VertexIndex { index: 2 }, VertexIndex { index: 3 })
+        ]
+    );
+}
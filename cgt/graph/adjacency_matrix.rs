@@ -1,4 +1,5 @@
 //! Graphs implemented as adjacency matrix
 
 pub mod directed;
+pub mod triangular;
 pub mod undirected;
@@ -2,24 +2,122 @@
 
 use std::{fmt::Display, iter::FusedIterator};
 
-use crate::graph::{Graph, VertexIndex};
+use crate::{
+    graph::{impl_disjoint_union_ops, Graph, InvalidMatrixSize, VertexIndex},
+    zobrist::{zobrist_key, ZobristHash, GLOBAL_INDEX},
+};
+
+const WORD_BITS: usize = u64::BITS as usize;
 
 /// Directed graph, implements [`Graph`] trait
+///
+/// Rows of the adjacency matrix are stored as bitsets packed in `u64` words.
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DirectedGraph {
     size: usize,
-    adjacency_matrix: Vec<bool>,
+    adjacency_matrix: Vec<u64>,
+}
+
+impl DirectedGraph {
+    /// Number of words used by a single row
+    #[inline]
+    const fn row_words(size: usize) -> usize {
+        size.div_ceil(WORD_BITS)
+    }
+
+    #[inline]
+    fn row(&self, vertex: VertexIndex) -> &[u64] {
+        let row_words = Self::row_words(self.size);
+        &self.adjacency_matrix[row_words * vertex.index..row_words * (vertex.index + 1)]
+    }
+
+    #[inline]
+    fn row_mut(&mut self, vertex: VertexIndex) -> &mut [u64] {
+        let row_words = Self::row_words(self.size);
+        &mut self.adjacency_matrix[row_words * vertex.index..row_words * (vertex.index + 1)]
+    }
+
+    /// Number of edges going out of the vertex, not counting loops
+    pub fn out_degree(&self, vertex: VertexIndex) -> usize {
+        self.row(vertex)
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum::<usize>()
+            - usize::from(self.are_adjacent(vertex, vertex))
+    }
+
+    /// Number of edges going into the vertex, not counting loops
+    pub fn in_degree(&self, vertex: VertexIndex) -> usize {
+        self.predecessors(vertex).filter(|u| *u != vertex).count()
+    }
+
+    /// Get iterator over vertices with an edge going into the vertex
+    pub const fn predecessors(&self, vertex: VertexIndex) -> PredecessorsIter<'_> {
+        PredecessorsIter {
+            vertex,
+            idx: VertexIndex { index: 0 },
+            graph: self,
+        }
+    }
+
+    /// Remove all outgoing edges of a vertex
+    pub fn disconnect_outgoing(&mut self, vertex: VertexIndex) {
+        self.row_mut(vertex).fill(0);
+    }
+
+    /// Remove all incoming edges of a vertex
+    pub fn disconnect_incoming(&mut self, vertex: VertexIndex) {
+        let row_words = Self::row_words(self.size);
+        let mask = !(1 << (vertex.index % WORD_BITS));
+        for row in self.adjacency_matrix.chunks_exact_mut(row_words) {
+            row[vertex.index / WORD_BITS] &= mask;
+        }
+    }
+}
+
+/// XOR of keys of set bits in the adjacency matrix
+fn words_hash(words: impl Iterator<Item = u64>) -> u64 {
+    let mut hash = 0;
+    for (idx, mut word) in words.enumerate() {
+        while word != 0 {
+            let bit = (idx * WORD_BITS) as u64 + u64::from(word.trailing_zeros());
+            hash ^= zobrist_key(bit, 1);
+            word &= word - 1;
+        }
+    }
+    hash
+}
+
+impl ZobristHash for DirectedGraph {
+    fn zobrist_hash(&self) -> u64 {
+        zobrist_key(GLOBAL_INDEX, self.size as u64)
+            ^ words_hash(self.adjacency_matrix.iter().copied())
+    }
+
+    /// Graphs with the same number of vertices differ only by changed edges
+    fn zobrist_hash_from(&self, hash: u64, other: &Self) -> u64 {
+        if self.size == other.size {
+            hash ^ words_hash(
+                self.adjacency_matrix
+                    .iter()
+                    .zip(&other.adjacency_matrix)
+                    .map(|(a, b)| a ^ b),
+            )
+        } else {
+            other.zobrist_hash()
+        }
+    }
 }
 
 impl Display for DirectedGraph {
     #[allow(clippy::missing_inline_in_public_items)]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (idx, elem) in self.adjacency_matrix.iter().enumerate() {
-            write!(f, "{}", u8::from(*elem))?;
-            if (idx + 1) % self.size == 0 {
-                writeln!(f)?;
+        for u in self.vertices() {
+            for v in self.vertices() {
+                write!(f, "{}", u8::from(self.are_adjacent(u, v)))?;
             }
+            writeln!(f)?;
         }
 
         Ok(())
@@ -35,6 +133,8 @@ impl Graph for DirectedGraph {
 
     type EdgesIter<'g> = EdgesIter<'g>;
 
+    const DIRECTED: bool = true;
+
     #[inline]
     fn empty(size: usize) -> Self
     where
@@ -42,7 +142,7 @@ impl Graph for DirectedGraph {
     {
         Self {
             size,
-            adjacency_matrix: vec![false; size * size],
+            adjacency_matrix: vec![0; size * Self::row_words(size)],
         }
     }
 
@@ -94,31 +194,77 @@ impl Graph for DirectedGraph {
     }
 
     fn connect(&mut self, lhs_vertex: VertexIndex, rhs_vertex: VertexIndex, connect: bool) {
-        self.adjacency_matrix[self.size * lhs_vertex.index + rhs_vertex.index] = connect;
+        let word = &mut self.row_mut(lhs_vertex)[rhs_vertex.index / WORD_BITS];
+        let bit = 1 << (rhs_vertex.index % WORD_BITS);
+        if connect {
+            *word |= bit;
+        } else {
+            *word &= !bit;
+        }
+    }
+
+    fn disconnect(&mut self, vertex: VertexIndex) {
+        self.disconnect_outgoing(vertex);
+        self.disconnect_incoming(vertex);
+    }
+
+    fn neighbors_mask(&self, vertex: VertexIndex) -> Option<u128> {
+        if self.size() > 128 {
+            return None;
+        }
+        let row = self.row(vertex);
+        let low = row.first().copied().unwrap_or(0);
+        let high = row.get(1).copied().unwrap_or(0);
+        Some(u128::from(low) | (u128::from(high) << WORD_BITS))
+    }
+
+    fn connect_mask(&mut self, vertex: VertexIndex, mask: u128, connect: bool) {
+        assert!(
+            self.size() >= 128 || mask >> self.size() == 0,
+            "Mask has vertices not in the graph"
+        );
+        let row = self.row_mut(vertex);
+        for (word, mask) in row
+            .iter_mut()
+            .zip([mask as u64, (mask >> WORD_BITS) as u64])
+        {
+            if connect {
+                *word |= mask;
+            } else {
+                *word &= !mask;
+            }
+        }
     }
 
     fn adjacent_to<'g>(&'g self, vertex: VertexIndex) -> Self::AdjacentIter<'g> {
+        let row = self.row(vertex);
         AdjacentIter {
-            vertex,
-            idx: VertexIndex { index: 0 },
-            graph: self,
+            row,
+            word_idx: 0,
+            current: row.first().copied().unwrap_or(0),
         }
     }
 
     fn are_adjacent(&self, lhs_vertex: VertexIndex, rhs_vertex: VertexIndex) -> bool {
-        self.adjacency_matrix[self.size * lhs_vertex.index + rhs_vertex.index]
+        self.row(lhs_vertex)[rhs_vertex.index / WORD_BITS] & (1 << (rhs_vertex.index % WORD_BITS))
+            != 0
     }
 
     #[inline]
-    fn from_flat_matrix(size: usize, vec: &[bool]) -> Option<Self> {
-        if vec.len() != size * size {
-            return None;
+    fn from_flat_matrix(size: usize, vec: &[bool]) -> Result<Self, InvalidMatrixSize> {
+        InvalidMatrixSize::check(size, vec.len())?;
+
+        let mut graph = Self::empty(size);
+        for (idx, elem) in vec.iter().enumerate() {
+            if *elem {
+                graph.connect(
+                    VertexIndex { index: idx / size },
+                    VertexIndex { index: idx % size },
+                    true,
+                );
+            }
         }
-
-        Some(Self {
-            size,
-            adjacency_matrix: vec.to_vec(),
-        })
+        Ok(graph)
     }
 
     fn edges<'g>(&'g self) -> Self::EdgesIter<'g> {
@@ -137,6 +283,8 @@ impl Graph for DirectedGraph {
     }
 }
 
+impl_disjoint_union_ops!(DirectedGraph);
+
 /// Iterator over graph edges, constructed with [`Graph::edges`].
 pub struct EdgesIter<'graph> {
     u: VertexIndex,
@@ -173,32 +321,32 @@ impl<'graph> FusedIterator for EdgesIter<'graph> {}
 /// Iterator of adjacent vertices. Obtained by calling [`Graph::adjacent_to`]
 #[derive(Debug)]
 pub struct AdjacentIter<'graph> {
-    vertex: VertexIndex,
-    idx: VertexIndex,
-    graph: &'graph DirectedGraph,
+    row: &'graph [u64],
+    word_idx: usize,
+    current: u64,
 }
 
 impl<'graph> Iterator for AdjacentIter<'graph> {
     type Item = VertexIndex;
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.idx.index >= self.graph.size {
-                return None;
-            }
-            if self.graph.are_adjacent(self.vertex, self.idx) {
-                let res = Some(self.idx);
-                self.idx.index += 1;
-                return res;
-            }
-            self.idx.index += 1;
+        while self.current == 0 {
+            self.word_idx += 1;
+            self.current = *self.row.get(self.word_idx)?;
         }
+        let bit = self.current.trailing_zeros() as usize;
+        // Clear lowest set bit
+        self.current &= self.current - 1;
+        Some(VertexIndex {
+            index: self.word_idx * WORD_BITS + bit,
+        })
     }
 }
 
 impl<'graph> FusedIterator for AdjacentIter<'graph> {}
 
-/// Iterator over degrees of vertices in a graph. Obtained with [`Graph::degrees`]
+/// Iterator over degrees of vertices in a graph, i.e. sums of their in-degree and out-degree.
+/// Obtained with [`Graph::degrees`]
 #[derive(Debug)]
 pub struct DegreeIter<'graph> {
     idx: VertexIndex,
@@ -213,16 +361,50 @@ impl<'graph> Iterator for DegreeIter<'graph> {
             return None;
         }
 
-        let res = self
-            .graph
-            .vertices()
-            .filter(|&u| u != self.idx && self.graph.are_adjacent(self.idx, u))
-            .count();
+        let res = self.graph.out_degree(self.idx) + self.graph.in_degree(self.idx);
         self.idx.index += 1;
         Some(res)
     }
 }
 
+/// Iterator over vertices with an edge going into a given vertex. Obtained with
+/// [`DirectedGraph::predecessors`]
+#[derive(Debug)]
+pub struct PredecessorsIter<'graph> {
+    vertex: VertexIndex,
+    idx: VertexIndex,
+    graph: &'graph DirectedGraph,
+}
+
+impl Iterator for PredecessorsIter<'_> {
+    type Item = VertexIndex;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.idx.index < self.graph.size() {
+            let u = self.idx;
+            self.idx.index += 1;
+            if self.graph.are_adjacent(u, self.vertex) {
+                return Some(u);
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for PredecessorsIter<'_> {}
+
+#[test]
+fn in_and_out_degrees() {
+    let g = test_matrix();
+    assert_eq!(
+        g.predecessors(VertexIndex { index: 0 }).collect::<Vec<_>>(),
+        vec![VertexIndex { index: 1 }, VertexIndex { index: 3 }]
+    );
+    assert_eq!(g.in_degree(VertexIndex { index: 3 }), 1);
+    assert_eq!(g.out_degree(VertexIndex { index: 3 }), 2);
+    assert_eq!(g.degrees().collect::<Vec<_>>(), vec![2, 2, 1, 3]);
+}
+
 #[test]
 fn adds_new_vertex() {
     let mut g = test_matrix();
@@ -0,0 +1,292 @@
+//! Undirected graph storing only the upper triangle of the adjacency matrix
+
+use std::{fmt::Display, iter::FusedIterator};
+
+use crate::graph::{impl_disjoint_union_ops, Graph, VertexIndex};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Undirected graph, implements [`Graph`] trait
+///
+/// Compared to [`super::undirected::UndirectedGraph`] it uses half of the memory, as only the
+/// upper triangle of the adjacency matrix (including the diagonal) is stored. Bits are packed
+/// column by column, so adding a vertex only appends a new column. Iterating over adjacent
+/// vertices is slower though, as it cannot scan a single packed row.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UndirectedGraph {
+    size: usize,
+    triangle: Vec<u64>,
+}
+
+impl UndirectedGraph {
+    /// Number of words needed to store triangle of a graph with given size
+    #[inline]
+    const fn triangle_words(size: usize) -> usize {
+        (size * (size + 1) / 2).div_ceil(WORD_BITS)
+    }
+
+    /// Position of the bit storing adjacency of two vertices
+    #[inline]
+    fn bit_index(lhs_vertex: VertexIndex, rhs_vertex: VertexIndex) -> usize {
+        let (row, column) = if lhs_vertex <= rhs_vertex {
+            (lhs_vertex.index, rhs_vertex.index)
+        } else {
+            (rhs_vertex.index, lhs_vertex.index)
+        };
+        column * (column + 1) / 2 + row
+    }
+}
+
+impl Display for UndirectedGraph {
+    #[allow(clippy::missing_inline_in_public_items)]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for u in self.vertices() {
+            for v in self.vertices() {
+                write!(f, "{}", u8::from(self.are_adjacent(u, v)))?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Graph for UndirectedGraph {
+    type VertexIter = std::iter::Map<std::ops::Range<usize>, fn(usize) -> VertexIndex>;
+
+    type AdjacentIter<'g> = AdjacentIter<'g>;
+
+    type DegreeIter<'g> = DegreeIter<'g>;
+
+    type EdgesIter<'g> = EdgesIter<'g>;
+
+    #[inline]
+    fn empty(size: usize) -> Self {
+        Self {
+            size,
+            triangle: vec![0; Self::triangle_words(size)],
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn vertices(&self) -> Self::VertexIter {
+        (0..self.size()).map(|index| VertexIndex { index })
+    }
+
+    #[inline]
+    fn add_vertex(&mut self) -> VertexIndex {
+        let new_vertex = self.size;
+        self.size += 1;
+        self.triangle.resize(Self::triangle_words(self.size), 0);
+        VertexIndex { index: new_vertex }
+    }
+
+    fn remove_vertex(&mut self, vertex_to_remove: VertexIndex) {
+        debug_assert!(self.size() > 0, "Graph has no vertices");
+        let mut new_graph = Self::empty(self.size() - 1);
+
+        for v in new_graph.vertices() {
+            for u in (0..=v.index).map(|index| VertexIndex { index }) {
+                // Branchless skip over vertex we're removing
+                let old_u = VertexIndex {
+                    index: u.index + usize::from(u >= vertex_to_remove),
+                };
+                let old_v = VertexIndex {
+                    index: v.index + usize::from(v >= vertex_to_remove),
+                };
+                new_graph.connect(u, v, self.are_adjacent(old_u, old_v));
+            }
+        }
+
+        *self = new_graph;
+    }
+
+    fn connect(&mut self, lhs_vertex: VertexIndex, rhs_vertex: VertexIndex, connect: bool) {
+        let bit_index = Self::bit_index(lhs_vertex, rhs_vertex);
+        let word = &mut self.triangle[bit_index / WORD_BITS];
+        let bit = 1 << (bit_index % WORD_BITS);
+        if connect {
+            *word |= bit;
+        } else {
+            *word &= !bit;
+        }
+    }
+
+    fn disconnect(&mut self, vertex: VertexIndex) {
+        for u in self.vertices() {
+            self.connect(vertex, u, false);
+        }
+    }
+
+    fn adjacent_to(&self, vertex: VertexIndex) -> Self::AdjacentIter<'_> {
+        AdjacentIter {
+            vertex,
+            idx: VertexIndex { index: 0 },
+            graph: self,
+        }
+    }
+
+    fn are_adjacent(&self, lhs_vertex: VertexIndex, rhs_vertex: VertexIndex) -> bool {
+        let bit_index = Self::bit_index(lhs_vertex, rhs_vertex);
+        self.triangle[bit_index / WORD_BITS] & (1 << (bit_index % WORD_BITS)) != 0
+    }
+
+    fn edges(&self) -> Self::EdgesIter<'_> {
+        EdgesIter {
+            u: VertexIndex { index: 0 },
+            v: VertexIndex { index: 0 },
+            graph: self,
+        }
+    }
+
+    fn degrees(&self) -> Self::DegreeIter<'_> {
+        DegreeIter {
+            idx: VertexIndex { index: 0 },
+            graph: self,
+        }
+    }
+}
+
+impl_disjoint_union_ops!(UndirectedGraph);
+
+/// Iterator over graph edges, constructed with [`Graph::edges`].
+pub struct EdgesIter<'graph> {
+    u: VertexIndex,
+    v: VertexIndex,
+    graph: &'graph UndirectedGraph,
+}
+
+impl Iterator for EdgesIter<'_> {
+    type Item = (VertexIndex, VertexIndex);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // Iterate column by column, only over the stored triangle
+            if self.u > self.v {
+                self.u.index = 0;
+                self.v.index += 1;
+            }
+
+            if self.v.index >= self.graph.size() {
+                return None;
+            }
+
+            let u = self.u;
+            self.u.index += 1;
+            if self.graph.are_adjacent(u, self.v) {
+                return Some((u, self.v));
+            }
+        }
+    }
+}
+
+impl FusedIterator for EdgesIter<'_> {}
+
+/// Iterator of adjacent vertices. Obtained by calling [`Graph::adjacent_to`]
+#[derive(Debug)]
+pub struct AdjacentIter<'graph> {
+    vertex: VertexIndex,
+    idx: VertexIndex,
+    graph: &'graph UndirectedGraph,
+}
+
+impl Iterator for AdjacentIter<'_> {
+    type Item = VertexIndex;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.idx.index < self.graph.size() {
+            let u = self.idx;
+            self.idx.index += 1;
+            if self.graph.are_adjacent(self.vertex, u) {
+                return Some(u);
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for AdjacentIter<'_> {}
+
+/// Iterator over degrees of vertices in a graph. Obtained with [`Graph::degrees`]
+#[derive(Debug)]
+pub struct DegreeIter<'graph> {
+    idx: VertexIndex,
+    graph: &'graph UndirectedGraph,
+}
+
+impl Iterator for DegreeIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx.index >= self.graph.size() {
+            return None;
+        }
+
+        let res = self
+            .graph
+            .adjacent_to(self.idx)
+            .filter(|&u| u != self.idx)
+            .count();
+        self.idx.index += 1;
+        Some(res)
+    }
+}
+
+impl FusedIterator for DegreeIter<'_> {}
+
+#[test]
+fn matches_full_matrix() {
+    use crate::graph::{adjacency_matrix, generators};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+    for size in [0, 1, 5, 11, 70] {
+        let triangular: UndirectedGraph = generators::random_gnp(size, 0.3, &mut rng);
+        let full = adjacency_matrix::undirected::UndirectedGraph::from_edges(
+            size,
+            &triangular.edges().collect::<Vec<_>>(),
+        );
+
+        assert_eq!(triangular.to_string(), full.to_string());
+        assert_eq!(
+            triangular.degrees().collect::<Vec<_>>(),
+            full.degrees().collect::<Vec<_>>()
+        );
+        for v in triangular.vertices() {
+            assert_eq!(
+                triangular.adjacent_to(v).collect::<Vec<_>>(),
+                full.adjacent_to(v).collect::<Vec<_>>()
+            );
+        }
+    }
+}
+
+#[test]
+fn add_and_remove_vertices() {
+    use crate::graph::generators;
+
+    let mut g: UndirectedGraph = generators::cycle(4);
+    let v = g.add_vertex();
+    g.connect(v, VertexIndex { index: 0 }, true);
+    assert_eq!(g.degrees().collect::<Vec<_>>(), vec![3, 2, 2, 2, 1]);
+
+    g.remove_vertex(VertexIndex { index: 1 });
+    assert_eq!(
+        g.to_string(),
+        "0011\n\
+         0010\n\
+         1100\n\
+         1000\n"
+    );
+
+    g.disconnect(VertexIndex { index: 0 });
+    assert_eq!(
+        g.edges().collect::<Vec<_>>(),
+        vec![(VertexIndex { index: 1 }, VertexIndex { index: 2 })]
+    );
+}
@@ -2,9 +2,12 @@
 
 use std::{fmt::Display, iter::FusedIterator};
 
-use crate::graph::{
-    adjacency_matrix::directed::{self, AdjacentIter},
-    Graph, VertexIndex,
+use crate::{
+    graph::{
+        adjacency_matrix::directed::{self, AdjacentIter},
+        impl_disjoint_union_ops, mask_vertices, Graph, InvalidMatrixSize, VertexIndex,
+    },
+    zobrist::ZobristHash,
 };
 
 /// Undirected graph, implements [`Graph`] trait
@@ -12,6 +15,18 @@ use crate::graph::{
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UndirectedGraph(directed::DirectedGraph);
 
+impl ZobristHash for UndirectedGraph {
+    #[inline]
+    fn zobrist_hash(&self) -> u64 {
+        self.0.zobrist_hash()
+    }
+
+    #[inline]
+    fn zobrist_hash_from(&self, hash: u64, other: &Self) -> u64 {
+        self.0.zobrist_hash_from(hash, &other.0)
+    }
+}
+
 impl Display for UndirectedGraph {
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -53,6 +68,25 @@ impl Graph for UndirectedGraph {
         self.0.connect(rhs_vertex, lhs_vertex, connect);
     }
 
+    fn disconnect(&mut self, vertex: VertexIndex) {
+        let adjacent: Vec<VertexIndex> = self.0.adjacent_to(vertex).collect();
+        for u in adjacent {
+            self.0.connect(u, vertex, false);
+        }
+        self.0.disconnect_outgoing(vertex);
+    }
+
+    fn neighbors_mask(&self, vertex: VertexIndex) -> Option<u128> {
+        self.0.neighbors_mask(vertex)
+    }
+
+    fn connect_mask(&mut self, vertex: VertexIndex, mask: u128, connect: bool) {
+        self.0.connect_mask(vertex, mask, connect);
+        for u in mask_vertices(mask) {
+            self.0.connect(u, vertex, connect);
+        }
+    }
+
     fn adjacent_to<'g>(&'g self, vertex: VertexIndex) -> Self::AdjacentIter<'g> {
         self.0.adjacent_to(vertex)
     }
@@ -78,17 +112,19 @@ impl Graph for UndirectedGraph {
 
     /// Create a graph from flattened adjecency matrix. Must be correct length
     #[inline]
-    fn from_flat_matrix(size: usize, vec: &[bool]) -> Option<Self> {
-        Some(Self(directed::DirectedGraph::from_flat_matrix(size, vec)?))
+    fn from_flat_matrix(size: usize, vec: &[bool]) -> Result<Self, InvalidMatrixSize> {
+        Ok(Self(directed::DirectedGraph::from_flat_matrix(size, vec)?))
     }
 
     /// Create a graph from adjecency matrix. Must be correct length
     #[inline]
-    fn from_matrix(size: usize, matrix: &[&[bool]]) -> Option<Self> {
-        Some(Self(directed::DirectedGraph::from_matrix(size, matrix)?))
+    fn from_matrix(size: usize, matrix: &[&[bool]]) -> Result<Self, InvalidMatrixSize> {
+        Ok(Self(directed::DirectedGraph::from_matrix(size, matrix)?))
     }
 }
 
+impl_disjoint_union_ops!(UndirectedGraph);
+
 /// Iterator over graph edges, constructed with [`Graph::edges`].
 pub struct EdgesIter<'graph> {
     u: VertexIndex,
@@ -140,8 +176,8 @@ impl<'graph> Iterator for DegreeIter<'graph> {
 
         let res = self
             .graph
-            .vertices()
-            .filter(|&u| u != self.idx && self.graph.are_adjacent(self.idx, u))
+            .adjacent_to(self.idx)
+            .filter(|&u| u != self.idx)
             .count();
         self.idx.index += 1;
         Some(res)
@@ -228,6 +264,36 @@ fn edges() {
     );
 }
 
+#[test]
+fn disconnect() {
+    let mut m = test_matrix();
+    m.disconnect(VertexIndex { index: 3 });
+    assert_eq!(
+        m.edges().collect::<Vec<_>>(),
+        vec![(VertexIndex { index: 0 }, VertexIndex { index: 1 })]
+    );
+}
+
+#[test]
+fn large_graph() {
+    let mut m = UndirectedGraph::empty(130);
+    m.connect(VertexIndex { index: 1 }, VertexIndex { index: 64 }, true);
+    m.connect(VertexIndex { index: 1 }, VertexIndex { index: 129 }, true);
+    m.connect(VertexIndex { index: 1 }, VertexIndex { index: 63 }, true);
+    assert_eq!(
+        m.adjacent_to(VertexIndex { index: 1 }).collect::<Vec<_>>(),
+        vec![
+            VertexIndex { index: 63 },
+            VertexIndex { index: 64 },
+            VertexIndex { index: 129 }
+        ]
+    );
+    assert_eq!(m.degree(), 3);
+
+    m.remove_vertex(VertexIndex { index: 0 });
+    assert!(m.are_adjacent(VertexIndex { index: 0 }, VertexIndex { index: 128 }));
+}
+
 #[test]
 fn connected() {
     let m = test_matrix();
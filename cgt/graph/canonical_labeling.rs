@@ -0,0 +1,443 @@
+//! Canonical labeling of graphs, used to test graph isomorphism.
+//!
+//! Implementation follows the individualization-refinement scheme of nauty. Vertices are split
+//! into an ordered partition that is refined until it is equitable, i.e. every vertex in a cell
+//! has the same number of neighbours in every other cell. If the partition is not discrete, every
+//! vertex of the first non-singleton cell is individualized in turn and the search continues.
+//! Every leaf of the search tree gives a labeling, and the labeling giving the largest adjacency
+//! matrix is canonical. Leaves with equal matrices give automorphisms, that are used to prune
+//! equivalent branches of the search tree.
+
+use crate::graph::{Graph, VertexIndex};
+
+/// Ordered partition of vertices
+type Partition = Vec<Vec<usize>>;
+
+/// Adjacency matrix and vertex colors in the order of the labeling
+type Certificate = (Vec<usize>, Vec<bool>);
+
+/// Canonical labeling of a graph with colored vertices
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CanonicalLabeling {
+    labeling: Vec<VertexIndex>,
+    automorphisms: Vec<Vec<VertexIndex>>,
+}
+
+impl CanonicalLabeling {
+    /// Compute canonical labeling of a graph, where every vertex is colored by `colors`.
+    /// Isomorphisms must preserve colors.
+    ///
+    /// # Panics
+    /// - `colors` length differs from graph size
+    pub fn new<G>(graph: &G, colors: &[usize]) -> Self
+    where
+        G: Graph,
+    {
+        assert_eq!(graph.size(), colors.len(), "Every vertex must have a color");
+
+        let mut distinct_colors = colors.to_vec();
+        distinct_colors.sort_unstable();
+        distinct_colors.dedup();
+        let partition: Partition = distinct_colors
+            .iter()
+            .map(|color| (0..graph.size()).filter(|v| colors[*v] == *color).collect())
+            .collect();
+
+        let mut search = Search {
+            graph,
+            colors,
+            first_leaf: None,
+            best_leaf: None,
+            automorphisms: Vec::new(),
+        };
+        let partition = search.refine(partition);
+        search.search(&partition, &mut Vec::new());
+
+        let (best_labeling, _) = search.best_leaf.expect("Search visits at least one leaf");
+        Self {
+            labeling: best_labeling
+                .into_iter()
+                .map(|index| VertexIndex { index })
+                .collect(),
+            automorphisms: search
+                .automorphisms
+                .into_iter()
+                .map(|permutation| {
+                    permutation
+                        .into_iter()
+                        .map(|index| VertexIndex { index })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// New index of every vertex
+    pub fn labeling(&self) -> &[VertexIndex] {
+        &self.labeling
+    }
+
+    /// Automorphisms of the colored graph found during the search. They generate a subgroup of
+    /// the automorphism group, that may be smaller than the whole group.
+    pub fn automorphisms(&self) -> &[Vec<VertexIndex>] {
+        &self.automorphisms
+    }
+
+    /// Orbits of the automorphism group. Every vertex is mapped to the smallest vertex in its orbit
+    pub fn orbits(&self) -> Vec<VertexIndex> {
+        let mut parent: Vec<usize> = (0..self.labeling.len()).collect();
+        for automorphism in &self.automorphisms {
+            for (v, u) in automorphism.iter().enumerate() {
+                union(&mut parent, v, u.index);
+            }
+        }
+        (0..parent.len())
+            .map(|v| VertexIndex {
+                index: find(&mut parent, v),
+            })
+            .collect()
+    }
+
+    /// Relabel graph using the canonical labeling
+    pub fn apply<G>(&self, graph: &G) -> G
+    where
+        G: Graph,
+    {
+        let mut result = G::empty(graph.size());
+        for u in graph.vertices() {
+            for v in graph.adjacent_to(u) {
+                result.connect(self.labeling[u.index], self.labeling[v.index], true);
+            }
+        }
+        result
+    }
+}
+
+struct Search<'graph, G> {
+    graph: &'graph G,
+    colors: &'graph [usize],
+    /// Labeling, certificate and path to the first visited leaf
+    first_leaf: Option<(Vec<usize>, Certificate, Vec<usize>)>,
+    best_leaf: Option<(Vec<usize>, Certificate)>,
+    automorphisms: Vec<Vec<usize>>,
+}
+
+impl<G> Search<'_, G>
+where
+    G: Graph,
+{
+    /// Refine partition until it is equitable
+    fn refine(&self, mut partition: Partition) -> Partition {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            let mut splitter_idx = 0;
+            while splitter_idx < partition.len() {
+                let splitter = partition[splitter_idx].clone();
+                let mut cell_idx = 0;
+                while cell_idx < partition.len() {
+                    let split = self.split(&partition[cell_idx], &splitter);
+                    let split_len = split.len();
+                    if split_len > 1 {
+                        partition.splice(cell_idx..=cell_idx, split);
+                        changed = true;
+                    }
+                    cell_idx += split_len;
+                }
+                splitter_idx += 1;
+            }
+        }
+        partition
+    }
+
+    /// Split cell by the number of neighbours in the splitter, in increasing order
+    fn split(&self, cell: &[usize], splitter: &[usize]) -> Vec<Vec<usize>> {
+        if cell.len() == 1 {
+            return vec![cell.to_vec()];
+        }
+
+        let mut counted: Vec<(usize, usize)> = cell
+            .iter()
+            .map(|&v| {
+                let count = splitter
+                    .iter()
+                    .filter(|&&u| {
+                        self.graph
+                            .are_adjacent(VertexIndex { index: v }, VertexIndex { index: u })
+                    })
+                    .count();
+                (count, v)
+            })
+            .collect();
+        counted.sort_unstable();
+
+        let mut result: Vec<Vec<usize>> = Vec::new();
+        let mut last_count = None;
+        for (count, v) in counted {
+            if last_count == Some(count) {
+                result.last_mut().unwrap().push(v);
+            } else {
+                result.push(vec![v]);
+                last_count = Some(count);
+            }
+        }
+        result
+    }
+
+    /// Explore search tree below the given partition. Returns the depth the search should
+    /// backtrack to, if equivalent subtree was found.
+    fn search(&mut self, partition: &[Vec<usize>], path: &mut Vec<usize>) -> Option<usize> {
+        let Some(target) = partition.iter().position(|cell| cell.len() > 1) else {
+            return self.leaf(partition, path);
+        };
+
+        let mut explored: Vec<usize> = Vec::new();
+        for &v in &partition[target] {
+            // Skip vertices equivalent to already explored ones under automorphisms that
+            // fix the path
+            let mut parent: Vec<usize> = (0..self.graph.size()).collect();
+            for automorphism in &self.automorphisms {
+                if path.iter().all(|&p| automorphism[p] == p) {
+                    for (a, &b) in automorphism.iter().enumerate() {
+                        union(&mut parent, a, b);
+                    }
+                }
+            }
+            let v_root = find(&mut parent, v);
+            if explored.iter().any(|&u| find(&mut parent, u) == v_root) {
+                continue;
+            }
+            explored.push(v);
+
+            let mut child = partition[..target].to_vec();
+            child.push(vec![v]);
+            child.push(
+                partition[target]
+                    .iter()
+                    .copied()
+                    .filter(|&u| u != v)
+                    .collect(),
+            );
+            child.extend_from_slice(&partition[target + 1..]);
+            let child = self.refine(child);
+
+            path.push(v);
+            let backtrack = self.search(&child, path);
+            path.pop();
+
+            if let Some(depth) = backtrack {
+                if depth < path.len() {
+                    return Some(depth);
+                }
+            }
+        }
+        None
+    }
+
+    fn leaf(&mut self, partition: &[Vec<usize>], path: &[usize]) -> Option<usize> {
+        let n = self.graph.size();
+        let mut labeling = vec![0; n];
+        for (new_index, cell) in partition.iter().enumerate() {
+            labeling[cell[0]] = new_index;
+        }
+        let mut order = vec![0; n];
+        for (v, &new_index) in labeling.iter().enumerate() {
+            order[new_index] = v;
+        }
+
+        let mut matrix = vec![false; n * n];
+        for (new_u, &u) in order.iter().enumerate() {
+            for (new_v, &v) in order.iter().enumerate() {
+                matrix[n * new_u + new_v] = self
+                    .graph
+                    .are_adjacent(VertexIndex { index: u }, VertexIndex { index: v });
+            }
+        }
+        let certificate = (order.iter().map(|&v| self.colors[v]).collect(), matrix);
+
+        // Automorphism maps vertex with given label in one leaf to the vertex with the same
+        // label in the other leaf
+        let automorphism_with = |other_labeling: &[usize]| {
+            (0..n)
+                .map(|v| order[other_labeling[v]])
+                .collect::<Vec<usize>>()
+        };
+
+        let Some((first_labeling, first_certificate, first_path)) = &self.first_leaf else {
+            self.first_leaf = Some((labeling.clone(), certificate.clone(), path.to_vec()));
+            self.best_leaf = Some((labeling, certificate));
+            return None;
+        };
+
+        if *first_certificate == certificate {
+            // Automorphism fixes the common part of both paths and maps the first path to the
+            // current one, so the whole subtree from the point where we left the first path is
+            // equivalent to the one already explored
+            let automorphism = automorphism_with(first_labeling);
+            let common = path
+                .iter()
+                .zip(first_path.iter())
+                .take_while(|(a, b)| a == b)
+                .count();
+            self.automorphisms.push(automorphism);
+            return Some(common);
+        }
+
+        let (best_labeling, best_certificate) = self.best_leaf.as_ref().unwrap();
+        match certificate.cmp(best_certificate) {
+            std::cmp::Ordering::Greater => self.best_leaf = Some((labeling, certificate)),
+            std::cmp::Ordering::Equal => {
+                let automorphism = automorphism_with(best_labeling);
+                self.automorphisms.push(automorphism);
+            }
+            std::cmp::Ordering::Less => {}
+        }
+        None
+    }
+}
+
+fn find(parent: &mut [usize], v: usize) -> usize {
+    let mut root = v;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    let mut v = v;
+    while parent[v] != root {
+        let next = parent[v];
+        parent[v] = root;
+        v = next;
+    }
+    root
+}
+
+fn union(parent: &mut [usize], a: usize, b: usize) {
+    let a = find(parent, a);
+    let b = find(parent, b);
+    // Keep the smaller vertex as the root, so orbits are represented by their smallest vertex
+    if a < b {
+        parent[b] = a;
+    } else {
+        parent[a] = b;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::adjacency_matrix::undirected::UndirectedGraph;
+
+    fn graph(size: usize, edges: &[(usize, usize)]) -> UndirectedGraph {
+        let edges: Vec<_> = edges
+            .iter()
+            .map(|(u, v)| (VertexIndex { index: *u }, VertexIndex { index: *v }))
+            .collect();
+        UndirectedGraph::from_edges(size, &edges)
+    }
+
+    #[test]
+    fn isomorphic_graphs() {
+        // Path 0-1-2-3 and the same path relabeled
+        let path = graph(4, &[(0, 1), (1, 2), (2, 3)]);
+        let relabeled = graph(4, &[(2, 0), (0, 3), (3, 1)]);
+        assert!(path.is_isomorphic_to(&relabeled));
+        assert_eq!(path.canonical_form(), relabeled.canonical_form());
+
+        let star = graph(4, &[(0, 1), (0, 2), (0, 3)]);
+        assert!(!path.is_isomorphic_to(&star));
+
+        // Both 3-regular on 6 vertices, but only one of them is bipartite
+        let prism = graph(
+            6,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 0),
+                (3, 4),
+                (4, 5),
+                (5, 3),
+                (0, 3),
+                (1, 4),
+                (2, 5),
+            ],
+        );
+        let bipartite = graph(
+            6,
+            &[
+                (0, 3),
+                (0, 4),
+                (0, 5),
+                (1, 3),
+                (1, 4),
+                (1, 5),
+                (2, 3),
+                (2, 4),
+                (2, 5),
+            ],
+        );
+        assert!(!prism.is_isomorphic_to(&bipartite));
+        assert!(prism.is_isomorphic_to(&prism));
+    }
+
+    #[test]
+    fn colors_are_respected() {
+        let path = graph(3, &[(0, 1), (1, 2)]);
+        let labeling = CanonicalLabeling::new(&path, &[0, 0, 0]);
+        let orbits: Vec<usize> = labeling.orbits().iter().map(|v| v.index).collect();
+        assert_eq!(orbits, vec![0, 1, 0]);
+
+        let labeling = CanonicalLabeling::new(&path, &[0, 0, 1]);
+        let orbits: Vec<usize> = labeling.orbits().iter().map(|v| v.index).collect();
+        assert_eq!(orbits, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn symmetric_graphs() {
+        let empty = UndirectedGraph::empty(20);
+        let labeling = CanonicalLabeling::new(&empty, &[0; 20]);
+        assert!(labeling.orbits().iter().all(|v| v.index == 0));
+
+        let cycle_edges: Vec<(usize, usize)> = (0..12).map(|i| (i, (i + 1) % 12)).collect();
+        let cycle = graph(12, &cycle_edges);
+        let shifted_edges: Vec<(usize, usize)> = (0..12)
+            .map(|i| ((i * 5) % 12, ((i + 1) * 5) % 12))
+            .collect();
+        assert!(cycle.is_isomorphic_to(&graph(12, &shifted_edges)));
+    }
+
+    #[test]
+    fn random_relabelings() {
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = |bound: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % bound as u64) as usize
+        };
+
+        for _ in 0..50 {
+            let size = 2 + next(10);
+            let mut edges = Vec::new();
+            for u in 0..size {
+                for v in (u + 1)..size {
+                    if next(3) == 0 {
+                        edges.push((u, v));
+                    }
+                }
+            }
+
+            let mut permutation: Vec<usize> = (0..size).collect();
+            for idx in (1..size).rev() {
+                permutation.swap(idx, next(idx + 1));
+            }
+            let relabeled: Vec<(usize, usize)> = edges
+                .iter()
+                .map(|(u, v)| (permutation[*u], permutation[*v]))
+                .collect();
+
+            assert_eq!(
+                graph(size, &edges).canonical_form(),
+                graph(size, &relabeled).canonical_form()
+            );
+        }
+    }
+}
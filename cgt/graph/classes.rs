@@ -0,0 +1,308 @@
+//! Graph classes and operators repairing graphs to belong to them
+//!
+//! Repairs are meant for searches that mutate graphs, so they change as few edges as they
+//! reasonably can, never add or remove vertices, and are randomized to not bias the search. Edge
+//! directions are ignored, edges are removed and added in both directions.
+
+use crate::graph::{Graph, VertexIndex};
+use rand::{seq::SliceRandom, Rng};
+
+/// Class of graphs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphClass {
+    /// Connected graphs without cycles. Graphs without vertices are not trees.
+    Tree,
+
+    /// Graphs that can be drawn on a plane without crossing edges
+    Planar,
+
+    /// Graphs without odd cycles
+    Bipartite,
+
+    /// Graphs without vertices of degree above given value
+    MaxDegree(usize),
+
+    /// Connected graphs
+    Connected,
+}
+
+impl GraphClass {
+    /// Check if the graph belongs to the class
+    pub fn contains<G>(self, graph: &G) -> bool
+    where
+        G: Graph,
+    {
+        match self {
+            Self::Tree => {
+                graph.size() > 0 && graph.is_connected() && edges(graph).len() == graph.size() - 1
+            }
+            Self::Planar => graph.is_planar(),
+            Self::Bipartite => graph.is_bipartite(),
+            Self::MaxDegree(max_degree) => degrees(graph).into_iter().all(|d| d <= max_degree),
+            Self::Connected => graph.is_connected(),
+        }
+    }
+}
+
+fn set_edge<G>(graph: &mut G, u: usize, v: usize, connect: bool)
+where
+    G: Graph,
+{
+    let (u, v) = (VertexIndex { index: u }, VertexIndex { index: v });
+    graph.connect(u, v, connect);
+    graph.connect(v, u, connect);
+}
+
+/// Undirected edges without loops, each once with the lower vertex first
+fn edges<G>(graph: &G) -> Vec<(usize, usize)>
+where
+    G: Graph,
+{
+    let mut edges = graph
+        .edges()
+        .filter(|(u, v)| u != v)
+        .map(|(u, v)| (u.index.min(v.index), u.index.max(v.index)))
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+    edges.dedup();
+    edges
+}
+
+fn degrees<G>(graph: &G) -> Vec<usize>
+where
+    G: Graph,
+{
+    let mut degrees = vec![0; graph.size()];
+    for (u, v) in edges(graph) {
+        degrees[u] += 1;
+        degrees[v] += 1;
+    }
+    degrees
+}
+
+/// Disjoint sets of vertices
+struct Components {
+    parent: Vec<usize>,
+}
+
+impl Components {
+    fn new(size: usize) -> Self {
+        Self {
+            parent: (0..size).collect(),
+        }
+    }
+
+    fn find(&mut self, mut v: usize) -> usize {
+        while self.parent[v] != v {
+            self.parent[v] = self.parent[self.parent[v]];
+            v = self.parent[v];
+        }
+        v
+    }
+
+    /// Merge sets of `u` and `v`, returns `false` if they were already in the same set
+    fn union(&mut self, u: usize, v: usize) -> bool {
+        let (u, v) = (self.find(u), self.find(v));
+        self.parent[u] = v;
+        u != v
+    }
+}
+
+/// Keep edges of a random spanning forest
+fn remove_cycles<G, R>(graph: &mut G, rng: &mut R)
+where
+    G: Graph,
+    R: Rng,
+{
+    let mut edges = edges(graph);
+    edges.shuffle(rng);
+    let mut components = Components::new(graph.size());
+    for (u, v) in edges {
+        if !components.union(u, v) {
+            set_edge(graph, u, v, false);
+        }
+    }
+}
+
+/// Remove edges between vertices on the same side of a random two-coloring by distance parity
+fn remove_odd_cycles<G, R>(graph: &mut G, rng: &mut R)
+where
+    G: Graph,
+    R: Rng,
+{
+    let edges = edges(graph);
+    let mut neighbors = vec![Vec::new(); graph.size()];
+    for &(u, v) in &edges {
+        neighbors[u].push(v);
+        neighbors[v].push(u);
+    }
+
+    let mut starts = (0..graph.size()).collect::<Vec<_>>();
+    starts.shuffle(rng);
+    let mut sides = vec![None; graph.size()];
+    let mut stack = Vec::new();
+    for start in starts {
+        if sides[start].is_some() {
+            continue;
+        }
+        sides[start] = Some(false);
+        stack.push(start);
+        while let Some(v) = stack.pop() {
+            let side = sides[v] == Some(true);
+            for &u in &neighbors[v] {
+                if sides[u].is_none() {
+                    sides[u] = Some(!side);
+                    stack.push(u);
+                }
+            }
+        }
+    }
+
+    for (u, v) in edges {
+        if sides[u] == sides[v] {
+            set_edge(graph, u, v, false);
+        }
+    }
+}
+
+/// Remove random edges until the graph is planar
+fn remove_crossings<G, R>(graph: &mut G, rng: &mut R)
+where
+    G: Graph,
+    R: Rng,
+{
+    let mut edges = edges(graph);
+    edges.shuffle(rng);
+    while !graph.is_planar() {
+        let Some((u, v)) = edges.pop() else {
+            break;
+        };
+        set_edge(graph, u, v, false);
+    }
+}
+
+/// Remove random edges of vertices with too high degree
+fn limit_degree<G, R>(graph: &mut G, max_degree: usize, rng: &mut R)
+where
+    G: Graph,
+    R: Rng,
+{
+    let mut degrees = degrees(graph);
+    let mut edges = edges(graph);
+    edges.shuffle(rng);
+    for (u, v) in edges {
+        if degrees[u] > max_degree || degrees[v] > max_degree {
+            set_edge(graph, u, v, false);
+            degrees[u] -= 1;
+            degrees[v] -= 1;
+        }
+    }
+}
+
+/// Connect components with bridges between random vertices of lowest degree. Bridges do not
+/// create cycles, so forests, bipartite and planar graphs stay in their classes.
+fn connect_components<G, R>(graph: &mut G, rng: &mut R)
+where
+    G: Graph,
+    R: Rng,
+{
+    let mut degrees = degrees(graph);
+    let mut components = Components::new(graph.size());
+    for (u, v) in edges(graph) {
+        components.union(u, v);
+    }
+
+    let mut vertices = (0..graph.size()).collect::<Vec<_>>();
+    vertices.shuffle(rng);
+    vertices.sort_by_key(|&v| degrees[v]);
+    for u_idx in 0..vertices.len() {
+        for v_idx in (u_idx + 1)..vertices.len() {
+            let (u, v) = (vertices[u_idx], vertices[v_idx]);
+            if components.union(u, v) {
+                set_edge(graph, u, v, true);
+                degrees[u] += 1;
+                degrees[v] += 1;
+            }
+        }
+    }
+}
+
+/// Change edges of the graph so it belongs to all given classes, if possible
+///
+/// Repairs are applied in order of cycle removal (trees), odd cycle removal (bipartite graphs),
+/// edge removal (planar graphs and maximum degree) and finally adding bridges (connected graphs
+/// and trees), so later steps do not break earlier ones. Only connecting components can fail
+/// to respect the maximum degree, e.g. a graph with maximum degree 1 and more than two vertices
+/// cannot be connected.
+pub fn repair<G, R>(graph: &mut G, classes: &[GraphClass], rng: &mut R)
+where
+    G: Graph,
+    R: Rng,
+{
+    if classes.contains(&GraphClass::Tree) {
+        remove_cycles(graph, rng);
+    }
+    if classes.contains(&GraphClass::Bipartite) {
+        remove_odd_cycles(graph, rng);
+    }
+    if classes.contains(&GraphClass::Planar) {
+        remove_crossings(graph, rng);
+    }
+    if let Some(max_degree) = classes
+        .iter()
+        .filter_map(|class| match class {
+            GraphClass::MaxDegree(max_degree) => Some(*max_degree),
+            _ => None,
+        })
+        .min()
+    {
+        limit_degree(graph, max_degree, rng);
+    }
+    if classes.contains(&GraphClass::Tree) || classes.contains(&GraphClass::Connected) {
+        connect_components(graph, rng);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{adjacency_matrix::undirected::UndirectedGraph, generators};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn repairs_random_graphs() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let class_sets = [
+            vec![GraphClass::Tree],
+            vec![GraphClass::Tree, GraphClass::MaxDegree(3)],
+            vec![GraphClass::Planar, GraphClass::Connected],
+            vec![GraphClass::Bipartite, GraphClass::MaxDegree(2)],
+            vec![
+                GraphClass::Bipartite,
+                GraphClass::Planar,
+                GraphClass::Connected,
+            ],
+        ];
+        for _ in 0..20 {
+            let graph = generators::random_gnp::<UndirectedGraph, _>(12, 0.5, &mut rng);
+            for classes in &class_sets {
+                let mut repaired = graph.clone();
+                repair(&mut repaired, classes, &mut rng);
+                for class in classes {
+                    assert!(class.contains(&repaired), "{class:?}: {repaired}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_graphs_in_class() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut tree = generators::caterpillar::<UndirectedGraph>(&[2, 0, 1]);
+        let expected = tree.clone();
+        repair(&mut tree, &[GraphClass::Tree, GraphClass::Planar], &mut rng);
+        assert_eq!(tree, expected);
+        assert!(!GraphClass::MaxDegree(1).contains(&tree));
+        assert!(!GraphClass::Tree.contains(&generators::cycle::<UndirectedGraph>(4)));
+    }
+}
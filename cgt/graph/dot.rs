@@ -0,0 +1,361 @@
+//! Parser for a subset of [Graphviz DOT](https://graphviz.org/doc/info/lang.html) language.
+//!
+//! Supported are node and edge statements with attribute lists, default `node` attributes,
+//! comments and quoted identifiers. Subgraphs, ports and HTML strings are not supported.
+
+use crate::{
+    graph::{Graph, VertexIndex},
+    nom_utils::impl_from_str_via_nom,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::{escaped_transform, tag, take_until, take_while1},
+    character::complete::{char, multispace1, none_of, not_line_ending},
+    combinator::{map, opt, value, verify},
+    multi::many0,
+    sequence::{delimited, preceded, terminated},
+    IResult, Parser,
+};
+use std::fmt::{self, Display};
+
+/// Vertex of a DOT graph
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DotVertex {
+    /// Identifier of the vertex, as written in the input
+    pub id: String,
+
+    /// Attributes of the vertex, in order of appearance
+    pub attributes: Vec<(String, String)>,
+}
+
+/// Graph parsed from DOT format
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Dot {
+    /// Whether the graph is a `digraph`
+    pub directed: bool,
+
+    /// Vertices in order of their first appearance
+    pub vertices: Vec<DotVertex>,
+
+    /// Edges in order of appearance
+    pub edges: Vec<(VertexIndex, VertexIndex)>,
+}
+
+enum Statement {
+    NodeDefaults(Vec<(String, String)>),
+    Node(String, Vec<(String, String)>),
+    Edge(Vec<String>),
+    Ignored,
+}
+
+/// Skip whitespace and comments
+fn skip(input: &str) -> IResult<&str, ()> {
+    value(
+        (),
+        many0(alt((
+            value((), multispace1),
+            value((), preceded(alt((tag("//"), tag("#"))), not_line_ending)),
+            value((), delimited(tag("/*"), take_until("*/"), tag("*/"))),
+        ))),
+    )(input)
+}
+
+fn token<'input, Output, F>(mut inner: F) -> impl FnMut(&'input str) -> IResult<&'input str, Output>
+where
+    F: Parser<&'input str, Output, nom::error::Error<&'input str>>,
+{
+    move |input: &'input str| {
+        let (input, ()) = skip(input)?;
+        let (input, res) = inner.parse(input)?;
+        let (input, ()) = skip(input)?;
+        Ok((input, res))
+    }
+}
+
+fn identifier(input: &str) -> IResult<&str, String> {
+    token(alt((
+        map(
+            take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '.'),
+            String::from,
+        ),
+        delimited(
+            char('"'),
+            map(
+                opt(escaped_transform(
+                    none_of("\\\""),
+                    '\\',
+                    alt((
+                        value("\"", char('"')),
+                        value("\\\\", char('\\')),
+                        value("\\n", char('n')),
+                        value("\\l", char('l')),
+                        value("\\r", char('r')),
+                    )),
+                )),
+                Option::unwrap_or_default,
+            ),
+            char('"'),
+        ),
+    )))(input)
+}
+
+fn keyword<'input>(
+    keyword: &'static str,
+) -> impl FnMut(&'input str) -> IResult<&'input str, String> {
+    verify(identifier, move |id: &str| id.eq_ignore_ascii_case(keyword))
+}
+
+fn attribute(input: &str) -> IResult<&str, (String, String)> {
+    let (input, key) = identifier(input)?;
+    let (input, _) = token(char('='))(input)?;
+    let (input, value) = identifier(input)?;
+    Ok((input, (key, value)))
+}
+
+fn attribute_list(input: &str) -> IResult<&str, Vec<(String, String)>> {
+    map(
+        many0(delimited(
+            token(char('[')),
+            many0(terminated(
+                attribute,
+                opt(token(alt((char(','), char(';'))))),
+            )),
+            token(char(']')),
+        )),
+        |lists| lists.into_iter().flatten().collect(),
+    )(input)
+}
+
+/// Edge operator. We don't check if it matches the graph kind
+fn edge_op(input: &str) -> IResult<&str, &str> {
+    token(alt((tag("--"), tag("->"))))(input)
+}
+
+fn statement(input: &str) -> IResult<&str, Statement> {
+    alt((
+        map(
+            preceded(keyword("node"), attribute_list),
+            Statement::NodeDefaults,
+        ),
+        map(
+            preceded(alt((keyword("edge"), keyword("graph"))), attribute_list),
+            |_| Statement::Ignored,
+        ),
+        map(attribute, |_| Statement::Ignored),
+        |input| {
+            let (input, first) = identifier(input)?;
+            let (input, rest) = many0(preceded(edge_op, identifier))(input)?;
+            let (input, attributes) = attribute_list(input)?;
+            if rest.is_empty() {
+                Ok((input, Statement::Node(first, attributes)))
+            } else {
+                let mut path = vec![first];
+                path.extend(rest);
+                Ok((input, Statement::Edge(path)))
+            }
+        },
+    ))(input)
+}
+
+impl Dot {
+    /// Parse graph in DOT format
+    #[allow(clippy::missing_errors_doc)]
+    pub fn parse(input: &str) -> IResult<&str, Self> {
+        let (input, _) = opt(keyword("strict"))(input)?;
+        let (input, directed) = alt((
+            value(true, keyword("digraph")),
+            value(false, keyword("graph")),
+        ))(input)?;
+        let (input, _) = opt(identifier)(input)?;
+        let (input, _) = token(char('{'))(input)?;
+        let (input, statements) = many0(|input| {
+            let (input, statement) = statement(input)?;
+            let (input, _) = opt(token(char(';')))(input)?;
+            Ok((input, statement))
+        })(input)?;
+        let (input, _) = token(char('}'))(input)?;
+
+        let mut dot = Self {
+            directed,
+            vertices: Vec::new(),
+            edges: Vec::new(),
+        };
+        let mut node_defaults = Vec::new();
+        for statement in statements {
+            match statement {
+                Statement::NodeDefaults(attributes) => node_defaults.extend(attributes),
+                Statement::Node(id, attributes) => {
+                    let vertex = dot.vertex_or_insert(id, &node_defaults);
+                    dot.vertices[vertex.index].attributes.extend(attributes);
+                }
+                Statement::Edge(path) => {
+                    let path: Vec<VertexIndex> = path
+                        .into_iter()
+                        .map(|id| dot.vertex_or_insert(id, &node_defaults))
+                        .collect();
+                    for edge in path.windows(2) {
+                        dot.edges.push((edge[0], edge[1]));
+                    }
+                }
+                Statement::Ignored => {}
+            }
+        }
+
+        Ok((input, dot))
+    }
+
+    fn vertex_or_insert(&mut self, id: String, defaults: &[(String, String)]) -> VertexIndex {
+        let index = self
+            .vertices
+            .iter()
+            .position(|vertex| vertex.id == id)
+            .unwrap_or_else(|| {
+                self.vertices.push(DotVertex {
+                    id,
+                    attributes: defaults.to_vec(),
+                });
+                self.vertices.len() - 1
+            });
+        VertexIndex { index }
+    }
+
+    /// Get value of vertex attribute. If attribute was set multiple times, the last value is
+    /// returned.
+    pub fn attribute(&self, vertex: VertexIndex, key: &str) -> Option<&str> {
+        self.vertices[vertex.index]
+            .attributes
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Fill color of a vertex, falling back to its `color` attribute
+    pub fn fill_color(&self, vertex: VertexIndex) -> Option<&str> {
+        self.attribute(vertex, "fillcolor")
+            .or_else(|| self.attribute(vertex, "color"))
+    }
+
+    /// Build graph with the same edges. Edges of undirected DOT graph are added in both
+    /// directions.
+    pub fn to_graph<G>(&self) -> G
+    where
+        G: Graph,
+    {
+        let mut graph = G::empty(self.vertices.len());
+        for (u, v) in &self.edges {
+            graph.connect(*u, *v, true);
+            if !self.directed {
+                graph.connect(*v, *u, true);
+            }
+        }
+        graph
+    }
+}
+
+fn write_id(f: &mut fmt::Formatter<'_>, id: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in id.chars() {
+        if c == '"' {
+            write!(f, "\\")?;
+        }
+        write!(f, "{}", c)?;
+    }
+    write!(f, "\"")
+}
+
+impl Display for Dot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (keyword, edge_op) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        write!(f, "{} {{", keyword)?;
+        for vertex in &self.vertices {
+            write_id(f, &vertex.id)?;
+            if !vertex.attributes.is_empty() {
+                write!(f, " [")?;
+                for (idx, (key, value)) in vertex.attributes.iter().enumerate() {
+                    if idx != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write_id(f, key)?;
+                    write!(f, "=")?;
+                    write_id(f, value)?;
+                }
+                write!(f, "]")?;
+            }
+            write!(f, ";")?;
+        }
+        for (u, v) in &self.edges {
+            write_id(f, &self.vertices[u.index].id)?;
+            write!(f, " {} ", edge_op)?;
+            write_id(f, &self.vertices[v.index].id)?;
+            write!(f, ";")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl_from_str_via_nom!(Dot);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::adjacency_list;
+    use std::str::FromStr;
+
+    #[test]
+    fn parses_statements() {
+        let dot = Dot::from_str(
+            r#"
+            strict graph G {
+                // Comment
+                node [shape=circle];
+                a [label="Vertex \"a\"", fillcolor=blue style=filled];
+                a -- b -- c; /* Multiline
+                comment */
+                c -- a
+                # Another comment
+                rankdir = LR;
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert!(!dot.directed);
+        assert_eq!(dot.vertices.len(), 3);
+        assert_eq!(
+            dot.attribute(VertexIndex { index: 0 }, "label"),
+            Some("Vertex \"a\"")
+        );
+        assert_eq!(dot.fill_color(VertexIndex { index: 0 }), Some("blue"));
+        assert_eq!(
+            dot.attribute(VertexIndex { index: 2 }, "shape"),
+            Some("circle")
+        );
+        assert_eq!(dot.edges.len(), 3);
+
+        let graph: adjacency_list::undirected::UndirectedGraph = dot.to_graph();
+        assert_eq!(graph.degrees().collect::<Vec<_>>(), vec![2, 2, 2]);
+
+        assert!(Dot::from_str("graph { a -- }").is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let dot = Dot::from_str("digraph { 0 [label=\"0\\n<2>\"]; 0 -> 1; 1 -> 2 }").unwrap();
+        assert!(dot.directed);
+        assert_eq!(
+            dot.attribute(VertexIndex { index: 0 }, "label"),
+            Some("0\\n<2>")
+        );
+        assert_eq!(Dot::from_str(&dot.to_string()).unwrap(), dot);
+
+        let graph: adjacency_list::directed::DirectedGraph = dot.to_graph();
+        assert!(graph.are_adjacent(VertexIndex { index: 0 }, VertexIndex { index: 1 }));
+        assert!(!graph.are_adjacent(VertexIndex { index: 1 }, VertexIndex { index: 0 }));
+    }
+}
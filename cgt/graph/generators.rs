@@ -0,0 +1,270 @@
+//! Constructors of common graph families.
+//!
+//! All generated graphs are undirected, so when a directed [`Graph`] is requested every edge
+//! is added in both directions.
+
+use crate::graph::{Graph, VertexIndex};
+use rand::{seq::SliceRandom, Rng};
+
+fn connect_both<G>(graph: &mut G, u: usize, v: usize)
+where
+    G: Graph,
+{
+    let (u, v) = (VertexIndex { index: u }, VertexIndex { index: v });
+    graph.connect(u, v, true);
+    graph.connect(v, u, true);
+}
+
+/// Path on `size` vertices, where vertex `i` is adjacent to vertex `i + 1`
+pub fn path<G>(size: usize) -> G
+where
+    G: Graph,
+{
+    let mut graph = G::empty(size);
+    for v in 1..size {
+        connect_both(&mut graph, v - 1, v);
+    }
+    graph
+}
+
+/// Cycle on `size` vertices. Cycles on less than three vertices are paths.
+pub fn cycle<G>(size: usize) -> G
+where
+    G: Graph,
+{
+    let mut graph = path(size);
+    if size > 2 {
+        connect_both(&mut graph, size - 1, 0);
+    }
+    graph
+}
+
+/// Star with center at vertex `0` and `leaves` leaves
+pub fn star<G>(leaves: usize) -> G
+where
+    G: Graph,
+{
+    let mut graph = G::empty(leaves + 1);
+    for leaf in 1..=leaves {
+        connect_both(&mut graph, 0, leaf);
+    }
+    graph
+}
+
+/// Complete graph on `size` vertices
+pub fn complete<G>(size: usize) -> G
+where
+    G: Graph,
+{
+    let mut graph = G::empty(size);
+    for v in 0..size {
+        for u in 0..v {
+            connect_both(&mut graph, u, v);
+        }
+    }
+    graph
+}
+
+/// Complete bipartite graph `K(left, right)`, where first `left` vertices are adjacent to the
+/// remaining `right` vertices
+pub fn complete_bipartite<G>(left: usize, right: usize) -> G
+where
+    G: Graph,
+{
+    let mut graph = G::empty(left + right);
+    for u in 0..left {
+        for v in left..(left + right) {
+            connect_both(&mut graph, u, v);
+        }
+    }
+    graph
+}
+
+/// Rectangular grid graph, where vertex at `(x, y)` has index `y * width + x`
+pub fn grid<G>(width: usize, height: usize) -> G
+where
+    G: Graph,
+{
+    let mut graph = G::empty(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            let v = y * width + x;
+            if x + 1 < width {
+                connect_both(&mut graph, v, v + 1);
+            }
+            if y + 1 < height {
+                connect_both(&mut graph, v, v + width);
+            }
+        }
+    }
+    graph
+}
+
+/// Caterpillar, i.e. a path (spine) with leaves attached to its vertices. Vertex `i` of the
+/// spine gets `legs[i]` leaves. Spine vertices come first, followed by leaves of each spine
+/// vertex in order.
+pub fn caterpillar<G>(legs: &[usize]) -> G
+where
+    G: Graph,
+{
+    let spine = legs.len();
+    let mut graph = G::empty(spine + legs.iter().sum::<usize>());
+    for v in 1..spine {
+        connect_both(&mut graph, v - 1, v);
+    }
+    let mut leaf = spine;
+    for (v, leaves) in legs.iter().enumerate() {
+        for _ in 0..*leaves {
+            connect_both(&mut graph, v, leaf);
+            leaf += 1;
+        }
+    }
+    graph
+}
+
+/// Spider, i.e. a generalized star with center at vertex `0` and paths of `legs[i]` vertices
+/// attached to it. Vertices of each leg follow the center in order, starting from the one
+/// adjacent to the center.
+pub fn spider<G>(legs: &[usize]) -> G
+where
+    G: Graph,
+{
+    let mut graph = G::empty(1 + legs.iter().sum::<usize>());
+    let mut v = 1;
+    for length in legs {
+        let mut previous = 0;
+        for _ in 0..*length {
+            connect_both(&mut graph, previous, v);
+            previous = v;
+            v += 1;
+        }
+    }
+    graph
+}
+
+/// Erdős–Rényi random graph `G(n, p)`, where every edge is present with probability `p`
+///
+/// # Panics
+/// - `p` is not in `[0, 1]`
+pub fn random_gnp<G, R>(size: usize, p: f64, rng: &mut R) -> G
+where
+    G: Graph,
+    R: Rng,
+{
+    let mut graph = G::empty(size);
+    for v in 0..size {
+        for u in 0..v {
+            if rng.gen_bool(p) {
+                connect_both(&mut graph, u, v);
+            }
+        }
+    }
+    graph
+}
+
+/// Random tree on `size` vertices, chosen uniformly from all labeled trees by decoding a
+/// random Prüfer sequence
+pub fn random_tree<G, R>(size: usize, rng: &mut R) -> G
+where
+    G: Graph,
+    R: Rng,
+{
+    let mut graph = G::empty(size);
+    if size < 2 {
+        return graph;
+    }
+
+    let sequence: Vec<usize> = (0..size - 2).map(|_| rng.gen_range(0..size)).collect();
+    let mut degree = vec![1; size];
+    for v in &sequence {
+        degree[*v] += 1;
+    }
+
+    for v in sequence {
+        let leaf = (0..size).find(|u| degree[*u] == 1).unwrap();
+        connect_both(&mut graph, leaf, v);
+        degree[leaf] -= 1;
+        degree[v] -= 1;
+    }
+
+    let mut remaining = (0..size).filter(|u| degree[*u] == 1);
+    let u = remaining.next().unwrap();
+    let v = remaining.next().unwrap();
+    connect_both(&mut graph, u, v);
+    graph
+}
+
+/// Random graph with no vertex of degree above `max_degree`. Pairs of vertices are visited in
+/// random order and connected with probability `p` if both have degree below `max_degree`.
+///
+/// # Panics
+/// - `p` is not in `[0, 1]`
+pub fn random_bounded_degree<G, R>(size: usize, max_degree: usize, p: f64, rng: &mut R) -> G
+where
+    G: Graph,
+    R: Rng,
+{
+    let mut pairs = (0..size)
+        .flat_map(|v| (0..v).map(move |u| (u, v)))
+        .collect::<Vec<_>>();
+    pairs.shuffle(rng);
+
+    let mut graph = G::empty(size);
+    let mut degree = vec![0; size];
+    for (u, v) in pairs {
+        if degree[u] < max_degree && degree[v] < max_degree && rng.gen_bool(p) {
+            connect_both(&mut graph, u, v);
+            degree[u] += 1;
+            degree[v] += 1;
+        }
+    }
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{adjacency_list, adjacency_matrix::undirected::UndirectedGraph};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn degrees(graph: &UndirectedGraph) -> Vec<usize> {
+        graph.degrees().collect()
+    }
+
+    #[test]
+    fn deterministic_families() {
+        assert_eq!(degrees(&path(4)), vec![1, 2, 2, 1]);
+        assert_eq!(degrees(&cycle(5)), vec![2; 5]);
+        assert_eq!(degrees(&star(3)), vec![3, 1, 1, 1]);
+        assert_eq!(degrees(&complete(5)), vec![4; 5]);
+        assert_eq!(degrees(&complete_bipartite(2, 3)), vec![3, 3, 2, 2, 2]);
+        assert_eq!(degrees(&grid(3, 2)), vec![2, 3, 2, 2, 3, 2]);
+        assert_eq!(degrees(&caterpillar(&[2, 0, 1])), vec![3, 2, 2, 1, 1, 1]);
+        assert_eq!(degrees(&spider(&[2, 1, 0])), vec![2, 2, 1, 1]);
+        assert!(spider::<UndirectedGraph>(&[1, 1, 1]).is_isomorphic_to(&star(3)));
+
+        let directed: adjacency_list::directed::DirectedGraph = path(3);
+        assert!(directed.are_adjacent(VertexIndex { index: 1 }, VertexIndex { index: 0 }));
+        assert!(!cycle::<UndirectedGraph>(6).is_isomorphic_to(&grid(3, 2)));
+        assert!(cycle::<UndirectedGraph>(4).is_isomorphic_to(&grid(2, 2)));
+    }
+
+    #[test]
+    fn random_families() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for size in 1..20 {
+            let tree: UndirectedGraph = random_tree(size, &mut rng);
+            assert!(tree.is_connected());
+            assert_eq!(tree.edges().count(), size - 1);
+        }
+
+        let empty: UndirectedGraph = random_gnp(10, 0.0, &mut rng);
+        assert_eq!(empty.edges().count(), 0);
+        let full: UndirectedGraph = random_gnp(10, 1.0, &mut rng);
+        assert_eq!(full, complete(10));
+
+        let bounded: UndirectedGraph = random_bounded_degree(10, 3, 1.0, &mut rng);
+        assert!(bounded.degrees().all(|degree| degree <= 3));
+        assert!(bounded.edges().count() >= 10);
+    }
+}
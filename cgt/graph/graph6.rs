@@ -0,0 +1,395 @@
+//! Reading and writing graphs in graph6, sparse6 and digraph6 formats used by nauty.
+//!
+//! All formats encode a graph as a line of printable ASCII characters, see
+//! <https://users.cecs.anu.edu.au/~bdm/data/formats.txt> for the specification.
+//! graph6 and sparse6 describe undirected graphs, so when writing a directed graph every arc
+//! is treated as an undirected edge. digraph6 stores full adjacency matrix.
+
+use crate::graph::{Graph, VertexIndex};
+
+const BIAS: u8 = 63;
+const LONG_SIZE: u8 = 126;
+
+const GRAPH6_HEADER: &str = ">>graph6<<";
+const SPARSE6_HEADER: &str = ">>sparse6<<";
+const DIGRAPH6_HEADER: &str = ">>digraph6<<";
+
+/// Stream of bits packed into 6-bit printable characters
+struct BitWriter {
+    output: String,
+    current: u8,
+    bits: u8,
+}
+
+impl BitWriter {
+    const fn new(output: String) -> Self {
+        Self {
+            output,
+            current: 0,
+            bits: 0,
+        }
+    }
+
+    fn push(&mut self, bit: bool) {
+        self.current = (self.current << 1) | u8::from(bit);
+        self.bits += 1;
+        if self.bits == 6 {
+            self.output.push(char::from(self.current + BIAS));
+            self.current = 0;
+            self.bits = 0;
+        }
+    }
+
+    /// Push lowest `width` bits of `value`, most significant first
+    fn push_value(&mut self, value: usize, width: u32) {
+        for bit in (0..width).rev() {
+            self.push((value >> bit) & 1 == 1);
+        }
+    }
+
+    /// Number of bits needed to fill the last character
+    const fn padding(&self) -> u8 {
+        if self.bits == 0 {
+            0
+        } else {
+            6 - self.bits
+        }
+    }
+
+    /// Pad last character with `bit` and return the output
+    fn finish(mut self, bit: bool) -> String {
+        while self.bits != 0 {
+            self.push(bit);
+        }
+        self.output
+    }
+}
+
+/// Reader of bits packed into 6-bit printable characters
+struct BitReader<'input> {
+    input: &'input [u8],
+    position: usize,
+}
+
+impl<'input> BitReader<'input> {
+    const fn new(input: &'input [u8]) -> Self {
+        Self { input, position: 0 }
+    }
+
+    const fn remaining(&self) -> usize {
+        self.input.len() * 6 - self.position
+    }
+
+    fn next_bit(&mut self) -> Option<bool> {
+        let byte = self.input.get(self.position / 6)?;
+        let bit = (byte - BIAS) >> (5 - self.position % 6) & 1 == 1;
+        self.position += 1;
+        Some(bit)
+    }
+
+    fn next_value(&mut self, width: u32) -> Option<usize> {
+        let mut value = 0;
+        for _ in 0..width {
+            value = (value << 1) | usize::from(self.next_bit()?);
+        }
+        Some(value)
+    }
+}
+
+fn write_size(output: &mut String, size: usize) {
+    let bytes: &[u32] = if size <= 62 {
+        &[0]
+    } else if size <= 258_047 {
+        output.push(char::from(LONG_SIZE));
+        &[12, 6, 0]
+    } else {
+        output.push(char::from(LONG_SIZE));
+        output.push(char::from(LONG_SIZE));
+        &[30, 24, 18, 12, 6, 0]
+    };
+    for shift in bytes {
+        output.push(char::from(((size >> shift) & 0b11_1111) as u8 + BIAS));
+    }
+}
+
+/// Parse the size of a graph, returning it with the rest of the input
+fn read_size(input: &[u8]) -> Option<(usize, &[u8])> {
+    let (width, input) = match input {
+        [LONG_SIZE, LONG_SIZE, rest @ ..] => (6, rest),
+        [LONG_SIZE, rest @ ..] => (3, rest),
+        _ => (1, input),
+    };
+    if input.len() < width {
+        return None;
+    }
+    let size = input[..width]
+        .iter()
+        .fold(0, |size, byte| (size << 6) | usize::from(byte - BIAS));
+    Some((size, &input[width..]))
+}
+
+/// Strip optional header, format prefix and trailing newline, and check that all remaining
+/// characters are in the printable range used by the formats
+fn strip_input<'input>(input: &'input str, header: &str, prefix: &str) -> Option<&'input [u8]> {
+    let input = input.trim_end_matches(['\n', '\r']);
+    let input = input.strip_prefix(header).unwrap_or(input);
+    let input = input.strip_prefix(prefix)?.as_bytes();
+    input
+        .iter()
+        .all(|byte| (BIAS..=LONG_SIZE).contains(byte))
+        .then_some(input)
+}
+
+/// Number of bits needed to write vertex index in sparse6 format
+const fn sparse6_width(size: usize) -> u32 {
+    if size <= 1 {
+        0
+    } else {
+        usize::BITS - (size - 1).leading_zeros()
+    }
+}
+
+/// Encode graph in graph6 format. Loops are ignored.
+pub fn to_graph6<G>(graph: &G) -> String
+where
+    G: Graph,
+{
+    let mut output = String::new();
+    write_size(&mut output, graph.size());
+    let mut writer = BitWriter::new(output);
+    for v in graph.vertices() {
+        for u in graph.vertices().take(v.index) {
+            writer.push(graph.are_adjacent(u, v) || graph.are_adjacent(v, u));
+        }
+    }
+    writer.finish(false)
+}
+
+/// Decode graph from graph6 format
+pub fn from_graph6<G>(input: &str) -> Option<G>
+where
+    G: Graph,
+{
+    let input = strip_input(input, GRAPH6_HEADER, "")?;
+    let (size, input) = read_size(input)?;
+    if input.len() != (size * size.saturating_sub(1) / 2).div_ceil(6) {
+        return None;
+    }
+
+    let mut graph = G::empty(size);
+    let mut reader = BitReader::new(input);
+    for v in 0..size {
+        for u in 0..v {
+            if reader.next_bit()? {
+                let (u, v) = (VertexIndex { index: u }, VertexIndex { index: v });
+                graph.connect(u, v, true);
+                graph.connect(v, u, true);
+            }
+        }
+    }
+    Some(graph)
+}
+
+/// Encode graph in sparse6 format
+pub fn to_sparse6<G>(graph: &G) -> String
+where
+    G: Graph,
+{
+    let size = graph.size();
+    let width = sparse6_width(size);
+    let mut output = String::from(":");
+    write_size(&mut output, size);
+    let mut writer = BitWriter::new(output);
+
+    let mut current = 0;
+    for v in graph.vertices() {
+        for u in graph.vertices().take(v.index + 1) {
+            if !(graph.are_adjacent(u, v) || graph.are_adjacent(v, u)) {
+                continue;
+            }
+
+            if v.index == current {
+                writer.push(false);
+            } else if v.index == current + 1 {
+                writer.push(true);
+            } else {
+                writer.push(true);
+                writer.push_value(v.index, width);
+                writer.push(false);
+            }
+            writer.push_value(u.index, width);
+            current = v.index;
+        }
+    }
+
+    // Padding with ones could be read as an extra edge to vertex `size - 1` in this case
+    if width < 6 && size == 1 << width && current + 2 == size && writer.padding() > width as u8 {
+        writer.push(false);
+    }
+    writer.finish(true)
+}
+
+/// Decode graph from sparse6 format
+pub fn from_sparse6<G>(input: &str) -> Option<G>
+where
+    G: Graph,
+{
+    let input = strip_input(input, SPARSE6_HEADER, ":")?;
+    let (size, input) = read_size(input)?;
+    let width = sparse6_width(size);
+
+    let mut graph = G::empty(size);
+    let mut reader = BitReader::new(input);
+    let mut v = 0;
+    while reader.remaining() > width as usize {
+        let b = reader.next_bit()?;
+        let x = reader.next_value(width)?;
+        if b {
+            v += 1;
+        }
+        if v >= size {
+            break;
+        }
+        if x > v {
+            v = x;
+        } else {
+            let (u, v) = (VertexIndex { index: x }, VertexIndex { index: v });
+            graph.connect(u, v, true);
+            graph.connect(v, u, true);
+        }
+    }
+    Some(graph)
+}
+
+/// Encode graph in digraph6 format
+pub fn to_digraph6<G>(graph: &G) -> String
+where
+    G: Graph,
+{
+    let mut output = String::from("&");
+    write_size(&mut output, graph.size());
+    let mut writer = BitWriter::new(output);
+    for u in graph.vertices() {
+        for v in graph.vertices() {
+            writer.push(graph.are_adjacent(u, v));
+        }
+    }
+    writer.finish(false)
+}
+
+/// Decode graph from digraph6 format
+pub fn from_digraph6<G>(input: &str) -> Option<G>
+where
+    G: Graph,
+{
+    let input = strip_input(input, DIGRAPH6_HEADER, "&")?;
+    let (size, input) = read_size(input)?;
+    if input.len() != (size * size).div_ceil(6) {
+        return None;
+    }
+
+    let mut graph = G::empty(size);
+    let mut reader = BitReader::new(input);
+    for u in 0..size {
+        for v in 0..size {
+            if reader.next_bit()? {
+                graph.connect(VertexIndex { index: u }, VertexIndex { index: v }, true);
+            }
+        }
+    }
+    Some(graph)
+}
+
+/// Decode graph from any of graph6, sparse6 or digraph6 format, detected by the first
+/// character or the header
+pub fn from_nauty<G>(input: &str) -> Option<G>
+where
+    G: Graph,
+{
+    if input.starts_with(':') || input.starts_with(SPARSE6_HEADER) {
+        from_sparse6(input)
+    } else if input.starts_with('&') || input.starts_with(DIGRAPH6_HEADER) {
+        from_digraph6(input)
+    } else {
+        from_graph6(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{adjacency_list, adjacency_matrix::undirected::UndirectedGraph};
+
+    fn graph(size: usize, edges: &[(usize, usize)]) -> UndirectedGraph {
+        let edges: Vec<_> = edges
+            .iter()
+            .map(|(u, v)| (VertexIndex { index: *u }, VertexIndex { index: *v }))
+            .collect();
+        UndirectedGraph::from_edges(size, &edges)
+    }
+
+    #[test]
+    fn graph6() {
+        let g = graph(5, &[(0, 2), (0, 4), (1, 3), (3, 4)]);
+        assert_eq!(to_graph6(&g), "DQc");
+        assert_eq!(from_graph6::<UndirectedGraph>("DQc\n"), Some(g.clone()));
+        assert_eq!(from_graph6::<UndirectedGraph>(">>graph6<<DQc"), Some(g));
+
+        assert_eq!(to_graph6(&UndirectedGraph::empty(0)), "?");
+        assert_eq!(from_graph6::<UndirectedGraph>("DQ"), None);
+        assert_eq!(from_graph6::<UndirectedGraph>("D Qc"), None);
+    }
+
+    #[test]
+    fn sparse6() {
+        let g = graph(7, &[(0, 1), (0, 2), (1, 2), (5, 6)]);
+        assert_eq!(to_sparse6(&g), ":Fa@x^");
+        assert_eq!(from_sparse6::<UndirectedGraph>(":Fa@x^"), Some(g));
+
+        // Padding special case
+        let g = graph(4, &[(0, 1), (1, 2), (2, 2)]);
+        assert_eq!(from_sparse6::<UndirectedGraph>(&to_sparse6(&g)), Some(g));
+    }
+
+    #[test]
+    fn digraph6() {
+        let edges: Vec<_> = [(0, 2), (0, 4), (3, 1), (3, 4)]
+            .iter()
+            .map(|(u, v)| (VertexIndex { index: *u }, VertexIndex { index: *v }))
+            .collect();
+        let g = adjacency_list::directed::DirectedGraph::from_edges(5, &edges);
+        assert_eq!(to_digraph6(&g), "&DI?AO?");
+        assert_eq!(from_nauty(">>digraph6<<&DI?AO?"), Some(g));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = |bound: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % bound as u64) as usize
+        };
+
+        for size in [0, 1, 2, 3, 4, 8, 16, 32, 62, 63, 100] {
+            let mut edges = Vec::new();
+            for u in 0..size {
+                for v in u..size {
+                    if next(4) == 0 {
+                        edges.push((u, v));
+                    }
+                }
+            }
+            let g = graph(size, &edges);
+            let mut without_loops = g.clone();
+            for v in g.vertices() {
+                without_loops.connect(v, v, false);
+            }
+
+            assert_eq!(from_nauty(&to_graph6(&g)), Some(without_loops));
+            assert_eq!(from_nauty(&to_sparse6(&g)), Some(g.clone()));
+            assert_eq!(from_nauty(&to_digraph6(&g)), Some(g));
+        }
+    }
+}
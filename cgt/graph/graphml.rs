@@ -0,0 +1,402 @@
+//! Reading and writing graphs in [GraphML](http://graphml.graphdrawing.org/) format.
+//!
+//! Only the structure of the graph and `<data>` elements of nodes with plain text values are
+//! supported. Other elements, like yEd graphics, are ignored.
+
+use crate::{
+    graph::{Graph, VertexIndex},
+    nom_utils::impl_from_str_via_nom,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_not, tag, take_until, take_while1},
+    character::complete::{char, multispace0, multispace1},
+    combinator::{map, opt, value},
+    multi::many0,
+    sequence::{delimited, preceded, tuple},
+    IResult,
+};
+use std::fmt::{self, Display};
+
+/// Vertex of a `GraphML` graph
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GraphMlVertex {
+    /// Identifier of the vertex, as written in the input
+    pub id: String,
+
+    /// Values of data attributes of the vertex, by attribute name
+    pub data: Vec<(String, String)>,
+}
+
+/// Graph in `GraphML` format
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GraphMl {
+    /// Whether edges are directed by default
+    pub directed: bool,
+
+    /// Vertices in order of their appearance
+    pub vertices: Vec<GraphMlVertex>,
+
+    /// Edges in order of appearance
+    pub edges: Vec<(VertexIndex, VertexIndex)>,
+}
+
+/// Generic XML element
+#[derive(Debug)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Self>,
+    text: String,
+}
+
+impl Element {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Self> + 'a {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+enum Content {
+    Element(Element),
+    Text(String),
+    Ignored,
+}
+
+fn unescape(input: &str) -> String {
+    input
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn escape(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Skip whitespace, comments, processing instructions and document type declaration
+fn misc(input: &str) -> IResult<&str, ()> {
+    value(
+        (),
+        many0(alt((
+            value((), multispace1),
+            value((), tuple((tag("<!--"), take_until("-->"), tag("-->")))),
+            value((), tuple((tag("<?"), take_until("?>"), tag("?>")))),
+            value((), tuple((tag("<!DOCTYPE"), take_until(">"), tag(">")))),
+        ))),
+    )(input)
+}
+
+fn name(input: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| c.is_alphanumeric() || ":_-.".contains(c))(input)
+}
+
+fn attribute(input: &str) -> IResult<&str, (String, String)> {
+    let (input, _) = multispace1(input)?;
+    let (input, key) = name(input)?;
+    let (input, _) = delimited(multispace0, char('='), multispace0)(input)?;
+    let (input, value) = alt((
+        delimited(char('"'), opt(is_not("\"")), char('"')),
+        delimited(char('\''), opt(is_not("'")), char('\'')),
+    ))(input)?;
+    Ok((input, (key.to_owned(), unescape(value.unwrap_or_default()))))
+}
+
+fn element(input: &str) -> IResult<&str, Element> {
+    let (input, _) = char('<')(input)?;
+    let (input, element_name) = name(input)?;
+    let (input, attributes) = many0(attribute)(input)?;
+    let (input, _) = multispace0(input)?;
+
+    let mut result = Element {
+        name: element_name.to_owned(),
+        attributes,
+        children: Vec::new(),
+        text: String::new(),
+    };
+
+    if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("/>")(input) {
+        return Ok((input, result));
+    }
+
+    let (input, _) = char('>')(input)?;
+    let (input, content) = many0(alt((
+        map(element, Content::Element),
+        map(is_not("<"), |text: &str| Content::Text(unescape(text))),
+        map(tuple((tag("<!--"), take_until("-->"), tag("-->"))), |_| {
+            Content::Ignored
+        }),
+    )))(input)?;
+    let (input, _) = tag("</")(input)?;
+    let (input, _) = tag(element_name)(input)?;
+    let (input, _) = preceded(multispace0, char('>'))(input)?;
+
+    for content in content {
+        match content {
+            Content::Element(child) => result.children.push(child),
+            Content::Text(text) => result.text.push_str(&text),
+            Content::Ignored => {}
+        }
+    }
+
+    Ok((input, result))
+}
+
+fn invalid(input: &str) -> nom::Err<nom::error::Error<&str>> {
+    nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Verify))
+}
+
+impl GraphMl {
+    /// Convert graph to `GraphML`, with vertex identifiers being their indices
+    pub fn from_graph<G>(graph: &G, directed: bool) -> Self
+    where
+        G: Graph,
+    {
+        Self {
+            directed,
+            vertices: graph
+                .vertices()
+                .map(|v| GraphMlVertex {
+                    id: v.index.to_string(),
+                    data: Vec::new(),
+                })
+                .collect(),
+            edges: graph
+                .vertices()
+                .flat_map(|u| graph.adjacent_to(u).map(move |v| (u, v)))
+                .filter(|(u, v)| directed || u <= v)
+                .collect(),
+        }
+    }
+
+    /// Parse graph in `GraphML` format. Only the first graph in the document is read.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn parse(input: &str) -> IResult<&str, Self> {
+        let (input, ()) = misc(input)?;
+        let (input, root) = element(input)?;
+        let (input, ()) = misc(input)?;
+        if root.name != "graphml" {
+            return Err(invalid(input));
+        }
+
+        // Key identifiers mapped to attribute names and default values
+        let mut keys: Vec<(&str, &str, Option<&str>)> = Vec::new();
+        for key in root.children("key") {
+            if matches!(key.attribute("for"), Some("node" | "all") | None) {
+                let id = key.attribute("id").ok_or_else(|| invalid(input))?;
+                let name = key.attribute("attr.name").unwrap_or(id);
+                let default = key.children("default").next().map(|d| d.text.as_str());
+                keys.push((id, name, default));
+            }
+        }
+
+        let graph = root
+            .children("graph")
+            .next()
+            .ok_or_else(|| invalid(input))?;
+        let mut result = Self {
+            directed: graph.attribute("edgedefault") == Some("directed"),
+            vertices: Vec::new(),
+            edges: Vec::new(),
+        };
+
+        for node in graph.children("node") {
+            let id = node.attribute("id").ok_or_else(|| invalid(input))?;
+            let mut data = Vec::new();
+            for (key_id, name, default) in &keys {
+                let value = node
+                    .children("data")
+                    .find(|d| d.attribute("key") == Some(key_id))
+                    .map(|d| d.text.trim())
+                    .or(*default);
+                if let Some(value) = value {
+                    data.push(((*name).to_owned(), value.to_owned()));
+                }
+            }
+            let vertex = result.vertex_or_insert(id);
+            result.vertices[vertex.index].data = data;
+        }
+
+        for edge in graph.children("edge") {
+            let source = edge.attribute("source").ok_or_else(|| invalid(input))?;
+            let target = edge.attribute("target").ok_or_else(|| invalid(input))?;
+            let source = result.vertex_or_insert(source);
+            let target = result.vertex_or_insert(target);
+            result.edges.push((source, target));
+        }
+
+        Ok((input, result))
+    }
+
+    fn vertex_or_insert(&mut self, id: &str) -> VertexIndex {
+        let index = self
+            .vertices
+            .iter()
+            .position(|vertex| vertex.id == id)
+            .unwrap_or_else(|| {
+                self.vertices.push(GraphMlVertex {
+                    id: id.to_owned(),
+                    data: Vec::new(),
+                });
+                self.vertices.len() - 1
+            });
+        VertexIndex { index }
+    }
+
+    /// Get value of vertex data attribute
+    pub fn data(&self, vertex: VertexIndex, name: &str) -> Option<&str> {
+        self.vertices[vertex.index]
+            .data
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Set value of vertex data attribute
+    pub fn set_data(&mut self, vertex: VertexIndex, name: &str, value: String) {
+        let data = &mut self.vertices[vertex.index].data;
+        match data.iter_mut().find(|(key, _)| key == name) {
+            Some((_, old_value)) => *old_value = value,
+            None => data.push((name.to_owned(), value)),
+        }
+    }
+
+    /// Build graph with the same edges. Edges of undirected graph are added in both
+    /// directions.
+    pub fn to_graph<G>(&self) -> G
+    where
+        G: Graph,
+    {
+        let mut graph = G::empty(self.vertices.len());
+        for (u, v) in &self.edges {
+            graph.connect(*u, *v, true);
+            if !self.directed {
+                graph.connect(*v, *u, true);
+            }
+        }
+        graph
+    }
+}
+
+impl Display for GraphMl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            f,
+            r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+        )?;
+
+        let mut names: Vec<&str> = Vec::new();
+        for vertex in &self.vertices {
+            for (name, _) in &vertex.data {
+                if !names.contains(&name.as_str()) {
+                    names.push(name);
+                }
+            }
+        }
+        for (idx, name) in names.iter().enumerate() {
+            writeln!(
+                f,
+                r#"  <key id="d{}" for="node" attr.name="{}" attr.type="string"/>"#,
+                idx,
+                escape(name)
+            )?;
+        }
+
+        let edge_default = if self.directed {
+            "directed"
+        } else {
+            "undirected"
+        };
+        writeln!(f, r#"  <graph edgedefault="{}">"#, edge_default)?;
+        for vertex in &self.vertices {
+            write!(f, r#"    <node id="{}">"#, escape(&vertex.id))?;
+            for (name, value) in &vertex.data {
+                let idx = names.iter().position(|n| n == name).unwrap();
+                write!(f, r#"<data key="d{}">{}</data>"#, idx, escape(value))?;
+            }
+            writeln!(f, "</node>")?;
+        }
+        for (u, v) in &self.edges {
+            writeln!(
+                f,
+                r#"    <edge source="{}" target="{}"/>"#,
+                escape(&self.vertices[u.index].id),
+                escape(&self.vertices[v.index].id)
+            )?;
+        }
+        writeln!(f, "  </graph>")?;
+        write!(f, "</graphml>")
+    }
+}
+
+impl_from_str_via_nom!(GraphMl);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{adjacency_list::directed::DirectedGraph, generators};
+    use std::str::FromStr;
+
+    #[test]
+    fn parses_networkx_output() {
+        let graphml = GraphMl::from_str(
+            r#"<?xml version='1.0' encoding='utf-8'?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+  <key id="d0" for="node" attr.name="color" attr.type="string">
+    <default>white</default>
+  </key>
+  <!-- Comment -->
+  <graph edgedefault="undirected">
+    <node id="a">
+      <data key="d0">blue</data>
+    </node>
+    <node id="b" />
+    <node id="c"><data key="d0">R&amp;D</data></node>
+    <edge source="a" target="b" />
+    <edge source="b" target="c" />
+  </graph>
+</graphml>"#,
+        )
+        .unwrap();
+
+        assert!(!graphml.directed);
+        assert_eq!(
+            graphml.data(VertexIndex { index: 0 }, "color"),
+            Some("blue")
+        );
+        assert_eq!(
+            graphml.data(VertexIndex { index: 1 }, "color"),
+            Some("white")
+        );
+        assert_eq!(graphml.data(VertexIndex { index: 2 }, "color"), Some("R&D"));
+        let graph: DirectedGraph = graphml.to_graph();
+        assert_eq!(graph, generators::path(3));
+
+        assert!(GraphMl::from_str("<graphml><graph></graphml>").is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let graph: DirectedGraph = generators::cycle(4);
+        let mut graphml = GraphMl::from_graph(&graph, true);
+        graphml.set_data(VertexIndex { index: 1 }, "label", "<1>".to_owned());
+        assert_eq!(graphml.edges.len(), 8);
+
+        let parsed = GraphMl::from_str(&graphml.to_string()).unwrap();
+        assert_eq!(parsed, graphml);
+        assert_eq!(parsed.to_graph::<DirectedGraph>(), graph);
+    }
+}
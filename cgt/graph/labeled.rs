@@ -0,0 +1,252 @@
+//! Graph with data attached to vertices and edges
+
+use crate::graph::{adjacency_matrix::undirected::UndirectedGraph, Graph, VertexIndex};
+use std::collections::BTreeMap;
+
+/// Graph with a label of type `V` on every vertex and a label of type `E` on every edge.
+///
+/// Edge labels are stored per direction. When adding an edge makes both directions adjacent, as
+/// in undirected graphs, both directions get the same label.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LabeledGraph<V, E, G = UndirectedGraph> {
+    graph: G,
+    vertex_labels: Vec<V>,
+    edge_labels: BTreeMap<(VertexIndex, VertexIndex), E>,
+}
+
+impl<V, E, G> LabeledGraph<V, E, G>
+where
+    G: Graph,
+    E: Clone,
+{
+    /// Create graph without any vertices
+    pub fn empty() -> Self {
+        Self {
+            graph: G::empty(0),
+            vertex_labels: Vec::new(),
+            edge_labels: BTreeMap::new(),
+        }
+    }
+
+    /// Create graph from unlabeled graph, where every existing edge gets the same label.
+    /// Returns `None` if `vertex_labels` and `graph` have conflicting sizes.
+    pub fn from_graph(graph: G, vertex_labels: Vec<V>, edge_label: &E) -> Option<Self> {
+        if vertex_labels.len() != graph.size() {
+            return None;
+        }
+
+        let mut edge_labels = BTreeMap::new();
+        for u in graph.vertices() {
+            for v in graph.adjacent_to(u) {
+                edge_labels.insert((u, v), edge_label.clone());
+            }
+        }
+
+        Some(Self {
+            graph,
+            vertex_labels,
+            edge_labels,
+        })
+    }
+
+    /// Underlying unlabeled graph
+    pub const fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// Number of vertices
+    pub fn size(&self) -> usize {
+        self.graph.size()
+    }
+
+    /// Labels of all vertices, in order
+    pub fn vertex_labels(&self) -> &[V] {
+        &self.vertex_labels
+    }
+
+    /// Label of a vertex
+    pub fn vertex_label(&self, vertex: VertexIndex) -> &V {
+        &self.vertex_labels[vertex.index]
+    }
+
+    /// Mutable label of a vertex
+    pub fn vertex_label_mut(&mut self, vertex: VertexIndex) -> &mut V {
+        &mut self.vertex_labels[vertex.index]
+    }
+
+    /// Label of an edge, or `None` if vertices are not adjacent
+    pub fn edge_label(&self, lhs_vertex: VertexIndex, rhs_vertex: VertexIndex) -> Option<&E> {
+        self.edge_labels.get(&(lhs_vertex, rhs_vertex))
+    }
+
+    /// Mutable label of an edge, or `None` if vertices are not adjacent. Note that in undirected
+    /// graphs only the label of the given direction is changed.
+    pub fn edge_label_mut(
+        &mut self,
+        lhs_vertex: VertexIndex,
+        rhs_vertex: VertexIndex,
+    ) -> Option<&mut E> {
+        self.edge_labels.get_mut(&(lhs_vertex, rhs_vertex))
+    }
+
+    /// Add a new disconnected vertex at the end of the graph
+    pub fn add_vertex(&mut self, label: V) -> VertexIndex {
+        self.vertex_labels.push(label);
+        self.graph.add_vertex()
+    }
+
+    /// Remove vertex with all its edges, returning its label. Indices of vertices after it are
+    /// shifted down.
+    pub fn remove_vertex(&mut self, vertex_to_remove: VertexIndex) -> V {
+        self.graph.remove_vertex(vertex_to_remove);
+        let shift = |vertex: VertexIndex| {
+            if vertex > vertex_to_remove {
+                VertexIndex {
+                    index: vertex.index - 1,
+                }
+            } else {
+                vertex
+            }
+        };
+        self.edge_labels = std::mem::take(&mut self.edge_labels)
+            .into_iter()
+            .filter(|((u, v), _)| *u != vertex_to_remove && *v != vertex_to_remove)
+            .map(|((u, v), label)| ((shift(u), shift(v)), label))
+            .collect();
+        self.vertex_labels.remove(vertex_to_remove.index)
+    }
+
+    /// Add edge with a label, replacing label of the edge if it already exists
+    pub fn add_edge(&mut self, lhs_vertex: VertexIndex, rhs_vertex: VertexIndex, label: E) {
+        // Reverse edge follows the edge iff graph is undirected
+        self.graph.connect(lhs_vertex, rhs_vertex, false);
+        let reverse_kept = self.graph.are_adjacent(rhs_vertex, lhs_vertex);
+        self.graph.connect(lhs_vertex, rhs_vertex, true);
+        if !reverse_kept && self.graph.are_adjacent(rhs_vertex, lhs_vertex) {
+            self.edge_labels
+                .insert((rhs_vertex, lhs_vertex), label.clone());
+        }
+        self.edge_labels.insert((lhs_vertex, rhs_vertex), label);
+    }
+
+    /// Remove edge, returning its label, or `None` if vertices were not adjacent
+    pub fn remove_edge(&mut self, lhs_vertex: VertexIndex, rhs_vertex: VertexIndex) -> Option<E> {
+        self.graph.connect(lhs_vertex, rhs_vertex, false);
+        if !self.graph.are_adjacent(rhs_vertex, lhs_vertex) {
+            self.edge_labels.remove(&(rhs_vertex, lhs_vertex));
+        }
+        self.edge_labels.remove(&(lhs_vertex, rhs_vertex))
+    }
+
+    /// Iterator over edges of the underlying graph with their labels
+    pub fn edges(&self) -> impl Iterator<Item = (VertexIndex, VertexIndex, &E)> + '_ {
+        self.graph
+            .edges()
+            .filter_map(|(u, v)| Some((u, v, self.edge_labels.get(&(u, v))?)))
+    }
+
+    /// Create a subgraph induced by given vertices, keeping their labels. Vertex `vertices[i]`
+    /// becomes vertex `i` of the new graph.
+    #[must_use]
+    pub fn induced_subgraph(&self, vertices: &[VertexIndex]) -> Self
+    where
+        V: Clone,
+    {
+        let mut new_indices = vec![None; self.size()];
+        for (index, v) in vertices.iter().enumerate() {
+            new_indices[v.index] = Some(VertexIndex { index });
+        }
+
+        Self {
+            graph: self.graph.induced_subgraph(vertices),
+            vertex_labels: vertices
+                .iter()
+                .map(|v| self.vertex_labels[v.index].clone())
+                .collect(),
+            edge_labels: self
+                .edge_labels
+                .iter()
+                .filter_map(|((u, v), label)| {
+                    Some((
+                        (new_indices[u.index]?, new_indices[v.index]?),
+                        label.clone(),
+                    ))
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{adjacency_list::directed::DirectedGraph, generators};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum EdgeColor {
+        Blue,
+        Red,
+    }
+
+    #[test]
+    fn undirected_labels() {
+        let mut graph: LabeledGraph<&str, EdgeColor> =
+            LabeledGraph::from_graph(generators::path(3), vec!["a", "b", "c"], &EdgeColor::Blue)
+                .unwrap();
+        let d = graph.add_vertex("d");
+        graph.add_edge(VertexIndex { index: 2 }, d, EdgeColor::Blue);
+        graph.add_edge(d, VertexIndex { index: 2 }, EdgeColor::Red);
+
+        assert_eq!(
+            graph.edge_label(d, VertexIndex { index: 2 }),
+            Some(&EdgeColor::Red)
+        );
+        assert_eq!(
+            graph
+                .edges()
+                .map(|(_, _, label)| *label)
+                .collect::<Vec<_>>(),
+            vec![EdgeColor::Blue, EdgeColor::Blue, EdgeColor::Red]
+        );
+
+        assert_eq!(graph.remove_vertex(VertexIndex { index: 0 }), "a");
+        assert_eq!(graph.vertex_labels(), &["b", "c", "d"]);
+        assert_eq!(
+            graph.edge_label(VertexIndex { index: 1 }, VertexIndex { index: 2 }),
+            Some(&EdgeColor::Red)
+        );
+        assert_eq!(
+            graph.remove_edge(VertexIndex { index: 0 }, VertexIndex { index: 1 }),
+            Some(EdgeColor::Blue)
+        );
+        assert_eq!(
+            graph.edge_label(VertexIndex { index: 1 }, VertexIndex { index: 0 }),
+            None
+        );
+
+        let subgraph =
+            graph.induced_subgraph(&[VertexIndex { index: 2 }, VertexIndex { index: 1 }]);
+        assert_eq!(subgraph.vertex_labels(), &["d", "c"]);
+        assert_eq!(
+            subgraph.edge_label(VertexIndex { index: 0 }, VertexIndex { index: 1 }),
+            Some(&EdgeColor::Red)
+        );
+    }
+
+    #[test]
+    fn directed_labels() {
+        let mut graph: LabeledGraph<(), EdgeColor, DirectedGraph> =
+            LabeledGraph::from_graph(DirectedGraph::empty(2), vec![(), ()], &EdgeColor::Blue)
+                .unwrap();
+        let (u, v) = (VertexIndex { index: 0 }, VertexIndex { index: 1 });
+        graph.add_edge(u, v, EdgeColor::Blue);
+        graph.add_edge(v, u, EdgeColor::Red);
+        assert_eq!(graph.edge_label(u, v), Some(&EdgeColor::Blue));
+        assert_eq!(graph.edge_label(v, u), Some(&EdgeColor::Red));
+
+        graph.remove_edge(u, v);
+        assert_eq!(graph.edge_label(u, v), None);
+        assert_eq!(graph.edge_label(v, u), Some(&EdgeColor::Red));
+    }
+}
@@ -67,3 +67,43 @@ impl SpringEmbedder {
         }
     }
 }
+
+/// Lay out vertices of size `vertex_radius` with a [`SpringEmbedder`], starting from a circle.
+/// Returns positions of vertices and side of the square that contains them.
+///
+/// Layout is deterministic, so the same graph is always drawn the same way.
+pub fn circle_spring_layout<G>(graph: &G, vertex_radius: f32) -> (Vec<V2f>, f32)
+where
+    G: Graph,
+{
+    let size = graph.size();
+    let radius = vertex_radius * (size as f32 + 4.0) * 0.5;
+    let mut positions = (0..size)
+        .map(|index| {
+            let angle = 2.0 * core::f32::consts::PI * index as f32 / size as f32;
+            V2f {
+                x: (radius - vertex_radius).mul_add(f32::cos(angle), radius),
+                y: (radius - vertex_radius).mul_add(f32::sin(angle), radius),
+            }
+        })
+        .collect::<Vec<_>>();
+    let spring_embedder = SpringEmbedder {
+        cooling_rate: 0.999,
+        c_attractive: 1.0,
+        c_repulsive: 250.0,
+        ideal_spring_length: 40.0,
+        iterations: 1024,
+        bounds: Some((
+            V2f {
+                x: vertex_radius,
+                y: vertex_radius,
+            },
+            V2f {
+                x: 2.0f32.mul_add(radius, -vertex_radius),
+                y: 2.0f32.mul_add(radius, -vertex_radius),
+            },
+        )),
+    };
+    spring_embedder.layout(graph, &mut positions);
+    (positions, 2.0 * radius)
+}
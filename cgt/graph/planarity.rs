@@ -0,0 +1,492 @@
+//! Planarity testing and combinatorial embeddings of undirected graphs.
+//!
+//! Every biconnected component is embedded using the algorithm of Demoucron, Malgrange and
+//! Pertuiset. Starting from a cycle, we repeatedly take a fragment of the graph that is not
+//! embedded yet and embed a path through it into a face that contains all vertices the fragment
+//! is attached to. Graph is planar if and only if such face can always be found. Embeddings of
+//! the components are then glued together at cut vertices.
+
+use crate::graph::{Graph, VertexIndex};
+use std::collections::{HashSet, VecDeque};
+
+/// Combinatorial embedding of a planar graph, given by the cyclic order of neighbours around
+/// every vertex (rotation system)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlanarEmbedding {
+    rotations: Vec<Vec<VertexIndex>>,
+}
+
+/// Part of the graph that is not embedded yet
+struct Fragment {
+    /// Embedded vertices the fragment is attached to
+    contacts: Vec<usize>,
+
+    /// Vertices of the fragment that are not embedded yet. Empty if fragment is a single edge
+    inner: Vec<usize>,
+}
+
+impl PlanarEmbedding {
+    /// Compute planar embedding of a graph, treating every arc as an undirected edge. Loops are
+    /// ignored.
+    ///
+    /// Returns `None` if graph is not planar.
+    pub fn new<G>(graph: &G) -> Option<Self>
+    where
+        G: Graph,
+    {
+        let size = graph.size();
+        let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); size];
+        for u in graph.vertices() {
+            for v in graph.adjacent_to(u) {
+                if u != v {
+                    adjacency[u.index].push(v.index);
+                    adjacency[v.index].push(u.index);
+                }
+            }
+        }
+        for adjacent in &mut adjacency {
+            adjacent.sort_unstable();
+            adjacent.dedup();
+        }
+
+        let edges = adjacency.iter().map(Vec::len).sum::<usize>() / 2;
+        if size >= 3 && edges > 3 * size - 6 {
+            return None;
+        }
+
+        let mut rotations = vec![Vec::new(); size];
+        for block in biconnected_components(&adjacency) {
+            let mut block_adjacency = vec![Vec::new(); size];
+            for (u, v) in &block {
+                block_adjacency[*u].push(*v);
+                block_adjacency[*v].push(*u);
+            }
+            let mut vertices: Vec<usize> = block.iter().flat_map(|(u, v)| [*u, *v]).collect();
+            vertices.sort_unstable();
+            vertices.dedup();
+
+            let faces = embed_biconnected(&block_adjacency, &vertices, block.len())?;
+
+            // In every face `.., u, v, w, ..` vertex `w` follows `u` in the rotation of `v`
+            let mut next = vec![None; size];
+            for v in vertices {
+                for face in &faces {
+                    if let Some(idx) = face.iter().position(|x| *x == v) {
+                        let u = face[(idx + face.len() - 1) % face.len()];
+                        let w = face[(idx + 1) % face.len()];
+                        next[u] = Some(w);
+                    }
+                }
+
+                let first = block_adjacency[v][0];
+                let mut u = first;
+                loop {
+                    rotations[v].push(VertexIndex { index: u });
+                    u = next[u].expect("Every neighbour appears in some face");
+                    if u == first {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Some(Self { rotations })
+    }
+
+    /// Neighbours of a vertex in the cyclic order around it
+    pub fn rotation(&self, vertex: VertexIndex) -> &[VertexIndex] {
+        &self.rotations[vertex.index]
+    }
+
+    /// Faces of the embedding, as closed walks along their boundaries. Isolated vertices are not
+    /// part of any face.
+    pub fn faces(&self) -> Vec<Vec<VertexIndex>> {
+        let mut visited: HashSet<(VertexIndex, VertexIndex)> = HashSet::new();
+        let mut faces = Vec::new();
+
+        for (u, rotation) in self.rotations.iter().enumerate() {
+            let u = VertexIndex { index: u };
+            for &v in rotation {
+                if visited.contains(&(u, v)) {
+                    continue;
+                }
+
+                let mut face = Vec::new();
+                let (mut a, mut b) = (u, v);
+                while visited.insert((a, b)) {
+                    face.push(a);
+                    let rotation = &self.rotations[b.index];
+                    let idx = rotation.iter().position(|x| *x == a).unwrap();
+                    (a, b) = (b, rotation[(idx + 1) % rotation.len()]);
+                }
+                faces.push(face);
+            }
+        }
+
+        faces
+    }
+}
+
+/// Split edges of the graph into biconnected components
+fn biconnected_components(adjacency: &[Vec<usize>]) -> Vec<Vec<(usize, usize)>> {
+    struct Search<'graph> {
+        adjacency: &'graph [Vec<usize>],
+        discovered: Vec<Option<usize>>,
+        low: Vec<usize>,
+        time: usize,
+        stack: Vec<(usize, usize)>,
+        components: Vec<Vec<(usize, usize)>>,
+    }
+
+    impl Search<'_> {
+        fn visit(&mut self, u: usize, parent: Option<usize>) {
+            self.discovered[u] = Some(self.time);
+            self.low[u] = self.time;
+            self.time += 1;
+
+            for &v in &self.adjacency[u] {
+                match self.discovered[v] {
+                    None => {
+                        self.stack.push((u, v));
+                        self.visit(v, Some(u));
+                        self.low[u] = self.low[u].min(self.low[v]);
+                        if Some(self.low[v]) >= self.discovered[u] {
+                            let mut component = Vec::new();
+                            while let Some(edge) = self.stack.pop() {
+                                component.push(edge);
+                                if edge == (u, v) {
+                                    break;
+                                }
+                            }
+                            self.components.push(component);
+                        }
+                    }
+                    Some(discovered)
+                        if Some(v) != parent && Some(discovered) < self.discovered[u] =>
+                    {
+                        self.stack.push((u, v));
+                        self.low[u] = self.low[u].min(discovered);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+
+    let mut search = Search {
+        adjacency,
+        discovered: vec![None; adjacency.len()],
+        low: vec![0; adjacency.len()],
+        time: 0,
+        stack: Vec::new(),
+        components: Vec::new(),
+    };
+    for v in 0..adjacency.len() {
+        if search.discovered[v].is_none() {
+            search.visit(v, None);
+        }
+    }
+    search.components
+}
+
+/// Embed biconnected graph, returning its faces. Vertices outside of the component must have
+/// no neighbours in `adjacency`.
+fn embed_biconnected(
+    adjacency: &[Vec<usize>],
+    vertices: &[usize],
+    edges: usize,
+) -> Option<Vec<Vec<usize>>> {
+    // Single edge has one face, going there and back
+    if edges == 1 {
+        return Some(vec![vertices.to_vec()]);
+    }
+
+    let mut embedded = vec![false; adjacency.len()];
+    let mut embedded_edges: HashSet<(usize, usize)> = HashSet::new();
+
+    // Component with at least two edges has a cycle through every edge
+    let u = vertices[0];
+    let v = adjacency[u][0];
+    let mut cycle = find_path(adjacency, v, u, |x| x != u)?;
+    cycle.push(v);
+    embed_path(&cycle, &mut embedded, &mut embedded_edges);
+    cycle.pop();
+    let mut reversed = cycle.clone();
+    reversed.reverse();
+    let mut faces = vec![cycle, reversed];
+
+    while embedded_edges.len() < edges {
+        let fragments = fragments(adjacency, vertices, &embedded, &embedded_edges);
+
+        let mut chosen = None;
+        for fragment in &fragments {
+            let admissible: Vec<usize> = faces
+                .iter()
+                .enumerate()
+                .filter(|(_, face)| fragment.contacts.iter().all(|c| face.contains(c)))
+                .map(|(idx, _)| idx)
+                .collect();
+            match admissible.as_slice() {
+                [] => return None,
+                [face] => {
+                    chosen = Some((fragment, *face));
+                    break;
+                }
+                [face, ..] => {
+                    chosen = chosen.or(Some((fragment, *face)));
+                }
+            }
+        }
+        let (fragment, face_idx) = chosen?;
+
+        let a = fragment.contacts[0];
+        let b = fragment.contacts[1];
+        let path = if fragment.inner.is_empty() {
+            vec![a, b]
+        } else {
+            let mut inner = vec![false; adjacency.len()];
+            for v in &fragment.inner {
+                inner[*v] = true;
+            }
+            find_path(adjacency, a, b, |x| inner[x])?
+        };
+        embed_path(&path, &mut embedded, &mut embedded_edges);
+
+        // Path splits the face in two, each of them going along the path in other direction
+        let face = &faces[face_idx];
+        let a_idx = face.iter().position(|x| *x == a)?;
+        let b_idx = face.iter().position(|x| *x == b)?;
+        let walk = |from: usize, to: usize| {
+            let mut walk = Vec::new();
+            let mut idx = from;
+            loop {
+                walk.push(face[idx]);
+                if idx == to {
+                    break walk;
+                }
+                idx = (idx + 1) % face.len();
+            }
+        };
+        let interior = &path[1..path.len() - 1];
+        let mut lhs = walk(a_idx, b_idx);
+        lhs.extend(interior.iter().rev());
+        let mut rhs = walk(b_idx, a_idx);
+        rhs.extend(interior);
+        faces[face_idx] = lhs;
+        faces.push(rhs);
+    }
+
+    Some(faces)
+}
+
+fn embed_path(path: &[usize], embedded: &mut [bool], embedded_edges: &mut HashSet<(usize, usize)>) {
+    for v in path {
+        embedded[*v] = true;
+    }
+    for edge in path.windows(2) {
+        embedded_edges.insert((edge[0].min(edge[1]), edge[0].max(edge[1])));
+    }
+}
+
+/// Find shortest path from `from` to `to` with at least one intermediate vertex, where all
+/// intermediate vertices satisfy `through`
+fn find_path<F>(adjacency: &[Vec<usize>], from: usize, to: usize, through: F) -> Option<Vec<usize>>
+where
+    F: Fn(usize) -> bool,
+{
+    let mut parent = vec![None; adjacency.len()];
+    let mut queue = VecDeque::from([from]);
+    parent[from] = Some(from);
+
+    while let Some(u) = queue.pop_front() {
+        for &v in &adjacency[u] {
+            if parent[v].is_some() {
+                continue;
+            }
+            if v == to && u != from {
+                let mut path = vec![to, u];
+                let mut current = u;
+                while current != from {
+                    current = parent[current].unwrap();
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if through(v) {
+                parent[v] = Some(u);
+                queue.push_back(v);
+            }
+        }
+    }
+    None
+}
+
+/// Find all fragments of the graph relative to the embedded subgraph
+fn fragments(
+    adjacency: &[Vec<usize>],
+    vertices: &[usize],
+    embedded: &[bool],
+    embedded_edges: &HashSet<(usize, usize)>,
+) -> Vec<Fragment> {
+    let mut fragments = Vec::new();
+
+    for &u in vertices {
+        if !embedded[u] {
+            continue;
+        }
+        for &v in &adjacency[u] {
+            if embedded[v] && u < v && !embedded_edges.contains(&(u, v)) {
+                fragments.push(Fragment {
+                    contacts: vec![u, v],
+                    inner: Vec::new(),
+                });
+            }
+        }
+    }
+
+    let mut seen = vec![false; adjacency.len()];
+    for &start in vertices {
+        if embedded[start] || seen[start] {
+            continue;
+        }
+
+        let mut inner = Vec::new();
+        let mut contacts = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        seen[start] = true;
+        while let Some(u) = queue.pop_front() {
+            inner.push(u);
+            for &v in &adjacency[u] {
+                if embedded[v] {
+                    contacts.push(v);
+                } else if !seen[v] {
+                    seen[v] = true;
+                    queue.push_back(v);
+                }
+            }
+        }
+        contacts.sort_unstable();
+        contacts.dedup();
+        fragments.push(Fragment { contacts, inner });
+    }
+
+    fragments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{adjacency_matrix::undirected::UndirectedGraph, generators};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn check_euler(graph: &UndirectedGraph) {
+        let embedding = PlanarEmbedding::new(graph).unwrap();
+        let vertices = graph.size() as isize;
+        let edges = graph.edges().count() as isize;
+        let faces = embedding.faces().len() as isize;
+        assert_eq!(vertices - edges + faces, 2, "{graph}");
+    }
+
+    #[test]
+    fn planar_graphs() {
+        check_euler(&generators::complete(4));
+        check_euler(&generators::cycle(7));
+        check_euler(&generators::grid(4, 3));
+        check_euler(&generators::star(5));
+        check_euler(&generators::complete_bipartite(2, 5));
+
+        // Two triangles sharing a vertex
+        let bowtie = UndirectedGraph::from_edges(
+            5,
+            &[
+                (VertexIndex { index: 0 }, VertexIndex { index: 1 }),
+                (VertexIndex { index: 1 }, VertexIndex { index: 2 }),
+                (VertexIndex { index: 2 }, VertexIndex { index: 0 }),
+                (VertexIndex { index: 2 }, VertexIndex { index: 3 }),
+                (VertexIndex { index: 3 }, VertexIndex { index: 4 }),
+                (VertexIndex { index: 4 }, VertexIndex { index: 2 }),
+            ],
+        );
+        check_euler(&bowtie);
+
+        assert_eq!(
+            PlanarEmbedding::new(&UndirectedGraph::empty(3)).map(|e| e.faces().len()),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn non_planar_graphs() {
+        assert!(!generators::complete::<UndirectedGraph>(5).is_planar());
+        assert!(!generators::complete_bipartite::<UndirectedGraph>(3, 3).is_planar());
+
+        // K(3,3) subdivision that passes the edge count check
+        let mut subdivided: UndirectedGraph = generators::complete_bipartite(3, 3);
+        let v = subdivided.add_vertex();
+        subdivided.connect(VertexIndex { index: 0 }, VertexIndex { index: 3 }, false);
+        subdivided.connect(VertexIndex { index: 0 }, v, true);
+        subdivided.connect(v, VertexIndex { index: 3 }, true);
+        assert!(!subdivided.is_planar());
+
+        // Petersen graph
+        let mut petersen = UndirectedGraph::empty(10);
+        for i in 0..5 {
+            let (outer, inner) = (VertexIndex { index: i }, VertexIndex { index: i + 5 });
+            petersen.connect(outer, VertexIndex { index: (i + 1) % 5 }, true);
+            petersen.connect(outer, inner, true);
+            petersen.connect(
+                inner,
+                VertexIndex {
+                    index: 5 + (i + 2) % 5,
+                },
+                true,
+            );
+        }
+        assert!(!petersen.is_planar());
+    }
+
+    #[test]
+    fn random_graphs() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        // Triangulations, built by placing new vertex in a random triangle
+        for size in 4..30 {
+            let mut graph: UndirectedGraph = generators::complete(3);
+            let mut triangles = vec![[0, 1, 2], [0, 1, 2]];
+            for _ in 3..size {
+                let v = graph.add_vertex();
+                let [a, b, c] = triangles.swap_remove(rng.gen_range(0..triangles.len()));
+                for u in [a, b, c] {
+                    graph.connect(v, VertexIndex { index: u }, true);
+                }
+                triangles.extend([[a, b, v.index], [b, c, v.index], [a, c, v.index]]);
+            }
+            check_euler(&graph);
+        }
+
+        // Subgraphs of a planar graph are planar
+        let grid: UndirectedGraph = generators::grid(5, 5);
+        for _ in 0..20 {
+            let subgraph: UndirectedGraph = generators::random_gnp(25, 0.8, &mut rng);
+            let mut subgraph = subgraph;
+            for u in grid.vertices() {
+                for v in grid.vertices() {
+                    if !grid.are_adjacent(u, v) {
+                        subgraph.connect(u, v, false);
+                    }
+                }
+            }
+            assert!(subgraph.is_planar());
+        }
+
+        for size in 3..12 {
+            for _ in 0..10 {
+                let graph: UndirectedGraph = generators::random_gnp(size, 0.4, &mut rng);
+                if graph.is_connected() && graph.is_planar() {
+                    check_euler(&graph);
+                }
+            }
+        }
+    }
+}
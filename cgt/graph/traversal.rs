@@ -0,0 +1,161 @@
+//! Breadth-first and depth-first graph traversals.
+//!
+//! Both traversals follow edges in their direction and visit only vertices reachable from the
+//! starting vertex.
+
+use crate::graph::{Graph, VertexIndex};
+use std::collections::VecDeque;
+
+/// Breadth-first traversal, see [`Graph::bfs_from`]
+#[derive(Debug, Clone)]
+pub struct Bfs<'graph, G> {
+    graph: &'graph G,
+    seen: Vec<bool>,
+    queue: VecDeque<(VertexIndex, usize)>,
+}
+
+impl<'graph, G> Bfs<'graph, G>
+where
+    G: Graph,
+{
+    pub(crate) fn new(graph: &'graph G, start: VertexIndex) -> Self {
+        let mut seen = vec![false; graph.size()];
+        seen[start.index] = true;
+        let mut queue = VecDeque::with_capacity(graph.size());
+        queue.push_back((start, 0));
+        Self { graph, seen, queue }
+    }
+}
+
+impl<G> Iterator for Bfs<'_, G>
+where
+    G: Graph,
+{
+    type Item = (VertexIndex, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (v, distance) = self.queue.pop_front()?;
+        for u in self.graph.adjacent_to(v) {
+            if !self.seen[u.index] {
+                self.seen[u.index] = true;
+                self.queue.push_back((u, distance + 1));
+            }
+        }
+        Some((v, distance))
+    }
+}
+
+/// Depth-first traversal in preorder, see [`Graph::dfs_from`]
+#[derive(Debug, Clone)]
+pub struct Dfs<'graph, G> {
+    graph: &'graph G,
+    seen: Vec<bool>,
+    stack: Vec<VertexIndex>,
+}
+
+impl<'graph, G> Dfs<'graph, G>
+where
+    G: Graph,
+{
+    pub(crate) fn new(graph: &'graph G, start: VertexIndex) -> Self {
+        Self {
+            graph,
+            seen: vec![false; graph.size()],
+            stack: vec![start],
+        }
+    }
+}
+
+impl<G> Iterator for Dfs<'_, G>
+where
+    G: Graph,
+{
+    type Item = VertexIndex;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let v = self.stack.pop()?;
+            if self.seen[v.index] {
+                continue;
+            }
+            self.seen[v.index] = true;
+
+            // Reversed, so neighbors are visited in the order of adjacency iterator
+            let stack_len = self.stack.len();
+            self.stack
+                .extend(self.graph.adjacent_to(v).filter(|u| !self.seen[u.index]));
+            self.stack[stack_len..].reverse();
+            return Some(v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{
+        adjacency_list::directed::DirectedGraph, adjacency_matrix::undirected::UndirectedGraph,
+        generators, Graph, VertexIndex,
+    };
+
+    fn indices<I>(vertices: I) -> Vec<usize>
+    where
+        I: Iterator<Item = VertexIndex>,
+    {
+        vertices.map(|v| v.index).collect()
+    }
+
+    #[test]
+    fn traversal_order() {
+        // 0 - 1 - 3
+        //  \
+        //   2 - 4
+        let tree = UndirectedGraph::from_edges(
+            5,
+            &[
+                (VertexIndex { index: 0 }, VertexIndex { index: 1 }),
+                (VertexIndex { index: 0 }, VertexIndex { index: 2 }),
+                (VertexIndex { index: 1 }, VertexIndex { index: 3 }),
+                (VertexIndex { index: 2 }, VertexIndex { index: 4 }),
+            ],
+        );
+        let start = VertexIndex { index: 0 };
+        assert_eq!(
+            tree.bfs_from(start)
+                .map(|(v, distance)| (v.index, distance))
+                .collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]
+        );
+        assert_eq!(indices(tree.dfs_from(start)), vec![0, 1, 3, 2, 4]);
+    }
+
+    #[test]
+    fn distances() {
+        let cycle: UndirectedGraph = generators::cycle(6);
+        assert_eq!(cycle.diameter(), Some(3));
+        assert_eq!(cycle.eccentricity(VertexIndex { index: 2 }), Some(3));
+        assert_eq!(cycle.distance_matrix()[1][5], Some(2));
+
+        let grid: UndirectedGraph = generators::grid(4, 3);
+        assert_eq!(grid.diameter(), Some(5));
+
+        let path: UndirectedGraph = generators::path(2);
+        let disconnected = &path + &path;
+        assert_eq!(disconnected.diameter(), None);
+        assert_eq!(disconnected.distance_matrix()[0][2], None);
+
+        // Directed path 0 -> 1 -> 2
+        let directed = DirectedGraph::from_edges(
+            3,
+            &[
+                (VertexIndex { index: 0 }, VertexIndex { index: 1 }),
+                (VertexIndex { index: 1 }, VertexIndex { index: 2 }),
+            ],
+        );
+        assert_eq!(directed.eccentricity(VertexIndex { index: 0 }), Some(2));
+        assert_eq!(directed.eccentricity(VertexIndex { index: 1 }), None);
+        assert_eq!(
+            indices(directed.dfs_from(VertexIndex { index: 1 })),
+            vec![1, 2]
+        );
+    }
+}
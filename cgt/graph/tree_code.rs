@@ -0,0 +1,235 @@
+//! Canonical codes of trees, computed with the Aho-Hopcroft-Ullman (AHU) algorithm.
+//!
+//! Code of a rooted tree is a sequence of balanced parentheses, where code of a vertex is its
+//! children codes in sorted order wrapped in a pair of parentheses. Two trees are isomorphic iff
+//! their codes are equal, and computing a code takes `O(n log n)` time, so it is much cheaper
+//! than [`CanonicalLabeling`](crate::graph::canonical_labeling::CanonicalLabeling) when only
+//! trees are considered.
+//!
+//! Instead of sorting codes directly, children are ordered by ranks computed level by level,
+//! bottom-up, which gives the same order without comparing long strings.
+
+use crate::graph::{Graph, VertexIndex};
+use std::fmt::{self, Display, Write};
+
+/// Canonical code of a tree
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreeCode {
+    /// `true` for opening and `false` for closing parenthesis
+    code: Vec<bool>,
+}
+
+/// Neighbors of every vertex, with edge directions ignored, or `None` if graph is not a tree
+fn tree_neighbors<G>(graph: &G) -> Option<Vec<Vec<usize>>>
+where
+    G: Graph,
+{
+    let mut neighbors = vec![Vec::new(); graph.size()];
+    let mut edges = 0;
+    for (u, v) in graph.edges() {
+        if u == v {
+            return None;
+        }
+        if u > v && graph.are_adjacent(v, u) {
+            continue;
+        }
+        neighbors[u.index].push(v.index);
+        neighbors[v.index].push(u.index);
+        edges += 1;
+    }
+
+    (edges + 1 == graph.size() && graph.size() > 0 && {
+        let mut seen = vec![false; graph.size()];
+        let mut stack = vec![0];
+        seen[0] = true;
+        while let Some(v) = stack.pop() {
+            for &u in &neighbors[v] {
+                if !seen[u] {
+                    seen[u] = true;
+                    stack.push(u);
+                }
+            }
+        }
+        seen.into_iter().all(|b| b)
+    })
+    .then_some(neighbors)
+}
+
+impl TreeCode {
+    /// Compute code of a tree rooted at `root`. Edge directions are ignored.
+    ///
+    /// Returns `None` if graph is not a tree.
+    pub fn rooted<G>(graph: &G, root: VertexIndex) -> Option<Self>
+    where
+        G: Graph,
+    {
+        let neighbors = tree_neighbors(graph)?;
+        Some(Self::from_neighbors(&neighbors, root.index))
+    }
+
+    /// Compute code of an unrooted tree, by rooting it at its center. If tree has two centers,
+    /// the smaller code is chosen. Edge directions are ignored.
+    ///
+    /// Returns `None` if graph is not a tree.
+    pub fn unrooted<G>(graph: &G) -> Option<Self>
+    where
+        G: Graph,
+    {
+        let neighbors = tree_neighbors(graph)?;
+
+        // Remove leaves layer by layer until at most two vertices remain
+        let mut degrees: Vec<usize> = neighbors.iter().map(Vec::len).collect();
+        let mut leaves: Vec<usize> = (0..neighbors.len()).filter(|v| degrees[*v] <= 1).collect();
+        let mut remaining = neighbors.len();
+        while remaining > 2 {
+            remaining -= leaves.len();
+            let mut next_leaves = Vec::new();
+            for leaf in leaves {
+                for &v in &neighbors[leaf] {
+                    degrees[v] -= 1;
+                    if degrees[v] == 1 {
+                        next_leaves.push(v);
+                    }
+                }
+            }
+            leaves = next_leaves;
+        }
+
+        leaves
+            .into_iter()
+            .map(|center| Self::from_neighbors(&neighbors, center))
+            .min()
+    }
+
+    fn from_neighbors(neighbors: &[Vec<usize>], root: usize) -> Self {
+        // Breadth-first order, so vertices are grouped by depth
+        let mut parent = vec![usize::MAX; neighbors.len()];
+        let mut depth = vec![0; neighbors.len()];
+        let mut order = Vec::with_capacity(neighbors.len());
+        parent[root] = root;
+        order.push(root);
+        let mut idx = 0;
+        while idx < order.len() {
+            let v = order[idx];
+            idx += 1;
+            for &u in &neighbors[v] {
+                if parent[u] == usize::MAX {
+                    parent[u] = v;
+                    depth[u] = depth[v] + 1;
+                    order.push(u);
+                }
+            }
+        }
+
+        let parent = &parent;
+        let children = |v: usize| {
+            neighbors[v]
+                .iter()
+                .copied()
+                .filter(move |u| parent[*u] == v)
+        };
+
+        // Rank vertices of every level by sorted ranks of their children. Ranks at each level
+        // preserve the order of children codes, so we never compare the codes.
+        let mut rank = vec![0; neighbors.len()];
+        let mut level_end = order.len();
+        while level_end > 0 {
+            let level_depth = depth[order[level_end - 1]];
+            let level_start = order[..level_end]
+                .iter()
+                .rposition(|v| depth[*v] != level_depth)
+                .map_or(0, |idx| idx + 1);
+
+            let mut keys: Vec<(Vec<usize>, usize)> = order[level_start..level_end]
+                .iter()
+                .map(|&v| {
+                    let mut key: Vec<usize> = children(v).map(|u| rank[u]).collect();
+                    key.sort_unstable();
+                    (key, v)
+                })
+                .collect();
+            keys.sort_unstable();
+
+            let mut current_rank = 0;
+            for idx in 0..keys.len() {
+                if idx > 0 && keys[idx - 1].0 != keys[idx].0 {
+                    current_rank += 1;
+                }
+                rank[keys[idx].1] = current_rank;
+            }
+            level_end = level_start;
+        }
+
+        // Write parentheses, visiting children in the order of their ranks
+        let mut code = Vec::with_capacity(2 * neighbors.len());
+        let mut stack = vec![Some(root)];
+        while let Some(entry) = stack.pop() {
+            match entry {
+                Some(v) => {
+                    code.push(true);
+                    stack.push(None);
+                    let mut vertex_children: Vec<usize> = children(v).collect();
+                    vertex_children.sort_unstable_by_key(|u| std::cmp::Reverse(rank[*u]));
+                    stack.extend(vertex_children.into_iter().map(Some));
+                }
+                None => code.push(false),
+            }
+        }
+
+        Self { code }
+    }
+}
+
+impl Display for TreeCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &open in &self.code {
+            f.write_char(if open { '(' } else { ')' })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{adjacency_matrix::undirected::UndirectedGraph, generators};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn rooted_codes() {
+        let path: UndirectedGraph = generators::path(3);
+        assert_eq!(
+            TreeCode::rooted(&path, VertexIndex { index: 0 })
+                .unwrap()
+                .to_string(),
+            "((()))"
+        );
+        assert_eq!(
+            TreeCode::rooted(&path, VertexIndex { index: 1 })
+                .unwrap()
+                .to_string(),
+            "(()())"
+        );
+
+        let cycle: UndirectedGraph = generators::cycle(3);
+        assert_eq!(TreeCode::unrooted(&cycle), None);
+        assert_eq!(TreeCode::unrooted(&(&path + &path)), None);
+    }
+
+    #[test]
+    fn unrooted_codes_match_isomorphism() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let trees: Vec<UndirectedGraph> = (0..40)
+            .map(|_| generators::random_tree(7, &mut rng))
+            .collect();
+        for lhs in &trees {
+            for rhs in &trees {
+                assert_eq!(
+                    TreeCode::unrooted(lhs) == TreeCode::unrooted(rhs),
+                    lhs.is_isomorphic_to(rhs)
+                );
+            }
+        }
+    }
+}
@@ -1,7 +1,13 @@
 //! Finite grids
 
-use std::{collections::VecDeque, fmt::Write};
-
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use std::fmt::Write;
+
+pub mod bit_grid;
+pub mod fixed_grid;
+pub mod hex_grid;
+pub mod masked_grid;
 pub mod small_bit_grid;
 pub mod vec_grid;
 
@@ -48,44 +54,231 @@ pub trait FiniteGrid: Grid + Sized {
         Ok(())
     }
 
+    /// Rotate grid 90° clockwise
+    #[must_use]
+    fn rotate90(&self) -> Self {
+        Symmetry::Rotate90.apply_tilewise(self)
+    }
+
+    /// Flip grid across the horizontal axis, i.e. reverse the order of rows
+    #[must_use]
+    fn flip_horizontal(&self) -> Self {
+        Symmetry::FlipHorizontal.apply_tilewise(self)
+    }
+
+    /// Flip grid across the vertical axis, i.e. reverse the order of columns
+    #[must_use]
+    fn flip_vertical(&self) -> Self {
+        Symmetry::FlipVertical.apply_tilewise(self)
+    }
+
+    /// Flip grid across the main diagonal, i.e. rows become columns
+    #[must_use]
+    fn transpose(&self) -> Self {
+        Symmetry::Transpose.apply_tilewise(self)
+    }
+
     /// Parse grid from string following notation from [`Self::display`]
     fn parse(input: &str) -> Option<Self>
+    where
+        Self::Item: CharTile + Default,
+    {
+        Self::try_parse(input).ok()
+    }
+
+    /// Parse grid from string following notation from [`Self::display`], reporting why the
+    /// input is invalid
+    ///
+    /// # Errors
+    /// - Rows have different lengths
+    /// - Input contains character that is not a tile
+    /// - Grid is too large
+    fn try_parse(input: &str) -> Result<Self, ParseGridError>
     where
         Self::Item: CharTile + Default,
     {
         let row_separator = '|';
-        let width = input.split(row_separator).next()?.len() as u8;
-        let height = input.chars().filter(|c| *c == row_separator).count() as u8 + 1;
-
-        let mut grid = Self::filled(width, height, Default::default())?;
-        let mut x = 0;
-        let mut y = 0;
-
-        for chr in input.chars() {
-            if chr == row_separator {
-                if x == width {
-                    x = 0;
-                    y += 1;
-                    continue;
-                }
-                // Not a rectangle
-                return None;
+        let rows = input.split(row_separator).collect::<Vec<_>>();
+        let width = rows[0].chars().count();
+        let too_large = || ParseGridError::TooLarge {
+            width,
+            height: rows.len(),
+        };
+
+        let mut grid = Self::filled(
+            u8::try_from(width).map_err(|_| too_large())?,
+            u8::try_from(rows.len()).map_err(|_| too_large())?,
+            Default::default(),
+        )
+        .ok_or_else(too_large)?;
+
+        for (y, row) in rows.iter().enumerate() {
+            let found = row.chars().count();
+            if found != width {
+                return Err(ParseGridError::NotRectangular {
+                    row: y,
+                    expected: width,
+                    found,
+                });
             }
-
-            if x >= width {
-                return None;
+            for (x, chr) in row.chars().enumerate() {
+                let value =
+                    Self::Item::char_to_tile(chr).ok_or(ParseGridError::InvalidTile(chr))?;
+                grid.set(x as u8, y as u8, value);
             }
+        }
+
+        Ok(grid)
+    }
+}
+
+/// Error returned when grid cannot be parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum ParseGridError {
+    /// Rows of the grid have different lengths
+    #[error("row {row} has {found} tiles, but the first row has {expected}")]
+    NotRectangular {
+        /// Index of the first row that has different length
+        row: usize,
+
+        /// Length of the first row
+        expected: usize,
+
+        /// Length of the row
+        found: usize,
+    },
+
+    /// Character does not represent any tile
+    #[error("invalid tile '{0}'")]
+    InvalidTile(char),
+
+    /// Grid does not fit in the grid type
+    #[error("grid of size {width}x{height} is too large")]
+    TooLarge {
+        /// Number of columns
+        width: usize,
+
+        /// Number of rows
+        height: usize,
+    },
+}
+
+/// Symmetry of a rectangular grid, i.e. an element of the dihedral group of a square
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Symmetry {
+    /// Grid is left unchanged
+    Identity,
+
+    /// Rotation by 90° clockwise
+    Rotate90,
 
-            let value = Self::Item::char_to_tile(chr)?;
-            grid.set(x, y, value);
-            x += 1;
+    /// Rotation by 180°
+    Rotate180,
+
+    /// Rotation by 270° clockwise
+    Rotate270,
+
+    /// Mirror image across the vertical axis, i.e. columns are reversed
+    FlipVertical,
+
+    /// Mirror image across the horizontal axis, i.e. rows are reversed
+    FlipHorizontal,
+
+    /// Mirror image across the main diagonal, i.e. rows become columns
+    Transpose,
+
+    /// Mirror image across the anti-diagonal
+    AntiTranspose,
+}
+
+impl Symmetry {
+    /// All symmetries of a grid
+    pub const ALL: [Self; 8] = [
+        Self::Identity,
+        Self::Rotate90,
+        Self::Rotate180,
+        Self::Rotate270,
+        Self::FlipVertical,
+        Self::FlipHorizontal,
+        Self::Transpose,
+        Self::AntiTranspose,
+    ];
+
+    /// Symmetries that keep rows as rows and columns as columns, so they don't change the
+    /// orientation of pieces like dominoes
+    pub const ORIENTATION_PRESERVING: [Self; 4] = [
+        Self::Identity,
+        Self::Rotate180,
+        Self::FlipVertical,
+        Self::FlipHorizontal,
+    ];
+
+    /// Check if symmetry swaps rows with columns
+    #[inline]
+    pub const fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Self::Rotate90 | Self::Rotate270 | Self::Transpose | Self::AntiTranspose
+        )
+    }
+
+    /// Position of the tile at `(x, y)` of a `width` by `height` grid after the transformation
+    #[inline]
+    pub const fn transform_position(self, x: u8, y: u8, width: u8, height: u8) -> (u8, u8) {
+        let (max_x, max_y) = (width - 1, height - 1);
+        match self {
+            Self::Identity => (x, y),
+            Self::Rotate90 => (max_y - y, x),
+            Self::Rotate180 => (max_x - x, max_y - y),
+            Self::Rotate270 => (y, max_x - x),
+            Self::FlipVertical => (max_x - x, y),
+            Self::FlipHorizontal => (x, max_y - y),
+            Self::Transpose => (y, x),
+            Self::AntiTranspose => (max_y - y, max_x - x),
         }
+    }
 
-        if x != width {
-            // Not a rectangle in the last row
-            return None;
+    /// Transform the grid
+    pub fn apply<G>(self, grid: &G) -> G
+    where
+        G: FiniteGrid,
+    {
+        match self {
+            Self::Identity => self.apply_tilewise(grid),
+            Self::Rotate90 => grid.rotate90(),
+            Self::Rotate180 => grid.flip_vertical().flip_horizontal(),
+            Self::Rotate270 => grid.transpose().flip_horizontal(),
+            Self::FlipVertical => grid.flip_vertical(),
+            Self::FlipHorizontal => grid.flip_horizontal(),
+            Self::Transpose => grid.transpose(),
+            Self::AntiTranspose => grid.transpose().flip_vertical().flip_horizontal(),
+        }
+    }
+
+    /// Transform the grid by moving tiles one by one
+    pub(crate) fn apply_tilewise<G>(self, grid: &G) -> G
+    where
+        G: FiniteGrid,
+    {
+        let (width, height) = (grid.width(), grid.height());
+        if width == 0 || height == 0 {
+            return G::zero_size();
+        }
+
+        let (new_width, new_height) = if self.swaps_axes() {
+            (height, width)
+        } else {
+            (width, height)
+        };
+        let mut result = G::filled(new_width, new_height, grid.get(0, 0))
+            .expect("unreachable: grid with the same number of tiles already exists");
+        for y in 0..height {
+            for x in 0..width {
+                let (new_x, new_y) = self.transform_position(x, y, width, height);
+                result.set(new_x, new_y, grid.get(x, y));
+            }
         }
-        Some(grid)
+        result
     }
 }
 
@@ -0,0 +1,176 @@
+//! Grid of any size holding a single bit of information per tile.
+
+use crate::grid::{BitTile, CharTile, FiniteGrid, Grid, ParseGridError};
+use alloc::{vec, vec::Vec};
+use std::{fmt::Display, marker::PhantomData, str::FromStr};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A grid of any size holding a single bit of information per tile.
+///
+/// Unlike [`SmallBitGrid`](crate::grid::small_bit_grid::SmallBitGrid) it is not limited to 64
+/// tiles, as tiles are packed into a vector of `u64` words.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BitGrid<T> {
+    width: u8,
+    height: u8,
+    grid: Vec<u64>,
+    _ty: PhantomData<T>,
+}
+
+impl<T> BitGrid<T>
+where
+    T: BitTile,
+{
+    /// Creates empty grid with given size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cgt::grid::bit_grid::BitGrid;
+    ///
+    /// assert_eq!(&format!("{}", BitGrid::<bool>::empty(2, 3)), "..|..|..");
+    /// ```
+    pub fn empty(width: u8, height: u8) -> Self {
+        Self {
+            width,
+            height,
+            grid: vec![0; (width as usize * height as usize).div_ceil(WORD_BITS)],
+            _ty: PhantomData,
+        }
+    }
+
+    /// Creates a grid from given array of bools.
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - Lineralized grid of size `width * height`, empty if if value is `false`.
+    ///
+    /// # Errors
+    /// - `grid` length is not `width * height`
+    pub fn from_arr(width: u8, height: u8, grid: &[bool]) -> Option<Self> {
+        if grid.len() != width as usize * height as usize {
+            return None;
+        }
+
+        let mut result = Self::empty(width, height);
+        for (idx, bit) in grid.iter().enumerate() {
+            result.grid[idx / WORD_BITS] |= u64::from(*bit) << (idx % WORD_BITS);
+        }
+        Some(result)
+    }
+
+    #[inline]
+    const fn bit_index(&self, x: u8, y: u8) -> usize {
+        self.width as usize * y as usize + x as usize
+    }
+}
+
+impl<T> Grid for BitGrid<T>
+where
+    T: BitTile,
+{
+    type Item = T;
+
+    fn get(&self, x: u8, y: u8) -> Self::Item {
+        let n = self.bit_index(x, y);
+        BitTile::bool_to_tile((self.grid[n / WORD_BITS] >> (n % WORD_BITS)) & 1 == 1)
+    }
+
+    fn set(&mut self, x: u8, y: u8, value: Self::Item) {
+        let n = self.bit_index(x, y);
+        let word = &mut self.grid[n / WORD_BITS];
+        let val = u64::from(value.tile_to_bool());
+        *word = (*word & !(1 << (n % WORD_BITS))) | (val << (n % WORD_BITS));
+    }
+}
+
+impl<T> FiniteGrid for BitGrid<T>
+where
+    T: BitTile,
+{
+    fn width(&self) -> u8 {
+        self.width
+    }
+
+    fn height(&self) -> u8 {
+        self.height
+    }
+
+    fn filled(width: u8, height: u8, value: T) -> Option<Self> {
+        let mut grid = Self::empty(width, height);
+        if value.tile_to_bool() {
+            let tiles = width as usize * height as usize;
+            grid.grid.fill(u64::MAX);
+            // Bits outside of the grid are kept unset, so equal grids have equal representations
+            if let Some(last) = grid.grid.last_mut() {
+                *last >>= (WORD_BITS - tiles % WORD_BITS) % WORD_BITS;
+            }
+        }
+        Some(grid)
+    }
+
+    fn zero_size() -> Self {
+        Self::empty(0, 0)
+    }
+}
+
+impl<T> Display for BitGrid<T>
+where
+    T: BitTile + CharTile,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.display(f, '|')
+    }
+}
+
+impl<T> FromStr for BitGrid<T>
+where
+    T: BitTile + CharTile + Default,
+{
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::small_bit_grid::SmallBitGrid;
+
+    #[test]
+    fn matches_small_bit_grid() {
+        let input = "..#|.#.|##.";
+        let grid = BitGrid::<bool>::from_str(input).unwrap();
+        assert_eq!(grid.to_string(), input);
+        assert_eq!(
+            BitGrid::<bool>::from_arr(
+                3,
+                3,
+                &[false, false, true, false, true, false, true, true, false]
+            ),
+            Some(grid)
+        );
+
+        let small = SmallBitGrid::<bool>::filled(8, 8, true).unwrap();
+        let large = BitGrid::<bool>::filled(8, 8, true).unwrap();
+        assert_eq!(small.to_string(), large.to_string());
+    }
+
+    #[test]
+    fn large_grid() {
+        let mut grid = BitGrid::<bool>::filled(20, 15, true).unwrap();
+        grid.set(19, 14, false);
+        grid.set(3, 7, false);
+        assert!(!grid.get(19, 14));
+        assert!(!grid.get(3, 7));
+        assert!(grid.get(4, 7));
+
+        grid.set(19, 14, true);
+        grid.set(3, 7, true);
+        assert_eq!(grid, BitGrid::filled(20, 15, true).unwrap());
+    }
+}
@@ -0,0 +1,171 @@
+//! Grid holding a single bit of information per tile, with row stride known at compile time.
+
+use crate::grid::{BitTile, CharTile, FiniteGrid, Grid, ParseGridError};
+use std::{fmt::Display, marker::PhantomData, str::FromStr};
+
+/// A grid that fits in a `W` by `H` frame, holding a single bit of information per tile.
+///
+/// Unlike [`SmallBitGrid`](crate::grid::small_bit_grid::SmallBitGrid) the layout of tiles
+/// depends only on `W`, so the position of every tile is computed with a multiplication by a
+/// constant and row scans can be unrolled by the compiler. The grid can still be smaller than the
+/// frame, as decompositions of game positions shrink the board, but it cannot grow past it - in
+/// particular transposing a grid that does not fit in a `H` by `W` frame panics, so prefer
+/// square frames when symmetries swapping axes are used.
+///
+/// # Examples
+///
+/// ```
+/// use cgt::grid::{fixed_grid::FixedGrid, FiniteGrid};
+///
+/// let grid = FixedGrid::<bool, 4, 4>::parse("..#|.#.").unwrap();
+/// assert_eq!(grid.width(), 3);
+/// assert_eq!(FixedGrid::<bool, 4, 4>::parse("....#|....."), None);
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixedGrid<T, const W: usize, const H: usize> {
+    width: u8,
+    height: u8,
+    grid: u64,
+    _ty: PhantomData<T>,
+}
+
+impl<T, const W: usize, const H: usize> FixedGrid<T, W, H> {
+    const FITS_IN_WORD: () = assert!(W * H <= u64::BITS as usize, "frame must fit in 64 bits");
+
+    #[inline]
+    const fn bit_index(x: u8, y: u8) -> usize {
+        W * y as usize + x as usize
+    }
+}
+
+impl<T, const W: usize, const H: usize> Grid for FixedGrid<T, W, H>
+where
+    T: BitTile,
+{
+    type Item = T;
+
+    #[inline]
+    fn get(&self, x: u8, y: u8) -> Self::Item {
+        BitTile::bool_to_tile((self.grid >> Self::bit_index(x, y)) & 1 == 1)
+    }
+
+    #[inline]
+    fn set(&mut self, x: u8, y: u8, value: Self::Item) {
+        let n = Self::bit_index(x, y);
+        self.grid = (self.grid & !(1 << n)) | (u64::from(value.tile_to_bool()) << n);
+    }
+}
+
+impl<T, const W: usize, const H: usize> FiniteGrid for FixedGrid<T, W, H>
+where
+    T: BitTile,
+{
+    fn width(&self) -> u8 {
+        self.width
+    }
+
+    fn height(&self) -> u8 {
+        self.height
+    }
+
+    fn filled(width: u8, height: u8, value: T) -> Option<Self> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::FITS_IN_WORD;
+
+        if width as usize > W || height as usize > H {
+            return None;
+        }
+
+        let mut grid = 0;
+        if value.tile_to_bool() {
+            // Bits outside of the grid are kept unset, so equal grids have equal representations
+            let row = u64::MAX
+                .checked_shr(u64::BITS - u32::from(width))
+                .unwrap_or(0);
+            for y in 0..height {
+                grid |= row << Self::bit_index(0, y);
+            }
+        }
+
+        Some(Self {
+            width,
+            height,
+            grid,
+            _ty: PhantomData,
+        })
+    }
+
+    fn zero_size() -> Self {
+        Self {
+            width: 0,
+            height: 0,
+            grid: 0,
+            _ty: PhantomData,
+        }
+    }
+}
+
+impl<T, const W: usize, const H: usize> Display for FixedGrid<T, W, H>
+where
+    T: BitTile + CharTile,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.display(f, '|')
+    }
+}
+
+impl<T, const W: usize, const H: usize> FromStr for FixedGrid<T, W, H>
+where
+    T: BitTile + CharTile + Default,
+{
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::small_bit_grid::SmallBitGrid;
+    #[cfg(feature = "std")]
+    use crate::short::partizan::{
+        games::domineering::Domineering, partizan_game::PartizanGame,
+        transposition_table::ParallelTranspositionTable,
+    };
+
+    #[test]
+    fn matches_small_bit_grid() {
+        let input = "..#|.#.|##.";
+        let grid = FixedGrid::<bool, 4, 4>::from_str(input).unwrap();
+        assert_eq!(grid.to_string(), input);
+        assert_eq!(
+            FixedGrid::<bool, 4, 4>::filled(3, 2, true)
+                .unwrap()
+                .to_string(),
+            "###|###"
+        );
+        assert_eq!(FixedGrid::<bool, 4, 4>::filled(5, 1, true), None);
+        assert_eq!(
+            grid.rotate90().to_string(),
+            SmallBitGrid::<bool>::from_str(input)
+                .unwrap()
+                .rotate90()
+                .to_string()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn domineering_canonical_form() {
+        let small = Domineering::new(SmallBitGrid::from_str("....|..#.|....|#...").unwrap());
+        let fixed =
+            Domineering::new(FixedGrid::<_, 4, 4>::from_str("....|..#.|....|#...").unwrap());
+        assert_eq!(
+            small.canonical_form(&ParallelTranspositionTable::new()),
+            fixed.canonical_form(&ParallelTranspositionTable::new())
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Hexagonal grid in the shape of a rhombus, like the board of Hex.
+//!
+//! Tiles use axial coordinates: `x` grows to the right along a row and `y` grows down-right
+//! along a column, so tile `(x, y)` is adjacent to `(x ± 1, y)`, `(x, y ± 1)`, `(x + 1, y - 1)`
+//! and `(x - 1, y + 1)`. As tiles are addressed by a pair of coordinates, the grid implements
+//! [`FiniteGrid`] and can be used with generic grid functions like
+//! [`decompositions`](crate::grid::decompositions) by passing [`DIRECTIONS`].
+//!
+//! In the one-line notation the grid looks like a rectangular one, e.g. `"..#|.#.|..."`, while
+//! [`HexGrid::to_rhombus`] shifts every row by half a tile to show the real shape:
+//! ```text
+//! . . #
+//!  . # .
+//!   . . .
+//! ```
+
+use crate::grid::{CharTile, FiniteGrid, Grid, ParseGridError};
+use alloc::{string::String, vec, vec::Vec};
+use std::{fmt::Display, str::FromStr};
+
+/// Offsets of all six neighbours of a tile in axial coordinates
+pub const DIRECTIONS: [(i32, i32); 6] = [(1, 0), (-1, 0), (0, 1), (0, -1), (1, -1), (-1, 1)];
+
+/// Number of steps between two tiles of a hexagonal grid
+pub const fn distance(lhs: (u8, u8), rhs: (u8, u8)) -> u8 {
+    let dx = lhs.0 as i32 - rhs.0 as i32;
+    let dy = lhs.1 as i32 - rhs.1 as i32;
+    ((dx.abs() + dy.abs() + (dx + dy).abs()) / 2) as u8
+}
+
+/// Hexagonal grid in the shape of a rhombus
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HexGrid<T> {
+    width: u8,
+    height: u8,
+    grid: Vec<T>,
+}
+
+impl<T> Grid for HexGrid<T>
+where
+    T: Clone,
+{
+    type Item = T;
+
+    fn get(&self, x: u8, y: u8) -> Self::Item {
+        self.grid[(self.width as usize) * (y as usize) + (x as usize)].clone()
+    }
+
+    fn set(&mut self, x: u8, y: u8, value: Self::Item) {
+        self.grid[(self.width as usize) * (y as usize) + (x as usize)] = value;
+    }
+}
+
+impl<T> FiniteGrid for HexGrid<T>
+where
+    T: Copy,
+{
+    fn width(&self) -> u8 {
+        self.width
+    }
+
+    fn height(&self) -> u8 {
+        self.height
+    }
+
+    fn filled(width: u8, height: u8, value: T) -> Option<Self> {
+        Some(Self {
+            width,
+            height,
+            grid: vec![value; width as usize * height as usize],
+        })
+    }
+
+    fn zero_size() -> Self {
+        Self {
+            width: 0,
+            height: 0,
+            grid: vec![],
+        }
+    }
+}
+
+impl<T> HexGrid<T>
+where
+    T: Copy,
+{
+    /// Iterate over coordinates of tiles adjacent to a given tile
+    pub fn neighbors(&self, x: u8, y: u8) -> impl Iterator<Item = (u8, u8)> {
+        let (width, height) = (self.width as i32, self.height as i32);
+        DIRECTIONS.into_iter().filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+            (nx >= 0 && nx < width && ny >= 0 && ny < height).then_some((nx as u8, ny as u8))
+        })
+    }
+
+    /// Render the grid on multiple lines, shifting every row by half a tile, so the shape of
+    /// the board is visible
+    pub fn to_rhombus(&self) -> String
+    where
+        T: CharTile,
+    {
+        let mut result = String::new();
+        for y in 0..self.height {
+            if y != 0 {
+                result.push('\n');
+            }
+            result.extend(std::iter::repeat_n(' ', y as usize));
+            for x in 0..self.width {
+                if x != 0 {
+                    result.push(' ');
+                }
+                result.push(self.get(x, y).tile_to_char());
+            }
+        }
+        result
+    }
+}
+
+impl<T> Display for HexGrid<T>
+where
+    T: CharTile + Copy,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.display(f, '|')
+    }
+}
+
+impl<T> FromStr for HexGrid<T>
+where
+    T: CharTile + Copy + Default,
+{
+    type Err = ParseGridError;
+
+    /// Parse grid either in the one-line notation or in the rhombus notation produced by
+    /// [`HexGrid::to_rhombus`]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains('\n') {
+            let one_line = s
+                .lines()
+                .map(|line| line.split_whitespace().collect::<String>())
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join("|");
+            Self::try_parse(&one_line)
+        } else {
+            Self::try_parse(s)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::decompositions;
+
+    #[test]
+    fn neighbors_and_distance() {
+        let grid = HexGrid::<bool>::filled(3, 3, false).unwrap();
+        assert_eq!(grid.neighbors(1, 1).count(), 6);
+        assert_eq!(
+            grid.neighbors(0, 0).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbors(2, 0).collect::<Vec<_>>(),
+            vec![(1, 0), (2, 1), (1, 1)]
+        );
+
+        assert_eq!(distance((0, 0), (2, 2)), 4);
+        assert_eq!(distance((2, 0), (0, 2)), 2);
+        assert_eq!(distance((1, 1), (1, 1)), 0);
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = HexGrid::<bool>::from_str("..#|.#.|...").unwrap();
+        assert_eq!(grid.to_rhombus(), ". . #\n . # .\n  . . .");
+        assert_eq!(HexGrid::from_str(&grid.to_rhombus()), Ok(grid.clone()));
+        assert_eq!(grid.to_string(), "..#|.#.|...");
+    }
+
+    #[test]
+    fn hex_decompositions() {
+        // Tiles (1, 0) and (0, 1) touch on hexagonal grid, but not on square grid
+        let grid = HexGrid::<bool>::from_str("#.|.#").unwrap();
+        let is_empty = |tile: bool| !tile;
+        assert_eq!(decompositions(&grid, is_empty, true, &DIRECTIONS).len(), 1);
+        assert_eq!(
+            decompositions(&grid, is_empty, true, &DIRECTIONS[..4]).len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! Grid wrapper marking some tiles as not being part of the board.
+//!
+//! Tiles out of play always read as the `hole` tile and writes to them are ignored, so games
+//! generic over [`Grid`] can be played on irregular boards without any changes, as long as the
+//! `hole` tile blocks moves, e.g. [`Tile::Taken`](crate::short::partizan::games::domineering::Tile::Taken)
+//! in Domineering.
+
+use crate::grid::{bit_grid::BitGrid, CharTile, FiniteGrid, Grid};
+use alloc::collections::VecDeque;
+use alloc::{string::ToString, vec::Vec};
+use std::fmt::Display;
+
+/// Character used for tiles out of play
+const HOLE_CHAR: char = ' ';
+
+/// Grid with some tiles marked as out of play
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaskedGrid<G, T> {
+    grid: G,
+    /// `true` for tiles out of play
+    mask: BitGrid<bool>,
+    hole: T,
+}
+
+impl<G, T> MaskedGrid<G, T>
+where
+    G: Grid<Item = T> + FiniteGrid,
+    T: Copy,
+{
+    /// Wrap a grid with all tiles in play. Tiles out of play will read as `hole`.
+    pub fn new(grid: G, hole: T) -> Self {
+        Self {
+            mask: BitGrid::empty(grid.width(), grid.height()),
+            grid,
+            hole,
+        }
+    }
+
+    /// Get the underlying grid. Values of tiles out of play are unspecified.
+    pub const fn inner(&self) -> &G {
+        &self.grid
+    }
+
+    /// Check if tile is part of the board
+    pub fn is_in_play(&self, x: u8, y: u8) -> bool {
+        !self.mask.get(x, y)
+    }
+
+    /// Add a tile to the board or remove it from the board. Removed tiles are set to `hole`.
+    pub fn set_in_play(&mut self, x: u8, y: u8, in_play: bool) {
+        self.mask.set(x, y, !in_play);
+        if !in_play {
+            self.grid.set(x, y, self.hole);
+        }
+    }
+
+    /// Parse grid in the one-line notation, where tiles out of play are written as spaces,
+    /// e.g. `" .|.."`
+    pub fn parse_masked(input: &str, hole: T) -> Option<Self>
+    where
+        T: CharTile + Default,
+    {
+        let holes: Vec<bool> = input
+            .chars()
+            .filter(|c| *c != '|')
+            .map(|c| c == HOLE_CHAR)
+            .collect();
+        let filled = input.replace(HOLE_CHAR, &hole.tile_to_char().to_string());
+
+        let mut grid = Self::new(G::parse(&filled)?, hole);
+        for y in 0..grid.grid.height() {
+            for x in 0..grid.grid.width() {
+                if holes[y as usize * grid.grid.width() as usize + x as usize] {
+                    grid.set_in_play(x, y, false);
+                }
+            }
+        }
+        Some(grid)
+    }
+
+    /// Iterate over tiles in play, adjacent to a given tile in given directions
+    pub fn neighbors<'a>(
+        &'a self,
+        x: u8,
+        y: u8,
+        directions: &'a [(i32, i32)],
+    ) -> impl Iterator<Item = (u8, u8)> + 'a {
+        directions.iter().filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+            (nx >= 0
+                && nx < self.grid.width() as i32
+                && ny >= 0
+                && ny < self.grid.height() as i32
+                && self.is_in_play(nx as u8, ny as u8))
+            .then_some((nx as u8, ny as u8))
+        })
+    }
+
+    /// Split the board into regions of tiles in play connected in given directions. Every region
+    /// is cropped to its bounding box, with tiles from other regions out of play.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn regions(&self, directions: &[(i32, i32)]) -> Vec<Self> {
+        let mut visited = BitGrid::<bool>::empty(self.grid.width(), self.grid.height());
+        let mut regions = Vec::new();
+
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                if !self.is_in_play(x, y) || visited.get(x, y) {
+                    continue;
+                }
+
+                let mut region = Vec::new();
+                let mut queue = VecDeque::from([(x, y)]);
+                visited.set(x, y, true);
+                while let Some((qx, qy)) = queue.pop_front() {
+                    region.push((qx, qy));
+                    for (nx, ny) in self.neighbors(qx, qy, directions) {
+                        if !visited.get(nx, ny) {
+                            visited.set(nx, ny, true);
+                            queue.push_back((nx, ny));
+                        }
+                    }
+                }
+
+                let min_x = region.iter().map(|(x, _)| *x).min().unwrap();
+                let max_x = region.iter().map(|(x, _)| *x).max().unwrap();
+                let min_y = region.iter().map(|(_, y)| *y).min().unwrap();
+                let max_y = region.iter().map(|(_, y)| *y).max().unwrap();
+                let (width, height) = (max_x - min_x + 1, max_y - min_y + 1);
+
+                let mut result = Self {
+                    grid: G::filled(width, height, self.hole)
+                        .expect("unreachable: region is smaller than the grid"),
+                    mask: BitGrid::filled(width, height, true)
+                        .expect("unreachable: bit grid has any size"),
+                    hole: self.hole,
+                };
+                for (rx, ry) in region {
+                    result.mask.set(rx - min_x, ry - min_y, false);
+                    result
+                        .grid
+                        .set(rx - min_x, ry - min_y, self.grid.get(rx, ry));
+                }
+                regions.push(result);
+            }
+        }
+
+        regions
+    }
+}
+
+impl<G, T> Grid for MaskedGrid<G, T>
+where
+    G: Grid<Item = T> + FiniteGrid,
+    T: Copy,
+{
+    type Item = T;
+
+    fn get(&self, x: u8, y: u8) -> Self::Item {
+        if self.is_in_play(x, y) {
+            self.grid.get(x, y)
+        } else {
+            self.hole
+        }
+    }
+
+    fn set(&mut self, x: u8, y: u8, value: Self::Item) {
+        if self.is_in_play(x, y) {
+            self.grid.set(x, y, value);
+        }
+    }
+}
+
+impl<G, T> FiniteGrid for MaskedGrid<G, T>
+where
+    G: Grid<Item = T> + FiniteGrid,
+    T: Copy + Default,
+{
+    fn width(&self) -> u8 {
+        self.grid.width()
+    }
+
+    fn height(&self) -> u8 {
+        self.grid.height()
+    }
+
+    /// Create grid with all tiles in play, where `value` is also used as the `hole` tile
+    fn filled(width: u8, height: u8, value: T) -> Option<Self> {
+        Some(Self::new(G::filled(width, height, value)?, value))
+    }
+
+    fn zero_size() -> Self {
+        Self::new(G::zero_size(), T::default())
+    }
+
+    fn rotate90(&self) -> Self {
+        Self {
+            grid: self.grid.rotate90(),
+            mask: self.mask.rotate90(),
+            hole: self.hole,
+        }
+    }
+
+    fn flip_horizontal(&self) -> Self {
+        Self {
+            grid: self.grid.flip_horizontal(),
+            mask: self.mask.flip_horizontal(),
+            hole: self.hole,
+        }
+    }
+
+    fn flip_vertical(&self) -> Self {
+        Self {
+            grid: self.grid.flip_vertical(),
+            mask: self.mask.flip_vertical(),
+            hole: self.hole,
+        }
+    }
+
+    fn transpose(&self) -> Self {
+        Self {
+            grid: self.grid.transpose(),
+            mask: self.mask.transpose(),
+            hole: self.hole,
+        }
+    }
+}
+
+impl<G, T> Display for MaskedGrid<G, T>
+where
+    G: Grid<Item = T> + FiniteGrid,
+    T: Copy + CharTile,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.grid.height() {
+            if y != 0 {
+                write!(f, "|")?;
+            }
+            for x in 0..self.grid.width() {
+                if self.is_in_play(x, y) {
+                    write!(f, "{}", self.grid.get(x, y).tile_to_char())?;
+                } else {
+                    write!(f, "{HOLE_CHAR}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::small_bit_grid::SmallBitGrid;
+    #[cfg(feature = "std")]
+    use crate::short::partizan::{
+        games::domineering::{Domineering, Tile},
+        partizan_game::PartizanGame,
+        transposition_table::ParallelTranspositionTable,
+    };
+
+    type Board = MaskedGrid<SmallBitGrid<bool>, bool>;
+
+    const DIRECTIONS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+    #[test]
+    fn parse_and_display() {
+        let grid = Board::parse_masked(" .#|...| . ", true).unwrap();
+        assert_eq!(grid.to_string(), " .#|...| . ");
+        assert!(!grid.is_in_play(0, 0));
+        assert!(grid.get(0, 0));
+        assert_eq!(
+            grid.neighbors(1, 1, &DIRECTIONS).collect::<Vec<_>>(),
+            vec![(2, 1), (0, 1), (1, 2), (1, 0)]
+        );
+    }
+
+    #[test]
+    fn regions() {
+        let grid = Board::parse_masked(".. ..|  ...|.    ", true).unwrap();
+        let regions = grid.regions(&DIRECTIONS);
+        assert_eq!(
+            regions.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["..", " ..|...", "."]
+        );
+    }
+
+    #[test]
+    fn transforms_keep_holes() {
+        let grid = Board::parse_masked(" .#|...", true).unwrap();
+        assert_eq!(grid.rotate90().to_string(), ". |..|.#");
+        assert_eq!(grid.flip_vertical().to_string(), "#. |...");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn domineering_on_masked_board() {
+        // L-shaped board
+        let board: MaskedGrid<SmallBitGrid<Tile>, Tile> =
+            MaskedGrid::parse_masked(". |..", Tile::Taken).unwrap();
+        let position = Domineering::new(board);
+        assert_eq!(
+            position
+                .canonical_form(&ParallelTranspositionTable::new())
+                .to_string(),
+            "*"
+        );
+    }
+}
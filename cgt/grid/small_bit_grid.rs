@@ -1,6 +1,9 @@
 //! Grid with up to 64 tiles holding a single bit of information.
 
-use crate::grid::{BitTile, CharTile, FiniteGrid, Grid};
+use crate::{
+    grid::{BitTile, CharTile, FiniteGrid, Grid, ParseGridError, Symmetry},
+    zobrist::{zobrist_key, ZobristHash, GLOBAL_INDEX},
+};
 use std::{fmt::Display, marker::PhantomData, str::FromStr};
 
 /// Internal representation of a grid
@@ -52,8 +55,11 @@ where
         Some(Self {
             width,
             height,
+            // Bits outside of the grid are kept unset, so equal grids have equal representations
             grid: if value.tile_to_bool() {
                 GridBits::MAX
+                    .checked_shr(GridBits::BITS - u32::from(width) * u32::from(height))
+                    .unwrap_or(0)
             } else {
                 0
             },
@@ -70,6 +76,76 @@ where
             _ty: PhantomData,
         }
     }
+
+    fn rotate90(&self) -> Self {
+        self.transpose().flip_vertical()
+    }
+
+    fn flip_horizontal(&self) -> Self {
+        let row_mask = Self::row_mask(self.width);
+        let (width, height) = (self.width as u32, self.height as u32);
+        let mut grid = 0;
+        for y in 0..height {
+            grid |= ((self.grid >> (width * y)) & row_mask) << (width * (height - y - 1));
+        }
+        Self { grid, ..*self }
+    }
+
+    fn flip_vertical(&self) -> Self {
+        let (width, height) = (self.width as u32, self.height as u32);
+        let column_mask = (0..height).fold(0, |mask: GridBits, y| mask | (1 << (width * y)));
+        let mut grid = 0;
+        for x in 0..width {
+            grid |= ((self.grid >> x) & column_mask) << (width - x - 1);
+        }
+        Self { grid, ..*self }
+    }
+
+    fn transpose(&self) -> Self {
+        if self.width > 8 || self.height > 8 {
+            return Symmetry::Transpose.apply_tilewise(self);
+        }
+
+        // Swap bits across the diagonal of 8 by 8 board in three steps: 4x4 blocks, 2x2 blocks
+        // and single bits
+        let mut grid = self.to_padded();
+        let t = 0x0f0f_0f0f_0000_0000 & (grid ^ (grid << 28));
+        grid ^= t ^ (t >> 28);
+        let t = 0x3333_0000_3333_0000 & (grid ^ (grid << 14));
+        grid ^= t ^ (t >> 14);
+        let t = 0x5500_5500_5500_5500 & (grid ^ (grid << 7));
+        grid ^= t ^ (t >> 7);
+
+        Self::from_padded(self.height, self.width, grid)
+    }
+}
+
+/// XOR of keys of set bits
+fn bits_hash(mut bits: GridBits) -> u64 {
+    let mut hash = 0;
+    while bits != 0 {
+        hash ^= zobrist_key(u64::from(bits.trailing_zeros()), 1);
+        bits &= bits - 1;
+    }
+    hash
+}
+
+impl<T> ZobristHash for SmallBitGrid<T> {
+    fn zobrist_hash(&self) -> u64 {
+        zobrist_key(
+            GLOBAL_INDEX,
+            (u64::from(self.width) << 8) | u64::from(self.height),
+        ) ^ bits_hash(self.grid)
+    }
+
+    /// Grids of the same size differ only by changed tiles
+    fn zobrist_hash_from(&self, hash: u64, other: &Self) -> u64 {
+        if (self.width, self.height) == (other.width, other.height) {
+            hash ^ bits_hash(self.grid ^ other.grid)
+        } else {
+            other.zobrist_hash()
+        }
+    }
 }
 
 impl<T> Display for SmallBitGrid<T>
@@ -164,47 +240,37 @@ where
         Self::from_number(width, height, arr_to_bits(grid))
     }
 
-    /// Rotate grid 90° clockwise
-    #[must_use]
-    #[allow(clippy::missing_panics_doc)]
-    pub fn rotate(&self) -> Self {
-        let mut result = Self::empty(self.height(), self.width()).unwrap();
-        for y in 0..self.height() {
-            for x in 0..self.width() {
-                result.set(result.width() - y - 1, x, self.get(x, y));
-            }
+    /// Mask of the lowest `width` bits, i.e. of a single row
+    const fn row_mask(width: u8) -> GridBits {
+        match GridBits::MAX.checked_shr(GridBits::BITS - width as u32) {
+            Some(mask) => mask,
+            None => 0,
         }
-        result
     }
 
-    /// Flip grid vertically
-    #[must_use]
-    pub fn vertical_flip(&self) -> Self
-    where
-        Self: Clone,
-    {
-        let mut result: Self = self.clone();
-        for y in 0..self.height() {
-            for x in 0..self.width() {
-                result.set(result.width() - x - 1, y, self.get(x, y));
-            }
+    /// Move rows so they start at multiples of 8 bits. Works only with grids up to 8 by 8.
+    fn to_padded(&self) -> GridBits {
+        let row_mask = Self::row_mask(self.width);
+        let mut padded = 0;
+        for y in 0..self.height as u32 {
+            padded |= ((self.grid >> (self.width as u32 * y)) & row_mask) << (8 * y);
         }
-        result
+        padded
     }
 
-    /// Flip grid horizontally
-    #[must_use]
-    pub fn horizontal_flip(&self) -> Self
-    where
-        Self: Clone,
-    {
-        let mut result: Self = self.clone();
-        for y in 0..self.height() {
-            for x in 0..self.width() {
-                result.set(x, result.height() - y - 1, self.get(x, y));
-            }
+    /// Reverse of [`Self::to_padded`]
+    fn from_padded(width: u8, height: u8, padded: GridBits) -> Self {
+        let row_mask = Self::row_mask(width);
+        let mut grid = 0;
+        for y in 0..height as u32 {
+            grid |= ((padded >> (8 * y)) & row_mask) << (width as u32 * y);
+        }
+        Self {
+            width,
+            height,
+            grid,
+            _ty: PhantomData,
         }
-        result
     }
 }
 
@@ -212,10 +278,10 @@ impl<T> FromStr for SmallBitGrid<T>
 where
     T: BitTile + CharTile + Default,
 {
-    type Err = ();
+    type Err = ParseGridError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Self::parse(s).ok_or(())
+        Self::try_parse(s)
     }
 }
 
@@ -281,6 +347,29 @@ mod tests {
         assert_eq!(inp, arr_to_bits(&bits_to_arr(inp)),);
     }
 
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            SmallBitGrid::<bool>::from_str("..#|.#"),
+            Err(ParseGridError::NotRectangular {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            SmallBitGrid::<bool>::from_str("..#|.x."),
+            Err(ParseGridError::InvalidTile('x'))
+        );
+        assert_eq!(
+            SmallBitGrid::<bool>::from_str(&".".repeat(65)),
+            Err(ParseGridError::TooLarge {
+                width: 65,
+                height: 1
+            })
+        );
+    }
+
     #[test]
     fn parse_grid() {
         let width = 3;
@@ -322,7 +411,7 @@ mod tests {
 	 #..#",
         )
         .unwrap()
-        .rotate();
+        .rotate90();
 
         assert_eq!(
             &format!("{position}"),
@@ -332,7 +421,7 @@ mod tests {
 	 #.."
         );
 
-        let position = position.rotate();
+        let position = position.rotate90();
         assert_eq!(
             &format!("{position}"),
             "#..#|\
@@ -351,17 +440,38 @@ mod tests {
         .unwrap();
 
         assert_eq!(
-            &format!("{}", position.vertical_flip()),
+            &format!("{}", position.flip_vertical()),
             "..##|\
 	 ....|\
 	 #..#",
         );
 
         assert_eq!(
-            &format!("{}", position.horizontal_flip()),
+            &format!("{}", position.flip_horizontal()),
             "#..#|\
 	 ....|\
 	 ##..",
         );
     }
+
+    #[test]
+    fn transforms_match_tilewise() {
+        for (width, height) in [(1, 1), (3, 2), (2, 5), (8, 8), (8, 3), (16, 4), (1, 64)] {
+            for seed in [0x0123_4567_89ab_cdef_u64, 0xfedc_ba98_7654_3210, u64::MAX] {
+                let grid = SmallBitGrid::<bool>::from_number(
+                    width,
+                    height,
+                    seed & SmallBitGrid::<bool>::row_mask(width * height),
+                )
+                .unwrap();
+                for symmetry in Symmetry::ALL {
+                    assert_eq!(
+                        symmetry.apply(&grid),
+                        symmetry.apply_tilewise(&grid),
+                        "{symmetry:?} of {grid}"
+                    );
+                }
+            }
+        }
+    }
 }
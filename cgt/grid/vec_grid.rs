@@ -1,6 +1,7 @@
 //! Grid with arbitrary finite size
 
 use crate::grid::{FiniteGrid, Grid};
+use alloc::{vec, vec::Vec};
 
 /// Grid with arbitrary finite size
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -4,7 +4,20 @@
 //! [impartial](crate::short::impartial::games) games, displaying games as SVG images,
 //! computing canonical form of a game value and
 //! [calculations on canonical forms](crate::short::partizan::canonical_form::CanonicalForm)
+//!
+//! Without the default `std` feature the crate is `no_std` and needs only `alloc`. Then only
+//! [numeric types](crate::numeric), [grids](crate::grid),
+//! [canonical forms](crate::short::partizan::canonical_form) and values derived from them, like
+//! thermographs, are available. Games, graphs and transposition tables require `std`.
+//!
+//! With the `tracing` feature solvers emit [`tracing`](https://docs.rs/tracing) spans around
+//! searches and construction of canonical forms, and `TRACE` level events on transposition table
+//! lookups and decompositions of positions, so time spent in each can be profiled with any
+//! subscriber.
 
+// Tests use the standard test harness, but without `std` feature the library code is still
+// compiled like in `no_std` builds
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![warn(missing_docs)]
 #![warn(clippy::all, clippy::nursery, clippy::pedantic)]
 #![allow(
@@ -23,13 +36,33 @@
         clippy::missing_panics_doc
     )]
 
+extern crate alloc;
+
+// Macros from `auto_ops` and `cgt_derive` expand to `::std` paths, all of which are in `core`
+#[cfg(not(any(feature = "std", test)))]
+extern crate core as std;
+
 pub mod drawing;
+#[cfg(feature = "std")]
+pub mod error;
+#[cfg(feature = "std")]
+pub mod genetic;
+#[cfg(feature = "std")]
 pub mod genetic_algorithm;
+#[cfg(feature = "std")]
 pub mod graph;
 pub mod grid;
+#[cfg(feature = "std")]
+pub mod local_search;
+#[cfg(feature = "std")]
 pub mod loopy;
 pub mod numeric;
+#[cfg(feature = "std")]
+pub mod pareto;
 pub mod short;
+#[cfg(any(test, feature = "proptest"))]
+pub mod strategies;
+pub mod zobrist;
 
 mod display;
 mod macros;
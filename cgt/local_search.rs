@@ -0,0 +1,313 @@
+//! Local search drivers, alternatives to [`GeneticAlgorithm`](crate::genetic_algorithm::GeneticAlgorithm)
+//!
+//! Both drivers explore neighbourhoods of a single object, using [`Algorithm::mutate`] to move
+//! between neighbours, so any [`Algorithm`] can be run with them. [`Algorithm::cross`] is not
+//! used.
+
+use crate::genetic_algorithm::{Algorithm, Scored};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::num::NonZeroUsize;
+
+/// Geometric cooling schedule of [`SimulatedAnnealing`]
+#[derive(Clone, Copy, Debug)]
+pub struct Cooling {
+    /// Temperature of the first step
+    pub initial_temperature: f64,
+
+    /// Factor the temperature is multiplied by after each step, between 0 and 1
+    pub rate: f64,
+}
+
+/// Simulated annealing runner
+///
+/// Every step mutates the current object. Better objects are always accepted, worse ones with
+/// probability `exp(Δ / T)` where `Δ` is the (negative) change of score and `T` is the current
+/// temperature, which decreases according to the [`Cooling`] schedule.
+pub struct SimulatedAnnealing<Alg, Object, Score> {
+    current: Scored<Object, Score>,
+    best: Scored<Object, Score>,
+    temperature: f64,
+    cooling_rate: f64,
+    step: usize,
+    seed: u64,
+    algorithm: Alg,
+}
+
+impl<Alg, Object, Score> SimulatedAnnealing<Alg, Object, Score>
+where
+    Alg: Algorithm<Object, Score>,
+    Score: Clone + Ord + Into<f64>,
+    Object: Clone,
+{
+    /// Create new instance starting from a random object
+    pub fn new(cooling: Cooling, algorithm: Alg) -> Self {
+        let object = algorithm.random(&mut StdRng::from_entropy());
+        Self::with_object(object, cooling, algorithm)
+    }
+
+    /// Like [`Self::new`] but will start from `object`
+    pub fn with_object(object: Object, cooling: Cooling, algorithm: Alg) -> Self {
+        Self::with_object_seeded(object, cooling, algorithm, StdRng::from_entropy().gen())
+    }
+
+    /// Like [`Self::with_object`] but all random choices are derived from `seed`, so runs with
+    /// the same seed, object and algorithm are identical
+    pub fn with_object_seeded(object: Object, cooling: Cooling, algorithm: Alg, seed: u64) -> Self {
+        let current = Scored {
+            score: algorithm.score(&object),
+            object,
+        };
+        Self {
+            best: current.clone(),
+            current,
+            temperature: cooling.initial_temperature,
+            cooling_rate: cooling.rate,
+            step: 0,
+            seed,
+            algorithm,
+        }
+    }
+
+    /// Perform one step
+    pub fn step(&mut self) {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut object = self.current.object.clone();
+        self.algorithm.mutate(&mut object, &mut rng);
+        let candidate = Scored {
+            score: self.algorithm.score(&object),
+            object,
+        };
+
+        let accept = candidate.score >= self.current.score || {
+            let delta: f64 = candidate.score.clone().into() - self.current.score.clone().into();
+            // `delta` is NaN when moving between infinities, then the move is rejected
+            rng.gen::<f64>() < (delta / self.temperature).exp()
+        };
+        if accept {
+            if candidate.score > self.best.score {
+                self.best = candidate.clone();
+            }
+            self.current = candidate;
+        }
+
+        self.temperature *= self.cooling_rate;
+        self.seed = rng.gen();
+        self.step += 1;
+    }
+
+    /// Get number of finished steps
+    pub const fn steps(&self) -> usize {
+        self.step
+    }
+
+    /// Get current temperature of the schedule
+    pub const fn temperature(&self) -> f64 {
+        self.temperature
+    }
+
+    /// Get object the search is currently at
+    pub const fn current(&self) -> &Scored<Object, Score> {
+        &self.current
+    }
+
+    /// Get object with highest fitness seen so far
+    pub const fn highest_score(&self) -> &Scored<Object, Score> {
+        &self.best
+    }
+
+    /// Get underlying algorithm
+    pub const fn algorithm(&self) -> &Alg {
+        &self.algorithm
+    }
+}
+
+/// Steepest-ascent hill climbing with random restarts
+///
+/// Every step scores `neighbours` mutations of the current object and moves to the best one if
+/// it is better than the current object. Otherwise the current object is a local optimum and the
+/// search restarts from a random object.
+pub struct HillClimbing<Alg, Object, Score> {
+    current: Scored<Object, Score>,
+    best: Scored<Object, Score>,
+    neighbours: NonZeroUsize,
+    restarts: usize,
+    step: usize,
+    seed: u64,
+    algorithm: Alg,
+}
+
+impl<Alg, Object, Score> HillClimbing<Alg, Object, Score>
+where
+    Alg: Algorithm<Object, Score>,
+    Score: Clone + Ord,
+    Object: Clone,
+{
+    /// Create new instance starting from a random object, looking at `neighbours` mutations in
+    /// every step
+    pub fn new(neighbours: NonZeroUsize, algorithm: Alg) -> Self {
+        let object = algorithm.random(&mut StdRng::from_entropy());
+        Self::with_object(object, neighbours, algorithm)
+    }
+
+    /// Like [`Self::new`] but will start from `object`
+    pub fn with_object(object: Object, neighbours: NonZeroUsize, algorithm: Alg) -> Self {
+        Self::with_object_seeded(object, neighbours, algorithm, StdRng::from_entropy().gen())
+    }
+
+    /// Like [`Self::with_object`] but all random choices are derived from `seed`, so runs with
+    /// the same seed, object and algorithm are identical
+    pub fn with_object_seeded(
+        object: Object,
+        neighbours: NonZeroUsize,
+        algorithm: Alg,
+        seed: u64,
+    ) -> Self {
+        let current = Scored {
+            score: algorithm.score(&object),
+            object,
+        };
+        Self {
+            best: current.clone(),
+            current,
+            neighbours,
+            restarts: 0,
+            step: 0,
+            seed,
+            algorithm,
+        }
+    }
+
+    /// Perform one step
+    pub fn step(&mut self) {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let objects = (0..self.neighbours.get())
+            .map(|_| {
+                let mut object = self.current.object.clone();
+                self.algorithm.mutate(&mut object, &mut rng);
+                object
+            })
+            .collect::<Vec<_>>();
+        let scores = self.algorithm.score_all(&objects);
+        let candidate = objects
+            .into_iter()
+            .zip(scores)
+            .map(|(object, score)| Scored { object, score })
+            .max_by(|lhs, rhs| Ord::cmp(&lhs.score, &rhs.score))
+            .expect("unreachable: there is at least one neighbour");
+
+        if candidate.score > self.current.score {
+            if candidate.score > self.best.score {
+                self.best = candidate.clone();
+            }
+            self.current = candidate;
+        } else {
+            let object = self.algorithm.random(&mut rng);
+            self.current = Scored {
+                score: self.algorithm.score(&object),
+                object,
+            };
+            self.restarts += 1;
+        }
+
+        self.seed = rng.gen();
+        self.step += 1;
+    }
+
+    /// Get number of finished steps
+    pub const fn steps(&self) -> usize {
+        self.step
+    }
+
+    /// Get number of restarts from local optima
+    pub const fn restarts(&self) -> usize {
+        self.restarts
+    }
+
+    /// Get object the search is currently at
+    pub const fn current(&self) -> &Scored<Object, Score> {
+        &self.current
+    }
+
+    /// Get object with highest fitness seen so far
+    pub const fn highest_score(&self) -> &Scored<Object, Score> {
+        &self.best
+    }
+
+    /// Get underlying algorithm
+    pub const fn algorithm(&self) -> &Alg {
+        &self.algorithm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numeric::rational::Rational;
+
+    /// Maximize number of set bits
+    struct Bits;
+
+    impl Algorithm<u16, Rational> for Bits {
+        fn mutate(&self, object: &mut u16, rng: &mut StdRng) {
+            *object ^= 1 << rng.gen_range(0..16);
+        }
+
+        fn cross(&self, lhs: &u16, _rhs: &u16, _rng: &mut StdRng) -> u16 {
+            *lhs
+        }
+
+        fn lowest_score(&self) -> Rational {
+            Rational::NegativeInfinity
+        }
+
+        fn score(&self, object: &u16) -> Rational {
+            Rational::from(i64::from(object.count_ones()))
+        }
+
+        fn random(&self, rng: &mut StdRng) -> u16 {
+            rng.gen()
+        }
+    }
+
+    #[test]
+    fn simulated_annealing_finds_maximum() {
+        let cooling = Cooling {
+            initial_temperature: 2.0,
+            rate: 0.99,
+        };
+        let mut search = SimulatedAnnealing::with_object(0, cooling, Bits);
+        while search.steps() < 2000 && search.highest_score().object != u16::MAX {
+            search.step();
+        }
+        assert_eq!(search.highest_score().object, u16::MAX);
+        assert!(search.temperature() < cooling.initial_temperature);
+    }
+
+    #[test]
+    fn hill_climbing_finds_maximum() {
+        let mut search = HillClimbing::with_object(0, NonZeroUsize::new(32).unwrap(), Bits);
+        while search.steps() < 200 && search.highest_score().object != u16::MAX {
+            search.step();
+        }
+        assert_eq!(search.highest_score().object, u16::MAX);
+        assert_eq!(search.highest_score().score, Rational::from(16));
+    }
+
+    #[test]
+    fn seeded_runs_are_reproducible() {
+        let cooling = Cooling {
+            initial_temperature: 2.0,
+            rate: 0.99,
+        };
+        let run = |seed| {
+            let mut search = SimulatedAnnealing::with_object_seeded(0, cooling, Bits, seed);
+            (0..100)
+                .map(|_| {
+                    search.step();
+                    search.current().object
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(run(42), run(42));
+    }
+}
@@ -0,0 +1,4 @@
+//! Loopy partizan games
+
+pub mod games;
+pub mod loopy_game;
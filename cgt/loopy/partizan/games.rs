@@ -0,0 +1,3 @@
+//! Loopy partizan games
+
+pub mod fox_and_geese;
@@ -0,0 +1,272 @@
+//! Fox and Geese is played on the dark squares of a checkerboard.
+//!
+//! Left moves the Geese, that can
+//! only move diagonally forward (down the board) to an adjacent empty square. Right moves the Fox,
+//! that can move diagonally in any direction to an adjacent empty square. There are no captures,
+//! Geese win by trapping the Fox and Fox wins by escaping behind the Geese, as then the game never
+//! ends (the Geese can always eventually run out of moves, but Fox can move forever).
+//!
+//! Since the Fox can move back and forth the game is loopy. Geese must move every other turn and
+//! they can only move forward, so there is no infinite alternating play and every position is a
+//! stopper, equal to its own onside and offside. Values are found by converting positions to
+//! [`LoopyGame`] with [`FoxAndGeese::to_loopy_game`].
+
+use crate::{
+    grid::{vec_grid::VecGrid, FiniteGrid, Grid, ParseGridError},
+    loopy::partizan::loopy_game::LoopyGame,
+};
+use cgt_derive::Tile;
+use std::{collections::HashMap, fmt::Display, hash::Hash, str::FromStr};
+
+/// Tile on the Fox and Geese board
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Tile)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tile {
+    /// Empty square
+    #[tile(char('.'), default)]
+    Empty,
+
+    /// Left player's piece
+    #[tile(char('G'))]
+    Goose,
+
+    /// Right player's piece
+    #[tile(char('F'))]
+    Fox,
+}
+
+/// Position of a Fox and Geese game
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FoxAndGeese<G = VecGrid<Tile>> {
+    grid: G,
+}
+
+impl<G> Display for FoxAndGeese<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.grid.display(f, '|')
+    }
+}
+
+impl<G> FromStr for FoxAndGeese<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid + Clone,
+{
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        G::try_parse(s).map(Self::new)
+    }
+}
+
+impl<G> FoxAndGeese<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid + Clone,
+{
+    /// Create new position from a grid
+    #[inline]
+    pub const fn new(grid: G) -> Self {
+        Self { grid }
+    }
+
+    /// Standard starting position on 8x8 board with four Geese in the top row and Fox in the
+    /// bottom left corner
+    ///
+    /// # Panics
+    /// - Grid cannot fit 8x8 board
+    pub fn standard() -> Self {
+        let mut grid = G::filled(8, 8, Tile::Empty).expect("Grid must fit 8x8 board");
+        for x in [1, 3, 5, 7] {
+            grid.set(x, 0, Tile::Goose);
+        }
+        grid.set(0, 7, Tile::Fox);
+        Self::new(grid)
+    }
+
+    /// Get underlying grid
+    #[inline]
+    pub const fn grid(&self) -> &G {
+        &self.grid
+    }
+
+    fn moves_for(&self, piece: Tile, directions: &[(i32, i32)]) -> Vec<Self> {
+        let mut moves = Vec::new();
+
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                if self.grid.get(x, y) != piece {
+                    continue;
+                }
+
+                for (dir_x, dir_y) in directions {
+                    let new_x = x as i32 + dir_x;
+                    let new_y = y as i32 + dir_y;
+                    if new_x < 0
+                        || new_x >= self.grid.width() as i32
+                        || new_y < 0
+                        || new_y >= self.grid.height() as i32
+                        || self.grid.get(new_x as u8, new_y as u8) != Tile::Empty
+                    {
+                        continue;
+                    }
+
+                    let mut new_grid = self.grid.clone();
+                    new_grid.set(x, y, Tile::Empty);
+                    new_grid.set(new_x as u8, new_y as u8, piece);
+                    moves.push(Self::new(new_grid));
+                }
+            }
+        }
+
+        moves
+    }
+
+    /// Get moves of the Geese
+    pub fn left_moves(&self) -> Vec<Self> {
+        self.moves_for(Tile::Goose, &[(-1, 1), (1, 1)])
+    }
+
+    /// Get moves of the Fox
+    pub fn right_moves(&self) -> Vec<Self> {
+        self.moves_for(Tile::Fox, &[(-1, 1), (1, 1), (-1, -1), (1, -1)])
+    }
+}
+
+impl<G> FoxAndGeese<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid + Clone + Hash + Eq,
+{
+    /// Build the game graph of all positions reachable from this one, starting at this position
+    pub fn to_loopy_game(&self) -> LoopyGame {
+        let mut indices = HashMap::new();
+        let mut positions = vec![self.clone()];
+        indices.insert(self.clone(), 0);
+
+        let mut edges = Vec::new();
+        let mut next = 0;
+        while let Some(position) = positions.get(next).cloned() {
+            for (is_left, moves) in [
+                (true, position.left_moves()),
+                (false, position.right_moves()),
+            ] {
+                for to in moves {
+                    let to_idx = *indices.entry(to.clone()).or_insert_with(|| {
+                        positions.push(to);
+                        positions.len() - 1
+                    });
+                    edges.push((is_left, next, to_idx));
+                }
+            }
+            next += 1;
+        }
+
+        let mut game = LoopyGame::new(positions.len());
+        for (is_left, from, to) in edges {
+            if is_left {
+                game.add_left_move(from, to);
+            } else {
+                game.add_right_move(from, to);
+            }
+        }
+        game
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::canonical_form::CanonicalForm;
+
+    fn short(input: &str) -> LoopyGame {
+        LoopyGame::from_canonical_form(&CanonicalForm::from_str(input).unwrap())
+    }
+
+    fn value_of(position: &str) -> LoopyGame {
+        let position: FoxAndGeese = FoxAndGeese::from_str(position).unwrap();
+        position.to_loopy_game()
+    }
+
+    fn assert_value(game: &LoopyGame, value: &LoopyGame) {
+        assert!(game.is_stopper());
+        for side in [game.onside(), game.offside()] {
+            assert!(LoopyGame::leq(&side, value) && LoopyGame::leq(value, &side));
+        }
+    }
+
+    #[test]
+    fn standard_position_moves() {
+        let position: FoxAndGeese = FoxAndGeese::standard();
+        assert_eq!(
+            &position.to_string(),
+            ".G.G.G.G|........|........|........|........|........|........|F......."
+        );
+        assert_eq!(position.left_moves().len(), 7);
+        assert_eq!(
+            position.right_moves(),
+            vec![FoxAndGeese::from_str(
+                ".G.G.G.G|........|........|........|........|........|.F......|........"
+            )
+            .unwrap()]
+        );
+    }
+
+    #[test]
+    fn trapped_fox() {
+        let position: FoxAndGeese = FoxAndGeese::from_str("F...|.G..|....").unwrap();
+        assert_eq!(position.right_moves(), vec![]);
+        assert_eq!(position.left_moves().len(), 2);
+    }
+
+    #[test]
+    fn values() {
+        // Fox is trapped, but either Goose move sets it free
+        assert_value(&value_of("F...|.G..|...."), &short("0"));
+
+        // Fox gets behind the Geese and can move forever
+        assert_value(&value_of(".G.G|....|..F.|...."), &LoopyGame::off());
+
+        let over = LoopyGame::over();
+        assert_value(&value_of(".G.G|....|....|F..."), &over);
+        assert_value(&value_of("G.G.|....|....|.F.."), &over);
+
+        assert_value(
+            &value_of(".G.G.G|......|......|......|......|F....."),
+            &short("*"),
+        );
+        assert_value(
+            &value_of(".G.G.G|......|......|......|......|..F..."),
+            &short("*"),
+        );
+    }
+
+    #[test]
+    fn trapped_fox_values() {
+        // Geese that do not guard the Fox count their spare moves
+        assert_value(&value_of("F...|.G..|....|..G."), &short("0"));
+        assert_value(&value_of("F...|.G.G|....|...."), &short("2"));
+        assert_value(&value_of("F.G.|.G..|....|...."), &short("3"));
+        assert_value(
+            &value_of("F.....|.G....|......|.....G|......|......"),
+            &short("2"),
+        );
+    }
+
+    #[test]
+    fn small_board_value() {
+        // Like on the standard board, Geese win whoever starts
+        let value = LoopyGame::construct_sum(&short("1"), &LoopyGame::over());
+        assert_value(&value_of(".G.G.G|......|......|F....."), &value);
+    }
+
+    #[test]
+    #[ignore = "standard position has about a million followers"]
+    fn standard_position_value() {
+        // Geese win whoever starts, as shown in Winning Ways
+        let position: FoxAndGeese = FoxAndGeese::standard();
+        let value = LoopyGame::construct_sum(&short("2"), &LoopyGame::over());
+        assert_value(&position.to_loopy_game(), &value);
+    }
+}
@@ -0,0 +1,689 @@
+//! Loopy partizan games represented as directed game graphs, where positions may repeat.
+//!
+//! Game graph has two kinds of edges: Left edges and Right edges. Play may continue forever,
+//! in which case the game is a draw. A game is a stopper if there is no infinite alternating
+//! sequence of moves, i.e. play must end when players alternate (although a position may still
+//! repeat when one player moves several times in a row in different components).
+//!
+//! Order on stoppers is given by survival: `G ≥ H` iff Left survives (does not run out of moves)
+//! in `G - H` when Right starts. Every game `G` is a sidling `G = G⁺ & G⁻` of two stoppers, its
+//! onside `G⁺` and offside `G⁻`, and general games are compared by both of them. Onside is found
+//! by approximating it from above with layered copies of the graph, where Right moves closing
+//! alternating cycles go to the copy below, see [`LoopyGame::onside`]. Offside is found the same
+//! way with roles of players swapped.
+
+use crate::short::partizan::canonical_form::CanonicalForm;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Outcome of a loopy game when given player starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LoopyOutcome {
+    /// Left wins
+    Left,
+
+    /// Right wins
+    Right,
+
+    /// Play continues forever
+    Draw,
+}
+
+/// Player to move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Turn {
+    Left,
+    Right,
+}
+
+/// Index of `(position, turn)` node in the graph of alternating play
+const fn node_index(position: usize, turn: Turn) -> usize {
+    match turn {
+        Turn::Left => 2 * position,
+        Turn::Right => 2 * position + 1,
+    }
+}
+
+/// Directed game graph with a distinguished starting position
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoopyGame {
+    left_moves: Vec<Vec<usize>>,
+    right_moves: Vec<Vec<usize>>,
+    start: usize,
+}
+
+impl LoopyGame {
+    /// Create a new game graph with `size` positions without any moves. Starting position is `0`.
+    ///
+    /// # Panics
+    /// - `size` is zero
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "Game graph must have at least one position");
+        Self {
+            left_moves: vec![Vec::new(); size],
+            right_moves: vec![Vec::new(); size],
+            start: 0,
+        }
+    }
+
+    /// Add a Left move from position `from` to position `to`
+    pub fn add_left_move(&mut self, from: usize, to: usize) {
+        self.left_moves[from].push(to);
+    }
+
+    /// Add a Right move from position `from` to position `to`
+    pub fn add_right_move(&mut self, from: usize, to: usize) {
+        self.right_moves[from].push(to);
+    }
+
+    /// Change the starting position of the game
+    pub const fn set_start(&mut self, start: usize) {
+        self.start = start;
+    }
+
+    /// Number of positions in the game graph
+    pub const fn size(&self) -> usize {
+        self.left_moves.len()
+    }
+
+    /// Left moves from given position
+    pub fn left_moves(&self, position: usize) -> &[usize] {
+        &self.left_moves[position]
+    }
+
+    /// Right moves from given position
+    pub fn right_moves(&self, position: usize) -> &[usize] {
+        &self.right_moves[position]
+    }
+
+    /// `on = {on|}`
+    pub fn on() -> Self {
+        let mut game = Self::new(1);
+        game.add_left_move(0, 0);
+        game
+    }
+
+    /// `off = {|off}`
+    pub fn off() -> Self {
+        -Self::on()
+    }
+
+    /// `dud = {dud|dud}`
+    pub fn dud() -> Self {
+        let mut game = Self::new(1);
+        game.add_left_move(0, 0);
+        game.add_right_move(0, 0);
+        game
+    }
+
+    /// `over = {0|over}`
+    pub fn over() -> Self {
+        let mut game = Self::new(2);
+        game.add_left_move(0, 1);
+        game.add_right_move(0, 0);
+        game
+    }
+
+    /// Embed a short game into the game graph
+    pub fn from_canonical_form(canonical_form: &CanonicalForm) -> Self {
+        fn go(
+            game: &mut LoopyGame,
+            seen: &mut HashMap<CanonicalForm, usize>,
+            canonical_form: &CanonicalForm,
+        ) -> usize {
+            if let Some(&position) = seen.get(canonical_form) {
+                return position;
+            }
+            let position = game.left_moves.len();
+            game.left_moves.push(Vec::new());
+            game.right_moves.push(Vec::new());
+            seen.insert(canonical_form.clone(), position);
+
+            let moves = canonical_form.to_moves();
+            for left_move in &moves.left {
+                let to = go(game, seen, left_move);
+                game.add_left_move(position, to);
+            }
+            for right_move in &moves.right {
+                let to = go(game, seen, right_move);
+                game.add_right_move(position, to);
+            }
+            position
+        }
+
+        let mut game = Self {
+            left_moves: Vec::new(),
+            right_moves: Vec::new(),
+            start: 0,
+        };
+        go(&mut game, &mut HashMap::new(), canonical_form);
+        game
+    }
+
+    /// Construct a disjunctive sum of two games
+    pub fn construct_sum(g: &Self, h: &Self) -> Self {
+        let index = |g_position: usize, h_position: usize| g_position * h.size() + h_position;
+
+        let mut sum = Self::new(g.size() * h.size());
+        for g_position in 0..g.size() {
+            for h_position in 0..h.size() {
+                let position = index(g_position, h_position);
+                for &to in &g.left_moves[g_position] {
+                    sum.add_left_move(position, index(to, h_position));
+                }
+                for &to in &h.left_moves[h_position] {
+                    sum.add_left_move(position, index(g_position, to));
+                }
+                for &to in &g.right_moves[g_position] {
+                    sum.add_right_move(position, index(to, h_position));
+                }
+                for &to in &h.right_moves[h_position] {
+                    sum.add_right_move(position, index(g_position, to));
+                }
+            }
+        }
+        sum.start = index(g.start, h.start);
+        sum
+    }
+
+    /// Construct a negative of a game by swapping Left and Right moves
+    #[must_use]
+    pub fn construct_negative(&self) -> Self {
+        Self {
+            left_moves: self.right_moves.clone(),
+            right_moves: self.left_moves.clone(),
+            start: self.start,
+        }
+    }
+
+    fn moves(&self, position: usize, turn: Turn) -> &[usize] {
+        match turn {
+            Turn::Left => &self.left_moves[position],
+            Turn::Right => &self.right_moves[position],
+        }
+    }
+
+    /// Retrograde analysis: for each `(position, turn)` node compute if `loser` eventually runs
+    /// out of moves regardless of what they play
+    fn losing_nodes(&self, loser: Turn) -> Vec<bool> {
+        let other = |turn| match turn {
+            Turn::Left => Turn::Right,
+            Turn::Right => Turn::Left,
+        };
+
+        let mut predecessors = vec![Vec::new(); 2 * self.size()];
+        let mut remaining_moves = vec![0usize; 2 * self.size()];
+        for position in 0..self.size() {
+            for turn in [Turn::Left, Turn::Right] {
+                let node = node_index(position, turn);
+                remaining_moves[node] = self.moves(position, turn).len();
+                for &to in self.moves(position, turn) {
+                    predecessors[node_index(to, other(turn))].push((position, turn));
+                }
+            }
+        }
+
+        let mut losing = vec![false; 2 * self.size()];
+        let mut queue = VecDeque::new();
+        for position in 0..self.size() {
+            if self.moves(position, loser).is_empty() {
+                let node = node_index(position, loser);
+                losing[node] = true;
+                queue.push_back(node);
+            }
+        }
+
+        while let Some(node) = queue.pop_front() {
+            for &(position, turn) in &predecessors[node] {
+                let predecessor = node_index(position, turn);
+                if losing[predecessor] {
+                    continue;
+                }
+                if turn == loser {
+                    // Loser loses only if every move is losing
+                    remaining_moves[predecessor] -= 1;
+                    if remaining_moves[predecessor] == 0 {
+                        losing[predecessor] = true;
+                        queue.push_back(predecessor);
+                    }
+                } else {
+                    // Winner needs only one move
+                    losing[predecessor] = true;
+                    queue.push_back(predecessor);
+                }
+            }
+        }
+
+        losing
+    }
+
+    fn outcome(&self, turn: Turn) -> LoopyOutcome {
+        let node = node_index(self.start, turn);
+        if self.losing_nodes(Turn::Right)[node] {
+            LoopyOutcome::Left
+        } else if self.losing_nodes(Turn::Left)[node] {
+            LoopyOutcome::Right
+        } else {
+            LoopyOutcome::Draw
+        }
+    }
+
+    /// Outcome of the game when Left starts
+    pub fn left_outcome(&self) -> LoopyOutcome {
+        self.outcome(Turn::Left)
+    }
+
+    /// Outcome of the game when Right starts
+    pub fn right_outcome(&self) -> LoopyOutcome {
+        self.outcome(Turn::Right)
+    }
+
+    /// Check if the game is a stopper, i.e. there is no infinite alternating sequence of moves
+    /// from the starting position
+    pub fn is_stopper(&self) -> bool {
+        // Remove nodes that cannot reach a cycle by repeatedly removing sinks
+        let mut predecessors = vec![Vec::new(); 2 * self.size()];
+        let mut out_degree = vec![0usize; 2 * self.size()];
+        for position in 0..self.size() {
+            for (turn, next_turn) in [(Turn::Left, Turn::Right), (Turn::Right, Turn::Left)] {
+                let node = node_index(position, turn);
+                out_degree[node] = self.moves(position, turn).len();
+                for &to in self.moves(position, turn) {
+                    predecessors[node_index(to, next_turn)].push(node);
+                }
+            }
+        }
+
+        let mut queue: VecDeque<usize> = (0..2 * self.size())
+            .filter(|node| out_degree[*node] == 0)
+            .collect();
+        let mut removed = vec![false; 2 * self.size()];
+        while let Some(node) = queue.pop_front() {
+            removed[node] = true;
+            for &predecessor in &predecessors[node] {
+                out_degree[predecessor] -= 1;
+                if out_degree[predecessor] == 0 {
+                    queue.push_back(predecessor);
+                }
+            }
+        }
+
+        removed[node_index(self.start, Turn::Left)] && removed[node_index(self.start, Turn::Right)]
+    }
+
+    /// Graph of alternating play, with an edge from `(position, turn)` to every move of the
+    /// player to move, after which the other player is to move
+    fn alternating_successors(&self) -> Vec<Vec<usize>> {
+        let mut successors = vec![Vec::new(); 2 * self.size()];
+        for position in 0..self.size() {
+            for (turn, next_turn) in [(Turn::Left, Turn::Right), (Turn::Right, Turn::Left)] {
+                successors[node_index(position, turn)] = self
+                    .moves(position, turn)
+                    .iter()
+                    .map(|&to| node_index(to, next_turn))
+                    .collect();
+            }
+        }
+        successors
+    }
+
+    /// Right moves `(position, index of the move)` on cycles of alternating play
+    fn alternating_cycle_right_moves(&self) -> Vec<(usize, usize)> {
+        let successors = self.alternating_successors();
+        let mut predecessors = vec![Vec::new(); successors.len()];
+        for (node, nodes) in successors.iter().enumerate() {
+            for &to in nodes {
+                predecessors[to].push(node);
+            }
+        }
+
+        // Kosaraju's algorithm: order nodes by finishing time, then collect components of the
+        // reversed graph in reverse finishing order
+        let mut finished = Vec::with_capacity(successors.len());
+        let mut visited = vec![false; successors.len()];
+        for root in 0..successors.len() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut stack = vec![(root, 0)];
+            while let Some((node, next)) = stack.pop() {
+                if let Some(&to) = successors[node].get(next) {
+                    stack.push((node, next + 1));
+                    if !visited[to] {
+                        visited[to] = true;
+                        stack.push((to, 0));
+                    }
+                } else {
+                    finished.push(node);
+                }
+            }
+        }
+
+        let mut component = vec![usize::MAX; successors.len()];
+        for (idx, &root) in finished.iter().rev().enumerate() {
+            if component[root] != usize::MAX {
+                continue;
+            }
+            component[root] = idx;
+            let mut stack = vec![root];
+            while let Some(node) = stack.pop() {
+                for &from in &predecessors[node] {
+                    if component[from] == usize::MAX {
+                        component[from] = idx;
+                        stack.push(from);
+                    }
+                }
+            }
+        }
+
+        let mut moves = Vec::new();
+        for position in 0..self.size() {
+            for (idx, &to) in self.right_moves[position].iter().enumerate() {
+                if component[node_index(position, Turn::Right)]
+                    == component[node_index(to, Turn::Left)]
+                {
+                    moves.push((position, idx));
+                }
+            }
+        }
+        moves
+    }
+
+    /// Stopper approximating the onside from above with `layers` copies of the graph. Right moves
+    /// on cycles of alternating play go to the copy below, and are removed from the bottom one.
+    /// Position `position` of copy `layer` is at index `layer * self.size() + position`.
+    fn layered(&self, cycle_moves: &[(usize, usize)], layers: usize) -> Self {
+        let size = self.size();
+        let mut game = Self::new(size * layers);
+        for layer in 0..layers {
+            for position in 0..size {
+                let from = layer * size + position;
+                for &to in &self.left_moves[position] {
+                    game.add_left_move(from, layer * size + to);
+                }
+                for (idx, &to) in self.right_moves[position].iter().enumerate() {
+                    if !cycle_moves.contains(&(position, idx)) {
+                        game.add_right_move(from, layer * size + to);
+                    } else if layer > 0 {
+                        game.add_right_move(from, (layer - 1) * size + to);
+                    }
+                }
+            }
+        }
+        game.start = (layers - 1) * size + self.start;
+        game
+    }
+
+    /// Approximation of the onside with `layers` copies of the graph, if it is equal to the
+    /// previous one at every position, see [`LoopyGame::onside`]
+    fn stable_approximation(&self, lowered: &[(usize, usize)], layers: usize) -> Option<Self> {
+        let size = self.size();
+        let approximation = self.layered(lowered, layers);
+        let difference = Self::construct_sum(&approximation, &approximation.construct_negative());
+        let losing = difference.losing_nodes(Turn::Left);
+        let leq = |lhs: usize, rhs: usize| {
+            !losing[node_index(rhs * approximation.size() + lhs, Turn::Right)]
+        };
+        let (top, below) = ((layers - 1) * size, (layers - 2) * size);
+        (0..size)
+            .all(|position| {
+                leq(top + position, below + position) && leq(below + position, top + position)
+            })
+            .then_some(approximation)
+    }
+
+    /// Right moves on cycles of alternating play that remain after removing given Right moves
+    /// `(position, index of the move)`. Indices refer to moves of the original game.
+    fn remaining_cycle_right_moves(&self, removed: &[(usize, usize)]) -> Vec<(usize, usize)> {
+        let mut game = self.clone();
+        let mut kept = Vec::with_capacity(self.size());
+        for (position, right_moves) in game.right_moves.iter_mut().enumerate() {
+            let indices = (0..right_moves.len())
+                .filter(|idx| !removed.contains(&(position, *idx)))
+                .collect::<Vec<_>>();
+            *right_moves = indices.iter().map(|&idx| right_moves[idx]).collect();
+            kept.push(indices);
+        }
+        game.alternating_cycle_right_moves()
+            .into_iter()
+            .map(|(position, idx)| (position, kept[position][idx]))
+            .collect()
+    }
+
+    /// Minimal sets of Right moves that break every cycle of alternating play, i.e. sets of
+    /// moves that can be lowered when sidling. Sets with fewer passes come first, as a lowered
+    /// pass tends to make approximations of its position approach the value without reaching
+    /// it, like `1, 1/2, 1/4, ...` approaching `over`.
+    fn lowered_right_moves(&self) -> Vec<Vec<(usize, usize)>> {
+        let is_pass =
+            |&(position, idx): &(usize, usize)| self.right_moves[position][idx] == position;
+
+        let mut minimal = Vec::new();
+        let mut seen = HashSet::new();
+        let mut stack = vec![self.alternating_cycle_right_moves()];
+        while let Some(lowered) = stack.pop() {
+            if !seen.insert(lowered.clone()) {
+                continue;
+            }
+            let mut is_minimal = true;
+            for idx in 0..lowered.len() {
+                let mut remaining = lowered.clone();
+                remaining.remove(idx);
+                if self.remaining_cycle_right_moves(&remaining).is_empty() {
+                    is_minimal = false;
+                    stack.push(remaining);
+                }
+            }
+            if is_minimal {
+                minimal.push(lowered);
+            }
+        }
+        minimal.sort_by_key(|lowered| {
+            lowered
+                .iter()
+                .filter(|right_move| is_pass(right_move))
+                .count()
+        });
+        minimal
+    }
+
+    /// Get the onside `G⁺` of the game, a stopper that Left can use as `G` when infinite play
+    /// is counted as her win
+    ///
+    /// Computed by sidling. Some Right moves on cycles of alternating play are lowered, so that
+    /// every cycle has a lowered move. First approximation is the game without lowered moves,
+    /// i.e. with them going to `on`, and every next one is `{G⁺(G^L) | G⁺(G^R)}` where only
+    /// lowered moves go to the previous approximation. Approximations decrease towards the
+    /// onside whatever moves are lowered, and once two consecutive ones are equal at every
+    /// position they are the onside. A position may approach its value without reaching it,
+    /// like `{0 | over}` when its pass is lowered, so every minimal set of lowered moves is
+    /// approximated side by side until one of them stabilizes.
+    ///
+    /// Stoppers are their own onsides.
+    #[must_use]
+    pub fn onside(&self) -> Self {
+        if self.is_stopper() {
+            return self.clone();
+        }
+
+        let lowered = self.lowered_right_moves();
+        let mut layers = 2;
+        loop {
+            if let Some(onside) = lowered
+                .iter()
+                .find_map(|lowered| self.stable_approximation(lowered, layers))
+            {
+                return onside;
+            }
+            layers += 1;
+        }
+    }
+
+    /// Get the offside `G⁻` of the game, a stopper that Right can use as `G` when infinite play
+    /// is counted as his win. See [`LoopyGame::onside`]
+    #[must_use]
+    pub fn offside(&self) -> Self {
+        self.construct_negative().onside().construct_negative()
+    }
+
+    /// Check if `lhs ≤ rhs` for stoppers, i.e. Left survives `rhs - lhs` when Right starts
+    fn stopper_leq(lhs: &Self, rhs: &Self) -> bool {
+        let difference = Self::construct_sum(rhs, &lhs.construct_negative());
+        let losing = difference.losing_nodes(Turn::Left);
+        !losing[node_index(difference.start, Turn::Right)]
+    }
+
+    /// Check if `lhs ≤ rhs`, comparing onsides and offsides of both games. For stoppers this is
+    /// whether Left survives `rhs - lhs` when Right starts
+    pub fn leq(lhs: &Self, rhs: &Self) -> bool {
+        Self::stopper_leq(&lhs.onside(), &rhs.onside())
+            && Self::stopper_leq(&lhs.offside(), &rhs.offside())
+    }
+}
+
+impl std::ops::Neg for LoopyGame {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.construct_negative()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn short(input: &str) -> LoopyGame {
+        LoopyGame::from_canonical_form(&CanonicalForm::from_str(input).unwrap())
+    }
+
+    #[test]
+    fn stoppers() {
+        assert!(LoopyGame::on().is_stopper());
+        assert!(LoopyGame::off().is_stopper());
+        assert!(LoopyGame::over().is_stopper());
+        assert!(short("{1|-1}").is_stopper());
+        assert!(!LoopyGame::dud().is_stopper());
+    }
+
+    #[test]
+    fn outcomes() {
+        assert_eq!(LoopyGame::on().left_outcome(), LoopyOutcome::Left);
+        assert_eq!(LoopyGame::on().right_outcome(), LoopyOutcome::Left);
+        assert_eq!(LoopyGame::dud().right_outcome(), LoopyOutcome::Draw);
+        assert_eq!(short("*").left_outcome(), LoopyOutcome::Left);
+        assert_eq!(short("*").right_outcome(), LoopyOutcome::Right);
+    }
+
+    #[test]
+    fn comparison() {
+        let on = LoopyGame::on();
+        let over = LoopyGame::over();
+
+        assert!(LoopyGame::leq(&short("100"), &on));
+        assert!(!LoopyGame::leq(&on, &short("100")));
+        assert!(LoopyGame::leq(&LoopyGame::off(), &short("-100")));
+
+        assert!(LoopyGame::leq(&short("0"), &over));
+        assert!(!LoopyGame::leq(&over, &short("0")));
+        assert!(LoopyGame::leq(&over, &short("1/8")));
+
+        assert!(LoopyGame::leq(&short("{1|-1}"), &short("{1|-1}")));
+        assert!(!LoopyGame::leq(&short("*"), &short("0")));
+    }
+
+    fn equal(lhs: &LoopyGame, rhs: &LoopyGame) -> bool {
+        LoopyGame::leq(lhs, rhs) && LoopyGame::leq(rhs, lhs)
+    }
+
+    /// `tis = {tisn|}` and `tisn = {|tis}`, starting at `tis`
+    fn tis() -> LoopyGame {
+        let mut game = LoopyGame::new(2);
+        game.add_left_move(0, 1);
+        game.add_right_move(1, 0);
+        game
+    }
+
+    #[test]
+    fn stoppers_are_own_sides() {
+        for game in [
+            LoopyGame::on(),
+            LoopyGame::off(),
+            LoopyGame::over(),
+            short("{1|*}"),
+        ] {
+            assert!(equal(&game.onside(), &game));
+            assert!(equal(&game.offside(), &game));
+        }
+    }
+
+    #[test]
+    fn sides() {
+        let dud = LoopyGame::dud();
+        assert!(equal(&dud.onside(), &LoopyGame::on()));
+        assert!(equal(&dud.offside(), &LoopyGame::off()));
+
+        let tis = tis();
+        assert!(tis.onside().is_stopper());
+        assert!(equal(&tis.onside(), &short("1")));
+        assert!(equal(&tis.offside(), &short("0")));
+
+        let mut tisn = tis;
+        tisn.set_start(1);
+        assert!(equal(&tisn.onside(), &short("0")));
+        assert!(equal(&tisn.offside(), &short("-1")));
+
+        // Right pass needs to be kept in place for approximations to stabilize
+        let mut game = LoopyGame::new(3);
+        game.add_left_move(0, 1);
+        game.add_left_move(1, 2);
+        game.add_right_move(1, 1);
+        game.add_right_move(2, 0);
+        let onside = game.onside();
+        assert!(onside.is_stopper());
+        assert!(equal(&onside, &short("1")));
+        assert!(LoopyGame::leq(&game.offside(), &onside));
+
+        // Right can move back and forth between the first and the last position forever
+        let mut game = LoopyGame::new(3);
+        game.add_left_move(0, 0);
+        game.add_left_move(0, 1);
+        game.add_left_move(1, 0);
+        game.add_left_move(1, 2);
+        game.add_right_move(0, 2);
+        game.add_right_move(1, 1);
+        game.add_right_move(1, 2);
+        game.add_right_move(2, 0);
+        game.add_right_move(2, 1);
+        let (onside, offside) = (game.onside(), game.offside());
+        assert!(onside.is_stopper() && offside.is_stopper());
+        assert!(LoopyGame::leq(&offside, &onside));
+        assert!(equal(&onside, &LoopyGame::off()));
+        assert!(equal(&offside, &LoopyGame::off()));
+    }
+
+    #[test]
+    fn non_stopper_comparison() {
+        let dud = LoopyGame::dud();
+        let on = LoopyGame::on();
+        let off = LoopyGame::off();
+
+        assert!(LoopyGame::leq(&dud, &on));
+        assert!(!LoopyGame::leq(&on, &dud));
+        assert!(LoopyGame::leq(&off, &dud));
+        assert!(!LoopyGame::leq(&short("0"), &dud));
+        assert!(!LoopyGame::leq(&dud, &short("0")));
+        assert!(equal(&dud, &LoopyGame::dud()));
+
+        let tis = tis();
+        assert!(LoopyGame::leq(&short("0"), &tis));
+        assert!(LoopyGame::leq(&tis, &short("1")));
+        assert!(!LoopyGame::leq(&short("1"), &tis));
+        assert!(!LoopyGame::leq(&tis, &short("0")));
+        assert!(!LoopyGame::leq(&tis, &dud));
+        assert!(LoopyGame::leq(&short("-1"), &tis));
+    }
+}
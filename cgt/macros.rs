@@ -56,6 +56,29 @@ macro_rules! if_chain {
 
 pub(crate) use if_chain;
 
+/// Enter a `tracing` span at given level until the end of the current block. Does nothing
+/// without the `tracing` feature.
+macro_rules! trace_span {
+    ($level:ident, $($args:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::span!(tracing::Level::$level, $($args)*).entered();
+    };
+}
+
+pub(crate) use trace_span;
+
+/// Emit a `tracing` event at `TRACE` level. Does nothing without the `tracing` feature.
+#[cfg(feature = "std")]
+macro_rules! trace_event {
+    ($($args:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::trace!($($args)*);
+    };
+}
+
+#[cfg(feature = "std")]
+pub(crate) use trace_event;
+
 #[test]
 #[allow(clippy::missing_const_for_fn)]
 fn test_if_chain() {
@@ -20,8 +20,10 @@ where
 // TODO: Fancy errors
 
 /// Implement [`std::str::FromStr`] using nom parser. Type must have `parse` method implemented.
+///
+/// Also implements serialization to and from strings, unless called with `without_serde`.
 macro_rules! impl_from_str_via_nom {
-    ($t: ident) => {
+    ($t: ident, without_serde) => {
         impl std::str::FromStr for $t {
             type Err = &'static str;
 
@@ -33,6 +35,9 @@ macro_rules! impl_from_str_via_nom {
                 }
             }
         }
+    };
+    ($t: ident) => {
+        $crate::nom_utils::impl_from_str_via_nom!($t, without_serde);
 
         #[cfg(feature = "serde")]
         impl serde::Serialize for $t {
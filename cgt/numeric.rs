@@ -1,6 +1,12 @@
 //! Various numerical types
 
+#[cfg(feature = "bigint")]
+pub mod big_rational;
 pub mod dyadic_rational_number;
+pub mod interval;
 pub mod nimber;
+pub mod ordinal;
 pub mod rational;
+pub mod surreal;
+#[cfg(feature = "std")]
 pub mod v2f;
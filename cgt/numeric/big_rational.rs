@@ -0,0 +1,586 @@
+//! Arbitrary precision counterparts of [`Rational`] and [`DyadicRationalNumber`]
+//!
+//! Results never overflow, at the cost of allocating. Values convert losslessly from the 64 bit
+//! types, and back when they fit.
+
+use crate::{
+    nom_utils::{self, impl_from_str_via_nom},
+    numeric::{dyadic_rational_number::DyadicRationalNumber, rational::Rational},
+};
+use auto_ops::impl_op_ex;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::Ratio;
+use num_traits::{One, Signed, Zero};
+use std::{cmp::Ordering, fmt::Display};
+
+#[cfg(test)]
+use std::str::FromStr;
+
+/// Parse optionally negative decimal integer of any size
+fn parse_big_integer(input: &str) -> nom::IResult<&str, BigInt> {
+    nom::combinator::map_res(
+        nom::combinator::recognize(nom::sequence::pair(
+            nom::combinator::opt(nom::character::complete::char('-')),
+            nom::character::complete::digit1,
+        )),
+        str::parse,
+    )(input)
+}
+
+/// Infinite rational number with arbitrary precision numerator and denominator
+#[derive(Debug, Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BigRational {
+    /// Negative infnity, smaller than all other values
+    NegativeInfinity,
+
+    /// A finite number
+    Value(Ratio<BigInt>),
+
+    /// Positive infnity, greater than all other values
+    PositiveInfinity,
+}
+
+impl BigRational {
+    /// Create a new rational. Panics if denominator is zero.
+    #[inline]
+    pub fn new(numerator: impl Into<BigInt>, denominator: impl Into<BigInt>) -> Self {
+        Self::Value(Ratio::new(numerator.into(), denominator.into()))
+    }
+
+    /// Check if value is infinite
+    #[inline]
+    pub const fn is_infinite(&self) -> bool {
+        !matches!(self, Self::Value(_))
+    }
+
+    fn parse(input: &str) -> nom::IResult<&str, Self> {
+        if let Ok((input, _)) =
+            nom_utils::lexeme(nom::bytes::complete::tag::<&str, &str, ()>("-∞"))(input)
+        {
+            return Ok((input, Self::NegativeInfinity));
+        }
+        if let Ok((input, _)) =
+            nom_utils::lexeme(nom::bytes::complete::tag::<&str, &str, ()>("∞"))(input)
+        {
+            return Ok((input, Self::PositiveInfinity));
+        }
+
+        let (input, numerator) = nom_utils::lexeme(parse_big_integer)(input)?;
+        match nom_utils::lexeme(nom::bytes::complete::tag::<&str, &str, ()>("/"))(input) {
+            Ok((input, _)) => {
+                let (input, denominator) = nom_utils::lexeme(parse_big_integer)(input)?;
+                if !denominator.is_positive() {
+                    return Err(nom::Err::Error(nom::error::Error::new(
+                        input,
+                        nom::error::ErrorKind::Verify,
+                    )));
+                }
+                Ok((input, Self::new(numerator, denominator)))
+            }
+            Err(_) => Ok((input, Self::from(numerator))),
+        }
+    }
+
+    /// Rounding towards zero
+    ///
+    /// # Errors
+    /// - Rational is infinite
+    pub fn try_round(&self) -> Option<BigInt> {
+        match self {
+            Self::Value(val) => Some(val.to_integer()),
+            Self::PositiveInfinity | Self::NegativeInfinity => None,
+        }
+    }
+
+    /// Convert to 64 bit rational
+    ///
+    /// # Errors
+    /// - Numerator does not fit in [`i64`] or denominator does not fit in [`u32`]
+    pub fn to_rational(&self) -> Option<Rational> {
+        match self {
+            Self::NegativeInfinity => Some(Rational::NegativeInfinity),
+            Self::Value(value) => Some(Rational::new(
+                i64::try_from(value.numer()).ok()?,
+                u32::try_from(value.denom()).ok()?,
+            )),
+            Self::PositiveInfinity => Some(Rational::PositiveInfinity),
+        }
+    }
+
+    /// Add two rationals, returning `None` when adding infinities of opposite signs
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Value(lhs), Self::Value(rhs)) => Some(Self::Value(lhs + rhs)),
+            (Self::PositiveInfinity, Self::NegativeInfinity)
+            | (Self::NegativeInfinity, Self::PositiveInfinity) => None,
+            (Self::PositiveInfinity | Self::NegativeInfinity, _) => Some(self.clone()),
+            (Self::Value(_), _) => Some(rhs.clone()),
+        }
+    }
+
+    /// Subtract two rationals, returning `None` when subtracting infinities of the same sign
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        self.checked_add(&-rhs)
+    }
+
+    /// Multiply two rationals, returning `None` when multiplying infinity by zero
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Value(lhs), Self::Value(rhs)) => Some(Self::Value(lhs * rhs)),
+            _ => Self::infinity_with_sign(self.sign(), rhs.sign()),
+        }
+    }
+
+    /// Divide two rationals, returning `None` on division by zero, or when dividing infinity by
+    /// infinity. Finite values divided by infinity are zero.
+    pub fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Value(lhs), Self::Value(rhs)) => {
+                (!rhs.is_zero()).then(|| Self::Value(lhs / rhs))
+            }
+            (Self::Value(_), _) => Some(Self::from(0)),
+            (_, Self::Value(_)) => Self::infinity_with_sign(self.sign(), rhs.sign()),
+            _ => None,
+        }
+    }
+
+    /// Sign of the rational, infinities included
+    fn sign(&self) -> Ordering {
+        match self {
+            Self::NegativeInfinity => Ordering::Less,
+            Self::Value(value) => value.numer().cmp(&BigInt::zero()),
+            Self::PositiveInfinity => Ordering::Greater,
+        }
+    }
+
+    /// Infinity with sign of product of values with given signs, `None` if any of them is zero
+    fn infinity_with_sign(lhs: Ordering, rhs: Ordering) -> Option<Self> {
+        match (lhs, rhs) {
+            (Ordering::Equal, _) | (_, Ordering::Equal) => None,
+            (lhs, rhs) if lhs == rhs => Some(Self::PositiveInfinity),
+            _ => Some(Self::NegativeInfinity),
+        }
+    }
+}
+
+impl From<Rational> for BigRational {
+    fn from(value: Rational) -> Self {
+        match value {
+            Rational::NegativeInfinity => Self::NegativeInfinity,
+            Rational::Value(value) => Self::new(*value.numer(), *value.denom()),
+            Rational::PositiveInfinity => Self::PositiveInfinity,
+        }
+    }
+}
+
+impl From<BigInt> for BigRational {
+    fn from(value: BigInt) -> Self {
+        Self::Value(Ratio::from_integer(value))
+    }
+}
+
+impl From<i64> for BigRational {
+    fn from(value: i64) -> Self {
+        Self::from(BigInt::from(value))
+    }
+}
+
+impl From<i32> for BigRational {
+    fn from(value: i32) -> Self {
+        Self::from(BigInt::from(value))
+    }
+}
+
+impl_op_ex!(+|lhs: &BigRational, rhs: &BigRational| -> BigRational {
+    lhs.checked_add(rhs).expect("rational addition adds infinities of opposite signs")
+});
+
+impl_op_ex!(+=|lhs: &mut BigRational, rhs: &BigRational| { *lhs = &*lhs + rhs });
+
+impl_op_ex!(-|lhs: &BigRational, rhs: &BigRational| -> BigRational {
+    lhs.checked_sub(rhs)
+        .expect("rational subtraction subtracts infinities of the same sign")
+});
+
+impl_op_ex!(-=|lhs: &mut BigRational, rhs: &BigRational| { *lhs = &*lhs - rhs });
+
+impl_op_ex!(*|lhs: &BigRational, rhs: &BigRational| -> BigRational {
+    lhs.checked_mul(rhs)
+        .expect("rational multiplication multiplies infinity by zero")
+});
+
+impl_op_ex!(*=|lhs: &mut BigRational, rhs: &BigRational| { *lhs = &*lhs * rhs });
+
+impl_op_ex!(/|lhs: &BigRational, rhs: &BigRational| -> BigRational {
+    lhs.checked_div(rhs).expect("rational division divides by zero or divides infinities")
+});
+
+impl_op_ex!(/=|lhs: &mut BigRational, rhs: &BigRational| { *lhs = &*lhs / rhs });
+
+impl_op_ex!(-|lhs: &BigRational| -> BigRational {
+    match lhs {
+        BigRational::NegativeInfinity => BigRational::PositiveInfinity,
+        BigRational::Value(value) => BigRational::Value(-value),
+        BigRational::PositiveInfinity => BigRational::NegativeInfinity,
+    }
+});
+
+impl Display for BigRational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NegativeInfinity => write!(f, "-∞"),
+            Self::Value(val) => write!(f, "{}", val),
+            Self::PositiveInfinity => write!(f, "∞"),
+        }
+    }
+}
+
+impl_from_str_via_nom!(BigRational);
+
+/// Number in form `n/2^m` with arbitrary precision numerator
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct BigDyadicRationalNumber {
+    numerator: BigInt,
+    denominator_exponent: u32,
+}
+
+impl BigDyadicRationalNumber {
+    /// Create a new dyadic
+    pub fn new(numerator: impl Into<BigInt>, denominator_exponent: u32) -> Self {
+        Self {
+            numerator: numerator.into(),
+            denominator_exponent,
+        }
+        .normalized()
+    }
+
+    /// Create a new integer
+    pub fn new_integer(number: impl Into<BigInt>) -> Self {
+        Self {
+            numerator: number.into(),
+            denominator_exponent: 0,
+        }
+    }
+
+    /// Create a new fraction. Returns [None] if denominator is not positive, or the number is
+    /// not dyadic
+    pub fn new_fraction(
+        numerator: impl Into<BigInt>,
+        denominator: impl Into<BigInt>,
+    ) -> Option<Self> {
+        let mut denominator = denominator.into();
+        if !denominator.is_positive() {
+            return None;
+        }
+
+        let mut denominator_exponent = 0;
+        while denominator.is_even() {
+            denominator >>= 1_u32;
+            denominator_exponent += 1;
+        }
+
+        denominator
+            .is_one()
+            .then(|| Self::new(numerator, denominator_exponent))
+    }
+
+    /// Get the numerator (`n` from `n/2^m`)
+    pub const fn numerator(&self) -> &BigInt {
+        &self.numerator
+    }
+
+    /// Get the denominator (`2^m` from `n/2^m`)
+    pub fn denominator(&self) -> BigInt {
+        BigInt::one() << self.denominator_exponent
+    }
+
+    /// Get denominator exponent (`m` from `n/2^m`)
+    pub const fn denominator_exponent(&self) -> u32 {
+        self.denominator_exponent
+    }
+
+    fn normalized(mut self) -> Self {
+        // [2*(n)]/[2*d] = n/d
+        while self.numerator.is_even() && self.denominator_exponent != 0 {
+            self.numerator >>= 1_u32;
+            self.denominator_exponent -= 1;
+        }
+        self
+    }
+
+    /// Convert to intger if it's an integer
+    pub fn to_integer(&self) -> Option<&BigInt> {
+        // exponent == 0 => denominator == 1 => It's an integer
+        (self.denominator_exponent == 0).then_some(&self.numerator)
+    }
+
+    /// Ceil division, i.e. the smallest integer greater or equal to the number
+    pub fn ceil(&self) -> BigInt {
+        -(-self).floor()
+    }
+
+    /// Floor division, i.e. the greatest integer less or equal to the number
+    pub fn floor(&self) -> BigInt {
+        // Shifts of negative numbers round towards negative infinity
+        &self.numerator >> self.denominator_exponent
+    }
+
+    /// Round a dyadic towards zero
+    pub fn round(&self) -> BigInt {
+        if self.numerator.is_negative() {
+            self.ceil()
+        } else {
+            self.floor()
+        }
+    }
+
+    /// Arithmetic mean of two rationals
+    #[must_use]
+    pub fn mean(&self, rhs: &Self) -> Self {
+        let sum = self + rhs;
+        Self::new(sum.numerator, sum.denominator_exponent + 1)
+    }
+
+    /// Convert to 64 bit dyadic, if numerator fits in [`i64`]
+    pub fn to_dyadic(&self) -> Option<DyadicRationalNumber> {
+        Some(DyadicRationalNumber::new(
+            i64::try_from(&self.numerator).ok()?,
+            self.denominator_exponent,
+        ))
+    }
+
+    /// Convert rational to dyadic
+    ///
+    /// # Errors
+    /// - Rational is infinite
+    /// - Rational is not dyadic
+    pub fn from_rational(rational: &BigRational) -> Option<Self> {
+        match rational {
+            BigRational::Value(value) => {
+                Self::new_fraction(value.numer().clone(), value.denom().clone())
+            }
+            BigRational::NegativeInfinity | BigRational::PositiveInfinity => None,
+        }
+    }
+
+    /// Convert dyadic to rational, which always fits
+    pub fn to_rational(&self) -> BigRational {
+        BigRational::Value(Ratio::new_raw(self.numerator.clone(), self.denominator()))
+    }
+
+    fn parse(input: &str) -> nom::IResult<&str, Self> {
+        let (input, numerator) = nom_utils::lexeme(parse_big_integer)(input)?;
+        match nom_utils::lexeme(nom::bytes::complete::tag::<&str, &str, ()>("/"))(input) {
+            Ok((input, _)) => {
+                let (input, denominator) = nom_utils::lexeme(parse_big_integer)(input)?;
+                Self::new_fraction(numerator, denominator).map_or_else(
+                    || {
+                        Err(nom::Err::Error(nom::error::Error::new(
+                            "Not a dyadic fraction",
+                            nom::error::ErrorKind::Verify,
+                        )))
+                    },
+                    |d| Ok((input, d)),
+                )
+            }
+            Err(_) => Ok((input, Self::new_integer(numerator))),
+        }
+    }
+}
+
+impl From<DyadicRationalNumber> for BigDyadicRationalNumber {
+    fn from(value: DyadicRationalNumber) -> Self {
+        Self::new(value.numerator(), value.denominator_exponent())
+    }
+}
+
+impl From<i64> for BigDyadicRationalNumber {
+    fn from(value: i64) -> Self {
+        Self::new_integer(value)
+    }
+}
+
+impl PartialOrd for BigDyadicRationalNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigDyadicRationalNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.denominator_exponent <= other.denominator_exponent {
+            let shift = other.denominator_exponent - self.denominator_exponent;
+            (&self.numerator << shift).cmp(&other.numerator)
+        } else {
+            let shift = self.denominator_exponent - other.denominator_exponent;
+            self.numerator.cmp(&(&other.numerator << shift))
+        }
+    }
+}
+
+impl_op_ex!(
+    +|lhs: &BigDyadicRationalNumber, rhs: &BigDyadicRationalNumber| -> BigDyadicRationalNumber {
+        let (finer, coarser) = if lhs.denominator_exponent >= rhs.denominator_exponent {
+            (lhs, rhs)
+        } else {
+            (rhs, lhs)
+        };
+        let shift = finer.denominator_exponent - coarser.denominator_exponent;
+        BigDyadicRationalNumber::new(
+            &finer.numerator + (&coarser.numerator << shift),
+            finer.denominator_exponent,
+        )
+    }
+);
+
+impl_op_ex!(+=|lhs: &mut BigDyadicRationalNumber, rhs: &BigDyadicRationalNumber| { *lhs = &*lhs + rhs; });
+
+impl_op_ex!(-|lhs: &BigDyadicRationalNumber,
+              rhs: &BigDyadicRationalNumber|
+ -> BigDyadicRationalNumber { lhs + (-rhs) });
+
+impl_op_ex!(-=|lhs: &mut BigDyadicRationalNumber, rhs: &BigDyadicRationalNumber| { *lhs = &*lhs - rhs; });
+
+impl_op_ex!(
+    -|lhs: &BigDyadicRationalNumber| -> BigDyadicRationalNumber {
+        BigDyadicRationalNumber {
+            numerator: -&lhs.numerator,
+            denominator_exponent: lhs.denominator_exponent,
+        }
+    }
+);
+
+impl Display for BigDyadicRationalNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(int) = self.to_integer() {
+            write!(f, "{}", int)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator())
+        }
+    }
+}
+
+impl_from_str_via_nom!(BigDyadicRationalNumber);
+
+#[test]
+fn rational_arbitrary_precision() {
+    let max = BigRational::from(Rational::from(i64::MAX));
+    let sum = &max + &max;
+    assert_eq!(sum.to_string(), "18446744073709551614");
+    assert_eq!(sum.to_rational(), None);
+    assert_eq!(&sum / &BigRational::from(2), max);
+    assert_eq!(-BigRational::from(i64::MIN), &max + &BigRational::from(1));
+    assert_eq!(
+        BigRational::from_str("1/36893488147419103232").unwrap(),
+        &BigRational::new(1, 2) * &BigRational::from_str("1/18446744073709551616").unwrap()
+    );
+    assert_eq!(
+        (&sum / &BigRational::from(4)).to_rational(),
+        Some(Rational::new(i64::MAX, 2))
+    );
+    assert_eq!(
+        BigRational::from(Rational::PositiveInfinity).to_rational(),
+        Some(Rational::PositiveInfinity)
+    );
+}
+
+#[test]
+fn rational_infinite_arithmetic() {
+    let inf = BigRational::PositiveInfinity;
+    let neg_inf = BigRational::NegativeInfinity;
+    assert_eq!(inf.checked_add(&neg_inf), None);
+    assert_eq!(inf.checked_sub(&inf), None);
+    assert_eq!(
+        BigRational::from(1).checked_sub(&inf),
+        Some(neg_inf.clone())
+    );
+    assert_eq!(
+        BigRational::from(-2).checked_mul(&neg_inf),
+        Some(inf.clone())
+    );
+    assert_eq!(BigRational::from(0).checked_mul(&inf), None);
+    assert_eq!(
+        BigRational::from(3).checked_div(&neg_inf),
+        Some(BigRational::from(0))
+    );
+    assert_eq!(
+        BigRational::from(1).checked_div(&BigRational::from(0)),
+        None
+    );
+    assert_eq!(inf.checked_div(&inf), None);
+}
+
+#[test]
+fn rational_parsing_works() {
+    for input in ["3/16", "42", "-1/2", "∞", "-∞", "1/18446744073709551616"] {
+        assert_eq!(&BigRational::from_str(input).unwrap().to_string(), input);
+    }
+}
+
+#[test]
+fn dyadic_arbitrary_precision() {
+    let max = BigDyadicRationalNumber::from(i64::MAX);
+    assert_eq!(
+        &max + BigDyadicRationalNumber::from(1),
+        BigDyadicRationalNumber::new(BigInt::from(i64::MAX) + 1, 0)
+    );
+    assert_eq!((&max + &max).to_dyadic(), None);
+    assert_eq!(
+        -BigDyadicRationalNumber::from(i64::MIN),
+        BigDyadicRationalNumber::new(-BigInt::from(i64::MIN), 0)
+    );
+
+    let tiny = BigDyadicRationalNumber::new(1, 100);
+    assert_eq!(
+        &tiny + BigDyadicRationalNumber::from(1),
+        BigDyadicRationalNumber::new((BigInt::from(1) << 100) + 1, 100)
+    );
+    assert_eq!(
+        tiny.to_rational(),
+        BigRational::new(1, BigInt::from(1) << 100)
+    );
+    assert_eq!(
+        BigDyadicRationalNumber::from_rational(&tiny.to_rational()),
+        Some(tiny.clone())
+    );
+    assert_eq!(tiny.to_dyadic(), Some(DyadicRationalNumber::new(1, 100)));
+    assert_eq!(
+        BigDyadicRationalNumber::from(DyadicRationalNumber::new(3, 2)).mean(&tiny),
+        BigDyadicRationalNumber::new(3 * (BigInt::from(1) << 98) + 1, 101)
+    );
+}
+
+#[test]
+fn dyadic_ordering_and_rounding() {
+    let tiny = BigDyadicRationalNumber::new(1, 100);
+    assert!(tiny < BigDyadicRationalNumber::from(1));
+    assert!(BigDyadicRationalNumber::new(-1, 100) < tiny);
+    assert!(BigDyadicRationalNumber::from(0) > BigDyadicRationalNumber::new(-1, 70));
+    assert_eq!(BigDyadicRationalNumber::new(-5, 2).ceil(), BigInt::from(-1));
+    assert_eq!(
+        BigDyadicRationalNumber::new(-5, 2).floor(),
+        BigInt::from(-2)
+    );
+    assert_eq!(
+        BigDyadicRationalNumber::new(-7, 1).round(),
+        BigInt::from(-3)
+    );
+    assert_eq!(
+        BigDyadicRationalNumber::new(-1, 100).ceil(),
+        BigInt::from(0)
+    );
+}
+
+#[test]
+fn dyadic_parsing_works() {
+    for input in ["3/16", "42", "-1/2", "1/1267650600228229401496703205376"] {
+        assert_eq!(
+            &BigDyadicRationalNumber::from_str(input)
+                .unwrap()
+                .to_string(),
+            input
+        );
+    }
+    assert!(BigDyadicRationalNumber::from_str("2/3").is_err());
+}
@@ -5,12 +5,15 @@ use crate::{
     numeric::rational::Rational,
 };
 use auto_ops::impl_op_ex;
-use std::{
-    fmt::Display,
-    ops::{Add, Sub},
-};
+use std::fmt::Display;
 
 /// Number in form `n/2^m`
+///
+/// Numerator is stored in 64 bits, and arithmetic operators panic when the result does not fit,
+/// instead of silently wrapping around. Use [`Self::checked_add`] and friends to handle overflow,
+/// or convert to
+/// [`BigDyadicRationalNumber`](crate::numeric::big_rational::BigDyadicRationalNumber) with
+/// `bigint` feature for arbitrary precision.
 #[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
 pub struct DyadicRationalNumber {
     numerator: i64,
@@ -36,22 +39,15 @@ impl DyadicRationalNumber {
     }
 
     /// Create a new fraction. Returns [None] if denominator is zero, or the number is not dyadic
-    pub fn new_fraction(numerator: i64, mut denominator: u32) -> Option<Self> {
-        let mut denominator_exponent = 0;
-
-        if denominator == 0 {
+    pub fn new_fraction(numerator: i64, denominator: u32) -> Option<Self> {
+        if denominator == 0 || !denominator.is_power_of_two() {
             return None;
         }
 
-        while denominator % 2 == 0 {
-            denominator /= 2;
-            denominator_exponent += 1;
-        }
-
-        (denominator == 1).then_some(
+        Some(
             Self {
                 numerator,
-                denominator_exponent,
+                denominator_exponent: denominator.trailing_zeros(),
             }
             .normalized(),
         )
@@ -85,54 +81,123 @@ impl DyadicRationalNumber {
     /// Internal function to normalize numbers
     fn normalize(&mut self) {
         // [2*(n)]/[2*d] = n/d
-        // while self.numerator.rem_euclid(2) == 0 && self.denominator_exponent != 0 {
         while self.numerator % 2 == 0 && self.denominator_exponent != 0 {
-            self.numerator >>= 1_i32;
+            self.numerator >>= 1_u32;
             self.denominator_exponent -= 1;
         }
     }
 
     /// Add to numerator. It is **NOT** addition function
+    ///
+    /// # Panics
+    /// - Numerator overflows, see [`Self::checked_step`]
     #[must_use]
     pub fn step(&self, n: i64) -> Self {
-        Self {
-            // numerator: self.numerator + (n << self.denominator_exponent),
-            numerator: self.numerator + n,
-            denominator_exponent: self.denominator_exponent,
-        }
-        .normalized()
+        self.checked_step(n)
+            .expect("dyadic rational step overflowed")
+    }
+
+    /// Add to numerator, returning `None` on overflow
+    pub fn checked_step(&self, n: i64) -> Option<Self> {
+        Some(
+            Self {
+                numerator: self.numerator.checked_add(n)?,
+                denominator_exponent: self.denominator_exponent,
+            }
+            .normalized(),
+        )
     }
 
     /// Convert to intger if it's an integer
-    pub fn to_integer(&self) -> Option<i64> {
+    pub const fn to_integer(&self) -> Option<i64> {
         // exponent == 0 => denominator == 1 => It's an integer
-        (self.denominator_exponent == 0).then_some(self.numerator)
+        if self.denominator_exponent == 0 {
+            Some(self.numerator)
+        } else {
+            None
+        }
     }
 
-    /// Ceil division
-    pub fn ceil(self) -> i64 {
-        // TODO: use `div_ceil` when `int_roundings` lands in stable
-        let n = self.numerator();
-        let d = self
-            .denominator()
-            .expect("unreachable: denominator cannot be zero") as i64;
-        (n + d - 1) / d
+    /// Ceil division, i.e. the smallest integer greater or equal to the number
+    pub const fn ceil(self) -> i64 {
+        let floor = self.floor();
+        if self.denominator_exponent == 0 {
+            floor
+        } else {
+            // Number is not an integer, so it is less than the maximum
+            floor + 1
+        }
+    }
+
+    /// Floor division, i.e. the greatest integer less or equal to the number
+    pub const fn floor(self) -> i64 {
+        // Arithmetic shift rounds towards negative infinity
+        if self.denominator_exponent < i64::BITS {
+            self.numerator >> self.denominator_exponent
+        } else if self.numerator < 0 {
+            -1
+        } else {
+            0
+        }
     }
 
-    /// Round a dyadic to the nearest integer
-    pub fn round(self) -> i64 {
-        self.numerator()
-            / self
-                .denominator()
-                .expect("unreachable: denominator cannot be zero") as i64
+    /// Round a dyadic towards zero
+    pub const fn round(self) -> i64 {
+        if self.numerator < 0 {
+            self.ceil()
+        } else {
+            self.floor()
+        }
+    }
+
+    /// Add two dyadics, returning `None` on overflow
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        let (finer, coarser) = if self.denominator_exponent >= rhs.denominator_exponent {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+        let shifted = checked_shl(
+            coarser.numerator,
+            finer.denominator_exponent - coarser.denominator_exponent,
+        )?;
+        Some(
+            Self {
+                numerator: finer.numerator.checked_add(shifted)?,
+                denominator_exponent: finer.denominator_exponent,
+            }
+            .normalized(),
+        )
+    }
+
+    /// Subtract two dyadics, returning `None` on overflow
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        self.checked_add(&rhs.checked_neg()?)
+    }
+
+    /// Negate a dyadic, returning `None` on overflow
+    pub fn checked_neg(&self) -> Option<Self> {
+        Some(Self {
+            numerator: self.numerator.checked_neg()?,
+            denominator_exponent: self.denominator_exponent,
+        })
     }
 
     /// Arithmetic mean of two rationals
+    ///
+    /// # Panics
+    /// - Sum overflows, see [`Self::checked_mean`]
     #[must_use]
     pub fn mean(&self, rhs: &Self) -> Self {
-        let mut res = *self + *rhs;
-        res.denominator_exponent += 1; // divide by 2
-        res.normalized()
+        self.checked_mean(rhs)
+            .expect("dyadic rational mean overflowed")
+    }
+
+    /// Arithmetic mean of two rationals, returning `None` on overflow
+    pub fn checked_mean(&self, rhs: &Self) -> Option<Self> {
+        let mut res = self.checked_add(rhs)?;
+        res.denominator_exponent = res.denominator_exponent.checked_add(1)?; // divide by 2
+        Some(res.normalized())
     }
 
     pub(crate) fn parse(input: &str) -> nom::IResult<&str, Self> {
@@ -150,7 +215,7 @@ impl DyadicRationalNumber {
                     |d| Ok((input, d)),
                 )
             }
-            Err(_) => Ok((input, Self::from(numerator))),
+            Err(_) => Ok((input, Self::new_integer(numerator))),
         }
     }
 
@@ -167,14 +232,40 @@ impl DyadicRationalNumber {
     /// Convert dyadic to rational
     ///
     /// # Panics
-    /// - If denominator is too large to fit in [`Rational`]
+    /// - If denominator is too large to fit in [`Rational`], see [`Self::checked_to_rational`]
     pub fn to_rational(self) -> Rational {
-        Rational::new(self.numerator(), self.denominator().unwrap() as u32)
+        self.checked_to_rational()
+            .expect("dyadic rational denominator does not fit in rational")
+    }
+
+    /// Convert dyadic to rational, returning `None` if denominator is too large to fit
+    pub fn checked_to_rational(self) -> Option<Rational> {
+        let denominator = u32::try_from(self.denominator()?).ok()?;
+        Some(Rational::new(self.numerator, denominator))
     }
 }
 
 impl_from_str_via_nom!(DyadicRationalNumber);
 
+/// `n * 2^shift`, or `None` if it does not fit
+fn checked_shl(n: i64, shift: u32) -> Option<i64> {
+    if n == 0 {
+        return Some(0);
+    }
+    let shifted = n.checked_shl(shift)?;
+    (shifted >> shift == n).then_some(shifted)
+}
+
+/// Compare `lhs * 2^shift` with `rhs` without overflowing
+fn cmp_shifted(lhs: i64, shift: u32, rhs: i64) -> std::cmp::Ordering {
+    if shift < 64 {
+        i128::cmp(&(i128::from(lhs) << shift), &i128::from(rhs))
+    } else {
+        // Any nonzero `lhs * 2^shift` is further from zero than `rhs`
+        lhs.cmp(&0).then_with(|| 0.cmp(&rhs))
+    }
+}
+
 #[test]
 fn step_works() {
     assert_eq!(
@@ -207,15 +298,18 @@ impl PartialOrd for DyadicRationalNumber {
 impl Ord for DyadicRationalNumber {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         if self.denominator_exponent <= other.denominator_exponent {
-            i64::cmp(
-                &(self.numerator << (other.denominator_exponent - self.denominator_exponent)),
-                &other.numerator,
+            cmp_shifted(
+                self.numerator,
+                other.denominator_exponent - self.denominator_exponent,
+                other.numerator,
             )
         } else {
-            i64::cmp(
-                &self.numerator,
-                &(other.numerator << (self.denominator_exponent - other.denominator_exponent)),
+            cmp_shifted(
+                other.numerator,
+                self.denominator_exponent - other.denominator_exponent,
+                self.numerator,
             )
+            .reverse()
         }
     }
 }
@@ -233,26 +327,10 @@ fn half_is_less_than_forty_two() {
 }
 
 impl_op_ex!(+|lhs: &DyadicRationalNumber, rhs: &DyadicRationalNumber| -> DyadicRationalNumber {
-    let (numerator, denominator_exponent) =
-    if lhs.denominator_exponent >= rhs.denominator_exponent {
-            let denominator_exponent = lhs.denominator_exponent;
-            let numerator = lhs.numerator
-        + (rhs.numerator << (lhs.denominator_exponent - rhs.denominator_exponent));
-        (numerator, denominator_exponent)
-    } else {
-            let denominator_exponent = rhs.denominator_exponent;
-            let numerator = rhs.numerator
-        + (lhs.numerator << (rhs.denominator_exponent - lhs.denominator_exponent));
-            (numerator, denominator_exponent)
-    };
-    DyadicRationalNumber {
-        numerator,
-        denominator_exponent,
-    }
-    .normalized()
+    lhs.checked_add(rhs).expect("dyadic rational addition overflowed")
 });
 
-impl_op_ex!(+=|lhs: &mut DyadicRationalNumber, rhs: &DyadicRationalNumber| { *lhs = lhs.add(rhs); });
+impl_op_ex!(+=|lhs: &mut DyadicRationalNumber, rhs: &DyadicRationalNumber| { *lhs = *lhs + rhs; });
 
 impl_op_ex!(
     -|lhs: &DyadicRationalNumber, rhs: &DyadicRationalNumber| -> DyadicRationalNumber {
@@ -260,13 +338,11 @@ impl_op_ex!(
     }
 );
 
-impl_op_ex!(-=|lhs: &mut DyadicRationalNumber, rhs: &DyadicRationalNumber| { *lhs = lhs.sub(rhs); });
+impl_op_ex!(-=|lhs: &mut DyadicRationalNumber, rhs: &DyadicRationalNumber| { *lhs = *lhs - rhs; });
 
 impl_op_ex!(-|lhs: &DyadicRationalNumber| -> DyadicRationalNumber {
-    DyadicRationalNumber {
-        numerator: -lhs.numerator,
-        denominator_exponent: lhs.denominator_exponent,
-    }
+    lhs.checked_neg()
+        .expect("dyadic rational negation overflowed")
 });
 
 impl Display for DyadicRationalNumber {
@@ -295,6 +371,48 @@ mod tests {
         assert_eq!(half + one, DyadicRationalNumber::new(3, 1));
     }
 
+    #[test]
+    fn overflow_is_detected() {
+        let max = DyadicRationalNumber::from(i64::MAX);
+        assert_eq!(max.checked_add(&DyadicRationalNumber::from(1)), None);
+        assert_eq!(
+            DyadicRationalNumber::new(1, 100).checked_add(&DyadicRationalNumber::from(1)),
+            None
+        );
+        assert_eq!(DyadicRationalNumber::from(i64::MIN).checked_neg(), None);
+        assert_eq!(
+            max.checked_sub(&DyadicRationalNumber::from(1)),
+            Some(DyadicRationalNumber::from(i64::MAX - 1))
+        );
+        assert_eq!(max.checked_step(1), None);
+        assert_eq!(max.checked_mean(&max), None);
+        assert_eq!(
+            DyadicRationalNumber::from(3).checked_mean(&DyadicRationalNumber::from(0)),
+            Some(DyadicRationalNumber::new(3, 1))
+        );
+        assert_eq!(DyadicRationalNumber::new(1, 70).checked_to_rational(), None);
+        assert_eq!(
+            DyadicRationalNumber::new(3, 2).checked_to_rational(),
+            Some(Rational::new(3, 4))
+        );
+    }
+
+    #[test]
+    fn comparison_does_not_overflow() {
+        let tiny = DyadicRationalNumber::new(1, 100);
+        assert!(tiny < DyadicRationalNumber::from(1));
+        assert!(DyadicRationalNumber::from(-1) < tiny);
+        assert!(DyadicRationalNumber::new(-1, 100) < tiny);
+        assert!(DyadicRationalNumber::from(i64::MAX) > DyadicRationalNumber::new(3, 1));
+        assert!(DyadicRationalNumber::from(0) > DyadicRationalNumber::new(-1, 70));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflowed")]
+    fn addition_panics_on_overflow() {
+        let _ = DyadicRationalNumber::from(i64::MAX) + DyadicRationalNumber::from(1);
+    }
+
     #[test]
     fn denominator_works() {
         assert_eq!(
@@ -316,6 +434,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ceil_floor_works() {
+        assert_eq!(DyadicRationalNumber::new(-5, 2).ceil(), -1);
+        assert_eq!(DyadicRationalNumber::new(-5, 2).floor(), -2);
+        assert_eq!(DyadicRationalNumber::new(5, 2).ceil(), 2);
+        assert_eq!(DyadicRationalNumber::new(5, 2).floor(), 1);
+        assert_eq!(DyadicRationalNumber::new(-2, 0).ceil(), -2);
+        assert_eq!(DyadicRationalNumber::new(-2, 0).floor(), -2);
+        assert_eq!(DyadicRationalNumber::new(-1, 100).floor(), -1);
+        assert_eq!(DyadicRationalNumber::new(-1, 100).ceil(), 0);
+        assert_eq!(DyadicRationalNumber::new(1, 63).round(), 0);
+        assert_eq!(DyadicRationalNumber::new(-7, 1).round(), -3);
+    }
+
     #[test]
     fn dyadic_rationals_pretty() {
         assert_eq!(format!("{}", DyadicRationalNumber::new(3, 8)), "3/256");
@@ -0,0 +1,268 @@
+//! Interval of numbers with open or closed endpoints
+
+use std::{
+    fmt::{self, Display},
+    ops::{Add, Neg, Sub},
+};
+
+/// Interval of numbers, with each endpoint either included (closed) or not (open)
+///
+/// Sum of intervals is the set of sums of their elements, so it can be used to combine bounds
+/// of components, e.g. confusion intervals or temperature bounds reported by searches that did
+/// not finish.
+///
+/// # Examples
+///
+/// ```
+/// use cgt::numeric::{interval::Interval, rational::Rational};
+///
+/// let a = Interval::closed(Rational::from(0), Rational::from(1));
+/// let b = Interval::open(Rational::from(2), Rational::PositiveInfinity);
+/// assert_eq!((a + b).to_string(), "(2, ∞)");
+/// assert_eq!((a - a).to_string(), "[-1, 1]");
+/// assert!(a.contains(Rational::new(1, 2)));
+/// ```
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interval<T> {
+    /// Lower endpoint
+    pub lower: T,
+
+    /// Whether the lower endpoint is included in the interval
+    pub lower_closed: bool,
+
+    /// Upper endpoint
+    pub upper: T,
+
+    /// Whether the upper endpoint is included in the interval
+    pub upper_closed: bool,
+}
+
+impl<T> Interval<T>
+where
+    T: Ord + Copy,
+{
+    /// Interval including both endpoints
+    #[inline]
+    pub const fn closed(lower: T, upper: T) -> Self {
+        Self {
+            lower,
+            lower_closed: true,
+            upper,
+            upper_closed: true,
+        }
+    }
+
+    /// Interval excluding both endpoints
+    #[inline]
+    pub const fn open(lower: T, upper: T) -> Self {
+        Self {
+            lower,
+            lower_closed: false,
+            upper,
+            upper_closed: false,
+        }
+    }
+
+    /// Interval containing exactly one number
+    #[inline]
+    pub const fn point(value: T) -> Self {
+        Self::closed(value, value)
+    }
+
+    /// Check if the number is in the interval
+    pub fn contains(&self, number: T) -> bool {
+        let above_lower = if self.lower_closed {
+            self.lower <= number
+        } else {
+            self.lower < number
+        };
+        let below_upper = if self.upper_closed {
+            number <= self.upper
+        } else {
+            number < self.upper
+        };
+        above_lower && below_upper
+    }
+
+    /// Check if the interval does not contain any number
+    pub fn is_empty(&self) -> bool {
+        self.lower > self.upper
+            || (self.lower == self.upper && !(self.lower_closed && self.upper_closed))
+    }
+
+    /// Numbers contained in both intervals
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (lower, lower_closed) = match self.lower.cmp(&other.lower) {
+            std::cmp::Ordering::Less => (other.lower, other.lower_closed),
+            std::cmp::Ordering::Equal => (self.lower, self.lower_closed && other.lower_closed),
+            std::cmp::Ordering::Greater => (self.lower, self.lower_closed),
+        };
+        let (upper, upper_closed) = match self.upper.cmp(&other.upper) {
+            std::cmp::Ordering::Less => (self.upper, self.upper_closed),
+            std::cmp::Ordering::Equal => (self.upper, self.upper_closed && other.upper_closed),
+            std::cmp::Ordering::Greater => (other.upper, other.upper_closed),
+        };
+        Self {
+            lower,
+            lower_closed,
+            upper,
+            upper_closed,
+        }
+    }
+
+    /// Smallest interval containing both intervals
+    #[must_use]
+    pub fn hull(&self, other: &Self) -> Self {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+
+        let (lower, lower_closed) = match self.lower.cmp(&other.lower) {
+            std::cmp::Ordering::Less => (self.lower, self.lower_closed),
+            std::cmp::Ordering::Equal => (self.lower, self.lower_closed || other.lower_closed),
+            std::cmp::Ordering::Greater => (other.lower, other.lower_closed),
+        };
+        let (upper, upper_closed) = match self.upper.cmp(&other.upper) {
+            std::cmp::Ordering::Less => (other.upper, other.upper_closed),
+            std::cmp::Ordering::Equal => (self.upper, self.upper_closed || other.upper_closed),
+            std::cmp::Ordering::Greater => (self.upper, self.upper_closed),
+        };
+        Self {
+            lower,
+            lower_closed,
+            upper,
+            upper_closed,
+        }
+    }
+
+    /// Distance between endpoints
+    pub fn width(&self) -> T
+    where
+        T: Sub<Output = T>,
+    {
+        self.upper - self.lower
+    }
+}
+
+impl<T> Add for Interval<T>
+where
+    T: Ord + Copy + Add<Output = T>,
+{
+    type Output = Self;
+
+    /// Interval of all sums `a + b` of `a` from `self` and `b` from `rhs`
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.is_empty() {
+            return self;
+        }
+        if rhs.is_empty() {
+            return rhs;
+        }
+
+        Self {
+            lower: self.lower + rhs.lower,
+            lower_closed: self.lower_closed && rhs.lower_closed,
+            upper: self.upper + rhs.upper,
+            upper_closed: self.upper_closed && rhs.upper_closed,
+        }
+    }
+}
+
+impl<T> Neg for Interval<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            lower: -self.upper,
+            lower_closed: self.upper_closed,
+            upper: -self.lower,
+            upper_closed: self.lower_closed,
+        }
+    }
+}
+
+impl<T> Sub for Interval<T>
+where
+    T: Ord + Copy + Add<Output = T> + Neg<Output = T>,
+{
+    type Output = Self;
+
+    /// Interval of all differences `a - b` of `a` from `self` and `b` from `rhs`
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl<T> Display for Interval<T>
+where
+    T: Ord + Copy + Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "{{}}");
+        }
+        write!(
+            f,
+            "{}{}, {}{}",
+            if self.lower_closed { '[' } else { '(' },
+            self.lower,
+            self.upper,
+            if self.upper_closed { ']' } else { ')' }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numeric::dyadic_rational_number::DyadicRationalNumber;
+
+    fn interval(lower: i64, lower_closed: bool, upper: i64, upper_closed: bool) -> Interval<i64> {
+        Interval {
+            lower,
+            lower_closed,
+            upper,
+            upper_closed,
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = interval(0, true, 2, false);
+        let b = interval(-1, false, 1, true);
+        assert_eq!(a + b, interval(-1, false, 3, false));
+        assert_eq!(-b, interval(-1, true, 1, false));
+        assert_eq!(a - b, interval(-1, true, 3, false));
+        assert_eq!(a + Interval::point(5), interval(5, true, 7, false));
+        assert!((a + Interval::open(3, 3)).is_empty());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = interval(0, true, 2, false);
+        let b = interval(1, false, 3, true);
+        assert_eq!(a.intersection(&b), interval(1, false, 2, false));
+        assert_eq!(a.hull(&b), interval(0, true, 3, true));
+        assert!(a.intersection(&interval(2, true, 4, true)).is_empty());
+        assert!(Interval::point(1).intersection(&Interval::closed(1, 1)) == Interval::point(1));
+        assert_eq!(a.width(), 2);
+    }
+
+    #[test]
+    fn display() {
+        let a = Interval::closed(
+            DyadicRationalNumber::from(-1),
+            DyadicRationalNumber::new(1, 1),
+        );
+        assert_eq!(a.to_string(), "[-1, 1/2]");
+        assert_eq!(Interval::open(0, 0).to_string(), "{}");
+    }
+}
@@ -1,11 +1,13 @@
 //! Nimber is a number that represents a Nim heap of a given size.
 
+use crate::numeric::ordinal::Ordinal;
+use alloc::vec::Vec;
 use auto_ops::impl_op_ex;
 use std::fmt::Display;
 
 /// Number that represents a Nim heap of given size.
 ///
-/// Addition is overloaded to Nim sum.
+/// Addition is overloaded to Nim sum, which cannot overflow.
 #[repr(transparent)]
 #[derive(Debug, Hash, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -24,17 +26,26 @@ impl Nimber {
 
     /// Compute the minimum excluded value from a vector of nimbers.
     /// See <https://en.wikipedia.org/wiki/Mex_(mathematics)>
-    pub fn mex(mut nimbers: Vec<Self>) -> Self {
+    ///
+    /// # Panics
+    /// - All nimbers up to `*u32::MAX` are present, see [`Self::checked_mex`]
+    pub fn mex(nimbers: Vec<Self>) -> Self {
+        Self::checked_mex(nimbers).expect("nimber mex overflowed")
+    }
+
+    /// Compute the minimum excluded value from a vector of nimbers, returning `None` if it does
+    /// not fit in [`u32`]
+    pub fn checked_mex(mut nimbers: Vec<Self>) -> Option<Self> {
         nimbers.sort();
-        let mut current = 0;
+        let mut current = 0_u32;
         for n in nimbers {
             match current.cmp(&n.0) {
-                std::cmp::Ordering::Less => return Self(current),
-                std::cmp::Ordering::Equal => current += 1,
+                std::cmp::Ordering::Less => return Some(Self(current)),
+                std::cmp::Ordering::Equal => current = current.checked_add(1)?,
                 std::cmp::Ordering::Greater => {}
             }
         }
-        Self(current)
+        Some(Self(current))
     }
 }
 
@@ -67,6 +78,96 @@ impl Display for Nimber {
     }
 }
 
+/// Nimber that represents a Nim heap of possibly infinite, ordinal size, e.g. `*ω`.
+///
+/// Addition is overloaded to Nim sum.
+#[derive(Debug, Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransfiniteNimber(Ordinal);
+
+impl TransfiniteNimber {
+    /// Construct new nimber
+    pub const fn new(value: Ordinal) -> Self {
+        Self(value)
+    }
+
+    /// Get the underlying nimber value
+    pub const fn value(&self) -> &Ordinal {
+        &self.0
+    }
+
+    /// Convert to finite nimber, if possible
+    pub fn to_nimber(&self) -> Option<Nimber> {
+        self.0
+            .to_finite()
+            .and_then(|value| u32::try_from(value).ok())
+            .map(Nimber)
+    }
+
+    /// Compute the minimum excluded value from a vector of nimbers.
+    /// See <https://en.wikipedia.org/wiki/Mex_(mathematics)>
+    pub fn mex(nimbers: Vec<Self>) -> Self {
+        Self(Ordinal::mex(nimbers.into_iter().map(|n| n.0).collect()))
+    }
+}
+
+impl From<Nimber> for TransfiniteNimber {
+    fn from(value: Nimber) -> Self {
+        Self(Ordinal::new(u64::from(value.0)))
+    }
+}
+
+impl From<Ordinal> for TransfiniteNimber {
+    fn from(value: Ordinal) -> Self {
+        Self(value)
+    }
+}
+
+impl_op_ex!(+|lhs: &TransfiniteNimber, rhs: &TransfiniteNimber| -> TransfiniteNimber {
+    TransfiniteNimber(lhs.0.nim_sum(&rhs.0))
+});
+impl_op_ex!(+=|lhs: &mut TransfiniteNimber, rhs: &TransfiniteNimber| { lhs.0 = lhs.0.nim_sum(&rhs.0) });
+
+// Subtraction is the same as addition
+impl_op_ex!(
+    -|lhs: &TransfiniteNimber, rhs: &TransfiniteNimber| -> TransfiniteNimber {
+        TransfiniteNimber(lhs.0.nim_sum(&rhs.0))
+    }
+);
+impl_op_ex!(-=|lhs: &mut TransfiniteNimber, rhs: &TransfiniteNimber| { lhs.0 = lhs.0.nim_sum(&rhs.0) });
+
+// Nimber is its own negative
+impl_op_ex!(-|lhs: &TransfiniteNimber| -> TransfiniteNimber { lhs.clone() });
+
+impl Display for TransfiniteNimber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0.to_finite() {
+            Some(0) => write!(f, "0"),
+            Some(1) => write!(f, "*"),
+            Some(n) => write!(f, "*{}", n),
+            None if self.0.terms().len() == 1 => write!(f, "*{}", self.0),
+            None => write!(f, "*({})", self.0),
+        }
+    }
+}
+
+#[test]
+fn transfinite_nimbers_work() {
+    let omega = TransfiniteNimber::new(Ordinal::omega());
+    let three = TransfiniteNimber::from(Nimber(3));
+    let sum = &omega + &three;
+    assert_eq!(sum.to_string(), "*(ω + 3)");
+    assert_eq!(&sum + &three, omega);
+    assert_eq!(omega.to_string(), "*ω");
+    assert_eq!((&three + &three).to_nimber(), Some(Nimber(0)));
+    assert_eq!(omega.to_nimber(), None);
+
+    assert_eq!(
+        TransfiniteNimber::mex(vec![TransfiniteNimber::from(Nimber(0)), sum]),
+        TransfiniteNimber::from(Nimber(1))
+    );
+}
+
 #[test]
 fn mex_works() {
     assert_eq!(
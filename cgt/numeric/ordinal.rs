@@ -0,0 +1,290 @@
+//! Ordinal numbers below `ε_0`, represented in Cantor normal form.
+//!
+//! Every such ordinal can be uniquely written as `ω^(α_1)·c_1 + ... + ω^(α_k)·c_k` with
+//! `α_1 > ... > α_k` and positive integer coefficients `c_i`, where the exponents are again
+//! ordinals in Cantor normal form.
+
+use alloc::{vec, vec::Vec};
+use auto_ops::impl_op_ex;
+use std::fmt::{self, Display};
+
+/// Ordinal number in Cantor normal form
+///
+/// Addition and multiplication are ordinal operations, so they are not commutative, e.g.
+/// `1 + ω = ω` but `ω + 1 > ω`.
+#[derive(Debug, Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ordinal {
+    /// Pairs of exponent and coefficient, sorted by exponents in descending order. Coefficients
+    /// are never zero. Note that derived lexicographic ordering is the ordinal ordering.
+    terms: Vec<(Self, u64)>,
+}
+
+impl Ordinal {
+    /// Zero
+    pub const fn zero() -> Self {
+        Self { terms: Vec::new() }
+    }
+
+    /// Finite ordinal
+    pub fn new(value: u64) -> Self {
+        Self::from_term(Self::zero(), value)
+    }
+
+    /// `ω^exponent·coefficient`
+    pub fn from_term(exponent: Self, coefficient: u64) -> Self {
+        if coefficient == 0 {
+            Self::zero()
+        } else {
+            Self {
+                terms: vec![(exponent, coefficient)],
+            }
+        }
+    }
+
+    /// `ω^exponent`
+    pub fn omega_power(exponent: Self) -> Self {
+        Self::from_term(exponent, 1)
+    }
+
+    /// The first infinite ordinal `ω`
+    pub fn omega() -> Self {
+        Self::omega_power(Self::new(1))
+    }
+
+    /// Get terms as pairs of exponent of `ω` and its coefficient, sorted by exponents in
+    /// descending order
+    pub fn terms(&self) -> &[(Self, u64)] {
+        &self.terms
+    }
+
+    /// Check if ordinal is zero
+    pub const fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Check if ordinal is finite
+    pub fn is_finite(&self) -> bool {
+        self.terms.iter().all(|(exponent, _)| exponent.is_zero())
+    }
+
+    /// Convert to a natural number, if ordinal is finite
+    pub fn to_finite(&self) -> Option<u64> {
+        match self.terms.as_slice() {
+            [] => Some(0),
+            [(exponent, coefficient)] if exponent.is_zero() => Some(*coefficient),
+            _ => None,
+        }
+    }
+
+    /// Check if ordinal is a successor, i.e. `α + 1` for some `α`
+    pub fn is_successor(&self) -> bool {
+        self.terms
+            .last()
+            .is_some_and(|(exponent, _)| exponent.is_zero())
+    }
+
+    /// Check if ordinal is a limit ordinal, i.e. non-zero and not a successor
+    pub fn is_limit(&self) -> bool {
+        !self.is_zero() && !self.is_successor()
+    }
+
+    /// Successor ordinal `α + 1`
+    #[must_use]
+    pub fn successor(&self) -> Self {
+        self + Self::new(1)
+    }
+
+    /// Natural (Hessenberg) sum, i.e. commutative sum obtained by adding coefficients of
+    /// matching terms
+    #[must_use]
+    pub fn natural_sum(&self, other: &Self) -> Self {
+        self.merge_terms(other, |lhs, rhs| lhs + rhs)
+    }
+
+    /// Nim sum, i.e. sum of `*α + *β` as nimbers. Since `ω^α = 2^(ω·α)`, it is computed by xor
+    /// of coefficients of matching terms.
+    #[must_use]
+    pub fn nim_sum(&self, other: &Self) -> Self {
+        self.merge_terms(other, |lhs, rhs| lhs ^ rhs)
+    }
+
+    /// Compute the minimum excluded value from a vector of ordinals.
+    /// See <https://en.wikipedia.org/wiki/Mex_(mathematics)>
+    pub fn mex(mut ordinals: Vec<Self>) -> Self {
+        ordinals.sort();
+        ordinals.dedup();
+        let mut current = Self::zero();
+        for ordinal in ordinals {
+            if ordinal != current {
+                break;
+            }
+            current = current.successor();
+        }
+        current
+    }
+
+    fn merge_terms<F>(&self, other: &Self, f: F) -> Self
+    where
+        F: Fn(u64, u64) -> u64,
+    {
+        let mut terms = Vec::with_capacity(self.terms.len() + other.terms.len());
+        let mut lhs = self.terms.iter().peekable();
+        let mut rhs = other.terms.iter().peekable();
+        loop {
+            let term = match (lhs.peek(), rhs.peek()) {
+                (None, None) => break,
+                (Some(_), None) => lhs.next().unwrap().clone(),
+                (None, Some(_)) => rhs.next().unwrap().clone(),
+                (Some((lhs_exponent, _)), Some((rhs_exponent, _))) => {
+                    match lhs_exponent.cmp(rhs_exponent) {
+                        std::cmp::Ordering::Greater => lhs.next().unwrap().clone(),
+                        std::cmp::Ordering::Less => rhs.next().unwrap().clone(),
+                        std::cmp::Ordering::Equal => {
+                            let (exponent, lhs_coefficient) = lhs.next().unwrap();
+                            let (_, rhs_coefficient) = rhs.next().unwrap();
+                            (exponent.clone(), f(*lhs_coefficient, *rhs_coefficient))
+                        }
+                    }
+                }
+            };
+            if term.1 != 0 {
+                terms.push(term);
+            }
+        }
+        Self { terms }
+    }
+}
+
+impl From<u64> for Ordinal {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl_op_ex!(+|lhs: &Ordinal, rhs: &Ordinal| -> Ordinal {
+    let Some((rhs_exponent, rhs_coefficient)) = rhs.terms.first() else {
+        return lhs.clone();
+    };
+
+    // Terms of `lhs` smaller than the leading term of `rhs` are absorbed
+    let mut terms: Vec<(Ordinal, u64)> = lhs
+        .terms
+        .iter()
+        .take_while(|(exponent, _)| exponent >= rhs_exponent)
+        .cloned()
+        .collect();
+    match terms.last_mut() {
+        Some((exponent, coefficient)) if exponent == rhs_exponent => {
+            *coefficient += rhs_coefficient;
+        }
+        _ => terms.push((rhs_exponent.clone(), *rhs_coefficient)),
+    }
+    terms.extend(rhs.terms.iter().skip(1).cloned());
+    Ordinal { terms }
+});
+
+impl_op_ex!(+=|lhs: &mut Ordinal, rhs: &Ordinal| { *lhs = &*lhs + rhs });
+
+impl_op_ex!(*|lhs: &Ordinal, rhs: &Ordinal| -> Ordinal {
+    let Some((lhs_exponent, lhs_coefficient)) = lhs.terms.first() else {
+        return Ordinal::zero();
+    };
+
+    // Multiplication distributes on the left over sums on the right
+    let mut result = Ordinal::zero();
+    for (rhs_exponent, rhs_coefficient) in &rhs.terms {
+        let product = if rhs_exponent.is_zero() {
+            let mut terms = lhs.terms.clone();
+            terms[0].1 = lhs_coefficient * rhs_coefficient;
+            Ordinal { terms }
+        } else {
+            Ordinal::from_term(lhs_exponent + rhs_exponent, *rhs_coefficient)
+        };
+        result += product;
+    }
+    result
+});
+
+impl_op_ex!(*=|lhs: &mut Ordinal, rhs: &Ordinal| { *lhs = &*lhs * rhs });
+
+impl Display for Ordinal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+
+        for (idx, (exponent, coefficient)) in self.terms.iter().enumerate() {
+            if idx != 0 {
+                write!(f, " + ")?;
+            }
+
+            if exponent.is_zero() {
+                write!(f, "{}", coefficient)?;
+                continue;
+            }
+
+            write!(f, "ω")?;
+            match exponent.to_finite() {
+                Some(1) => {}
+                Some(_) => write!(f, "^{}", exponent)?,
+                None if exponent.terms == [(Self::new(1), 1)] => write!(f, "^{}", exponent)?,
+                None => write!(f, "^({})", exponent)?,
+            }
+            if *coefficient != 1 {
+                write!(f, "·{}", coefficient)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_works() {
+        let omega = Ordinal::omega();
+        let one = Ordinal::new(1);
+        let two = Ordinal::new(2);
+
+        assert_eq!(&one + &omega, omega);
+        assert_eq!((&omega + &one).to_string(), "ω + 1");
+        assert_eq!((&two * &omega), omega);
+        assert_eq!((&omega * &two).to_string(), "ω·2");
+        assert_eq!(
+            ((&omega + &one) * (&omega + &one)).to_string(),
+            "ω^2 + ω + 1"
+        );
+        assert_eq!(Ordinal::omega_power(omega.clone()).to_string(), "ω^ω");
+        assert_eq!(Ordinal::omega_power(&omega + &one).to_string(), "ω^(ω + 1)");
+        assert_eq!((&omega + &one).natural_sum(&omega).to_string(), "ω·2 + 1");
+    }
+
+    #[test]
+    fn ordering_works() {
+        let omega = Ordinal::omega();
+        assert!(Ordinal::new(1_000_000) < omega);
+        assert!(omega < omega.successor());
+        assert!(&omega * &Ordinal::new(2) < &omega * &omega);
+        assert!(omega.is_limit());
+        assert!(omega.successor().is_successor());
+        assert_eq!(Ordinal::new(3).to_finite(), Some(3));
+        assert_eq!(omega.to_finite(), None);
+    }
+
+    #[test]
+    fn nim_sum_works() {
+        let omega = Ordinal::omega();
+        let a = &omega * &Ordinal::new(3) + Ordinal::new(5);
+        let b = &omega + Ordinal::new(6);
+        assert_eq!(a.nim_sum(&b).to_string(), "ω·2 + 3");
+        assert_eq!(a.nim_sum(&a), Ordinal::zero());
+
+        assert_eq!(
+            Ordinal::mex(vec![Ordinal::new(1), Ordinal::new(0), omega]),
+            Ordinal::new(2)
+        );
+    }
+}
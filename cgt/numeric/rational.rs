@@ -2,16 +2,19 @@
 
 use crate::nom_utils::{self, impl_from_str_via_nom};
 use auto_ops::impl_op_ex;
-use num_rational::Rational64;
-use std::{
-    fmt::Display,
-    ops::{Add, Div, Mul, Sub},
-};
+use num_rational::{Ratio, Rational64};
+use num_traits::{Signed, Zero};
+use std::{cmp::Ordering, fmt::Display};
 
 #[cfg(test)]
 use std::str::FromStr;
 
 /// Infinite rational number.
+///
+/// Finite values are stored as 64 bit fractions, and arithmetic operators panic when the result
+/// does not fit, instead of silently wrapping around. Use [`Self::checked_add`] and friends to
+/// handle overflow, or convert to [`BigRational`](crate::numeric::big_rational::BigRational) with
+/// `bigint` feature for arbitrary precision.
 #[derive(Debug, Hash, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Rational {
     /// Negative infnity, smaller than all other values
@@ -28,7 +31,7 @@ impl Rational {
     /// Create a new rational. Panics if denominator is zero.
     #[inline]
     pub fn new(numerator: i64, denominator: u32) -> Self {
-        Self::Value(Rational64::new(numerator, denominator as i64))
+        Self::Value(Rational64::new(numerator, i64::from(denominator)))
     }
 
     /// Check if value is infinite
@@ -37,8 +40,18 @@ impl Rational {
         !matches!(self, Self::Value(_))
     }
 
-    // TODO: Handle infinities
     fn parse(input: &str) -> nom::IResult<&str, Self> {
+        if let Ok((input, _)) =
+            nom_utils::lexeme(nom::bytes::complete::tag::<&str, &str, ()>("-∞"))(input)
+        {
+            return Ok((input, Self::NegativeInfinity));
+        }
+        if let Ok((input, _)) =
+            nom_utils::lexeme(nom::bytes::complete::tag::<&str, &str, ()>("∞"))(input)
+        {
+            return Ok((input, Self::PositiveInfinity));
+        }
+
         let (input, numerator) = nom_utils::lexeme(nom::character::complete::i64)(input)?;
         match nom_utils::lexeme(nom::bytes::complete::tag::<&str, &str, ()>("/"))(input) {
             Ok((input, _)) => {
@@ -70,21 +83,164 @@ impl Rational {
     ///
     /// # Errors
     /// - Rational is infinite
-    pub const fn to_fraction(self) -> Option<(i64, u32)> {
+    /// - Denominator does not fit in [`u32`]
+    pub fn to_fraction(self) -> Option<(i64, u32)> {
         if let Self::Value(r) = self {
-            Some((*r.numer(), *r.denom() as u32))
+            Some((*r.numer(), u32::try_from(*r.denom()).ok()?))
         } else {
             None
         }
     }
 
+    /// Add two rationals, returning `None` on overflow or when adding infinities of opposite
+    /// signs
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Value(_), Self::Value(_)) => narrowed(&(widened(self)? + widened(rhs)?)),
+            (Self::PositiveInfinity, Self::NegativeInfinity)
+            | (Self::NegativeInfinity, Self::PositiveInfinity) => None,
+            (Self::PositiveInfinity | Self::NegativeInfinity, _) => Some(*self),
+            (Self::Value(_), _) => Some(*rhs),
+        }
+    }
+
+    /// Subtract two rationals, returning `None` on overflow or when subtracting infinities of
+    /// the same sign
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Value(_), Self::Value(_)) => narrowed(&(widened(self)? - widened(rhs)?)),
+            (_, Self::Value(_)) => Some(*self),
+            _ => self.checked_add(&rhs.checked_neg()?),
+        }
+    }
+
+    /// Multiply two rationals, returning `None` on overflow or when multiplying infinity by zero
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Value(_), Self::Value(_)) => narrowed(&(widened(self)? * widened(rhs)?)),
+            _ => Self::infinity_with_sign(self.sign(), rhs.sign()),
+        }
+    }
+
+    /// Divide two rationals, returning `None` on overflow, division by zero, or when dividing
+    /// infinity by infinity. Finite values divided by infinity are zero.
+    pub fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Value(_), Self::Value(_)) => {
+                let rhs = widened(rhs)?;
+                if rhs.is_zero() {
+                    return None;
+                }
+                narrowed(&(widened(self)? / rhs))
+            }
+            (Self::Value(_), _) => Some(Self::from(0)),
+            (_, Self::Value(_)) => Self::infinity_with_sign(self.sign(), rhs.sign()),
+            _ => None,
+        }
+    }
+
+    /// Negate a rational, returning `None` on overflow. Infinities are swapped.
+    pub fn checked_neg(&self) -> Option<Self> {
+        match self {
+            Self::NegativeInfinity => Some(Self::PositiveInfinity),
+            Self::Value(_) => narrowed(&-widened(self)?),
+            Self::PositiveInfinity => Some(Self::NegativeInfinity),
+        }
+    }
+
+    /// Like [`Self::checked_add`], but results too large to fit go to infinity of the matching
+    /// sign. Still returns `None` when the result is not too large but its denominator does not
+    /// fit.
+    pub fn checked_saturating_add(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Value(_), Self::Value(_)) => saturated(&(widened(self)? + widened(rhs)?)),
+            _ => self.checked_add(rhs),
+        }
+    }
+
+    /// Like [`Self::checked_sub`], but results too large to fit go to infinity of the matching
+    /// sign. Still returns `None` when the result is not too large but its denominator does not
+    /// fit.
+    pub fn checked_saturating_sub(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Value(_), Self::Value(_)) => saturated(&(widened(self)? - widened(rhs)?)),
+            _ => self.checked_sub(rhs),
+        }
+    }
+
+    /// Like [`Self::checked_mul`], but results too large to fit go to infinity of the matching
+    /// sign. Still returns `None` when the result is not too large but its denominator does not
+    /// fit.
+    pub fn checked_saturating_mul(&self, rhs: &Self) -> Option<Self> {
+        match (self, rhs) {
+            (Self::Value(_), Self::Value(_)) => saturated(&(widened(self)? * widened(rhs)?)),
+            _ => self.checked_mul(rhs),
+        }
+    }
+
+    /// Sign of the rational, infinities included
+    fn sign(&self) -> Ordering {
+        match self {
+            Self::NegativeInfinity => Ordering::Less,
+            Self::Value(value) => value.numer().cmp(&0),
+            Self::PositiveInfinity => Ordering::Greater,
+        }
+    }
+
+    /// Infinity with sign of product of values with given signs, `None` if any of them is zero
+    fn infinity_with_sign(lhs: Ordering, rhs: Ordering) -> Option<Self> {
+        match (lhs, rhs) {
+            (Ordering::Equal, _) | (_, Ordering::Equal) => None,
+            (lhs, rhs) if lhs == rhs => Some(Self::PositiveInfinity),
+            _ => Some(Self::NegativeInfinity),
+        }
+    }
+
     /// Get floating point approximation if rational is finite
     pub fn as_f32(self) -> Option<f32> {
-        let (n, d) = self.to_fraction()?;
-        Some(n as f32 / d as f32)
+        match self {
+            Self::Value(_) => Some(f64::from(self) as f32),
+            Self::NegativeInfinity | Self::PositiveInfinity => None,
+        }
     }
 }
 
+/// Fraction that can hold result of a single operation on two finite rationals
+type Wide = Ratio<i128>;
+
+/// Convert finite value to a fraction wide enough for the result of a single operation
+fn widened(value: &Rational) -> Option<Wide> {
+    match value {
+        Rational::Value(value) => Some(Ratio::new_raw(
+            i128::from(*value.numer()),
+            i128::from(*value.denom()),
+        )),
+        Rational::NegativeInfinity | Rational::PositiveInfinity => None,
+    }
+}
+
+/// Convert reduced wide fraction back to a rational, or to infinity of the same sign if it is
+/// too large. Returns `None` if it is not too large, but too precise to fit.
+fn saturated(value: &Wide) -> Option<Rational> {
+    narrowed(value).or_else(|| {
+        if value.abs() < Wide::from_integer(i64::MAX.into()) {
+            None
+        } else if value.is_negative() {
+            Some(Rational::NegativeInfinity)
+        } else {
+            Some(Rational::PositiveInfinity)
+        }
+    })
+}
+
+/// Convert reduced wide fraction back to a rational, if it fits
+fn narrowed(value: &Wide) -> Option<Rational> {
+    Some(Rational::Value(Ratio::new_raw(
+        i64::try_from(*value.numer()).ok()?,
+        i64::try_from(*value.denom()).ok()?,
+    )))
+}
+
 impl From<Rational64> for Rational {
     fn from(value: Rational64) -> Self {
         Self::Value(value)
@@ -93,7 +249,7 @@ impl From<Rational64> for Rational {
 
 impl From<i64> for Rational {
     fn from(value: i64) -> Self {
-        Self::from(Rational64::from(value))
+        Self::Value(Rational64::from_integer(value))
     }
 }
 
@@ -103,70 +259,46 @@ impl From<i32> for Rational {
     }
 }
 
-impl_op_ex!(+|lhs: &Rational, rhs: &Rational| -> Rational {
-    match (lhs, rhs) {
-        (Rational::Value(lhs), Rational::Value(rhs)) => Rational::from(lhs + rhs),
-        (Rational::Value(_), Rational::PositiveInfinity) |
-        (Rational::PositiveInfinity, Rational::Value(_)) => Rational::PositiveInfinity,
-        (Rational::Value(_), Rational::NegativeInfinity) |
-        (Rational::NegativeInfinity, Rational::Value(_)) => Rational::NegativeInfinity,
-        _ => {
-            panic!()
+impl From<Rational> for f64 {
+    /// Nearest float, infinities are mapped to float infinities
+    fn from(value: Rational) -> Self {
+        match value {
+            Rational::NegativeInfinity => Self::NEG_INFINITY,
+            Rational::Value(value) => *value.numer() as Self / *value.denom() as Self,
+            Rational::PositiveInfinity => Self::INFINITY,
         }
     }
+}
+
+impl_op_ex!(+|lhs: &Rational, rhs: &Rational| -> Rational {
+    lhs.checked_add(rhs)
+        .expect("rational addition overflowed or adds infinities of opposite signs")
 });
 
-impl_op_ex!(+=|lhs: &mut Rational, rhs: &Rational| {*lhs = lhs.add(rhs) });
+impl_op_ex!(+=|lhs: &mut Rational, rhs: &Rational| {*lhs = *lhs + rhs });
 
 impl_op_ex!(-|lhs: &Rational, rhs: &Rational| -> Rational {
-    if let (Rational::Value(lhs), Rational::Value(rhs)) = (lhs, rhs) {
-        Rational::from(lhs - rhs)
-    } else {
-        panic!()
-    }
+    lhs.checked_sub(rhs)
+        .expect("rational subtraction overflowed or subtracts infinities of the same sign")
 });
 
-impl_op_ex!(-=|lhs: &mut Rational, rhs: &Rational| {*lhs = lhs.sub(rhs) });
+impl_op_ex!(-=|lhs: &mut Rational, rhs: &Rational| {*lhs = *lhs - rhs });
 
 impl_op_ex!(*|lhs: &Rational, rhs: &Rational| -> Rational {
-    match (lhs, rhs) {
-        (Rational::Value(lhs), Rational::Value(rhs)) => Rational::from(lhs * rhs),
-        (Rational::Value(lhs), Rational::PositiveInfinity) if lhs > &0.into() => {
-            Rational::PositiveInfinity
-        }
-        (Rational::Value(lhs), Rational::PositiveInfinity) if lhs < &0.into() => {
-            Rational::NegativeInfinity
-        }
-        (Rational::Value(lhs), Rational::NegativeInfinity) if lhs > &0.into() => {
-            Rational::NegativeInfinity
-        }
-        (Rational::Value(lhs), Rational::NegativeInfinity) if lhs < &0.into() => {
-            Rational::PositiveInfinity
-        }
-        (Rational::Value(_), _) => {
-            panic!()
-        }
-        (rhs, lhs) => Mul::mul(lhs, rhs), // NOTE: Be careful here not to loop
-    }
+    lhs.checked_mul(rhs)
+        .expect("rational multiplication overflowed or multiplies infinity by zero")
 });
 
-impl_op_ex!(*=|lhs: &mut Rational, rhs: &Rational| {*lhs = lhs.mul(rhs) });
+impl_op_ex!(*=|lhs: &mut Rational, rhs: &Rational| {*lhs = *lhs * rhs });
 
 impl_op_ex!(/|lhs: &Rational, rhs: &Rational| -> Rational {
-    if let (Rational::Value(lhs), Rational::Value(rhs)) = (lhs, rhs) {
-        Rational::from(lhs / rhs)
-    } else {
-        panic!()
-    }
+    lhs.checked_div(rhs)
+        .expect("rational division overflowed, divides by zero or divides infinities")
 });
-impl_op_ex!(/=|lhs: &mut Rational, rhs: &Rational| {*lhs = lhs.div(rhs) });
+impl_op_ex!(/=|lhs: &mut Rational, rhs: &Rational| {*lhs = *lhs / rhs });
 
 impl_op_ex!(-|lhs: &Rational| -> Rational {
-    match lhs {
-        Rational::NegativeInfinity => Rational::PositiveInfinity,
-        Rational::Value(val) => Rational::Value(-val),
-        Rational::PositiveInfinity => Rational::NegativeInfinity,
-    }
+    lhs.checked_neg().expect("rational negation overflowed")
 });
 
 impl Display for Rational {
@@ -193,4 +325,104 @@ fn parsing_works_positive() {
     test_parsing_works("42");
     test_parsing_works("-1/2");
     test_parsing_works("2/3");
+    test_parsing_works("∞");
+    test_parsing_works("-∞");
+}
+
+#[test]
+fn overflow_is_detected() {
+    let max = Rational::from(i64::MAX);
+    assert_eq!(max.checked_add(&Rational::from(1)), None);
+    assert_eq!(max.checked_mul(&Rational::from(2)), None);
+    assert_eq!(
+        Rational::new(1, 3).checked_add(&Rational::new(1, 6)),
+        Some(Rational::new(1, 2))
+    );
+    assert_eq!(
+        Rational::new(i64::MAX, 2).checked_div(&Rational::new(i64::MAX, 4)),
+        Some(Rational::from(2))
+    );
+    assert_eq!(Rational::from(1).checked_div(&Rational::from(0)), None);
+    assert_eq!(
+        Rational::PositiveInfinity.checked_add(&max),
+        Some(Rational::PositiveInfinity)
+    );
+    assert_eq!(Rational::from(i64::MIN).checked_neg(), None);
+    assert_eq!(Rational::new(1, 2).to_fraction(), Some((1, 2)));
+    assert_eq!(
+        Rational::new(1, u32::MAX)
+            .checked_mul(&Rational::new(1, 2))
+            .and_then(Rational::to_fraction),
+        None
+    );
+}
+
+#[test]
+fn infinite_arithmetic() {
+    let inf = Rational::PositiveInfinity;
+    let neg_inf = Rational::NegativeInfinity;
+    assert_eq!(inf.checked_add(&inf), Some(inf));
+    assert_eq!(inf.checked_add(&neg_inf), None);
+    assert_eq!(inf.checked_sub(&inf), None);
+    assert_eq!(Rational::from(1).checked_sub(&inf), Some(neg_inf));
+    assert_eq!(Rational::from(-2).checked_mul(&neg_inf), Some(inf));
+    assert_eq!(inf.checked_mul(&neg_inf), Some(neg_inf));
+    assert_eq!(Rational::from(0).checked_mul(&inf), None);
+    assert_eq!(
+        Rational::from(3).checked_div(&neg_inf),
+        Some(Rational::from(0))
+    );
+    assert_eq!(neg_inf.checked_div(&Rational::from(-3)), Some(inf));
+    assert_eq!(inf.checked_div(&inf), None);
+    assert_eq!(Rational::new(1, 2) * inf, inf);
+}
+
+#[test]
+fn saturating_arithmetic() {
+    let max = Rational::from(i64::MAX);
+    let min = Rational::from(i64::MIN);
+    assert_eq!(
+        max.checked_saturating_add(&max),
+        Some(Rational::PositiveInfinity)
+    );
+    assert_eq!(
+        min.checked_saturating_sub(&max),
+        Some(Rational::NegativeInfinity)
+    );
+    assert_eq!(
+        max.checked_saturating_sub(&min),
+        Some(Rational::PositiveInfinity)
+    );
+    assert_eq!(
+        min.checked_saturating_mul(&max),
+        Some(Rational::NegativeInfinity)
+    );
+    assert_eq!(
+        Rational::PositiveInfinity.checked_saturating_sub(&min),
+        Some(Rational::PositiveInfinity)
+    );
+    assert_eq!(
+        Rational::from(3).checked_saturating_mul(&Rational::NegativeInfinity),
+        Some(Rational::NegativeInfinity)
+    );
+    assert_eq!(
+        Rational::from(1).checked_saturating_add(&Rational::new(1, 2)),
+        Some(Rational::new(3, 2))
+    );
+    assert_eq!(
+        Rational::PositiveInfinity.checked_saturating_add(&Rational::NegativeInfinity),
+        None
+    );
+    assert_eq!(
+        Rational::from(0).checked_saturating_mul(&Rational::PositiveInfinity),
+        None
+    );
+    assert_eq!(
+        Rational::new(1, i32::MAX as u32)
+            .checked_saturating_mul(&Rational::new(1, i32::MAX as u32)),
+        Some(Rational::Value(Rational64::new(
+            1,
+            i64::from(i32::MAX).pow(2)
+        )))
+    );
 }
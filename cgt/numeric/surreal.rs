@@ -0,0 +1,277 @@
+//! Surreal numbers beyond dyadic rationals.
+//!
+//! Numbers are represented in Conway normal form `Σ r_i ω^(e_i)` with finitely many terms,
+//! rational coefficients `r_i` and integer exponents `e_i`. This covers all dyadic rationals
+//! (numbers born on finite days), other rationals like `1/3`, and simple infinite and
+//! infinitesimal numbers like `ω`, `ω - 1` or `ε = ω^-1`. The set is closed under addition,
+//! negation and multiplication.
+
+use crate::numeric::dyadic_rational_number::DyadicRationalNumber;
+use alloc::vec::Vec;
+use auto_ops::impl_op_ex;
+use num_rational::Rational64;
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+};
+
+/// Surreal number in Conway normal form with finitely many terms
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct Surreal {
+    /// Pairs of exponent and coefficient, sorted by exponents in descending order. Coefficients
+    /// are never zero.
+    terms: Vec<(i64, Rational64)>,
+}
+
+impl Surreal {
+    /// Construct number from pairs of exponents of `ω` and their coefficients
+    pub fn from_terms(terms: impl IntoIterator<Item = (i64, Rational64)>) -> Self {
+        let mut terms: Vec<(i64, Rational64)> = terms.into_iter().collect();
+        terms.sort_by(|(lhs, _), (rhs, _)| rhs.cmp(lhs));
+
+        let mut merged: Vec<(i64, Rational64)> = Vec::with_capacity(terms.len());
+        for (exponent, coefficient) in terms {
+            match merged.last_mut() {
+                Some((last_exponent, last_coefficient)) if *last_exponent == exponent => {
+                    *last_coefficient += coefficient;
+                }
+                _ => merged.push((exponent, coefficient)),
+            }
+        }
+        merged.retain(|(_, coefficient)| *coefficient != Rational64::from(0));
+
+        Self { terms: merged }
+    }
+
+    /// Zero
+    pub const fn zero() -> Self {
+        Self { terms: Vec::new() }
+    }
+
+    /// Real rational number
+    pub fn new_rational(value: Rational64) -> Self {
+        Self::from_terms([(0, value)])
+    }
+
+    /// `ω^exponent`
+    pub fn omega_power(exponent: i64) -> Self {
+        Self::from_terms([(exponent, Rational64::from(1))])
+    }
+
+    /// The first infinite ordinal `ω = {0, 1, 2, ... |}`
+    pub fn omega() -> Self {
+        Self::omega_power(1)
+    }
+
+    /// The infinitesimal `ε = 1/ω = {0 | 1, 1/2, 1/4, ...}`
+    pub fn epsilon() -> Self {
+        Self::omega_power(-1)
+    }
+
+    /// Get terms as pairs of exponent of `ω` and its coefficient, sorted by exponents in
+    /// descending order
+    pub fn terms(&self) -> &[(i64, Rational64)] {
+        &self.terms
+    }
+
+    /// Coefficient of the `ω^exponent` term
+    pub fn coefficient(&self, exponent: i64) -> Rational64 {
+        self.terms
+            .iter()
+            .find(|(e, _)| *e == exponent)
+            .map_or_else(|| Rational64::from(0), |(_, coefficient)| *coefficient)
+    }
+
+    /// Check if number is real, i.e. neither infinite nor has infinitesimal part
+    pub fn is_real(&self) -> bool {
+        self.terms.iter().all(|(exponent, _)| *exponent == 0)
+    }
+
+    /// Check if number is infinite
+    pub fn is_infinite(&self) -> bool {
+        self.terms
+            .first()
+            .is_some_and(|(exponent, _)| *exponent > 0)
+    }
+
+    /// Check if number is infinitesimal, i.e. non-zero and smaller than every positive real
+    pub fn is_infinitesimal(&self) -> bool {
+        self.terms
+            .first()
+            .is_some_and(|(exponent, _)| *exponent < 0)
+    }
+
+    /// Convert to dyadic rational, if number is born on a finite day
+    pub fn to_dyadic(&self) -> Option<DyadicRationalNumber> {
+        if !self.is_real() {
+            return None;
+        }
+        let value = self.coefficient(0);
+        let denominator = *value.denom();
+        if denominator.count_ones() != 1 {
+            return None;
+        }
+        Some(DyadicRationalNumber::new(
+            *value.numer(),
+            denominator.trailing_zeros(),
+        ))
+    }
+
+    /// Sign of the number
+    pub fn signum(&self) -> Ordering {
+        self.terms
+            .first()
+            .map_or(Ordering::Equal, |(_, coefficient)| {
+                coefficient.cmp(&Rational64::from(0))
+            })
+    }
+}
+
+impl From<i64> for Surreal {
+    fn from(value: i64) -> Self {
+        Self::new_rational(Rational64::from(value))
+    }
+}
+
+impl From<Rational64> for Surreal {
+    fn from(value: Rational64) -> Self {
+        Self::new_rational(value)
+    }
+}
+
+impl From<DyadicRationalNumber> for Surreal {
+    fn from(value: DyadicRationalNumber) -> Self {
+        let denominator = 1_i64 << value.denominator_exponent();
+        Self::new_rational(Rational64::new(value.numerator(), denominator))
+    }
+}
+
+impl_op_ex!(+|lhs: &Surreal, rhs: &Surreal| -> Surreal {
+    Surreal::from_terms(lhs.terms.iter().chain(rhs.terms.iter()).copied())
+});
+
+impl_op_ex!(+=|lhs: &mut Surreal, rhs: &Surreal| { *lhs = &*lhs + rhs });
+
+impl_op_ex!(-|lhs: &Surreal| -> Surreal {
+    Surreal {
+        terms: lhs
+            .terms
+            .iter()
+            .map(|(exponent, coefficient)| (*exponent, -coefficient))
+            .collect(),
+    }
+});
+
+impl_op_ex!(-|lhs: &Surreal, rhs: &Surreal| -> Surreal { lhs + (-rhs) });
+
+impl_op_ex!(-=|lhs: &mut Surreal, rhs: &Surreal| { *lhs = &*lhs - rhs });
+
+impl_op_ex!(*|lhs: &Surreal, rhs: &Surreal| -> Surreal {
+    Surreal::from_terms(
+        lhs.terms
+            .iter()
+            .flat_map(|(lhs_exponent, lhs_coefficient)| {
+                rhs.terms
+                    .iter()
+                    .map(move |(rhs_exponent, rhs_coefficient)| {
+                        (
+                            lhs_exponent + rhs_exponent,
+                            lhs_coefficient * rhs_coefficient,
+                        )
+                    })
+            }),
+    )
+});
+
+impl_op_ex!(*=|lhs: &mut Surreal, rhs: &Surreal| { *lhs = &*lhs * rhs });
+
+impl PartialOrd for Surreal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Surreal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self - other).signum()
+    }
+}
+
+impl Display for Surreal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+
+        for (idx, (exponent, coefficient)) in self.terms.iter().enumerate() {
+            let is_negative = *coefficient < Rational64::from(0);
+            let magnitude = if is_negative {
+                -coefficient
+            } else {
+                *coefficient
+            };
+
+            match (idx == 0, is_negative) {
+                (true, true) => write!(f, "-")?,
+                (true, false) => {}
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+            }
+
+            if *exponent == 0 {
+                write!(f, "{}", magnitude)?;
+                continue;
+            }
+
+            if magnitude != Rational64::from(1) {
+                if magnitude.is_integer() {
+                    write!(f, "{}", magnitude)?;
+                } else {
+                    write!(f, "({})", magnitude)?;
+                }
+            }
+            write!(f, "ω")?;
+            if *exponent != 1 {
+                write!(f, "^{}", exponent)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordering_works() {
+        let omega = Surreal::omega();
+        let epsilon = Surreal::epsilon();
+        let one = Surreal::from(1);
+
+        assert!(omega > Surreal::from(1_000_000));
+        assert!(&omega - &one < omega);
+        assert!(epsilon > Surreal::zero());
+        assert!(epsilon < Surreal::from(Rational64::new(1, 1_000_000)));
+        assert!(-&epsilon < Surreal::zero());
+        assert_eq!(&omega * &epsilon, one);
+    }
+
+    #[test]
+    fn dyadic_conversion() {
+        let half = DyadicRationalNumber::new(1, 1);
+        assert_eq!(Surreal::from(half).to_dyadic(), Some(half));
+        assert_eq!(Surreal::from(Rational64::new(1, 3)).to_dyadic(), None);
+        assert_eq!(Surreal::omega().to_dyadic(), None);
+    }
+
+    #[test]
+    fn display_works() {
+        let omega = Surreal::omega();
+        let value = &(&omega * &omega) - &(&Surreal::from(2) * &omega)
+            + Surreal::from(Rational64::new(1, 3))
+            + Surreal::epsilon();
+        assert_eq!(value.to_string(), "ω^2 - 2ω + 1/3 + ω^-1");
+        assert_eq!((-Surreal::from(5)).to_string(), "-5");
+    }
+}
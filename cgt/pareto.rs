@@ -0,0 +1,121 @@
+//! Pareto fronts of objects scored with many objectives
+//!
+//! All objectives are maximized, negate objectives that should be minimized.
+
+use crate::genetic_algorithm::Scored;
+
+/// Check if `lhs` is at least as good as `rhs` in every objective and better in at least one
+///
+/// # Panics
+/// - Objective vectors have different lengths
+pub fn dominates<Score>(lhs: &[Score], rhs: &[Score]) -> bool
+where
+    Score: Ord,
+{
+    assert_eq!(lhs.len(), rhs.len(), "Different number of objectives");
+    lhs.iter().zip(rhs).all(|(lhs, rhs)| lhs >= rhs)
+        && lhs.iter().zip(rhs).any(|(lhs, rhs)| lhs > rhs)
+}
+
+/// Set of objects not dominated by any other object seen so far
+#[derive(Clone, Debug)]
+pub struct ParetoFront<Object, Score> {
+    members: Vec<Scored<Object, Vec<Score>>>,
+}
+
+impl<Object, Score> Default for ParetoFront<Object, Score>
+where
+    Object: PartialEq,
+    Score: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Object, Score> ParetoFront<Object, Score>
+where
+    Object: PartialEq,
+    Score: Ord,
+{
+    /// Create empty front
+    pub const fn new() -> Self {
+        Self {
+            members: Vec::new(),
+        }
+    }
+
+    /// Add object to the front, unless it is dominated by a member or already present. Members
+    /// dominated by the object are removed. Returns `true` if the object was added.
+    pub fn insert(&mut self, object: Object, objectives: Vec<Score>) -> bool {
+        if self
+            .members
+            .iter()
+            .any(|member| member.object == object || dominates(&member.score, &objectives))
+        {
+            return false;
+        }
+
+        self.members
+            .retain(|member| !dominates(&objectives, &member.score));
+        self.members.push(Scored {
+            object,
+            score: objectives,
+        });
+        true
+    }
+
+    /// Number of members that dominate given objectives
+    pub fn dominated_by(&self, objectives: &[Score]) -> usize {
+        self.members
+            .iter()
+            .filter(|member| dominates(&member.score, objectives))
+            .count()
+    }
+
+    /// Members of the front, in order of insertion
+    pub fn members(&self) -> &[Scored<Object, Vec<Score>>] {
+        &self.members
+    }
+
+    /// Number of members of the front
+    pub const fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Check if the front has no members
+    pub const fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dominance() {
+        assert!(dominates(&[2, 1], &[1, 1]));
+        assert!(!dominates(&[1, 1], &[1, 1]));
+        assert!(!dominates(&[2, 0], &[1, 1]));
+    }
+
+    #[test]
+    fn keeps_non_dominated() {
+        let mut front = ParetoFront::new();
+        assert!(front.insert('a', vec![1, 1]));
+        assert!(front.insert('b', vec![2, 0]));
+        assert!(!front.insert('c', vec![0, 1]));
+        assert!(!front.insert('a', vec![1, 1]));
+        assert_eq!(front.dominated_by(&[0, 0]), 2);
+        assert!(front.insert('d', vec![2, 1]));
+        assert_eq!(
+            front
+                .members()
+                .iter()
+                .map(|member| member.object)
+                .collect::<Vec<_>>(),
+            vec!['d']
+        );
+    }
+}
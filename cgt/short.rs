@@ -1,4 +1,5 @@
 //! Short games - normal play
 
+#[cfg(feature = "std")]
 pub mod impartial;
 pub mod partizan;
@@ -2,3 +2,5 @@
 
 pub mod games;
 pub mod impartial_game;
+pub mod misere_canonical_form;
+pub mod misere_quotient;
@@ -1,6 +1,8 @@
 //! Impartial game - both players have the same moves
 
-use crate::numeric::nimber::Nimber;
+use crate::{
+    numeric::nimber::Nimber, short::impartial::misere_canonical_form::MisereCanonicalForm,
+};
 
 /// Impartial game
 pub trait ImpartialGame: Sized {
@@ -16,4 +18,14 @@ pub trait ImpartialGame: Sized {
         }
         Nimber::mex(game_moves)
     }
+
+    /// Calculate the canonical form of the position under misère play
+    fn misere_canonical_form(&self) -> MisereCanonicalForm {
+        let moves = self.moves();
+        let mut options = Vec::with_capacity(moves.len());
+        for m in moves {
+            options.push(m.misere_canonical_form());
+        }
+        MisereCanonicalForm::new(options)
+    }
 }
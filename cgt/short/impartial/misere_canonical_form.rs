@@ -0,0 +1,217 @@
+//! Canonical forms of impartial games under misère play, i.e. the player that cannot move in
+//! their turn wins.
+//!
+//! Unlike normal play, impartial games under misère play do not collapse to nimbers, so the whole
+//! simplified game tree has to be kept.
+
+use crate::{display, numeric::nimber::Nimber};
+use auto_ops::impl_op_ex;
+use std::fmt::{self, Display};
+
+/// Misère outcome of a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MisereOutcome {
+    /// Next player wins
+    N,
+
+    /// Previous player wins
+    P,
+}
+
+/// Game tree of an impartial game simplified using Conway's misère simplification rules.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MisereCanonicalForm {
+    options: Vec<Self>,
+}
+
+impl MisereCanonicalForm {
+    /// Construct canonical form of a game with given options. Options are assumed to be already
+    /// in canonical form.
+    pub fn new(mut options: Vec<Self>) -> Self {
+        options.sort();
+        options.dedup();
+
+        // G is equivalent to its grandchild G'' if every option of G that is not an option of G''
+        // is reversible through G'' and outcomes of G and G'' agree.
+        for option in &options {
+            for grandchild in &option.options {
+                if !grandchild.options.iter().all(|g| options.contains(g)) {
+                    continue;
+                }
+
+                let mut extra_options = options.iter().filter(|g| !grandchild.options.contains(g));
+
+                let all_reversible = extra_options
+                    .clone()
+                    .all(|extra| extra.options.contains(grandchild));
+
+                // Options of G'' are already options of G, so outcomes can only differ when G''
+                // has no moves.
+                let same_outcome = !grandchild.options.is_empty()
+                    || extra_options.any(|extra| extra.outcome() == MisereOutcome::P);
+
+                if all_reversible && same_outcome {
+                    return grandchild.clone();
+                }
+            }
+        }
+
+        Self { options }
+    }
+
+    /// Construct the canonical form of nimber `*n`
+    pub fn new_nimber(nimber: Nimber) -> Self {
+        let mut options = Vec::with_capacity(nimber.value() as usize);
+        for n in 0..nimber.value() {
+            options.push(Self::new_nimber(Nimber::new(n)));
+        }
+        Self::new(options)
+    }
+
+    /// Get options of the game
+    pub fn options(&self) -> &[Self] {
+        &self.options
+    }
+
+    /// Check if the game is a nimber, and return its value if so
+    pub fn to_nimber(&self) -> Option<Nimber> {
+        for (idx, option) in self.options.iter().enumerate() {
+            if option.to_nimber()? != Nimber::new(idx as u32) {
+                return None;
+            }
+        }
+        Some(Nimber::new(self.options.len() as u32))
+    }
+
+    /// Get the misère outcome of the game
+    pub fn outcome(&self) -> MisereOutcome {
+        if self.options.is_empty()
+            || self
+                .options
+                .iter()
+                .any(|option| option.outcome() == MisereOutcome::P)
+        {
+            MisereOutcome::N
+        } else {
+            MisereOutcome::P
+        }
+    }
+
+    /// Get the normal play nim value of the game
+    pub fn nim_value(&self) -> Nimber {
+        Nimber::mex(self.options.iter().map(Self::nim_value).collect())
+    }
+
+    /// Get the misère Grundy value of the game, that is `1` for the game without options, and
+    /// minimum excluded value of options otherwise
+    pub fn misere_nim_value(&self) -> Nimber {
+        if self.options.is_empty() {
+            return Nimber::new(1);
+        }
+        Nimber::mex(self.options.iter().map(Self::misere_nim_value).collect())
+    }
+
+    /// Construct a disjunctive sum of two games
+    pub fn construct_sum(g: &Self, h: &Self) -> Self {
+        let mut options = Vec::with_capacity(g.options.len() + h.options.len());
+        for g_option in &g.options {
+            options.push(Self::construct_sum(g_option, h));
+        }
+        for h_option in &h.options {
+            options.push(Self::construct_sum(g, h_option));
+        }
+        Self::new(options)
+    }
+
+    /// Compute the genus symbol of the game
+    pub fn genus(&self) -> Genus {
+        let two = Self::new_nimber(Nimber::new(2));
+
+        let mut misere = Vec::new();
+        let mut current = self.clone();
+        loop {
+            misere.push(current.misere_nim_value().value());
+            let len = misere.len();
+            if len >= 3 && misere[len - 1] == misere[len - 3] {
+                misere.pop();
+                break;
+            }
+            current = &current + &two;
+        }
+
+        Genus {
+            nim_value: self.nim_value().value(),
+            misere,
+        }
+    }
+}
+
+impl_op_ex!(+|g: &MisereCanonicalForm, h: &MisereCanonicalForm| -> MisereCanonicalForm {
+    MisereCanonicalForm::construct_sum(g, h)
+});
+
+impl Display for MisereCanonicalForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(nimber) = self.to_nimber() {
+            return write!(f, "{}", nimber);
+        }
+        display::braces(f, |f| display::commas(f, &self.options))
+    }
+}
+
+/// Genus symbol `g^(γ0 γ1 ...)` of an impartial game
+///
+/// `g` is the normal play nim value and `γn` is the misère Grundy value of the game plus `n`
+/// copies of `*2`. The sequence `γ` is eventually periodic with period 2, and it is stored only
+/// up to the point where it starts repeating.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Genus {
+    /// Normal play nim value
+    pub nim_value: u32,
+
+    /// Misère Grundy values of the game plus consecutive numbers of `*2`
+    pub misere: Vec<u32>,
+}
+
+impl Display for Genus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}^", self.nim_value)?;
+        for gamma in &self.misere {
+            write!(f, "{}", gamma)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nimber(n: u32) -> MisereCanonicalForm {
+        MisereCanonicalForm::new_nimber(Nimber::new(n))
+    }
+
+    #[test]
+    fn star_plus_star_is_zero() {
+        assert_eq!(&nimber(1) + &nimber(1), nimber(0));
+        assert_ne!(&nimber(2) + &nimber(2), nimber(0));
+    }
+
+    #[test]
+    fn genus_works() {
+        assert_eq!(nimber(0).genus().to_string(), "0^120");
+        assert_eq!(nimber(1).genus().to_string(), "1^031");
+        assert_eq!(nimber(2).genus().to_string(), "2^20");
+        assert_eq!((&nimber(2) + &nimber(2)).genus().to_string(), "0^02");
+    }
+
+    #[test]
+    fn display_works() {
+        let g = MisereCanonicalForm::new(vec![nimber(2)]);
+        assert_eq!(g.to_string(), "{*2}");
+        assert_eq!(g.outcome(), MisereOutcome::P);
+    }
+}
@@ -0,0 +1,557 @@
+//! Misère quotients of sets of impartial games, as introduced by Plambeck and Siegel.
+//!
+//! Misère quotient of a set of games `A` closed under sums and options is the commutative monoid
+//! of games from `A` modulo indistinguishability: `G` and `H` are identified if for every `X`
+//! from `A` the misère outcomes of `G + X` and `H + X` are the same.
+//!
+//! Quotient is computed for sums of the hereditary closure of given games, i.e. of the games and
+//! all their positions. Candidate quotient identifies sums that no test sum tells apart, and it is
+//! accepted once it is commutative and predicts outcomes of all sums: a sum must be a P-position
+//! exactly when it has options and none of them is a P-position. Element of an option depends only
+//! on elements of sums with one position less, so it is enough to check the finitely many
+//! combinations of these. Every failed check gives a new test sum, and the candidate is refined.
+
+use crate::short::impartial::misere_canonical_form::MisereCanonicalForm;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{self, Display, Write},
+};
+
+/// Misère quotient of a set of impartial games
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MisereQuotient {
+    generators: Vec<MisereCanonicalForm>,
+    elements: Vec<Vec<u32>>,
+    generator_elements: Vec<usize>,
+    p_portion: Vec<usize>,
+    table: Vec<Vec<usize>>,
+}
+
+/// Helper for caching outcomes of sums of generators
+struct Outcomes {
+    /// Options of every generator as indices of generators, with `None` for zero
+    options: Vec<Vec<Option<usize>>>,
+    cache: HashMap<Vec<u32>, bool>,
+}
+
+impl Outcomes {
+    fn is_p(&mut self, multiplicities: &[u32]) -> bool {
+        if let Some(is_p) = self.cache.get(multiplicities) {
+            return *is_p;
+        }
+
+        // Game without options is a win for the next player
+        let mut is_p = multiplicities.iter().any(|m| *m != 0);
+        'outer: for (generator, m) in multiplicities.iter().enumerate() {
+            if *m == 0 {
+                continue;
+            }
+            for option in self.options[generator].clone() {
+                let mut option_multiplicities = multiplicities.to_vec();
+                option_multiplicities[generator] -= 1;
+                if let Some(option) = option {
+                    option_multiplicities[option] += 1;
+                }
+                if self.is_p(&option_multiplicities) {
+                    is_p = false;
+                    break 'outer;
+                }
+            }
+        }
+
+        self.cache.insert(multiplicities.to_vec(), is_p);
+        is_p
+    }
+
+    fn is_p_word(&mut self, word: &[usize]) -> bool {
+        let mut multiplicities = vec![0; self.options.len()];
+        for generator in word {
+            multiplicities[*generator] += 1;
+        }
+        self.is_p(&multiplicities)
+    }
+}
+
+/// Candidate quotient, where sums with equal outcomes after adding every test sum are identified
+struct Candidate {
+    /// Sums that tell elements apart, as words of generators. The empty sum comes first.
+    tests: Vec<Vec<usize>>,
+
+    /// Shortest words of every element
+    paths: Vec<Vec<usize>>,
+
+    /// Outcomes of paths after adding test sums
+    signatures: Vec<Vec<bool>>,
+
+    /// Element of the path of an element plus a generator
+    transitions: Vec<Vec<usize>>,
+}
+
+impl Candidate {
+    fn new(outcomes: &mut Outcomes, tests: Vec<Vec<usize>>, max_size: usize) -> Option<Self> {
+        let generators = outcomes.options.len();
+        let mut signature = |path: &[usize]| {
+            tests
+                .iter()
+                .map(|test| outcomes.is_p_word(&[path, test].concat()))
+                .collect::<Vec<_>>()
+        };
+
+        let mut paths = vec![vec![]];
+        let mut signatures = vec![signature(&[])];
+        let mut transitions = Vec::new();
+        let mut elements = HashMap::new();
+        elements.insert(signatures[0].clone(), 0);
+        while transitions.len() < paths.len() {
+            let element = transitions.len();
+            let mut element_transitions = Vec::with_capacity(generators);
+            for generator in 0..generators {
+                let mut path = paths[element].clone();
+                path.push(generator);
+                let path_signature = signature(&path);
+                let next = *elements.entry(path_signature.clone()).or_insert_with(|| {
+                    paths.push(path);
+                    signatures.push(path_signature);
+                    paths.len() - 1
+                });
+                element_transitions.push(next);
+            }
+            transitions.push(element_transitions);
+            if paths.len() > max_size {
+                return None;
+            }
+        }
+
+        Some(Self {
+            tests,
+            paths,
+            signatures,
+            transitions,
+        })
+    }
+
+    /// Element of the path of `element` plus generators from `word`
+    fn follow(&self, element: usize, word: &[usize]) -> usize {
+        word.iter().fold(element, |element, generator| {
+            self.transitions[element][*generator]
+        })
+    }
+
+    /// Check if element is predicted to be a P-position
+    fn is_p(&self, element: usize) -> bool {
+        self.signatures[element][0]
+    }
+
+    /// Find a sum whose outcome is predicted wrong, if any
+    fn counterexample(&self, outcomes: &mut Outcomes) -> Option<Vec<usize>> {
+        let generators = outcomes.options.len();
+        let mispredicted = |outcomes: &mut Outcomes, word: &Vec<usize>| {
+            outcomes.is_p_word(word) != self.is_p(self.follow(0, word))
+        };
+
+        // Adding generators in different order must give the same element. Otherwise some test
+        // tells the two results apart, and one of the sums with that test is predicted wrong.
+        for element in 0..self.paths.len() {
+            for lhs in 0..generators {
+                for rhs in lhs + 1..generators {
+                    let first = self.follow(element, &[lhs, rhs]);
+                    let second = self.follow(element, &[rhs, lhs]);
+                    if first == second {
+                        continue;
+                    }
+
+                    let test = self.signatures[first]
+                        .iter()
+                        .zip(&self.signatures[second])
+                        .position(|(a, b)| a != b)
+                        .expect("different elements have different signatures");
+                    let test = &self.tests[test];
+                    let path = &self.paths[element];
+                    return [
+                        [path.as_slice(), &[lhs, rhs][..], test].concat(),
+                        [path.as_slice(), &[rhs, lhs][..], test].concat(),
+                        [self.paths[first].as_slice(), test].concat(),
+                        [self.paths[second].as_slice(), test].concat(),
+                    ]
+                    .into_iter()
+                    .find(|word| mispredicted(outcomes, word));
+                }
+            }
+        }
+
+        // Check the outcome rule on every combination of the element of a sum and elements of the
+        // sum without one copy of every generator in it
+        let start: (usize, Vec<Option<usize>>) = (0, vec![None; generators]);
+        let mut seen = HashSet::from([start.clone()]);
+        let mut queue = VecDeque::from([(start, Vec::new())]);
+        while let Some(((element, without), path)) = queue.pop_front() {
+            let mut sum_options = Vec::new();
+            for (generator, without) in without.iter().enumerate() {
+                let Some(without) = *without else {
+                    continue;
+                };
+                for option in &outcomes.options[generator] {
+                    let option_element =
+                        option.map_or(without, |option| self.transitions[without][option]);
+                    sum_options.push((option_element, generator, *option));
+                }
+            }
+
+            let predicted = !path.is_empty()
+                && sum_options
+                    .iter()
+                    .all(|(option_element, _, _)| !self.is_p(*option_element));
+            if predicted != self.is_p(element) {
+                let mut words = vec![path.clone()];
+                for (_, generator, option) in sum_options {
+                    let mut word = path.clone();
+                    let moved = word
+                        .iter()
+                        .position(|g| *g == generator)
+                        .expect("generator is in the sum");
+                    word.remove(moved);
+                    word.extend(option);
+                    words.push(word);
+                }
+                return words.into_iter().find(|word| mispredicted(outcomes, word));
+            }
+
+            for generator in 0..generators {
+                let mut next_without: Vec<Option<usize>> = without
+                    .iter()
+                    .map(|without| without.map(|without| self.transitions[without][generator]))
+                    .collect();
+                next_without[generator] = Some(element);
+                let next = (self.transitions[element][generator], next_without);
+                if seen.insert(next.clone()) {
+                    let mut next_path = path.clone();
+                    next_path.push(generator);
+                    queue.push_back((next, next_path));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Find a test that tells apart two sums identified by the candidate, given a sum whose
+    /// outcome is predicted wrong
+    fn new_test(&self, outcomes: &mut Outcomes, word: &[usize]) -> Vec<usize> {
+        // Replacing prefixes of the word by paths of their elements changes the outcome somewhere
+        let mut element = 0;
+        let mut is_p = outcomes.is_p_word(word);
+        for idx in 0..word.len() {
+            element = self.transitions[element][word[idx]];
+            let suffix = &word[idx + 1..];
+            let next_is_p = outcomes.is_p_word(&[&self.paths[element], suffix].concat());
+            if next_is_p != is_p {
+                return suffix.to_vec();
+            }
+            is_p = next_is_p;
+        }
+        unreachable!("outcome of the word is predicted correctly")
+    }
+}
+
+impl MisereQuotient {
+    /// Compute misère quotient of sums of `games` and all their positions. Returns `None` if the
+    /// quotient has more than `max_size` elements.
+    pub fn new(games: Vec<MisereCanonicalForm>, max_size: usize) -> Option<Self> {
+        // Given games come first, so they get the first letters
+        let zero = MisereCanonicalForm::new(vec![]);
+        let mut generators = Vec::new();
+        let mut indices = HashMap::new();
+        let mut queue = VecDeque::from(games);
+        while let Some(game) = queue.pop_front() {
+            if game == zero || indices.contains_key(&game) {
+                continue;
+            }
+            indices.insert(game.clone(), generators.len());
+            queue.extend(game.options().iter().cloned());
+            generators.push(game);
+        }
+
+        let mut outcomes = Outcomes {
+            options: generators
+                .iter()
+                .map(|generator| {
+                    generator
+                        .options()
+                        .iter()
+                        .map(|option| indices.get(option).copied())
+                        .collect()
+                })
+                .collect(),
+            cache: HashMap::new(),
+        };
+
+        // Every test tells apart two more sums, so there is at most one test less than elements
+        let mut tests = vec![vec![]];
+        let candidate = loop {
+            let candidate = Candidate::new(&mut outcomes, tests, max_size)?;
+            let Some(word) = candidate.counterexample(&mut outcomes) else {
+                break candidate;
+            };
+            let test = candidate.new_test(&mut outcomes, &word);
+            tests = candidate.tests;
+            tests.push(test);
+            if tests.len() > max_size {
+                return None;
+            }
+        };
+
+        let elements: Vec<Vec<u32>> = candidate
+            .paths
+            .iter()
+            .map(|path| {
+                let mut multiplicities = vec![0; generators.len()];
+                for generator in path {
+                    multiplicities[*generator] += 1;
+                }
+                multiplicities
+            })
+            .collect();
+        let table = candidate
+            .paths
+            .iter()
+            .map(|lhs| {
+                candidate
+                    .paths
+                    .iter()
+                    .map(|rhs| candidate.follow(candidate.follow(0, lhs), rhs))
+                    .collect()
+            })
+            .collect();
+        let p_portion = (0..elements.len())
+            .filter(|element| candidate.is_p(*element))
+            .collect();
+
+        Some(Self {
+            generator_elements: candidate.transitions[0].clone(),
+            generators,
+            elements,
+            p_portion,
+            table,
+        })
+    }
+
+    /// Get generators of the quotient, i.e. given games followed by their positions
+    pub fn generators(&self) -> &[MisereCanonicalForm] {
+        &self.generators
+    }
+
+    /// Number of elements in the quotient
+    pub const fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Check if quotient is empty. Never true, as there is always an identity.
+    pub const fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Get minimal representative of element as multiplicities of generators. Identity has index 0.
+    pub fn representative(&self, element: usize) -> &[u32] {
+        &self.elements[element]
+    }
+
+    /// Get element of a game from the hereditary closure of the generators
+    pub fn element(&self, game: &MisereCanonicalForm) -> Option<usize> {
+        if game.options().is_empty() {
+            return Some(0);
+        }
+        self.generators
+            .iter()
+            .position(|generator| generator == game)
+            .map(|generator| self.generator_elements[generator])
+    }
+
+    /// Get elements that are P-positions
+    pub fn p_portion(&self) -> &[usize] {
+        &self.p_portion
+    }
+
+    /// Multiply two elements of the quotient
+    pub fn multiply(&self, lhs: usize, rhs: usize) -> usize {
+        self.table[lhs][rhs]
+    }
+
+    fn write_word(&self, f: &mut impl Write, element: usize) -> fmt::Result {
+        let multiplicities = &self.elements[element];
+        if multiplicities.iter().all(|m| *m == 0) {
+            return write!(f, "1");
+        }
+        for (idx, m) in multiplicities.iter().enumerate() {
+            let letter = char::from(b'a' + (idx % 26) as u8);
+            match m {
+                0 => {}
+                1 => write!(f, "{}", letter)?,
+                _ => write!(f, "{}^{}", letter, m)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for MisereQuotient {
+    /// Print the presentation of the quotient, i.e. generators with relations, and P-portion
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<")?;
+        for idx in 0..self.generators.len() {
+            if idx != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", char::from(b'a' + (idx % 26) as u8))?;
+        }
+
+        // Relations come from products that are not their own minimal representative
+        let mut first_relation = true;
+        for (lhs, lhs_element) in self.elements.iter().enumerate() {
+            for (generator, generator_element) in self.generator_elements.iter().enumerate() {
+                let mut product = lhs_element.clone();
+                product[generator] += 1;
+                if self.elements.contains(&product) {
+                    continue;
+                }
+
+                write!(f, "{}", if first_relation { " | " } else { ", " })?;
+                first_relation = false;
+                if lhs != 0 {
+                    self.write_word(f, lhs)?;
+                }
+                write!(f, "{} = ", char::from(b'a' + (generator % 26) as u8))?;
+                self.write_word(f, self.multiply(lhs, *generator_element))?;
+            }
+        }
+        write!(f, ">, P = {{")?;
+        for (idx, element) in self.p_portion.iter().enumerate() {
+            if idx != 0 {
+                write!(f, ", ")?;
+            }
+            self.write_word(f, *element)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numeric::nimber::Nimber;
+
+    fn nimber(n: u32) -> MisereCanonicalForm {
+        MisereCanonicalForm::new_nimber(Nimber::new(n))
+    }
+
+    #[test]
+    fn misere_nim_quotients() {
+        let quotient = MisereQuotient::new(vec![nimber(1)], 16).unwrap();
+        assert_eq!(quotient.len(), 2);
+        assert_eq!(quotient.to_string(), "<a | aa = 1>, P = {a}");
+
+        // T_2 = <a, b | a^2 = 1, b^3 = b>
+        let quotient = MisereQuotient::new(vec![nimber(1), nimber(2)], 16).unwrap();
+        assert_eq!(quotient.len(), 6);
+        assert_eq!(quotient.p_portion().len(), 2);
+
+        assert_eq!(MisereQuotient::new(vec![nimber(1), nimber(2)], 5), None);
+    }
+
+    /// Canonical forms of heaps of octal game 0.75 up to `max_heap`
+    fn heaps_0_75(max_heap: usize) -> Vec<MisereCanonicalForm> {
+        let mut heaps: Vec<MisereCanonicalForm> = vec![MisereCanonicalForm::new(vec![])];
+        for n in 1..=max_heap {
+            let mut options = Vec::new();
+            // Remove one token leaving zero, one or two heaps
+            options.push(heaps[n - 1].clone());
+            for a in 1..n - 1 {
+                options.push(&heaps[a] + &heaps[n - 1 - a]);
+            }
+            // Remove two tokens leaving zero or two heaps
+            if n == 2 {
+                options.push(heaps[0].clone());
+            }
+            for a in 1..n.saturating_sub(2) {
+                options.push(&heaps[a] + &heaps[n - 2 - a]);
+            }
+            heaps.push(MisereCanonicalForm::new(options));
+        }
+        heaps
+    }
+
+    /// Element `a^i x` of `R_8 = <a, b, c | a^2 = 1, b^3 = b, bc = ab, c^2 = b^2>` as `(i, x)` with
+    /// `x` being one of `1`, `b`, `b^2` and `c`
+    fn r8_product((lhs_a, lhs): (usize, usize), (rhs_a, rhs): (usize, usize)) -> (usize, usize) {
+        let (a, x) = match (lhs.min(rhs), lhs.max(rhs)) {
+            (0, x) => (0, x),
+            (1, 1) | (2, 2) | (3, 3) => (0, 2),
+            (1, 2) => (0, 1),
+            (1, 3) => (1, 1),
+            (2, 3) => (1, 2),
+            _ => unreachable!(),
+        };
+        ((lhs_a + rhs_a + a) % 2, x)
+    }
+
+    /// Check that the quotient is isomorphic to the submonoid of `R_8` generated by `a`, `b` and
+    /// `c` (if `has_c`), where `a`, `b` and `c` are heaps 1, 2 and 5 of 0.75, and that the P-portion
+    /// is `{a, b^2}`
+    fn assert_r8(quotient: &MisereQuotient, heaps: &[MisereCanonicalForm], has_c: bool) {
+        let letters = if has_c { 4 } else { 3 };
+        let r8_elements: Vec<(usize, usize)> = (0..2)
+            .flat_map(|a| (0..letters).map(move |x| (a, x)))
+            .collect();
+        assert_eq!(quotient.len(), r8_elements.len());
+
+        let a = quotient.element(&heaps[1]).unwrap();
+        let b = quotient.element(&heaps[2]).unwrap();
+        let c = heaps
+            .get(5)
+            .map_or(0, |heap| quotient.element(heap).unwrap());
+        let x_elements = [0, b, quotient.multiply(b, b), c];
+        let isomorphism = |(i, x): (usize, usize)| {
+            let x = x_elements[x];
+            if i == 0 {
+                x
+            } else {
+                quotient.multiply(a, x)
+            }
+        };
+
+        let mut image: Vec<usize> = r8_elements.iter().map(|e| isomorphism(*e)).collect();
+        for lhs in &r8_elements {
+            for rhs in &r8_elements {
+                assert_eq!(
+                    isomorphism(r8_product(*lhs, *rhs)),
+                    quotient.multiply(isomorphism(*lhs), isomorphism(*rhs))
+                );
+            }
+        }
+        image.sort_unstable();
+        image.dedup();
+        assert_eq!(image.len(), r8_elements.len());
+
+        let mut p_portion = vec![isomorphism((1, 0)), isomorphism((0, 2))];
+        p_portion.sort_unstable();
+        assert_eq!(quotient.p_portion(), p_portion);
+    }
+
+    #[test]
+    fn octal_0_75_quotient() {
+        // Heaps up to 4 give T_2 = <a, b | a^2 = 1, b^3 = b>, and all larger heaps give
+        // R_8 (Plambeck and Siegel, Misère quotients for impartial games)
+        let heaps = heaps_0_75(4);
+        let quotient = MisereQuotient::new(heaps[1..].to_vec(), 64).unwrap();
+        assert_r8(&quotient, &heaps, false);
+
+        let heaps = heaps_0_75(7);
+        let quotient = MisereQuotient::new(heaps[1..].to_vec(), 64).unwrap();
+        assert_r8(&quotient, &heaps, true);
+        let heap_elements: Vec<usize> = heaps
+            .iter()
+            .map(|heap| quotient.element(heap).unwrap())
+            .collect();
+        let [one, a, b, c] = [0, 1, 2, 5].map(|heap| heap_elements[heap]);
+        assert_eq!(heap_elements, [one, a, b, a, b, c, b, c]);
+    }
+}
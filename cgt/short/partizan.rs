@@ -1,8 +1,33 @@
 //! Partizan games
 
+#[cfg(feature = "std")]
+pub mod batch;
+#[cfg(feature = "std")]
+pub mod cancellation;
 pub mod canonical_form;
+#[cfg(feature = "std")]
+pub mod census;
+#[cfg(feature = "std")]
+pub mod cgsuite;
+#[cfg(feature = "std")]
+pub mod game_dag;
+#[cfg(feature = "std")]
+pub mod game_tree;
+#[cfg(feature = "std")]
 pub mod games;
+#[cfg(feature = "std")]
+pub mod misere_form;
+#[cfg(feature = "std")]
 pub mod partizan_game;
+#[cfg(feature = "std")]
+pub mod position;
+#[cfg(feature = "std")]
+pub mod random_position;
+#[cfg(feature = "std")]
+pub mod simplification;
+pub mod superstar;
 pub mod thermograph;
 pub mod trajectory;
+#[cfg(feature = "std")]
 pub mod transposition_table;
+pub mod uptimal;
@@ -0,0 +1,127 @@
+//! Evaluation of many positions at once, sharing a single transposition table.
+
+use crate::short::partizan::{
+    canonical_form::CanonicalForm, partizan_game::PartizanGame,
+    transposition_table::TranspositionTable,
+};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+
+/// Result of evaluating a single position with [`evaluate_all`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Evaluation {
+    /// Canonical form of the position
+    pub canonical_form: CanonicalForm,
+
+    /// Time spent on computing the canonical form. Positions evaluated later are likely to take
+    /// less time, as the transposition table is already warmed up by earlier ones.
+    pub duration: Duration,
+}
+
+/// Compute canonical forms of all positions, sharing one transposition table between them.
+/// With the `rayon` feature positions are evaluated in parallel. Results are in the same order
+/// as positions.
+///
+/// Positions are claimed in the table while being evaluated, like in
+/// [`PartizanGame::canonical_form`], so every claim is released once its value is inserted.
+pub fn evaluate_all<G, TT>(positions: &[G], transposition_table: &TT) -> Vec<Evaluation>
+where
+    G: PartizanGame,
+    TT: TranspositionTable<G> + Sync,
+{
+    evaluate_all_with_progress(positions, transposition_table, |_| {})
+}
+
+/// Like [`evaluate_all`] but calls `on_evaluated` after every evaluated position, e.g. to report
+/// progress. With the `rayon` feature it is called from many threads, in no particular order.
+pub fn evaluate_all_with_progress<G, TT, F>(
+    positions: &[G],
+    transposition_table: &TT,
+    on_evaluated: F,
+) -> Vec<Evaluation>
+where
+    G: PartizanGame,
+    TT: TranspositionTable<G> + Sync,
+    F: Fn(&Evaluation) + Sync,
+{
+    let evaluate = |position: &G| {
+        let start = Instant::now();
+        let canonical_form = position.canonical_form(transposition_table);
+        let evaluation = Evaluation {
+            canonical_form,
+            duration: start.elapsed(),
+        };
+        on_evaluated(&evaluation);
+        evaluation
+    };
+
+    #[cfg(feature = "rayon")]
+    let evaluations = positions.par_iter().map(evaluate).collect();
+    #[cfg(not(feature = "rayon"))]
+    let evaluations = positions.iter().map(evaluate).collect();
+
+    evaluations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        grid::small_bit_grid::SmallBitGrid,
+        short::partizan::{
+            games::domineering::Domineering,
+            transposition_table::{Entry, ParallelTranspositionTable},
+        },
+    };
+    use std::{
+        str::FromStr,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    #[test]
+    fn shares_transposition_table() {
+        let positions = ["..|..", "...|...", ".#|..", "..|..|.."]
+            .map(|grid| Domineering::new(SmallBitGrid::from_str(grid).unwrap()));
+        let transposition_table = ParallelTranspositionTable::new();
+        let evaluations = evaluate_all(&positions, &transposition_table);
+
+        assert_eq!(evaluations.len(), positions.len());
+        for (position, evaluation) in positions.iter().zip(&evaluations) {
+            assert_eq!(
+                transposition_table.lookup_position(position),
+                Some(evaluation.canonical_form.clone())
+            );
+        }
+        assert_eq!(evaluations[0].canonical_form.to_string(), "{1|-1}");
+    }
+
+    #[test]
+    fn reports_progress() {
+        let positions = ["..|..", "...|...", ".#|.."]
+            .map(|grid| Domineering::new(SmallBitGrid::from_str(grid).unwrap()));
+        let evaluated = AtomicUsize::new(0);
+        evaluate_all_with_progress(&positions, &ParallelTranspositionTable::new(), |_| {
+            evaluated.fetch_add(1, Ordering::Relaxed);
+        });
+        assert_eq!(evaluated.into_inner(), positions.len());
+    }
+
+    #[test]
+    fn duplicate_positions() {
+        let positions = ["...|...|...", "...|...|...", "..|..", "...|...|..."]
+            .map(|grid| Domineering::new(SmallBitGrid::from_str(grid).unwrap()));
+        let transposition_table = ParallelTranspositionTable::new();
+        let evaluations = evaluate_all(&positions, &transposition_table);
+
+        assert_eq!(evaluations[0].canonical_form, evaluations[1].canonical_form);
+        assert_eq!(evaluations[0].canonical_form, evaluations[3].canonical_form);
+        for position in &positions {
+            assert!(matches!(
+                transposition_table.entry(*position),
+                Entry::Occupied(_)
+            ));
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Aborting long-running computations.
+//!
+//! Solvers that accept a [`Cancellation`] check it periodically and return [`Cancelled`] as
+//! soon as it is triggered. Results of subpositions that were fully evaluated before that are
+//! still saved in the transposition table, so a later attempt continues from where the previous
+//! one stopped.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// Handle used to cancel a computation, either explicitly or after a deadline. Clones share the
+/// cancellation flag, so a computation can be cancelled from another thread.
+#[derive(Debug, Clone, Default)]
+pub struct Cancellation {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl Cancellation {
+    /// Create a handle that is cancelled only explicitly with [`Self::cancel`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a handle that is cancelled at given instant
+    pub fn with_deadline(deadline: Instant) -> Self {
+        Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            deadline: Some(deadline),
+        }
+    }
+
+    /// Create a handle that is cancelled after given time from now
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self::with_deadline(Instant::now() + timeout)
+    }
+
+    /// Cancel the computation
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Check if computation should be aborted
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// Error returned when computation was aborted with a [`Cancellation`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+#[error("computation was cancelled")]
+pub struct Cancelled;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_and_deadline() {
+        let cancellation = Cancellation::new();
+        let shared = cancellation.clone();
+        assert!(!cancellation.is_cancelled());
+        shared.cancel();
+        assert!(cancellation.is_cancelled());
+
+        assert!(Cancellation::with_timeout(Duration::ZERO).is_cancelled());
+        assert!(!Cancellation::with_timeout(Duration::from_hours(1)).is_cancelled());
+    }
+}
@@ -2,18 +2,25 @@
 
 use crate::{
     display,
-    macros::if_chain,
+    macros::{if_chain, trace_span},
     nom_utils::{impl_from_str_via_nom, lexeme},
     numeric::dyadic_rational_number::DyadicRationalNumber,
+    numeric::interval::Interval,
     numeric::nimber::Nimber,
     numeric::rational::Rational,
+    short::partizan::superstar::Superstar,
     short::partizan::thermograph::Thermograph,
     short::partizan::trajectory::Trajectory,
+    short::partizan::uptimal::Uptimal,
 };
+use alloc::{borrow::Cow, format, string::String, vec, vec::Vec};
 use auto_ops::impl_op_ex;
+use hashbrown::HashMap;
 use nom::{
     branch::alt,
+    bytes::complete::tag,
     character::complete::{char, one_of, u32},
+    combinator::opt,
     error::ErrorKind,
     multi::separated_list0,
 };
@@ -21,8 +28,18 @@ use std::{
     cmp::Ordering,
     fmt::{self, Display, Write},
     hash::Hash,
+    iter::Sum,
 };
 
+#[cfg(feature = "std")]
+pub mod comparison_cache;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "std")]
+mod slot_cache;
+#[cfg(feature = "std")]
+pub mod thermograph_cache;
+
 /// A number-up-star game position that is a sum of a number, up and, nimber.
 #[derive(Debug, Hash, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Nus {
@@ -100,39 +117,65 @@ impl Nus {
         self.number() == DyadicRationalNumber::from(0) && self.up_multiple() == 0
     }
 
+    /// Add two number-up-star sums, returning `None` if the number or up multiple overflows
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(Self {
+            number: self.number.checked_add(&rhs.number)?,
+            up_multiple: self.up_multiple.checked_add(rhs.up_multiple)?,
+            nimber: self.nimber + rhs.nimber,
+        })
+    }
+
+    /// Negate a number-up-star sum, returning `None` if the number or up multiple overflows
+    pub fn checked_neg(&self) -> Option<Self> {
+        Some(Self {
+            number: self.number.checked_neg()?,
+            up_multiple: self.up_multiple.checked_neg()?,
+            nimber: self.nimber, // Nimber is its own negative
+        })
+    }
+
     fn to_moves(self) -> Moves {
+        self.checked_to_moves()
+            .expect("number-up-star options overflowed")
+    }
+
+    /// Options of the sum, `None` if options of the number overflow
+    fn checked_to_moves(self) -> Option<Moves> {
         // Case: Just a number
         if self.is_number() {
             if self.number() == DyadicRationalNumber::from(0) {
-                return Moves {
+                return Some(Moves {
                     left: vec![],
                     right: vec![],
-                };
+                });
             }
 
             if let Some(integer) = self.number().to_integer() {
                 let sign = if integer >= 0 { 1 } else { -1 };
                 let prev = CanonicalForm::new_nus(Self::new_integer(integer - sign));
 
-                if integer >= 0 {
-                    return Moves {
+                if sign > 0 {
+                    return Some(Moves {
                         left: vec![prev],
                         right: vec![],
-                    };
+                    });
                 } else if sign < 0 {
-                    return Moves {
+                    return Some(Moves {
                         left: vec![],
                         right: vec![prev],
-                    };
+                    });
                 }
             } else {
                 let rational = self.number();
-                let left_move = CanonicalForm::new_nus(Self::new_number(rational.step(-1)));
-                let right_move = CanonicalForm::new_nus(Self::new_number(rational.step(1)));
-                return Moves {
+                let left_move =
+                    CanonicalForm::new_nus(Self::new_number(rational.checked_step(-1)?));
+                let right_move =
+                    CanonicalForm::new_nus(Self::new_number(rational.checked_step(1)?));
+                return Some(Moves {
                     left: vec![left_move],
                     right: vec![right_move],
-                };
+                });
             }
         }
 
@@ -151,7 +194,7 @@ impl Nus {
                 moves.left.push(CanonicalForm::new_nus(new_nus));
                 moves.right.push(CanonicalForm::new_nus(new_nus));
             }
-            return moves;
+            return Some(moves);
         }
 
         // Case: number-up-star
@@ -207,7 +250,7 @@ impl Nus {
             };
         }
 
-        moves
+        Some(moves)
     }
 
     /// Parse nus from string, using notation without pluses between number, up, and star components
@@ -268,24 +311,59 @@ impl Nus {
     }
 }
 
-impl_from_str_via_nom!(Nus);
+impl_from_str_via_nom!(Nus, without_serde);
 
 impl_op_ex!(+|lhs: &Nus, rhs: &Nus| -> Nus {
-    Nus {
-        number: lhs.number() + rhs.number(),
-        up_multiple: lhs.up_multiple() + rhs.up_multiple(),
-        nimber: lhs.nimber() + rhs.nimber(),
-    }
+    lhs.checked_add(rhs).expect("number-up-star addition overflowed")
 });
 
 impl_op_ex!(-|lhs: &Nus| -> Nus {
-    Nus {
-        number: -lhs.number(),
-        up_multiple: -lhs.up_multiple(),
-        nimber: lhs.nimber(), // Nimber is its own negative
-    }
+    lhs.checked_neg()
+        .expect("number-up-star negation overflowed")
 });
 
+impl Nus {
+    /// Render the sum as LaTeX math, e.g. `\frac{1}{2}\Uparrow*`
+    pub fn to_latex(&self) -> String {
+        let mut res = String::new();
+        let number = self.number();
+
+        if number != DyadicRationalNumber::from(0)
+            || (self.up_multiple() == 0 && self.nimber() == Nimber::from(0))
+        {
+            match number.to_integer() {
+                Some(integer) => write!(res, "{}", integer).unwrap(),
+                None => write!(
+                    res,
+                    "{}\\frac{{{}}}{{{}}}",
+                    if number.numerator() < 0 { "-" } else { "" },
+                    number.numerator().abs(),
+                    1_u64 << number.denominator_exponent()
+                )
+                .unwrap(),
+            }
+        }
+
+        match self.up_multiple() {
+            0 => {}
+            1 => res.push_str("\\uparrow"),
+            -1 => res.push_str("\\downarrow"),
+            2 => res.push_str("\\Uparrow"),
+            -2 => res.push_str("\\Downarrow"),
+            n if n > 0 => write!(res, "{}\\cdot\\uparrow", n).unwrap(),
+            n => write!(res, "{}\\cdot\\downarrow", -n).unwrap(),
+        }
+
+        match self.nimber().value() {
+            0 => {}
+            1 => res.push('*'),
+            n => write!(res, "*{}", n).unwrap(),
+        }
+
+        res
+    }
+}
+
 impl Display for Nus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.number() == DyadicRationalNumber::from(0)
@@ -328,6 +406,21 @@ pub struct Moves {
     pub right: Vec<CanonicalForm>,
 }
 
+/// Role of an option in a game, see [`Moves::left_option_kinds`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OptionKind {
+    /// Option that remains in the canonical form
+    Canonical,
+
+    /// Option that is no better for its player than another option. From equal options only the
+    /// first one is not dominated.
+    Dominated,
+
+    /// Option that the opponent can reverse with a response at least as good for them as the
+    /// game itself
+    Reversible,
+}
+
 impl PartialOrd for Moves {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -378,10 +471,67 @@ impl Moves {
         CanonicalForm::new_from_moves(self)
     }
 
+    /// Classify Left options, with dominated options taking precedence over reversible ones
+    pub fn left_option_kinds(&self) -> Vec<OptionKind> {
+        self.option_kinds(true)
+    }
+
+    /// Classify Right options, with dominated options taking precedence over reversible ones
+    pub fn right_option_kinds(&self) -> Vec<OptionKind> {
+        self.option_kinds(false)
+    }
+
+    fn option_kinds(&self, left: bool) -> Vec<OptionKind> {
+        let left_moves: Vec<Option<CanonicalForm>> = self.left.iter().cloned().map(Some).collect();
+        let right_moves: Vec<Option<CanonicalForm>> =
+            self.right.iter().cloned().map(Some).collect();
+        let moves = if left { &self.left } else { &self.right };
+        // `better(a, b)` holds if `a` is at least as good as `b` for the moving player
+        let better = |a: &CanonicalForm, b: &CanonicalForm| if left { b <= a } else { a <= b };
+
+        moves
+            .iter()
+            .enumerate()
+            .map(|(i, option)| {
+                let dominated = moves.iter().enumerate().any(|(j, other)| {
+                    i != j && better(other, option) && (j < i || !better(option, other))
+                });
+                let reversible = if left {
+                    option.to_moves().right.iter().any(|g_lr| {
+                        Self::leq_arrays(g_lr, &left_moves, &right_moves)
+                            .expect("options of a number overflowed")
+                    })
+                } else {
+                    option.to_moves().left.iter().any(|g_rl| {
+                        Self::geq_arrays(g_rl, &left_moves, &right_moves)
+                            .expect("options of a number overflowed")
+                    })
+                };
+
+                if dominated {
+                    OptionKind::Dominated
+                } else if reversible {
+                    OptionKind::Reversible
+                } else {
+                    OptionKind::Canonical
+                }
+            })
+            .collect()
+    }
+
     /// Try converting moves to NUS. Returns [None] if moves do not form a NUS
-    // Macro expands to loads of ifs
-    #[allow(clippy::cognitive_complexity)]
+    ///
+    /// # Panics
+    /// - Value of the moves overflows
     pub fn to_nus(&self) -> Option<Nus> {
+        self.checked_to_nus().expect("number-up-star overflowed")
+    }
+
+    /// Like [`Self::to_nus`], but returns `None` on overflow and `Some(None)` if moves do not
+    /// form a NUS
+    // Macro expands to loads of ifs
+    #[allow(clippy::cognitive_complexity, clippy::option_option)]
+    fn checked_to_nus(&self) -> Option<Option<Nus>> {
         let mut result = Nus::new_integer(0);
 
         let num_lo = self.left.len();
@@ -404,8 +554,10 @@ impl Moves {
                 // We assume that entry is normalized, no left moves, thus there must be only one
                 // right entry that's a number
                 debug_assert!(num_ro == 1, "Entry not normalized");
-                result.number =
-                    self.right[0].to_nus_unchecked().number() - DyadicRationalNumber::from(1);
+                result.number = self.right[0]
+                    .to_nus_unchecked()
+                    .number()
+                    .checked_sub(&DyadicRationalNumber::from(1))?;
                 result.up_multiple = 0;
                 result.nimber = Nimber::from(0);
             };
@@ -416,8 +568,10 @@ impl Moves {
                 // We assume that entry is normalized, no left moves, thus there must be only one
                 // right entry that's a number
                 debug_assert!(num_lo == 1, "Entry not normalized");
-                result.number =
-                    self.left[0].to_nus_unchecked().number() + DyadicRationalNumber::from(1);
+                result.number = self.left[0]
+                    .to_nus_unchecked()
+                    .number()
+                    .checked_add(&DyadicRationalNumber::from(1))?;
                 result.up_multiple = 0;
                 result.nimber = Nimber::from(0);
             };
@@ -431,7 +585,7 @@ impl Moves {
                 // Case: {n|m}, n < m
                 // We're a number but not an integer.  Conveniently, since the option lists are
                 // canonicalized, the value of this game is the mean of its left & right moves.
-                result.number = DyadicRationalNumber::mean(&left_number, &right_number);
+                result.number = left_number.checked_mean(&right_number)?;
                 result.up_multiple = 0;
                 result.nimber = Nimber::from(0);
             };
@@ -476,7 +630,7 @@ impl Moves {
             then {
                 // Case: n + {0|G}, G is a number-up-star of up multiple >= 0
                 result.number = right_nus.number();
-                result.up_multiple = right_nus.up_multiple() + 1;
+                result.up_multiple = right_nus.up_multiple().checked_add(1)?;
                 result.nimber = right_nus.nimber() + Nimber::from(1);
             };
 
@@ -490,7 +644,7 @@ impl Moves {
             then {
                 // Inverse of the previous one
                 result.number = left_nus.number();
-                result.up_multiple = left_nus.up_multiple() - 1;
+                result.up_multiple = left_nus.up_multiple().checked_sub(1)?;
                 result.nimber = left_nus.nimber() + Nimber::from(1);
             };
 
@@ -510,13 +664,13 @@ impl Moves {
                         || !l.is_number_up_star()
                         || l.to_nus_unchecked().number() != r.to_nus_unchecked().number()
                     {
-                        return None;
+                        return Some(None);
                     }
 
                     if l.to_nus_unchecked().up_multiple() != 0
                         || l.to_nus_unchecked().nimber().value() != (i as u32)
                     {
-                        return None;
+                        return Some(None);
                     }
                 }
                 // It's a nimber
@@ -526,11 +680,11 @@ impl Moves {
             };
 
             else {
-                return None;
+                return Some(None);
             };
         };
 
-        Some(result)
+        Some(Some(result))
     }
 
     // TODO: Rewrite it to work on mutable vec and not clone
@@ -570,50 +724,50 @@ impl Moves {
     }
 
     /// Return false if `H <= GL` for some left option `GL` of `G` or `HR <= G` for some right
-    /// option `HR` of `H`. Otherwise return true.
+    /// option `HR` of `H`. Otherwise return true. `None` if options of a number overflow.
     fn leq_arrays(
         game: &CanonicalForm,
         left_moves: &[Option<CanonicalForm>],
         right_moves: &[Option<CanonicalForm>],
-    ) -> bool {
+    ) -> Option<bool> {
         for r_opt in right_moves.iter().flatten() {
             if r_opt <= game {
-                return false;
+                return Some(false);
             }
         }
 
-        let game_moves = game.to_moves();
+        let game_moves = game.checked_moves()?;
         for l_move in &game_moves.left {
-            if Self::geq_arrays(l_move, left_moves, right_moves) {
-                return false;
+            if Self::geq_arrays(l_move, left_moves, right_moves)? {
+                return Some(false);
             }
         }
 
-        true
+        Some(true)
     }
 
     fn geq_arrays(
         game: &CanonicalForm,
         left_moves: &[Option<CanonicalForm>],
         right_moves: &[Option<CanonicalForm>],
-    ) -> bool {
+    ) -> Option<bool> {
         for l_opt in left_moves.iter().flatten() {
             if game <= l_opt {
-                return false;
+                return Some(false);
             }
         }
 
-        let game_moves = game.to_moves();
+        let game_moves = game.checked_moves()?;
         for r_move in &game_moves.right {
-            if Self::leq_arrays(r_move, left_moves, right_moves) {
-                return false;
+            if Self::leq_arrays(r_move, left_moves, right_moves)? {
+                return Some(false);
             }
         }
 
-        true
+        Some(true)
     }
 
-    fn bypass_reversible_moves_l(&self) -> Self {
+    fn bypass_reversible_moves_l(&self) -> Option<Self> {
         let mut i: i64 = 0;
 
         let mut left_moves: Vec<Option<CanonicalForm>> =
@@ -632,9 +786,9 @@ impl Moves {
                 }
                 Some(g) => g.clone(),
             };
-            for g_lr in g_l.to_moves().right {
-                if Self::leq_arrays(&g_lr, &left_moves, &right_moves) {
-                    let g_lr_moves = g_lr.to_moves();
+            for g_lr in g_l.checked_moves()?.into_owned().right {
+                if Self::leq_arrays(&g_lr, &left_moves, &right_moves)? {
+                    let g_lr_moves = g_lr.checked_moves()?;
                     let mut new_left_moves: Vec<Option<CanonicalForm>> =
                         vec![None; left_moves.len() + g_lr_moves.left.len() - 1];
                     new_left_moves[..(i as usize)].clone_from_slice(&left_moves[..(i as usize)]);
@@ -655,13 +809,13 @@ impl Moves {
 
             i += 1;
         }
-        Self {
+        Some(Self {
             left: left_moves.iter().flatten().cloned().collect(),
             right: self.right.clone(),
-        }
+        })
     }
 
-    fn bypass_reversible_moves_r(&self) -> Self {
+    fn bypass_reversible_moves_r(&self) -> Option<Self> {
         let mut i: i64 = 0;
 
         let left_moves: Vec<Option<CanonicalForm>> = self.left.iter().cloned().map(Some).collect();
@@ -679,9 +833,9 @@ impl Moves {
                 }
                 Some(game) => game.clone(),
             };
-            for g_rl in g_r.to_moves().left {
-                if Self::geq_arrays(&g_rl, &left_moves, &right_moves) {
-                    let g_rl_moves = g_rl.to_moves();
+            for g_rl in g_r.checked_moves()?.into_owned().left {
+                if Self::geq_arrays(&g_rl, &left_moves, &right_moves)? {
+                    let g_rl_moves = g_rl.checked_moves()?;
                     let mut new_right_moves: Vec<Option<CanonicalForm>> =
                         vec![None; right_moves.len() + g_rl_moves.right.len() - 1];
                     new_right_moves[..(i as usize)].clone_from_slice(&right_moves[..(i as usize)]);
@@ -702,37 +856,40 @@ impl Moves {
 
             i += 1;
         }
-        Self {
+        Some(Self {
             left: self.left.clone(),
             right: right_moves.iter().flatten().cloned().collect(),
-        }
+        })
     }
 
-    fn canonicalize(&self) -> Self {
-        let moves = self.bypass_reversible_moves_l();
-        let moves = moves.bypass_reversible_moves_r();
+    /// Canonical moves, `None` if options of a number overflow
+    fn checked_canonicalize(&self) -> Option<Self> {
+        let moves = self.bypass_reversible_moves_l()?;
+        let moves = moves.bypass_reversible_moves_r()?;
 
         let left = Self::eliminate_dominated_moves(&moves.left, true);
         let right = Self::eliminate_dominated_moves(&moves.right, false);
 
-        Self { left, right }
+        Some(Self { left, right })
     }
 
-    fn thermograph(&self) -> Thermograph {
+    fn checked_thermograph(&self) -> Option<Thermograph> {
         let mut left_scaffold = Trajectory::new_constant(Rational::NegativeInfinity);
         let mut right_scaffold = Trajectory::new_constant(Rational::PositiveInfinity);
 
         for left_move in &self.left {
-            left_scaffold = left_scaffold.max(&CanonicalForm::thermograph(left_move).right_wall);
+            left_scaffold = left_scaffold
+                .checked_max(&CanonicalForm::checked_thermograph(left_move)?.right_wall)?;
         }
         for right_move in &self.right {
-            right_scaffold = right_scaffold.min(&CanonicalForm::thermograph(right_move).left_wall);
+            right_scaffold = right_scaffold
+                .checked_min(&CanonicalForm::checked_thermograph(right_move)?.left_wall)?;
         }
 
-        left_scaffold.tilt(Rational::from(-1));
-        right_scaffold.tilt(Rational::from(1));
+        left_scaffold.checked_tilt(Rational::from(-1))?;
+        right_scaffold.checked_tilt(Rational::from(1))?;
 
-        Thermograph::thermographic_intersection(left_scaffold, right_scaffold)
+        Thermograph::checked_thermographic_intersection(left_scaffold, right_scaffold)
     }
 
     /// Print moves with NUS unwrapped using `{G^L | G^R}` notation
@@ -799,14 +956,81 @@ impl Display for Moves {
     }
 }
 
-impl_from_str_via_nom!(Moves);
+impl_from_str_via_nom!(Moves, without_serde);
+
+/// Stop of a game, i.e. the number reached when both players play optimally, adorned with the
+/// information who has to move once the number is reached.
+///
+/// Moving in a number is never beneficial, so from Left's perspective `x` reached with Right to
+/// move is better than `x` reached with Left to move. The ordering reflects that.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdornedStop {
+    /// The number reached
+    pub number: DyadicRationalNumber,
+
+    /// Whether Right has to move once the number is reached
+    pub right_to_move: bool,
+}
+
+/// Set of numbers a game is confused with, i.e. numbers `x` such that `G ‖ x`.
+///
+/// Confusion interval spans between the Right and Left stops, and endpoints are included
+/// depending on the adornment of the stops. Games that are not confused with any number, like
+/// numbers themselves, have an empty interval.
+pub type ConfusionInterval = Interval<DyadicRationalNumber>;
+
+/// Reason why `G ≤ H` does not hold
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub enum ComparisonWitness {
+    /// Left option `G^L` of `G` such that `H ≤ G^L`. Left wins `G - H` by moving to it.
+    LeftOption(CanonicalForm),
+
+    /// Right option `H^R` of `H` such that `H^R ≤ G`. Left wins `G - H` by moving to it.
+    RightOption(CanonicalForm),
+}
+
+impl Display for ComparisonWitness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LeftOption(option) => write!(f, "Left option {} of lhs", option),
+            Self::RightOption(option) => write!(f, "Right option {} of rhs", option),
+        }
+    }
+}
+
+/// Classification of an infinitesimal by comparison with zero, ups and stars
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InfinitesimalClass {
+    /// Equal to zero
+    Zero,
+
+    /// Equal to a non-zero nimber
+    Nimber,
+
+    /// Greater than zero. `ups` is the largest `k` such that `k.↑ ≤ G` or `k.↑* ≤ G`, in
+    /// particular it is zero for games smaller than `↑`, like tinies or `↑^2`.
+    Positive {
+        /// Number of ups below the game
+        ups: u32,
+    },
+
+    /// Less than zero. `downs` is the largest `k` such that `G ≤ k.↓` or `G ≤ k.↓*`
+    Negative {
+        /// Number of downs above the game
+        downs: u32,
+    },
+
+    /// Confused with zero, like `↑*` or `{0|v}`
+    Fuzzy,
+}
 
 /// Canonical game form
 ///
 /// Note that ordering is defined structurally for the sake of data structures. For proper partial
 /// ordering see instance for [`CanonicalForm`].
 #[derive(Debug, Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum CanonicalFormInner {
     /// Number Up Star sum
     Nus(Nus),
@@ -816,6 +1040,11 @@ enum CanonicalFormInner {
 }
 
 /// Canonical game form
+///
+/// With the `serde` feature, canonical forms are serialized as trees like
+/// `{"moves": {"left": [{"nus": {"number": "1", "up_multiple": 0, "nimber": 0}}], "right": []}}`
+/// that do not depend on the internal representation, and can also be read from strings like
+/// `"{1|-1}"`.
 #[repr(transparent)]
 #[derive(Debug, Hash, Clone, PartialEq, Eq)]
 pub struct CanonicalForm {
@@ -853,36 +1082,68 @@ impl CanonicalForm {
     }
 
     /// Construct negative.0 of a game. Alias for negation [`-`] operator
+    ///
+    /// # Panics
+    /// - Number in the negative overflows, see [`Self::checked_negative`]
     #[must_use]
     pub fn construct_negative(&self) -> Self {
+        self.checked_negative()
+            .expect("canonical form negation overflowed")
+    }
+
+    /// Construct negative of a game, returning `None` if any number in it overflows
+    pub fn checked_negative(&self) -> Option<Self> {
         match &self.inner {
-            CanonicalFormInner::Nus(nus) => Self::new_nus(-nus),
+            CanonicalFormInner::Nus(nus) => Some(Self::new_nus(nus.checked_neg()?)),
             CanonicalFormInner::Moves(moves) => {
                 let new_left_moves = moves
-                    .left
+                    .right
                     .iter()
-                    .map(Self::construct_negative)
-                    .collect::<Vec<_>>();
+                    .map(Self::checked_negative)
+                    .collect::<Option<Vec<_>>>()?;
                 let new_right_moves = moves
-                    .right
+                    .left
                     .iter()
-                    .map(Self::construct_negative)
-                    .collect::<Vec<_>>();
+                    .map(Self::checked_negative)
+                    .collect::<Option<Vec<_>>>()?;
                 let new_moves = Moves {
                     left: new_left_moves,
                     right: new_right_moves,
                 };
-                Self::construct_from_canonical_moves(new_moves)
+                Self::checked_from_canonical_moves(new_moves)
             }
         }
     }
 
     /// Construct a sum of two games. Alias for [`+`] operator
+    ///
+    /// # Panics
+    /// - Number in the sum overflows, see [`Self::checked_sum`]
     pub fn construct_sum(g: &Self, h: &Self) -> Self {
+        Self::checked_sum(g, h).expect("canonical form sum overflowed")
+    }
+
+    /// Construct a sum of two games, returning `None` if any number in the sum or in the options
+    /// considered while simplifying it overflows
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cgt::short::partizan::canonical_form::CanonicalForm;
+    ///
+    /// let one = CanonicalForm::new_integer(1);
+    /// let two = CanonicalForm::checked_sum(&one, &one);
+    /// assert_eq!(two, Some(CanonicalForm::new_integer(2)));
+    /// assert_eq!(
+    ///     CanonicalForm::checked_sum(&CanonicalForm::new_integer(i64::MAX), &one),
+    ///     None
+    /// );
+    /// ```
+    pub fn checked_sum(g: &Self, h: &Self) -> Option<Self> {
         if let (CanonicalFormInner::Nus(g_nus), CanonicalFormInner::Nus(h_nus)) =
             (&g.inner, &h.inner)
         {
-            return Self::new_nus(g_nus + h_nus);
+            return Some(Self::new_nus(g_nus.checked_add(h_nus)?));
         }
 
         // We want to return { GL+H, G+HL | GR+H, G+HR }
@@ -892,46 +1153,135 @@ impl CanonicalForm {
         let mut moves = Moves::empty();
 
         if !g.is_number() {
-            let g_moves = g.to_moves();
+            let g_moves = g.checked_moves()?;
             for g_l in &g_moves.left {
-                moves.left.push(Self::construct_sum(g_l, h));
+                moves.left.push(Self::checked_sum(g_l, h)?);
             }
             for g_r in &g_moves.right {
-                moves.right.push(Self::construct_sum(g_r, h));
+                moves.right.push(Self::checked_sum(g_r, h)?);
             }
         }
         if !h.is_number() {
-            let h_moves = h.to_moves();
+            let h_moves = h.checked_moves()?;
             for h_l in &h_moves.left {
-                moves.left.push(Self::construct_sum(g, h_l));
+                moves.left.push(Self::checked_sum(g, h_l)?);
             }
             for h_r in &h_moves.right {
-                moves.right.push(Self::construct_sum(g, h_r));
+                moves.right.push(Self::checked_sum(g, h_r)?);
             }
         }
 
+        Self::checked_new_from_moves(moves)
+    }
+
+    /// Construct a sequential compound `G → H`, where players move in `G` until it has no moves
+    /// for either player, and only then continue in `H`.
+    ///
+    /// Note that sequential compound depends on form of `G`, here the canonical form is used.
+    pub fn construct_sequential_compound(g: &Self, h: &Self) -> Self {
+        let g_moves = g.to_moves();
+        if g_moves.left.is_empty() && g_moves.right.is_empty() {
+            return h.clone();
+        }
+
+        let moves = Moves {
+            left: g_moves
+                .left
+                .iter()
+                .map(|g_l| Self::construct_sequential_compound(g_l, h))
+                .collect(),
+            right: g_moves
+                .right
+                .iter()
+                .map(|g_r| Self::construct_sequential_compound(g_r, h))
+                .collect(),
+        };
         Self::new_from_moves(moves)
     }
 
-    /// VERY INTERNAL
-    fn construct_from_canonical_moves(mut moves: Moves) -> Self {
+    /// Construct a selective compound `G ∨ H`, where player moves in any non-empty subset of
+    /// components.
+    ///
+    /// Note that selective compound depends on forms of the components, here the canonical forms
+    /// are used.
+    pub fn construct_selective_compound(g: &Self, h: &Self) -> Self {
+        Self::construct_selective_compound_cached(g, h, &mut HashMap::new())
+    }
+
+    fn construct_selective_compound_cached(
+        g: &Self,
+        h: &Self,
+        cache: &mut HashMap<(Self, Self), Self>,
+    ) -> Self {
+        if let Some(result) = cache.get(&(g.clone(), h.clone())) {
+            return result.clone();
+        }
+
+        let g_moves = g.to_moves();
+        let h_moves = h.to_moves();
+
+        let mut side = |g_options: &[Self], h_options: &[Self]| {
+            let mut options = Vec::new();
+            for g_option in g_options {
+                options.push(Self::construct_selective_compound_cached(
+                    g_option, h, cache,
+                ));
+            }
+            for h_option in h_options {
+                options.push(Self::construct_selective_compound_cached(
+                    g, h_option, cache,
+                ));
+            }
+            for g_option in g_options {
+                for h_option in h_options {
+                    options.push(Self::construct_selective_compound_cached(
+                        g_option, h_option, cache,
+                    ));
+                }
+            }
+            options
+        };
+
+        let left = side(&g_moves.left, &h_moves.left);
+        let right = side(&g_moves.right, &h_moves.right);
+        let result = Self::new_from_moves(Moves { left, right });
+        cache.insert((g.clone(), h.clone()), result.clone());
+        result
+    }
+
+    /// VERY INTERNAL. Construct a game from moves that are already canonical, `None` if the game
+    /// is a number that overflows
+    pub(crate) fn checked_from_canonical_moves(mut moves: Moves) -> Option<Self> {
         moves.left.sort_by(|lhs, rhs| lhs.inner.cmp(&rhs.inner));
         moves.right.sort_by(|lhs, rhs| lhs.inner.cmp(&rhs.inner));
 
-        if let Some(nus) = moves.to_nus() {
-            return Self::new_nus(nus);
+        if let Some(nus) = moves.checked_to_nus()? {
+            return Some(Self::new_nus(nus));
         }
 
         // Game is not a nus
-        Self::from_inner(CanonicalFormInner::Moves(moves))
+        Some(Self::from_inner(CanonicalFormInner::Moves(moves)))
     }
 
     /// Safe function to construct a game from possible moves
-    pub fn new_from_moves(mut moves: Moves) -> Self {
+    ///
+    /// # Panics
+    /// - Number in the game or in the options considered while simplifying it overflows
+    pub fn new_from_moves(moves: Moves) -> Self {
+        Self::checked_new_from_moves(moves).expect("canonical form overflowed")
+    }
+
+    fn checked_new_from_moves(mut moves: Moves) -> Option<Self> {
+        trace_span!(
+            TRACE,
+            "new_from_moves",
+            left = moves.left.len(),
+            right = moves.right.len()
+        );
         moves.eliminate_duplicates();
-        moves = moves.canonicalize();
+        moves = moves.checked_canonicalize()?;
 
-        Self::construct_from_canonical_moves(moves)
+        Self::checked_from_canonical_moves(moves)
     }
 
     #[inline]
@@ -947,12 +1297,45 @@ impl CanonicalForm {
         }
     }
 
+    /// Get moves of the game, borrowing them unless the game is a Number Up Star sum
+    fn moves(&self) -> Cow<'_, Moves> {
+        match &self.inner {
+            CanonicalFormInner::Nus(nus) => Cow::Owned(nus.to_moves()),
+            CanonicalFormInner::Moves(moves) => Cow::Borrowed(moves),
+        }
+    }
+
+    /// Like [`Self::moves`], but returns `None` if options of a number overflow
+    fn checked_moves(&self) -> Option<Cow<'_, Moves>> {
+        match &self.inner {
+            CanonicalFormInner::Nus(nus) => nus.checked_to_moves().map(Cow::Owned),
+            CanonicalFormInner::Moves(moves) => Some(Cow::Borrowed(moves)),
+        }
+    }
+
     /// Check if game is a Number Up Star sum
     #[inline]
     pub const fn is_number_up_star(&self) -> bool {
         matches!(self.inner, CanonicalFormInner::Nus(_))
     }
 
+    /// Approximate heap memory owned by the form in bytes, i.e. lists of moves of the form and
+    /// all its followers. Number Up Star sums are stored inline and own no heap memory.
+    pub fn heap_size(&self) -> usize {
+        match self.inner {
+            CanonicalFormInner::Nus(_) => 0,
+            CanonicalFormInner::Moves(ref moves) => {
+                (moves.left.capacity() + moves.right.capacity()) * size_of::<Self>()
+                    + moves
+                        .left
+                        .iter()
+                        .chain(&moves.right)
+                        .map(Self::heap_size)
+                        .sum::<usize>()
+            }
+        }
+    }
+
     /// Check if a game is only a number
     #[inline]
     pub fn is_number(&self) -> bool {
@@ -965,6 +1348,148 @@ impl CanonicalForm {
         matches!(self.inner, CanonicalFormInner::Nus(nus) if nus.is_nimber())
     }
 
+    /// Check if a game is a sum of a number and a nimber
+    #[inline]
+    pub const fn is_nimberish(&self) -> bool {
+        matches!(self.inner, CanonicalFormInner::Nus(nus) if nus.up_multiple() == 0)
+    }
+
+    /// Check if a game is all-small, i.e. in every follower either both players can move, or
+    /// none of them can
+    pub fn is_all_small(&self) -> bool {
+        match self.inner {
+            CanonicalFormInner::Nus(nus) => nus.number() == DyadicRationalNumber::from(0),
+            CanonicalFormInner::Moves(ref moves) => {
+                !moves.left.is_empty()
+                    && !moves.right.is_empty()
+                    && moves.left.iter().all(Self::is_all_small)
+                    && moves.right.iter().all(Self::is_all_small)
+            }
+        }
+    }
+
+    /// Check if a game is an infinitesimal, i.e. it lies strictly between all positive and all
+    /// negative numbers. Zero is also considered to be an infinitesimal.
+    pub fn is_infinitesimal(&self) -> bool {
+        let zero = DyadicRationalNumber::from(0);
+        self.left_stop() == zero && self.right_stop() == zero
+    }
+
+    /// Classify an infinitesimal by comparing it with zero, ups and stars. Returns `None` if the
+    /// game is not an infinitesimal.
+    pub fn infinitesimal_class(&self) -> Option<InfinitesimalClass> {
+        if !self.is_infinitesimal() {
+            return None;
+        }
+
+        if let Some(nus) = self.to_nus() {
+            if nus.is_nimber() {
+                return Some(if nus.nimber() == Nimber::new(0) {
+                    InfinitesimalClass::Zero
+                } else {
+                    InfinitesimalClass::Nimber
+                });
+            }
+        }
+
+        // Largest `k` such that `k.↑` or `k.↑*` fits below the game
+        let ups_below = |game: &Self| {
+            let bound = game.birthday() + 1;
+            let mut ups = 0;
+            while ups < bound {
+                let up = Self::new_nus(Nus::new(
+                    DyadicRationalNumber::from(0),
+                    ups as i32 + 1,
+                    Nimber::new(0),
+                ));
+                let up_star = Self::new_nus(Nus::new(
+                    DyadicRationalNumber::from(0),
+                    ups as i32 + 1,
+                    Nimber::new(1),
+                ));
+                if !Self::leq(&up, game) && !Self::leq(&up_star, game) {
+                    break;
+                }
+                ups += 1;
+            }
+            ups
+        };
+
+        let zero = Self::new_integer(0);
+        Some(match Self::compare(self, &zero) {
+            Some(Ordering::Greater) => InfinitesimalClass::Positive {
+                ups: ups_below(self),
+            },
+            Some(Ordering::Less) => InfinitesimalClass::Negative {
+                downs: ups_below(&-self),
+            },
+            Some(Ordering::Equal) => InfinitesimalClass::Zero,
+            None => InfinitesimalClass::Fuzzy,
+        })
+    }
+
+    /// Render the game as LaTeX math. Braces are omitted where the number of bars disambiguates
+    /// the game, e.g. `{3|{2|1}}` is rendered as `\{3 \,||\, 2 \,|\, 1\}`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cgt::short::partizan::canonical_form::CanonicalForm;
+    /// use std::str::FromStr;
+    ///
+    /// let g = CanonicalForm::from_str("{1/2|v*}").unwrap();
+    /// assert_eq!(g.to_latex(), r"\{\frac{1}{2} \,|\, \downarrow*\}");
+    /// ```
+    pub fn to_latex(&self) -> String {
+        match self.inner {
+            CanonicalFormInner::Nus(ref nus) => nus.to_latex(),
+            CanonicalFormInner::Moves(_) => format!("\\{{{}\\}}", self.to_latex_inline().0),
+        }
+    }
+
+    /// Render game without outer braces, returning number of bars used by the top level separator
+    fn to_latex_inline(&self) -> (String, usize) {
+        let moves = match self.inner {
+            CanonicalFormInner::Nus(ref nus) => return (nus.to_latex(), 0),
+            CanonicalFormInner::Moves(ref moves) => moves,
+        };
+
+        // Option can be written without braces only if it is the only option on its side
+        let render_side = |options: &[Self]| -> (String, usize) {
+            if let [option] = options {
+                return option.to_latex_inline();
+            }
+            let rendered: Vec<String> = options.iter().map(Self::to_latex).collect();
+            (rendered.join(", "), 0)
+        };
+
+        let (left, left_bars) = render_side(&moves.left);
+        let (right, right_bars) = render_side(&moves.right);
+        let bars = left_bars.max(right_bars) + 1;
+        let mut res = left;
+        if !res.is_empty() {
+            res.push(' ');
+        }
+        write!(res, "\\,{}\\,", "|".repeat(bars)).unwrap();
+        if !right.is_empty() {
+            res.push(' ');
+        }
+        res.push_str(&right);
+        (res, bars)
+    }
+
+    /// Birthday of a game, i.e. the height of its canonical game tree
+    pub fn birthday(&self) -> u32 {
+        let moves = self.to_moves();
+        moves
+            .left
+            .iter()
+            .chain(moves.right.iter())
+            .map(|option| option.birthday() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
     /// Convert game to NUS if it is a NUS
     #[inline]
     pub const fn to_nus(&self) -> Option<Nus> {
@@ -1003,7 +1528,7 @@ impl CanonicalForm {
                 Ordering::Less => return true,
                 Ordering::Greater => return false,
                 Ordering::Equal => {
-                    if lhs_nus.up_multiple() < rhs_nus.up_multiple() - 1 {
+                    if lhs_nus.up_multiple() < rhs_nus.up_multiple().saturating_sub(1) {
                         return true;
                     } else if lhs_nus.up_multiple() < rhs_nus.up_multiple() {
                         return (lhs_nus.nimber() + rhs_nus.nimber()) != Nimber::from(1);
@@ -1013,8 +1538,20 @@ impl CanonicalForm {
             }
         }
 
+        #[cfg(feature = "std")]
+        if comparison_cache::is_enabled() {
+            return comparison_cache::leq_cached(lhs_game, rhs_game, || {
+                Self::leq_moves(lhs_game, rhs_game)
+            });
+        }
+
+        Self::leq_moves(lhs_game, rhs_game)
+    }
+
+    /// Less than or equals comparison on two games that are not both Number Up Star sums
+    fn leq_moves(lhs_game: &Self, rhs_game: &Self) -> bool {
         if !lhs_game.is_number() {
-            let lhs_game_moves = lhs_game.to_moves();
+            let lhs_game_moves = lhs_game.moves();
             for lhs_l in &lhs_game_moves.left {
                 if Self::leq(rhs_game, lhs_l) {
                     return false;
@@ -1023,7 +1560,7 @@ impl CanonicalForm {
         }
 
         if !rhs_game.is_number() {
-            let rhs_game_moves = rhs_game.to_moves();
+            let rhs_game_moves = rhs_game.moves();
             for rhs_r in &rhs_game_moves.right {
                 if Self::leq(rhs_r, lhs_game) {
                     return false;
@@ -1034,7 +1571,89 @@ impl CanonicalForm {
         true
     }
 
+    /// Greater than or equals comparison on two games
+    #[inline]
+    pub fn geq(lhs_game: &Self, rhs_game: &Self) -> bool {
+        Self::leq(rhs_game, lhs_game)
+    }
+
+    /// Check if two games are confused with each other, i.e. the first player wins their difference
+    #[inline]
+    pub fn fuzzy(lhs_game: &Self, rhs_game: &Self) -> bool {
+        !Self::leq(lhs_game, rhs_game) && !Self::leq(rhs_game, lhs_game)
+    }
+
+    /// Compare two games, returning `None` if they are confused with each other
+    pub fn compare(lhs_game: &Self, rhs_game: &Self) -> Option<Ordering> {
+        match (Self::leq(lhs_game, rhs_game), Self::leq(rhs_game, lhs_game)) {
+            (true, true) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            (false, false) => None,
+        }
+    }
+
+    /// Explain why `lhs_game ≤ rhs_game` does not hold. Returns `None` if it does hold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cgt::short::partizan::canonical_form::{CanonicalForm, ComparisonWitness};
+    /// use std::str::FromStr;
+    ///
+    /// let star = CanonicalForm::from_str("*").unwrap();
+    /// let zero = CanonicalForm::new_integer(0);
+    /// assert_eq!(
+    ///     CanonicalForm::leq_witness(&star, &zero),
+    ///     Some(ComparisonWitness::LeftOption(zero.clone()))
+    /// );
+    /// assert_eq!(CanonicalForm::leq_witness(&zero, &zero), None);
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    pub fn leq_witness(lhs_game: &Self, rhs_game: &Self) -> Option<ComparisonWitness> {
+        if Self::leq(lhs_game, rhs_game) {
+            return None;
+        }
+
+        let lhs_moves = lhs_game.to_moves();
+        if let Some(lhs_l) = lhs_moves
+            .left
+            .iter()
+            .find(|lhs_l| Self::leq(rhs_game, lhs_l))
+        {
+            return Some(ComparisonWitness::LeftOption(lhs_l.clone()));
+        }
+
+        let rhs_moves = rhs_game.to_moves();
+        let rhs_r = rhs_moves
+            .right
+            .iter()
+            .find(|rhs_r| Self::leq(rhs_r, lhs_game))
+            .expect("lhs is not less or equal to rhs so there must be a winning move");
+        Some(ComparisonWitness::RightOption(rhs_r.clone()))
+    }
+
+    /// Explain why `lhs_game ≥ rhs_game` does not hold. Returns `None` if it does hold.
+    #[inline]
+    pub fn geq_witness(lhs_game: &Self, rhs_game: &Self) -> Option<ComparisonWitness> {
+        Self::leq_witness(rhs_game, lhs_game)
+    }
+
     /// Calculate temperature of the game. Avoids computing a thermograph is game is a NUS
+    ///
+    /// Temperature of a number `k/2^n` is `-1/2^n`, and temperature of a number plus
+    /// an infinitesimal is `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cgt::{numeric::dyadic_rational_number::DyadicRationalNumber, short::partizan::canonical_form::CanonicalForm};
+    /// use std::str::FromStr;
+    ///
+    /// let switch = CanonicalForm::from_str("{3|-1}").unwrap();
+    /// assert_eq!(switch.temperature(), DyadicRationalNumber::from(2));
+    /// assert_eq!(switch.mean(), DyadicRationalNumber::from(1));
+    /// ```
     #[allow(clippy::missing_panics_doc)]
     pub fn temperature(&self) -> DyadicRationalNumber {
         match self.inner {
@@ -1047,19 +1666,37 @@ impl CanonicalForm {
                     DyadicRationalNumber::from(0)
                 }
             }
-            CanonicalFormInner::Moves(ref moves) => moves.thermograph().temperature(),
+            CanonicalFormInner::Moves(_) => self.thermograph().temperature(),
         }
     }
 
     /// Construct a thermograph of a game, using thermographic intersection of
     /// left and right scaffolds
+    ///
+    /// # Panics
+    /// - Value of the thermograph overflows, see [`Self::checked_thermograph`]
     pub fn thermograph(&self) -> Thermograph {
+        self.checked_thermograph()
+            .expect("thermograph value overflowed")
+    }
+
+    /// Like [`Self::thermograph`], but returns `None` if a value of the thermograph of the game
+    /// or of any of its followers overflows
+    pub fn checked_thermograph(&self) -> Option<Thermograph> {
         match self.inner {
-            CanonicalFormInner::Moves(ref moves) => moves.thermograph(),
+            CanonicalFormInner::Moves(ref moves) => {
+                #[cfg(feature = "std")]
+                if thermograph_cache::is_enabled() {
+                    return thermograph_cache::thermograph_cached(self, || {
+                        moves.checked_thermograph()
+                    });
+                }
+                moves.checked_thermograph()
+            }
             CanonicalFormInner::Nus(nus) => {
                 if let Some(nus_integer) = nus.number().to_integer() {
                     if nus.is_number() {
-                        return Thermograph::with_mast(Rational::new(nus_integer, 1));
+                        return Some(Thermograph::with_mast(Rational::from(nus_integer)));
                     }
                 }
 
@@ -1072,65 +1709,125 @@ impl CanonicalForm {
                         up_multiple: 0,
                         nimber: Nimber::from(nus.nimber().value().cmp(&0) as u32), // signum(nus.nimber)
                     });
-                    let new_game_moves = new_game.to_moves();
-                    new_game_moves.thermograph()
+                    let new_game_moves = new_game.checked_moves()?;
+                    new_game_moves.checked_thermograph()
                 } else {
                     let new_game = Self::new_nus(Nus {
                         number: nus.number(),
                         up_multiple: nus.up_multiple().cmp(&0) as i32, // signum(nus.up_multiple)
                         nimber: Nimber::from(0),
                     });
-                    let new_game_moves = new_game.to_moves();
-                    new_game_moves.thermograph()
+                    let new_game_moves = new_game.checked_moves()?;
+                    new_game_moves.checked_thermograph()
                 }
             }
         }
     }
 
     /// The number reached when Left plays first.
+    ///
+    /// Stops only compare numbers already present in the game, so unlike
+    /// [`Self::thermograph`] they cannot overflow.
     pub fn left_stop(&self) -> DyadicRationalNumber {
-        if let Some(number) = self.to_number() {
-            return number;
-        }
-
-        self.to_moves()
-            .left
-            .iter()
-            .map(Self::right_stop)
-            .max()
-            .expect("Not a number so must have moves")
+        self.adorned_left_stop().number
     }
 
-    /// The number reached when Right plays first.
+    /// The number reached when Right plays first. See [`Self::left_stop`].
     pub fn right_stop(&self) -> DyadicRationalNumber {
-        if let Some(number) = self.to_number() {
-            return number;
-        }
+        self.adorned_right_stop().number
+    }
 
-        self.to_moves()
-            .right
-            .iter()
-            .map(Self::left_stop)
-            .max()
-            .expect("Not a number so must have moves")
+    /// The number reached when Left plays first, with information who has to move next.
+    pub fn adorned_left_stop(&self) -> AdornedStop {
+        self.adorned_stop_cached(true)
     }
 
-    /// Confusion interval is the region between Left and Right stops
-    pub fn confusion_interval(&self) -> (DyadicRationalNumber, DyadicRationalNumber) {
-        (self.left_stop(), self.right_stop())
+    /// The number reached when Right plays first, with information who has to move next.
+    pub fn adorned_right_stop(&self) -> AdornedStop {
+        self.adorned_stop_cached(false)
     }
 
-    /// Compute the mean value of the position
-    ///
-    /// Mean value is the result of cooling a position by value greater than temperature
-    pub fn mean(&self) -> DyadicRationalNumber {
-        match self.inner {
-            CanonicalFormInner::Nus(nus) => nus.number(),
-            CanonicalFormInner::Moves(ref moves) => {
-                let mast = moves.thermograph().get_mast();
-                DyadicRationalNumber::from_rational(mast)
-                    .expect("Thermograph mast to have a finite dyadic value")
-            }
+    fn adorned_stop_cached(&self, left_first: bool) -> AdornedStop {
+        #[cfg(feature = "std")]
+        if !self.is_number_up_star() && thermograph_cache::is_enabled() {
+            return thermograph_cache::adorned_stop_cached(self, left_first, || {
+                self.adorned_stop(left_first, &mut HashMap::new())
+            });
+        }
+        self.adorned_stop(left_first, &mut HashMap::new())
+    }
+
+    fn adorned_stop(
+        &self,
+        left_first: bool,
+        cache: &mut HashMap<(Self, bool), AdornedStop>,
+    ) -> AdornedStop {
+        if let Some(number) = self.to_number() {
+            return AdornedStop {
+                number,
+                right_to_move: !left_first,
+            };
+        }
+
+        if let Some(stop) = cache.get(&(self.clone(), left_first)) {
+            return *stop;
+        }
+
+        // Options of a number are never taken, and options of other Number Up Star sums have the
+        // same number, so nothing here can overflow
+        let moves = self.moves();
+        let stop = if left_first {
+            moves
+                .left
+                .iter()
+                .map(|left_move| left_move.adorned_stop(false, cache))
+                .max()
+        } else {
+            moves
+                .right
+                .iter()
+                .map(|right_move| right_move.adorned_stop(true, cache))
+                .min()
+        }
+        .expect("Not a number so must have moves");
+
+        cache.insert((self.clone(), left_first), stop);
+        stop
+    }
+
+    /// Confusion interval is the region between Left and Right stops
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cgt::short::partizan::canonical_form::CanonicalForm;
+    /// use std::str::FromStr;
+    ///
+    /// let g = CanonicalForm::from_str("{1|-1}").unwrap();
+    /// assert_eq!(g.confusion_interval().to_string(), "[-1, 1]");
+    /// ```
+    pub fn confusion_interval(&self) -> ConfusionInterval {
+        let left_stop = self.adorned_left_stop();
+        let right_stop = self.adorned_right_stop();
+        ConfusionInterval {
+            lower: right_stop.number,
+            lower_closed: !right_stop.right_to_move,
+            upper: left_stop.number,
+            upper_closed: left_stop.right_to_move,
+        }
+    }
+
+    /// Compute the mean value of the position
+    ///
+    /// Mean value is the result of cooling a position by value greater than temperature
+    pub fn mean(&self) -> DyadicRationalNumber {
+        match self.inner {
+            CanonicalFormInner::Nus(nus) => nus.number(),
+            CanonicalFormInner::Moves(_) => {
+                let mast = self.thermograph().get_mast();
+                DyadicRationalNumber::from_rational(mast)
+                    .expect("Thermograph mast to have a finite dyadic value")
+            }
         }
     }
 
@@ -1206,7 +1903,142 @@ impl CanonicalForm {
         new_moves.canonical_form()
     }
 
-    /// A remote star of game `g` is a nimber `*N` if no position of `g` including `g` has value `N*`
+    /// Overheat position from `s` to `t`.
+    ///
+    /// Overheating is defined as `\int^t_s G = G.s` (`G` copies of `s`) if `G` is an integer,
+    /// or `\int^t_s G = {\int^t_s G^L + t | \int^t_s G^R - t}` otherwise
+    #[must_use]
+    pub fn overheat(&self, s: &Self, t: &Self) -> Self {
+        if let Some(integer) = self.to_number().and_then(|number| number.to_integer()) {
+            let mut res = Self::new_integer(0);
+            for _ in 0..integer.abs() {
+                res += s;
+            }
+            return if integer < 0 { -res } else { res };
+        }
+
+        let moves = self.to_moves();
+
+        let mut new_left_moves = Vec::with_capacity(moves.left.len());
+        for left_move in moves.left {
+            new_left_moves.push(left_move.overheat(s, t) + t);
+        }
+
+        let mut new_right_moves = Vec::with_capacity(moves.right.len());
+        for right_move in moves.right {
+            new_right_moves.push(right_move.overheat(s, t) - t);
+        }
+
+        let new_moves = Moves {
+            left: new_left_moves,
+            right: new_right_moves,
+        };
+
+        new_moves.canonical_form()
+    }
+
+    /// Ordinal sum `G:H`, i.e. game where any move in `G` also annihilates `H`.
+    ///
+    /// Ordinal sum is defined as `G:H = {G^L, G:H^L | G^R, G:H^R}`. Note that it depends on
+    /// form of `G`, here the canonical form is used.
+    #[must_use]
+    pub fn ordinal_sum(&self, h: &Self) -> Self {
+        self.ordinal_sum_cached(h, &mut HashMap::new())
+    }
+
+    fn ordinal_sum_cached(&self, h: &Self, cache: &mut HashMap<Self, Self>) -> Self {
+        if let Some(result) = cache.get(h) {
+            return result.clone();
+        }
+
+        let g_moves = self.to_moves();
+        let h_moves = h.to_moves();
+
+        let mut new_left_moves = g_moves.left;
+        for h_left in &h_moves.left {
+            new_left_moves.push(self.ordinal_sum_cached(h_left, cache));
+        }
+
+        let mut new_right_moves = g_moves.right;
+        for h_right in &h_moves.right {
+            new_right_moves.push(self.ordinal_sum_cached(h_right, cache));
+        }
+
+        let new_moves = Moves {
+            left: new_left_moves,
+            right: new_right_moves,
+        };
+        let result = new_moves.canonical_form();
+        cache.insert(h.clone(), result.clone());
+        result
+    }
+
+    /// Norton product `G.U` of `G` by a positive game `U`.
+    ///
+    /// Norton product is defined as `G` copies of `U` if `G` is an integer, or
+    /// `{G^L.U + U + I | G^R.U - (U + I)}` otherwise, where `I` ranges over incentives of `U`,
+    /// i.e. `U^L - U` and `U - U^R`. Result depends on form of `U`, here the canonical form is
+    /// used.
+    #[must_use]
+    pub fn norton_multiply(&self, u: &Self) -> Self {
+        let u_moves = u.to_moves();
+        let translations: Vec<Self> = u_moves
+            .left
+            .iter()
+            .cloned()
+            .chain(u_moves.right.iter().map(|u_right| u + u - u_right))
+            .collect();
+        self.norton_multiply_cached(u, &translations, &mut HashMap::new())
+    }
+
+    fn norton_multiply_cached(
+        &self,
+        u: &Self,
+        translations: &[Self],
+        cache: &mut HashMap<Self, Self>,
+    ) -> Self {
+        if let Some(integer) = self.to_number().and_then(|number| number.to_integer()) {
+            let mut res = Self::new_integer(0);
+            for _ in 0..integer.abs() {
+                res += u;
+            }
+            return if integer < 0 { -res } else { res };
+        }
+
+        if let Some(result) = cache.get(self) {
+            return result.clone();
+        }
+
+        let moves = self.to_moves();
+
+        let mut new_left_moves = Vec::with_capacity(moves.left.len() * translations.len());
+        for left_move in &moves.left {
+            let left_product = left_move.norton_multiply_cached(u, translations, cache);
+            for translation in translations {
+                new_left_moves.push(&left_product + translation);
+            }
+        }
+
+        let mut new_right_moves = Vec::with_capacity(moves.right.len() * translations.len());
+        for right_move in &moves.right {
+            let right_product = right_move.norton_multiply_cached(u, translations, cache);
+            for translation in translations {
+                new_right_moves.push(&right_product - translation);
+            }
+        }
+
+        let new_moves = Moves {
+            left: new_left_moves,
+            right: new_right_moves,
+        };
+        let result = new_moves.canonical_form();
+        cache.insert(self.clone(), result.clone());
+        result
+    }
+
+    /// A remote star of game `g` is a nimber `*N` if no position of `g` including `g` has value `*N`
+    ///
+    /// Remote star is used by the atomic weight calculus, see [`Self::atomic_weight`].
     #[must_use]
     #[allow(clippy::or_fun_call)]
     pub fn far_star(&self) -> Nimber {
@@ -1226,8 +2058,11 @@ impl CanonicalForm {
             .unwrap_or(Nimber::from(1))
     }
 
-    // FIXME: Handle cases when atomic weight does not exist
     /// Atmoic weight of a position, sometimes called "uppitiness"
+    ///
+    /// Atomic weight is computed as `{G^L.aw - 2 | G^R.aw + 2}`, unless the result is an integer
+    /// and `G` is comparable with its remote star (see [`Self::far_star`]), in which case the
+    /// eccentric rule is used. Result is meaningful only for all-small games.
     #[must_use]
     pub fn atomic_weight(&self) -> Self {
         match self.inner {
@@ -1280,7 +2115,7 @@ impl CanonicalForm {
                         .right
                         .iter()
                         .map(|right_move| {
-                            let greatest = right_move.left_stop().round();
+                            let greatest = right_move.left_stop().floor();
                             if right_move <= &Self::new_integer(greatest) {
                                 greatest - 1
                             } else {
@@ -1297,10 +2132,43 @@ impl CanonicalForm {
         }
     }
 
-    /// Parse game using `{a,b,...|c,d,...}` notation
+    /// Parse switch `x±G = x + {G|-G}`, where number `x` is optional
+    fn parse_switch(input: &str) -> nom::IResult<&str, Self> {
+        let (input, number) = opt(lexeme(DyadicRationalNumber::parse))(input)?;
+        let (input, _) = lexeme(alt((tag("±"), tag("+-"))))(input)?;
+        let (input, game) = Self::parse(input)?;
+        let switch = Self::new_from_moves(Moves {
+            left: vec![game.clone()],
+            right: vec![-game],
+        });
+        Ok((
+            input,
+            number.map_or_else(
+                || switch.clone(),
+                |number| Self::new_dyadic(number) + &switch,
+            ),
+        ))
+    }
+
+    /// Check if the game is an uptimal, and return its uptimal expansion if so
+    pub fn to_uptimal(&self) -> Option<Uptimal> {
+        Uptimal::from_canonical_form(self)
+    }
+
+    /// Check if the game is a superstar `↑^{a b c ...}`, and return it if so
+    pub fn to_superstar(&self) -> Option<Superstar> {
+        Superstar::from_canonical_form(self)
+    }
+
+    /// Parse game using `{a,b,...|c,d,...}` notation. Uptimal notation (see [`Uptimal`]) and
+    /// switches like `±1` or `3±{2|1}` are also accepted.
     #[allow(clippy::missing_errors_doc)]
     pub fn parse(input: &str) -> nom::IResult<&str, Self> {
         alt((
+            Self::parse_switch,
+            |input| {
+                Uptimal::parse(input).map(|(input, uptimal)| (input, uptimal.to_canonical_form()))
+            },
             |input| Nus::parse(input).map(|(input, nus)| (input, Self::new_nus(nus))),
             |input| Moves::parse(input).map(|(input, moves)| (input, Self::new_from_moves(moves))),
         ))(input)
@@ -1310,15 +2178,7 @@ impl CanonicalForm {
 impl PartialOrd for CanonicalForm {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        if self == other {
-            Some(Ordering::Equal)
-        } else if Self::leq(self, other) {
-            Some(Ordering::Less)
-        } else if Self::leq(other, self) {
-            Some(Ordering::Greater)
-        } else {
-            None
-        }
+        Self::compare(self, other)
     }
 
     fn le(&self, other: &Self) -> bool {
@@ -1330,6 +2190,42 @@ impl PartialOrd for CanonicalForm {
     }
 }
 
+/// Compare games with numbers, e.g. `g > 1`, using the partial order of games
+macro_rules! impl_number_comparison {
+    ($number:ty, $constructor:path) => {
+        impl PartialEq<$number> for CanonicalForm {
+            #[inline]
+            fn eq(&self, other: &$number) -> bool {
+                self.to_number() == Some(DyadicRationalNumber::from(*other))
+            }
+        }
+
+        impl PartialOrd<$number> for CanonicalForm {
+            #[inline]
+            fn partial_cmp(&self, other: &$number) -> Option<Ordering> {
+                Self::compare(self, &$constructor(*other))
+            }
+        }
+
+        impl PartialEq<CanonicalForm> for $number {
+            #[inline]
+            fn eq(&self, other: &CanonicalForm) -> bool {
+                other == self
+            }
+        }
+
+        impl PartialOrd<CanonicalForm> for $number {
+            #[inline]
+            fn partial_cmp(&self, other: &CanonicalForm) -> Option<Ordering> {
+                other.partial_cmp(self).map(Ordering::reverse)
+            }
+        }
+    };
+}
+
+impl_number_comparison!(i64, CanonicalForm::new_integer);
+impl_number_comparison!(DyadicRationalNumber, CanonicalForm::new_dyadic);
+
 impl_op_ex!(+|g: &CanonicalForm, h: &CanonicalForm| -> CanonicalForm { CanonicalForm::construct_sum(g, h) });
 impl_op_ex!(+=|g: &mut CanonicalForm, h: &CanonicalForm| { *g = CanonicalForm::construct_sum(g, h) });
 impl_op_ex!(-|g: &CanonicalForm| -> CanonicalForm { CanonicalForm::construct_negative(g) });
@@ -1340,16 +2236,32 @@ impl_op_ex!(-=|g: &mut CanonicalForm, h: &CanonicalForm| {
     *g = CanonicalForm::construct_sum(g, &CanonicalForm::construct_negative(h));
 });
 
+impl Sum for CanonicalForm {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new_integer(0), |acc, g| acc + g)
+    }
+}
+
+impl<'a> Sum<&'a Self> for CanonicalForm {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::new_integer(0), |acc, g| acc + g)
+    }
+}
+
 impl Display for CanonicalForm {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.inner {
             CanonicalFormInner::Nus(nus) => nus.fmt(f),
-            CanonicalFormInner::Moves(moves) => moves.fmt(f),
+            CanonicalFormInner::Moves(moves) => match self.to_uptimal() {
+                // All-small games are way more readable in uptimal notation
+                Some(uptimal) => uptimal.fmt(f),
+                None => moves.fmt(f),
+            },
         }
     }
 }
 
-impl_from_str_via_nom!(CanonicalForm);
+impl_from_str_via_nom!(CanonicalForm, without_serde);
 
 #[cfg(test)]
 mod tests {
@@ -1472,6 +2384,36 @@ mod tests {
         assert_eq!(&minus_forty_two.to_string(), "-42");
     }
 
+    #[test]
+    fn classifies_options() {
+        let zero = CanonicalForm::new_integer(0);
+        let one = CanonicalForm::new_integer(1);
+        let star = CanonicalForm::new_nimber(DyadicRationalNumber::from(0), Nimber::from(1));
+
+        // {1, 0, 1 | 0}: 0 is dominated by 1 and the second 1 duplicates the first
+        let moves = Moves {
+            left: vec![one.clone(), zero.clone(), one],
+            right: vec![zero],
+        };
+        assert_eq!(
+            moves.left_option_kinds(),
+            vec![
+                OptionKind::Canonical,
+                OptionKind::Dominated,
+                OptionKind::Dominated
+            ]
+        );
+        assert_eq!(moves.right_option_kinds(), vec![OptionKind::Canonical]);
+
+        // {* | *} = 0, Right reverses * through 0 and Left reverses * through 0
+        let moves = Moves {
+            left: vec![star.clone()],
+            right: vec![star],
+        };
+        assert_eq!(moves.left_option_kinds(), vec![OptionKind::Reversible]);
+        assert_eq!(moves.right_option_kinds(), vec![OptionKind::Reversible]);
+    }
+
     #[test]
     fn constructs_rationals() {
         let rational = DyadicRationalNumber::new(3, 4);
@@ -1536,12 +2478,77 @@ mod tests {
         };
     }
 
+    #[test]
+    fn heap_size() {
+        assert_eq!(CanonicalForm::from_str("1/2v*").unwrap().heap_size(), 0);
+        let switch = CanonicalForm::from_str("{1|-1}").unwrap();
+        let hot = CanonicalForm::from_str("{3|{1|-1}}").unwrap();
+        assert!(switch.heap_size() >= 2 * size_of::<CanonicalForm>());
+        assert!(hot.heap_size() >= switch.heap_size() + 2 * size_of::<CanonicalForm>());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn comparison_cache_agrees() {
+        let games = ["{1|-1}", "{3|{1|-1}}", "{{2|0}|-1}", "^*", "{0,*|0}", "1/2"]
+            .map(|game| CanonicalForm::from_str(game).unwrap());
+        let expected = games
+            .iter()
+            .flat_map(|g| games.iter().map(|h| CanonicalForm::leq(g, h)))
+            .collect::<Vec<_>>();
+
+        comparison_cache::set_enabled(true);
+        let before = comparison_cache::statistics();
+        for _ in 0..2 {
+            let cached = games
+                .iter()
+                .flat_map(|g| games.iter().map(|h| CanonicalForm::leq(g, h)))
+                .collect::<Vec<_>>();
+            assert_eq!(cached, expected);
+        }
+        comparison_cache::set_enabled(false);
+        assert!(comparison_cache::statistics().hits > before.hits);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn thermograph_cache_agrees() {
+        let games = [
+            "{1|-1}",
+            "{3|{1|-1}}",
+            "{{2|0}|-1}",
+            "{{3|1},4|{0|-2}}",
+            "{0,*|0}",
+        ]
+        .map(|game| CanonicalForm::from_str(game).unwrap());
+        let evaluate = |game: &CanonicalForm| {
+            (
+                game.thermograph(),
+                game.temperature(),
+                game.mean(),
+                game.adorned_left_stop(),
+                game.adorned_right_stop(),
+            )
+        };
+
+        thermograph_cache::set_enabled(false);
+        let expected = games.iter().map(evaluate).collect::<Vec<_>>();
+        thermograph_cache::set_enabled(true);
+        let before = thermograph_cache::statistics();
+        for _ in 0..2 {
+            assert_eq!(games.iter().map(evaluate).collect::<Vec<_>>(), expected);
+        }
+        assert!(thermograph_cache::statistics().hits > before.hits);
+    }
+
     #[test]
     fn negative() {
         assert_negative_eq!("0", "0");
         assert_negative_eq!("42", "-42");
         assert_negative_eq!("-42", "42");
-        assert_negative_eq!("{^|*}", "{v|*}");
+        assert_negative_eq!("^.11", "v.11");
+        assert_negative_eq!("{0|*2, *3}", "{*2, *3|0}");
+        assert_negative_eq!("{3|1}", "{-1|-3}");
     }
 
     #[test]
@@ -1618,10 +2625,10 @@ mod tests {
             right: vec![weird_right],
         };
         assert_ne!(
-            &Moves::print_deep_to_str(&weird.canonicalize()),
+            &Moves::print_deep_to_str(&weird.checked_canonicalize().unwrap()),
             "{|{{{|}|{{|}|{|}}}|{|{|{|}}}}}"
         );
-        assert_eq!(&weird.canonicalize().to_string(), "{|}");
+        assert_eq!(&weird.checked_canonicalize().unwrap().to_string(), "{|}");
         let weird = CanonicalForm::new_from_moves(weird);
         let weird_moves = weird.to_moves();
         assert_eq!(&weird_moves.to_string(), "{|}");
@@ -1642,8 +2649,63 @@ mod tests {
             right: vec![one],
         });
 
-        let sum = one_zero + zero_one;
+        let sum = &one_zero + &zero_one;
         assert_eq!(&sum.to_string(), "{3/2|1/2}");
+        assert_eq!(
+            [one_zero.clone(), zero_one.clone()]
+                .into_iter()
+                .sum::<CanonicalForm>(),
+            sum
+        );
+        assert_eq!(
+            [&one_zero, &zero_one].into_iter().sum::<CanonicalForm>(),
+            sum
+        );
+        assert_eq!(
+            std::iter::empty::<CanonicalForm>().sum::<CanonicalForm>(),
+            CanonicalForm::new_integer(0)
+        );
+        assert_eq!(&sum - &zero_one - &one_zero, CanonicalForm::new_integer(0));
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let one = CanonicalForm::new_integer(1);
+        let max = CanonicalForm::new_integer(i64::MAX);
+        assert_eq!(CanonicalForm::checked_sum(&max, &one), None);
+        assert_eq!(
+            CanonicalForm::new_integer(i64::MIN).checked_negative(),
+            None
+        );
+        assert_eq!(
+            CanonicalForm::checked_sum(&max, &-&one),
+            Some(CanonicalForm::new_integer(i64::MAX - 1))
+        );
+
+        let switch = CanonicalForm::new_from_moves(Moves {
+            left: vec![max.clone()],
+            right: vec![-&max],
+        });
+        assert_eq!(CanonicalForm::checked_sum(&switch, &one), None);
+        assert_eq!(switch.checked_negative(), Some(switch.clone()));
+        assert_eq!(switch.checked_thermograph(), None);
+        assert_eq!(switch.left_stop(), DyadicRationalNumber::from(i64::MAX));
+    }
+
+    #[test]
+    fn compare_with_numbers() {
+        let switch = CanonicalForm::from_str("{3|2}").unwrap();
+        assert!(switch > 1);
+        assert!(switch < 4);
+        assert!(1 < switch);
+        assert_eq!(switch.partial_cmp(&2), None);
+        assert!(switch > DyadicRationalNumber::new(3, 1));
+        assert!(CanonicalForm::new_integer(2) == 2);
+        assert!(DyadicRationalNumber::new(1, 1) == CanonicalForm::from_str("1/2").unwrap());
+
+        let star = CanonicalForm::from_str("*").unwrap();
+        assert_eq!(star.partial_cmp(&0), None);
+        assert!(star > -1);
     }
 
     #[test]
@@ -1659,6 +2721,89 @@ mod tests {
         assert_eq!(g.temperature(), DyadicRationalNumber::from(1));
     }
 
+    macro_rules! assert_temperature_mean {
+        ($cf:expr, $temperature:expr, $mean:expr) => {
+            let g = CanonicalForm::from_str($cf).unwrap();
+            assert_eq!(
+                g.temperature(),
+                DyadicRationalNumber::from_str($temperature).unwrap(),
+                "Invalid temperature"
+            );
+            assert_eq!(
+                g.mean(),
+                DyadicRationalNumber::from_str($mean).unwrap(),
+                "Invalid mean"
+            );
+        };
+    }
+
+    #[test]
+    fn temperature_and_mean() {
+        assert_temperature_mean!("0", "-1", "0");
+        assert_temperature_mean!("5", "-1", "5");
+        assert_temperature_mean!("3/4", "-1/4", "3/4");
+        assert_temperature_mean!("^*", "0", "0");
+        assert_temperature_mean!("{1|-1}", "1", "0");
+        assert_temperature_mean!("{5|1}", "2", "3");
+        assert_temperature_mean!("{2|-1}", "3/2", "1/2");
+        assert_temperature_mean!("{{3|2}|0}", "5/4", "5/4");
+
+        // Domineering ..#|..#|... from the domineering tests
+        assert_temperature_mean!("{1/2|-2}", "5/4", "-3/4");
+    }
+
+    #[test]
+    fn infinitesimal_classification() {
+        let class = |input: &str| {
+            CanonicalForm::from_str(input)
+                .unwrap()
+                .infinitesimal_class()
+        };
+
+        assert_eq!(class("0"), Some(InfinitesimalClass::Zero));
+        assert_eq!(class("*3"), Some(InfinitesimalClass::Nimber));
+        assert_eq!(class("1/2"), None);
+        assert_eq!(class("{1|-1}"), None);
+        assert_eq!(class("^"), Some(InfinitesimalClass::Positive { ups: 1 }));
+        assert_eq!(class("^2*"), Some(InfinitesimalClass::Positive { ups: 2 }));
+        assert_eq!(class("^<2>"), Some(InfinitesimalClass::Positive { ups: 0 }));
+        assert_eq!(
+            class("{0|{0|-2}}"),
+            Some(InfinitesimalClass::Positive { ups: 0 })
+        );
+        assert_eq!(class("v3"), Some(InfinitesimalClass::Negative { downs: 3 }));
+        assert_eq!(class("^*"), Some(InfinitesimalClass::Fuzzy));
+    }
+
+    #[test]
+    fn all_small_works() {
+        let cf = |input: &str| CanonicalForm::from_str(input).unwrap();
+
+        assert!(cf("0").is_all_small());
+        assert!(cf("^*3").is_all_small());
+        assert!(cf("{0|^<2>}").is_all_small());
+        assert!(!cf("1").is_all_small());
+        assert!(!cf("{0|{0|-2}}").is_all_small());
+        assert!(cf("{0|{0|-2}}").is_infinitesimal());
+        assert!(cf("1*").is_nimberish());
+        assert!(!cf("1^").is_nimberish());
+        assert_eq!(cf("^<2>").birthday(), 3);
+    }
+
+    #[test]
+    fn parse_uptimals() {
+        let up_second = CanonicalForm::from_str("^<2>").unwrap();
+        assert_eq!(up_second.to_string(), "^<2>");
+        assert_eq!(up_second.to_moves().to_string(), "{0|v*}");
+        assert_eq!(up_second.to_uptimal().unwrap().to_string(), "^<2>");
+        assert_eq!(CanonicalForm::from_str("↑²").unwrap(), up_second);
+        assert_eq!(CanonicalForm::from_str("^^*").unwrap().to_string(), "^2*");
+        assert_eq!(
+            CanonicalForm::from_str("{^.11|0}").unwrap(),
+            CanonicalForm::from_str("{{^|*}|0}").unwrap()
+        );
+    }
+
     #[test]
     fn parse_games() {
         macro_rules! test_game_parse {
@@ -1678,6 +2823,100 @@ mod tests {
         test_game_parse!("{12/16|14/16}", "13/16");
         test_game_parse!("{0|2}", "1");
         test_game_parse!("{0,*,*2|0,*,*2}", "*3");
+        test_game_parse!("{3|2}", "{3|2}");
+        test_game_parse!("{1,1*|0}", "{1, 1*|0}");
+        test_game_parse!("±1", "{1|-1}");
+        test_game_parse!("+-1", "{1|-1}");
+        test_game_parse!("3±1", "{4|2}");
+        test_game_parse!("±{2|1}", "{{2|1}|{-1|-2}}");
+        test_game_parse!("{±1|-2}", "{{1|-1}|-2}");
+        test_game_parse!("±*", "0");
+        test_game_parse!("*2", "*2");
+        test_game_parse!("^*", "^*");
+    }
+
+    #[test]
+    fn far_star_works() {
+        let far_star = |input: &str| CanonicalForm::from_str(input).unwrap().far_star();
+
+        assert_eq!(far_star("0"), Nimber::new(1));
+        assert_eq!(far_star("*2"), Nimber::new(3));
+        assert_eq!(far_star("^"), Nimber::new(2));
+        assert_eq!(far_star("{0|*2, *3}"), Nimber::new(4));
+        assert_eq!(
+            CanonicalForm::from_str("{0|*2, *3}")
+                .unwrap()
+                .to_superstar()
+                .unwrap()
+                .to_string(),
+            "^{2 3}"
+        );
+    }
+
+    #[test]
+    fn compounds_work() {
+        let cf = |input: &str| CanonicalForm::from_str(input).unwrap();
+
+        assert_eq!(
+            CanonicalForm::construct_sequential_compound(&cf("0"), &cf("{1|-1}")),
+            cf("{1|-1}")
+        );
+        assert_eq!(
+            CanonicalForm::construct_sequential_compound(&cf("*"), &cf("1")),
+            cf("1*")
+        );
+        assert_eq!(
+            CanonicalForm::construct_sequential_compound(&cf("1"), &cf("*")),
+            cf("0")
+        );
+
+        assert_eq!(
+            CanonicalForm::construct_selective_compound(&cf("*"), &cf("*")),
+            cf("*2")
+        );
+        assert_eq!(
+            CanonicalForm::construct_selective_compound(&cf("1"), &cf("1")),
+            cf("2")
+        );
+        assert_eq!(
+            CanonicalForm::construct_selective_compound(&cf("1"), &cf("-1")),
+            cf("0")
+        );
+    }
+
+    #[test]
+    fn latex_works() {
+        let latex = |input: &str| CanonicalForm::from_str(input).unwrap().to_latex();
+
+        assert_eq!(latex("0"), "0");
+        assert_eq!(latex("-3/4"), r"-\frac{3}{4}");
+        assert_eq!(latex("2^2*3"), r"2\Uparrow*3");
+        assert_eq!(latex("v5*"), r"5\cdot\downarrow*");
+        assert_eq!(latex("{3|{2|1}}"), r"\{3 \,||\, 2 \,|\, 1\}");
+        assert_eq!(latex("{{4|3}|{2|1}}"), r"\{4 \,|\, 3 \,||\, 2 \,|\, 1\}");
+        assert_eq!(
+            latex("{{4|1}, {3|2}|0}"),
+            r"\{\{3 \,|\, 2\}, \{4 \,|\, 1\} \,|\, 0\}"
+        );
+        assert_eq!(latex("{0|v*}"), r"\{0 \,|\, \downarrow*\}");
+    }
+
+    #[test]
+    fn parse_print_roundtrip() {
+        for input in [
+            "{3|2}",
+            "{1, 1*|0}",
+            "{{1|-1}|-2}",
+            "^.11",
+            "v<2>*",
+            "{0|*2, *3}",
+            "{{2|1}|{-1|-2}}",
+            "1/2^2*3",
+        ] {
+            let game = CanonicalForm::from_str(input).unwrap();
+            assert_eq!(game.to_string(), input);
+            assert_eq!(CanonicalForm::from_str(&game.to_string()).unwrap(), game);
+        }
     }
 
     #[test]
@@ -1704,10 +2943,54 @@ mod tests {
         test_ordering!("1", "*", Some(Ordering::Greater));
     }
 
+    #[test]
+    fn ordinal_sum_works() {
+        let cf = |input: &str| CanonicalForm::from_str(input).unwrap();
+
+        assert_eq!(cf("1").ordinal_sum(&cf("1")), cf("2"));
+        assert_eq!(cf("1").ordinal_sum(&cf("-1")), cf("1/2"));
+        assert_eq!(cf("*").ordinal_sum(&cf("1")), cf("^*"));
+        assert_eq!(cf("0").ordinal_sum(&cf("{1|-1}")), cf("{1|-1}"));
+    }
+
+    #[test]
+    fn norton_multiply_works() {
+        let cf = |input: &str| CanonicalForm::from_str(input).unwrap();
+
+        assert_eq!(cf("2").norton_multiply(&cf("^")), cf("^2"));
+        assert_eq!(cf("-1").norton_multiply(&cf("^")), cf("v"));
+        assert_eq!(cf("1/2").norton_multiply(&cf("1")), cf("1/2"));
+        assert_eq!(cf("{1|-1}").norton_multiply(&cf("1")), cf("{1|-1}"));
+        assert_eq!(cf("1/2").norton_multiply(&cf("2")), cf("1*"));
+    }
+
+    #[test]
+    fn comparison_witnesses() {
+        let cf = |input: &str| CanonicalForm::from_str(input).unwrap();
+
+        assert!(CanonicalForm::fuzzy(&cf("{1|-1}"), &cf("0")));
+        assert!(CanonicalForm::geq(&cf("^"), &cf("0")));
+        assert!(!CanonicalForm::fuzzy(&cf("^"), &cf("0")));
+
+        assert_eq!(CanonicalForm::leq_witness(&cf("0"), &cf("^")), None);
+        assert_eq!(
+            CanonicalForm::leq_witness(&cf("^"), &cf("0")),
+            Some(ComparisonWitness::LeftOption(cf("0")))
+        );
+        assert_eq!(
+            CanonicalForm::leq_witness(&cf("0"), &cf("v")),
+            Some(ComparisonWitness::RightOption(cf("0")))
+        );
+        assert_eq!(
+            CanonicalForm::geq_witness(&cf("{1|-1}"), &cf("0")),
+            Some(ComparisonWitness::RightOption(cf("-1")))
+        );
+    }
+
     macro_rules! assert_stops {
         ($cf:expr, $left:expr, $right:expr) => {
             let g = CanonicalForm::from_str($cf).unwrap();
-            let (left_stop, right_stop) = g.confusion_interval();
+            let (left_stop, right_stop) = (g.left_stop(), g.right_stop());
             assert_eq!(
                 left_stop,
                 DyadicRationalNumber::from_str($left).expect("Could not parse left stop"),
@@ -1731,6 +3014,50 @@ mod tests {
         assert_stops!("v", "0", "0");
         assert_stops!("*", "0", "0");
         assert_stops!("^", "0", "0");
+        assert_stops!("{3|{1|0},{2|-1}}", "3", "1");
+    }
+
+    #[test]
+    fn confusion_interval_works() {
+        let interval = |cf: &str| {
+            CanonicalForm::from_str(cf)
+                .unwrap()
+                .confusion_interval()
+                .to_string()
+        };
+
+        assert_eq!(interval("1/2"), "{}");
+        assert_eq!(interval("*"), "[0, 0]");
+        assert_eq!(interval("^"), "{}");
+        assert_eq!(interval("^*"), "[0, 0]");
+        assert_eq!(interval("{3|{1|0},{2|-1}}"), "(1, 3]");
+        assert_eq!(interval("{{2|1}|-1}"), "[-1, 1)");
+        assert_eq!(interval("{1|^}"), "(0, 1]");
+
+        let g = CanonicalForm::from_str("{1|^}").unwrap();
+        assert!(!g
+            .confusion_interval()
+            .contains(DyadicRationalNumber::from(0)));
+        assert!(g
+            .confusion_interval()
+            .contains(DyadicRationalNumber::from_str("1/2").unwrap()));
+    }
+
+    #[test]
+    fn adorned_stops_work() {
+        let adorned = |cf: &str| {
+            let g = CanonicalForm::from_str(cf).unwrap();
+            (g.adorned_left_stop(), g.adorned_right_stop())
+        };
+        let stop = |number: i64, right_to_move: bool| AdornedStop {
+            number: DyadicRationalNumber::from(number),
+            right_to_move,
+        };
+
+        assert_eq!(adorned("1"), (stop(1, false), stop(1, true)));
+        assert_eq!(adorned("*"), (stop(0, true), stop(0, false)));
+        assert_eq!(adorned("{2|1}"), (stop(2, true), stop(1, false)));
+        assert!(stop(0, false) < stop(0, true));
     }
 
     macro_rules! assert_cooled {
@@ -1763,6 +3090,22 @@ mod tests {
         assert_eq!(g, heated);
     }
 
+    #[test]
+    fn overheating_works() {
+        let one = CanonicalForm::new_integer(1);
+        let one_star = CanonicalForm::from_str("1*").unwrap();
+
+        let g = CanonicalForm::new_integer(2);
+        assert_eq!(g.overheat(&one_star, &one).to_string(), "2");
+        assert_eq!(g.overheat(&one, &one).to_string(), "2");
+
+        let g = CanonicalForm::from_str("*").unwrap();
+        assert_eq!(g.overheat(&one, &one).to_string(), "{1|-1}");
+
+        let g = CanonicalForm::from_str("{1|0}").unwrap();
+        assert_eq!(g.overheat(&one_star, &one).to_string(), "{2*|-1}");
+    }
+
     #[test]
     fn cooling_heating_roundtrip() {
         let g = CanonicalForm::from_str("{2|-1}").unwrap();
@@ -1795,5 +3138,29 @@ mod tests {
         assert_atomic_weight_eq!("{^2|*}", "1");
         assert_atomic_weight_eq!("{^2,{^|*}|*}", "1");
         assert_atomic_weight_eq!("{*|v2}", "-1");
+        assert_atomic_weight_eq!("^*", "1");
+        assert_atomic_weight_eq!("^2*", "2");
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn sum_with_negative_is_zero(g in crate::strategies::canonical_form(3)) {
+            proptest::prop_assert_eq!(&g + &(-&g), CanonicalForm::new_integer(0));
+        }
+
+        #[test]
+        fn sum_is_commutative_and_associative(
+            g in crate::strategies::canonical_form(2),
+            h in crate::strategies::canonical_form(2),
+            k in crate::strategies::canonical_form(2),
+        ) {
+            proptest::prop_assert_eq!(&g + &h, &h + &g);
+            proptest::prop_assert_eq!(&(&g + &h) + &k, &g + &(&h + &k));
+        }
+
+        #[test]
+        fn parse_roundtrip(g in crate::strategies::canonical_form(3)) {
+            proptest::prop_assert_eq!(CanonicalForm::from_str(&g.to_string()), Ok(g));
+        }
     }
 }
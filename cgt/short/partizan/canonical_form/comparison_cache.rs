@@ -0,0 +1,80 @@
+//! Cache of comparisons between canonical forms
+//!
+//! Simplification of games eliminates dominated options and bypasses reversible ones, comparing
+//! the same pairs of followers over and over. When the cache is [enabled](set_enabled),
+//! [`CanonicalForm::leq`] keeps results of comparisons between games that are not Number Up Star
+//! sums (these are compared in constant time) in a small cache local to each thread, where a new
+//! result replaces the old one with the same slot.
+//!
+//! Cache is disabled by default. Looking up a comparison hashes both games, which takes about as
+//! long as comparing games of searches like Domineering, so despite high hit rates the cache
+//! makes them slower. It pays off when the same large games are compared many times. Use
+//! `cargo bench --bench canonical_form` to measure both.
+
+use crate::short::partizan::canonical_form::{
+    slot_cache::{Counters, SlotCache},
+    CanonicalForm,
+};
+use std::{
+    cell::RefCell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+pub use crate::short::partizan::canonical_form::slot_cache::Statistics;
+
+/// Number of comparisons stored by each thread
+const CAPACITY: usize = 1 << 12;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static COUNTERS: Counters = Counters::new();
+
+thread_local! {
+    static CACHE: RefCell<SlotCache<(CanonicalForm, CanonicalForm), bool>> =
+        RefCell::new(SlotCache::new(CAPACITY, &COUNTERS));
+}
+
+/// Enable or disable the cache in all threads. Results stored before disabling are kept.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Check if comparisons are cached
+#[inline]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Get usage statistics of caches of all threads
+///
+/// Threads update the shared counters in batches, so recent lookups may be missing.
+pub fn statistics() -> Statistics {
+    CACHE.with_borrow_mut(SlotCache::flush);
+    COUNTERS.statistics()
+}
+
+/// Reset counters reported by [`statistics`]
+pub fn reset_statistics() {
+    CACHE.with_borrow_mut(SlotCache::flush);
+    COUNTERS.reset();
+}
+
+/// Get the result of `lhs <= rhs` from the cache, or compute it with `leq` and store it
+pub(crate) fn leq_cached<F>(lhs: &CanonicalForm, rhs: &CanonicalForm, leq: F) -> bool
+where
+    F: FnOnce() -> bool,
+{
+    let lookup = CACHE.with_borrow_mut(|cache| {
+        cache.lookup((lhs, rhs), |(stored_lhs, stored_rhs)| {
+            stored_lhs == lhs && stored_rhs == rhs
+        })
+    });
+    match lookup {
+        Ok(result) => result,
+        Err(slot) => {
+            // Cache cannot be borrowed while comparing, as comparison of options uses it too
+            let result = leq();
+            CACHE.with_borrow_mut(|cache| cache.store(slot, (lhs.clone(), rhs.clone()), result));
+            result
+        }
+    }
+}
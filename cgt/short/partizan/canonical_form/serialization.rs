@@ -0,0 +1,238 @@
+//! Serialization of canonical forms as trees of values
+//!
+//! Schema does not depend on the internal representation of games, so values stored with one
+//! version can be read by later ones. [`Nus`] is a map with exact `number` (e.g. `"3/2"`),
+//! `up_multiple` and `nimber`, [`Moves`] is a map with `left` and `right` lists of canonical
+//! forms, and [`CanonicalForm`] is either `{"nus": ...}` or `{"moves": ...}`:
+//!
+//! ```text
+//! {"moves": {"left": [{"nus": {"number": "1", "up_multiple": 0, "nimber": 0}}],
+//!            "right": [{"nus": {"number": "-1", "up_multiple": 0, "nimber": 0}}]}}
+//! ```
+//!
+//! Moves of deserialized canonical forms are canonicalized, so they do not have to be in
+//! canonical form, e.g. a game written by hand or by another program. All three types can also
+//! be read from strings like `"{1|-1}"`, which earlier versions wrote.
+
+use crate::{
+    numeric::{dyadic_rational_number::DyadicRationalNumber, nimber::Nimber},
+    short::partizan::canonical_form::{CanonicalForm, CanonicalFormInner, Moves, Nus},
+};
+use alloc::{string::String, vec::Vec};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Nus")]
+struct NusTree {
+    number: DyadicRationalNumber,
+    up_multiple: i32,
+    nimber: Nimber,
+}
+
+impl From<Nus> for NusTree {
+    fn from(nus: Nus) -> Self {
+        Self {
+            number: nus.number(),
+            up_multiple: nus.up_multiple(),
+            nimber: nus.nimber(),
+        }
+    }
+}
+
+impl From<NusTree> for Nus {
+    fn from(tree: NusTree) -> Self {
+        Self::new(tree.number, tree.up_multiple, tree.nimber)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename = "Moves")]
+struct MovesTreeRef<'a> {
+    left: &'a [CanonicalForm],
+    right: &'a [CanonicalForm],
+}
+
+impl<'a> From<&'a Moves> for MovesTreeRef<'a> {
+    fn from(moves: &'a Moves) -> Self {
+        Self {
+            left: &moves.left,
+            right: &moves.right,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Moves")]
+struct MovesTree {
+    left: Vec<CanonicalForm>,
+    right: Vec<CanonicalForm>,
+}
+
+#[derive(Serialize)]
+#[serde(rename = "CanonicalForm", rename_all = "snake_case")]
+enum CanonicalFormTreeRef<'a> {
+    Nus(NusTree),
+    Moves(MovesTreeRef<'a>),
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "CanonicalForm", rename_all = "snake_case")]
+enum CanonicalFormTree {
+    Nus(NusTree),
+    Moves(MovesTree),
+}
+
+/// Tree or a string in the format of earlier versions
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OrString<T> {
+    String(String),
+    Tree(T),
+}
+
+impl<T> OrString<T> {
+    fn into_tree<U, E>(self, parse: impl FnOnce(&str) -> Result<U, &'static str>) -> Result<U, E>
+    where
+        U: From<T>,
+        E: de::Error,
+    {
+        match self {
+            Self::String(input) => {
+                parse(&input).map_err(|err| E::custom(format_args!("'{input}': {err}")))
+            }
+            Self::Tree(tree) => Ok(U::from(tree)),
+        }
+    }
+}
+
+impl Serialize for Nus {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        NusTree::from(*self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Nus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        OrString::<NusTree>::deserialize(deserializer)?.into_tree(Self::from_str)
+    }
+}
+
+impl From<MovesTree> for Moves {
+    fn from(tree: MovesTree) -> Self {
+        Self {
+            left: tree.left,
+            right: tree.right,
+        }
+    }
+}
+
+impl Serialize for Moves {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        MovesTreeRef::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Moves {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        OrString::<MovesTree>::deserialize(deserializer)?.into_tree(Self::from_str)
+    }
+}
+
+impl From<CanonicalFormTree> for CanonicalForm {
+    fn from(tree: CanonicalFormTree) -> Self {
+        match tree {
+            CanonicalFormTree::Nus(nus) => Self::new_nus(Nus::from(nus)),
+            CanonicalFormTree::Moves(moves) => Self::new_from_moves(Moves::from(moves)),
+        }
+    }
+}
+
+impl Serialize for CanonicalForm {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match &self.inner {
+            CanonicalFormInner::Nus(nus) => CanonicalFormTreeRef::Nus(NusTree::from(*nus)),
+            CanonicalFormInner::Moves(moves) => {
+                CanonicalFormTreeRef::Moves(MovesTreeRef::from(moves))
+            }
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CanonicalForm {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        OrString::<CanonicalFormTree>::deserialize(deserializer)?.into_tree(Self::from_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(input: &str) {
+        let game = CanonicalForm::from_str(input).unwrap();
+        let json = serde_json::to_string(&game).unwrap();
+        assert_eq!(
+            serde_json::from_str::<CanonicalForm>(&json).unwrap(),
+            game,
+            "{json}"
+        );
+    }
+
+    #[test]
+    fn roundtrips() {
+        roundtrip("0");
+        roundtrip("-123v58*34");
+        roundtrip("{1|-1}");
+        roundtrip("{{3|1},4|{0|-2}}");
+        roundtrip("{0,*|0}");
+        roundtrip("{3|{1|-1}}");
+    }
+
+    #[test]
+    fn schema() {
+        assert_eq!(
+            serde_json::to_string(&CanonicalForm::from_str("{1|-1/2*}").unwrap()).unwrap(),
+            r#"{"moves":{"left":[{"nus":{"number":"1","up_multiple":0,"nimber":0}}],"right":[{"nus":{"number":"-1/2","up_multiple":0,"nimber":1}}]}}"#
+        );
+    }
+
+    #[test]
+    fn canonicalizes_moves() {
+        let json = r#"{"moves":{"left":[{"nus":{"number":"0","up_multiple":0,"nimber":0}},
+                                       {"nus":{"number":"-1","up_multiple":0,"nimber":0}}],
+                                "right":[]}}"#;
+        assert_eq!(
+            serde_json::from_str::<CanonicalForm>(json).unwrap(),
+            CanonicalForm::new_integer(1)
+        );
+    }
+
+    #[test]
+    fn reads_strings() {
+        assert_eq!(
+            serde_json::from_str::<CanonicalForm>(r#""{1|-1}""#).unwrap(),
+            CanonicalForm::from_str("{1|-1}").unwrap()
+        );
+        assert!(serde_json::from_str::<CanonicalForm>(r#""{1|""#).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+//! Fixed size caches of results of computations on canonical forms, local to each thread
+//!
+//! Every key has one slot, selected by its hash, and a new result replaces the old one stored in
+//! the same slot. Hits and misses are counted by each thread and added to counters shared by all
+//! threads in batches, so counting does not make threads wait for each other.
+
+use std::{
+    fmt::Display,
+    hash::Hash,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Number of lookups after which counters of a thread are added to the shared ones
+const FLUSH_INTERVAL: u64 = 1 << 10;
+
+/// Usage statistics of a cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Statistics {
+    /// Number of results found in the cache
+    pub hits: u64,
+
+    /// Number of results that were computed
+    pub misses: u64,
+}
+
+impl Statistics {
+    /// Total number of lookups
+    #[inline]
+    pub const fn lookups(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    /// Fraction of lookups that found the result, `None` if there were no lookups
+    #[inline]
+    pub fn hit_rate(&self) -> Option<f64> {
+        (self.lookups() != 0).then(|| self.hits as f64 / self.lookups() as f64)
+    }
+}
+
+impl Display for Statistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Hits: {}, Misses: {}", self.hits, self.misses)?;
+        if let Some(hit_rate) = self.hit_rate() {
+            write!(f, " ({:.2}% hit rate)", hit_rate * 100.0)?;
+        }
+        Ok(())
+    }
+}
+
+/// Counters of lookups shared by caches of all threads
+pub struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl Counters {
+    pub const fn new() -> Self {
+        Self {
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn statistics(&self) -> Statistics {
+        Statistics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn reset(&self) {
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Cache of a single thread
+pub struct SlotCache<K, V> {
+    entries: Box<[Option<(K, V)>]>,
+    hasher: ahash::RandomState,
+    hits: u64,
+    misses: u64,
+    counters: &'static Counters,
+}
+
+impl<K, V> SlotCache<K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    pub fn new(capacity: usize, counters: &'static Counters) -> Self {
+        Self {
+            entries: vec![None; capacity].into_boxed_slice(),
+            hasher: ahash::RandomState::new(),
+            hits: 0,
+            misses: 0,
+            counters,
+        }
+    }
+
+    /// Add counters of this thread to the shared ones
+    pub fn flush(&mut self) {
+        self.counters.hits.fetch_add(self.hits, Ordering::Relaxed);
+        self.counters
+            .misses
+            .fetch_add(self.misses, Ordering::Relaxed);
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// Get the stored result for a key with the given hash, for which `matches` returns true, or
+    /// the slot where the result should be stored
+    pub fn lookup<Q, F>(&mut self, key: Q, matches: F) -> Result<V, usize>
+    where
+        Q: Hash,
+        F: FnOnce(&K) -> bool,
+    {
+        let slot = self.hasher.hash_one(key) as usize % self.entries.len();
+        let result = match &self.entries[slot] {
+            Some((stored, value)) if matches(stored) => {
+                self.hits += 1;
+                Ok(value.clone())
+            }
+            _ => {
+                self.misses += 1;
+                Err(slot)
+            }
+        };
+        if self.hits + self.misses >= FLUSH_INTERVAL {
+            self.flush();
+        }
+        result
+    }
+
+    /// Store result in the slot returned by [`Self::lookup`]
+    pub fn store(&mut self, slot: usize, key: K, value: V) {
+        self.entries[slot] = Some((key, value));
+    }
+}
+
+impl<K, V> Drop for SlotCache<K, V> {
+    fn drop(&mut self) {
+        self.counters.hits.fetch_add(self.hits, Ordering::Relaxed);
+        self.counters
+            .misses
+            .fetch_add(self.misses, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,100 @@
+//! Cache of thermographs and stops of canonical forms
+//!
+//! Thermograph of a game is built from thermographs of its options, so searches computing
+//! temperatures of many positions compute thermographs of the same followers over and over.
+//! When the cache is [enabled](set_enabled), [`CanonicalForm::thermograph`] (and temperatures and
+//! means built on it) and adorned stops of games that are not Number Up Star sums are kept in
+//! small caches local to each thread, where a new result replaces the old one with the same slot.
+
+use crate::short::partizan::{
+    canonical_form::{
+        slot_cache::{Counters, SlotCache},
+        AdornedStop, CanonicalForm,
+    },
+    thermograph::Thermograph,
+};
+use std::{
+    cell::RefCell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+pub use crate::short::partizan::canonical_form::slot_cache::Statistics;
+
+/// Number of thermographs and of stops stored by each thread
+const CAPACITY: usize = 1 << 12;
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+static COUNTERS: Counters = Counters::new();
+
+thread_local! {
+    static THERMOGRAPHS: RefCell<SlotCache<CanonicalForm, Thermograph>> =
+        RefCell::new(SlotCache::new(CAPACITY, &COUNTERS));
+
+    static STOPS: RefCell<SlotCache<(CanonicalForm, bool), AdornedStop>> =
+        RefCell::new(SlotCache::new(CAPACITY, &COUNTERS));
+}
+
+/// Enable or disable the cache in all threads. Results stored before disabling are kept.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Check if thermographs and stops are cached
+#[inline]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Get usage statistics of caches of all threads, with lookups of thermographs and stops
+/// counted together
+///
+/// Threads update the shared counters in batches, so recent lookups may be missing.
+pub fn statistics() -> Statistics {
+    THERMOGRAPHS.with_borrow_mut(SlotCache::flush);
+    STOPS.with_borrow_mut(SlotCache::flush);
+    COUNTERS.statistics()
+}
+
+/// Reset counters reported by [`statistics`]
+pub fn reset_statistics() {
+    THERMOGRAPHS.with_borrow_mut(SlotCache::flush);
+    STOPS.with_borrow_mut(SlotCache::flush);
+    COUNTERS.reset();
+}
+
+/// Get the thermograph of `game` from the cache, or compute it with `thermograph` and store it
+/// unless it overflowed
+pub(crate) fn thermograph_cached<F>(game: &CanonicalForm, thermograph: F) -> Option<Thermograph>
+where
+    F: FnOnce() -> Option<Thermograph>,
+{
+    match THERMOGRAPHS.with_borrow_mut(|cache| cache.lookup(game, |stored| stored == game)) {
+        Ok(thermograph) => Some(thermograph),
+        Err(slot) => {
+            // Cache cannot be borrowed while computing, as thermographs of options use it too
+            let result = thermograph()?;
+            THERMOGRAPHS.with_borrow_mut(|cache| cache.store(slot, game.clone(), result.clone()));
+            Some(result)
+        }
+    }
+}
+
+/// Get the adorned stop of `game` from the cache, or compute it with `stop` and store it
+pub(crate) fn adorned_stop_cached<F>(game: &CanonicalForm, left_first: bool, stop: F) -> AdornedStop
+where
+    F: FnOnce() -> AdornedStop,
+{
+    let lookup = STOPS.with_borrow_mut(|cache| {
+        cache.lookup((game, left_first), |(stored, stored_left_first)| {
+            stored == game && *stored_left_first == left_first
+        })
+    });
+    match lookup {
+        Ok(stop) => stop,
+        Err(slot) => {
+            let result = stop();
+            STOPS.with_borrow_mut(|cache| cache.store(slot, (game.clone(), left_first), result));
+            result
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! Census of all short partizan games born by a given day.
+//!
+//! Games born by day `n + 1` are exactly `{A | B}` where `A` and `B` are antichains of games
+//! born by day `n`, as options of a canonical form never dominate each other. Number of games
+//! born by days `0, 1, 2, 3` is `1, 4, 22, 1474` respectively. Day 4 is far out of reach.
+
+use crate::short::partizan::canonical_form::{CanonicalForm, Moves};
+use std::collections::HashSet;
+
+/// All canonical forms born by a given day
+#[derive(Debug, Clone)]
+pub struct Census {
+    day: u32,
+    games: Vec<CanonicalForm>,
+}
+
+impl Census {
+    /// Enumerate all games born by day `day`
+    pub fn new(day: u32) -> Self {
+        let mut games = vec![CanonicalForm::new_integer(0)];
+        for _ in 0..day {
+            games = Self::next_day(&games);
+        }
+        Self { day, games }
+    }
+
+    /// Day of the census
+    pub const fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// All games born by the day of the census, in order of their birthdays
+    pub fn games(&self) -> &[CanonicalForm] {
+        &self.games
+    }
+
+    /// Number of games born by the day of the census
+    pub const fn len(&self) -> usize {
+        self.games.len()
+    }
+
+    /// Check if census is empty. Never true, as zero is born on day 0.
+    pub const fn is_empty(&self) -> bool {
+        self.games.is_empty()
+    }
+
+    /// Iterate over all games born by the day of the census
+    pub fn iter(&self) -> std::slice::Iter<'_, CanonicalForm> {
+        self.games.iter()
+    }
+
+    /// Iterate over games that satisfy `predicate`
+    pub fn filter<'a, P>(&'a self, mut predicate: P) -> impl Iterator<Item = &'a CanonicalForm>
+    where
+        P: FnMut(&CanonicalForm) -> bool + 'a,
+    {
+        self.games.iter().filter(move |game| predicate(game))
+    }
+
+    /// Count games that satisfy `predicate`
+    pub fn count<P>(&self, predicate: P) -> usize
+    where
+        P: FnMut(&CanonicalForm) -> bool,
+    {
+        self.filter(predicate).count()
+    }
+
+    /// Iterate over games that are born exactly on the day of the census
+    pub fn born_on_day(&self) -> impl Iterator<Item = &CanonicalForm> {
+        self.filter(|game| game.birthday() == self.day)
+    }
+
+    fn next_day(games: &[CanonicalForm]) -> Vec<CanonicalForm> {
+        let antichains = Self::antichains(games);
+
+        // Keep previous games first, so the result is sorted by birthday
+        let mut seen: HashSet<CanonicalForm> = games.iter().cloned().collect();
+        let mut result = games.to_vec();
+        for left in &antichains {
+            for right in &antichains {
+                let game = CanonicalForm::new_from_moves(Moves {
+                    left: left.clone(),
+                    right: right.clone(),
+                });
+                if seen.insert(game.clone()) {
+                    result.push(game);
+                }
+            }
+        }
+        result
+    }
+
+    /// All sets of pairwise incomparable games
+    fn antichains(games: &[CanonicalForm]) -> Vec<Vec<CanonicalForm>> {
+        fn go(
+            games: &[CanonicalForm],
+            start: usize,
+            current: &mut Vec<CanonicalForm>,
+            result: &mut Vec<Vec<CanonicalForm>>,
+        ) {
+            result.push(current.clone());
+            for idx in start..games.len() {
+                let candidate = &games[idx];
+                let incomparable = current.iter().all(|game| {
+                    !CanonicalForm::leq(game, candidate) && !CanonicalForm::leq(candidate, game)
+                });
+                if incomparable {
+                    current.push(candidate.clone());
+                    go(games, idx + 1, current, result);
+                    current.pop();
+                }
+            }
+        }
+
+        let mut result = Vec::new();
+        go(games, 0, &mut Vec::new(), &mut result);
+        result
+    }
+}
+
+impl<'a> IntoIterator for &'a Census {
+    type Item = &'a CanonicalForm;
+    type IntoIter = std::slice::Iter<'a, CanonicalForm>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for Census {
+    type Item = CanonicalForm;
+    type IntoIter = std::vec::IntoIter<CanonicalForm>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.games.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_counts() {
+        assert_eq!(Census::new(0).len(), 1);
+        assert_eq!(Census::new(1).len(), 4);
+        assert_eq!(Census::new(2).len(), 22);
+        assert_eq!(Census::new(3).len(), 1474);
+    }
+
+    #[test]
+    fn filtering_works() {
+        let census = Census::new(2);
+        assert_eq!(census.born_on_day().count(), 18);
+        assert_eq!(census.count(CanonicalForm::is_number), 7);
+        assert_eq!(census.count(CanonicalForm::is_infinitesimal), 7);
+    }
+}
@@ -0,0 +1,319 @@
+//! Interoperability with [CGSuite](https://www.cgsuite.org/)
+//!
+//! Values are exported as cgsuite expressions, e.g. `^^*` or `{2|{1|0}}`, and values printed by
+//! cgsuite, including its slash notation `{2||1|0}`, are parsed back into [`CanonicalForm`].
+//! Positions are exported as their full game trees, so cgsuite computes their canonical forms
+//! independently.
+//!
+//! # Examples
+//!
+//! ```
+//! use cgt::short::partizan::{canonical_form::CanonicalForm, cgsuite};
+//! use std::str::FromStr;
+//!
+//! let game = CanonicalForm::from_str("{2|{1|0}}").unwrap();
+//! assert_eq!(cgsuite::from_cgsuite("{2||1|0}").unwrap(), game);
+//! assert_eq!(cgsuite::to_cgsuite(&CanonicalForm::from_str("^2*").unwrap()), "^^*");
+//! ```
+
+use crate::{
+    error::Error,
+    nom_utils::lexeme,
+    numeric::{dyadic_rational_number::DyadicRationalNumber, nimber::Nimber},
+    short::partizan::{
+        canonical_form::{CanonicalForm, Moves, Nus},
+        partizan_game::PartizanGame,
+    },
+};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, u32},
+    combinator::opt,
+    multi::{many1_count, separated_list0},
+    IResult,
+};
+use std::fmt::Write;
+
+/// Write game as a cgsuite expression
+pub fn to_cgsuite(game: &CanonicalForm) -> String {
+    let mut buf = String::new();
+    write_value(&mut buf, game);
+    buf
+}
+
+fn write_value(buf: &mut String, game: &CanonicalForm) {
+    if let Some(nus) = game.to_nus() {
+        write_nus(buf, &nus);
+    } else {
+        let moves = game.to_moves();
+        write_options(buf, &moves.left, &moves.right, write_value);
+    }
+}
+
+fn write_nus(buf: &mut String, nus: &Nus) {
+    if nus.number() != DyadicRationalNumber::from(0)
+        || (nus.up_multiple() == 0 && nus.nimber() == Nimber::new(0))
+    {
+        write!(buf, "{}", nus.number()).unwrap();
+    }
+
+    let arrow = if nus.up_multiple() > 0 { '^' } else { 'v' };
+    match nus.up_multiple().unsigned_abs() {
+        0 => {}
+        1 => buf.push(arrow),
+        2 => {
+            buf.push(arrow);
+            buf.push(arrow);
+        }
+        multiple => write!(buf, "{arrow}{multiple}").unwrap(),
+    }
+
+    if nus.nimber() != Nimber::new(0) {
+        write!(buf, "{}", nus.nimber()).unwrap();
+    }
+}
+
+fn write_options<T>(buf: &mut String, left: &[T], right: &[T], write: fn(&mut String, &T)) {
+    buf.push('{');
+    for (idx, option) in left.iter().enumerate() {
+        if idx != 0 {
+            buf.push(',');
+        }
+        write(buf, option);
+    }
+    buf.push('|');
+    for (idx, option) in right.iter().enumerate() {
+        if idx != 0 {
+            buf.push(',');
+        }
+        write(buf, option);
+    }
+    buf.push('}');
+}
+
+/// Write position as a cgsuite expression of its full game tree
+///
+/// Game tree grows exponentially with the size of the position, so this is feasible only for
+/// small positions.
+pub fn position_to_cgsuite<G>(position: &G) -> String
+where
+    G: PartizanGame,
+{
+    let mut buf = String::new();
+    write_position(&mut buf, position);
+    buf
+}
+
+fn write_position<G>(buf: &mut String, position: &G)
+where
+    G: PartizanGame,
+{
+    write_options(
+        buf,
+        &position.left_moves(),
+        &position.right_moves(),
+        write_position,
+    );
+}
+
+/// Parse value in the cgsuite notation
+///
+/// # Errors
+/// - Input is not a valid cgsuite value
+pub fn from_cgsuite(input: &str) -> Result<CanonicalForm, Error> {
+    match parse_value(input) {
+        Ok(("", game)) => Ok(game),
+        Ok((rest, _)) => Err(Error::Syntax(format!("unexpected input '{rest}'"))),
+        Err(_) => Err(Error::Syntax(format!("invalid CGSuite value '{input}'"))),
+    }
+}
+
+fn parse_value(input: &str) -> IResult<&str, CanonicalForm> {
+    alt((parse_switch, parse_braces, parse_nus))(input)
+}
+
+/// `x±G`, where number `x` is optional
+fn parse_switch(input: &str) -> IResult<&str, CanonicalForm> {
+    let (input, number) = opt(lexeme(DyadicRationalNumber::parse))(input)?;
+    let (input, _) = lexeme(alt((tag("±"), tag("+-"))))(input)?;
+    let (input, game) = parse_value(input)?;
+    let switch = CanonicalForm::new_from_moves(Moves {
+        left: vec![game.clone()],
+        right: vec![-game],
+    });
+    let number = CanonicalForm::new_dyadic(number.unwrap_or_else(|| DyadicRationalNumber::from(0)));
+    Ok((input, number + switch))
+}
+
+/// Number, ups written as `^^` or `^3` (and downs with `v`) and nimber, e.g. `1/2^^*3`
+fn parse_nus(input: &str) -> IResult<&str, CanonicalForm> {
+    let full_input = input;
+    let (input, number) = opt(lexeme(DyadicRationalNumber::parse))(input)?;
+    let (input, up_multiple) = opt(alt((
+        |input| parse_arrows(input, '^'),
+        |input| parse_arrows(input, 'v').map(|(input, multiple)| (input, -multiple)),
+    )))(input)?;
+    let (input, nimber) = opt(|input| {
+        let (input, _) = lexeme(char('*'))(input)?;
+        let (input, nimber) = opt(lexeme(u32))(input)?;
+        Ok((input, nimber.unwrap_or(1)))
+    })(input)?;
+
+    if number.is_none() && up_multiple.is_none() && nimber.is_none() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            full_input,
+            nom::error::ErrorKind::Fail,
+        )));
+    }
+
+    let nus = Nus::new(
+        number.unwrap_or_else(|| DyadicRationalNumber::from(0)),
+        up_multiple.unwrap_or(0),
+        Nimber::new(nimber.unwrap_or(0)),
+    );
+    Ok((input, CanonicalForm::new_nus(nus)))
+}
+
+/// Repeated arrows, or a single arrow followed by the multiple
+fn parse_arrows(input: &str, arrow: char) -> IResult<&str, i32> {
+    let (input, count) = lexeme(many1_count(char(arrow)))(input)?;
+    if count == 1 {
+        let (input, multiple) = opt(lexeme(u32))(input)?;
+        Ok((input, multiple.unwrap_or(1) as i32))
+    } else {
+        Ok((input, count as i32))
+    }
+}
+
+/// Options in braces, separated by runs of bars in the slash notation
+fn parse_braces(input: &str) -> IResult<&str, CanonicalForm> {
+    let full_input = input;
+    let (mut input, _) = lexeme(char('{'))(input)?;
+    let mut segments = Vec::new();
+    let mut bars = Vec::new();
+    loop {
+        let (rest, options) = separated_list0(lexeme(char(',')), parse_value)(input)?;
+        segments.push(options);
+        if let Ok((rest, count)) =
+            lexeme(many1_count(char::<_, nom::error::Error<&str>>('|')))(rest)
+        {
+            bars.push(count);
+            input = rest;
+        } else {
+            input = rest;
+            break;
+        }
+    }
+    let (input, _) = lexeme(char('}'))(input)?;
+
+    split_slashes(&segments, &bars).map_or_else(
+        || {
+            Err(nom::Err::Error(nom::error::Error::new(
+                full_input,
+                nom::error::ErrorKind::Verify,
+            )))
+        },
+        |game| Ok((input, game)),
+    )
+}
+
+/// Build game from option lists separated by bars, splitting at the longest run of bars.
+/// Returns `None` if there are no bars or the longest run is not unique.
+fn split_slashes(segments: &[Vec<CanonicalForm>], bars: &[usize]) -> Option<CanonicalForm> {
+    let longest = *bars.iter().max()?;
+    let split = bars.iter().position(|&count| count == longest)?;
+    if bars.iter().filter(|&&count| count == longest).count() != 1 {
+        return None;
+    }
+
+    let side = |segments: &[Vec<CanonicalForm>], bars: &[usize]| {
+        if bars.is_empty() {
+            Some(segments[0].clone())
+        } else {
+            split_slashes(segments, bars).map(|game| vec![game])
+        }
+    };
+    Some(CanonicalForm::new_from_moves(Moves {
+        left: side(&segments[..=split], &bars[..split])?,
+        right: side(&segments[split + 1..], &bars[split + 1..])?,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::{
+        games::domineering::Domineering, transposition_table::ParallelTranspositionTable,
+    };
+    use std::str::FromStr;
+
+    macro_rules! test_export {
+        ($game:expr, $cgsuite:expr) => {
+            assert_eq!(
+                to_cgsuite(&CanonicalForm::from_str($game).unwrap()),
+                $cgsuite
+            );
+        };
+    }
+
+    macro_rules! test_import {
+        ($cgsuite:expr, $game:expr) => {
+            assert_eq!(
+                from_cgsuite($cgsuite).unwrap(),
+                CanonicalForm::from_str($game).unwrap()
+            );
+        };
+    }
+
+    #[test]
+    fn exports_values() {
+        test_export!("0", "0");
+        test_export!("-3/2", "-3/2");
+        test_export!("^", "^");
+        test_export!("v2*", "vv*");
+        test_export!("1/2^3*2", "1/2^3*2");
+        test_export!("{2|{1|0}}", "{2|{1|0}}");
+        test_export!("{1,1*|-1}", "{1,1*|-1}");
+    }
+
+    #[test]
+    fn imports_values() {
+        test_import!("^^*", "^2*");
+        test_import!("vvv", "v3");
+        test_import!("+-1", "{1|-1}");
+        test_import!("3±1", "{4|2}");
+        test_import!("{2||1|0}", "{2|{1|0}}");
+        test_import!("{3|2||1}", "{{3|2}|1}");
+        test_import!("{4|||3||2|1}", "{4|{3|{2|1}}}");
+        test_import!("{ 0 , * | }", "{0,*|}");
+        assert!(from_cgsuite("{1|2|3}").is_err());
+        assert!(from_cgsuite("{1|2").is_err());
+    }
+
+    #[test]
+    fn roundtrip() {
+        for game in [
+            "^^*",
+            "{3|1}",
+            "{2|{1|0}}",
+            "{{3|2}|1}",
+            "{1,^|-1}",
+            "-1/4v*3",
+        ] {
+            let game = CanonicalForm::from_str(game).unwrap();
+            assert_eq!(from_cgsuite(&to_cgsuite(&game)).unwrap(), game);
+        }
+    }
+
+    #[test]
+    fn exports_positions() {
+        let position: Domineering = Domineering::from_str("..|..").unwrap();
+        let tree = position_to_cgsuite(&position);
+        assert_eq!(tree, "{{{|}|}|{|{|}}}");
+        assert_eq!(
+            from_cgsuite(&tree).unwrap(),
+            position.canonical_form(&ParallelTranspositionTable::new())
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Full game graph of a position, with transpositions merged.
+//!
+//! Every position reachable from the root appears exactly once, and moves to it from different
+//! positions point to the same node, so the graph is a DAG rather than a tree. Each node carries
+//! the canonical form of its position.
+
+use crate::short::partizan::{
+    canonical_form::CanonicalForm, partizan_game::PartizanGame,
+    transposition_table::TranspositionTable,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{Display, Write},
+};
+
+/// Single position in a [`GameDag`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameDagNode<G> {
+    /// Position of the game
+    pub position: G,
+
+    /// Canonical form of the position
+    pub canonical_form: CanonicalForm,
+
+    /// Indices of nodes reachable with a Left move
+    pub left: Vec<usize>,
+
+    /// Indices of nodes reachable with a Right move
+    pub right: Vec<usize>,
+}
+
+/// Game graph of a position, see [module level documentation](self)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameDag<G> {
+    nodes: Vec<GameDagNode<G>>,
+}
+
+impl<G> GameDag<G>
+where
+    G: PartizanGame,
+{
+    /// Index of the starting position
+    pub const ROOT: usize = 0;
+
+    /// Expand position into its full game graph. Nodes are numbered in breadth first order, so
+    /// the root is [`Self::ROOT`] and options are listed in the order of
+    /// [`PartizanGame::left_moves`] and [`PartizanGame::right_moves`].
+    pub fn from_position<TT>(position: &G, transposition_table: &TT) -> Self
+    where
+        TT: TranspositionTable<G> + Sync,
+    {
+        let mut indices: HashMap<G, usize> = HashMap::new();
+        let mut positions = vec![position.clone()];
+        let mut edges: Vec<(Vec<usize>, Vec<usize>)> = Vec::new();
+        indices.insert(position.clone(), Self::ROOT);
+
+        let mut queue = VecDeque::from([Self::ROOT]);
+        while let Some(idx) = queue.pop_front() {
+            let left_moves = positions[idx].left_moves();
+            let right_moves = positions[idx].right_moves();
+            let mut intern = |option: G| {
+                *indices.entry(option.clone()).or_insert_with(|| {
+                    positions.push(option);
+                    queue.push_back(positions.len() - 1);
+                    positions.len() - 1
+                })
+            };
+            let left = left_moves.into_iter().map(&mut intern).collect();
+            let right = right_moves.into_iter().map(&mut intern).collect();
+            edges.push((left, right));
+        }
+
+        let nodes = positions
+            .into_iter()
+            .zip(edges)
+            .map(|(position, (left, right))| GameDagNode {
+                canonical_form: position.canonical_form(transposition_table),
+                position,
+                left,
+                right,
+            })
+            .collect();
+        Self { nodes }
+    }
+
+    /// Get all nodes
+    pub fn nodes(&self) -> &[GameDagNode<G>] {
+        &self.nodes
+    }
+
+    /// Get node at given index
+    pub fn node(&self, idx: usize) -> Option<&GameDagNode<G>> {
+        self.nodes.get(idx)
+    }
+
+    /// Get the starting position
+    #[allow(clippy::missing_panics_doc)]
+    pub fn root(&self) -> &GameDagNode<G> {
+        self.nodes
+            .first()
+            .expect("unreachable: root is always present")
+    }
+
+    /// Number of distinct positions
+    pub const fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Check if graph has no positions. Always `false`, as the root is always present.
+    pub const fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Number of moves of both players
+    pub fn edge_count(&self) -> usize {
+        self.nodes
+            .iter()
+            .map(|node| node.left.len() + node.right.len())
+            .sum()
+    }
+
+    /// Render to a [graphviz](https://graphviz.org/) format, that can be later rendered to an
+    /// image with external engine. Left moves are blue, Right moves are red.
+    pub fn to_graphviz(&self) -> String
+    where
+        G: Display,
+    {
+        let mut buf = String::new();
+
+        write!(buf, "digraph G {{").unwrap();
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let label = format!("{}\n{}", node.position, node.canonical_form)
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            write!(buf, "{idx} [label=\"{label}\", shape=box];").unwrap();
+        }
+        for (idx, node) in self.nodes.iter().enumerate() {
+            for option in &node.left {
+                write!(buf, "{idx} -> {option} [color=blue];").unwrap();
+            }
+            for option in &node.right {
+                write!(buf, "{idx} -> {option} [color=red];").unwrap();
+            }
+        }
+        write!(buf, "}}").unwrap();
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        grid::small_bit_grid::SmallBitGrid,
+        short::partizan::{
+            games::domineering::Domineering, transposition_table::ParallelTranspositionTable,
+        },
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn transpositions_are_merged() {
+        let position = Domineering::new(SmallBitGrid::from_str("..|..").unwrap());
+        let dag = GameDag::from_position(&position, &ParallelTranspositionTable::new());
+
+        // Left and Right options are different, but both of them reach the same final position
+        assert_eq!(dag.len(), 4);
+        assert_eq!(dag.edge_count(), 4);
+        assert_eq!(dag.root().canonical_form.to_string(), "{1|-1}");
+        assert_eq!(
+            dag.node(dag.root().left[0]).unwrap().position.to_string(),
+            ".|."
+        );
+        assert_eq!(
+            dag.node(dag.root().right[0]).unwrap().position.to_string(),
+            ".."
+        );
+
+        let end = dag
+            .nodes()
+            .iter()
+            .position(|node| node.left.is_empty() && node.right.is_empty())
+            .unwrap();
+        assert!(dag
+            .nodes()
+            .iter()
+            .filter(|node| node.position != dag.root().position)
+            .all(|node| node.left.iter().chain(&node.right).all(|o| *o == end)));
+    }
+
+    #[test]
+    fn graphviz() {
+        let position = Domineering::new(SmallBitGrid::from_str(".|.").unwrap());
+        let dag = GameDag::from_position(&position, &ParallelTranspositionTable::new());
+        assert_eq!(
+            dag.to_graphviz(),
+            "digraph G {0 [label=\".|.\\n1\", shape=box];1 [label=\"\\n0\", shape=box];0 -> 1 [color=blue];}"
+        );
+    }
+}
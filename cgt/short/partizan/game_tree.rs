@@ -0,0 +1,232 @@
+//! Arbitrary game trees, without any simplification.
+//!
+//! Unlike [`CanonicalForm`], game tree keeps all options exactly as given, including dominated
+//! and reversible ones, so operations like sums and negation preserve the structure of the game.
+//! Game tree can be converted to canonical form at any moment.
+
+use crate::{
+    display,
+    nom_utils::{impl_from_str_via_nom, lexeme},
+    short::partizan::{
+        canonical_form::{CanonicalForm, Moves},
+        partizan_game::PartizanGame,
+    },
+};
+use auto_ops::impl_op_ex;
+use nom::{branch::alt, bytes::complete::tag, character::complete::char, multi::separated_list0};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+/// Game tree of a short partizan game
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameTree {
+    left: Vec<Self>,
+    right: Vec<Self>,
+}
+
+impl GameTree {
+    /// Construct new game from Left and Right options. Options are kept in the given order.
+    pub const fn new(left: Vec<Self>, right: Vec<Self>) -> Self {
+        Self { left, right }
+    }
+
+    /// Game without any moves
+    pub const fn zero() -> Self {
+        Self {
+            left: Vec::new(),
+            right: Vec::new(),
+        }
+    }
+
+    /// Expand canonical form into its full game tree
+    pub fn from_canonical_form(canonical_form: &CanonicalForm) -> Self {
+        let moves = canonical_form.to_moves();
+        Self {
+            left: moves.left.iter().map(Self::from_canonical_form).collect(),
+            right: moves.right.iter().map(Self::from_canonical_form).collect(),
+        }
+    }
+
+    /// Left options
+    pub fn left_options(&self) -> &[Self] {
+        &self.left
+    }
+
+    /// Right options
+    pub fn right_options(&self) -> &[Self] {
+        &self.right
+    }
+
+    /// Height of the game tree
+    pub fn birthday(&self) -> u32 {
+        self.left
+            .iter()
+            .chain(self.right.iter())
+            .map(|option| option.birthday() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Number of nodes in the game tree, including the root
+    pub fn size(&self) -> usize {
+        1 + self
+            .left
+            .iter()
+            .chain(self.right.iter())
+            .map(Self::size)
+            .sum::<usize>()
+    }
+
+    /// Construct a disjunctive sum of two games
+    pub fn construct_sum(g: &Self, h: &Self) -> Self {
+        let mut left = Vec::with_capacity(g.left.len() + h.left.len());
+        let mut right = Vec::with_capacity(g.right.len() + h.right.len());
+        for g_left in &g.left {
+            left.push(Self::construct_sum(g_left, h));
+        }
+        for h_left in &h.left {
+            left.push(Self::construct_sum(g, h_left));
+        }
+        for g_right in &g.right {
+            right.push(Self::construct_sum(g_right, h));
+        }
+        for h_right in &h.right {
+            right.push(Self::construct_sum(g, h_right));
+        }
+        Self { left, right }
+    }
+
+    /// Construct a negative of the game, i.e. swap Left and Right options
+    #[must_use]
+    pub fn construct_negative(&self) -> Self {
+        Self {
+            left: self.right.iter().map(Self::construct_negative).collect(),
+            right: self.left.iter().map(Self::construct_negative).collect(),
+        }
+    }
+
+    /// Simplify game tree to its canonical form
+    pub fn to_canonical_form(&self) -> CanonicalForm {
+        self.to_canonical_form_cached(&mut HashMap::new())
+    }
+
+    fn to_canonical_form_cached(&self, cache: &mut HashMap<Self, CanonicalForm>) -> CanonicalForm {
+        if let Some(canonical_form) = cache.get(self) {
+            return canonical_form.clone();
+        }
+
+        let moves = Moves {
+            left: self
+                .left
+                .iter()
+                .map(|option| option.to_canonical_form_cached(cache))
+                .collect(),
+            right: self
+                .right
+                .iter()
+                .map(|option| option.to_canonical_form_cached(cache))
+                .collect(),
+        };
+        let canonical_form = CanonicalForm::new_from_moves(moves);
+        cache.insert(self.clone(), canonical_form.clone());
+        canonical_form
+    }
+
+    fn parse_list(input: &str) -> nom::IResult<&str, Vec<Self>> {
+        separated_list0(lexeme(tag(",")), Self::parse)(input)
+    }
+
+    /// Parse game tree using `{a,b,...|c,d,...}` notation. Options that are not in braces are
+    /// parsed as canonical forms and expanded into their game trees.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn parse(input: &str) -> nom::IResult<&str, Self> {
+        alt((
+            |input| {
+                let (input, _) = lexeme(char('{'))(input)?;
+                let (input, left) = Self::parse_list(input)?;
+                let (input, _) = lexeme(char('|'))(input)?;
+                let (input, right) = Self::parse_list(input)?;
+                let (input, _) = lexeme(char('}'))(input)?;
+                Ok((input, Self { left, right }))
+            },
+            |input| {
+                CanonicalForm::parse(input).map(|(input, canonical_form)| {
+                    (input, Self::from_canonical_form(&canonical_form))
+                })
+            },
+        ))(input)
+    }
+}
+
+impl PartizanGame for GameTree {
+    fn left_moves(&self) -> Vec<Self> {
+        self.left.clone()
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.right.clone()
+    }
+}
+
+impl From<&CanonicalForm> for GameTree {
+    fn from(canonical_form: &CanonicalForm) -> Self {
+        Self::from_canonical_form(canonical_form)
+    }
+}
+
+impl_op_ex!(+|g: &GameTree, h: &GameTree| -> GameTree { GameTree::construct_sum(g, h) });
+impl_op_ex!(-|g: &GameTree| -> GameTree { g.construct_negative() });
+impl_op_ex!(-|g: &GameTree, h: &GameTree| -> GameTree {
+    GameTree::construct_sum(g, &h.construct_negative())
+});
+
+impl Display for GameTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.left.is_empty() && self.right.is_empty() {
+            return write!(f, "0");
+        }
+        display::braces(f, |f| {
+            display::commas(f, &self.left)?;
+            write!(f, "|")?;
+            display::commas(f, &self.right)
+        })
+    }
+}
+
+impl_from_str_via_nom!(GameTree);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn keeps_structure() {
+        let tree = GameTree::from_str("{0, {|}|{0|0}}").unwrap();
+        assert_eq!(tree.to_string(), "{0, 0|{0|0}}");
+        assert_eq!(tree.left_options().len(), 2);
+        assert_eq!(tree.to_canonical_form().to_string(), "^");
+
+        let one = GameTree::from_str("1").unwrap();
+        assert_eq!(one.to_string(), "{0|}");
+    }
+
+    #[test]
+    fn symbolic_operations() {
+        let star = GameTree::from_str("*").unwrap();
+        let sum = &star + &star;
+        assert_eq!(sum.to_string(), "{{0|0}, {0|0}|{0|0}, {0|0}}");
+        assert_eq!(sum.birthday(), 2);
+        assert_eq!(sum.size(), 13);
+        assert_eq!(sum.to_canonical_form(), CanonicalForm::new_integer(0));
+
+        let up = GameTree::from_str("^").unwrap();
+        assert_eq!((-&up).to_canonical_form().to_string(), "v");
+        assert_eq!(
+            (&up - &up).to_canonical_form(),
+            CanonicalForm::new_integer(0)
+        );
+    }
+}
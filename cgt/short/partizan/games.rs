@@ -1,8 +1,11 @@
 //! Partizan games under normal play i.e. the player that cannot move in their turn loses.
 
 pub mod amazons;
+pub mod col;
 pub mod domineering;
+pub mod domineering_table;
 pub mod fission;
 pub mod ski_jumps;
 pub mod snort;
 pub mod toads_and_frogs;
+pub mod vertex_coloring;
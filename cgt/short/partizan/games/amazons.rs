@@ -1,11 +1,30 @@
 //! Amazons game
+//!
+//! Played on a rectangular board of any size up to 255x255 with any number of amazons for each
+//! player. Each turn player moves one of their amazons like a chess queen and then shoots an
+//! arrow, also like a queen, from the new position. Arrows are represented as stones.
 
 use crate::{
-    grid::{decompositions, move_top_left, vec_grid::VecGrid, FiniteGrid, Grid},
-    short::partizan::partizan_game::PartizanGame,
+    drawing::svg::{self, ImmSvg, Svg},
+    genetic::{cross_grids, mutate_grid, Chromosome},
+    grid::{
+        decompositions, move_top_left, vec_grid::VecGrid, FiniteGrid, Grid, ParseGridError,
+        Symmetry,
+    },
+    short::partizan::{
+        partizan_game::PartizanGame,
+        position::NamedGame,
+        random_position::{sample_pieces, GridParameters, RandomPosition},
+        transposition_table::Symmetric,
+    },
 };
 use cgt_derive::Tile;
-use std::{fmt::Display, hash::Hash, str::FromStr};
+use rand::Rng;
+use std::{
+    fmt::{self, Display},
+    hash::Hash,
+    str::FromStr,
+};
 
 /// Tile in the game of Amazons
 #[derive(Debug, Hash, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Tile)]
@@ -51,14 +70,33 @@ where
     }
 }
 
+/// Error returned when Amazons position cannot be parsed or is not a valid starting position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum ParseAmazonsError {
+    /// Board could not be parsed
+    #[error(transparent)]
+    Grid(#[from] ParseGridError),
+
+    /// There are no Left amazons on the board
+    #[error("position has no Left amazons")]
+    NoLeftAmazons,
+
+    /// There are no Right amazons on the board
+    #[error("position has no Right amazons")]
+    NoRightAmazons,
+}
+
 impl<G> FromStr for Amazons<G>
 where
     G: Grid<Item = Tile> + FiniteGrid,
 {
-    type Err = ();
+    type Err = ParseGridError;
 
+    /// Parse any board, including followers where a player has no amazons left to move, so
+    /// every position can be parsed back from its [`Display`] output. Use
+    /// [`Amazons::parse_checked`] to accept only starting positions.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self::new(G::parse(s).ok_or(())?))
+        G::try_parse(s).map(Self::new)
     }
 }
 
@@ -73,6 +111,96 @@ const DIRECTIONS: [(i32, i32); 8] = [
     (-1, -1),
 ];
 
+/// Layout of a board packed into a single `u128`, one bit per tile
+///
+/// Every row has an extra padding column that is never empty, so queen moves stop at the edge
+/// of the board instead of wrapping to the next row, and whole rays can be found with shifts.
+#[derive(Debug, Clone, Copy)]
+struct Bitboard {
+    padded_width: u32,
+
+    /// Tiles on the edges of the board: top row, bottom row, left column and right column
+    edges: [u128; 4],
+}
+
+impl Bitboard {
+    /// Returns `None` if the board does not fit in 128 bits
+    fn new(width: u8, height: u8) -> Option<Self> {
+        let padded_width = u32::from(width) + 1;
+        if padded_width * u32::from(height) > u128::BITS || width == 0 || height == 0 {
+            return None;
+        }
+
+        let top_row = (1 << width) - 1;
+        let left_column =
+            (0..u32::from(height)).fold(0, |column, y| column | 1 << (y * padded_width));
+        Some(Self {
+            padded_width,
+            edges: [
+                top_row,
+                top_row << ((u32::from(height) - 1) * padded_width),
+                left_column,
+                left_column << (width - 1),
+            ],
+        })
+    }
+
+    /// Check if a row or column on the edge has only blocking tiles, so the grid can be
+    /// shrunk with [`move_top_left`]
+    #[inline]
+    fn has_blocked_edge(self, non_blocking: u128) -> bool {
+        self.edges.iter().any(|edge| edge & non_blocking == 0)
+    }
+
+    #[inline]
+    const fn bit(self, x: u8, y: u8) -> u128 {
+        1 << (y as u32 * self.padded_width + x as u32)
+    }
+
+    #[inline]
+    const fn coordinates(self, bit: u128) -> (u8, u8) {
+        let index = bit.trailing_zeros();
+        (
+            (index % self.padded_width) as u8,
+            (index / self.padded_width) as u8,
+        )
+    }
+
+    /// Tiles reachable from `from` like a chess queen, moving only through `empty` tiles
+    fn queen_moves(self, from: u128, empty: u128) -> u128 {
+        let width = self.padded_width as i32;
+        let mut moves = 0;
+        for step in [1, width - 1, width, width + 1] {
+            for step in [step, -step] {
+                let mut ray = shift(from, step) & empty;
+                while ray != 0 {
+                    moves |= ray;
+                    ray = shift(ray, step) & empty;
+                }
+            }
+        }
+        moves
+    }
+}
+
+#[inline]
+fn shift(mask: u128, by: i32) -> u128 {
+    if by >= 0 {
+        mask.checked_shl(by as u32).unwrap_or(0)
+    } else {
+        mask.checked_shr(by.unsigned_abs()).unwrap_or(0)
+    }
+}
+
+/// Iterate over set bits, each as a single bit mask
+fn bits(mut mask: u128) -> impl Iterator<Item = u128> {
+    std::iter::from_fn(move || {
+        let bit = mask & mask.wrapping_neg();
+        mask &= !bit;
+        (bit != 0).then_some(bit)
+    })
+}
+
 impl<G> Amazons<G>
 where
     G: Grid<Item = Tile> + FiniteGrid,
@@ -83,7 +211,213 @@ where
         Self { grid }
     }
 
+    /// Create new Amazons game from a grid, rejecting boards where a player has no amazons.
+    /// Such positions still appear as followers and decompositions, and can be created with
+    /// [`Self::new`].
+    ///
+    /// # Errors
+    /// - There are no Left or no Right amazons on the board
+    pub fn new_checked(grid: G) -> Result<Self, ParseAmazonsError> {
+        let position = Self::new(grid);
+        if position.amazons_count(Tile::Left) == 0 {
+            return Err(ParseAmazonsError::NoLeftAmazons);
+        }
+        if position.amazons_count(Tile::Right) == 0 {
+            return Err(ParseAmazonsError::NoRightAmazons);
+        }
+        Ok(position)
+    }
+
+    /// Parse the board like [`FromStr`], then check it with [`Self::new_checked`]
+    ///
+    /// # Errors
+    /// - Board could not be parsed
+    /// - There are no Left or no Right amazons on the board
+    pub fn parse_checked(s: &str) -> Result<Self, ParseAmazonsError> {
+        Self::new_checked(G::try_parse(s)?)
+    }
+
+    /// Get underlying grid
+    #[inline]
+    pub const fn grid(&self) -> &G {
+        &self.grid
+    }
+
+    /// Output position as LaTeX `TikZ` picture with 1x1 tiles, stones as gray tiles and amazons
+    /// as blue and red circles
+    pub fn to_tikz(&self) -> String {
+        self.to_tikz_with_scale(1.)
+    }
+
+    /// Like [`Self::to_tikz`] but allows to specify image scale. Scale must be positive
+    ///
+    /// # Panics
+    /// - `scale` is negative
+    pub fn to_tikz_with_scale(&self, scale: f32) -> String {
+        use std::fmt::Write;
+
+        assert!(scale >= 0., "Scale must be positive");
+
+        let mut buf = String::new();
+        write!(buf, "\\begin{{tikzpicture}}[scale={}] ", scale).unwrap();
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                let tikz_y = self.grid.height() - y - 1;
+                match self.grid.get(x, y) {
+                    Tile::Empty => {}
+                    Tile::Stone => {
+                        write!(
+                            buf,
+                            "\\fill[fill=gray] ({},{}) rectangle ({},{}); ",
+                            x,
+                            tikz_y,
+                            x + 1,
+                            tikz_y + 1,
+                        )
+                        .unwrap();
+                    }
+                    amazon @ (Tile::Left | Tile::Right) => {
+                        let color = if amazon == Tile::Left { "blue" } else { "red" };
+                        write!(
+                            buf,
+                            "\\filldraw[fill={}] ({}.5,{}.5) circle (0.3); ",
+                            color, x, tikz_y,
+                        )
+                        .unwrap();
+                    }
+                }
+            }
+        }
+        write!(
+            buf,
+            "\\draw[step=1cm,black] (0,0) grid ({}, {}); \\end{{tikzpicture}}",
+            self.grid.width(),
+            self.grid.height()
+        )
+        .unwrap();
+        buf
+    }
+
+    /// Render board to a [graphviz](https://graphviz.org/) format, with a node pinned at the
+    /// position of every tile and edges between adjacent tiles. Render with `neato` to keep the
+    /// positions.
+    pub fn to_graphviz(&self) -> String {
+        use std::fmt::Write;
+
+        let width = self.grid.width();
+        let height = self.grid.height();
+        let mut buf = String::new();
+        write!(
+            buf,
+            "graph G {{layout=neato;node [shape=square, style=filled, label=\"\"];"
+        )
+        .unwrap();
+        for y in 0..height {
+            for x in 0..width {
+                let (fill, shape) = match self.grid.get(x, y) {
+                    Tile::Empty => ("white", "square"),
+                    Tile::Stone => ("gray", "square"),
+                    Tile::Left => ("blue", "circle"),
+                    Tile::Right => ("red", "circle"),
+                };
+                write!(
+                    buf,
+                    "t{x}_{y} [pos=\"{x},{}!\", fillcolor={fill}, shape={shape}];",
+                    height - y - 1
+                )
+                .unwrap();
+            }
+        }
+        for y in 0..height {
+            for x in 0..width {
+                if x + 1 < width {
+                    write!(buf, "t{x}_{y} -- t{}_{y};", x + 1).unwrap();
+                }
+                if y + 1 < height {
+                    write!(buf, "t{x}_{y} -- t{x}_{};", y + 1).unwrap();
+                }
+            }
+        }
+        write!(buf, "}}").unwrap();
+        buf
+    }
+
+    /// Count number of amazons of given player
+    pub fn amazons_count(&self, amazon: Tile) -> usize {
+        let mut res = 0;
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                if self.grid.get(x, y) == amazon {
+                    res += 1;
+                }
+            }
+        }
+        res
+    }
+
     fn moves_for(&self, own_amazon: Tile) -> Vec<Self>
+    where
+        G: Clone + PartialEq,
+    {
+        Bitboard::new(self.grid.width(), self.grid.height()).map_or_else(
+            || self.moves_for_scan(own_amazon),
+            |bitboard| self.moves_for_bitboard(bitboard, own_amazon),
+        )
+    }
+
+    fn moves_for_bitboard(&self, bitboard: Bitboard, own_amazon: Tile) -> Vec<Self>
+    where
+        G: Clone,
+    {
+        let mut empty = 0;
+        let mut amazons = 0;
+        let mut non_blocking = 0;
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                let tile = self.grid.get(x, y);
+                if tile == Tile::Empty {
+                    empty |= bitboard.bit(x, y);
+                } else if tile == own_amazon {
+                    amazons |= bitboard.bit(x, y);
+                }
+                if tile.is_non_blocking() {
+                    non_blocking |= bitboard.bit(x, y);
+                }
+            }
+        }
+
+        let mut moves = Vec::new();
+        for amazon in bits(amazons) {
+            let (x, y) = bitboard.coordinates(amazon);
+            // Tile left by the amazon is empty when shooting the arrow
+            let empty_after_move = empty | amazon;
+            for new_amazon in bits(bitboard.queen_moves(amazon, empty)) {
+                let (new_amazon_x, new_amazon_y) = bitboard.coordinates(new_amazon);
+                let mut new_grid = self.grid.clone();
+                new_grid.set(x, y, Tile::Empty);
+                new_grid.set(new_amazon_x, new_amazon_y, own_amazon);
+
+                let arrows = bitboard.queen_moves(new_amazon, empty_after_move & !new_amazon);
+                for arrow in bits(arrows) {
+                    // Arrow is placed only for the time of copying the grid, so the grid after
+                    // amazon move is not cloned for every arrow
+                    let (arrow_x, arrow_y) = bitboard.coordinates(arrow);
+                    new_grid.set(arrow_x, arrow_y, Tile::Stone);
+                    if bitboard.has_blocked_edge(non_blocking & !arrow) {
+                        moves.push(Self::new(move_top_left(&new_grid, Tile::is_non_blocking)));
+                    } else {
+                        moves.push(Self::new(new_grid.clone()));
+                    }
+                    new_grid.set(arrow_x, arrow_y, Tile::Empty);
+                }
+            }
+        }
+
+        moves
+    }
+
+    /// Move generation scanning the grid tile by tile, used for boards too large for a bitboard
+    fn moves_for_scan(&self, own_amazon: Tile) -> Vec<Self>
     where
         G: Clone + PartialEq,
     {
@@ -140,6 +474,70 @@ where
     }
 }
 
+impl<G> Svg for Amazons<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid,
+{
+    fn to_svg<W>(&self, buf: &mut W) -> fmt::Result
+    where
+        W: fmt::Write,
+    {
+        // Chosen arbitrarily
+        let tile_size = 48;
+        let grid_width = 4;
+
+        let offset = grid_width / 2;
+        let svg_width = self.grid.width() as u32 * tile_size + grid_width;
+        let svg_height = self.grid.height() as u32 * tile_size + grid_width;
+
+        ImmSvg::new(buf, svg_width, svg_height, |buf| {
+            for y in 0..self.grid.height() {
+                for x in 0..self.grid.width() {
+                    let amazon_color = match self.grid.get(x, y) {
+                        Tile::Empty => continue,
+                        Tile::Stone => {
+                            ImmSvg::rect(
+                                buf,
+                                (x as u32 * tile_size + offset) as i32,
+                                (y as u32 * tile_size + offset) as i32,
+                                tile_size,
+                                tile_size,
+                                "gray",
+                            )?;
+                            continue;
+                        }
+                        Tile::Left => "blue",
+                        Tile::Right => "red",
+                    };
+                    let circle = svg::Circle {
+                        cx: (x as u32 * tile_size + offset + tile_size / 2) as i32,
+                        cy: (y as u32 * tile_size + offset + tile_size / 2) as i32,
+                        r: tile_size / 3,
+                        stroke: "black".to_owned(),
+                        stroke_width: 2,
+                        fill: amazon_color.to_owned(),
+                    };
+                    ImmSvg::circle(buf, &circle)?;
+                }
+            }
+
+            let grid = svg::Grid {
+                x1: 0,
+                y1: 0,
+                x2: svg_width as i32,
+                y2: svg_height as i32,
+                grid_width,
+                tile_size,
+            };
+            ImmSvg::grid(buf, &grid)
+        })
+    }
+}
+
+impl NamedGame for Amazons {
+    const NAME: &'static str = "amazons";
+}
+
 impl<G> PartizanGame for Amazons<G>
 where
     G: Grid<Item = Tile> + FiniteGrid + Clone + Hash + Send + Sync + Eq,
@@ -152,19 +550,95 @@ where
         self.moves_for(Tile::Right)
     }
 
+    /// Split the board into regions separated by stones, each with the amazons standing in it.
+    /// Regions without any amazon are always zero, so they are skipped.
     fn decompositions(&self) -> Vec<Self> {
         decompositions(&self.grid, Tile::is_non_blocking, Tile::Stone, &DIRECTIONS)
             .into_iter()
             .map(Self::new)
+            .filter(|region| {
+                region.amazons_count(Tile::Left) != 0 || region.amazons_count(Tile::Right) != 0
+            })
             .collect::<Vec<_>>()
     }
 }
 
+impl<G> Symmetric for Amazons<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid + Ord,
+{
+    /// Smallest position among all its rotations and reflections
+    fn canonical_symmetry(&self) -> Self {
+        Symmetry::ALL
+            .into_iter()
+            .map(|symmetry| Self::new(symmetry.apply(&self.grid)))
+            .min()
+            .expect("unreachable: there is at least one symmetry")
+    }
+}
+
+impl<G> RandomPosition for Amazons<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid,
+{
+    type Parameters = GridParameters;
+
+    /// Board of `width`x`height` tiles with `pieces` amazons of each player, and every other
+    /// tile having a stone with probability `density`
+    fn random_position<R>(parameters: &GridParameters, rng: &mut R) -> Option<Self>
+    where
+        R: Rng,
+    {
+        let width = parameters.width as usize;
+        let mut grid = G::filled(parameters.width, parameters.height, Tile::Empty)?;
+        for y in 0..parameters.height {
+            for x in 0..parameters.width {
+                if rng.gen_bool(parameters.density) {
+                    grid.set(x, y, Tile::Stone);
+                }
+            }
+        }
+        let amazons = sample_pieces(width * parameters.height as usize, parameters.pieces, rng)?;
+        for (i, tile) in amazons.into_iter().enumerate() {
+            let amazon = if i < parameters.pieces as usize {
+                Tile::Left
+            } else {
+                Tile::Right
+            };
+            grid.set((tile % width) as u8, (tile / width) as u8, amazon);
+        }
+        Some(Self::new(grid))
+    }
+}
+
+impl<G> Chromosome for Amazons<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid + Clone,
+{
+    fn mutate<R>(&mut self, mutation_rate: f32, rng: &mut R)
+    where
+        R: Rng,
+    {
+        let tiles = [Tile::Empty, Tile::Left, Tile::Right, Tile::Stone];
+        let mut grid = self.grid().clone();
+        mutate_grid(&mut grid, &tiles, mutation_rate, rng);
+        *self = Self::new(grid);
+    }
+
+    fn crossover<R>(&self, other: &Self, rng: &mut R) -> Self
+    where
+        R: Rng,
+    {
+        Self::new(cross_grids(self.grid(), other.grid(), rng))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::short::partizan::{
-        canonical_form::CanonicalForm, transposition_table::ParallelTranspositionTable,
+        canonical_form::{CanonicalForm, Moves},
+        transposition_table::{ParallelTranspositionTable, SymmetricTranspositionTable},
     };
     use std::str::FromStr;
 
@@ -184,9 +658,270 @@ mod tests {
         }};
     }
 
+    #[test]
+    fn multiple_amazons() {
+        let pos: Amazons = amazons!("xx|..");
+        assert_eq!(pos.amazons_count(Tile::Left), 2);
+        assert_eq!(pos.amazons_count(Tile::Right), 0);
+        assert_eq!(pos.left_moves().len(), 8);
+        assert_eq!(pos.right_moves().len(), 0);
+
+        test_canonical_form!("x.o", "*");
+        test_canonical_form!("x.o|o.x", "0");
+    }
+
+    #[test]
+    fn invalid_positions() {
+        assert_eq!(
+            Amazons::<VecGrid<Tile>>::parse_checked("xx|.."),
+            Err(ParseAmazonsError::NoRightAmazons)
+        );
+        assert_eq!(
+            Amazons::<VecGrid<Tile>>::parse_checked("..|o#"),
+            Err(ParseAmazonsError::NoLeftAmazons)
+        );
+        assert!(matches!(
+            Amazons::<VecGrid<Tile>>::parse_checked("x.|o"),
+            Err(ParseAmazonsError::Grid(
+                ParseGridError::NotRectangular { .. }
+            ))
+        ));
+        assert_eq!(
+            Amazons::<VecGrid<Tile>>::parse_checked("x.|.o"),
+            Ok(amazons!("x.|.o"))
+        );
+
+        // Unchecked parser accepts followers where a player has no amazons
+        assert!(Amazons::<VecGrid<Tile>>::from_str("xx|..").is_ok());
+        assert!(matches!(
+            Amazons::<VecGrid<Tile>>::from_str("x.|o"),
+            Err(ParseGridError::NotRectangular { .. })
+        ));
+        assert!(matches!(
+            Amazons::<VecGrid<Tile>>::from_str("x.|oq"),
+            Err(ParseGridError::InvalidTile('q'))
+        ));
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let pos: Amazons = amazons!("x..#|....|.#.o");
+        for follower in pos
+            .left_moves()
+            .into_iter()
+            .chain(pos.right_moves())
+            .chain(pos.decompositions())
+        {
+            let parsed: Amazons = amazons!(&follower.to_string());
+            assert_eq!(parsed, follower);
+        }
+    }
+
+    #[test]
+    fn known_values() {
+        // Best move is to run to the middle and wall off own part of the board:
+        // x..o -> .x#o = 1
+        test_canonical_form!("x..o", "{1|-1}");
+        test_canonical_form!("x...o", "{2|-2}");
+        test_canonical_form!("xx|..|oo", "{1|-1}");
+        test_canonical_form!("x.o|o.x|...", "{1*|-1*}");
+        test_canonical_form!(".x.|...|.o.", "*");
+        test_canonical_form!("xo.|...", "{1/4|-1, {0|-2}}");
+        test_canonical_form!("x..|..o", "{1, {2|0}|-1, {0|-2}}");
+        test_canonical_form!("x..|.#.|..o", "0");
+
+        // 4x4 and 5x4 boards, also checked against evaluation straight from the rules in
+        // `reference_values`
+        test_canonical_form!("x.##|#..#|#..#|##.o", "{5|-5}");
+        test_canonical_form!("x..#|##.#|#.##|#..o", "0");
+        test_canonical_form!(
+            "x#..|.#.#|#.#.|..#o",
+            "{{2, {5|2, {4|1}}|2}|{-2|-2, {-2, {-1|-4}|-5}}}"
+        );
+        test_canonical_form!("x.##|#..#|##.#|#..#|##.o", "{{5|3}|0, {1|-3, {-2|-4}}}");
+    }
+
+    /// Canonical form computed directly from the rules, without bitboards, decompositions or
+    /// symmetries
+    fn reference_value(
+        board: &mut Vec<Vec<Tile>>,
+        cache: &mut std::collections::HashMap<Vec<Vec<Tile>>, CanonicalForm>,
+    ) -> CanonicalForm {
+        if let Some(value) = cache.get(board) {
+            return value.clone();
+        }
+
+        let height = board.len() as i32;
+        let width = board[0].len() as i32;
+        let ray = |board: &Vec<Vec<Tile>>, (mut x, mut y): (i32, i32), (dx, dy): (i32, i32)| {
+            let mut tiles = Vec::new();
+            loop {
+                x += dx;
+                y += dy;
+                if !(0..width).contains(&x)
+                    || !(0..height).contains(&y)
+                    || board[y as usize][x as usize] != Tile::Empty
+                {
+                    return tiles;
+                }
+                tiles.push((x, y));
+            }
+        };
+
+        let mut moves = [Vec::new(), Vec::new()];
+        for (amazon, moves) in [Tile::Left, Tile::Right].into_iter().zip(&mut moves) {
+            for y in 0..height {
+                for x in 0..width {
+                    if board[y as usize][x as usize] != amazon {
+                        continue;
+                    }
+                    board[y as usize][x as usize] = Tile::Empty;
+                    for direction in DIRECTIONS {
+                        for (to_x, to_y) in ray(board, (x, y), direction) {
+                            board[to_y as usize][to_x as usize] = amazon;
+                            for arrow_direction in DIRECTIONS {
+                                for (arrow_x, arrow_y) in ray(board, (to_x, to_y), arrow_direction)
+                                {
+                                    board[arrow_y as usize][arrow_x as usize] = Tile::Stone;
+                                    moves.push(reference_value(board, cache));
+                                    board[arrow_y as usize][arrow_x as usize] = Tile::Empty;
+                                }
+                            }
+                            board[to_y as usize][to_x as usize] = Tile::Empty;
+                        }
+                    }
+                    board[y as usize][x as usize] = amazon;
+                }
+            }
+        }
+
+        let [left, right] = moves;
+        let value = CanonicalForm::new_from_moves(Moves { left, right });
+        cache.insert(board.clone(), value.clone());
+        value
+    }
+
+    fn assert_reference_value(input: &str) {
+        let pos: Amazons = amazons!(input);
+        let mut board = (0..pos.grid().height())
+            .map(|y| {
+                (0..pos.grid().width())
+                    .map(|x| pos.grid().get(x, y))
+                    .collect()
+            })
+            .collect();
+        let expected = reference_value(&mut board, &mut std::collections::HashMap::new());
+        assert_eq!(
+            pos.canonical_form(&ParallelTranspositionTable::new()),
+            expected,
+            "{input}"
+        );
+    }
+
+    #[test]
+    fn reference_values() {
+        for input in [
+            "x.##|#..#|#..#|##.o",
+            "x..#|##.#|#.##|#..o",
+            "x#..|.#.#|#.#.|..#o",
+            "x.##|#..#|##.#|#..#|##.o",
+            "x..o|....|####|####",
+            "x...|o...|####|####|####",
+        ] {
+            assert_reference_value(input);
+        }
+    }
+
+    #[test]
+    #[ignore = "takes about a minute in release mode"]
+    fn reference_values_large() {
+        for input in [
+            "x..#|#..#|#..#|#..o",
+            "x.#.|....|.#..|o.##",
+            "x..##|#...#|#...#|##..o",
+            "x.#.o|.#.#.|#.#.#|o.#.x",
+        ] {
+            assert_reference_value(input);
+        }
+    }
+
+    #[test]
+    fn bitboard_moves_match_scan() {
+        for input in [
+            "x..#|....|.#.o",
+            "x.o|o.x",
+            "#x.|.o#|..x",
+            &format!("x{}o", ".".repeat(129)),
+        ] {
+            let pos: Amazons = amazons!(input);
+            for amazon in [Tile::Left, Tile::Right] {
+                let mut moves = pos.moves_for(amazon);
+                let mut expected = pos.moves_for_scan(amazon);
+                moves.sort();
+                expected.sort();
+                assert_eq!(moves, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn decompositions() {
+        let pos: Amazons = amazons!("x.#..|..#o.|###..|..x#.");
+        // Bottom right region is connected through the diagonal between `x` and `.`
+        let regions = pos.decompositions();
+        assert_eq!(
+            regions.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["x.|..", "###..|###o.|###..|..x#."]
+        );
+
+        let tt = ParallelTranspositionTable::new();
+        let sum = regions
+            .iter()
+            .fold(CanonicalForm::new_integer(0), |acc, region| {
+                acc + region.canonical_form(&tt)
+            });
+        assert_eq!(pos.canonical_form(&tt), sum);
+
+        let pos: Amazons = amazons!("..#|.#.");
+        assert!(pos.decompositions().is_empty());
+        assert_eq!(pos.canonical_form(&tt), CanonicalForm::new_integer(0));
+    }
+
     #[test]
     fn canonical_form() {
         // Confirmed with cgsuite
         test_canonical_form!("x..#|....|.#.o", "{{6|{3|1, {3|0, {1/2|0}}}}, {6|{4*|-3, {3, {3|0, {1/2|0}}|-4}}}|-3, {0, {0|-2}, {1|-3}|-5}, {0, {0, *|0, {0, {1/2, {1|0}|v}|v}}|-5}, {{2, {2|0}|0, {0, {2|0, {2|0}}|0}}, {2, {3|0}|0, {0, {0, ^*|0}|-1}}, {{2|0}, {2|{1|1/4}, {2|0}}|v*, {1/2|{{0|-1}, {*|-1}|-1}}, {{0, ^*|0}|-1}}, {{3|0}, {3|1, {2|0}}, {3, {3|1}|1, {1|0, *}}|-1/16, {0|-1}, {*|-1}}|-5, {v, v*, {0, {0, ^*|0}|-1}|-5}, {{1/2|{-1/4, {0|-1}, {*|-1}|-1}}, {{1|1/4}|{-1/4|-1}}, {{1|{1|0}, {1|*}}|-1/2}|-5}}}");
     }
+
+    #[test]
+    fn symmetric_transposition_table() {
+        let pos: Amazons = amazons!("x..|...|..o");
+        let rotated: Amazons = amazons!("..x|...|o..");
+        assert_eq!(pos.canonical_symmetry(), rotated.canonical_symmetry());
+
+        let tt = SymmetricTranspositionTable::<Amazons>::default();
+        assert_eq!(
+            pos.canonical_form(&tt),
+            pos.canonical_form(&ParallelTranspositionTable::new())
+        );
+    }
+
+    #[test]
+    fn tikz_works() {
+        let pos: Amazons = amazons!("x.|#o");
+        assert_eq!(
+            pos.to_tikz(),
+            r"\begin{tikzpicture}[scale=1] \filldraw[fill=blue] (0.5,1.5) circle (0.3); \fill[fill=gray] (0,0) rectangle (1,1); \filldraw[fill=red] (1.5,0.5) circle (0.3); \draw[step=1cm,black] (0,0) grid (2, 2); \end{tikzpicture}"
+        );
+    }
+
+    #[test]
+    fn graphviz_board() {
+        let position: Amazons = amazons!("x.|#o");
+        let graphviz = position.to_graphviz();
+        assert!(graphviz.starts_with("graph G {"));
+        assert!(graphviz.contains("t0_0 [pos=\"0,1!\", fillcolor=blue, shape=circle];"));
+        assert!(graphviz.contains("t0_1 [pos=\"0,0!\", fillcolor=gray, shape=square];"));
+        assert_eq!(graphviz.matches(" -- ").count(), 4);
+    }
 }
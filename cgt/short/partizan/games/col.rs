@@ -0,0 +1,76 @@
+//! Col is played on undirected graph. In each turn Left colors a vertex blue and Right colors
+//! a vertex red. Players can only choose a vertex that is not adjacent to a vertex in their own
+//! color.
+//!
+//! Col is a [vertex coloring](super::vertex_coloring) game with [`ColRules`].
+
+use crate::{
+    graph::adjacency_matrix::undirected::UndirectedGraph,
+    short::partizan::{games::vertex_coloring::Rules, position::NamedGame, simplification::Player},
+};
+
+pub use crate::short::partizan::games::vertex_coloring::VertexColoring;
+
+/// Rules of Col - players cannot color vertices adjacent to their own vertices
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColRules;
+
+impl Rules for ColRules {
+    fn allows(self, player: Player, neighbor: Player) -> bool {
+        player != neighbor
+    }
+}
+
+/// Position of a [col](self) game
+pub type Col<G = UndirectedGraph> = VertexColoring<ColRules, G>;
+
+impl NamedGame for Col {
+    const NAME: &'static str = "col";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::generators,
+        short::partizan::{
+            partizan_game::PartizanGame, transposition_table::ParallelTranspositionTable,
+        },
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn small_values() {
+        let transposition_table = ParallelTranspositionTable::new();
+        let value = |position: Col| position.canonical_form(&transposition_table).to_string();
+
+        assert_eq!(value(Col::new(generators::path(1))), "*");
+        assert_eq!(value(Col::new(generators::path(2))), "0");
+        assert_eq!(value(Col::from_str("L").unwrap()), "1");
+        assert_eq!(value(Col::from_str(".R;0-1").unwrap()), "-1/2");
+    }
+
+    #[test]
+    fn values_are_numbers_or_number_stars() {
+        let transposition_table = ParallelTranspositionTable::new();
+        for size in 1..=7 {
+            let graphs: [UndirectedGraph; 4] = [
+                generators::path(size),
+                generators::cycle(size),
+                generators::star(size),
+                generators::complete(size),
+            ];
+            for graph in graphs {
+                // Col is cold, so all values are numbers or numbers plus star
+                let value = Col::new(graph).canonical_form(&transposition_table);
+                assert!(
+                    value
+                        .to_nus()
+                        .is_some_and(|nus| nus.up_multiple() == 0 && nus.nimber().value() <= 1),
+                    "{value}"
+                );
+            }
+        }
+    }
+}
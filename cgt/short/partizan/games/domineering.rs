@@ -1,14 +1,29 @@
 //! The game is played on a rectengular grid. Left places vertical dominoes, Right places
 //! horizontal dominoes.
+//!
+//! Boards with holes (irregular regions) are represented by marking missing cells as
+//! [`Tile::Taken`], e.g. `"..#|.#."`, as a missing cell is indistinguishable from one already
+//! covered by a domino.
 
 extern crate alloc;
 use crate::{
     drawing::svg::{self, ImmSvg, Svg},
-    grid::{decompositions, move_top_left, small_bit_grid::SmallBitGrid, FiniteGrid, Grid},
-    short::partizan::partizan_game::PartizanGame,
+    genetic::{cross_grids, mutate_grid, Chromosome},
+    grid::{
+        decompositions, move_top_left, small_bit_grid::SmallBitGrid, FiniteGrid, Grid,
+        ParseGridError, Symmetry,
+    },
+    short::partizan::{
+        partizan_game::PartizanGame,
+        position::NamedGame,
+        random_position::{GridParameters, RandomPosition},
+        transposition_table::Symmetric,
+    },
+    zobrist::{zobrist_key, ZobristHash, GLOBAL_INDEX},
 };
 use cgt_derive::Tile;
 use core::{fmt, hash::Hash};
+use rand::Rng;
 use std::{fmt::Display, str::FromStr};
 
 /// Tile on a Domineering grid
@@ -31,18 +46,89 @@ impl Tile {
     }
 }
 
+/// Shape of the board surface, i.e. which edges of the grid are glued together
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Topology {
+    /// Regular rectangular board without wrapping
+    #[default]
+    Plane,
+
+    /// Left and right edges are glued together, so horizontal dominoes can be placed across
+    /// the seam
+    Cylinder,
+
+    /// Both left and right, and top and bottom edges are glued together
+    Torus,
+}
+
+impl Topology {
+    /// Check if the first column is adjacent to the last one
+    #[inline]
+    pub const fn wraps_horizontally(self) -> bool {
+        matches!(self, Self::Cylinder | Self::Torus)
+    }
+
+    /// Check if the first row is adjacent to the last one
+    #[inline]
+    pub const fn wraps_vertically(self) -> bool {
+        matches!(self, Self::Torus)
+    }
+}
+
+impl Display for Topology {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Plane => write!(f, "plane"),
+            Self::Cylinder => write!(f, "cylinder"),
+            Self::Torus => write!(f, "torus"),
+        }
+    }
+}
+
+impl FromStr for Topology {
+    type Err = ParseDomineeringError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "plane" => Ok(Self::Plane),
+            "cylinder" => Ok(Self::Cylinder),
+            "torus" => Ok(Self::Torus),
+            topology => Err(ParseDomineeringError::InvalidTopology(topology.to_owned())),
+        }
+    }
+}
+
+/// Error returned when Domineering position cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum ParseDomineeringError {
+    /// Board could not be parsed
+    #[error(transparent)]
+    Grid(#[from] ParseGridError),
+
+    /// Topology prefix is not one of `plane`, `cylinder` or `torus`
+    #[error("invalid topology '{0}'")]
+    InvalidTopology(String),
+}
+
 /// A Domineering position on a rectengular grid.
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Domineering<G = SmallBitGrid<Tile>> {
     grid: G,
+    topology: Topology,
 }
 
 impl<G> Display for Domineering<G>
 where
     G: Grid<Item = Tile> + FiniteGrid,
 {
+    /// Grids on a plane are written as is, other topologies are written before the grid, e.g.
+    /// `cylinder;.#.|...`
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.topology != Topology::Plane {
+            write!(f, "{};", self.topology)?;
+        }
         self.grid.display(f, '|')
     }
 }
@@ -51,10 +137,14 @@ impl<G> FromStr for Domineering<G>
 where
     G: Grid<Item = Tile> + FiniteGrid,
 {
-    type Err = ();
+    type Err = ParseDomineeringError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self::new(G::parse(s).ok_or(())?))
+        let (topology, grid) = match s.split_once(';') {
+            Some((topology, grid)) => (Topology::from_str(topology)?, grid),
+            None => (Topology::Plane, s),
+        };
+        Ok(Self::with_topology(G::try_parse(grid)?, topology))
     }
 }
 
@@ -64,7 +154,53 @@ where
 {
     /// Create a domineering position from a grid.
     pub const fn new(grid: G) -> Self {
-        Self { grid }
+        Self::with_topology(grid, Topology::Plane)
+    }
+
+    /// Create a domineering position from a grid with edges glued according to `topology`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cgt::short::partizan::{
+    ///     games::domineering::{Domineering, Topology},
+    ///     partizan_game::PartizanGame,
+    /// };
+    /// use std::str::FromStr;
+    ///
+    /// let plane: Domineering = Domineering::from_str(".#.").unwrap();
+    /// assert_eq!(plane.right_moves(), vec![]);
+    ///
+    /// let cylinder = Domineering::with_topology(*plane.grid(), Topology::Cylinder);
+    /// assert_eq!(cylinder.right_moves().len(), 1);
+    /// ```
+    pub const fn with_topology(grid: G, topology: Topology) -> Self {
+        Self { grid, topology }
+    }
+
+    /// Get board topology
+    pub const fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Create an empty `width`x`height` position with missing cells at `holes` given as `(x, y)`
+    /// coordinates. Returns `None` if grid cannot be created or any hole is outside the grid.
+    ///
+    /// # Examples
+    /// ```
+    /// use cgt::short::partizan::games::domineering::Domineering;
+    ///
+    /// let position: Domineering = Domineering::with_holes(3, 2, &[(2, 0), (1, 1)]).unwrap();
+    /// assert_eq!(&format!("{}", position), "..#|.#.");
+    /// ```
+    pub fn with_holes(width: u8, height: u8, holes: &[(u8, u8)]) -> Option<Self> {
+        let mut grid = G::filled(width, height, Tile::Empty)?;
+        for &(x, y) in holes {
+            if x >= width || y >= height {
+                return None;
+            }
+            grid.set(x, y, Tile::Taken);
+        }
+        Some(Self::new(grid))
     }
 
     /// Get underlying grid
@@ -78,15 +214,25 @@ where
     }
 
     /// Output positions as LaTeX `TikZ` picture where empty tiles are 1x1 tiles
+    pub fn to_tikz(&self) -> String {
+        self.to_tikz_with_scale(1.)
+    }
+
+    /// Same as [`Self::to_tikz`]
     pub fn to_latex(&self) -> String {
-        self.to_latex_with_scale(1.)
+        self.to_tikz()
     }
 
-    /// Like [`Self::to_latex`] but allows to specify image scale. Scale must be positive
+    /// Same as [`Self::to_tikz_with_scale`]
+    pub fn to_latex_with_scale(&self, scale: f32) -> String {
+        self.to_tikz_with_scale(scale)
+    }
+
+    /// Like [`Self::to_tikz`] but allows to specify image scale. Scale must be positive
     ///
     /// # Panics
     /// - `scale` is negative
-    pub fn to_latex_with_scale(&self, scale: f32) -> String {
+    pub fn to_tikz_with_scale(&self, scale: f32) -> String {
         use std::fmt::Write;
 
         assert!(scale >= 0., "Scale must be positive");
@@ -137,32 +283,64 @@ where
     where
         G: Ord + Clone,
     {
-        let mut moves = Vec::new();
+        let mut moves: Vec<Self> = self.moves_iter::<DIR_X, DIR_Y>().collect();
+        moves.sort_unstable();
+        moves.dedup();
+        moves
+    }
 
-        if self.grid.height() == 0 || self.grid.width() == 0 {
-            return moves;
-        }
+    /// Lazily place dominoes, the same position may be generated more than once
+    fn moves_iter<const DIR_X: u8, const DIR_Y: u8>(&self) -> impl Iterator<Item = Self> + '_
+    where
+        G: Clone,
+    {
+        let width = self.grid.width();
+        let height = self.grid.height();
+
+        // With wrapping the domino may start in the last column/row and end in the first one
+        let (max_x, max_y) = if height == 0 || width == 0 {
+            (0, 0)
+        } else {
+            (
+                if self.topology.wraps_horizontally() {
+                    width
+                } else {
+                    width - DIR_X
+                },
+                if self.topology.wraps_vertically() {
+                    height
+                } else {
+                    height - DIR_Y
+                },
+            )
+        };
+
+        (0..max_y)
+            .flat_map(move |y| (0..max_x).map(move |x| (x, y)))
+            .filter_map(move |(x, y)| {
+                let next_x = ((x as u16 + DIR_X as u16) % width as u16) as u8;
+                let next_y = ((y as u16 + DIR_Y as u16) % height as u16) as u8;
+
+                // Single tile wide board glued to itself
+                if next_x == x && next_y == y {
+                    return None;
+                }
 
-        for y in 0..(self.grid.height() - DIR_Y) {
-            for x in 0..(self.grid.width() - DIR_X) {
-                let next_x = x + DIR_X;
-                let next_y = y + DIR_Y;
                 if self.grid.get(x, y) == Tile::Empty
                     && self.grid.get(next_x, next_y) == Tile::Empty
                 {
                     let mut new_grid = self.clone();
                     new_grid.grid.set(x, y, Tile::Taken);
                     new_grid.grid.set(next_x, next_y, Tile::Taken);
-                    moves.push(new_grid.move_top_left());
+                    Some(new_grid.move_top_left())
+                } else {
+                    None
                 }
-            }
-        }
-        moves.sort_unstable();
-        moves.dedup();
-        moves
+            })
     }
 
-    /// Remove filled rows and columns from the edges
+    /// Remove filled rows and columns from the edges. Positions with wrapping topology are
+    /// returned unchanged, as removing a row or column would glue together different edges.
     ///
     /// # Examples
     /// ```
@@ -175,10 +353,17 @@ where
     // Panic at `Self::empty(minimized_width, minimized_height).unwrap();` is unreachable
     #[must_use]
     #[allow(clippy::missing_panics_doc)]
-    pub fn move_top_left(&self) -> Self {
+    pub fn move_top_left(&self) -> Self
+    where
+        G: Clone,
+    {
         // TODO: We should use this to also "fill 1x1 holes" i.e. when we have grids that after running
         // bfs has 1x1 regions we can fill them in and reduce grid then.
 
+        if self.topology != Topology::Plane {
+            return self.clone();
+        }
+
         Self::new(move_top_left(&self.grid, Tile::is_non_blocking))
     }
 }
@@ -230,6 +415,10 @@ where
     }
 }
 
+impl NamedGame for Domineering {
+    const NAME: &'static str = "domineering";
+}
+
 impl<G> PartizanGame for Domineering<G>
 where
     G: Grid<Item = Tile> + FiniteGrid + Clone + Hash + Send + Sync + Ord,
@@ -282,6 +471,14 @@ where
         self.moves_for::<1, 0>()
     }
 
+    fn left_moves_iter(&self) -> impl Iterator<Item = Self> + '_ {
+        self.moves_iter::<0, 1>()
+    }
+
+    fn right_moves_iter(&self) -> impl Iterator<Item = Self> + '_ {
+        self.moves_iter::<1, 0>()
+    }
+
     /// Get decompisitons of given position
     ///
     /// # Examples
@@ -303,6 +500,11 @@ where
     /// );
     /// ```
     fn decompositions(&self) -> Vec<Self> {
+        // Decompositions do not track components connected across the seam
+        if self.topology != Topology::Plane {
+            return vec![self.clone()];
+        }
+
         let directions = [(1, 0), (-1, 0), (0, 1), (0, -1)];
         decompositions(&self.grid, Tile::is_non_blocking, Tile::Taken, &directions)
             .into_iter()
@@ -311,12 +513,101 @@ where
     }
 }
 
+impl<G> Symmetric for Domineering<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid + Ord,
+{
+    /// Smallest position among its flips and rotation by 180°. Rotations by 90° are not
+    /// considered, as they swap Left and Right dominoes.
+    fn canonical_symmetry(&self) -> Self {
+        Symmetry::ORIENTATION_PRESERVING
+            .into_iter()
+            .map(|symmetry| Self {
+                grid: symmetry.apply(&self.grid),
+                topology: self.topology,
+            })
+            .min()
+            .expect("unreachable: there is at least one symmetry")
+    }
+}
+
+impl<G> Domineering<G> {
+    #[inline]
+    const fn topology_key(&self) -> u64 {
+        // Dimensions of the grid are at the global index, so topology uses the one below
+        zobrist_key(GLOBAL_INDEX - 1, self.topology as u64)
+    }
+}
+
+impl<G> ZobristHash for Domineering<G>
+where
+    G: ZobristHash,
+{
+    #[inline]
+    fn zobrist_hash(&self) -> u64 {
+        self.grid.zobrist_hash() ^ self.topology_key()
+    }
+
+    #[inline]
+    fn zobrist_hash_from(&self, hash: u64, other: &Self) -> u64 {
+        self.grid
+            .zobrist_hash_from(hash ^ self.topology_key(), &other.grid)
+            ^ other.topology_key()
+    }
+}
+
+impl<G> RandomPosition for Domineering<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid,
+{
+    type Parameters = GridParameters;
+
+    /// Plane of `width`x`height` tiles, each taken with probability `density`
+    fn random_position<R>(parameters: &GridParameters, rng: &mut R) -> Option<Self>
+    where
+        R: Rng,
+    {
+        let mut grid = G::filled(parameters.width, parameters.height, Tile::Empty)?;
+        for y in 0..parameters.height {
+            for x in 0..parameters.width {
+                if rng.gen_bool(parameters.density) {
+                    grid.set(x, y, Tile::Taken);
+                }
+            }
+        }
+        Some(Self::new(grid))
+    }
+}
+
+impl<G> Chromosome for Domineering<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid + Clone,
+{
+    fn mutate<R>(&mut self, mutation_rate: f32, rng: &mut R)
+    where
+        R: Rng,
+    {
+        let tiles = [Tile::Empty, Tile::Taken];
+        mutate_grid(self.grid_mut(), &tiles, mutation_rate, rng);
+    }
+
+    fn crossover<R>(&self, other: &Self, rng: &mut R) -> Self
+    where
+        R: Rng,
+    {
+        Self::new(cross_grids(self.grid(), other.grid(), rng))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         numeric::dyadic_rational_number::DyadicRationalNumber,
-        short::partizan::transposition_table::ParallelTranspositionTable,
+        short::partizan::{
+            canonical_form::{CanonicalForm, Moves},
+            transposition_table::{ParallelTranspositionTable, SymmetricTranspositionTable},
+        },
     };
     use std::str::FromStr;
 
@@ -326,6 +617,27 @@ mod tests {
         Domineering::new(SmallBitGrid::empty(10, 10).unwrap());
     }
 
+    #[test]
+    fn lazy_moves_match_moves() {
+        let position: Domineering = Domineering::from_str("...|.#.|...").unwrap();
+        let sorted = |moves: Vec<Domineering>| {
+            let mut moves = moves;
+            moves.sort_unstable();
+            moves.dedup();
+            moves
+        };
+        assert_eq!(
+            sorted(position.left_moves_iter().collect()),
+            position.left_moves()
+        );
+        assert_eq!(
+            sorted(position.right_moves_iter().collect()),
+            position.right_moves()
+        );
+        let empty: Domineering = Domineering::new(SmallBitGrid::empty(0, 0).unwrap());
+        assert_eq!(empty.left_moves_iter().next(), None);
+    }
+
     #[test]
     fn parse_display_roundtrip() {
         let inp = "...|#.#|##.|###";
@@ -333,6 +645,104 @@ mod tests {
         assert_eq!(&format!("{}", pos), inp,);
     }
 
+    #[test]
+    fn holes_out_of_bounds() {
+        assert!(Domineering::<SmallBitGrid<Tile>>::with_holes(2, 2, &[(2, 0)]).is_none());
+        assert_eq!(
+            Domineering::<SmallBitGrid<Tile>>::with_holes(2, 2, &[(1, 0)]),
+            Domineering::from_str(".#|..").ok()
+        );
+    }
+
+    #[test]
+    fn wrapping_moves() {
+        let grid = *Domineering::<SmallBitGrid<Tile>>::from_str(".#.|...")
+            .unwrap()
+            .grid();
+
+        let cylinder = Domineering::with_topology(grid, Topology::Cylinder);
+        assert_eq!(cylinder.left_moves().len(), 2);
+        assert_eq!(cylinder.right_moves().len(), 4);
+
+        let torus = Domineering::with_topology(grid, Topology::Torus);
+        assert_eq!(torus.left_moves().len(), 2);
+        assert_eq!(torus.right_moves().len(), 4);
+
+        assert_ne!(
+            cylinder.canonical_form(&ParallelTranspositionTable::new()),
+            Domineering::new(grid).canonical_form(&ParallelTranspositionTable::new())
+        );
+    }
+
+    #[test]
+    fn topology_round_trip() {
+        for (input, topology) in [
+            (".#.|...", Topology::Plane),
+            ("cylinder;.#.|...", Topology::Cylinder),
+            ("torus;.#.|...", Topology::Torus),
+        ] {
+            let position: Domineering = Domineering::from_str(input).unwrap();
+            assert_eq!(position.topology(), topology);
+            assert_eq!(position.to_string(), input);
+        }
+
+        assert_eq!(
+            Domineering::<SmallBitGrid<Tile>>::from_str("plane;.#.|..."),
+            Domineering::from_str(".#.|...")
+        );
+        assert_eq!(
+            Domineering::<SmallBitGrid<Tile>>::from_str("sphere;.#.|..."),
+            Err(ParseDomineeringError::InvalidTopology("sphere".to_owned()))
+        );
+    }
+
+    #[test]
+    fn large_board() {
+        use crate::grid::bit_grid::BitGrid;
+
+        // 10x10 board with a single free 2x2 square in the corner
+        let mut position = Domineering::<BitGrid<Tile>>::with_holes(10, 10, &[]).unwrap();
+        for y in 0..10 {
+            for x in 0..10 {
+                if x >= 2 || y >= 2 {
+                    position.grid_mut().set(x, y, Tile::Taken);
+                }
+            }
+        }
+        let empty = Domineering::<BitGrid<Tile>>::with_holes(10, 10, &[]).unwrap();
+        assert_eq!(empty.left_moves().len(), 90);
+        assert_eq!(
+            position.decompositions(),
+            vec![Domineering::from_str("..|..").unwrap()]
+        );
+        assert_eq!(
+            position
+                .canonical_form(&ParallelTranspositionTable::new())
+                .to_string(),
+            "{1|-1}"
+        );
+    }
+
+    #[test]
+    fn symmetric_transposition_table() {
+        let position: Domineering = Domineering::from_str("#...|....|....").unwrap();
+        let flipped: Domineering = Domineering::from_str("...#|....|....").unwrap();
+        assert_eq!(position.canonical_symmetry(), flipped.canonical_symmetry());
+
+        let transposition_table = ParallelTranspositionTable::new();
+        let symmetric_transposition_table: SymmetricTranspositionTable<Domineering> =
+            SymmetricTranspositionTable::default();
+        assert_eq!(
+            position.canonical_form(&transposition_table),
+            position.canonical_form(&symmetric_transposition_table)
+        );
+        assert!(symmetric_transposition_table.inner().len() < transposition_table.len());
+        assert_eq!(
+            flipped.canonical_form(&symmetric_transposition_table),
+            position.canonical_form(&transposition_table)
+        );
+    }
+
     // Values confirmed with gcsuite
 
     #[cfg(test)]
@@ -434,4 +844,31 @@ mod tests {
         // FIXME: takes too long
         // assert_temperature!(Domineering::from_str("#...|....|....|...."), 1);
     }
+
+    proptest::proptest! {
+        #![proptest_config(proptest::prelude::ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn decompositions_are_sound(position in crate::strategies::domineering::<SmallBitGrid<Tile>>(4, 4)) {
+            let transposition_table = ParallelTranspositionTable::new();
+            let components = position.decompositions();
+            let sum = components
+                .iter()
+                .map(|component| component.canonical_form(&transposition_table))
+                .fold(CanonicalForm::new_integer(0), |acc, value| &acc + &value);
+            let from_options = CanonicalForm::new_from_moves(Moves {
+                left: position
+                    .left_moves()
+                    .iter()
+                    .map(|option| option.canonical_form(&transposition_table))
+                    .collect(),
+                right: position
+                    .right_moves()
+                    .iter()
+                    .map(|option| option.canonical_form(&transposition_table))
+                    .collect(),
+            });
+            proptest::prop_assert_eq!(sum, from_options);
+        }
+    }
 }
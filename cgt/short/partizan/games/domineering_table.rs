@@ -0,0 +1,384 @@
+//! Precomputed canonical forms of empty Domineering rectangles
+//!
+//! Only rectangles with `width <= height` are stored, as rotating the board swaps the players, so
+//! value of the `height`x`width` rectangle is the negative of the `width`x`height` one.
+//!
+//! Tables of any size are computed with [`RectangleTable::generate`], e.g. by
+//! `cgt-cli domineering rectangle-table --max-size 6`, and stored in a packed binary format, see
+//! [`RectangleTable::to_packed`], or as text, one rectangle per line as
+//! `width height canonical_form`. With the `domineering_table` feature a packed table is included
+//! in the library, see [`bundled`]. To use a table in a search, store its values in the
+//! transposition table with [`RectangleTable::insert_into`].
+
+use crate::{
+    error::Error,
+    numeric::{dyadic_rational_number::DyadicRationalNumber, nimber::Nimber},
+    short::partizan::{
+        canonical_form::{CanonicalForm, Moves, Nus},
+        games::domineering::Domineering,
+        partizan_game::PartizanGame,
+        transposition_table::TranspositionTable,
+    },
+};
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+/// Magic number starting tables in the packed format, including the format version
+pub const PACKED_MAGIC: &[u8] = b"CGTDOM\x01";
+
+/// Canonical forms of empty Domineering rectangles up to a given size
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RectangleTable {
+    max_size: u8,
+    values: HashMap<(u8, u8), CanonicalForm>,
+}
+
+impl RectangleTable {
+    /// Compute values of all rectangles with both sides at most `max_size`
+    pub fn generate<TT>(max_size: u8, transposition_table: &TT) -> Self
+    where
+        TT: TranspositionTable<Domineering> + Sync,
+    {
+        let mut values = HashMap::new();
+        for height in 1..=max_size {
+            for width in 1..=height {
+                let position: Domineering = Domineering::with_holes(width, height, &[])
+                    .expect("unreachable: rectangle fits in the grid");
+                values.insert(
+                    (width, height),
+                    position.canonical_form(transposition_table),
+                );
+            }
+        }
+        Self { max_size, values }
+    }
+
+    /// Largest side length of rectangles in the table
+    pub const fn max_size(&self) -> u8 {
+        self.max_size
+    }
+
+    /// Get value of an empty `width`x`height` rectangle. Returns `None` if the rectangle is not
+    /// in the table.
+    pub fn get(&self, width: u8, height: u8) -> Option<CanonicalForm> {
+        if width == 0 || height == 0 {
+            return Some(CanonicalForm::new_integer(0));
+        }
+        if width <= height {
+            self.values.get(&(width, height)).cloned()
+        } else {
+            self.values.get(&(height, width)).map(|value| -value)
+        }
+    }
+
+    /// Store values of all rectangles, in both orientations, in the transposition table, so
+    /// searches reaching an empty rectangle don't recompute it
+    pub fn insert_into<TT>(&self, transposition_table: &TT)
+    where
+        TT: TranspositionTable<Domineering>,
+    {
+        for (&(width, height), value) in &self.values {
+            for (width, height, value) in [(width, height, value.clone()), (height, width, -value)]
+            {
+                if let Some(position) = Domineering::with_holes(width, height, &[]) {
+                    transposition_table.insert_position(position, value);
+                }
+            }
+        }
+    }
+
+    /// Encode the table in the packed binary format
+    ///
+    /// The format starts with [`PACKED_MAGIC`] and the largest side length as a byte. Then all
+    /// distinct subgames of the values follow, each after its options, so subgames shared between
+    /// values are stored once. Number-up-star games are stored as tag `0` followed by numerator,
+    /// denominator exponent, up multiple and nimber. Other games are stored as tag `1` followed
+    /// by the number of Left options and their indices, and the same for Right options. At the
+    /// end there is an index of the value of every rectangle, ordered by height and then width.
+    /// All numbers are LEB128 varints, signed ones are zigzag encoded.
+    pub fn to_packed(&self) -> Vec<u8> {
+        let mut encoder = Encoder::default();
+        let roots: Vec<usize> = Self::rectangles(self.max_size)
+            .map(|rectangle| encoder.node(&self.values[&rectangle]))
+            .collect();
+
+        let mut bytes = PACKED_MAGIC.to_vec();
+        bytes.push(self.max_size);
+        write_unsigned(&mut bytes, encoder.nodes.len() as u64);
+        bytes.extend(encoder.nodes.concat());
+        for root in roots {
+            write_unsigned(&mut bytes, root as u64);
+        }
+        bytes
+    }
+
+    /// Decode a table in the packed binary format, see [`RectangleTable::to_packed`]
+    ///
+    /// # Errors
+    /// - Input is not a valid packed table
+    pub fn from_packed(bytes: &[u8]) -> Result<Self, Error> {
+        let invalid = || Error::Syntax("invalid packed domineering table".to_owned());
+
+        let mut decoder = Decoder {
+            bytes: bytes.strip_prefix(PACKED_MAGIC).ok_or_else(invalid)?,
+        };
+        let max_size = decoder.byte().ok_or_else(invalid)?;
+
+        let node_count = decoder.unsigned().ok_or_else(invalid)?;
+        let mut nodes = Vec::new();
+        for _ in 0..node_count {
+            let node = decoder.node(&nodes).ok_or_else(invalid)?;
+            nodes.push(node);
+        }
+
+        let mut values = HashMap::new();
+        for rectangle in Self::rectangles(max_size) {
+            let value = decoder
+                .index()
+                .and_then(|idx| nodes.get(idx))
+                .ok_or_else(invalid)?;
+            values.insert(rectangle, value.clone());
+        }
+        if !decoder.bytes.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self { max_size, values })
+    }
+
+    /// All rectangles with `width <= height <= max_size`, ordered by height and then width
+    fn rectangles(max_size: u8) -> impl Iterator<Item = (u8, u8)> {
+        (1..=max_size).flat_map(|height| (1..=height).map(move |width| (width, height)))
+    }
+}
+
+impl Display for RectangleTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for height in 1..=self.max_size {
+            for width in 1..=height {
+                if let Some(value) = self.values.get(&(width, height)) {
+                    writeln!(f, "{width} {height} {value}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RectangleTable {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut max_size = 0;
+        let mut values = HashMap::new();
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            let invalid_line = || Error::Syntax(format!("invalid table entry '{line}'"));
+            let mut fields = line.splitn(3, ' ');
+            let mut size = || -> Option<u8> { fields.next()?.parse().ok() };
+            let (width, height) = size().zip(size()).ok_or_else(invalid_line)?;
+            let value = fields
+                .next()
+                .and_then(|value| CanonicalForm::from_str(value).ok())
+                .ok_or_else(invalid_line)?;
+            if width == 0 || width > height {
+                return Err(invalid_line());
+            }
+            max_size = max_size.max(height);
+            values.insert((width, height), value);
+        }
+        Ok(Self { max_size, values })
+    }
+}
+
+/// Writer of the subgames in the packed format
+#[derive(Default)]
+struct Encoder {
+    nodes: Vec<Vec<u8>>,
+    indices: HashMap<CanonicalForm, usize>,
+}
+
+impl Encoder {
+    /// Store the game after all of its options and return its index
+    fn node(&mut self, game: &CanonicalForm) -> usize {
+        if let Some(&idx) = self.indices.get(game) {
+            return idx;
+        }
+
+        let mut node = Vec::new();
+        if let Some(nus) = game.to_nus() {
+            node.push(0);
+            write_signed(&mut node, nus.number().numerator());
+            write_unsigned(&mut node, u64::from(nus.number().denominator_exponent()));
+            write_signed(&mut node, i64::from(nus.up_multiple()));
+            write_unsigned(&mut node, u64::from(nus.nimber().value()));
+        } else {
+            let moves = game.to_moves();
+            let left: Vec<usize> = moves.left.iter().map(|option| self.node(option)).collect();
+            let right: Vec<usize> = moves.right.iter().map(|option| self.node(option)).collect();
+            node.push(1);
+            for options in [left, right] {
+                write_unsigned(&mut node, options.len() as u64);
+                for option in options {
+                    write_unsigned(&mut node, option as u64);
+                }
+            }
+        }
+
+        let idx = self.nodes.len();
+        self.nodes.push(node);
+        self.indices.insert(game.clone(), idx);
+        idx
+    }
+}
+
+/// Reader of the packed format
+struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl Decoder<'_> {
+    fn byte(&mut self) -> Option<u8> {
+        let (&byte, rest) = self.bytes.split_first()?;
+        self.bytes = rest;
+        Some(byte)
+    }
+
+    fn unsigned(&mut self) -> Option<u64> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    fn signed(&mut self) -> Option<i64> {
+        let value = self.unsigned()?;
+        Some((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    fn index(&mut self) -> Option<usize> {
+        usize::try_from(self.unsigned()?).ok()
+    }
+
+    /// Read a game, with options referring to the already read `nodes`
+    fn node(&mut self, nodes: &[CanonicalForm]) -> Option<CanonicalForm> {
+        match self.byte()? {
+            0 => {
+                let numerator = self.signed()?;
+                let denominator_exponent = u32::try_from(self.unsigned()?).ok()?;
+                let up_multiple = i32::try_from(self.signed()?).ok()?;
+                let nimber = u32::try_from(self.unsigned()?).ok()?;
+                Some(CanonicalForm::new_nus(Nus::new(
+                    DyadicRationalNumber::new(numerator, denominator_exponent),
+                    up_multiple,
+                    Nimber::new(nimber),
+                )))
+            }
+            1 => {
+                let left = self.options(nodes)?;
+                let right = self.options(nodes)?;
+                Some(CanonicalForm::new_from_moves(Moves { left, right }))
+            }
+            _ => None,
+        }
+    }
+
+    fn options(&mut self, nodes: &[CanonicalForm]) -> Option<Vec<CanonicalForm>> {
+        let count = self.unsigned()?;
+        (0..count)
+            .map(|_| nodes.get(self.index()?).cloned())
+            .collect()
+    }
+}
+
+fn write_unsigned(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value & 0x7f) as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn write_signed(bytes: &mut Vec<u8>, value: i64) {
+    write_unsigned(bytes, ((value << 1) ^ (value >> 63)) as u64);
+}
+
+/// Table of rectangles included in the library. Its size is the size of the table it was built
+/// from, see `cgt-cli domineering rectangle-table` to rebuild it.
+#[cfg(feature = "domineering_table")]
+pub fn bundled() -> &'static RectangleTable {
+    static TABLE: std::sync::OnceLock<RectangleTable> = std::sync::OnceLock::new();
+    TABLE.get_or_init(|| {
+        RectangleTable::from_packed(include_bytes!("domineering_table.bin"))
+            .expect("unreachable: bundled table is valid")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
+
+    #[test]
+    fn round_trip() {
+        let tt = ParallelTranspositionTable::new();
+        let table = RectangleTable::generate(3, &tt);
+        assert_eq!(table.get(1, 2), Some(CanonicalForm::new_integer(1)));
+        assert_eq!(table.get(2, 1), Some(CanonicalForm::new_integer(-1)));
+        assert_eq!(table.get(2, 2), CanonicalForm::from_str("{1|-1}").ok());
+        assert_eq!(table.get(3, 4), None);
+
+        let position: Domineering = Domineering::with_holes(3, 2, &[]).unwrap();
+        assert_eq!(table.get(3, 2), Some(position.canonical_form(&tt)));
+
+        assert_eq!(
+            RectangleTable::from_str(&table.to_string()),
+            Ok(table.clone())
+        );
+        assert_eq!(RectangleTable::from_packed(&table.to_packed()), Ok(table));
+    }
+
+    #[test]
+    fn packed_format() {
+        let tt = ParallelTranspositionTable::new();
+        let table = RectangleTable::generate(4, &tt);
+        let packed = table.to_packed();
+        assert!(packed.len() < table.to_string().len());
+        assert_eq!(RectangleTable::from_packed(&packed), Ok(table));
+
+        assert!(RectangleTable::from_packed(&packed[..packed.len() - 1]).is_err());
+        assert!(RectangleTable::from_packed(&[packed.as_slice(), &[0]].concat()).is_err());
+        assert!(RectangleTable::from_packed(b"CGTDOM\x02\x01").is_err());
+    }
+
+    #[test]
+    fn inserted_into_transposition_table() {
+        let table = RectangleTable::generate(3, &ParallelTranspositionTable::new());
+        let tt = ParallelTranspositionTable::new();
+        table.insert_into(&tt);
+        assert_eq!(tt.len(), 9);
+
+        let position: Domineering = Domineering::with_holes(3, 2, &[]).unwrap();
+        assert_eq!(tt.lookup_position(&position), table.get(3, 2));
+
+        let position: Domineering = Domineering::from_str("...|...|#..").unwrap();
+        assert_eq!(
+            position.canonical_form(&tt),
+            position.canonical_form(&ParallelTranspositionTable::new())
+        );
+    }
+
+    #[cfg(feature = "domineering_table")]
+    #[test]
+    fn bundled_matches_search() {
+        let tt = ParallelTranspositionTable::new();
+        let table = bundled();
+        assert!(table.max_size() >= 5);
+        for (width, height) in [(1, 5), (4, 3), (4, 4), (2, 5)] {
+            let position: Domineering = Domineering::with_holes(width, height, &[]).unwrap();
+            assert_eq!(table.get(width, height), Some(position.canonical_form(&tt)));
+        }
+    }
+}
@@ -2,10 +2,16 @@
 
 use crate::{
     drawing::svg::{self, ImmSvg, Svg},
-    grid::{vec_grid::VecGrid, FiniteGrid, Grid},
-    short::partizan::partizan_game::PartizanGame,
+    genetic::{cross_grids, mutate_grid, Chromosome},
+    grid::{vec_grid::VecGrid, FiniteGrid, Grid, ParseGridError},
+    short::partizan::{
+        partizan_game::PartizanGame,
+        position::NamedGame,
+        random_position::{GridParameters, RandomPosition},
+    },
 };
 use cgt_derive::Tile;
+use rand::Rng;
 use std::{
     fmt::{self, Display},
     hash::Hash,
@@ -49,10 +55,10 @@ impl<G> FromStr for Fission<G>
 where
     G: Grid<Item = Tile> + FiniteGrid,
 {
-    type Err = ();
+    type Err = ParseGridError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self::new(G::parse(s).ok_or(())?))
+        G::try_parse(s).map(Self::new)
     }
 }
 
@@ -66,19 +72,34 @@ where
         Self { grid }
     }
 
+    /// Get underlying grid
+    #[inline]
+    pub const fn grid(&self) -> &G {
+        &self.grid
+    }
+
     #[inline]
     fn moves_for<const DIR_X: u8, const DIR_Y: u8>(&self) -> Vec<Self>
     where
         G: Clone,
     {
-        let mut moves = Vec::new();
+        self.moves_iter::<DIR_X, DIR_Y>().collect()
+    }
 
-        if self.grid.height() == 0 || self.grid.width() == 0 {
-            return moves;
-        }
+    /// Lazily split stones
+    fn moves_iter<const DIR_X: u8, const DIR_Y: u8>(&self) -> impl Iterator<Item = Self> + '_
+    where
+        G: Clone,
+    {
+        let (max_x, max_y) = if self.grid.height() == 0 || self.grid.width() == 0 {
+            (DIR_X, DIR_Y)
+        } else {
+            (self.grid.width() - DIR_X, self.grid.height() - DIR_Y)
+        };
 
-        for y in DIR_Y..(self.grid.height() - DIR_Y) {
-            for x in DIR_X..(self.grid.width() - DIR_X) {
+        (DIR_Y..max_y)
+            .flat_map(move |y| (DIR_X..max_x).map(move |x| (x, y)))
+            .filter_map(move |(x, y)| {
                 let prev_x = x - DIR_X;
                 let prev_y = y - DIR_Y;
                 let next_x = x + DIR_X;
@@ -92,15 +113,18 @@ where
                     new_grid.set(x, y, Tile::Empty);
                     new_grid.set(prev_x, prev_y, Tile::Stone);
                     new_grid.set(next_x, next_y, Tile::Stone);
-                    moves.push(Self::new(new_grid));
+                    Some(Self::new(new_grid))
+                } else {
+                    None
                 }
-            }
-        }
-
-        moves
+            })
     }
 }
 
+impl NamedGame for Fission {
+    const NAME: &'static str = "fission";
+}
+
 impl<G> PartizanGame for Fission<G>
 where
     G: Grid<Item = Tile> + FiniteGrid + Clone + Hash + Send + Sync + Eq,
@@ -112,6 +136,14 @@ where
     fn right_moves(&self) -> Vec<Self> {
         self.moves_for::<1, 0>()
     }
+
+    fn left_moves_iter(&self) -> impl Iterator<Item = Self> + '_ {
+        self.moves_iter::<0, 1>()
+    }
+
+    fn right_moves_iter(&self) -> impl Iterator<Item = Self> + '_ {
+        self.moves_iter::<1, 0>()
+    }
 }
 
 impl<G> Svg for Fission<G>
@@ -182,6 +214,51 @@ where
     }
 }
 
+impl<G> RandomPosition for Fission<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid,
+{
+    type Parameters = GridParameters;
+
+    /// Board of `width`x`height` tiles, each having a stone with probability `density`
+    fn random_position<R>(parameters: &GridParameters, rng: &mut R) -> Option<Self>
+    where
+        R: Rng,
+    {
+        let mut grid = G::filled(parameters.width, parameters.height, Tile::Empty)?;
+        for y in 0..parameters.height {
+            for x in 0..parameters.width {
+                if rng.gen_bool(parameters.density) {
+                    grid.set(x, y, Tile::Stone);
+                }
+            }
+        }
+        Some(Self::new(grid))
+    }
+}
+
+impl<G> Chromosome for Fission<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid + Clone,
+{
+    fn mutate<R>(&mut self, mutation_rate: f32, rng: &mut R)
+    where
+        R: Rng,
+    {
+        let tiles = [Tile::Empty, Tile::Stone, Tile::Blocked];
+        let mut grid = self.grid().clone();
+        mutate_grid(&mut grid, &tiles, mutation_rate, rng);
+        *self = Self::new(grid);
+    }
+
+    fn crossover<R>(&self, other: &Self, rng: &mut R) -> Self
+    where
+        R: Rng,
+    {
+        Self::new(cross_grids(self.grid(), other.grid(), rng))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
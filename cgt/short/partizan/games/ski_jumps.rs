@@ -11,10 +11,17 @@
 
 use crate::{
     drawing::svg::{self, ImmSvg, Svg},
-    grid::{vec_grid::VecGrid, CharTile, FiniteGrid, Grid},
-    short::partizan::{canonical_form::CanonicalForm, partizan_game::PartizanGame},
+    genetic::{cross_grids, mutate_grid, Chromosome},
+    grid::{vec_grid::VecGrid, CharTile, FiniteGrid, Grid, ParseGridError},
+    short::partizan::{
+        canonical_form::CanonicalForm,
+        partizan_game::PartizanGame,
+        position::NamedGame,
+        random_position::{sample_pieces, GridParameters, RandomPosition},
+    },
 };
 use core::fmt;
+use rand::Rng;
 use std::{fmt::Display, hash::Hash, str::FromStr};
 
 /// Skier type
@@ -91,10 +98,10 @@ impl<G> FromStr for SkiJumps<G>
 where
     G: Grid<Item = Tile> + FiniteGrid,
 {
-    type Err = ();
+    type Err = ParseGridError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self::new(G::parse(s).ok_or(())?))
+        G::try_parse(s).map(Self::new)
     }
 }
 
@@ -108,6 +115,12 @@ where
         Self { grid }
     }
 
+    /// Get underlying grid
+    #[inline]
+    pub const fn grid(&self) -> &G {
+        &self.grid
+    }
+
     /// Check if jumping move is possible
     pub fn jump_available(&self) -> bool {
         for y in 0..self.grid.height() {
@@ -181,6 +194,10 @@ where
     }
 }
 
+impl NamedGame for SkiJumps {
+    const NAME: &'static str = "ski-jumps";
+}
+
 impl<G> PartizanGame for SkiJumps<G>
 where
     G: Grid<Item = Tile> + FiniteGrid + Clone + Hash + Send + Sync + Eq,
@@ -305,6 +322,66 @@ where
     }
 }
 
+impl<G> RandomPosition for SkiJumps<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid,
+{
+    type Parameters = GridParameters;
+
+    /// Slope of `width`x`height` tiles with `pieces` skiers of each player, each of them being a
+    /// jumper or a slipper with equal probability
+    fn random_position<R>(parameters: &GridParameters, rng: &mut R) -> Option<Self>
+    where
+        R: Rng,
+    {
+        let width = parameters.width as usize;
+        let mut grid = G::filled(parameters.width, parameters.height, Tile::Empty)?;
+        let skiers = sample_pieces(width * parameters.height as usize, parameters.pieces, rng)?;
+        for (i, tile) in skiers.into_iter().enumerate() {
+            let skier = if rng.gen_bool(0.5) {
+                Skier::Jumper
+            } else {
+                Skier::Slipper
+            };
+            let skier = if i < parameters.pieces as usize {
+                Tile::Left(skier)
+            } else {
+                Tile::Right(skier)
+            };
+            grid.set((tile % width) as u8, (tile / width) as u8, skier);
+        }
+        Some(Self::new(grid))
+    }
+}
+
+impl<G> Chromosome for SkiJumps<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid + Clone,
+{
+    fn mutate<R>(&mut self, mutation_rate: f32, rng: &mut R)
+    where
+        R: Rng,
+    {
+        let tiles = [
+            Tile::Empty,
+            Tile::Left(Skier::Jumper),
+            Tile::Left(Skier::Slipper),
+            Tile::Right(Skier::Jumper),
+            Tile::Right(Skier::Slipper),
+        ];
+        let mut grid = self.grid().clone();
+        mutate_grid(&mut grid, &tiles, mutation_rate, rng);
+        *self = Self::new(grid);
+    }
+
+    fn crossover<R>(&self, other: &Self, rng: &mut R) -> Self
+    where
+        R: Rng,
+    {
+        Self::new(cross_grids(self.grid(), other.grid(), rng))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
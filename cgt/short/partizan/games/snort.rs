@@ -1,158 +1,69 @@
 //! Snort is played on undirected graph. In each turn Left colors a vertex blue and Right colors
 //! a vertex red. Players can only choose a vertex that is adjecent to only empty vertices or to
 //! vertices in their own color.
+//!
+//! On directed graphs only the out-neighborhood restricts the choice, i.e. a player can color
+//! a vertex unless it has an edge to a vertex in the opponent's color. See [`DirectedSnort`].
+//!
+//! Snort is a [vertex coloring](super::vertex_coloring) game with [`SnortRules`].
 
 use crate::{
-    graph::{adjacency_matrix::undirected::UndirectedGraph, Graph, VertexIndex},
-    numeric::{dyadic_rational_number::DyadicRationalNumber, nimber::Nimber},
-    short::partizan::{canonical_form::CanonicalForm, partizan_game::PartizanGame},
+    genetic::{
+        snort::{Crossover, Mutation},
+        Chromosome,
+    },
+    graph::{
+        adjacency_matrix::{directed::DirectedGraph, undirected::UndirectedGraph},
+        generators, Graph, VertexIndex,
+    },
+    short::partizan::{
+        canonical_form::{CanonicalForm, Moves},
+        position::NamedGame,
+        random_position::{GraphParameters, RandomPosition},
+        simplification::Player,
+    },
 };
-use std::{
-    collections::VecDeque,
-    fmt::Write,
-    hash::Hash,
-    num::NonZeroU32,
-    ops::{Index, IndexMut},
-};
-
-/// Color of Snort vertex. Note that we are taking tinting apporach rather than direct tracking
-/// of adjacent colors.
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde_repr::Serialize_repr, serde_repr::Deserialize_repr)
-)]
-#[repr(u8)]
-pub enum VertexColor {
-    /// Vertex without color, not connected to any taken vertices
-    Empty = 0,
-
-    /// Vertex that is adjecent to left
-    TintLeft = 1,
-
-    /// Vertex that is adjecent to right
-    TintRight = 2,
-
-    /// Vertex that is either taken or connected to both colors
-    Taken = 3,
-}
-
-impl TryFrom<u8> for VertexColor {
-    type Error = ();
-
-    #[inline]
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        match value {
-            0 => Ok(Self::Empty),
-            1 => Ok(Self::TintLeft),
-            2 => Ok(Self::TintRight),
-            3 => Ok(Self::Taken),
-            _ => Err(()),
-        }
-    }
-}
-
-/// Type of vertex (or group of them) in the graph. We abstract over vertices to support efficient
-/// calculations of positions with star-like structure
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub enum VertexKind {
-    /// Single graph vertex
-    Single(VertexColor),
-
-    /// Cluster of vertices that are not connected to each other, but may be connected to other
-    /// vertices in the graph.
-    Cluster(VertexColor, NonZeroU32),
-}
-
-impl VertexKind {
-    #[inline]
-    /// Get color of the vertex
-    pub const fn color(self) -> VertexColor {
-        match self {
-            Self::Single(color) | Self::Cluster(color, _) => color,
-        }
-    }
+use rand::Rng;
+use std::{num::NonZeroU32, str::FromStr};
 
-    #[inline]
-    /// Get mutable color of the vertex
-    pub fn color_mut(&mut self) -> &mut VertexColor {
-        match self {
-            Self::Single(color) | Self::Cluster(color, _) => color,
-        }
-    }
+pub use crate::short::partizan::games::vertex_coloring::{
+    GraphvizStyle, InvalidPosition, Move, Rules, VertexColor, VertexColoring, VertexColors,
+    VertexKind,
+};
 
-    #[inline]
-    fn degree_factor(self) -> usize {
-        match self {
-            VertexKind::Single(_) => 1,
-            VertexKind::Cluster(_, cluster_size) => cluster_size.get() as usize,
-        }
-    }
-}
+#[cfg(test)]
+use crate::{drawing::svg::Svg, error::Error, short::partizan::partizan_game::PartizanGame};
+#[cfg(test)]
+use std::hash::Hash;
 
-/// Vertices colors of the game graph
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+/// Rules of Snort - players cannot color vertices adjacent to opponent's vertices
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct VertexColors {
-    /// Color of each vertex
-    pub inner: Vec<VertexKind>,
-}
-
-impl Index<VertexIndex> for VertexColors {
-    type Output = VertexKind;
+pub struct SnortRules;
 
-    fn index(&self, index: VertexIndex) -> &Self::Output {
-        &self.inner[index.index]
+impl Rules for SnortRules {
+    fn allows(self, player: Player, neighbor: Player) -> bool {
+        player == neighbor
     }
-}
 
-impl IndexMut<VertexIndex> for VertexColors {
-    fn index_mut(&mut self, index: VertexIndex) -> &mut Self::Output {
-        &mut self.inner[index.index]
+    fn known_value<G>(position: &Snort<G>) -> Option<CanonicalForm>
+    where
+        G: Graph,
+    {
+        known_value(position)
     }
 }
 
 /// Position of a [snort](self) game
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Snort<G = UndirectedGraph> {
-    /// Vertices colors of the game graph
-    pub vertices: VertexColors,
+pub type Snort<G = UndirectedGraph> = VertexColoring<SnortRules, G>;
 
-    /// Get graph of the game. This includes only edges
-    pub graph: G,
-}
+/// Position of a [snort](self) game on a directed graph
+pub type DirectedSnort = Snort<DirectedGraph>;
 
 impl<G> Snort<G>
 where
     G: Graph + Clone,
 {
-    /// Create new Snort position with all vertices empty.
-    pub fn new(graph: G) -> Self {
-        Self {
-            vertices: VertexColors {
-                inner: vec![VertexKind::Single(VertexColor::Empty); graph.size()],
-            },
-            graph,
-        }
-    }
-
-    // TODO: Perform that check
-    /// Create a Snort position with initial colors. It's up to the user to ensure that no conflicting
-    /// colors are connected in the graph.
-    /// Returns `None` if `vertices` and `graph` have conflicting sizes.
-    pub fn with_colors(vertices: Vec<VertexKind>, graph: G) -> Option<Self> {
-        if vertices.len() != graph.size() {
-            return None;
-        }
-
-        Some(Self {
-            vertices: VertexColors { inner: vertices },
-            graph,
-        })
-    }
-
     /// Construct new position on caterpillar `C(n+1, n, n+1)`
     ///
     /// The caterpillar `C(n+1, n, n+1)` consists of a main path of length 3, whose central vertex
@@ -183,228 +94,189 @@ where
         )
         .unwrap()
     }
+}
 
-    fn vertex_degree(&self, this_vertex: VertexIndex) -> usize {
-        let mut res = 0;
-        for one_away in self.graph.vertices() {
-            if one_away != this_vertex && self.graph.are_adjacent(this_vertex, one_away) {
-                res += self.vertices[one_away].degree_factor();
-            }
-        }
-        res
-    }
-
-    fn vertex_second_degree(&self, this_vertex: VertexIndex) -> usize {
-        let mut res = 0;
-        let mut seen = vec![false; self.graph.size()];
-
-        for one_away in self.graph.vertices() {
-            if one_away != this_vertex && self.graph.are_adjacent(this_vertex, one_away) {
-                for two_away in self.graph.vertices() {
-                    if two_away != one_away
-                        && two_away != this_vertex
-                        && self.graph.are_adjacent(one_away, two_away)
-                        && !seen[two_away.index]
-                    {
-                        seen[two_away.index] = true;
-                        res += self.vertices[two_away].degree_factor();
-                    }
-                }
-            }
-        }
+#[test]
+fn text_format_round_trip() {
+    let snort: Snort = Snort::new_three_caterpillar(NonZeroU32::new(2).unwrap());
+    assert_eq!(snort.to_string(), "....<3>.<2>.<3>;0-1,0-2,0-4,1-3,2-5");
+    assert_eq!(Snort::from_str(&snort.to_string()), Ok(snort));
 
-        res
-    }
+    let position: Snort = Snort::from_str("L.#R; 0-1, 1-3 ,0-3").unwrap();
+    assert_eq!(position.to_string(), "L.#R;0-1,0-3,1-3");
+    assert_eq!(
+        Snort::<UndirectedGraph>::from_str("..")
+            .unwrap()
+            .to_string(),
+        "..;"
+    );
 
-    /// Get degree of the underlying game graph, correctly counting clusters of vertices
-    ///
-    /// Note that using [`Graph::degree`] will yield incorrect results
-    pub fn degree(&self) -> usize {
-        self.graph
-            .vertices()
-            .into_iter()
-            .map(|v| self.vertex_degree(v))
-            .max()
-            .unwrap_or(0)
-    }
+    let error = |input: &str| Snort::<UndirectedGraph>::from_str(input).unwrap_err();
+    assert_eq!(
+        error("..;0-2").to_string(),
+        "edge '0-2' connects missing vertex"
+    );
+    assert_eq!(
+        error("..;0-0"),
+        Error::Position(InvalidPosition::Loop(VertexIndex { index: 0 }))
+    );
+    assert_eq!(error(".x;").to_string(), "invalid vertex 'x'");
+    assert_eq!(error(".<0>;").to_string(), "invalid cluster size '0'");
+    assert_eq!(error(".#;0-1").to_string(), "taken vertex 1 has edges");
+}
 
-    /// Get second degree of the underlying game graph
-    ///
-    /// Second degree of a vertex is the number of all vertices two away from a given vertex
-    /// and just like in first degree, second degree of a graph is the maximum value among vertices
-    pub fn second_degree(&self) -> usize {
-        self.graph
-            .vertices()
-            .into_iter()
-            .map(|v| self.vertex_second_degree(v))
-            .max()
-            .unwrap_or(0)
-    }
+#[test]
+fn with_colors_validation() {
+    let edge = [(VertexIndex { index: 0 }, VertexIndex { index: 1 })];
 
-    /// Get moves for a given player. Works only for `TintLeft` and `TintRight`.
-    /// Any other input is undefined.
-    fn moves_for<const COLOR: u8>(&self) -> Vec<Self> {
-        // const ADT generics are unsable, so here we go
-        let own_tint_color: VertexColor = VertexColor::try_from(COLOR).unwrap();
+    assert!(Snort::with_colors(
+        vec![
+            VertexKind::Single(VertexColor::TintLeft),
+            VertexKind::Single(VertexColor::TintRight),
+        ],
+        UndirectedGraph::from_edges(2, &edge),
+    )
+    .is_ok());
+    assert_eq!(
+        Snort::with_colors(
+            vec![VertexKind::Single(VertexColor::Empty)],
+            UndirectedGraph::from_edges(2, &edge),
+        ),
+        Err(InvalidPosition::SizeMismatch {
+            vertices: 1,
+            graph: 2
+        })
+    );
+    assert_eq!(
+        Snort::with_colors(
+            vec![
+                VertexKind::Single(VertexColor::Empty),
+                VertexKind::Cluster(VertexColor::Taken, NonZeroU32::new(2).unwrap()),
+            ],
+            UndirectedGraph::from_edges(2, &edge),
+        ),
+        Err(InvalidPosition::ConnectedTakenVertex(VertexIndex {
+            index: 1
+        }))
+    );
+    assert_eq!(
+        Snort::with_colors(
+            vec![VertexKind::Single(VertexColor::Empty)],
+            UndirectedGraph::from_edges(1, &[(VertexIndex { index: 0 }, VertexIndex { index: 0 })]),
+        ),
+        Err(InvalidPosition::Loop(VertexIndex { index: 0 }))
+    );
+}
 
-        let mut moves = Vec::with_capacity(self.graph.size());
+#[test]
+fn canonical_labeling_identifies_isomorphic_positions() {
+    let position: Snort = Snort::from_str("L..<2>;0-1,1-2").unwrap();
+    let relabeled: Snort = Snort::from_str(".<2>.L;0-1,1-2").unwrap();
+    let recolored: Snort = Snort::from_str("R..<2>;0-1,1-2").unwrap();
 
-        // Vertices where player can move
-        let move_vertices = self
-            .vertices
-            .inner
-            .iter()
-            .enumerate()
-            .filter(|(_, vertex)| {
-                let vertex_color = vertex.color();
-                vertex_color == own_tint_color || vertex_color == VertexColor::Empty
-            })
-            .map(|(index, _)| VertexIndex { index });
-
-        // Go through list of vertices with legal move
-        for move_vertex_idx in move_vertices {
-            let mut position: Self = self.clone();
-
-            // Take vertex
-            let move_vertex = &mut position.vertices[move_vertex_idx];
-            match move_vertex {
-                VertexKind::Single(move_vertex_color) => *move_vertex_color = VertexColor::Taken,
-                VertexKind::Cluster(_, cluster_size) => {
-                    if *cluster_size == NonZeroU32::new(1).unwrap() {
-                        *move_vertex = VertexKind::Single(VertexColor::Taken);
-                    } else {
-                        // Vertices in cluster are disconnected so nothing changes color
-                        *cluster_size = NonZeroU32::new(cluster_size.get() - 1).unwrap();
-                    }
-                }
-            }
+    assert_eq!(
+        position.canonically_labeled(),
+        relabeled.canonically_labeled()
+    );
+    assert_ne!(
+        position.canonically_labeled(),
+        recolored.canonically_labeled()
+    );
+}
 
-            // Disconnect `move_vertex` from adjecent vertices and tint them
-            for adjacent_vertex_idx in self.graph.adjacent_to(move_vertex_idx) {
-                // Disconnect move vertex from adjecent, we disconnect only single vertices
-                // because clusters are still alive. If cluster is dead it's turned into single
-                // before (See: 'take vertex' above), so it still works.
-                if let VertexKind::Single(_) = position.vertices[move_vertex_idx] {
-                    position
-                        .graph
-                        .connect(move_vertex_idx, adjacent_vertex_idx, false);
-                }
-
-                // No loops in snort graphs
-                if adjacent_vertex_idx != move_vertex_idx {
-                    let adjacent_vertex = &mut position.vertices[adjacent_vertex_idx];
-                    let adjacent_vertex_color = adjacent_vertex.color_mut();
-
-                    // Tint adjacent vertex
-                    if *adjacent_vertex_color == own_tint_color
-                        || *adjacent_vertex_color == VertexColor::Empty
-                    {
-                        // If adjacent vertex is empty or tinted in own color, tint it in own
-                        *adjacent_vertex_color = own_tint_color;
-                    } else {
-                        // Otherwise the vertex is tinted in opponents color, so no one can longer
-                        // move there, thus we mark is as taken and disconnect from the graph
-                        *adjacent_vertex_color = VertexColor::Taken;
-                        for v in position.graph.vertices() {
-                            position.graph.connect(v, adjacent_vertex_idx, false);
-                        }
-                    }
-                }
-            }
-            moves.push(position);
-        }
-        moves
+#[test]
+fn moves_name_colored_vertices() {
+    let path: Snort = Snort::from_str("...;0-1,1-2").unwrap();
+    let left_move = |index| Move {
+        vertex: VertexIndex { index },
+        player: Player::Left,
+    };
+
+    let moves = path.moves_with_vertices(Player::Left);
+    assert_eq!(
+        moves.iter().map(|(m, _)| *m).collect::<Vec<_>>(),
+        vec![left_move(0), left_move(1)]
+    );
+    for (m, position) in moves {
+        assert_eq!(path.apply_move(m), Some(position));
     }
+    assert_eq!(left_move(1).to_string(), "Left colors 1");
+
+    let position = path.apply_move(left_move(0)).unwrap();
+    assert_eq!(position.to_string(), "#L.;1-2");
+    let right_move = |index| Move {
+        vertex: VertexIndex { index },
+        player: Player::Right,
+    };
+    assert_eq!(position.apply_move(right_move(1)), None);
+    assert_eq!(position.apply_move(right_move(3)), None);
+    assert_eq!(
+        position.apply_move(right_move(2)).unwrap().to_string(),
+        "###;"
+    );
+}
 
-    /// BFS search to get the decompisitons, should be used only as a helper for [`Self::decompositions`]
-    fn bfs(&self, visited: &mut [bool], v: VertexIndex) -> Self {
-        let mut vertices_to_take: Vec<VertexIndex> = Vec::new();
-
-        let mut q: VecDeque<VertexIndex> = VecDeque::new();
-        q.push_back(v);
-        visited[v.index] = true;
-
-        while let Some(v) = q.pop_front() {
-            vertices_to_take.push(v);
-
-            for u in self.graph.adjacent_to(v) {
-                if !visited[u.index] {
-                    visited[u.index] = true;
-                    q.push_back(u);
-                }
-            }
-        }
-
-        let mut new_graph = G::empty(vertices_to_take.len());
-        for (new_v, old_v) in vertices_to_take.iter().enumerate() {
-            for old_u in self.graph.adjacent_to(*old_v) {
-                if let Some(new_u) = vertices_to_take.iter().position(|x| *x == old_u) {
-                    new_graph.connect(
-                        VertexIndex { index: new_v },
-                        VertexIndex { index: new_u },
-                        true,
-                    );
-                }
-            }
-        }
-
-        let mut new_vertices = Vec::with_capacity(vertices_to_take.len());
-        for v in &vertices_to_take {
-            new_vertices.push(self.vertices[*v]);
-        }
+#[test]
+fn directed_snort() {
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
 
-        Self {
-            vertices: VertexColors {
-                inner: new_vertices,
-            },
-            graph: new_graph,
-        }
-    }
+    // Coloring `1` restricts only `0`, coloring `0` does not restrict anything
+    let position = DirectedSnort::from_str("..;0>1").unwrap();
+    assert_eq!(position.to_string(), "..;0>1");
+    assert_eq!(
+        position
+            .moves_with_vertices(Player::Left)
+            .into_iter()
+            .map(|(m, position)| format!("{}: {position}", m.vertex.index))
+            .collect::<Vec<_>>(),
+        vec!["0: #.;", "1: L#;"]
+    );
+    assert_eq!(
+        position
+            .apply_move(Move {
+                vertex: VertexIndex { index: 1 },
+                player: Player::Right
+            })
+            .unwrap()
+            .apply_move(Move {
+                vertex: VertexIndex { index: 0 },
+                player: Player::Left
+            }),
+        None
+    );
 
-    /// Render to a [graphviz](https://graphviz.org/) format, that can be later rendered to an
-    /// image with external engine.
-    pub fn to_graphviz(&self) -> String {
-        let mut buf = String::new();
-
-        write!(buf, "graph G {{").unwrap();
-
-        for (vertex_idx, vertex) in self.vertices.inner.iter().enumerate() {
-            let color = match vertex.color() {
-                VertexColor::Empty => "white",
-                VertexColor::TintLeft => "blue",
-                VertexColor::TintRight => "red",
-                VertexColor::Taken => continue,
-            };
-            let shape = match vertex {
-                VertexKind::Single(_) => "circle",
-                VertexKind::Cluster(_, _) => "square",
-            };
-            let label = match vertex {
-                VertexKind::Single(_) => format!("\"{}\"", vertex_idx),
-                VertexKind::Cluster(_, cluster_size) => {
-                    format!("\"{}\\n<{}>\"", vertex_idx, cluster_size.get())
-                }
-            };
-
-            write!(buf,
-                   "{} [label={}, fillcolor={}, style=filled, shape={}, fixedsize=true, width=1, height=1, fontsize=24];",
-                   vertex_idx,
-                   label,
-                   color,
-                   shape).unwrap();
-        }
+    // Edge pointing to an earlier vertex still joins the component
+    assert_eq!(
+        DirectedSnort::from_str("..;1>0")
+            .unwrap()
+            .decompositions()
+            .len(),
+        1
+    );
 
-        for v in self.graph.vertices() {
-            for u in self.graph.vertices() {
-                if v < u && self.graph.are_adjacent(v, u) {
-                    write!(buf, "{} -- {};", v.index, u.index).unwrap();
-                }
-            }
-        }
+    // Edges in both directions behave like undirected edges
+    let undirected: Snort = Snort::new_three_caterpillar(NonZeroU32::new(2).unwrap());
+    let directed = DirectedSnort::from_str(&undirected.to_string()).unwrap();
+    assert_eq!(
+        directed.canonical_form(&ParallelTranspositionTable::new()),
+        undirected.canonical_form(&ParallelTranspositionTable::new())
+    );
 
-        write!(buf, "}}").unwrap();
-        buf
-    }
+    assert!(matches!(
+        Snort::<UndirectedGraph>::from_str("..;0>1"),
+        Err(Error::Syntax(_))
+    ));
+    assert!(matches!(
+        DirectedSnort::from_str(".#;0>1"),
+        Err(Error::Position(_))
+    ));
+    assert_eq!(
+        DirectedSnort::from_str("...;0>2,2>1")
+            .unwrap()
+            .compress_clusters()
+            .to_string(),
+        "...;0>2,2>1"
+    );
 }
 
 #[test]
@@ -416,60 +288,337 @@ fn degree_works() {
     assert_eq!(snort.degree(), 12);
 }
 
-impl<G> PartizanGame for Snort<G>
+#[test]
+fn svg_works() {
+    let position: Snort = Snort::from_str("L.R.<2>;0-1,1-2,2-3").unwrap();
+    let mut svg = String::new();
+    position.to_svg(&mut svg).unwrap();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert_eq!(svg.matches("<circle").count(), 3);
+    assert_eq!(svg.matches("<rect").count(), 1);
+    assert_eq!(svg.matches("<line").count(), 3);
+    assert_eq!(svg.matches("fill=\"blue\"").count(), 1);
+    assert_eq!(svg.matches("fill=\"red\"").count(), 1);
+}
+
+#[test]
+fn tikz_works() {
+    let position: Snort = Snort::from_str("L.R.<2>;0-1,1-2,2-3").unwrap();
+    let tikz = position.to_tikz_with_scale(0.5);
+    assert!(tikz.starts_with(r"\begin{tikzpicture}[scale=0.5] "));
+    assert!(tikz.ends_with(r"\end{tikzpicture}"));
+    assert_eq!(tikz.matches(r"\draw ").count(), 3);
+    assert_eq!(tikz.matches("draw,circle").count(), 3);
+    assert_eq!(tikz.matches("draw,rectangle,fill=white").count(), 1);
+    assert_eq!(tikz.matches("fill=blue").count(), 1);
+}
+/// Canonical forms of Snort on paths with 0, 1, 2, ... empty vertices
+const PATH_VALUES: [&str; 10] = [
+    "0",
+    "*",
+    "{1|-1}",
+    "{2|-2}",
+    "{{2|1}|{-1|-2}}",
+    "{1, {3|0}|-1, {0|-3}}",
+    "*",
+    "{1, {{4|3}|{*|-1*}}, {{4|3}|{1|-1}, {1*|*}}|-1, {{*|-1*}, {1|-1}|{-3|-4}}, {{1*|*}|{-3|-4}}}",
+    "{{{5|2}, {5|2*}|{2|-2}, {2*|-2}, {{2|1}|{0|-1}}}|{{2|-2}, {2|-2*}, {{1|0}|{-1|-2}}|{-2|-5}, {-2*|-5}}}",
+    "{2*|-2*}",
+];
+
+/// Canonical forms of Snort on odd cycles with 3, 5, 7, ... empty vertices
+const ODD_CYCLE_VALUES: [&str; 6] = [
+    "{2|-2}",
+    "*",
+    "{1*|-1*}",
+    "{{{6|2, {3|1}}|1}|{-1|{-2, {-1|-3}|-6}}}",
+    "*",
+    "{1*|-1*}",
+];
+
+/// Get value of position on a standard graph family without searching the game tree
+///
+/// Known values are:
+/// - Complete graph `K_n` and star with `n` leaves: `±(n-1)` and `±n` respectively, as the
+///   first move tints all remaining vertices
+/// - Even cycles: `0`, as second player can answer every move with the opposite vertex
+/// - Paths with up to 9 vertices and odd cycles with up to 13 vertices: precomputed
+///
+/// Returns `None` if position has colored vertices or clusters, is on a directed graph or its
+/// value is not known.
+pub fn known_value<G>(position: &Snort<G>) -> Option<CanonicalForm>
 where
-    G: Graph + Clone + Hash + Eq + Send + Sync,
+    G: Graph,
 {
-    fn left_moves(&self) -> Vec<Self> {
-        self.moves_for::<{ VertexColor::TintLeft as u8 }>()
+    if G::DIRECTED
+        || position
+            .vertices
+            .inner
+            .iter()
+            .any(|vertex| *vertex != VertexKind::Single(VertexColor::Empty))
+    {
+        return None;
     }
 
-    fn right_moves(&self) -> Vec<Self> {
-        self.moves_for::<{ VertexColor::TintRight as u8 }>()
+    let size = position.graph.size();
+    if size <= 1 {
+        return CanonicalForm::from_str(PATH_VALUES[size]).ok();
     }
 
-    /// Decompose the game graph into disconnected components
-    fn decompositions(&self) -> Vec<Self> {
-        let mut visited = vec![false; self.vertices.inner.len()];
-        let mut res = Vec::new();
+    let degrees: Vec<usize> = position.graph.degrees().collect();
+    let switch = |value: usize| {
+        CanonicalForm::new_from_moves(Moves {
+            left: vec![CanonicalForm::new_integer(value as i64)],
+            right: vec![CanonicalForm::new_integer(-(value as i64))],
+        })
+    };
 
-        for v in self.graph.vertices() {
-            if !matches!(self.vertices[v].color(), VertexColor::Taken) && !visited[v.index] {
-                res.push(self.bfs(&mut visited, v));
-            }
-        }
+    if degrees.iter().all(|degree| *degree == size - 1) {
+        return Some(switch(size - 1));
+    }
+    if degrees.iter().filter(|degree| **degree == size - 1).count() == 1
+        && degrees.iter().filter(|degree| **degree == 1).count() == size - 1
+    {
+        return Some(switch(size - 1));
+    }
+    if degrees.iter().any(|degree| *degree > 2) || !position.graph.is_connected() {
+        return None;
+    }
 
-        res
+    let edges = degrees.iter().sum::<usize>() / 2;
+    if edges == size - 1 {
+        return PATH_VALUES
+            .get(size)
+            .map(|value| CanonicalForm::from_str(value).unwrap());
+    }
+    if size.is_multiple_of(2) {
+        return Some(CanonicalForm::new_integer(0));
     }
+    ODD_CYCLE_VALUES
+        .get((size - 3) / 2)
+        .map(|value| CanonicalForm::from_str(value).unwrap())
+}
 
-    fn reductions(&self) -> Option<CanonicalForm> {
-        if let &[vertex] = &self.vertices.inner[..] {
-            let cf = match vertex {
-                VertexKind::Single(VertexColor::Empty) => {
-                    CanonicalForm::new_nimber(DyadicRationalNumber::from(0), Nimber::new(1))
-                }
-                VertexKind::Single(VertexColor::TintLeft) => CanonicalForm::new_integer(1),
-                VertexKind::Single(VertexColor::TintRight) => CanonicalForm::new_integer(-1),
-                VertexKind::Cluster(VertexColor::Empty, cluster_size) => {
-                    let nimber = Nimber::new(cluster_size.get() % 2);
-                    CanonicalForm::new_nimber(DyadicRationalNumber::from(0), nimber)
-                }
-                VertexKind::Cluster(VertexColor::TintLeft, cluster_size) => {
-                    CanonicalForm::new_integer(cluster_size.get() as i64)
-                }
-                VertexKind::Cluster(VertexColor::TintRight, cluster_size) => {
-                    CanonicalForm::new_integer(-(cluster_size.get() as i64))
-                }
-                VertexKind::Single(VertexColor::Taken)
-                | VertexKind::Cluster(VertexColor::Taken, _) => CanonicalForm::new_integer(0),
-            };
-            return Some(cf);
-        }
+impl<G> RandomPosition for Snort<G>
+where
+    G: Graph + Clone,
+{
+    type Parameters = GraphParameters;
+
+    /// Empty position on a [random graph](generators::random_gnp)
+    fn random_position<R>(parameters: &GraphParameters, rng: &mut R) -> Option<Self>
+    where
+        R: Rng,
+    {
+        Some(Self::new(generators::random_gnp(
+            parameters.vertices,
+            parameters.edge_probability,
+            rng,
+        )))
+    }
+}
 
-        None
+impl Chromosome for Snort {
+    /// Apply all [mutation operators](Mutation) with the same rate
+    fn mutate<R>(&mut self, mutation_rate: f32, rng: &mut R)
+    where
+        R: Rng,
+    {
+        Mutation::uniform(mutation_rate).apply(self, rng);
+    }
+
+    /// Cross positions with the [prefix crossover](Crossover::Prefix)
+    fn crossover<R>(&self, other: &Self, rng: &mut R) -> Self
+    where
+        R: Rng,
+    {
+        Crossover::Prefix.apply(self, other, rng)
     }
 }
 
+impl NamedGame for Snort {
+    const NAME: &'static str = "snort";
+}
+
+impl NamedGame for DirectedSnort {
+    const NAME: &'static str = "directed-snort";
+}
+#[test]
+fn adjacency_list_backend() {
+    use crate::{
+        graph::adjacency_list, short::partizan::transposition_table::ParallelTranspositionTable,
+    };
+
+    let edges = [
+        (VertexIndex { index: 0 }, VertexIndex { index: 1 }),
+        (VertexIndex { index: 1 }, VertexIndex { index: 2 }),
+        (VertexIndex { index: 1 }, VertexIndex { index: 3 }),
+        (VertexIndex { index: 3 }, VertexIndex { index: 4 }),
+    ];
+    let matrix = Snort::new(UndirectedGraph::from_edges(5, &edges));
+    let list = Snort::new(adjacency_list::undirected::UndirectedGraph::from_edges(
+        5, &edges,
+    ));
+    assert_eq!(matrix.degree(), list.degree());
+    assert_eq!(matrix.second_degree(), list.second_degree());
+    assert_eq!(
+        matrix.canonical_form(&ParallelTranspositionTable::new()),
+        list.canonical_form(&ParallelTranspositionTable::new())
+    );
+}
+
+#[test]
+fn symmetric_moves_are_skipped() {
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
+
+    // Star with 5 leaves, where moving to any of the leaves gives the same position
+    let star: Snort = Snort::new(crate::graph::generators::star(5));
+    assert_eq!(star.left_moves().len(), 2);
+    assert_eq!(star.right_moves().len(), 2);
+
+    // Twins are collapsed before looking for other symmetries, keeping the first of them
+    let bipartite: Snort = Snort::from_str("L....;0-2,0-3,0-4,1-2,1-3,1-4").unwrap();
+    assert_eq!(
+        bipartite
+            .moves_with_vertices(Player::Left)
+            .into_iter()
+            .map(|(m, _)| m.vertex.index)
+            .collect::<Vec<_>>(),
+        vec![0, 1, 2]
+    );
+
+    // Same star expressed with a cluster of leaves
+    let cluster = Snort::with_colors(
+        vec![
+            VertexKind::Single(VertexColor::Empty),
+            VertexKind::Cluster(VertexColor::Empty, NonZeroU32::new(5).unwrap()),
+        ],
+        UndirectedGraph::from_edges(2, &[(VertexIndex { index: 0 }, VertexIndex { index: 1 })]),
+    )
+    .unwrap();
+    assert_eq!(
+        star.canonical_form(&ParallelTranspositionTable::new()),
+        cluster.canonical_form(&ParallelTranspositionTable::new())
+    );
+}
+
+#[test]
+fn compress_clusters_works() {
+    use crate::{
+        graph::generators, short::partizan::transposition_table::ParallelTranspositionTable,
+    };
+
+    let star: Snort = Snort::new(generators::star(5));
+    let compressed = star.compress_clusters();
+    assert_eq!(compressed.to_string(), "..<5>;0-1");
+    assert_eq!(
+        star.canonical_form(&ParallelTranspositionTable::new()),
+        compressed.canonical_form(&ParallelTranspositionTable::new())
+    );
+
+    // Leaves tinted differently cannot be merged
+    let position: Snort = Snort::from_str("L.LRL<2>;0-1,0-2,0-3,0-4").unwrap();
+    let compressed = position.compress_clusters();
+    assert_eq!(compressed.to_string(), "L.L<3>R;0-1,0-2,0-3");
+    assert_eq!(
+        position.canonical_form(&ParallelTranspositionTable::new()),
+        compressed.canonical_form(&ParallelTranspositionTable::new())
+    );
+}
+
+#[test]
+fn graphviz_round_trip() {
+    let snort: Snort = Snort::with_colors(
+        vec![
+            VertexKind::Single(VertexColor::TintLeft),
+            VertexKind::Cluster(VertexColor::Empty, NonZeroU32::new(3).unwrap()),
+            VertexKind::Single(VertexColor::TintRight),
+        ],
+        UndirectedGraph::from_edges(
+            3,
+            &[
+                (VertexIndex { index: 0 }, VertexIndex { index: 1 }),
+                (VertexIndex { index: 1 }, VertexIndex { index: 2 }),
+            ],
+        ),
+    )
+    .unwrap();
+    assert_eq!(
+        Snort::from_graphviz(&snort.to_graphviz()),
+        Some(snort.clone())
+    );
+    assert_eq!(Snort::from_graphml(&snort.to_graphml()), Some(snort));
+
+    let path: Snort = Snort::from_graphviz("graph { a -- b -- c; a [fillcolor=blue] }").unwrap();
+    assert_eq!(
+        path.vertices[VertexIndex { index: 0 }],
+        VertexKind::Single(VertexColor::TintLeft)
+    );
+    assert_eq!(path.graph.degrees().collect::<Vec<_>>(), vec![1, 2, 1]);
+
+    assert_eq!(
+        Snort::<UndirectedGraph>::from_graphviz("graph { a [color=green] }"),
+        None
+    );
+}
+
+#[test]
+fn graphviz_style() {
+    let position: Snort = Snort::from_str("L#.<2>;0-2").unwrap();
+    assert_eq!(
+        position.to_graphviz(),
+        "graph G {0 [label=\"0\", fillcolor=blue, style=filled, shape=circle, fixedsize=true, \
+         width=1, height=1, fontsize=24];2 [label=\"2\\n<2>\", fillcolor=white, style=filled, \
+         shape=square, fixedsize=true, width=1, height=1, fontsize=24];0 -- 2;}"
+    );
+
+    let style = GraphvizStyle {
+        left_color: "lightblue".to_owned(),
+        show_taken: true,
+        vertex_names: vec!["a".to_owned(), "b".to_owned()],
+        layout: Some("neato".to_owned()),
+        vertex_attributes: String::new(),
+        edge_attributes: "penwidth=2".to_owned(),
+        ..GraphvizStyle::default()
+    };
+    assert_eq!(
+        position.to_graphviz_with_style(&style),
+        "graph G {layout=neato;0 [label=\"a\", fillcolor=lightblue, style=filled, shape=circle];\
+         1 [label=\"b\", fillcolor=gray, style=filled, shape=circle];\
+         2 [label=\"2\\n<2>\", fillcolor=white, style=filled, shape=square];\
+         0 -- 2 [penwidth=2];}"
+    );
+
+    let style = GraphvizStyle {
+        show_taken: true,
+        ..GraphvizStyle::default()
+    };
+    assert_eq!(
+        Snort::from_graphviz(&position.to_graphviz_with_style(&style)),
+        Some(position)
+    );
+    assert!(DirectedSnort::from_str("..;1>0")
+        .unwrap()
+        .to_graphviz()
+        .ends_with("1 -> 0;}"));
+}
+
+#[test]
+fn disjoint_union_is_sum() {
+    use crate::{
+        graph::generators, short::partizan::transposition_table::ParallelTranspositionTable,
+    };
+
+    let transposition_table = ParallelTranspositionTable::new();
+    let path: Snort = Snort::new(generators::path(2));
+    let star: Snort = Snort::new(generators::star(3));
+    assert_eq!(
+        path.disjoint_union(&star)
+            .canonical_form(&transposition_table),
+        path.canonical_form(&transposition_table) + star.canonical_form(&transposition_table)
+    );
+}
+
 #[test]
 fn no_moves() {
     let position = Snort::new(UndirectedGraph::empty(0));
@@ -530,3 +679,101 @@ fn correct_sensible() {
         .unwrap()]
     );
 }
+
+#[test]
+fn known_values_match_search() {
+    use crate::{
+        graph::generators, short::partizan::transposition_table::ParallelTranspositionTable,
+    };
+
+    let transposition_table = ParallelTranspositionTable::new();
+    let search = |position: Snort| {
+        CanonicalForm::new_from_moves(Moves {
+            left: position
+                .left_moves()
+                .iter()
+                .map(|option| option.canonical_form(&transposition_table))
+                .collect(),
+            right: position
+                .right_moves()
+                .iter()
+                .map(|option| option.canonical_form(&transposition_table))
+                .collect(),
+        })
+    };
+
+    for size in 2..=13 {
+        for position in [
+            Snort::new(generators::path(size)),
+            Snort::new(generators::cycle(size)),
+            Snort::new(generators::star(size)),
+            Snort::new(generators::complete(size)),
+        ] {
+            if let Some(value) = known_value(&position) {
+                assert_eq!(value, search(position));
+            }
+        }
+    }
+
+    assert_eq!(
+        known_value(&Snort::<UndirectedGraph>::new(generators::star(4))),
+        Some(CanonicalForm::from_str("{4|-4}").unwrap())
+    );
+    assert_eq!(
+        known_value(&Snort::<UndirectedGraph>::new(generators::path(10))),
+        None
+    );
+    assert_eq!(
+        known_value(&Snort::<UndirectedGraph>::from_str("L..;0-1,1-2").unwrap()),
+        None
+    );
+}
+
+#[cfg(test)]
+fn value_from_options<G>(position: &Snort<G>) -> CanonicalForm
+where
+    G: Graph + Clone + Hash + Eq + Send + Sync,
+{
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
+
+    let transposition_table = ParallelTranspositionTable::new();
+    CanonicalForm::new_from_moves(Moves {
+        left: position
+            .left_moves()
+            .iter()
+            .map(|option| option.canonical_form(&transposition_table))
+            .collect(),
+        right: position
+            .right_moves()
+            .iter()
+            .map(|option| option.canonical_form(&transposition_table))
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #![proptest_config(proptest::prelude::ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn decompositions_are_sound(position in crate::strategies::snort::<UndirectedGraph>(6)) {
+        use crate::short::partizan::transposition_table::ParallelTranspositionTable;
+
+        proptest::prop_assert_eq!(
+            position.canonical_form(&ParallelTranspositionTable::new()),
+            value_from_options(&position)
+        );
+    }
+
+    #[test]
+    fn directed_decompositions_are_sound(
+        position in crate::strategies::snort::<DirectedGraph>(5)
+    ) {
+        use crate::short::partizan::transposition_table::ParallelTranspositionTable;
+
+        proptest::prop_assert_eq!(
+            position.canonical_form(&ParallelTranspositionTable::new()),
+            value_from_options(&position)
+        );
+    }
+}
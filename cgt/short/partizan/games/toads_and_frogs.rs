@@ -7,11 +7,18 @@
 
 use crate::{
     drawing::svg::{self, ImmSvg, Svg},
-    grid::CharTile,
-    short::partizan::partizan_game::PartizanGame,
+    genetic::Chromosome,
+    grid::{CharTile, ParseGridError},
+    short::partizan::{
+        partizan_game::PartizanGame,
+        position::NamedGame,
+        random_position::{sample_pieces, GridParameters, RandomPosition},
+    },
 };
 use cgt_derive::Tile;
+use rand::{seq::SliceRandom, Rng};
 use std::{
+    cmp::min,
     fmt::{self, Display},
     str::FromStr,
 };
@@ -45,15 +52,21 @@ impl ToadsAndFrogs {
     pub fn new(tiles: Vec<Tile>) -> Self {
         Self { tiles }
     }
+
+    /// Get tiles of the row
+    #[inline]
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
 }
 
 impl FromStr for ToadsAndFrogs {
-    type Err = ();
+    type Err = ParseGridError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut tiles = Vec::with_capacity(s.len());
         for c in s.chars() {
-            tiles.push(Tile::char_to_tile(c).ok_or(())?);
+            tiles.push(Tile::char_to_tile(c).ok_or(ParseGridError::InvalidTile(c))?);
         }
         Ok(Self::new(tiles))
     }
@@ -123,6 +136,10 @@ impl Svg for ToadsAndFrogs {
     }
 }
 
+impl NamedGame for ToadsAndFrogs {
+    const NAME: &'static str = "toads-and-frogs";
+}
+
 impl PartizanGame for ToadsAndFrogs {
     fn left_moves(&self) -> Vec<Self> {
         let own = Tile::Toad;
@@ -181,6 +198,52 @@ impl PartizanGame for ToadsAndFrogs {
     }
 }
 
+impl RandomPosition for ToadsAndFrogs {
+    type Parameters = GridParameters;
+
+    /// Row of `width` tiles with `pieces` toads and frogs. Height and density are ignored.
+    fn random_position<R>(parameters: &GridParameters, rng: &mut R) -> Option<Self>
+    where
+        R: Rng,
+    {
+        let mut tiles = vec![Tile::Empty; parameters.width as usize];
+        let creatures = sample_pieces(tiles.len(), parameters.pieces, rng)?;
+        for (i, tile) in creatures.into_iter().enumerate() {
+            tiles[tile] = if i < parameters.pieces as usize {
+                Tile::Toad
+            } else {
+                Tile::Frog
+            };
+        }
+        Some(Self::new(tiles))
+    }
+}
+
+impl Chromosome for ToadsAndFrogs {
+    fn mutate<R>(&mut self, mutation_rate: f32, rng: &mut R)
+    where
+        R: Rng,
+    {
+        let available_tiles = [Tile::Empty, Tile::Toad, Tile::Frog];
+        for tile in &mut self.tiles {
+            if rng.gen::<f32>() < mutation_rate {
+                *tile = *available_tiles.choose(rng).unwrap();
+            }
+        }
+    }
+
+    /// Take tiles before a random split from `self` and the rest from `other`
+    fn crossover<R>(&self, other: &Self, rng: &mut R) -> Self
+    where
+        R: Rng,
+    {
+        let split = rng.gen_range(0..=min(self.tiles.len(), other.tiles.len()));
+        let mut tiles = self.tiles[..split].to_vec();
+        tiles.extend_from_slice(&other.tiles[split..]);
+        Self::new(tiles)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::short::partizan::{
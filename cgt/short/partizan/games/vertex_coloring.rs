@@ -0,0 +1,1600 @@
+//! Vertex coloring games played on graphs.
+//!
+//! In each turn Left colors a vertex blue and Right colors a vertex red. Which vertices are
+//! legal is controlled by [`Rules`], that say whether a player may color a vertex that is
+//! adjacent to a vertex of their own color, or to a vertex of their opponent's color.
+//!
+//! [Snort](super::snort) and [Col](super::col) are special cases of this game, and
+//! [`ColoringRules`] allow any restriction matrix to be chosen at runtime.
+//!
+//! On directed graphs only the out-neighborhood restricts the choice, i.e. a colored vertex
+//! restricts only the vertices with an edge to it.
+
+use crate::{
+    drawing::svg::{self, ImmSvg, Svg},
+    error::Error,
+    graph::{
+        adjacency_matrix::undirected::UndirectedGraph, canonical_labeling::CanonicalLabeling,
+        dot::Dot, generators, graphml::GraphMl, layout::circle_spring_layout, Graph, VertexIndex,
+    },
+    numeric::{dyadic_rational_number::DyadicRationalNumber, nimber::Nimber},
+    short::partizan::{
+        canonical_form::CanonicalForm,
+        partizan_game::PartizanGame,
+        position::NamedGame,
+        random_position::{GraphParameters, RandomPosition},
+        simplification::Player,
+    },
+    zobrist::{zobrist_key, ZobristHash},
+};
+use rand::Rng;
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
+    fmt::{self, Debug, Display, Write},
+    hash::Hash,
+    num::NonZeroU32,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+/// Color of vertex. Note that we are taking tinting apporach rather than direct tracking
+/// of adjacent colors.
+///
+/// Colored vertices are removed from the graph, and their neighbors are tinted with players
+/// that can still color them.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_repr::Serialize_repr, serde_repr::Deserialize_repr)
+)]
+#[repr(u8)]
+pub enum VertexColor {
+    /// Vertex that both players can color
+    Empty = 0,
+
+    /// Vertex that only Left can color, e.g. in Snort a vertex adjacent to left
+    TintLeft = 1,
+
+    /// Vertex that only Right can color, e.g. in Snort a vertex adjacent to right
+    TintRight = 2,
+
+    /// Vertex that is either taken or cannot be colored by any player
+    Taken = 3,
+}
+
+impl VertexColor {
+    /// Character used in the text format of [`VertexColoring`] positions
+    #[inline]
+    const fn to_char(self) -> char {
+        match self {
+            Self::Empty => '.',
+            Self::TintLeft => 'L',
+            Self::TintRight => 'R',
+            Self::Taken => '#',
+        }
+    }
+
+    #[inline]
+    const fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Empty),
+            'L' => Some(Self::TintLeft),
+            'R' => Some(Self::TintRight),
+            '#' => Some(Self::Taken),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<u8> for VertexColor {
+    type Error = ();
+
+    #[inline]
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Empty),
+            1 => Ok(Self::TintLeft),
+            2 => Ok(Self::TintRight),
+            3 => Ok(Self::Taken),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Type of vertex (or group of them) in the graph. We abstract over vertices to support efficient
+/// calculations of positions with star-like structure
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VertexKind {
+    /// Single graph vertex
+    Single(VertexColor),
+
+    /// Cluster of vertices that are not connected to each other, but may be connected to other
+    /// vertices in the graph.
+    Cluster(VertexColor, NonZeroU32),
+}
+
+impl VertexKind {
+    #[inline]
+    /// Get color of the vertex
+    pub const fn color(self) -> VertexColor {
+        match self {
+            Self::Single(color) | Self::Cluster(color, _) => color,
+        }
+    }
+
+    #[inline]
+    /// Get mutable color of the vertex
+    pub const fn color_mut(&mut self) -> &mut VertexColor {
+        match self {
+            Self::Single(color) | Self::Cluster(color, _) => color,
+        }
+    }
+
+    /// Vertices of the same class are interchangeable when looking for graph symmetries
+    #[inline]
+    const fn symmetry_class(self) -> usize {
+        match self {
+            Self::Single(color) => color as usize,
+            Self::Cluster(color, cluster_size) => 4 * cluster_size.get() as usize + color as usize,
+        }
+    }
+
+    #[inline]
+    const fn degree_factor(self) -> usize {
+        match self {
+            Self::Single(_) => 1,
+            Self::Cluster(_, cluster_size) => cluster_size.get() as usize,
+        }
+    }
+}
+
+/// Color of vertices that only `player` can color
+#[inline]
+const fn own_tint_color(player: Player) -> VertexColor {
+    match player {
+        Player::Left => VertexColor::TintLeft,
+        Player::Right => VertexColor::TintRight,
+    }
+}
+
+/// Adjacency restrictions of a [vertex coloring](self) game
+pub trait Rules:
+    Debug + Clone + Copy + Default + Hash + PartialEq + Eq + Send + Sync + 'static
+{
+    /// Check if `player` can color a vertex that is adjacent to a vertex colored by `neighbor`
+    fn allows(self, player: Player, neighbor: Player) -> bool;
+
+    /// Color that `player` tints vertices restricted by their move, i.e. players that can still
+    /// color them
+    fn tint_color(self, player: Player) -> VertexColor {
+        match (
+            self.allows(Player::Left, player),
+            self.allows(Player::Right, player),
+        ) {
+            (true, true) => VertexColor::Empty,
+            (true, false) => VertexColor::TintLeft,
+            (false, true) => VertexColor::TintRight,
+            (false, false) => VertexColor::Taken,
+        }
+    }
+
+    /// Rules fixed by the type, like the ones of Snort, are not part of the text format and are
+    /// not serialized
+    fn is_fixed(&self) -> bool {
+        true
+    }
+
+    /// Write rules that are not [fixed](Rules::is_fixed) as the prefix of the text format of
+    /// [`VertexColoring`], followed by `;`
+    ///
+    /// # Errors
+    /// - Formatter returns an error
+    fn write_rules(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let _ = f;
+        Ok(())
+    }
+
+    /// Parse rules written by [`Rules::write_rules`] from the start of `input`, returning them
+    /// with the rest of the input
+    ///
+    /// # Errors
+    /// - Rules are missing or invalid
+    fn parse_rules(input: &str) -> Result<(Self, &str), Error> {
+        Ok((Self::default(), input))
+    }
+
+    /// Get value of position without searching the game tree, used by
+    /// [`PartizanGame::reductions`]
+    fn known_value<G>(position: &VertexColoring<Self, G>) -> Option<CanonicalForm>
+    where
+        G: Graph,
+    {
+        let _ = position;
+        None
+    }
+}
+
+/// Adjacency restriction matrix chosen at runtime. Default rules are the ones of Snort.
+///
+/// Entry `matrix[player][neighbor]` says whether `player` is allowed to color a vertex that is
+/// adjacent to a vertex already colored by `neighbor`, where `0` is Left and `1` is Right.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColoringRules {
+    /// Restriction matrix
+    pub matrix: [[bool; 2]; 2],
+}
+
+impl ColoringRules {
+    /// Create new rules from restriction matrix. See [`ColoringRules`] for the layout.
+    pub const fn new(matrix: [[bool; 2]; 2]) -> Self {
+        Self { matrix }
+    }
+
+    /// Rules of Snort - players cannot color vertices adjacent to opponent's vertices
+    pub const fn snort() -> Self {
+        Self::new([[true, false], [false, true]])
+    }
+
+    /// Rules of Col - players cannot color vertices adjacent to their own vertices
+    pub const fn col() -> Self {
+        Self::new([[false, true], [true, false]])
+    }
+
+    const fn player_index(player: Player) -> usize {
+        match player {
+            Player::Left => 0,
+            Player::Right => 1,
+        }
+    }
+}
+
+impl Default for ColoringRules {
+    fn default() -> Self {
+        Self::snort()
+    }
+}
+
+/// Rules are written as `snort`, `col`, or the restriction matrix as four digits in row order
+/// (e.g. `1001` is Snort)
+impl Rules for ColoringRules {
+    fn allows(self, player: Player, neighbor: Player) -> bool {
+        self.matrix[Self::player_index(player)][Self::player_index(neighbor)]
+    }
+
+    fn is_fixed(&self) -> bool {
+        false
+    }
+
+    fn write_rules(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self == Self::snort() {
+            write!(f, "snort")?;
+        } else if self == Self::col() {
+            write!(f, "col")?;
+        } else {
+            for allowed in self.matrix.iter().flatten() {
+                write!(f, "{}", u8::from(*allowed))?;
+            }
+        }
+        write!(f, ";")
+    }
+
+    fn parse_rules(input: &str) -> Result<(Self, &str), Error> {
+        let (rules, rest) = input
+            .split_once(';')
+            .ok_or_else(|| Error::Syntax("missing coloring rules".to_owned()))?;
+
+        let rules = match rules.trim() {
+            "snort" => Self::snort(),
+            "col" => Self::col(),
+            matrix => {
+                let invalid_rules = || Error::Syntax(format!("invalid coloring rules '{matrix}'"));
+                let allowed = matrix
+                    .chars()
+                    .map(|c| match c {
+                        '0' => Ok(false),
+                        '1' => Ok(true),
+                        _ => Err(invalid_rules()),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let &[ll, lr, rl, rr] = &allowed[..] else {
+                    return Err(invalid_rules());
+                };
+                Self::new([[ll, lr], [rl, rr]])
+            }
+        };
+        Ok((rules, rest))
+    }
+}
+
+/// Reason why [`VertexColoring::with_colors`] rejected a position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum InvalidPosition {
+    /// Number of vertex colors differs from the number of graph vertices
+    #[error("got {vertices} vertex colors for a graph with {graph} vertices")]
+    SizeMismatch {
+        /// Number of vertex colors
+        vertices: usize,
+
+        /// Number of graph vertices
+        graph: usize,
+    },
+
+    /// Vertex is connected to itself
+    #[error("vertex {} is connected to itself", .0.index)]
+    Loop(VertexIndex),
+
+    /// Taken vertex is still connected to other vertices
+    #[error("taken vertex {} has edges", .0.index)]
+    ConnectedTakenVertex(VertexIndex),
+}
+
+/// Style of [`VertexColoring::to_graphviz_with_style`] output. Default style is used by
+/// [`VertexColoring::to_graphviz`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphvizStyle {
+    /// Fill color of empty vertices
+    pub empty_color: String,
+
+    /// Fill color of vertices tinted by Left
+    pub left_color: String,
+
+    /// Fill color of vertices tinted by Right
+    pub right_color: String,
+
+    /// Fill color of taken vertices
+    pub taken_color: String,
+
+    /// Render taken vertices. They are skipped by default
+    pub show_taken: bool,
+
+    /// Show vertex names, and cluster sizes of clusters, in vertex labels
+    pub show_labels: bool,
+
+    /// Names of vertices used in labels. Vertices without a name are labeled with their index
+    pub vertex_names: Vec<String>,
+
+    /// Layout engine hint, e.g. `neato`, written as the `layout` graph attribute
+    pub layout: Option<String>,
+
+    /// Additional attributes of every vertex, e.g. `fontsize=24`
+    pub vertex_attributes: String,
+
+    /// Attributes of every edge, e.g. `penwidth=2`
+    pub edge_attributes: String,
+}
+
+impl Default for GraphvizStyle {
+    fn default() -> Self {
+        Self {
+            empty_color: "white".to_owned(),
+            left_color: "blue".to_owned(),
+            right_color: "red".to_owned(),
+            taken_color: "gray".to_owned(),
+            show_taken: false,
+            show_labels: true,
+            vertex_names: Vec::new(),
+            layout: None,
+            vertex_attributes: "fixedsize=true, width=1, height=1, fontsize=24".to_owned(),
+            edge_attributes: String::new(),
+        }
+    }
+}
+
+/// Move in a [`VertexColoring`] position
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Move {
+    /// Vertex colored by the move. If it is a cluster, one of its vertices is colored
+    pub vertex: VertexIndex,
+
+    /// Player making the move
+    pub player: Player,
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} colors {}", self.player, self.vertex.index)
+    }
+}
+
+/// Vertices colors of the game graph
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VertexColors {
+    /// Color of each vertex
+    pub inner: Vec<VertexKind>,
+}
+
+impl Index<VertexIndex> for VertexColors {
+    type Output = VertexKind;
+
+    fn index(&self, index: VertexIndex) -> &Self::Output {
+        &self.inner[index.index]
+    }
+}
+
+impl IndexMut<VertexIndex> for VertexColors {
+    fn index_mut(&mut self, index: VertexIndex) -> &mut Self::Output {
+        &mut self.inner[index.index]
+    }
+}
+
+/// Position of a [vertex coloring](self) game with rules `R`
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VertexColoring<R = ColoringRules, G = UndirectedGraph> {
+    /// Vertices colors of the game graph
+    pub vertices: VertexColors,
+
+    /// Get graph of the game. This includes only edges
+    pub graph: G,
+
+    /// Adjacency restrictions
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Rules::is_fixed",
+            bound(serialize = "R: Rules + serde::Serialize")
+        )
+    )]
+    pub rules: R,
+}
+
+impl<R, G> VertexColoring<R, G>
+where
+    R: Rules,
+    G: Graph + Clone,
+{
+    /// Create new position with all vertices empty and default rules.
+    pub fn new(graph: G) -> Self {
+        Self {
+            vertices: VertexColors {
+                inner: vec![VertexKind::Single(VertexColor::Empty); graph.size()],
+            },
+            graph,
+            rules: R::default(),
+        }
+    }
+
+    /// Replace rules of the position
+    #[must_use]
+    pub fn with_rules(self, rules: R) -> Self {
+        Self { rules, ..self }
+    }
+
+    /// Create a position with initial colors and default rules.
+    ///
+    /// Tinted vertices may be adjacent to each other, even if tinted by different players, as
+    /// tint only records the color of already removed neighbors. Taken vertices are out of the
+    /// game, so they must not have any edges.
+    ///
+    /// # Errors
+    /// - `vertices` and `graph` have conflicting sizes
+    /// - A vertex is connected to itself
+    /// - A taken vertex is connected to other vertices
+    pub fn with_colors(vertices: Vec<VertexKind>, graph: G) -> Result<Self, InvalidPosition> {
+        if vertices.len() != graph.size() {
+            return Err(InvalidPosition::SizeMismatch {
+                vertices: vertices.len(),
+                graph: graph.size(),
+            });
+        }
+
+        for vertex in graph.vertices() {
+            if graph.are_adjacent(vertex, vertex) {
+                return Err(InvalidPosition::Loop(vertex));
+            }
+            if vertices[vertex.index].color() == VertexColor::Taken
+                && (graph.adjacent_to(vertex).next().is_some()
+                    || (G::DIRECTED && graph.vertices().any(|u| graph.are_adjacent(u, vertex))))
+            {
+                return Err(InvalidPosition::ConnectedTakenVertex(vertex));
+            }
+        }
+
+        Ok(Self {
+            vertices: VertexColors { inner: vertices },
+            graph,
+            rules: R::default(),
+        })
+    }
+
+    /// Construct position that is a sum of two positions, by placing their graphs side by side.
+    /// Both positions must have the same rules.
+    ///
+    /// # Panics
+    /// - Positions have different rules
+    #[must_use]
+    pub fn disjoint_union(&self, other: &Self) -> Self {
+        assert_eq!(self.rules, other.rules, "Positions have different rules");
+        let mut vertices = self.vertices.inner.clone();
+        vertices.extend_from_slice(&other.vertices.inner);
+        Self {
+            vertices: VertexColors { inner: vertices },
+            graph: self.graph.disjoint_union(&other.graph),
+            rules: self.rules,
+        }
+    }
+
+    /// Merge vertices of the same color with identical neighborhoods into clusters. In directed
+    /// graphs both incoming and outgoing edges must be the same.
+    ///
+    /// Such vertices are never adjacent to each other, and a move on any of them tints the same
+    /// vertices, so a single [`VertexKind::Cluster`] is equivalent to all of them while making
+    /// the graph smaller. Order of remaining vertices is preserved.
+    #[must_use]
+    pub fn compress_clusters(&self) -> Self {
+        let mut representatives: Vec<VertexIndex> = Vec::with_capacity(self.graph.size());
+        let mut sizes: Vec<u32> = Vec::with_capacity(self.graph.size());
+        let mut seen: HashMap<_, usize> = HashMap::new();
+
+        for vertex in self.graph.vertices() {
+            let kind = self.vertices[vertex];
+            let size = kind.degree_factor() as u32;
+
+            match seen.entry((kind.color(), self.neighborhood(vertex))) {
+                Entry::Occupied(entry) => sizes[*entry.get()] += size,
+                Entry::Vacant(entry) => {
+                    entry.insert(representatives.len());
+                    representatives.push(vertex);
+                    sizes.push(size);
+                }
+            }
+        }
+
+        let vertices = representatives
+            .iter()
+            .zip(sizes)
+            .map(|(vertex, size)| {
+                let color = self.vertices[*vertex].color();
+                if size == 1 {
+                    VertexKind::Single(color)
+                } else {
+                    VertexKind::Cluster(color, NonZeroU32::new(size).unwrap())
+                }
+            })
+            .collect();
+
+        Self {
+            vertices: VertexColors { inner: vertices },
+            graph: self.graph.induced_subgraph(&representatives),
+            rules: self.rules,
+        }
+    }
+
+    /// Relabel vertices, so that positions isomorphic as graphs with vertex colors and cluster
+    /// sizes have identical representations. See [`CanonicalLabeling`].
+    #[must_use]
+    pub fn canonically_labeled(&self) -> Self {
+        let classes: Vec<usize> = self
+            .vertices
+            .inner
+            .iter()
+            .map(|vertex| vertex.symmetry_class())
+            .collect();
+        let labeling = CanonicalLabeling::new(&self.graph, &classes);
+
+        let mut vertices = self.vertices.inner.clone();
+        for (vertex, label) in self.vertices.inner.iter().zip(labeling.labeling()) {
+            vertices[label.index] = *vertex;
+        }
+        Self {
+            vertices: VertexColors { inner: vertices },
+            graph: labeling.apply(&self.graph),
+            rules: self.rules,
+        }
+    }
+
+    fn vertex_degree(&self, this_vertex: VertexIndex) -> usize {
+        self.graph
+            .adjacent_to(this_vertex)
+            .filter(|one_away| *one_away != this_vertex)
+            .map(|one_away| self.vertices[one_away].degree_factor())
+            .sum()
+    }
+
+    fn vertex_second_degree(&self, this_vertex: VertexIndex) -> usize {
+        let mut res = 0;
+        let mut seen = vec![false; self.graph.size()];
+
+        for one_away in self.graph.adjacent_to(this_vertex) {
+            if one_away == this_vertex {
+                continue;
+            }
+            for two_away in self.graph.adjacent_to(one_away) {
+                if two_away != one_away && two_away != this_vertex && !seen[two_away.index] {
+                    seen[two_away.index] = true;
+                    res += self.vertices[two_away].degree_factor();
+                }
+            }
+        }
+
+        res
+    }
+
+    /// Get degree of the underlying game graph, correctly counting clusters of vertices
+    ///
+    /// Note that using [`Graph::degree`] will yield incorrect results
+    pub fn degree(&self) -> usize {
+        self.graph
+            .vertices()
+            .map(|v| self.vertex_degree(v))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Get second degree of the underlying game graph
+    ///
+    /// Second degree of a vertex is the number of all vertices two away from a given vertex
+    /// and just like in first degree, second degree of a graph is the maximum value among vertices
+    pub fn second_degree(&self) -> usize {
+        self.graph
+            .vertices()
+            .map(|v| self.vertex_second_degree(v))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Get options of a given player together with moves leading to them
+    ///
+    /// Moves to vertices that are symmetric to already listed ones are skipped, as they lead
+    /// to isomorphic positions. Use [`Self::apply_move`] to make any legal move.
+    pub fn moves_with_vertices(&self, player: Player) -> Vec<(Move, Self)> {
+        self.moves_with_vertices_iter(player).collect()
+    }
+
+    /// Lazy version of [`Self::moves_with_vertices`]. Vertices to move to are chosen upfront,
+    /// but resulting positions are created only when iterator is advanced.
+    pub fn moves_with_vertices_iter(
+        &self,
+        player: Player,
+    ) -> impl Iterator<Item = (Move, Self)> + '_ {
+        let own_tint_color = own_tint_color(player);
+
+        // Vertices where player can move. Vertices of the same kind with the same neighbors can
+        // be swapped, so moves to them lead to the same position. That is cheap to check and
+        // often leaves only one vertex, so we don't have to look for other symmetries.
+        let mut seen = HashSet::new();
+        let mut move_vertices: Vec<VertexIndex> = self
+            .graph
+            .vertices()
+            .filter(|vertex| {
+                let vertex_kind = self.vertices[*vertex];
+                let vertex_color = vertex_kind.color();
+                (vertex_color == own_tint_color || vertex_color == VertexColor::Empty)
+                    && seen.insert((vertex_kind, self.neighborhood(*vertex)))
+            })
+            .collect();
+
+        // Moves to vertices in the same orbit of the automorphism group of the colored graph
+        // lead to isomorphic positions, so we consider only one vertex from each orbit
+        if move_vertices.len() > 1 {
+            let classes: Vec<usize> = self
+                .vertices
+                .inner
+                .iter()
+                .map(|vertex| vertex.symmetry_class())
+                .collect();
+            let orbits = CanonicalLabeling::new(&self.graph, &classes).orbits();
+            move_vertices.retain(|vertex| orbits[vertex.index] == *vertex);
+        }
+
+        // Go through list of vertices with legal move
+        move_vertices.into_iter().map(move |vertex| {
+            let m = Move { vertex, player };
+            (m, self.make_move(vertex, player))
+        })
+    }
+
+    /// Color a vertex. Returns `None` if the vertex does not exist or `player` cannot color it.
+    pub fn apply_move(&self, m: Move) -> Option<Self> {
+        let own_tint_color = own_tint_color(m.player);
+        let vertex_color = self.vertices.inner.get(m.vertex.index)?.color();
+        if vertex_color != own_tint_color && vertex_color != VertexColor::Empty {
+            return None;
+        }
+        Some(self.make_move(m.vertex, m.player))
+    }
+
+    /// Color `move_vertex_idx` by `player`, assuming that the move is legal
+    fn make_move(&self, move_vertex_idx: VertexIndex, player: Player) -> Self {
+        let mut position: Self = self.clone();
+        let tint_color = self.rules.tint_color(player);
+
+        // Take vertex
+        let move_vertex = &mut position.vertices[move_vertex_idx];
+        match move_vertex {
+            VertexKind::Single(move_vertex_color) => *move_vertex_color = VertexColor::Taken,
+            VertexKind::Cluster(_, cluster_size) => {
+                if *cluster_size == NonZeroU32::new(1).unwrap() {
+                    *move_vertex = VertexKind::Single(VertexColor::Taken);
+                } else {
+                    // Vertices in cluster are disconnected so nothing changes color
+                    *cluster_size = NonZeroU32::new(cluster_size.get() - 1).unwrap();
+                }
+            }
+        }
+
+        // Disconnect move vertex from adjecent, we disconnect only single vertices
+        // because clusters are still alive. If cluster is dead it's turned into single
+        // before (See: 'take vertex' above), so it still works.
+        if let VertexKind::Single(_) = position.vertices[move_vertex_idx] {
+            match self.graph.neighbors_mask(move_vertex_idx) {
+                Some(neighbors) if !G::DIRECTED => {
+                    position
+                        .graph
+                        .connect_mask(move_vertex_idx, neighbors, false);
+                }
+                _ => position.graph.disconnect(move_vertex_idx),
+            }
+        }
+
+        // Tint vertices that are restricted by `move_vertex`. In directed graphs these are the
+        // vertices with an edge to `move_vertex`.
+        if G::DIRECTED {
+            for restricted_vertex_idx in self.predecessors(move_vertex_idx) {
+                position.tint(restricted_vertex_idx, tint_color);
+            }
+        } else {
+            for adjacent_vertex_idx in self.graph.adjacent_to(move_vertex_idx) {
+                // No loops in game graphs
+                if adjacent_vertex_idx != move_vertex_idx {
+                    position.tint(adjacent_vertex_idx, tint_color);
+                }
+            }
+        }
+
+        position
+    }
+
+    /// Tint vertex after a neighboring vertex was colored
+    fn tint(&mut self, vertex_idx: VertexIndex, tint_color: VertexColor) {
+        let vertex_color = self.vertices[vertex_idx].color_mut();
+
+        // Bits of colors mark players that cannot color the vertex, so tints accumulate
+        *vertex_color = VertexColor::try_from(*vertex_color as u8 | tint_color as u8).unwrap();
+        if *vertex_color == VertexColor::Taken {
+            // No one can longer move there, thus we disconnect it from the graph
+            self.graph.disconnect(vertex_idx);
+        }
+    }
+
+    /// Sorted vertices adjacent to `vertex`, and in directed graphs also sorted vertices with
+    /// an edge to `vertex`. Vertices with the same neighborhood are not adjacent to each other.
+    fn neighborhood(&self, vertex: VertexIndex) -> (Vec<VertexIndex>, Vec<VertexIndex>) {
+        let mut neighbors: Vec<VertexIndex> = self.graph.adjacent_to(vertex).collect();
+        neighbors.sort_unstable();
+        let predecessors = if G::DIRECTED {
+            self.predecessors(vertex).collect()
+        } else {
+            Vec::new()
+        };
+        (neighbors, predecessors)
+    }
+
+    /// Vertices other than `vertex` that have an edge to `vertex`
+    fn predecessors(&self, vertex: VertexIndex) -> impl Iterator<Item = VertexIndex> + '_ {
+        self.graph
+            .vertices()
+            .filter(move |&u| u != vertex && self.graph.are_adjacent(u, vertex))
+    }
+
+    /// BFS search to get the decompisitons, should be used only as a helper for [`Self::decompositions`]
+    fn bfs(&self, visited: &mut [bool], v: VertexIndex) -> Self {
+        let mut vertices_to_take: Vec<VertexIndex> = Vec::new();
+
+        let mut q: VecDeque<VertexIndex> = VecDeque::new();
+        q.push_back(v);
+        visited[v.index] = true;
+
+        while let Some(v) = q.pop_front() {
+            vertices_to_take.push(v);
+
+            for u in self.graph.adjacent_to(v) {
+                if !visited[u.index] {
+                    visited[u.index] = true;
+                    q.push_back(u);
+                }
+            }
+
+            // Components of directed graphs are connected ignoring edge directions
+            if G::DIRECTED {
+                for u in self.predecessors(v) {
+                    if !visited[u.index] {
+                        visited[u.index] = true;
+                        q.push_back(u);
+                    }
+                }
+            }
+        }
+
+        let new_graph = self.graph.induced_subgraph(&vertices_to_take);
+
+        let mut new_vertices = Vec::with_capacity(vertices_to_take.len());
+        for v in &vertices_to_take {
+            new_vertices.push(self.vertices[*v]);
+        }
+
+        Self {
+            vertices: VertexColors {
+                inner: new_vertices,
+            },
+            graph: new_graph,
+            rules: self.rules,
+        }
+    }
+
+    /// Output position as LaTeX `TikZ` picture, laid out like [`Self::to_svg`]. Taken vertices
+    /// are skipped.
+    pub fn to_tikz(&self) -> String {
+        self.to_tikz_with_scale(1.)
+    }
+
+    /// Like [`Self::to_tikz`] but allows to specify image scale. Scale must be positive
+    ///
+    /// # Panics
+    /// - `scale` is negative
+    pub fn to_tikz_with_scale(&self, scale: f32) -> String {
+        graph_to_tikz(&self.graph, scale, |v| {
+            let shape = match self.vertices[v] {
+                VertexKind::Single(_) => "circle",
+                VertexKind::Cluster(_, _) => "rectangle",
+            };
+            let fill = match self.vertices[v].color() {
+                VertexColor::Empty => "white",
+                VertexColor::TintLeft => "blue",
+                VertexColor::TintRight => "red",
+                VertexColor::Taken => return None,
+            };
+            Some((shape, fill))
+        })
+    }
+
+    /// Render to a [graphviz](https://graphviz.org/) format, that can be later rendered to an
+    /// image with external engine.
+    pub fn to_graphviz(&self) -> String {
+        self.to_graphviz_with_style(&GraphvizStyle::default())
+    }
+
+    /// Render to a [graphviz](https://graphviz.org/) format with custom colors, labels and
+    /// attributes
+    pub fn to_graphviz_with_style(&self, style: &GraphvizStyle) -> String {
+        let mut buf = String::new();
+
+        let (graph_kind, edge_op) = if G::DIRECTED {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        write!(buf, "{graph_kind} G {{").unwrap();
+        if let Some(layout) = &style.layout {
+            write!(buf, "layout={layout};").unwrap();
+        }
+
+        for (vertex_idx, vertex) in self.vertices.inner.iter().enumerate() {
+            let color = match vertex.color() {
+                VertexColor::Empty => &style.empty_color,
+                VertexColor::TintLeft => &style.left_color,
+                VertexColor::TintRight => &style.right_color,
+                VertexColor::Taken if style.show_taken => &style.taken_color,
+                VertexColor::Taken => continue,
+            };
+            let shape = match vertex {
+                VertexKind::Single(_) => "circle",
+                VertexKind::Cluster(_, _) => "square",
+            };
+            let name = style
+                .vertex_names
+                .get(vertex_idx)
+                .cloned()
+                .unwrap_or_else(|| vertex_idx.to_string());
+            let label = match vertex {
+                _ if !style.show_labels => "\"\"".to_owned(),
+                VertexKind::Single(_) => format!("\"{}\"", name),
+                VertexKind::Cluster(_, cluster_size) => {
+                    format!("\"{}\\n<{}>\"", name, cluster_size.get())
+                }
+            };
+
+            write!(
+                buf,
+                "{} [label={}, fillcolor={}, style=filled, shape={}",
+                vertex_idx, label, color, shape
+            )
+            .unwrap();
+            if !style.vertex_attributes.is_empty() {
+                write!(buf, ", {}", style.vertex_attributes).unwrap();
+            }
+            write!(buf, "];").unwrap();
+        }
+
+        for v in self.graph.vertices() {
+            for u in self.graph.adjacent_to(v) {
+                if G::DIRECTED || v < u {
+                    write!(buf, "{} {edge_op} {}", v.index, u.index).unwrap();
+                    if !style.edge_attributes.is_empty() {
+                        write!(buf, " [{}]", style.edge_attributes).unwrap();
+                    }
+                    write!(buf, ";").unwrap();
+                }
+            }
+        }
+
+        write!(buf, "}}").unwrap();
+        buf
+    }
+
+    /// Load position from [graphviz](https://graphviz.org/) format, in the same style as
+    /// produced by [`Self::to_graphviz`]. Vertex `fillcolor` (or `color`) of `white`, `blue`,
+    /// `red` or `gray` maps to empty, Left-tinted, Right-tinted or taken vertex, with missing
+    /// color meaning empty.
+    /// Vertices with `square` shape are clusters, with size given in label as `<n>`.
+    ///
+    /// Returns `None` if input cannot be parsed or has unknown colors.
+    pub fn from_graphviz(input: &str) -> Option<Self> {
+        let dot: Dot = input.parse().ok()?;
+        let vertices = (0..dot.vertices.len())
+            .map(|index| {
+                let vertex = VertexIndex { index };
+                let color = match dot.fill_color(vertex).unwrap_or("white") {
+                    "white" => VertexColor::Empty,
+                    "blue" => VertexColor::TintLeft,
+                    "red" => VertexColor::TintRight,
+                    "gray" => VertexColor::Taken,
+                    _ => return None,
+                };
+                if dot.attribute(vertex, "shape") != Some("square") {
+                    return Some(VertexKind::Single(color));
+                }
+
+                let label = dot.attribute(vertex, "label")?;
+                let (_, cluster_size) = label.split_once('<')?;
+                let (cluster_size, _) = cluster_size.split_once('>')?;
+                Some(VertexKind::Cluster(color, cluster_size.parse().ok()?))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Self::with_colors(vertices, dot.to_graph()).ok()
+    }
+
+    /// Render to [GraphML](http://graphml.graphdrawing.org/) format. Vertex `color` data is
+    /// `white`, `blue`, `red` or `gray` for empty, Left-tinted, Right-tinted or taken vertex, and
+    /// clusters have additional `cluster_size` data.
+    pub fn to_graphml(&self) -> String {
+        let mut graphml = GraphMl::from_graph(&self.graph, false);
+        for vertex in self.graph.vertices() {
+            let color = match self.vertices[vertex].color() {
+                VertexColor::Empty => "white",
+                VertexColor::TintLeft => "blue",
+                VertexColor::TintRight => "red",
+                VertexColor::Taken => "gray",
+            };
+            graphml.set_data(vertex, "color", color.to_owned());
+            if let VertexKind::Cluster(_, cluster_size) = self.vertices[vertex] {
+                graphml.set_data(vertex, "cluster_size", cluster_size.to_string());
+            }
+        }
+        graphml.to_string()
+    }
+
+    /// Load position from [GraphML](http://graphml.graphdrawing.org/) format, in the same style
+    /// as produced by [`Self::to_graphml`]. Missing color means empty vertex.
+    ///
+    /// Returns `None` if input cannot be parsed or has unknown colors.
+    pub fn from_graphml(input: &str) -> Option<Self> {
+        let graphml: GraphMl = input.parse().ok()?;
+        let vertices = (0..graphml.vertices.len())
+            .map(|index| {
+                let vertex = VertexIndex { index };
+                let color = match graphml.data(vertex, "color").unwrap_or("white") {
+                    "white" => VertexColor::Empty,
+                    "blue" => VertexColor::TintLeft,
+                    "red" => VertexColor::TintRight,
+                    "gray" => VertexColor::Taken,
+                    _ => return None,
+                };
+                match graphml.data(vertex, "cluster_size") {
+                    None => Some(VertexKind::Single(color)),
+                    Some(cluster_size) => {
+                        Some(VertexKind::Cluster(color, cluster_size.parse().ok()?))
+                    }
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Self::with_colors(vertices, graphml.to_graph()).ok()
+    }
+}
+
+/// Compact text format: [rules](Rules::write_rules) that are not fixed by the type, colors of
+/// vertices (`.` empty, `L` tinted by Left, `R` tinted by Right, `#` taken) with cluster sizes in
+/// angle brackets, followed by `;` and comma separated edges. In directed graphs `u>v` is an edge
+/// from `u` to `v`, and `u-v` are edges in both directions.
+///
+/// # Examples
+///
+/// ```
+/// use cgt::short::partizan::games::{
+///     snort::Snort,
+///     vertex_coloring::{ColoringRules, VertexColoring},
+/// };
+/// use std::str::FromStr;
+///
+/// // Left-tinted vertex connected to a cluster of three empty vertices
+/// let position: Snort = Snort::from_str("L.<3>;0-1").unwrap();
+/// assert_eq!(position.to_string(), "L.<3>;0-1");
+///
+/// // Rules chosen at runtime are written first
+/// let position: VertexColoring = VertexColoring::from_str("col;..;0-1").unwrap();
+/// assert_eq!(position.rules, ColoringRules::col());
+/// ```
+impl<R, G> Display for VertexColoring<R, G>
+where
+    R: Rules,
+    G: Graph,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.rules.write_rules(f)?;
+        for vertex in &self.vertices.inner {
+            write!(f, "{}", vertex.color().to_char())?;
+            if let VertexKind::Cluster(_, cluster_size) = vertex {
+                write!(f, "<{}>", cluster_size)?;
+            }
+        }
+        write!(f, ";")?;
+        write_edges(f, &self.graph)
+    }
+}
+
+/// Write edges of the graph as a comma separated list of `v-u` (or `v>u` in directed graphs)
+pub(crate) fn write_edges<G>(f: &mut fmt::Formatter<'_>, graph: &G) -> fmt::Result
+where
+    G: Graph,
+{
+    let mut first = true;
+    for v in graph.vertices() {
+        for u in graph.adjacent_to(v) {
+            if G::DIRECTED || v < u {
+                if !first {
+                    write!(f, ",")?;
+                }
+                let separator = if G::DIRECTED { '>' } else { '-' };
+                write!(f, "{}{separator}{}", v.index, u.index)?;
+                first = false;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parse graph with `size` vertices from edges written by [`write_edges`]. In directed graphs
+/// `v-u` is an edge in both directions.
+pub(crate) fn parse_edges<G>(size: usize, edges: &str) -> Result<G, Error>
+where
+    G: Graph,
+{
+    let edges = edges
+        .split(',')
+        .map(str::trim)
+        .filter(|edge| !edge.is_empty())
+        .map(|edge| {
+            let invalid_edge = || Error::Syntax(format!("invalid edge '{edge}'"));
+            let (v, u, directed) = match edge.split_once('>') {
+                Some((v, u)) if G::DIRECTED => (v, u, true),
+                Some(_) => {
+                    return Err(Error::Syntax(format!(
+                        "directed edge '{edge}' in undirected graph"
+                    )))
+                }
+                None => {
+                    let (v, u) = edge.split_once('-').ok_or_else(invalid_edge)?;
+                    (v, u, false)
+                }
+            };
+            let v = v.trim().parse::<usize>().map_err(|_| invalid_edge())?;
+            let u = u.trim().parse::<usize>().map_err(|_| invalid_edge())?;
+            if v >= size || u >= size {
+                return Err(Error::Syntax(format!(
+                    "edge '{edge}' connects missing vertex"
+                )));
+            }
+            let v = VertexIndex { index: v };
+            let u = VertexIndex { index: u };
+            if v == u {
+                return Err(Error::Position(InvalidPosition::Loop(v)));
+            }
+            if directed || !G::DIRECTED {
+                Ok(vec![(v, u)])
+            } else {
+                Ok(vec![(v, u), (u, v)])
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .concat();
+
+    Ok(G::from_edges(size, &edges))
+}
+
+/// Radius of vertices in [`VertexColoring::to_svg`]
+const SVG_VERTEX_RADIUS: f32 = 16.0;
+
+/// Draw graph as LaTeX `TikZ` picture, laid out by [`circle_spring_layout`], with one unit per
+/// vertex diameter. `style` gives shape and fill color of each vertex, or `None` to skip it.
+///
+/// # Panics
+/// - `scale` is negative
+pub(crate) fn graph_to_tikz<G>(
+    graph: &G,
+    scale: f32,
+    style: impl Fn(VertexIndex) -> Option<(&'static str, &'static str)>,
+) -> String
+where
+    G: Graph,
+{
+    assert!(scale >= 0., "Scale must be positive");
+
+    let (positions, side) = circle_spring_layout(graph, SVG_VERTEX_RADIUS);
+    let unit = 2.0 * SVG_VERTEX_RADIUS;
+    let coordinates = |v: VertexIndex| {
+        let position = positions[v.index];
+        format!(
+            "({:.2},{:.2})",
+            position.x / unit,
+            (side - position.y) / unit
+        )
+    };
+    let arrow = if G::DIRECTED { "[->]" } else { "" };
+
+    let mut buf = String::new();
+    write!(buf, "\\begin{{tikzpicture}}[scale={}] ", scale).unwrap();
+    for v in graph.vertices().filter(|v| style(*v).is_some()) {
+        for u in graph.adjacent_to(v) {
+            if (G::DIRECTED || v < u) && style(u).is_some() {
+                write!(
+                    buf,
+                    "\\draw{} {} -- {}; ",
+                    arrow,
+                    coordinates(v),
+                    coordinates(u)
+                )
+                .unwrap();
+            }
+        }
+    }
+    for v in graph.vertices() {
+        if let Some((shape, fill)) = style(v) {
+            write!(
+                buf,
+                "\\node[draw,{},fill={},minimum size=0.8cm,inner sep=0pt] at {} {{{}}}; ",
+                shape,
+                fill,
+                coordinates(v),
+                v.index
+            )
+            .unwrap();
+        }
+    }
+    write!(buf, "\\end{{tikzpicture}}").unwrap();
+    buf
+}
+
+/// Draws the graph with vertices laid out by [`circle_spring_layout`], so the picture does not
+/// depend on external tools like graphviz. Taken vertices are skipped, like
+/// in [`VertexColoring::to_graphviz`].
+impl<R, G> Svg for VertexColoring<R, G>
+where
+    R: Rules,
+    G: Graph,
+{
+    fn to_svg<W>(&self, buf: &mut W) -> fmt::Result
+    where
+        W: Write,
+    {
+        let (positions, side) = circle_spring_layout(&self.graph, SVG_VERTEX_RADIUS);
+
+        let is_drawn = |v: VertexIndex| self.vertices[v].color() != VertexColor::Taken;
+        let side = side as u32;
+        ImmSvg::new(buf, side, side, |buf| {
+            ImmSvg::g(buf, "black", |buf| {
+                for v in self.graph.vertices().filter(|v| is_drawn(*v)) {
+                    for u in self.graph.adjacent_to(v) {
+                        if (G::DIRECTED || v < u) && is_drawn(u) {
+                            ImmSvg::line(
+                                buf,
+                                positions[v.index].x as i32,
+                                positions[v.index].y as i32,
+                                positions[u.index].x as i32,
+                                positions[u.index].y as i32,
+                                2,
+                            )?;
+                        }
+                    }
+                }
+                Ok(())
+            })?;
+
+            for v in self.graph.vertices().filter(|v| is_drawn(*v)) {
+                let fill = match self.vertices[v].color() {
+                    VertexColor::TintLeft => "blue",
+                    VertexColor::TintRight => "red",
+                    VertexColor::Empty | VertexColor::Taken => "white",
+                };
+                let (x, y) = (positions[v.index].x as i32, positions[v.index].y as i32);
+                match self.vertices[v] {
+                    VertexKind::Single(_) => {
+                        let circle = svg::Circle {
+                            cx: x,
+                            cy: y,
+                            r: SVG_VERTEX_RADIUS as u32,
+                            stroke: "black".to_owned(),
+                            stroke_width: 2,
+                            fill: fill.to_owned(),
+                        };
+                        ImmSvg::circle(buf, &circle)?;
+                    }
+                    VertexKind::Cluster(_, _) => {
+                        let side = 2 * SVG_VERTEX_RADIUS as u32;
+                        ImmSvg::g(buf, "black", |buf| {
+                            ImmSvg::rect(
+                                buf,
+                                x - SVG_VERTEX_RADIUS as i32,
+                                y - SVG_VERTEX_RADIUS as i32,
+                                side,
+                                side,
+                                fill,
+                            )
+                        })?;
+                    }
+                }
+                let label = svg::Text {
+                    x,
+                    y: y + 5,
+                    text: v.index.to_string(),
+                    text_anchor: svg::TextAnchor::Middle,
+                };
+                ImmSvg::text(buf, &label)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl<R, G> FromStr for VertexColoring<R, G>
+where
+    R: Rules,
+    G: Graph + Clone,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rules, s) = R::parse_rules(s)?;
+        let (vertices_str, edges_str) = s.split_once(';').unwrap_or((s, ""));
+
+        let mut vertices = Vec::new();
+        let mut chars = vertices_str.trim().chars().peekable();
+        while let Some(c) = chars.next() {
+            let color = VertexColor::from_char(c)
+                .ok_or_else(|| Error::Syntax(format!("invalid vertex '{c}'")))?;
+            if chars.next_if_eq(&'<').is_some() {
+                let cluster_size: String = chars.by_ref().take_while(|c| *c != '>').collect();
+                vertices.push(VertexKind::Cluster(
+                    color,
+                    cluster_size.parse().map_err(|_| {
+                        Error::Syntax(format!("invalid cluster size '{cluster_size}'"))
+                    })?,
+                ));
+            } else {
+                vertices.push(VertexKind::Single(color));
+            }
+        }
+
+        let graph = parse_edges(vertices.len(), edges_str)?;
+        Ok(Self::with_colors(vertices, graph)?.with_rules(rules))
+    }
+}
+impl VertexColors {
+    /// Key of vertex color at given index. Values of keys are disjoint from the ones used for
+    /// edges of the graph.
+    fn vertex_key(index: usize, vertex: VertexKind) -> u64 {
+        let value = match vertex {
+            VertexKind::Single(color) => color as u64,
+            VertexKind::Cluster(color, size) => (u64::from(size.get()) << 2) | color as u64,
+        };
+        zobrist_key(index as u64, value + 2)
+    }
+
+    /// XOR of keys of vertex colors
+    fn zobrist_hash(&self) -> u64 {
+        self.inner
+            .iter()
+            .enumerate()
+            .fold(0, |hash, (index, &vertex)| {
+                hash ^ Self::vertex_key(index, vertex)
+            })
+    }
+
+    /// XOR of keys of vertices that have different colors in `self` and `other`, or `None` if
+    /// they have different number of vertices
+    fn hash_difference(&self, other: &Self) -> Option<u64> {
+        (self.inner.len() == other.inner.len()).then(|| {
+            self.inner
+                .iter()
+                .zip(&other.inner)
+                .enumerate()
+                .filter(|(_, (lhs, rhs))| lhs != rhs)
+                .fold(0, |hash, (index, (&lhs, &rhs))| {
+                    hash ^ Self::vertex_key(index, lhs) ^ Self::vertex_key(index, rhs)
+                })
+        })
+    }
+}
+
+impl<R, G> ZobristHash for VertexColoring<R, G>
+where
+    G: ZobristHash,
+{
+    fn zobrist_hash(&self) -> u64 {
+        self.graph.zobrist_hash() ^ self.vertices.zobrist_hash()
+    }
+
+    /// Moves change colors of only a few vertices, so only their keys are updated. Graphs of
+    /// positions with the same number of vertices have the same size, so the graph updates its
+    /// edges incrementally too.
+    fn zobrist_hash_from(&self, hash: u64, other: &Self) -> u64 {
+        self.vertices.hash_difference(&other.vertices).map_or_else(
+            || other.zobrist_hash(),
+            |difference| self.graph.zobrist_hash_from(hash, &other.graph) ^ difference,
+        )
+    }
+}
+
+impl<R, G> PartizanGame for VertexColoring<R, G>
+where
+    R: Rules,
+    G: Graph + Clone + Hash + Eq + Send + Sync,
+{
+    fn left_moves(&self) -> Vec<Self> {
+        self.moves_with_vertices(Player::Left)
+            .into_iter()
+            .map(|(_, position)| position)
+            .collect()
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.moves_with_vertices(Player::Right)
+            .into_iter()
+            .map(|(_, position)| position)
+            .collect()
+    }
+
+    fn left_moves_iter(&self) -> impl Iterator<Item = Self> + '_ {
+        self.moves_with_vertices_iter(Player::Left)
+            .map(|(_, position)| position)
+    }
+
+    fn right_moves_iter(&self) -> impl Iterator<Item = Self> + '_ {
+        self.moves_with_vertices_iter(Player::Right)
+            .map(|(_, position)| position)
+    }
+
+    /// Decompose the game graph into disconnected components
+    fn decompositions(&self) -> Vec<Self> {
+        let mut visited = vec![false; self.vertices.inner.len()];
+        let mut res = Vec::new();
+
+        for v in self.graph.vertices() {
+            if !matches!(self.vertices[v].color(), VertexColor::Taken) && !visited[v.index] {
+                res.push(self.bfs(&mut visited, v));
+            }
+        }
+
+        res
+    }
+
+    fn reductions(&self) -> Option<CanonicalForm> {
+        if let Some(value) = R::known_value(self) {
+            return Some(value);
+        }
+
+        if let &[vertex] = &self.vertices.inner[..] {
+            let cf = match vertex {
+                VertexKind::Single(VertexColor::Empty) => {
+                    CanonicalForm::new_nimber(DyadicRationalNumber::from(0), Nimber::new(1))
+                }
+                VertexKind::Single(VertexColor::TintLeft) => CanonicalForm::new_integer(1),
+                VertexKind::Single(VertexColor::TintRight) => CanonicalForm::new_integer(-1),
+                VertexKind::Cluster(VertexColor::Empty, cluster_size) => {
+                    let nimber = Nimber::new(cluster_size.get() % 2);
+                    CanonicalForm::new_nimber(DyadicRationalNumber::from(0), nimber)
+                }
+                VertexKind::Cluster(VertexColor::TintLeft, cluster_size) => {
+                    CanonicalForm::new_integer(cluster_size.get() as i64)
+                }
+                VertexKind::Cluster(VertexColor::TintRight, cluster_size) => {
+                    CanonicalForm::new_integer(-(cluster_size.get() as i64))
+                }
+                VertexKind::Single(VertexColor::Taken)
+                | VertexKind::Cluster(VertexColor::Taken, _) => CanonicalForm::new_integer(0),
+            };
+            return Some(cf);
+        }
+
+        None
+    }
+}
+impl NamedGame for VertexColoring {
+    const NAME: &'static str = "vertex-coloring";
+}
+
+impl<G> RandomPosition for VertexColoring<ColoringRules, G>
+where
+    G: Graph + Clone,
+{
+    type Parameters = (ColoringRules, GraphParameters);
+
+    /// Empty position with given rules on a [random graph](generators::random_gnp)
+    fn random_position<R>(
+        (rules, parameters): &(ColoringRules, GraphParameters),
+        rng: &mut R,
+    ) -> Option<Self>
+    where
+        R: Rng,
+    {
+        Some(
+            Self::new(generators::random_gnp(
+                parameters.vertices,
+                parameters.edge_probability,
+                rng,
+            ))
+            .with_rules(*rules),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::{
+        games::{col::Col, snort::Snort},
+        transposition_table::ParallelTranspositionTable,
+    };
+
+    fn path(n: usize) -> UndirectedGraph {
+        generators::path(n)
+    }
+
+    #[test]
+    fn special_cases() {
+        for n in 1..6 {
+            let snort: VertexColoring = VertexColoring::new(path(n));
+            assert_eq!(
+                snort.canonical_form(&ParallelTranspositionTable::new()),
+                Snort::new(path(n)).canonical_form(&ParallelTranspositionTable::new())
+            );
+
+            let col = snort.with_rules(ColoringRules::col());
+            assert_eq!(
+                col.canonical_form(&ParallelTranspositionTable::new()),
+                Col::new(path(n)).canonical_form(&ParallelTranspositionTable::new())
+            );
+        }
+    }
+
+    #[test]
+    fn custom_rules() {
+        let transposition_table = ParallelTranspositionTable::new();
+
+        // Left can color anything, Right cannot color vertices adjacent to Left's vertices
+        let rules = ColoringRules::new([[true, true], [false, true]]);
+        let position: VertexColoring = VertexColoring::new(path(2)).with_rules(rules);
+        assert_eq!(
+            position.rules.tint_color(Player::Left),
+            VertexColor::TintLeft
+        );
+        assert_eq!(position.rules.tint_color(Player::Right), VertexColor::Empty);
+        assert_eq!(
+            position.canonical_form(&transposition_table).to_string(),
+            "{1|*}"
+        );
+
+        // No restrictions at all, so vertices are independent
+        let rules = ColoringRules::new([[true, true], [true, true]]);
+        let position: VertexColoring = VertexColoring::new(path(3)).with_rules(rules);
+        assert_eq!(
+            position.canonical_form(&transposition_table).to_string(),
+            "*"
+        );
+    }
+
+    #[test]
+    fn incremental_zobrist_hash() {
+        let positions: [VertexColoring; 3] = [
+            VertexColoring::new(path(5)),
+            VertexColoring::new(generators::cycle(6)).with_rules(ColoringRules::col()),
+            VertexColoring::from_str("snort;.L.<3>R..;0-1,1-2,2-3,3-4,4-5,0-5,1-4").unwrap(),
+        ];
+
+        for position in positions {
+            let hash = position.zobrist_hash();
+            for option in position
+                .left_moves()
+                .into_iter()
+                .chain(position.right_moves())
+            {
+                assert_eq!(
+                    position.zobrist_hash_from(hash, &option),
+                    option.zobrist_hash()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn graphviz_round_trip() {
+        let position: VertexColoring = VertexColoring::from_graphviz(
+            "graph { 0 -- 1 -- 2; 0 [fillcolor=blue, style=filled] }",
+        )
+        .unwrap()
+        .with_rules(ColoringRules::col());
+        assert_eq!(
+            position.vertices.inner,
+            vec![
+                VertexKind::Single(VertexColor::TintLeft),
+                VertexKind::Single(VertexColor::Empty),
+                VertexKind::Single(VertexColor::Empty)
+            ]
+        );
+        assert_eq!(position.graph, path(3));
+
+        assert_eq!(
+            VertexColoring::from_graphml(&position.to_graphml())
+                .map(|graphml| graphml.with_rules(ColoringRules::col())),
+            Some(position)
+        );
+    }
+
+    #[test]
+    fn text_format_round_trip() {
+        let position: VertexColoring = VertexColoring::from_str("col;.L.;0-1,1-2").unwrap();
+        assert_eq!(position.rules, ColoringRules::col());
+        assert_eq!(position.to_string(), "col;.L.;0-1,1-2");
+
+        let rules = ColoringRules::new([[true, true], [false, true]]);
+        let position: VertexColoring = VertexColoring::new(path(2)).with_rules(rules);
+        assert_eq!(position.to_string(), "1101;..;0-1");
+        assert_eq!(VertexColoring::from_str("1101;..;0-1"), Ok(position));
+        assert_eq!(
+            VertexColoring::<ColoringRules>::from_str("snort;..;0-1")
+                .unwrap()
+                .rules,
+            ColoringRules::snort()
+        );
+
+        let error = |input: &str| VertexColoring::<ColoringRules>::from_str(input).unwrap_err();
+        assert_eq!(
+            error("101;..;0-1").to_string(),
+            "invalid coloring rules '101'"
+        );
+        assert_eq!(error("..").to_string(), "missing coloring rules");
+        assert_eq!(error("col;.x").to_string(), "invalid vertex 'x'");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialization() {
+        // Fixed rules are not serialized, so Snort positions keep their format
+        let snort: Snort = Snort::from_str("L.;0-1").unwrap();
+        let json = serde_json::to_string(&snort).unwrap();
+        assert!(!json.contains("rules"), "{json}");
+        assert_eq!(serde_json::from_str::<Snort>(&json).unwrap(), snort);
+
+        let position: VertexColoring = VertexColoring::from_str("col;L.;0-1").unwrap();
+        let json = serde_json::to_string(&position).unwrap();
+        assert_eq!(
+            serde_json::from_str::<VertexColoring>(&json).unwrap(),
+            position
+        );
+    }
+}
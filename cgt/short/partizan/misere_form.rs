@@ -0,0 +1,626 @@
+//! Partizan games under misère play, i.e. the player that cannot move in their turn wins.
+//!
+//! There is no useful canonical form of all partizan games under misère play, so games are
+//! studied modulo a universe - a set of games closed under sums and followers. This module
+//! focuses on the dead-ending universe, where once a player runs out of moves in a component,
+//! they never get a move there again (e.g. Domineering).
+//!
+//! `G ≥ H` modulo universe `U` iff for every `X` in `U` the outcome of `G + X` is at least as
+//! good for Left as the outcome of `H + X`. See [`MisereForm::leq_modulo`] for comparison modulo
+//! the dead-ending universe and [`MisereForm::reduced`] for reduced forms.
+
+use crate::{display, short::partizan::partizan_game::PartizanGame};
+use auto_ops::impl_op_ex;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display},
+};
+
+/// Outcome of a partizan game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Outcome {
+    /// Left wins regardless of who starts
+    L,
+
+    /// Next player wins
+    N,
+
+    /// Previous player wins
+    P,
+
+    /// Right wins regardless of who starts
+    R,
+}
+
+impl Outcome {
+    /// Construct outcome from results of play when each player starts
+    pub const fn new(left_wins_going_first: bool, left_wins_going_second: bool) -> Self {
+        match (left_wins_going_first, left_wins_going_second) {
+            (true, true) => Self::L,
+            (true, false) => Self::N,
+            (false, true) => Self::P,
+            (false, false) => Self::R,
+        }
+    }
+
+    /// Check if `self` is at least as good for Left as `other`, using `L > N, P > R` ordering.
+    pub const fn geq(self, other: Self) -> bool {
+        matches!(
+            (self, other),
+            (Self::L, _) | (_, Self::R) | (Self::N, Self::N) | (Self::P, Self::P)
+        )
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::L => write!(f, "L"),
+            Self::N => write!(f, "N"),
+            Self::P => write!(f, "P"),
+            Self::R => write!(f, "R"),
+        }
+    }
+}
+
+/// Game tree of a partizan game, played under misère convention
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MisereForm {
+    left: Vec<Self>,
+    right: Vec<Self>,
+}
+
+impl MisereForm {
+    /// Construct new game from Left and Right options
+    pub fn new(mut left: Vec<Self>, mut right: Vec<Self>) -> Self {
+        left.sort();
+        left.dedup();
+        right.sort();
+        right.dedup();
+        Self { left, right }
+    }
+
+    /// Game without any moves
+    pub const fn zero() -> Self {
+        Self {
+            left: Vec::new(),
+            right: Vec::new(),
+        }
+    }
+
+    /// Construct misère form from game position
+    pub fn from_game<G>(game: &G) -> Self
+    where
+        G: PartizanGame,
+    {
+        Self::new(
+            game.left_moves().iter().map(Self::from_game).collect(),
+            game.right_moves().iter().map(Self::from_game).collect(),
+        )
+    }
+
+    /// Left options
+    pub fn left_moves(&self) -> &[Self] {
+        &self.left
+    }
+
+    /// Right options
+    pub fn right_moves(&self) -> &[Self] {
+        &self.right
+    }
+
+    /// Check if Left wins when moving first
+    pub fn left_wins_going_first(&self) -> bool {
+        self.left.is_empty() || self.left.iter().any(Self::left_wins_going_second)
+    }
+
+    /// Check if Left wins when Right moves first
+    pub fn left_wins_going_second(&self) -> bool {
+        !self.right.is_empty() && self.right.iter().all(Self::left_wins_going_first)
+    }
+
+    /// Misère outcome of the game
+    pub fn outcome(&self) -> Outcome {
+        Outcome::new(self.left_wins_going_first(), self.left_wins_going_second())
+    }
+
+    /// Check if Left has no moves in the game and all its followers
+    pub fn is_dead_left_end(&self) -> bool {
+        self.left.is_empty() && self.right.iter().all(Self::is_dead_left_end)
+    }
+
+    /// Check if Right has no moves in the game and all its followers
+    pub fn is_dead_right_end(&self) -> bool {
+        self.right.is_empty() && self.left.iter().all(Self::is_dead_right_end)
+    }
+
+    /// Check if the game is dead-ending, i.e. every end in every follower is dead
+    pub fn is_dead_ending(&self) -> bool {
+        (!self.left.is_empty() || self.is_dead_left_end())
+            && (!self.right.is_empty() || self.is_dead_right_end())
+            && self.left.iter().all(Self::is_dead_ending)
+            && self.right.iter().all(Self::is_dead_ending)
+    }
+
+    /// Construct a disjunctive sum of two games
+    pub fn construct_sum(g: &Self, h: &Self) -> Self {
+        let mut left = Vec::with_capacity(g.left.len() + h.left.len());
+        let mut right = Vec::with_capacity(g.right.len() + h.right.len());
+        for g_left in &g.left {
+            left.push(Self::construct_sum(g_left, h));
+        }
+        for h_left in &h.left {
+            left.push(Self::construct_sum(g, h_left));
+        }
+        for g_right in &g.right {
+            right.push(Self::construct_sum(g_right, h));
+        }
+        for h_right in &h.right {
+            right.push(Self::construct_sum(g, h_right));
+        }
+        Self::new(left, right)
+    }
+
+    /// Construct a conjugate of the game, i.e. swap Left and Right options.
+    /// Note that under misère play conjugate is not an additive inverse.
+    #[must_use]
+    pub fn construct_conjugate(&self) -> Self {
+        Self::new(
+            self.right.iter().map(Self::construct_conjugate).collect(),
+            self.left.iter().map(Self::construct_conjugate).collect(),
+        )
+    }
+
+    /// All games born by day `day`, without any simplification
+    pub fn born_by_day(day: u32) -> Vec<Self> {
+        let mut games = vec![Self::zero()];
+        for _ in 0..day {
+            let subsets: Vec<Vec<Self>> = (0..(1_usize << games.len()))
+                .map(|mask| {
+                    games
+                        .iter()
+                        .enumerate()
+                        .filter(|(idx, _)| mask & (1 << idx) != 0)
+                        .map(|(_, g)| g.clone())
+                        .collect()
+                })
+                .collect();
+            let mut new_games = Vec::with_capacity(subsets.len() * subsets.len());
+            for left in &subsets {
+                for right in &subsets {
+                    new_games.push(Self::new(left.clone(), right.clone()));
+                }
+            }
+            new_games.sort();
+            new_games.dedup();
+            games = new_games;
+        }
+        games
+    }
+
+    /// All dead-ending games born by day `day`, without any simplification
+    pub fn dead_ending_born_by_day(day: u32) -> Vec<Self> {
+        Self::born_by_day(day)
+            .into_iter()
+            .filter(Self::is_dead_ending)
+            .collect()
+    }
+
+    /// Check if `lhs ≤ rhs` modulo the dead-ending universe
+    ///
+    /// # Panics
+    ///
+    /// Panics if either of the games is not dead-ending
+    pub fn leq_modulo(lhs: &Self, rhs: &Self) -> bool {
+        assert!(
+            lhs.is_dead_ending() && rhs.is_dead_ending(),
+            "Games must be dead-ending"
+        );
+        DeadEndingComparator::default().geq(rhs, lhs)
+    }
+
+    /// Check if `lhs` and `rhs` are equivalent modulo the dead-ending universe
+    ///
+    /// # Panics
+    ///
+    /// Panics if either of the games is not dead-ending
+    pub fn eq_modulo(lhs: &Self, rhs: &Self) -> bool {
+        assert!(
+            lhs.is_dead_ending() && rhs.is_dead_ending(),
+            "Games must be dead-ending"
+        );
+        let mut comparator = DeadEndingComparator::default();
+        comparator.geq(lhs, rhs) && comparator.geq(rhs, lhs)
+    }
+
+    /// Reduced form of a dead-ending game, i.e. equivalent game modulo the dead-ending universe
+    /// with dominated options removed and reversible options bypassed, in all followers.
+    ///
+    /// Options reversible through an end are kept, as bypassing them could turn the game into an
+    /// end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the game is not dead-ending
+    #[must_use]
+    pub fn reduced(&self) -> Self {
+        assert!(self.is_dead_ending(), "Game must be dead-ending");
+        DeadEndingComparator::default().reduced(self)
+    }
+}
+
+/// Comparison modulo the dead-ending universe
+///
+/// The dead-ending universe is absolute, so `G ≥ H` iff the maintenance and proviso conditions
+/// hold (Larsson, Nowakowski, Santos - Absolute combinatorial game theory):
+///
+/// - for every `H^L` there is `G^L ≥ H^L` or `H^LR ≤ G`,
+/// - for every `G^R` there is `H^R ≤ G^R` or `G^RL ≥ H`,
+/// - Left wins `G + X` going first whenever she wins `H + X` going first, for every Left end `X`,
+/// - Left wins `G + X` going second whenever she wins `H + X` going second, for every Right
+///   end `X`.
+#[derive(Default)]
+struct DeadEndingComparator {
+    cache: HashMap<(MisereForm, MisereForm), bool>,
+    reduced: HashMap<MisereForm, MisereForm>,
+}
+
+impl DeadEndingComparator {
+    fn geq(&mut self, g: &MisereForm, h: &MisereForm) -> bool {
+        if g == h {
+            return true;
+        }
+        if let Some(&result) = self.cache.get(&(g.clone(), h.clone())) {
+            return result;
+        }
+
+        let result = self.maintenance(g, h)
+            && Self::left_end_proviso(g, h)
+            && Self::left_end_proviso(&h.construct_conjugate(), &g.construct_conjugate());
+        self.cache.insert((g.clone(), h.clone()), result);
+        result
+    }
+
+    fn maintenance(&mut self, g: &MisereForm, h: &MisereForm) -> bool {
+        h.left.iter().all(|h_left| {
+            g.left.iter().any(|g_left| self.geq(g_left, h_left))
+                || h_left
+                    .right
+                    .iter()
+                    .any(|h_left_right| self.geq(g, h_left_right))
+        }) && g.right.iter().all(|g_right| {
+            h.right.iter().any(|h_right| self.geq(g_right, h_right))
+                || g_right
+                    .left
+                    .iter()
+                    .any(|g_right_left| self.geq(g_right_left, h))
+        })
+    }
+
+    /// Check that for every dead Left end `X`, if Left wins `H + X` going first, she also wins
+    /// `G + X` going first.
+    ///
+    /// Left never moves in `X`, so `X` affects the play only through the positions in which
+    /// Left wins going first after Right moves in `X`. We collect sets of followers of `G` and
+    /// `H` that Left wins going first in a sum with some dead Left end, building the ends
+    /// from the previously found sets until no new set appears.
+    fn left_end_proviso(g: &MisereForm, h: &MisereForm) -> bool {
+        let followers = Followers::new(&[g, h]);
+        let g = followers.index[g];
+        let h = followers.index[h];
+
+        let mut left_wins = HashSet::new();
+        left_wins.insert(followers.left_wins_going_first(None));
+        loop {
+            let mut intersections = left_wins.clone();
+            loop {
+                let mut new_intersections = Vec::new();
+                for a in &intersections {
+                    for b in &intersections {
+                        let intersection: Vec<bool> =
+                            a.iter().zip(b).map(|(&a, &b)| a && b).collect();
+                        if !intersections.contains(&intersection) {
+                            new_intersections.push(intersection);
+                        }
+                    }
+                }
+                if new_intersections.is_empty() {
+                    break;
+                }
+                intersections.extend(new_intersections);
+            }
+
+            let mut changed = false;
+            for right_options in &intersections {
+                changed |= left_wins.insert(followers.left_wins_going_first(Some(right_options)));
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        left_wins.iter().all(|wins| !wins[h] || wins[g])
+    }
+
+    fn reduced(&mut self, game: &MisereForm) -> MisereForm {
+        if let Some(reduced) = self.reduced.get(game) {
+            return reduced.clone();
+        }
+
+        let mut left: Vec<MisereForm> = game.left.iter().map(|g| self.reduced(g)).collect();
+        let mut right: Vec<MisereForm> = game.right.iter().map(|g| self.reduced(g)).collect();
+        loop {
+            left = self.remove_dominated(left, |this, a, b| this.geq(b, a));
+            right = self.remove_dominated(right, Self::geq);
+            let current = MisereForm::new(left.clone(), right.clone());
+
+            let mut changed = false;
+            let mut new_left = Vec::with_capacity(left.len());
+            for left_option in left {
+                let reversing = left_option.right.iter().find(|left_right| {
+                    !left_right.left.is_empty() && self.geq(&current, left_right)
+                });
+                if let Some(reversing) = reversing {
+                    new_left.extend(reversing.left.iter().cloned());
+                    changed = true;
+                } else {
+                    new_left.push(left_option);
+                }
+            }
+            let mut new_right = Vec::with_capacity(right.len());
+            for right_option in right {
+                let reversing = right_option.left.iter().find(|right_left| {
+                    !right_left.right.is_empty() && self.geq(right_left, &current)
+                });
+                if let Some(reversing) = reversing {
+                    new_right.extend(reversing.right.iter().cloned());
+                    changed = true;
+                } else {
+                    new_right.push(right_option);
+                }
+            }
+
+            left = new_left;
+            right = new_right;
+            if !changed {
+                break;
+            }
+        }
+
+        let reduced = MisereForm::new(left, right);
+        self.reduced.insert(game.clone(), reduced.clone());
+        reduced
+    }
+
+    /// Remove options `a` for which there is other option `b` with `dominated(a, b)`
+    fn remove_dominated<F>(&mut self, mut options: Vec<MisereForm>, dominated: F) -> Vec<MisereForm>
+    where
+        F: Fn(&mut Self, &MisereForm, &MisereForm) -> bool,
+    {
+        options.sort();
+        options.dedup();
+        let mut idx = 0;
+        while idx < options.len() {
+            let is_dominated = (0..options.len())
+                .any(|other| other != idx && dominated(self, &options[idx], &options[other]));
+            if is_dominated {
+                options.remove(idx);
+            } else {
+                idx += 1;
+            }
+        }
+        options
+    }
+}
+
+/// All followers of some games, indexed such that options come before the game
+struct Followers {
+    index: HashMap<MisereForm, usize>,
+    left: Vec<Vec<usize>>,
+    right: Vec<Vec<usize>>,
+}
+
+impl Followers {
+    fn new(games: &[&MisereForm]) -> Self {
+        let mut followers = Self {
+            index: HashMap::new(),
+            left: Vec::new(),
+            right: Vec::new(),
+        };
+        for game in games {
+            followers.insert(game);
+        }
+        followers
+    }
+
+    fn insert(&mut self, game: &MisereForm) -> usize {
+        if let Some(&idx) = self.index.get(game) {
+            return idx;
+        }
+        let left = game.left.iter().map(|g| self.insert(g)).collect();
+        let right = game.right.iter().map(|g| self.insert(g)).collect();
+        let idx = self.left.len();
+        self.left.push(left);
+        self.right.push(right);
+        self.index.insert(game.clone(), idx);
+        idx
+    }
+
+    /// For every follower `F` check if Left wins `F + X` going first, where `X` is a dead Left
+    /// end. `X` is described by the positions that Left wins going first after Right's move in
+    /// `X`, or `None` if `X` is zero.
+    fn left_wins_going_first(&self, end_right_options: Option<&[bool]>) -> Vec<bool> {
+        let mut left_wins_going_first = vec![false; self.left.len()];
+        let mut left_wins_going_second = vec![false; self.left.len()];
+        for idx in 0..self.left.len() {
+            left_wins_going_second[idx] = (end_right_options.is_some()
+                || !self.right[idx].is_empty())
+                && self.right[idx]
+                    .iter()
+                    .all(|&right| left_wins_going_first[right])
+                && end_right_options.is_none_or(|wins| wins[idx]);
+            left_wins_going_first[idx] = self.left[idx].is_empty()
+                || self.left[idx]
+                    .iter()
+                    .any(|&left| left_wins_going_second[left]);
+        }
+        left_wins_going_first
+    }
+}
+
+impl_op_ex!(+|g: &MisereForm, h: &MisereForm| -> MisereForm { MisereForm::construct_sum(g, h) });
+
+impl Display for MisereForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.left.is_empty() && self.right.is_empty() {
+            return write!(f, "0");
+        }
+        display::braces(f, |f| {
+            display::commas(f, &self.left)?;
+            write!(f, "|")?;
+            display::commas(f, &self.right)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::games::domineering::Domineering;
+    use std::str::FromStr;
+
+    #[test]
+    fn misere_outcomes() {
+        let zero = MisereForm::zero();
+        assert_eq!(zero.outcome(), Outcome::N);
+
+        let one = MisereForm::new(vec![zero.clone()], vec![]);
+        assert_eq!(one.outcome(), Outcome::R);
+
+        let star = MisereForm::new(vec![zero.clone()], vec![zero]);
+        assert_eq!(star.outcome(), Outcome::P);
+    }
+
+    #[test]
+    fn domineering_is_dead_ending() {
+        let position: Domineering = Domineering::from_str("..|.#").unwrap();
+        let form = MisereForm::from_game(&position);
+        assert!(form.is_dead_ending());
+        assert_eq!(form.outcome(), Outcome::P);
+
+        let not_dead = MisereForm::new(
+            vec![],
+            vec![MisereForm::new(vec![MisereForm::zero()], vec![])],
+        );
+        assert!(!not_dead.is_dead_ending());
+    }
+
+    #[test]
+    fn comparison_modulo_dead_ending() {
+        let zero = MisereForm::zero();
+        let one = MisereForm::new(vec![zero.clone()], vec![]);
+        let star = MisereForm::new(vec![zero.clone()], vec![zero.clone()]);
+
+        // 1 + * is N while * is P
+        assert!(!MisereForm::leq_modulo(&one, &zero));
+        assert!(!MisereForm::leq_modulo(&zero, &one));
+
+        assert!(!MisereForm::eq_modulo(&star, &zero));
+        assert!(MisereForm::eq_modulo(&star, &star));
+
+        // Unlike in the dicot universe, * + * is not zero, 1 + * + * is P while 1 is R
+        assert!(!MisereForm::eq_modulo(&(&star + &star), &zero));
+
+        let minus_one = one.construct_conjugate();
+        // {-1|1} = 0, each player's only move leaves the opponent without a move
+        let switch = MisereForm::new(vec![minus_one.clone()], vec![one]);
+        assert!(MisereForm::eq_modulo(&switch, &zero));
+
+        let minus_one_star = MisereForm::new(vec![minus_one], vec![star]);
+        assert!(MisereForm::leq_modulo(&zero, &minus_one_star));
+        assert!(!MisereForm::leq_modulo(&minus_one_star, &zero));
+    }
+
+    #[test]
+    fn dead_ends_are_invertible() {
+        let zero = MisereForm::zero();
+        let minus_one = MisereForm::new(vec![], vec![zero.clone()]);
+        let minus_two = MisereForm::new(vec![], vec![minus_one.clone()]);
+        let end = MisereForm::new(vec![], vec![zero.clone(), minus_two.clone()]);
+
+        for x in [&minus_one, &minus_two, &end] {
+            assert!(MisereForm::eq_modulo(&(x + x.construct_conjugate()), &zero));
+        }
+
+        // Dead ends are not all equivalent to integers
+        assert!(!MisereForm::eq_modulo(&end, &minus_one));
+        assert!(!MisereForm::eq_modulo(&end, &minus_two));
+    }
+
+    #[test]
+    fn comparison_agrees_with_outcomes() {
+        let games = MisereForm::dead_ending_born_by_day(1);
+        let test_games = MisereForm::dead_ending_born_by_day(2);
+
+        for g in &games {
+            for h in &games {
+                let distinguished = test_games
+                    .iter()
+                    .any(|x| !(h + x).outcome().geq((g + x).outcome()));
+                assert_eq!(MisereForm::leq_modulo(g, h), !distinguished, "{g} ≤ {h}");
+            }
+        }
+    }
+
+    #[test]
+    fn reduced_forms() {
+        let zero = MisereForm::zero();
+        let one = MisereForm::new(vec![zero.clone()], vec![]);
+        let star = MisereForm::new(vec![zero.clone()], vec![zero.clone()]);
+        let minus_one = one.construct_conjugate();
+
+        // {-1|0} is dominated by -1
+        let dominated = MisereForm::new(
+            vec![zero.clone()],
+            vec![
+                minus_one.clone(),
+                MisereForm::new(vec![minus_one.clone()], vec![zero.clone()]),
+            ],
+        );
+        assert_eq!(
+            dominated.reduced(),
+            MisereForm::new(vec![zero.clone()], vec![minus_one])
+        );
+
+        // {*|1} is reversible through * ≥ {0|{*|1}}
+        let reversible_right = MisereForm::new(
+            vec![zero.clone()],
+            vec![MisereForm::new(vec![star.clone()], vec![one])],
+        );
+        assert_eq!(reversible_right.reduced(), star);
+
+        // {0|*} is reversible through * ≤ {0, {0|*}|0}
+        let reversible_left = MisereForm::new(
+            vec![
+                zero.clone(),
+                MisereForm::new(vec![zero.clone()], vec![star.clone()]),
+            ],
+            vec![zero],
+        );
+        assert_eq!(reversible_left.reduced(), star);
+
+        let test_games = MisereForm::dead_ending_born_by_day(2);
+        for game in [dominated, reversible_right, reversible_left] {
+            let reduced = game.reduced();
+            assert!(MisereForm::eq_modulo(&game, &reduced));
+            for x in &test_games {
+                assert_eq!((&game + x).outcome(), (&reduced + x).outcome());
+            }
+        }
+
+        // Options reversible through an end are kept
+        assert_eq!(star.reduced(), star);
+    }
+}
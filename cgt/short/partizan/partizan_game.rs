@@ -1,19 +1,38 @@
 //! Shared traits for short partizan games
 
 use crate::{
-    numeric::rational::Rational,
+    macros::{trace_event, trace_span},
+    numeric::{dyadic_rational_number::DyadicRationalNumber, rational::Rational},
     short::partizan::{
+        cancellation::{Cancellation, Cancelled},
         canonical_form::{CanonicalForm, Moves},
+        misere_form::Outcome,
         thermograph::Thermograph,
         trajectory::Trajectory,
-        transposition_table::TranspositionTable,
+        transposition_table::{Entry, TranspositionTable, VacantEntry},
     },
 };
-use std::hash::Hash;
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    num::NonZeroUsize,
+};
 
 #[cfg(feature = "rayon")]
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 
+/// Knobs of [`PartizanGame::sensible_canonical_form`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SensibleSearch {
+    /// Evaluate options in order of a mobility heuristic, so the ones most likely to dominate
+    /// their siblings are found first
+    pub order_moves: bool,
+
+    /// Evaluate at most this many options of each player in every position, after ordering.
+    /// Result is exact only if this is `None`, as skipped options may not be dominated.
+    pub max_options: Option<NonZeroUsize>,
+}
+
 /// A short partizan game
 pub trait PartizanGame: Sized + Clone + Hash + Send + Sync + Eq {
     /// List of all moves for the Left player
@@ -22,10 +41,29 @@ pub trait PartizanGame: Sized + Clone + Hash + Send + Sync + Eq {
     /// List of all moves for the Right player
     fn right_moves(&self) -> Vec<Self>;
 
+    /// Moves for the Left player, generated as they are consumed
+    ///
+    /// Searches that may stop before looking at all options should prefer it over
+    /// [`Self::left_moves`], so unused positions are never created. The same position may be
+    /// returned more than once. By default all moves are generated with [`Self::left_moves`].
+    fn left_moves_iter(&self) -> impl Iterator<Item = Self> + '_ {
+        self.left_moves().into_iter()
+    }
+
+    /// Moves for the Right player, generated as they are consumed. See [`Self::left_moves_iter`]
+    fn right_moves_iter(&self) -> impl Iterator<Item = Self> + '_ {
+        self.right_moves().into_iter()
+    }
+
     /// Split game into disjoint sum
     ///
-    /// Note that default implementation doesn't find any decompisitons and may lead to performance
-    /// issues
+    /// Value of the position must be the sum of values of the components. Canonical form
+    /// searches evaluate each component separately and look it up in the transposition table,
+    /// so games only have to override this method to get sum decomposition. Empty list is the
+    /// zero game.
+    ///
+    /// Note that default implementation doesn't find any decompositions and may lead to
+    /// performance issues
     fn decompositions(&self) -> Vec<Self> {
         vec![self.clone()]
     }
@@ -35,31 +73,23 @@ pub trait PartizanGame: Sized + Clone + Hash + Send + Sync + Eq {
     /// This is copy-pasted from [`super::canonical_form`] module, but works on positions rather than
     /// it's canonical forms, although the algorithm is the same
     ///
-    /// Note that for some games, going through canonical form to compute the thermograph may
-    /// be faster
+    /// Thermographs of positions are reused only within a single call, and positions are not
+    /// split with [`Self::decompositions`], as thermographs of components do not determine the
+    /// thermograph of their sum. For games with many transpositions or independent regions,
+    /// going through canonical form with a shared transposition table may be faster.
     ///
     /// See: zubzero-thermography
     fn thermograph_direct(&self) -> Thermograph {
-        let left_moves = self.left_moves();
-        let right_moves = self.right_moves();
-        if left_moves.is_empty() && right_moves.is_empty() {
-            return Thermograph::with_mast(Rational::from(0));
-        }
-
-        let mut left_scaffold = Trajectory::new_constant(Rational::NegativeInfinity);
-        let mut right_scaffold = Trajectory::new_constant(Rational::PositiveInfinity);
-
-        for left_move in &left_moves {
-            left_scaffold = left_scaffold.max(&left_move.thermograph_direct().right_wall);
-        }
-        for right_move in &right_moves {
-            right_scaffold = right_scaffold.min(&right_move.thermograph_direct().left_wall);
-        }
-
-        left_scaffold.tilt(Rational::from(-1));
-        right_scaffold.tilt(Rational::from(1));
+        thermograph_cached(self, &mut HashMap::new())
+    }
 
-        Thermograph::thermographic_intersection(left_scaffold, right_scaffold)
+    /// Compute the temperature without going through canonical form, see
+    /// [`Self::thermograph_direct`]
+    ///
+    /// Options that are dominated or reversible don't have to be simplified away, but nothing is
+    /// cached between calls.
+    fn temperature_direct(&self) -> DyadicRationalNumber {
+        self.thermograph_direct().temperature()
     }
 
     /// Handle special cases when computing canonical form doesn't have to compute all moves.
@@ -68,58 +98,171 @@ pub trait PartizanGame: Sized + Clone + Hash + Send + Sync + Eq {
     }
 
     /// Get the canonical form of the game position
+    ///
+    /// Without the `rayon` feature this is the same as [`Self::canonical_form_iterative`]. With
+    /// `rayon` decompositions and options are evaluated in parallel, which recurses once per move,
+    /// so very deep game trees should use [`Self::canonical_form_iterative`] instead. Positions
+    /// are claimed in the transposition table while being evaluated, but a position claimed by
+    /// another thread is evaluated again rather than waited for, as waiting could deadlock the
+    /// thread pool.
     fn canonical_form<TT>(&self, transposition_table: &TT) -> CanonicalForm
     where
         TT: TranspositionTable<Self> + Sync,
     {
-        if let Some(id) = transposition_table.lookup_position(self) {
-            return id;
+        #[cfg(not(feature = "rayon"))]
+        {
+            self.canonical_form_iterative(transposition_table)
         }
 
-        if let Some(cf) = self.reductions() {
-            return cf;
+        #[cfg(feature = "rayon")]
+        {
+            trace_span!(TRACE, "canonical_form");
+            let entry = match transposition_table.entry(self.clone()) {
+                Entry::Occupied(value) => {
+                    trace_event!(hit = true, "transposition table lookup");
+                    return value;
+                }
+                Entry::InProgress => None,
+                Entry::Vacant(entry) => Some(entry),
+            };
+            trace_event!(hit = false, "transposition table lookup");
+
+            if let Some(cf) = self.reductions() {
+                return cf;
+            }
+
+            let decompositions = self.decompositions();
+            trace_event!(components = decompositions.len(), "decomposition");
+            let result = decompositions
+                .into_par_iter()
+                .map(|position| {
+                    lookup(transposition_table, &position).map_or_else(
+                        || {
+                            // Left and Right options don't depend on each other, so they are
+                            // computed concurrently, sharing the transposition table
+                            let (left, right) = rayon::join(
+                                || {
+                                    position
+                                        .left_moves()
+                                        .into_par_iter()
+                                        .map(|o| o.canonical_form(transposition_table))
+                                        .collect()
+                                },
+                                || {
+                                    position
+                                        .right_moves()
+                                        .into_par_iter()
+                                        .map(|o| o.canonical_form(transposition_table))
+                                        .collect()
+                                },
+                            );
+
+                            CanonicalForm::new_from_moves(Moves { left, right })
+                        },
+                        |cached_sub_result| cached_sub_result,
+                    )
+                })
+                .reduce(|| CanonicalForm::new_integer(0), |a, b| a + b);
+
+            if let Some(entry) = entry {
+                entry.insert(result)
+            } else {
+                transposition_table.insert_position(self.clone(), result.clone());
+                result
+            }
         }
+    }
 
-        #[cfg(feature = "rayon")]
-        let decompositions = self.decompositions().into_par_iter();
-        #[cfg(not(feature = "rayon"))]
-        let decompositions = self.decompositions().into_iter();
-
-        let sub_results = decompositions.map(|position| {
-            transposition_table.lookup_position(&position).map_or_else(
-                || {
-                    #[cfg(feature = "rayon")]
-                    let left = position.left_moves().into_par_iter();
-                    #[cfg(not(feature = "rayon"))]
-                    let left = position.left_moves().into_iter();
-
-                    #[cfg(feature = "rayon")]
-                    let right = position.right_moves().into_par_iter();
-                    #[cfg(not(feature = "rayon"))]
-                    let right = position.right_moves().into_iter();
-
-                    let moves = Moves {
-                        left: left
-                            .map(|o| o.canonical_form(transposition_table))
-                            .collect(),
-                        right: right
-                            .map(|o| o.canonical_form(transposition_table))
-                            .collect(),
-                    };
-
-                    CanonicalForm::new_from_moves(moves)
-                },
-                |cached_sub_result| cached_sub_result,
-            )
-        });
+    /// Get the canonical form of the game position, using an explicit stack instead of recursion
+    ///
+    /// Gives the same result as [`Self::canonical_form`], but doesn't overflow the call stack
+    /// on deep game trees, e.g. long Hackenbush strings. Positions are evaluated sequentially,
+    /// and positions claimed in the transposition table by other threads are waited for.
+    fn canonical_form_iterative<TT>(&self, transposition_table: &TT) -> CanonicalForm
+    where
+        TT: TranspositionTable<Self>,
+    {
+        canonical_form_with_stack(self, transposition_table, None)
+            .expect("unreachable: computation without cancellation cannot be cancelled")
+    }
 
-        #[cfg(feature = "rayon")]
-        let result = sub_results.reduce(|| CanonicalForm::new_integer(0), |a, b| a + b);
-        #[cfg(not(feature = "rayon"))]
-        let result = sub_results.fold(CanonicalForm::new_integer(0), |a, b| a + b);
+    /// Get the canonical form of the game position, like [`Self::canonical_form_iterative`], but
+    /// abort when `cancellation` is triggered
+    ///
+    /// # Errors
+    /// - Computation was cancelled before it finished
+    fn canonical_form_cancellable<TT>(
+        &self,
+        transposition_table: &TT,
+        cancellation: &Cancellation,
+    ) -> Result<CanonicalForm, Cancelled>
+    where
+        TT: TranspositionTable<Self>,
+    {
+        canonical_form_with_stack(self, transposition_table, Some(cancellation))
+    }
+
+    /// Get the canonical form of the game position, dropping dominated options as soon as they
+    /// are found
+    ///
+    /// Duplicate options are evaluated once, and options already in the transposition table are
+    /// taken first, so they can be used to discard their siblings. Only exact values are
+    /// inserted into the transposition table, so it is safe to share it with
+    /// [`Self::canonical_form`] even if [`SensibleSearch::max_options`] is set.
+    fn sensible_canonical_form<TT>(
+        &self,
+        transposition_table: &TT,
+        search: &SensibleSearch,
+    ) -> CanonicalForm
+    where
+        TT: TranspositionTable<Self>,
+    {
+        trace_span!(DEBUG, "sensible_canonical_form");
+        sensible_search(self, transposition_table, search).0
+    }
+
+    /// Get the outcome of the game position under normal play, without constructing canonical
+    /// forms
+    ///
+    /// Search of options stops as soon as a winning move is found. Canonical forms already stored
+    /// in the transposition table are used to cut the search, but new ones are not inserted.
+    fn outcome<TT>(&self, transposition_table: &TT) -> Outcome
+    where
+        TT: TranspositionTable<Self>,
+    {
+        outcome_search(self, transposition_table, None)
+            .expect("unreachable: computation without cancellation cannot be cancelled")
+    }
 
-        transposition_table.insert_position(self.clone(), result.clone());
-        result
+    /// Get the outcome of the game position, like [`Self::outcome`], but abort when
+    /// `cancellation` is triggered
+    ///
+    /// # Errors
+    /// - Computation was cancelled before it finished
+    fn outcome_cancellable<TT>(
+        &self,
+        transposition_table: &TT,
+        cancellation: &Cancellation,
+    ) -> Result<Outcome, Cancelled>
+    where
+        TT: TranspositionTable<Self>,
+    {
+        outcome_search(self, transposition_table, Some(cancellation))
+    }
+
+    /// Count leaves of the game tree of given depth, where every position has both Left and
+    /// Right options as children
+    ///
+    /// Only moves are generated, without evaluating any positions, so it can be used to check
+    /// move generators against known counts and to measure their speed.
+    fn perft(&self, depth: u32) -> u64 {
+        perft_search(self, depth, &mut None)
+    }
+
+    /// Count leaves of the game tree like [`Self::perft`], but remember counts of subtrees in
+    /// `cache`, so positions reached by different sequences of moves are expanded once
+    fn perft_cached(&self, depth: u32, cache: &mut HashMap<(Self, u32), u64>) -> u64 {
+        perft_search(self, depth, &mut Some(cache))
     }
 
     // TODO: Find a way to reduce duplication - maybe macro?
@@ -132,8 +275,12 @@ pub trait PartizanGame: Sized + Clone + Hash + Send + Sync + Eq {
         let canonical_form = self.canonical_form(transposition_table);
         let moves = canonical_form.to_moves();
 
-        self.left_moves()
-            .into_iter()
+        #[cfg(feature = "rayon")]
+        let left_moves = self.left_moves().into_par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let left_moves = self.left_moves().into_iter();
+
+        left_moves
             .filter(|m| {
                 let move_game_form = m.canonical_form(transposition_table);
                 let res = moves.left.iter().any(|k| k <= &move_game_form);
@@ -150,8 +297,12 @@ pub trait PartizanGame: Sized + Clone + Hash + Send + Sync + Eq {
         let canonical_form = self.canonical_form(transposition_table);
         let moves = canonical_form.to_moves();
 
-        self.right_moves()
-            .into_iter()
+        #[cfg(feature = "rayon")]
+        let right_moves = self.right_moves().into_par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let right_moves = self.right_moves().into_iter();
+
+        right_moves
             .filter(|m| {
                 let move_game_form = m.canonical_form(transposition_table);
                 let res = moves.right.iter().any(|k| k >= &move_game_form);
@@ -159,4 +310,633 @@ pub trait PartizanGame: Sized + Clone + Hash + Send + Sync + Eq {
             })
             .collect::<Vec<_>>()
     }
+
+    /// Best move for the Left player, or `None` if Left has no moves
+    ///
+    /// Winning moves, to positions at least zero, are preferred. Otherwise, and among winning
+    /// moves, the sensible move with the highest Right stop is chosen, so Left gets as much as
+    /// possible when both players play greedily afterwards.
+    fn best_left_move<TT>(&self, transposition_table: &TT) -> Option<Self>
+    where
+        TT: TranspositionTable<Self> + Sync,
+    {
+        let zero = CanonicalForm::new_integer(0);
+        self.sensible_left_moves(transposition_table)
+            .into_iter()
+            .max_by_key(|m| {
+                let canonical_form = m.canonical_form(transposition_table);
+                (canonical_form >= zero, canonical_form.right_stop())
+            })
+    }
+
+    /// Best move for the Right player, or `None` if Right has no moves. See
+    /// [`Self::best_left_move`]
+    fn best_right_move<TT>(&self, transposition_table: &TT) -> Option<Self>
+    where
+        TT: TranspositionTable<Self> + Sync,
+    {
+        let zero = CanonicalForm::new_integer(0);
+        self.sensible_right_moves(transposition_table)
+            .into_iter()
+            .max_by_key(|m| {
+                let canonical_form = m.canonical_form(transposition_table);
+                (canonical_form <= zero, -canonical_form.left_stop())
+            })
+    }
+}
+
+/// Implementation of [`PartizanGame::thermograph_direct`], reusing thermographs of positions
+/// reachable in more than one way
+fn thermograph_cached<G>(position: &G, cache: &mut HashMap<G, Thermograph>) -> Thermograph
+where
+    G: PartizanGame,
+{
+    if let Some(thermograph) = cache.get(position) {
+        return thermograph.clone();
+    }
+
+    let left_moves = position.left_moves();
+    let right_moves = position.right_moves();
+    let thermograph = if left_moves.is_empty() && right_moves.is_empty() {
+        Thermograph::with_mast(Rational::from(0))
+    } else {
+        let mut left_scaffold = Trajectory::new_constant(Rational::NegativeInfinity);
+        let mut right_scaffold = Trajectory::new_constant(Rational::PositiveInfinity);
+
+        for left_move in &left_moves {
+            left_scaffold = left_scaffold.max(&thermograph_cached(left_move, cache).right_wall);
+        }
+        for right_move in &right_moves {
+            right_scaffold = right_scaffold.min(&thermograph_cached(right_move, cache).left_wall);
+        }
+
+        left_scaffold.tilt(Rational::from(-1));
+        right_scaffold.tilt(Rational::from(1));
+
+        Thermograph::thermographic_intersection(left_scaffold, right_scaffold)
+    };
+
+    cache.insert(position.clone(), thermograph.clone());
+    thermograph
+}
+
+/// Look up position in the transposition table, tracing hits and misses
+#[inline]
+fn lookup<G, TT>(transposition_table: &TT, position: &G) -> Option<CanonicalForm>
+where
+    TT: TranspositionTable<G>,
+{
+    let value = transposition_table.lookup_position(position);
+    trace_event!(hit = value.is_some(), "transposition table lookup");
+    value
+}
+
+/// Implementation of [`PartizanGame::sensible_canonical_form`], also returning if the result is
+/// exact
+fn sensible_search<G, TT>(
+    position: &G,
+    transposition_table: &TT,
+    search: &SensibleSearch,
+) -> (CanonicalForm, bool)
+where
+    G: PartizanGame,
+    TT: TranspositionTable<G>,
+{
+    if let Some(id) = lookup(transposition_table, position) {
+        return (id, true);
+    }
+
+    if let Some(cf) = position.reductions() {
+        return (cf, true);
+    }
+
+    let mut exact = true;
+    let mut result = CanonicalForm::new_integer(0);
+    let decompositions = position.decompositions();
+    trace_event!(components = decompositions.len(), "decomposition");
+    for component in decompositions {
+        if let Some(id) = lookup(transposition_table, &component) {
+            result += id;
+            continue;
+        }
+
+        let (left, left_exact) =
+            sensible_options(component.left_moves(), true, transposition_table, search);
+        let (right, right_exact) =
+            sensible_options(component.right_moves(), false, transposition_table, search);
+        exact &= left_exact && right_exact;
+        result += CanonicalForm::new_from_moves(Moves { left, right });
+    }
+
+    if exact {
+        transposition_table.insert_position(position.clone(), result.clone());
+    }
+    (result, exact)
+}
+
+/// Evaluate options of one player, keeping only the undominated ones
+fn sensible_options<G, TT>(
+    options: Vec<G>,
+    left: bool,
+    transposition_table: &TT,
+    search: &SensibleSearch,
+) -> (Vec<CanonicalForm>, bool)
+where
+    G: PartizanGame,
+    TT: TranspositionTable<G>,
+{
+    let mut seen = HashSet::with_capacity(options.len());
+    let mut options = options
+        .into_iter()
+        .filter(|option| seen.insert(option.clone()))
+        .map(|option| {
+            let known = lookup(transposition_table, &option);
+            (option, known)
+        })
+        .collect::<Vec<_>>();
+
+    if search.order_moves {
+        // Moves of the player that is about to move are good for them, opponent's moves are good
+        // for the opponent
+        options.sort_by_cached_key(|(option, _)| {
+            let (own, other) = (option.left_moves().len(), option.right_moves().len());
+            let (own, other) = if left { (own, other) } else { (other, own) };
+            other as isize - own as isize
+        });
+    }
+    // Known values are free, so they go first to discard as many siblings as possible
+    options.sort_by_key(|(_, known)| known.is_none());
+
+    let limit = search.max_options.map_or(usize::MAX, NonZeroUsize::get);
+    let exact = options.len() <= limit;
+
+    let mut undominated: Vec<CanonicalForm> = Vec::new();
+    let dominates = |lhs: &CanonicalForm, rhs: &CanonicalForm| {
+        if left {
+            CanonicalForm::leq(rhs, lhs)
+        } else {
+            CanonicalForm::leq(lhs, rhs)
+        }
+    };
+    let mut all_exact = exact;
+    for (option, known) in options.into_iter().take(limit) {
+        let value = known.unwrap_or_else(|| {
+            let (value, option_exact) = sensible_search(&option, transposition_table, search);
+            all_exact &= option_exact;
+            value
+        });
+        if undominated.iter().any(|other| dominates(other, &value)) {
+            continue;
+        }
+        undominated.retain(|other| !dominates(&value, other));
+        undominated.push(value);
+    }
+
+    (undominated, all_exact)
+}
+
+/// Implementation of [`PartizanGame::perft`] and [`PartizanGame::perft_cached`]
+fn perft_search<G>(position: &G, depth: u32, cache: &mut Option<&mut HashMap<(G, u32), u64>>) -> u64
+where
+    G: PartizanGame,
+{
+    match depth {
+        0 => return 1,
+        1 => {
+            return position.left_moves_iter().count() as u64
+                + position.right_moves_iter().count() as u64
+        }
+        _ => {}
+    }
+
+    let key = (position.clone(), depth);
+    if let Some(count) = cache.as_ref().and_then(|cache| cache.get(&key)) {
+        return *count;
+    }
+
+    let count = position
+        .left_moves_iter()
+        .chain(position.right_moves_iter())
+        .map(|option| perft_search(&option, depth - 1, cache))
+        .sum();
+
+    if let Some(cache) = cache {
+        cache.insert(key, count);
+    }
+    count
+}
+
+/// Implementation of [`PartizanGame::outcome`] and [`PartizanGame::outcome_cancellable`]
+fn outcome_search<G, TT>(
+    position: &G,
+    transposition_table: &TT,
+    cancellation: Option<&Cancellation>,
+) -> Result<Outcome, Cancelled>
+where
+    G: PartizanGame,
+    TT: TranspositionTable<G>,
+{
+    trace_span!(DEBUG, "outcome");
+    let mut cache = HashMap::new();
+    Ok(Outcome::new(
+        wins_going_first(
+            position,
+            true,
+            transposition_table,
+            cancellation,
+            &mut cache,
+        )?,
+        !wins_going_first(
+            position,
+            false,
+            transposition_table,
+            cancellation,
+            &mut cache,
+        )?,
+    ))
+}
+
+/// Check if player wins when making the first move, used by [`PartizanGame::outcome`]
+fn wins_going_first<G, TT>(
+    position: &G,
+    left: bool,
+    transposition_table: &TT,
+    cancellation: Option<&Cancellation>,
+    cache: &mut HashMap<(G, bool), bool>,
+) -> Result<bool, Cancelled>
+where
+    G: PartizanGame,
+    TT: TranspositionTable<G>,
+{
+    if let Some(wins) = cache.get(&(position.clone(), left)) {
+        return Ok(*wins);
+    }
+
+    if cancellation.is_some_and(Cancellation::is_cancelled) {
+        return Err(Cancelled);
+    }
+
+    let wins = if let Some(canonical_form) = lookup(transposition_table, position) {
+        let zero = CanonicalForm::new_integer(0);
+        if left {
+            !CanonicalForm::leq(&canonical_form, &zero)
+        } else {
+            !CanonicalForm::leq(&zero, &canonical_form)
+        }
+    } else {
+        // Moves are generated lazily, as search stops at the first winning one
+        let moves: Box<dyn Iterator<Item = G> + '_> = if left {
+            Box::new(position.left_moves_iter())
+        } else {
+            Box::new(position.right_moves_iter())
+        };
+        let mut wins = false;
+        for m in moves {
+            if !wins_going_first(&m, !left, transposition_table, cancellation, cache)? {
+                wins = true;
+                break;
+            }
+        }
+        wins
+    };
+
+    cache.insert((position.clone(), left), wins);
+    Ok(wins)
+}
+
+/// Implementation of [`PartizanGame::canonical_form_iterative`] and
+/// [`PartizanGame::canonical_form_cancellable`]
+fn canonical_form_with_stack<G, TT>(
+    position: &G,
+    transposition_table: &TT,
+    cancellation: Option<&Cancellation>,
+) -> Result<CanonicalForm, Cancelled>
+where
+    G: PartizanGame,
+    TT: TranspositionTable<G>,
+{
+    trace_span!(DEBUG, "canonical_form");
+    let mut tasks = vec![Task::Position(position.clone())];
+    let mut results: Vec<CanonicalForm> = Vec::new();
+
+    while let Some(task) = tasks.pop() {
+        if cancellation.is_some_and(Cancellation::is_cancelled) {
+            return Err(Cancelled);
+        }
+
+        match task {
+            Task::Position(position) => match transposition_table.entry(position.clone()) {
+                Entry::Occupied(value) => {
+                    trace_event!(hit = true, "transposition table lookup");
+                    results.push(value);
+                }
+                // Claims are held only by positions being evaluated, and a position cannot
+                // depend on its own value, so waiting cannot deadlock
+                Entry::InProgress => {
+                    transposition_table.wait_for_position(&position);
+                    tasks.push(Task::Position(position));
+                }
+                Entry::Vacant(entry) => {
+                    trace_event!(hit = false, "transposition table lookup");
+                    if let Some(cf) = position.reductions() {
+                        results.push(cf);
+                    } else {
+                        let decompositions = position.decompositions();
+                        trace_event!(components = decompositions.len(), "decomposition");
+                        tasks.push(Task::Sum {
+                            entry,
+                            components: decompositions.len(),
+                        });
+                        tasks.extend(decompositions.into_iter().rev().map(Task::Component));
+                    }
+                }
+            },
+            Task::Component(component) => {
+                if let Some(id) = lookup(transposition_table, &component) {
+                    results.push(id);
+                } else {
+                    let left_moves = component.left_moves();
+                    let right_moves = component.right_moves();
+                    tasks.push(Task::Moves {
+                        left: left_moves.len(),
+                        right: right_moves.len(),
+                    });
+                    // Pushed in reverse, so results are in the order of moves
+                    tasks.extend(
+                        left_moves
+                            .into_iter()
+                            .chain(right_moves)
+                            .rev()
+                            .map(Task::Position),
+                    );
+                }
+            }
+            Task::Moves { left, right } => {
+                let right = results.split_off(results.len() - right);
+                let left = results.split_off(results.len() - left);
+                results.push(CanonicalForm::new_from_moves(Moves { left, right }));
+            }
+            Task::Sum { entry, components } => {
+                let result = results
+                    .drain(results.len() - components..)
+                    .fold(CanonicalForm::new_integer(0), |a, b| a + b);
+                results.push(entry.insert(result));
+            }
+        }
+    }
+
+    debug_assert_eq!(results.len(), 1, "exactly one result must be left");
+    Ok(results
+        .pop()
+        .expect("unreachable: root position is always evaluated"))
+}
+
+/// Pending step of [`PartizanGame::canonical_form_iterative`]
+enum Task<'table, G, TT>
+where
+    TT: TranspositionTable<G>,
+{
+    /// Evaluate position and push its canonical form
+    Position(G),
+
+    /// Evaluate single component of a decomposition and push its canonical form
+    Component(G),
+
+    /// Replace canonical forms of Left and Right options with the canonical form of a game
+    Moves { left: usize, right: usize },
+
+    /// Replace canonical forms of components with their sum, and cache it for the claimed position
+    Sum {
+        entry: VacantEntry<'table, G, TT>,
+        components: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
+
+    /// Left can count down to zero one step at a time, so the value of `n` is `n`
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+    struct Countdown(u32);
+
+    impl PartizanGame for Countdown {
+        fn left_moves(&self) -> Vec<Self> {
+            self.0.checked_sub(1).map(Countdown).into_iter().collect()
+        }
+
+        fn right_moves(&self) -> Vec<Self> {
+            vec![]
+        }
+    }
+
+    #[test]
+    fn outcome_matches_canonical_form() {
+        use crate::{
+            grid::small_bit_grid::SmallBitGrid, short::partizan::games::domineering::Domineering,
+        };
+        use std::str::FromStr;
+
+        for (grid, outcome) in [
+            ("..|..", Outcome::N),
+            ("...|...", Outcome::N),
+            ("..|..|..", Outcome::N),
+            (".|.|.", Outcome::L),
+            ("...", Outcome::R),
+            ("...|#..", Outcome::N),
+            (".#|#.", Outcome::P),
+            ("####", Outcome::P),
+        ] {
+            let position = Domineering::new(SmallBitGrid::from_str(grid).unwrap());
+            let transposition_table = ParallelTranspositionTable::new();
+            assert_eq!(position.outcome(&transposition_table), outcome, "{grid}");
+
+            let canonical_form = position.canonical_form(&transposition_table);
+            let zero = CanonicalForm::new_integer(0);
+            assert_eq!(
+                Outcome::new(
+                    !CanonicalForm::leq(&canonical_form, &zero),
+                    CanonicalForm::leq(&zero, &canonical_form)
+                ),
+                outcome,
+                "{grid}"
+            );
+            assert_eq!(position.outcome(&transposition_table), outcome, "{grid}");
+        }
+    }
+
+    #[test]
+    fn sensible_canonical_form_matches() {
+        use crate::{
+            grid::small_bit_grid::SmallBitGrid, short::partizan::games::domineering::Domineering,
+        };
+        use std::str::FromStr;
+
+        let position = Domineering::new(SmallBitGrid::from_str("....|....|..#.").unwrap());
+        let expected = position.canonical_form(&ParallelTranspositionTable::new());
+
+        for order_moves in [false, true] {
+            let search = SensibleSearch {
+                order_moves,
+                max_options: None,
+            };
+            assert_eq!(
+                position.sensible_canonical_form(&ParallelTranspositionTable::new(), &search),
+                expected
+            );
+        }
+
+        // Approximate values must not end up in the transposition table
+        let transposition_table = ParallelTranspositionTable::new();
+        let search = SensibleSearch {
+            order_moves: true,
+            max_options: NonZeroUsize::new(1),
+        };
+        position.sensible_canonical_form(&transposition_table, &search);
+        assert_eq!(position.canonical_form(&transposition_table), expected);
+    }
+
+    #[test]
+    fn best_moves_win() {
+        use crate::{
+            grid::small_bit_grid::SmallBitGrid, short::partizan::games::domineering::Domineering,
+        };
+        use std::str::FromStr;
+
+        let transposition_table = ParallelTranspositionTable::new();
+        let zero = CanonicalForm::new_integer(0);
+
+        // Value is 1, Left wins moving first and Right has no moves
+        let position = Domineering::new(SmallBitGrid::from_str(".#|.#").unwrap());
+        assert!(position.best_right_move(&transposition_table).is_none());
+        let left_move = position.best_left_move(&transposition_table).unwrap();
+        assert!(left_move.canonical_form(&transposition_table) >= zero);
+
+        // Value is {1|-1}, both players win moving first
+        let position = Domineering::new(SmallBitGrid::from_str("..|.#").unwrap());
+        let left_move = position.best_left_move(&transposition_table).unwrap();
+        assert!(left_move.canonical_form(&transposition_table) >= zero);
+        let right_move = position.best_right_move(&transposition_table).unwrap();
+        assert!(right_move.canonical_form(&transposition_table) <= zero);
+
+        let transposition_table = ParallelTranspositionTable::new();
+        let countdown = Countdown(3);
+        assert_eq!(
+            countdown.best_left_move(&transposition_table),
+            Some(Countdown(2))
+        );
+    }
+
+    #[test]
+    fn temperature_direct() {
+        use crate::{
+            grid::small_bit_grid::SmallBitGrid, short::partizan::games::domineering::Domineering,
+        };
+        use std::str::FromStr;
+
+        for grid in ["..|..", "...|...", "..|..|..", "...|#..", "....|..#.|...."] {
+            let position = Domineering::new(SmallBitGrid::from_str(grid).unwrap());
+            let canonical_form = position.canonical_form(&ParallelTranspositionTable::new());
+            assert_eq!(
+                position.temperature_direct(),
+                canonical_form.temperature(),
+                "{grid}"
+            );
+        }
+    }
+
+    #[test]
+    fn perft() {
+        use crate::{
+            graph::generators,
+            short::partizan::games::{domineering::Domineering, snort::Snort},
+        };
+        use std::str::FromStr;
+
+        let position: Domineering = Domineering::from_str("..|..").unwrap();
+        assert_eq!(position.perft(0), 1);
+        assert_eq!(position.perft(1), 4);
+        assert_eq!(position.perft(2), 4);
+        assert_eq!(position.perft(3), 0);
+
+        assert_eq!(Countdown(3).perft(3), 1);
+        assert_eq!(Countdown(3).perft(4), 0);
+
+        let position: Snort = Snort::new(generators::path(5));
+        let mut cache = HashMap::new();
+        for depth in 0..6 {
+            assert_eq!(
+                position.perft_cached(depth, &mut cache),
+                position.perft(depth),
+                "{depth}"
+            );
+        }
+    }
+
+    #[test]
+    fn cancellation() {
+        let transposition_table = ParallelTranspositionTable::new();
+        let cancelled = Cancellation::new();
+        cancelled.cancel();
+        assert_eq!(
+            Countdown(10).canonical_form_cancellable(&transposition_table, &cancelled),
+            Err(Cancelled)
+        );
+        assert_eq!(
+            Countdown(10).outcome_cancellable(&transposition_table, &cancelled),
+            Err(Cancelled)
+        );
+
+        let not_cancelled = Cancellation::new();
+        assert_eq!(
+            Countdown(10).canonical_form_cancellable(&transposition_table, &not_cancelled),
+            Ok(CanonicalForm::new_integer(10))
+        );
+        assert_eq!(
+            Countdown(10).outcome_cancellable(&transposition_table, &not_cancelled),
+            Ok(Outcome::L)
+        );
+    }
+
+    #[test]
+    fn iterative_does_not_overflow() {
+        let transposition_table = ParallelTranspositionTable::new();
+        assert_eq!(
+            Countdown(100_000).canonical_form_iterative(&transposition_table),
+            CanonicalForm::new_integer(100_000)
+        );
+    }
+
+    #[test]
+    fn concurrent_evaluation_is_not_repeated() {
+        use crate::{
+            grid::small_bit_grid::SmallBitGrid,
+            short::partizan::{
+                games::domineering::Domineering, transposition_table::CountingTranspositionTable,
+            },
+        };
+        use std::str::FromStr;
+
+        let position = Domineering::new(SmallBitGrid::from_str("....|....|....").unwrap());
+        let transposition_table = CountingTranspositionTable::<Domineering>::default();
+        let values = std::thread::scope(|scope| {
+            let threads = (0..4)
+                .map(|_| scope.spawn(|| position.canonical_form_iterative(&transposition_table)))
+                .collect::<Vec<_>>();
+            threads
+                .into_iter()
+                .map(|thread| thread.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        assert!(values.windows(2).all(|pair| pair[0] == pair[1]));
+
+        // Every position is claimed by one thread, and others wait for its value
+        let statistics = transposition_table.statistics();
+        assert_eq!(statistics.insertions, statistics.positions as u64);
+        assert!(matches!(
+            transposition_table.entry(position),
+            Entry::Occupied(_)
+        ));
+    }
 }
@@ -0,0 +1,383 @@
+//! Text format of positions shared by all games
+//!
+//! Position string is `game:payload`, where `game` is the name of one of [`GAMES`] and `payload`
+//! is the position in the format of the game's [`FromStr`] and [`Display`] implementations, e.g.
+//! `domineering:..#|...` or `snort:...;0-1,1-2`. Position of any game can be parsed into
+//! [`AnyPosition`], which can be evaluated without knowing its type.
+//!
+//! # Examples
+//!
+//! ```
+//! use cgt::short::partizan::position::AnyPosition;
+//! use std::str::FromStr;
+//!
+//! let position = AnyPosition::from_str("domineering:..|.#").unwrap();
+//! assert_eq!(position.game(), "domineering");
+//! assert_eq!(position.canonical_form().to_string(), "*");
+//! assert_eq!(position.to_string(), "domineering:..|.#");
+//! ```
+
+use crate::{
+    error::Error,
+    short::partizan::{
+        cancellation::{Cancellation, Cancelled},
+        canonical_form::CanonicalForm,
+        cgsuite,
+        games::{
+            amazons::Amazons,
+            col::Col,
+            domineering::Domineering,
+            fission::Fission,
+            ski_jumps::SkiJumps,
+            snort::{DirectedSnort, Snort},
+            toads_and_frogs::ToadsAndFrogs,
+            vertex_coloring::VertexColoring,
+        },
+        misere_form::Outcome,
+        partizan_game::PartizanGame,
+        transposition_table::{
+            CountingTranspositionTable, ParallelTranspositionTable, Statistics, TableKind,
+            TranspositionTable,
+        },
+    },
+};
+use std::{
+    fmt::{self, Debug, Display},
+    str::FromStr,
+};
+
+/// Game with a name in the [position text format](self)
+pub trait NamedGame: PartizanGame + Display + FromStr<Err: Into<Error>> + 'static {
+    /// Name of the game, used as the prefix of position strings
+    const NAME: &'static str;
+}
+
+/// Game that can be parsed by [`parse_position`]
+#[derive(Debug, Clone, Copy)]
+pub struct GameFormat {
+    /// Name of the game, see [`NamedGame::NAME`]
+    pub name: &'static str,
+
+    /// Parse payload of the position string, i.e. the part after `name:`
+    pub parse: fn(&str) -> Result<AnyPosition, Error>,
+}
+
+impl GameFormat {
+    /// Format of positions of `G`
+    pub const fn of<G>() -> Self
+    where
+        G: NamedGame,
+    {
+        Self {
+            name: G::NAME,
+            parse: |payload| {
+                G::from_str(payload)
+                    .map(AnyPosition::new)
+                    .map_err(Into::into)
+            },
+        }
+    }
+}
+
+/// All games supported by [`parse_position`]
+pub const GAMES: &[GameFormat] = &[
+    GameFormat::of::<Amazons>(),
+    GameFormat::of::<Col>(),
+    GameFormat::of::<DirectedSnort>(),
+    GameFormat::of::<Domineering>(),
+    GameFormat::of::<Fission>(),
+    GameFormat::of::<SkiJumps>(),
+    GameFormat::of::<Snort>(),
+    GameFormat::of::<ToadsAndFrogs>(),
+    GameFormat::of::<VertexColoring>(),
+];
+
+/// Parse position in the `game:payload` format of any of the [`GAMES`]
+///
+/// # Errors
+/// - Input does not start with a name of a known game
+/// - Payload is not a valid position of the game
+pub fn parse_position(input: &str) -> Result<AnyPosition, Error> {
+    let (name, payload) = input
+        .split_once(':')
+        .ok_or_else(|| Error::Syntax(format!("missing game name in '{input}'")))?;
+    let game = GAMES
+        .iter()
+        .find(|game| game.name == name.trim())
+        .ok_or_else(|| Error::Syntax(format!("unknown game '{}'", name.trim())))?;
+    (game.parse)(payload)
+}
+
+/// Operations of [`AnyPosition`] that do not depend on the type of the game
+trait ErasedPosition: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn fmt_payload(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+    fn clone_box(&self) -> Box<dyn ErasedPosition>;
+    fn left_moves(&self) -> Vec<AnyPosition>;
+    fn right_moves(&self) -> Vec<AnyPosition>;
+    fn decompositions(&self) -> Vec<AnyPosition>;
+    fn best_left_move(&self) -> Option<AnyPosition>;
+    fn best_right_move(&self) -> Option<AnyPosition>;
+    fn canonical_form(
+        &self,
+        cancellation: Option<&Cancellation>,
+    ) -> Result<CanonicalForm, Cancelled>;
+    fn canonical_form_with_table(&self, table: TableKind) -> (CanonicalForm, Statistics);
+    fn outcome(&self) -> Outcome;
+    fn to_cgsuite(&self) -> String;
+}
+
+impl<G> ErasedPosition for G
+where
+    G: NamedGame,
+{
+    fn name(&self) -> &'static str {
+        G::NAME
+    }
+
+    fn fmt_payload(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+
+    fn clone_box(&self) -> Box<dyn ErasedPosition> {
+        Box::new(self.clone())
+    }
+
+    fn left_moves(&self) -> Vec<AnyPosition> {
+        PartizanGame::left_moves(self)
+            .into_iter()
+            .map(AnyPosition::new)
+            .collect()
+    }
+
+    fn right_moves(&self) -> Vec<AnyPosition> {
+        PartizanGame::right_moves(self)
+            .into_iter()
+            .map(AnyPosition::new)
+            .collect()
+    }
+
+    fn decompositions(&self) -> Vec<AnyPosition> {
+        PartizanGame::decompositions(self)
+            .into_iter()
+            .map(AnyPosition::new)
+            .collect()
+    }
+
+    fn best_left_move(&self) -> Option<AnyPosition> {
+        PartizanGame::best_left_move(self, &ParallelTranspositionTable::new()).map(AnyPosition::new)
+    }
+
+    fn best_right_move(&self) -> Option<AnyPosition> {
+        PartizanGame::best_right_move(self, &ParallelTranspositionTable::new())
+            .map(AnyPosition::new)
+    }
+
+    fn canonical_form(
+        &self,
+        cancellation: Option<&Cancellation>,
+    ) -> Result<CanonicalForm, Cancelled> {
+        let transposition_table = ParallelTranspositionTable::new();
+        cancellation.map_or_else(
+            || Ok(PartizanGame::canonical_form(self, &transposition_table)),
+            |cancellation| self.canonical_form_cancellable(&transposition_table, cancellation),
+        )
+    }
+
+    fn canonical_form_with_table(&self, table: TableKind) -> (CanonicalForm, Statistics) {
+        let transposition_table = CountingTranspositionTable::new(table.build::<G>());
+        let canonical_form = PartizanGame::canonical_form(self, &transposition_table);
+        (canonical_form, transposition_table.statistics())
+    }
+
+    fn outcome(&self) -> Outcome {
+        PartizanGame::outcome(self, &ParallelTranspositionTable::new())
+    }
+
+    fn to_cgsuite(&self) -> String {
+        cgsuite::position_to_cgsuite(self)
+    }
+}
+
+/// Position of any [`NamedGame`], with the type of the game erased
+///
+/// Parsed from and displayed in the `game:payload` [format](self). Every evaluation uses a new
+/// transposition table, so positions that are evaluated many times should be converted back to
+/// their concrete types.
+pub struct AnyPosition {
+    inner: Box<dyn ErasedPosition>,
+}
+
+impl AnyPosition {
+    /// Erase type of the position
+    pub fn new<G>(position: G) -> Self
+    where
+        G: NamedGame,
+    {
+        Self {
+            inner: Box::new(position),
+        }
+    }
+
+    /// Name of the game of the position
+    pub fn game(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    /// List of all moves for the Left player
+    pub fn left_moves(&self) -> Vec<Self> {
+        self.inner.left_moves()
+    }
+
+    /// List of all moves for the Right player
+    pub fn right_moves(&self) -> Vec<Self> {
+        self.inner.right_moves()
+    }
+
+    /// Split position into disjoint sum, see [`PartizanGame::decompositions`]
+    pub fn decompositions(&self) -> Vec<Self> {
+        self.inner.decompositions()
+    }
+
+    /// Best move for the Left player, see [`PartizanGame::best_left_move`]
+    pub fn best_left_move(&self) -> Option<Self> {
+        self.inner.best_left_move()
+    }
+
+    /// Best move for the Right player, see [`PartizanGame::best_right_move`]
+    pub fn best_right_move(&self) -> Option<Self> {
+        self.inner.best_right_move()
+    }
+
+    /// Get the canonical form of the position
+    pub fn canonical_form(&self) -> CanonicalForm {
+        self.inner
+            .canonical_form(None)
+            .expect("unreachable: computation without cancellation cannot be cancelled")
+    }
+
+    /// Get the canonical form of the position, aborting when `cancellation` is triggered
+    ///
+    /// # Errors
+    /// - Computation was cancelled before it finished
+    pub fn canonical_form_cancellable(
+        &self,
+        cancellation: &Cancellation,
+    ) -> Result<CanonicalForm, Cancelled> {
+        self.inner.canonical_form(Some(cancellation))
+    }
+
+    /// Get the canonical form of the position using a fresh transposition table of given kind,
+    /// together with statistics of the table after the evaluation
+    pub fn canonical_form_with_table(&self, table: TableKind) -> (CanonicalForm, Statistics) {
+        self.inner.canonical_form_with_table(table)
+    }
+
+    /// Get the outcome of the position under normal play
+    pub fn outcome(&self) -> Outcome {
+        self.inner.outcome()
+    }
+
+    /// Full game tree of the position as a cgsuite expression, see
+    /// [`cgsuite::position_to_cgsuite`]
+    pub fn to_cgsuite(&self) -> String {
+        self.inner.to_cgsuite()
+    }
+}
+
+impl Clone for AnyPosition {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone_box(),
+        }
+    }
+}
+
+impl Display for AnyPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.inner.name())?;
+        self.inner.fmt_payload(f)
+    }
+}
+
+impl Debug for AnyPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AnyPosition")
+            .field(&format_args!("{self}"))
+            .finish()
+    }
+}
+
+impl FromStr for AnyPosition {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_position(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for input in [
+            "amazons:x..#|.#.o",
+            "col:L..;0-1,1-2",
+            "directed-snort:...;0>1,1-2",
+            "domineering:..#|...",
+            "fission:..|.x",
+            "ski-jumps:L...|..R.",
+            "snort:.L.;0-1,1-2",
+            "toads-and-frogs:T..F",
+            "vertex-coloring:col;...;0-1,1-2",
+        ] {
+            let position = parse_position(input).unwrap();
+            let reparsed = parse_position(&position.to_string()).unwrap();
+            assert_eq!(reparsed.to_string(), position.to_string());
+            assert_eq!(reparsed.canonical_form(), position.canonical_form());
+        }
+    }
+
+    #[test]
+    fn names_are_unique() {
+        for (i, game) in GAMES.iter().enumerate() {
+            assert!(GAMES[i + 1..].iter().all(|other| other.name != game.name));
+        }
+    }
+
+    #[test]
+    fn matches_concrete_type() {
+        let position = parse_position("snort:....;0-1,1-2,2-3").unwrap();
+        let snort: Snort = Snort::from_str("....;0-1,1-2,2-3").unwrap();
+        assert_eq!(
+            position.canonical_form(),
+            PartizanGame::canonical_form(&snort, &ParallelTranspositionTable::new())
+        );
+        assert_eq!(
+            position.left_moves().len(),
+            PartizanGame::left_moves(&snort).len()
+        );
+        assert_eq!(
+            position.outcome(),
+            PartizanGame::outcome(&snort, &ParallelTranspositionTable::new())
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            parse_position("..#|...").unwrap_err(),
+            Error::Syntax("missing game name in '..#|...'".to_owned())
+        );
+        assert_eq!(
+            parse_position("chess:rnbqkbnr").unwrap_err(),
+            Error::Syntax("unknown game 'chess'".to_owned())
+        );
+        assert!(matches!(
+            parse_position("domineering:..|."),
+            Err(Error::Grid(_))
+        ));
+    }
+}
@@ -0,0 +1,160 @@
+//! Sampling random positions of games
+//!
+//! [`RandomPosition`] lets tools like censuses, fuzzers or Monte-Carlo estimates of temperature
+//! work with any game, given parameters of the game's distribution.
+
+use rand::{seq::index, Rng};
+
+/// Games that can sample random positions
+pub trait RandomPosition: Sized {
+    /// Size and other parameters of the distribution of positions
+    type Parameters;
+
+    /// Sample a random position. Returns `None` if no position matches the parameters, e.g.
+    /// board is too large for the grid or there are more pieces than tiles.
+    fn random_position<R>(parameters: &Self::Parameters, rng: &mut R) -> Option<Self>
+    where
+        R: Rng;
+}
+
+/// Parameters of random positions of games played on grids
+///
+/// Games use only some of the parameters, see the implementations of [`RandomPosition`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridParameters {
+    /// Width of the board
+    pub width: u8,
+
+    /// Height of the board
+    pub height: u8,
+
+    /// Number of pieces of each player, placed on distinct random tiles
+    pub pieces: u8,
+
+    /// Probability that a tile without pieces is filled, e.g. taken in Domineering
+    pub density: f64,
+}
+
+/// Parameters of random positions of games played on graphs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GraphParameters {
+    /// Number of vertices
+    pub vertices: usize,
+
+    /// Probability that an edge is present, see
+    /// [`random_gnp`](crate::graph::generators::random_gnp)
+    pub edge_probability: f64,
+}
+
+/// Choose `pieces` distinct tiles of each player out of `tiles` tiles, as indices of tiles of
+/// Left and Right
+pub(crate) fn sample_pieces<R>(tiles: usize, pieces: u8, rng: &mut R) -> Option<Vec<usize>>
+where
+    R: Rng,
+{
+    let amount = 2 * pieces as usize;
+    (amount <= tiles).then(|| index::sample(rng, tiles, amount).into_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::{adjacency_matrix::undirected::UndirectedGraph, Graph},
+        short::partizan::games::{
+            amazons::Amazons,
+            domineering::Domineering,
+            fission::Fission,
+            ski_jumps::SkiJumps,
+            snort::Snort,
+            toads_and_frogs::ToadsAndFrogs,
+            vertex_coloring::{ColoringRules, VertexColoring},
+        },
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const GRID: GridParameters = GridParameters {
+        width: 5,
+        height: 3,
+        pieces: 2,
+        density: 0.3,
+    };
+
+    const GRAPH: GraphParameters = GraphParameters {
+        vertices: 6,
+        edge_probability: 0.5,
+    };
+
+    fn count(position: &impl ToString, c: char) -> usize {
+        position
+            .to_string()
+            .chars()
+            .filter(|tile| *tile == c)
+            .count()
+    }
+
+    #[test]
+    fn sizes_and_pieces() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let domineering: Domineering = Domineering::random_position(&GRID, &mut rng).unwrap();
+        assert_eq!(domineering.to_string().len(), 5 * 3 + 2);
+
+        let amazons: Amazons = Amazons::random_position(&GRID, &mut rng).unwrap();
+        assert_eq!(count(&amazons, 'x'), 2);
+        assert_eq!(count(&amazons, 'o'), 2);
+
+        let fission: Fission = Fission::random_position(&GRID, &mut rng).unwrap();
+        assert_eq!(fission.to_string().len(), 5 * 3 + 2);
+
+        let ski_jumps: SkiJumps = SkiJumps::random_position(&GRID, &mut rng).unwrap();
+        assert_eq!(count(&ski_jumps, 'L') + count(&ski_jumps, 'l'), 2);
+        assert_eq!(count(&ski_jumps, 'R') + count(&ski_jumps, 'r'), 2);
+
+        let toads_and_frogs = ToadsAndFrogs::random_position(&GRID, &mut rng).unwrap();
+        assert_eq!(toads_and_frogs.to_string().len(), 5);
+        assert_eq!(count(&toads_and_frogs, 'T'), 2);
+        assert_eq!(count(&toads_and_frogs, 'F'), 2);
+
+        let snort: Snort = Snort::random_position(&GRAPH, &mut rng).unwrap();
+        assert_eq!(snort.graph.size(), 6);
+
+        let coloring: VertexColoring =
+            VertexColoring::random_position(&(ColoringRules::col(), GRAPH), &mut rng).unwrap();
+        assert_eq!(coloring.graph.size(), 6);
+        assert_eq!(coloring.rules, ColoringRules::col());
+    }
+
+    #[test]
+    fn impossible_parameters() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let crowded = GridParameters {
+            width: 2,
+            height: 2,
+            pieces: 3,
+            density: 0.0,
+        };
+        let amazons: Option<Amazons> = Amazons::random_position(&crowded, &mut rng);
+        assert!(amazons.is_none());
+        assert!(ToadsAndFrogs::random_position(&crowded, &mut rng).is_none());
+
+        let too_large = GridParameters {
+            width: 16,
+            height: 16,
+            pieces: 0,
+            density: 0.0,
+        };
+        let domineering: Option<Domineering> = Domineering::random_position(&too_large, &mut rng);
+        assert!(domineering.is_none());
+    }
+
+    #[test]
+    fn deterministic_with_seed() {
+        let sample = |seed| {
+            let position: Snort<UndirectedGraph> =
+                Snort::random_position(&GRAPH, &mut StdRng::seed_from_u64(seed)).unwrap();
+            position.to_string()
+        };
+        assert_eq!(sample(7), sample(7));
+    }
+}
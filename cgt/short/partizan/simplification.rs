@@ -0,0 +1,484 @@
+//! Step-by-step trace of reducing a game to its canonical form.
+//!
+//! Game `{G^L | G^R}` with canonical options is reduced by repeatedly applying the following
+//! steps until none of them applies:
+//! - Removing duplicate options
+//! - Removing dominated options, i.e. Left option `G^L1` if `G^L1 ≤ G^L2` for other Left
+//!   option `G^L2` (and symmetrically for Right)
+//! - Bypassing reversible options, i.e. replacing Left option `G^L` with all Left options of
+//!   `G^LR` if `G^LR ≤ G` (and symmetrically for Right)
+//! - Replacing options with the options of the simplest number between them, if all options
+//!   are numbers and every Left option is less than every Right option
+
+use crate::{
+    display,
+    numeric::dyadic_rational_number::DyadicRationalNumber,
+    short::partizan::canonical_form::{CanonicalForm, Moves},
+};
+use std::fmt::{self, Display};
+
+/// Player whose option is affected by the simplification step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Player {
+    /// Left player
+    Left,
+
+    /// Right player
+    Right,
+}
+
+impl Display for Player {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Left => write!(f, "Left"),
+            Self::Right => write!(f, "Right"),
+        }
+    }
+}
+
+/// Single step of the simplification
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SimplificationStep {
+    /// Option appeared more than once
+    RemoveDuplicate {
+        /// Owner of the option
+        player: Player,
+
+        /// Removed option
+        option: CanonicalForm,
+    },
+
+    /// Option was dominated by other option of the same player
+    RemoveDominated {
+        /// Owner of the option
+        player: Player,
+
+        /// Removed option
+        option: CanonicalForm,
+
+        /// Option that is at least as good for the player
+        dominated_by: CanonicalForm,
+    },
+
+    /// Option was reversible and got replaced by options of its reversing move
+    BypassReversible {
+        /// Owner of the option
+        player: Player,
+
+        /// Bypassed option
+        option: CanonicalForm,
+
+        /// Response of the opponent that reverses the option
+        reversed_through: CanonicalForm,
+
+        /// Options that replaced the bypassed option
+        replacements: Vec<CanonicalForm>,
+    },
+
+    /// All options were numbers, so the game is the simplest number between them
+    SimplestNumber {
+        /// Replaced options
+        options: Moves,
+
+        /// Simplest number between the options
+        number: DyadicRationalNumber,
+    },
+}
+
+impl Display for SimplificationStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RemoveDuplicate { player, option } => {
+                write!(f, "Remove duplicate {} option {}", player, option)
+            }
+            Self::RemoveDominated {
+                player,
+                option,
+                dominated_by,
+            } => write!(
+                f,
+                "Remove {} option {} dominated by {}",
+                player, option, dominated_by
+            ),
+            Self::BypassReversible {
+                player,
+                option,
+                reversed_through,
+                replacements,
+            } => {
+                write!(
+                    f,
+                    "Bypass {} option {} reversible through {}, replacing it with ",
+                    player, option, reversed_through
+                )?;
+                display::braces(f, |f| display::commas(f, replacements))
+            }
+            Self::SimplestNumber { options, number } => {
+                write!(
+                    f,
+                    "Replace options {} with simplest number {} between them",
+                    options, number
+                )
+            }
+        }
+    }
+}
+
+/// Trace of reducing a game to its canonical form
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimplificationTrace {
+    initial: Moves,
+    steps: Vec<SimplificationStep>,
+    result: CanonicalForm,
+}
+
+impl SimplificationTrace {
+    /// Simplify game given by its (canonical) options, recording every step
+    ///
+    /// # Panics
+    /// - Number in the game or in the options considered while simplifying it overflows
+    pub fn new(moves: Moves) -> Self {
+        // Simplification steps do not change the value of the game, so comparisons with `G`
+        // can be done against its canonical form
+        let game = CanonicalForm::new_from_moves(moves.clone());
+
+        let mut steps = Vec::new();
+        let mut left = moves.left.clone();
+        let mut right = moves.right.clone();
+
+        Self::remove_duplicates(&mut left, Player::Left, &mut steps);
+        Self::remove_duplicates(&mut right, Player::Right, &mut steps);
+
+        loop {
+            if Self::remove_dominated(&mut left, Player::Left, &mut steps)
+                || Self::remove_dominated(&mut right, Player::Right, &mut steps)
+                || Self::replace_with_simplest_number(&mut left, &mut right, &mut steps)
+                || Self::bypass_reversible(&mut left, &game, Player::Left, &mut steps)
+                || Self::bypass_reversible(&mut right, &game, Player::Right, &mut steps)
+            {
+                continue;
+            }
+            break;
+        }
+
+        Self {
+            initial: moves,
+            steps,
+            result: CanonicalForm::checked_from_canonical_moves(Moves { left, right })
+                .expect("canonical form overflowed"),
+        }
+    }
+
+    /// Options of the game before simplification
+    pub const fn initial(&self) -> &Moves {
+        &self.initial
+    }
+
+    /// Steps of the simplification, in order of application
+    pub fn steps(&self) -> &[SimplificationStep] {
+        &self.steps
+    }
+
+    /// Canonical form of the game
+    pub const fn result(&self) -> &CanonicalForm {
+        &self.result
+    }
+
+    fn remove_duplicates(
+        options: &mut Vec<CanonicalForm>,
+        player: Player,
+        steps: &mut Vec<SimplificationStep>,
+    ) {
+        let mut idx = 0;
+        while idx < options.len() {
+            if options[..idx].contains(&options[idx]) {
+                let option = options.remove(idx);
+                steps.push(SimplificationStep::RemoveDuplicate { player, option });
+            } else {
+                idx += 1;
+            }
+        }
+    }
+
+    /// Remove first dominated option, if any
+    fn remove_dominated(
+        options: &mut Vec<CanonicalForm>,
+        player: Player,
+        steps: &mut Vec<SimplificationStep>,
+    ) -> bool {
+        let is_worse = |lhs: &CanonicalForm, rhs: &CanonicalForm| match player {
+            Player::Left => CanonicalForm::leq(lhs, rhs),
+            Player::Right => CanonicalForm::leq(rhs, lhs),
+        };
+
+        for (idx, option) in options.iter().enumerate() {
+            let dominating = options
+                .iter()
+                .enumerate()
+                .find(|(other, dominating)| *other != idx && is_worse(option, dominating));
+            if let Some((_, dominated_by)) = dominating {
+                steps.push(SimplificationStep::RemoveDominated {
+                    player,
+                    option: option.clone(),
+                    dominated_by: dominated_by.clone(),
+                });
+                options.remove(idx);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Replace options with options of the simplest number between them, if all of them are
+    /// numbers and they are not options of that number already
+    fn replace_with_simplest_number(
+        left: &mut Vec<CanonicalForm>,
+        right: &mut Vec<CanonicalForm>,
+        steps: &mut Vec<SimplificationStep>,
+    ) -> bool {
+        let numbers = |options: &[CanonicalForm]| {
+            options
+                .iter()
+                .map(CanonicalForm::to_number)
+                .collect::<Option<Vec<_>>>()
+        };
+        let (Some(left_numbers), Some(right_numbers)) = (numbers(left), numbers(right)) else {
+            return false;
+        };
+
+        let lower = left_numbers.into_iter().max();
+        let upper = right_numbers.into_iter().min();
+        if let (Some(lower), Some(upper)) = (&lower, &upper) {
+            if lower >= upper {
+                return false;
+            }
+        }
+
+        let number =
+            simplest_number(lower.as_ref(), upper.as_ref()).expect("simplest number overflowed");
+        let number_moves = CanonicalForm::new_dyadic(number).to_moves();
+        let same_options = |lhs: &[CanonicalForm], rhs: &[CanonicalForm]| {
+            lhs.len() == rhs.len() && lhs.iter().all(|option| rhs.contains(option))
+        };
+        if same_options(&number_moves.left, left) && same_options(&number_moves.right, right) {
+            return false;
+        }
+
+        steps.push(SimplificationStep::SimplestNumber {
+            options: Moves {
+                left: left.clone(),
+                right: right.clone(),
+            },
+            number,
+        });
+        *left = number_moves.left;
+        *right = number_moves.right;
+        true
+    }
+
+    /// Bypass first reversible option, if any
+    fn bypass_reversible(
+        options: &mut Vec<CanonicalForm>,
+        game: &CanonicalForm,
+        player: Player,
+        steps: &mut Vec<SimplificationStep>,
+    ) -> bool {
+        for (idx, option) in options.iter().enumerate() {
+            let option_moves = option.to_moves();
+            let responses = match player {
+                Player::Left => &option_moves.right,
+                Player::Right => &option_moves.left,
+            };
+            let reverses = |response: &CanonicalForm| match player {
+                Player::Left => CanonicalForm::leq(response, game),
+                Player::Right => CanonicalForm::geq(response, game),
+            };
+
+            let Some(reversed_through) = responses
+                .iter()
+                .find(|response| reverses(response))
+                .cloned()
+            else {
+                continue;
+            };
+
+            let reversed_moves = reversed_through.to_moves();
+            let replacements = match player {
+                Player::Left => reversed_moves.left,
+                Player::Right => reversed_moves.right,
+            };
+            steps.push(SimplificationStep::BypassReversible {
+                player,
+                option: option.clone(),
+                reversed_through,
+                replacements: replacements.clone(),
+            });
+
+            options.remove(idx);
+            for replacement in replacements {
+                if options.contains(&replacement) {
+                    steps.push(SimplificationStep::RemoveDuplicate {
+                        player,
+                        option: replacement,
+                    });
+                } else {
+                    options.push(replacement);
+                }
+            }
+            return true;
+        }
+        false
+    }
+}
+
+/// Simplest number strictly between `lower` and `upper`, missing bounds are infinite. Returns
+/// `None` if the number overflows.
+fn simplest_number(
+    lower: Option<&DyadicRationalNumber>,
+    upper: Option<&DyadicRationalNumber>,
+) -> Option<DyadicRationalNumber> {
+    let zero = DyadicRationalNumber::from(0);
+
+    // Integer closest to zero that is above the lower bound and below the upper bound, if they
+    // allow any
+    let integer = match (lower, upper) {
+        (Some(lower), _) if lower >= &zero => {
+            DyadicRationalNumber::new_integer(lower.floor()).checked_step(1)?
+        }
+        (_, Some(upper)) if upper <= &zero => {
+            DyadicRationalNumber::new_integer(upper.ceil()).checked_step(-1)?
+        }
+        _ => zero,
+    };
+    if lower.is_none_or(|lower| lower < &integer) && upper.is_none_or(|upper| &integer < upper) {
+        return Some(integer);
+    }
+
+    // No integer between the bounds, so both of them are between the same consecutive integers.
+    // Bisecting that interval reaches numbers with smaller denominators first.
+    let (lower, upper) = (lower?, upper?);
+    let mut low = DyadicRationalNumber::new_integer(lower.floor());
+    let mut high = low.checked_step(1)?;
+    loop {
+        let middle = low.checked_mean(&high)?;
+        if &middle <= lower {
+            low = middle;
+        } else if &middle >= upper {
+            high = middle;
+        } else {
+            return Some(middle);
+        }
+    }
+}
+
+impl Display for SimplificationTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.initial)?;
+        for step in &self.steps {
+            writeln!(f, "{}", step)?;
+        }
+        write!(f, "= {}", self.result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn trace(input: &str) -> SimplificationTrace {
+        SimplificationTrace::new(Moves::from_str(input).unwrap())
+    }
+
+    #[test]
+    fn dominated_options() {
+        let trace = trace("{0, 1, 1|3, 2}");
+        assert_eq!(
+            trace.steps(),
+            &[
+                SimplificationStep::RemoveDuplicate {
+                    player: Player::Left,
+                    option: CanonicalForm::new_integer(1),
+                },
+                SimplificationStep::RemoveDominated {
+                    player: Player::Left,
+                    option: CanonicalForm::new_integer(0),
+                    dominated_by: CanonicalForm::new_integer(1),
+                },
+                SimplificationStep::RemoveDominated {
+                    player: Player::Right,
+                    option: CanonicalForm::new_integer(3),
+                    dominated_by: CanonicalForm::new_integer(2),
+                },
+            ]
+        );
+        assert_eq!(trace.result().to_string(), "3/2");
+    }
+
+    #[test]
+    fn reversible_options() {
+        // Right can answer `*` with `0`, that is at most `{*|}`
+        let trace = trace("{*|}");
+        assert_eq!(
+            trace.steps(),
+            &[SimplificationStep::BypassReversible {
+                player: Player::Left,
+                option: CanonicalForm::from_str("*").unwrap(),
+                reversed_through: CanonicalForm::new_integer(0),
+                replacements: vec![],
+            }]
+        );
+        assert_eq!(trace.result().to_string(), "0");
+        assert_eq!(
+            trace.to_string(),
+            "{*|}\nBypass Left option * reversible through 0, replacing it with {}\n= 0"
+        );
+    }
+
+    #[test]
+    fn simplest_number() {
+        let trace = trace("{0|2}");
+        assert_eq!(
+            trace.steps(),
+            &[SimplificationStep::SimplestNumber {
+                options: Moves::from_str("{0|2}").unwrap(),
+                number: DyadicRationalNumber::from(1),
+            }]
+        );
+        assert_eq!(trace.result().to_string(), "1");
+
+        let trace = self::trace("{-1/2|7/8}");
+        assert_eq!(
+            trace.to_string(),
+            "{-1/2|7/8}\nReplace options {-1/2|7/8} with simplest number 0 between them\n= 0"
+        );
+
+        let trace = self::trace("{5/8|3/4}");
+        assert_eq!(trace.result().to_string(), "11/16");
+        assert!(self::trace("{1/2|1}").steps().is_empty());
+    }
+
+    #[test]
+    fn agrees_with_canonical_form() {
+        for input in [
+            "{{2|1}, 1|{0|-1}, -1}",
+            "{*2, *|*2, *}",
+            "{{1|*}|{*|-1}, 0}",
+            "{0, *|*}",
+            "{0|2}",
+            "{-1, 1/2|3/4, 5}",
+            "{1|1}",
+            "{-3/2|-1}",
+            "{-5|}",
+            "{|-3/4}",
+            "{1, {2|0}|3}",
+        ] {
+            let moves = Moves::from_str(input).unwrap();
+            let trace = SimplificationTrace::new(moves.clone());
+            assert_eq!(trace.result(), &CanonicalForm::new_from_moves(moves));
+        }
+    }
+}
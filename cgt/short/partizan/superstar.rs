@@ -0,0 +1,114 @@
+//! Superstars, i.e. games of the form `↑^{a b c ...} = {0, *a, *b, *c, ... | *a, *b, *c, ...}`.
+//!
+//! Superstars appear frequently as values of all-small games. For example `↑^{1} = ↑` and
+//! `↑^{0} = *`.
+
+use crate::{
+    numeric::{dyadic_rational_number::DyadicRationalNumber, nimber::Nimber},
+    short::partizan::canonical_form::{CanonicalForm, Moves, Nus},
+};
+use alloc::vec::Vec;
+use std::fmt::{self, Display};
+
+/// Superstar `↑^{a b c ...}`
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Superstar {
+    nimbers: Vec<Nimber>,
+}
+
+impl Superstar {
+    /// Create new superstar with given nimbers in the exponent
+    pub fn new(mut nimbers: Vec<Nimber>) -> Self {
+        nimbers.sort();
+        nimbers.dedup();
+        Self { nimbers }
+    }
+
+    /// Get nimbers in the exponent
+    pub fn nimbers(&self) -> &[Nimber] {
+        &self.nimbers
+    }
+
+    /// Convert superstar to canonical form
+    pub fn to_canonical_form(&self) -> CanonicalForm {
+        let zero = DyadicRationalNumber::from(0);
+        let stars: Vec<CanonicalForm> = self
+            .nimbers
+            .iter()
+            .map(|nimber| CanonicalForm::new_nimber(zero, *nimber))
+            .collect();
+
+        let mut left = stars.clone();
+        left.push(CanonicalForm::new_integer(0));
+        CanonicalForm::new_from_moves(Moves { left, right: stars })
+    }
+
+    /// Recognize if the game is a superstar
+    pub fn from_canonical_form(game: &CanonicalForm) -> Option<Self> {
+        let moves = game.to_moves();
+        let to_nimber = |option: &CanonicalForm| {
+            option
+                .to_nus()
+                .filter(|nus| nus.is_nimber())
+                .map(Nus::nimber)
+        };
+
+        let right: Vec<Nimber> = moves.right.iter().map(to_nimber).collect::<Option<_>>()?;
+        let left: Vec<Nimber> = moves.left.iter().map(to_nimber).collect::<Option<_>>()?;
+
+        // Canonical form may have lost some of the stars due to reversibility, so we try to
+        // restore them from both sides
+        let mut nimbers = right;
+        nimbers.extend(left.into_iter().filter(|nimber| *nimber != Nimber::new(0)));
+        let superstar = Self::new(nimbers);
+        (&superstar.to_canonical_form() == game).then_some(superstar)
+    }
+}
+
+impl Display for Superstar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "^{{")?;
+        for (idx, nimber) in self.nimbers.iter().enumerate() {
+            if idx != 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", nimber.value())?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn superstar(nimbers: &[u32]) -> Superstar {
+        Superstar::new(nimbers.iter().copied().map(Nimber::new).collect())
+    }
+
+    #[test]
+    fn construction_works() {
+        assert_eq!(superstar(&[0]).to_canonical_form().to_string(), "*");
+        assert_eq!(superstar(&[1]).to_canonical_form().to_string(), "^");
+        assert_eq!(
+            superstar(&[2, 3]).to_canonical_form().to_string(),
+            "{0|*2, *3}"
+        );
+        assert_eq!(superstar(&[3, 2]).to_string(), "^{2 3}");
+    }
+
+    #[test]
+    fn recognition_works() {
+        for nimbers in [&[0][..], &[1], &[2], &[2, 3], &[0, 2, 5]] {
+            let superstar = superstar(nimbers);
+            let game = superstar.to_canonical_form();
+            let recognized = Superstar::from_canonical_form(&game).unwrap();
+            assert_eq!(recognized.to_canonical_form(), game);
+        }
+
+        let game = CanonicalForm::from_str("{1|-1}").unwrap();
+        assert_eq!(Superstar::from_canonical_form(&game), None);
+    }
+}
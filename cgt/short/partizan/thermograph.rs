@@ -6,8 +6,9 @@ use crate::{
     numeric::{dyadic_rational_number::DyadicRationalNumber, rational::Rational},
     short::partizan::trajectory::Trajectory,
 };
-use ahash::{HashSet, HashSetExt};
+use alloc::{format, string::String, vec, vec::Vec};
 use core::fmt;
+use hashbrown::HashSet;
 use std::{cmp::Ordering, fmt::Display, iter::once};
 
 /// See [thermograph](self) header
@@ -80,20 +81,112 @@ impl Thermograph {
         }
     }
 
+    /// Output thermograph as LaTeX `TikZ` picture with axes, both walls and the mast, where
+    /// positive values are on the left
+    pub fn to_tikz(&self) -> String {
+        self.to_tikz_with_scale(1.)
+    }
+
+    /// Like [`Self::to_tikz`] but allows to specify image scale. Scale must be positive
+    ///
+    /// # Panics
+    /// - `scale` is negative
+    /// - Thermograph has infinite walls
+    pub fn to_tikz_with_scale(&self, scale: f32) -> String {
+        use std::fmt::Write;
+
+        assert!(scale >= 0., "Scale must be positive");
+
+        // Values grow to the left, `0.0 -` avoids printing negative zero
+        let x = |value: Rational| 0.0 - value.as_f32().expect("Thermograph walls must be finite");
+        let y = |temperature: Rational| temperature.as_f32().unwrap();
+
+        let minus_one = Rational::from(-1);
+        let temperature = self.temperature().to_rational();
+        let top = temperature + Rational::from(1);
+        let x_min = self.right_wall.value_at(minus_one);
+        let x_max = self.left_wall.value_at(minus_one);
+
+        let mut buf = String::new();
+        write!(buf, "\\begin{{tikzpicture}}[scale={}] ", scale).unwrap();
+
+        // Axes, with ticks at integers
+        write!(
+            buf,
+            "\\draw[->] ({},0) -- ({},0); \\draw[->] (0,-1) -- (0,{}); ",
+            x(x_min) + 0.5,
+            x(x_max) - 0.5,
+            y(top) + 0.5,
+        )
+        .unwrap();
+        let floor = |value: Rational| {
+            let (numerator, denominator) = value.to_fraction().unwrap();
+            numerator.div_euclid(denominator as i64)
+        };
+        for value in -floor(-x_min)..=floor(x_max) {
+            write!(
+                buf,
+                "\\draw ({},0.1) -- ({},-0.1) node[below] {{${}$}}; ",
+                x(Rational::from(value)),
+                x(Rational::from(value)),
+                value
+            )
+            .unwrap();
+        }
+
+        for wall in [&self.left_wall, &self.right_wall] {
+            let mut points = vec![temperature];
+            points.extend(wall.critical_points.iter().copied());
+            points.push(minus_one);
+            points.dedup();
+
+            write!(buf, "\\draw[thick] ").unwrap();
+            for (idx, point) in points.into_iter().enumerate() {
+                if idx != 0 {
+                    write!(buf, " -- ").unwrap();
+                }
+                write!(buf, "({},{})", x(wall.value_at(point)), y(point)).unwrap();
+            }
+            write!(buf, "; ").unwrap();
+        }
+
+        let mast = self.get_mast();
+        write!(
+            buf,
+            "\\draw[thick,->] ({},{}) -- ({},{}); \\end{{tikzpicture}}",
+            x(mast),
+            y(temperature),
+            x(mast),
+            y(top),
+        )
+        .unwrap();
+        buf
+    }
+
     /// Calculate a thermograph given left and right scaffold. Note that scaffolds should be
     /// [tilted](Trajectory::tilt) before.
-    #[allow(clippy::cognitive_complexity, clippy::missing_panics_doc)]
     pub fn thermographic_intersection(
         left_scaffold: Trajectory,
         right_scaffold: Trajectory,
     ) -> Self {
+        Self::checked_thermographic_intersection(left_scaffold, right_scaffold)
+            .expect("thermograph value overflowed")
+    }
+
+    /// Like [`Self::thermographic_intersection`], but returns `None` if a value of the walls
+    /// overflows
+    #[allow(clippy::cognitive_complexity, clippy::missing_panics_doc)]
+    pub fn checked_thermographic_intersection(
+        left_scaffold: Trajectory,
+        right_scaffold: Trajectory,
+    ) -> Option<Self> {
         if left_scaffold == Trajectory::new_constant(Rational::PositiveInfinity)
             || right_scaffold == Trajectory::new_constant(Rational::NegativeInfinity)
         {
-            return Self {
+            return Some(Self {
                 left_wall: left_scaffold,
                 right_wall: right_scaffold,
-            };
+            });
         }
 
         let mut left_wall_cps: Vec<Rational> = Vec::new();
@@ -106,8 +199,8 @@ impl Thermograph {
         let minus_one = Rational::from(-1);
         let zero = Rational::from(0);
 
-        let ls_at_base: Rational = left_scaffold.value_at(minus_one);
-        let rs_at_base: Rational = right_scaffold.value_at(minus_one);
+        let ls_at_base: Rational = left_scaffold.checked_value_at(minus_one)?;
+        let rs_at_base: Rational = right_scaffold.checked_value_at(minus_one)?;
 
         let mut previous_cave_value: Option<Rational>;
 
@@ -167,18 +260,18 @@ impl Thermograph {
             }
 
             let now_in_hill_region: bool = matches!(
-                left_scaffold.compare_to_at(&right_scaffold, current_cp),
+                left_scaffold.checked_compare_to_at(&right_scaffold, &current_cp)?,
                 Ordering::Greater | Ordering::Equal
             );
             if previous_cave_value.is_none() && !now_in_hill_region {
                 // We were previously in a hill region, but just entered a cave region.
                 // Extend the hill to the crossover point.
-                let crossover_point = Trajectory::intersection_point(
+                let crossover_point = Trajectory::checked_intersection_point(
                     &left_scaffold.slopes[(next_cp_left + 1) as usize],
                     &left_scaffold.x_intercepts[(next_cp_left + 1) as usize],
                     &right_scaffold.slopes[(next_cp_right + 1) as usize],
                     &right_scaffold.x_intercepts[(next_cp_right + 1) as usize],
-                );
+                )?;
 
                 // debug_assert_eq!(
                 //     left_scaffold.value_at(&crossover_point),
@@ -208,25 +301,27 @@ impl Thermograph {
                 // Now add the cave mast.
                 let cave_mast_slope: Rational;
                 let cave_mast_intercept: Rational;
-                if left_scaffold.value_at(current_cp) > left_scaffold.value_at(crossover_point) {
+                if left_scaffold.checked_value_at(current_cp)?
+                    > left_scaffold.checked_value_at(crossover_point)?
+                {
                     // The left scaffold moves to the left above the crossover point.
                     // The cave mast follows the left scaffold.
                     cave_mast_slope = left_scaffold.slopes[(next_cp_left + 1) as usize];
                     cave_mast_intercept = left_scaffold.x_intercepts[(next_cp_left + 1) as usize];
-                    previous_cave_value = Some(left_scaffold.value_at(current_cp));
-                } else if right_scaffold.value_at(current_cp)
-                    < right_scaffold.value_at(crossover_point)
+                    previous_cave_value = Some(left_scaffold.checked_value_at(current_cp)?);
+                } else if right_scaffold.checked_value_at(current_cp)?
+                    < right_scaffold.checked_value_at(crossover_point)?
                 {
                     // The right scaffold moves to the right above the crossover point.
                     // The cave mast follows the right scaffold.
                     cave_mast_slope = right_scaffold.slopes[(next_cp_right + 1) as usize];
                     cave_mast_intercept = right_scaffold.x_intercepts[(next_cp_right + 1) as usize];
-                    previous_cave_value = Some(right_scaffold.value_at(current_cp));
+                    previous_cave_value = Some(right_scaffold.checked_value_at(current_cp)?);
                 } else {
                     // Neither of the above.
                     // The cave mast extends vertically above the crossover point.
                     cave_mast_slope = Rational::from(0);
-                    cave_mast_intercept = left_scaffold.value_at(crossover_point);
+                    cave_mast_intercept = left_scaffold.checked_value_at(crossover_point)?;
                     previous_cave_value = Some(cave_mast_intercept);
                 }
 
@@ -259,26 +354,31 @@ impl Thermograph {
                 // case (i) or (ii) depending on which happens *first*.
 
                 // First determine which crossing points exist and find their values.
-                let left_scaffold_crossing_point =
-                    if &left_scaffold.value_at(current_cp) > previous_cave_value_r {
-                        Some(
-                            (previous_cave_value_r
-                                - left_scaffold.x_intercepts[(next_cp_left + 1) as usize])
-                                / left_scaffold.slopes[(next_cp_left + 1) as usize],
-                        )
-                    } else {
-                        None
-                    };
-                let right_scaffold_crossing_point =
-                    if &right_scaffold.value_at(current_cp) < previous_cave_value_r {
-                        Some(
-                            (previous_cave_value_r
-                                - right_scaffold.x_intercepts[(next_cp_right + 1) as usize])
-                                / right_scaffold.slopes[(next_cp_right + 1) as usize],
-                        )
-                    } else {
-                        None
-                    };
+                let left_scaffold_crossing_point = if &left_scaffold.checked_value_at(current_cp)?
+                    > previous_cave_value_r
+                {
+                    Some(
+                        previous_cave_value_r
+                            .checked_sub(&left_scaffold.x_intercepts[(next_cp_left + 1) as usize])?
+                            .checked_div(&left_scaffold.slopes[(next_cp_left + 1) as usize])?,
+                    )
+                } else {
+                    None
+                };
+                let right_scaffold_crossing_point = if &right_scaffold
+                    .checked_value_at(current_cp)?
+                    < previous_cave_value_r
+                {
+                    Some(
+                        previous_cave_value_r
+                            .checked_sub(
+                                &right_scaffold.x_intercepts[(next_cp_right + 1) as usize],
+                            )?
+                            .checked_div(&right_scaffold.slopes[(next_cp_right + 1) as usize])?,
+                    )
+                } else {
+                    None
+                };
 
                 if left_scaffold_crossing_point.is_some()
                     && (right_scaffold_crossing_point.is_none()
@@ -320,14 +420,14 @@ impl Thermograph {
                     // To handle the right wall we need to know whether we've re-entered a hill
                     // region or not.
                     let new_right_cp = if now_in_hill_region {
-                        Trajectory::intersection_point(
+                        Trajectory::checked_intersection_point(
                             &left_scaffold.slopes[(next_cp_left + 1) as usize],
                             &left_scaffold.x_intercepts[(next_cp_left + 1) as usize],
                             &right_scaffold.slopes[(next_cp_right + 1) as usize],
                             &right_scaffold.x_intercepts[(next_cp_right + 1) as usize],
-                        )
+                        )?
                     } else {
-                        previous_cave_value = Some(left_scaffold.value_at(current_cp));
+                        previous_cave_value = Some(left_scaffold.checked_value_at(current_cp)?);
                         current_cp
                     };
 
@@ -380,14 +480,14 @@ impl Thermograph {
                     let new_left_cp = if now_in_hill_region {
                         // A hill region is indeed re-entered.  So the tilted mast for Left extends
                         // just up to the scaffolds' next point of intersection.
-                        Trajectory::intersection_point(
+                        Trajectory::checked_intersection_point(
                             &left_scaffold.slopes[(next_cp_left + 1) as usize],
                             &left_scaffold.x_intercepts[(next_cp_left + 1) as usize],
                             &right_scaffold.slopes[(next_cp_right + 1) as usize],
                             &right_scaffold.x_intercepts[(next_cp_right + 1) as usize],
-                        )
+                        )?
                     } else {
-                        previous_cave_value = Some(right_scaffold.value_at(current_cp));
+                        previous_cave_value = Some(right_scaffold.checked_value_at(current_cp)?);
                         current_cp
                     };
                     Trajectory::extend_trajectory(
@@ -478,10 +578,10 @@ impl Thermograph {
             x_intercepts: right_wall_x_intercepts,
         };
 
-        Self {
+        Some(Self {
             left_wall,
             right_wall,
-        }
+        })
     }
 }
 
@@ -621,3 +721,28 @@ impl Display for Thermograph {
         display::parens(f, |f| write!(f, "{}, {}", self.left_wall, self.right_wall))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::canonical_form::CanonicalForm;
+    use std::str::FromStr;
+
+    #[test]
+    fn tikz_works() {
+        let thermograph = CanonicalForm::from_str("{3|-1}").unwrap().thermograph();
+        assert_eq!(
+            thermograph.to_tikz(),
+            r"\begin{tikzpicture}[scale=1] \draw[->] (2.5,0) -- (-4.5,0); \draw[->] (0,-1) -- (0,3.5); \draw (2,0.1) -- (2,-0.1) node[below] {$-2$}; \draw (1,0.1) -- (1,-0.1) node[below] {$-1$}; \draw (0,0.1) -- (0,-0.1) node[below] {$0$}; \draw (-1,0.1) -- (-1,-0.1) node[below] {$1$}; \draw (-2,0.1) -- (-2,-0.1) node[below] {$2$}; \draw (-3,0.1) -- (-3,-0.1) node[below] {$3$}; \draw (-4,0.1) -- (-4,-0.1) node[below] {$4$}; \draw[thick] (-1,2) -- (-4,-1); \draw[thick] (-1,2) -- (2,-1); \draw[thick,->] (-1,2) -- (-1,3); \end{tikzpicture}"
+        );
+    }
+
+    #[test]
+    fn svg_is_standalone() {
+        let thermograph = CanonicalForm::from_str("{3|-1}").unwrap().thermograph();
+        let mut svg = String::new();
+        thermograph.to_svg(&mut svg).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>"));
+    }
+}
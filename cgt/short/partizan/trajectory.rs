@@ -2,6 +2,7 @@
 //! [thermograph](crate::short::partizan::thermograph) scaffolds.
 
 use crate::{display, numeric::rational::Rational};
+use alloc::{vec, vec::Vec};
 use itertools::Itertools;
 use std::{cmp::Ordering, fmt::Display};
 
@@ -34,13 +35,22 @@ impl Trajectory {
     /// Tilts this trajectory by `r`.
     /// If this trajectory has value `a(x)` at `x`, then the tilted trajectory has value `a(x) + rx`
     pub fn tilt(&mut self, r: Rational) {
+        self.checked_tilt(r).expect("trajectory slope overflowed");
+    }
+
+    /// Like [`Self::tilt`], but leaves the trajectory unchanged and returns `None` if a slope
+    /// overflows
+    pub fn checked_tilt(&mut self, r: Rational) -> Option<()> {
         if self.is_infinite() {
-            return;
+            return Some(());
         }
 
-        for slope in &mut self.slopes {
-            *slope += r;
-        }
+        self.slopes = self
+            .slopes
+            .iter()
+            .map(|slope| slope.checked_add(&r))
+            .collect::<Option<Vec<_>>>()?;
+        Some(())
     }
 
     /// Create a new trajectory with given slopes and critical points. Returns [None] if input
@@ -102,42 +112,54 @@ impl Trajectory {
 
     /// Gets the x value of this trajectory at the specified height (y value).
     pub fn value_at(&self, r: Rational) -> Rational {
+        self.checked_value_at(r)
+            .expect("trajectory value overflowed")
+    }
+
+    /// Like [`Self::value_at`], but returns `None` if the value overflows
+    pub fn checked_value_at(&self, r: Rational) -> Option<Rational> {
         let i = self
             .critical_points
             .iter()
             .take_while(|critical_point| r < **critical_point)
             .count();
         if r.is_infinite() && self.slopes[i] == Rational::from(0) {
-            self.x_intercepts[i]
+            Some(self.x_intercepts[i])
         } else {
-            (r * self.slopes[i]) + self.x_intercepts[i]
+            r.checked_mul(&self.slopes[i])?
+                .checked_add(&self.x_intercepts[i])
         }
     }
 
+    /// Compare values of trajectories at `t`, `None` if any of the values overflows
+    ///
     /// # Panics
     /// - When `t < -1`
-    pub(crate) fn compare_to_at(&self, other: &Self, t: Rational) -> Ordering {
-        assert!(t >= Rational::from(-1), "t < -1");
+    pub(crate) fn checked_compare_to_at(&self, other: &Self, t: &Rational) -> Option<Ordering> {
+        assert!(*t >= Rational::from(-1), "t < -1");
 
-        if t == Rational::PositiveInfinity {
+        if *t == Rational::PositiveInfinity {
             if self.slopes[0] == other.slopes[0] {
-                self.x_intercepts[0].cmp(&other.x_intercepts[0])
+                Some(self.x_intercepts[0].cmp(&other.x_intercepts[0]))
             } else {
-                self.slopes[0].cmp(&other.slopes[0])
+                Some(self.slopes[0].cmp(&other.slopes[0]))
             }
         } else {
-            self.value_at(t).cmp(&other.value_at(t))
+            Some(self.checked_value_at(*t)?.cmp(&other.checked_value_at(*t)?))
         }
     }
 
+    /// Height at which the lines cross, `None` if it overflows
     #[inline]
-    pub(crate) fn intersection_point(
+    pub(crate) fn checked_intersection_point(
         slope1: &Rational,
         x_intercept1: &Rational,
         slope2: &Rational,
         x_intercept2: &Rational,
-    ) -> Rational {
-        (x_intercept2 - x_intercept1) / (slope1 - slope2)
+    ) -> Option<Rational> {
+        x_intercept2
+            .checked_sub(x_intercept1)?
+            .checked_div(&slope1.checked_sub(slope2)?)
     }
 
     pub(crate) fn extend_trajectory(
@@ -171,18 +193,33 @@ impl Trajectory {
         self.x_intercepts[0].is_infinite()
     }
 
+    // Used only by thermographs of games, which need `std`
+    #[cfg(feature = "std")]
     #[inline]
     pub(crate) fn max(&self, other: &Self) -> Self {
-        self.minmax::<true>(other)
+        self.checked_max(other)
+            .expect("trajectory value overflowed")
     }
 
+    #[cfg(feature = "std")]
     #[inline]
     pub(crate) fn min(&self, other: &Self) -> Self {
-        self.minmax::<false>(other)
+        self.checked_min(other)
+            .expect("trajectory value overflowed")
+    }
+
+    #[inline]
+    pub(crate) fn checked_max(&self, other: &Self) -> Option<Self> {
+        self.checked_minmax::<true>(other)
+    }
+
+    #[inline]
+    pub(crate) fn checked_min(&self, other: &Self) -> Option<Self> {
+        self.checked_minmax::<false>(other)
     }
 
     #[allow(clippy::useless_let_if_seq, clippy::cognitive_complexity)]
-    fn minmax<const MAX: bool>(&self, other: &Self) -> Self {
+    fn checked_minmax<const MAX: bool>(&self, other: &Self) -> Option<Self> {
         let max_multiplier = if MAX { -1 } else { 1 };
         // We scan down through the critical points.  We keep track of which
         // trajectory was dominant at the previous critical point:
@@ -237,8 +274,9 @@ impl Trajectory {
 
             let dominant_at_current_critical_point = max_multiplier
                 * (self
-                    .value_at(current_critical_point)
-                    .cmp(&other.value_at(current_critical_point)) as i32);
+                    .checked_value_at(current_critical_point)?
+                    .cmp(&other.checked_value_at(current_critical_point)?)
+                    as i32);
 
             if (dominant_at_current_critical_point < 0 && dominant_at_previous_critical_point > 0)
                 || (dominant_at_current_critical_point > 0
@@ -248,10 +286,12 @@ impl Trajectory {
                 // must have been a crossover since the last critical point.
                 // The crossover occurs at the intersection of the two line
                 // segments above this critical point.
-                let crossover_point = (other.x_intercepts[next_critical_point_other]
-                    - self.x_intercepts[next_critical_point_self])
-                    / (self.slopes[next_critical_point_self]
-                        - other.slopes[next_critical_point_other]);
+                let crossover_point = Self::checked_intersection_point(
+                    &self.slopes[next_critical_point_self],
+                    &self.x_intercepts[next_critical_point_self],
+                    &other.slopes[next_critical_point_other],
+                    &other.x_intercepts[next_critical_point_other],
+                )?;
                 new_critical_points.push(crossover_point);
                 new_slopes.push(if dominant_at_previous_critical_point < 0 {
                     self.slopes[next_critical_point_self]
@@ -343,8 +383,8 @@ impl Trajectory {
         let negative_one = Rational::from(-1);
         let mut dominant_at_tail = max_multiplier
             * (self
-                .value_at(negative_one)
-                .cmp(&other.value_at(negative_one)) as i32);
+                .checked_value_at(negative_one)?
+                .cmp(&other.checked_value_at(negative_one)?) as i32);
         if dominant_at_tail == 0 {
             dominant_at_tail = max_multiplier
                 * (self
@@ -366,11 +406,11 @@ impl Trajectory {
             *other.x_intercepts.last().unwrap()
         });
 
-        Self {
+        Some(Self {
             critical_points: new_critical_points,
             slopes: new_slopes,
             x_intercepts: new_x_intercepts,
-        }
+        })
     }
 }
 
@@ -1,24 +1,293 @@
 //! Thread safe transposition table for game values
 
-use crate::short::partizan::canonical_form::CanonicalForm;
+use crate::{
+    error::Error,
+    short::partizan::{cancellation::Cancellation, canonical_form::CanonicalForm},
+};
 use append_only_vec::AppendOnlyVec;
 use dashmap::DashMap;
-use std::{hash::Hash, marker::PhantomData};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    hash::Hash,
+    marker::PhantomData,
+    mem::size_of,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Condvar, Mutex,
+    },
+};
 
 /// Interface of a transposition table
+///
+/// Every solver is generic over this trait, so a new caching strategy needs only an implementation
+/// of it, without changes to games. Tables are used through shared references from many threads,
+/// so implementations use interior mutability. The trait is also implemented for references,
+/// [`Box`] and [`Arc`] of tables, so a table can be shared between solvers or picked at runtime
+/// as `Box<dyn TranspositionTable<G> + Sync>`.
 pub trait TranspositionTable<G> {
     /// Lookup a position value if exists
     fn lookup_position(&self, position: &G) -> Option<CanonicalForm>;
 
     /// Save position and its game value
     fn insert_position(&self, position: G, value: CanonicalForm);
+
+    /// Claim position for evaluation, so others reaching it can wait for its value instead of
+    /// evaluating it again. Returns `false` if the position is already claimed or evaluated.
+    /// Tables that do not track claims let everyone evaluate every position.
+    #[inline]
+    fn claim_position(&self, _position: &G) -> bool {
+        true
+    }
+
+    /// Release claim taken with [`Self::claim_position`]
+    #[inline]
+    fn release_position(&self, _position: &G) {}
+
+    /// Block until the position is not claimed by anyone
+    #[inline]
+    fn wait_for_position(&self, _position: &G) {}
+
+    /// Get the value of the position, or claim it for evaluation if no one else is evaluating it
+    fn entry(&self, position: G) -> Entry<'_, G, Self>
+    where
+        Self: Sized,
+    {
+        if let Some(value) = self.lookup_position(&position) {
+            return Entry::Occupied(value);
+        }
+        if self.claim_position(&position) {
+            return Entry::Vacant(VacantEntry {
+                table: self,
+                position,
+            });
+        }
+        // Claim could fail because the value was inserted after the lookup
+        self.lookup_position(&position)
+            .map_or(Entry::InProgress, Entry::Occupied)
+    }
+
+    /// Get the value of the position, computing it with `f` if it is not known. If another thread
+    /// is already evaluating the position, wait for its result instead of computing it again.
+    ///
+    /// Waiting is safe when `f` evaluates positions on the calling thread only, like
+    /// [`PartizanGame::canonical_form_iterative`](crate::short::partizan::partizan_game::PartizanGame::canonical_form_iterative).
+    /// If `f` spawns work on a thread pool with work stealing, two threads could end up waiting
+    /// for each other, so [`Self::entry`] should be used instead.
+    fn get_or_insert_with<F>(&self, position: &G, f: F) -> CanonicalForm
+    where
+        Self: Sized,
+        G: Clone,
+        F: FnOnce() -> CanonicalForm,
+    {
+        loop {
+            match self.entry(position.clone()) {
+                Entry::Occupied(value) => return value,
+                Entry::Vacant(entry) => return entry.insert(f()),
+                Entry::InProgress => self.wait_for_position(position),
+            }
+        }
+    }
+
+    /// Get usage statistics of the table. Tables report only what they track, so lookups are
+    /// counted only when wrapped in [`CountingTranspositionTable`].
+    #[inline]
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+macro_rules! impl_transposition_table_for_pointer {
+    ($($pointer:ty),*) => {
+        $(
+            impl<G, TT> TranspositionTable<G> for $pointer
+            where
+                TT: TranspositionTable<G> + ?Sized,
+            {
+                #[inline]
+                fn lookup_position(&self, position: &G) -> Option<CanonicalForm> {
+                    (**self).lookup_position(position)
+                }
+
+                #[inline]
+                fn insert_position(&self, position: G, value: CanonicalForm) {
+                    (**self).insert_position(position, value);
+                }
+
+                #[inline]
+                fn claim_position(&self, position: &G) -> bool {
+                    (**self).claim_position(position)
+                }
+
+                #[inline]
+                fn release_position(&self, position: &G) {
+                    (**self).release_position(position);
+                }
+
+                #[inline]
+                fn wait_for_position(&self, position: &G) {
+                    (**self).wait_for_position(position);
+                }
+
+                #[inline]
+                fn statistics(&self) -> Statistics {
+                    (**self).statistics()
+                }
+            }
+        )*
+    };
+}
+
+impl_transposition_table_for_pointer!(&TT, Box<TT>, Arc<TT>);
+
+/// Usage statistics of a transposition table, see [`TranspositionTable::statistics`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Statistics {
+    /// Number of lookups that found the position
+    pub hits: u64,
+
+    /// Number of lookups that did not find the position
+    pub misses: u64,
+
+    /// Number of insertions
+    pub insertions: u64,
+
+    /// Number of currently stored positions
+    pub positions: usize,
+
+    /// Number of stored values that are Number Up Star sums
+    pub nus_values: usize,
+
+    /// Number of stored values that are lists of moves
+    pub moves_values: usize,
+
+    /// Approximate memory used by the table and stored values in bytes. Heap memory owned by
+    /// positions is not included.
+    pub memory: usize,
+}
+
+impl Statistics {
+    /// Total number of lookups
+    #[inline]
+    pub const fn lookups(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    /// Fraction of lookups that found the position, `None` if there were no lookups
+    #[inline]
+    pub fn hit_rate(&self) -> Option<f64> {
+        (self.lookups() != 0).then(|| self.hits as f64 / self.lookups() as f64)
+    }
+    /// Total number of stored values
+    #[inline]
+    pub const fn values(&self) -> usize {
+        self.nus_values + self.moves_values
+    }
+
+    /// Count values stored by a table, returning their heap memory in bytes
+    fn count_values<'a>(&mut self, values: impl IntoIterator<Item = &'a CanonicalForm>) -> usize {
+        let mut heap = 0;
+        for value in values {
+            if value.is_number_up_star() {
+                self.nus_values += 1;
+            } else {
+                self.moves_values += 1;
+                heap += value.heap_size();
+            }
+        }
+        heap
+    }
+}
+
+impl Display for Statistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Hits: {}, Misses: {}", self.hits, self.misses)?;
+        if let Some(hit_rate) = self.hit_rate() {
+            write!(f, " ({:.2}% hit rate)", hit_rate * 100.0)?;
+        }
+        write!(
+            f,
+            ", Insertions: {}, Positions: {}, Values: {} ({} NUS, {} moves), Memory: {:.2} MiB",
+            self.insertions,
+            self.positions,
+            self.values(),
+            self.nus_values,
+            self.moves_values,
+            self.memory as f64 / (1024.0 * 1024.0)
+        )
+    }
 }
 
 /// Transaction table (cache) of game positions and canonical forms.
+///
+/// Both maps are split into independently locked shards, so threads inserting different
+/// positions rarely wait for each other. By default there are four shards per available core.
+///
+/// Positions are claimed with [`TranspositionTable::entry`] while being evaluated, so threads
+/// reaching the same position at once do not evaluate it twice. Claims are kept in a set sharded
+/// like the maps.
 pub struct ParallelTranspositionTable<G> {
     values: AppendOnlyVec<CanonicalForm>,
     positions: DashMap<G, usize, ahash::RandomState>,
     known_values: DashMap<CanonicalForm, usize, ahash::RandomState>,
+    in_progress: DashMap<G, (), ahash::RandomState>,
+    released_lock: Mutex<()>,
+    released: Condvar,
+}
+
+/// Entry of a position in a transposition table, obtained with [`TranspositionTable::entry`]
+pub enum Entry<'table, G, TT = ParallelTranspositionTable<G>>
+where
+    TT: TranspositionTable<G>,
+{
+    /// Position is already evaluated
+    Occupied(CanonicalForm),
+
+    /// Position is being evaluated by someone else. Value can be computed anyway, or the position
+    /// can be revisited later, after evaluating other positions.
+    InProgress,
+
+    /// Position is not evaluated, and is now claimed by the caller
+    Vacant(VacantEntry<'table, G, TT>),
+}
+
+/// Position claimed for evaluation. Claim is released when the value is inserted or the entry is
+/// dropped.
+pub struct VacantEntry<'table, G, TT = ParallelTranspositionTable<G>>
+where
+    TT: TranspositionTable<G>,
+{
+    table: &'table TT,
+    position: G,
+}
+
+impl<G, TT> VacantEntry<'_, G, TT>
+where
+    G: Clone,
+    TT: TranspositionTable<G>,
+{
+    /// Get the claimed position
+    #[inline]
+    pub const fn position(&self) -> &G {
+        &self.position
+    }
+
+    /// Save value of the position and release the claim
+    pub fn insert(self, value: CanonicalForm) -> CanonicalForm {
+        self.table
+            .insert_position(self.position.clone(), value.clone());
+        value
+    }
+}
+
+impl<G, TT> Drop for VacantEntry<'_, G, TT>
+where
+    TT: TranspositionTable<G>,
+{
+    fn drop(&mut self) {
+        self.table.release_position(&self.position);
+    }
 }
 
 impl<G> ParallelTranspositionTable<G>
@@ -31,6 +300,23 @@ where
         Self::default()
     }
 
+    /// Create new empty transposition table with given number of shards per map. More shards
+    /// reduce contention when many threads insert at once.
+    ///
+    /// # Panics
+    /// - `shards` is not a power of two greater than 1
+    #[inline]
+    pub fn with_shard_amount(shards: usize) -> Self {
+        Self {
+            values: AppendOnlyVec::new(),
+            positions: DashMap::with_hasher_and_shard_amount(ahash::RandomState::new(), shards),
+            known_values: DashMap::with_hasher_and_shard_amount(ahash::RandomState::new(), shards),
+            in_progress: DashMap::with_hasher_and_shard_amount(ahash::RandomState::new(), shards),
+            released_lock: Mutex::new(()),
+            released: Condvar::new(),
+        }
+    }
+
     /// Get number of saved positions
     #[inline]
     pub fn len(&self) -> usize {
@@ -42,6 +328,79 @@ where
     pub fn is_empty(&self) -> bool {
         self.positions.is_empty()
     }
+
+    /// Remove positions for which `keep` returns `false`, together with values that are no
+    /// longer used by any position. Returns the number of removed positions.
+    ///
+    /// Table only grows during a search, so long running computations can call it between
+    /// searches to reclaim memory, e.g. keeping only positions of the current search frontier
+    /// or positions whose values are still needed.
+    pub fn retain<F>(&mut self, mut keep: F) -> usize
+    where
+        F: FnMut(&G, &CanonicalForm) -> bool,
+    {
+        let before = self.positions.len();
+        let old_values = std::mem::replace(&mut self.values, AppendOnlyVec::new());
+        self.known_values.clear();
+
+        let mut new_ids = HashMap::new();
+        self.positions.retain(|position, id| {
+            let value = &old_values[*id];
+            if !keep(position, value) {
+                return false;
+            }
+            *id = *new_ids.entry(*id).or_insert_with(|| {
+                let new_id = self.values.push(value.clone());
+                self.known_values.insert(value.clone(), new_id);
+                new_id
+            });
+            true
+        });
+        self.positions.shrink_to_fit();
+        self.known_values.shrink_to_fit();
+
+        before - self.positions.len()
+    }
+
+    /// Get contents of the table with positions sorted, see [`Snapshot`]
+    pub fn snapshot(&self) -> Snapshot<G>
+    where
+        G: Ord + Clone,
+    {
+        self.snapshot_by_key(G::clone)
+    }
+
+    /// Get contents of the table with positions sorted by `key`, for positions that are not
+    /// [`Ord`], e.g. by their text format. Positions must have distinct keys for the snapshot to
+    /// be deterministic.
+    pub fn snapshot_by_key<K, F>(&self, mut key: F) -> Snapshot<G>
+    where
+        G: Clone,
+        K: Ord,
+        F: FnMut(&G) -> K,
+    {
+        let mut positions = self
+            .positions
+            .iter()
+            .map(|entry| (key(entry.key()), entry.key().clone(), *entry.value()))
+            .collect::<Vec<_>>();
+        positions.sort_unstable_by(|lhs, rhs| lhs.0.cmp(&rhs.0));
+
+        let mut values = Vec::new();
+        let mut new_ids = HashMap::new();
+        let positions = positions
+            .into_iter()
+            .map(|(_, position, id)| {
+                let new_id = *new_ids.entry(id).or_insert_with(|| {
+                    values.push(self.values[id].clone());
+                    values.len() - 1
+                });
+                (position, new_id)
+            })
+            .collect();
+
+        Snapshot { values, positions }
+    }
 }
 
 impl<G> Default for ParallelTranspositionTable<G>
@@ -54,13 +413,58 @@ where
             values: AppendOnlyVec::new(),
             positions: DashMap::default(),
             known_values: DashMap::default(),
+            in_progress: DashMap::default(),
+            released_lock: Mutex::new(()),
+            released: Condvar::new(),
+        }
+    }
+}
+
+impl<G> From<Snapshot<G>> for ParallelTranspositionTable<G>
+where
+    G: Eq + Hash + Clone,
+{
+    fn from(snapshot: Snapshot<G>) -> Self {
+        let table = Self::new();
+        for (position, id) in snapshot.positions {
+            table.insert_position(position, snapshot.values[id].clone());
+        }
+        table
+    }
+}
+
+/// Contents of a [`ParallelTranspositionTable`] that do not depend on the order of insertions
+///
+/// Table identifies values by the order in which they were first inserted, so the same positions
+/// evaluated by different runs or thread interleavings are stored differently. In a snapshot
+/// positions are sorted and values are numbered in order of their first position, so snapshots
+/// of tables with the same contents are equal, and can be serialized and compared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot<G> {
+    /// Distinct values of positions
+    pub values: Vec<CanonicalForm>,
+
+    /// Sorted positions with indices of their values in [`Self::values`]
+    pub positions: Vec<(G, usize)>,
+}
+
+impl<G> Display for Snapshot<G>
+where
+    G: Display,
+{
+    /// One position per line, followed by its value
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (position, id) in &self.positions {
+            writeln!(f, "{position} {}", self.values[*id])?;
         }
+        Ok(())
     }
 }
 
 impl<G> TranspositionTable<G> for ParallelTranspositionTable<G>
 where
-    G: Eq + Hash,
+    G: Eq + Hash + Clone,
 {
     #[allow(clippy::missing_panics_doc)]
     #[inline]
@@ -81,6 +485,455 @@ where
             self.positions.insert(position, inserted);
         }
     }
+
+    #[inline]
+    fn claim_position(&self, position: &G) -> bool {
+        if self.in_progress.insert(position.clone(), ()).is_some() {
+            return false;
+        }
+        // Value might have been inserted, and the claim released, after the caller's lookup
+        if self.positions.contains_key(position) {
+            self.release_position(position);
+            return false;
+        }
+        true
+    }
+
+    #[allow(clippy::missing_panics_doc)]
+    fn release_position(&self, position: &G) {
+        self.in_progress.remove(position);
+        // Waiters check the claim while holding the lock, so taking it here makes sure that no
+        // one is between the check and the wait
+        drop(self.released_lock.lock().unwrap());
+        self.released.notify_all();
+    }
+
+    #[allow(clippy::missing_panics_doc)]
+    fn wait_for_position(&self, position: &G) {
+        let released_lock = self.released_lock.lock().unwrap();
+        drop(
+            self.released
+                .wait_while(released_lock, |()| self.in_progress.contains_key(position))
+                .unwrap(),
+        );
+    }
+
+    fn statistics(&self) -> Statistics {
+        let mut statistics = Statistics {
+            positions: self.positions.len(),
+            ..Statistics::default()
+        };
+        // Values are stored twice, in the list and in the reverse index
+        let heap = 2 * statistics.count_values(self.values.iter());
+        statistics.memory = statistics.positions * (size_of::<G>() + size_of::<usize>())
+            + statistics.values() * (2 * size_of::<CanonicalForm>() + size_of::<usize>())
+            + heap;
+        statistics
+    }
+}
+
+/// Two entries of [`BoundedTranspositionTable`], most recently used first
+type Bucket<G> = [Option<(G, CanonicalForm)>; 2];
+
+/// Transposition table of fixed size, replacing old positions with new ones when full
+///
+/// Positions are hashed into buckets of two entries, like in chess engines. A new position takes
+/// the first entry of its bucket, moving the previous one to the second entry and evicting the
+/// one that was there, and a position found in the second entry is moved back to the first one.
+/// This keeps recently used positions in the table, while memory use stays constant.
+pub struct BoundedTranspositionTable<G> {
+    buckets: Box<[Mutex<Bucket<G>>]>,
+    hasher: ahash::RandomState,
+}
+
+impl<G> BoundedTranspositionTable<G>
+where
+    G: Eq + Hash,
+{
+    /// Create new empty transposition table, storing at least `capacity` positions
+    pub fn new(capacity: usize) -> Self {
+        let buckets = capacity.div_ceil(2).max(1).next_power_of_two();
+        Self {
+            buckets: (0..buckets).map(|_| Mutex::new([None, None])).collect(),
+            hasher: ahash::RandomState::new(),
+        }
+    }
+
+    /// Maximum number of stored positions
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        2 * self.buckets.len()
+    }
+
+    /// Get number of saved positions
+    pub fn len(&self) -> usize {
+        self.buckets
+            .iter()
+            .map(|bucket| bucket.lock().unwrap().iter().flatten().count())
+            .sum()
+    }
+
+    /// Check if table stores any position
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    fn bucket(&self, position: &G) -> &Mutex<Bucket<G>> {
+        let hash = self.hasher.hash_one(position) as usize;
+        &self.buckets[hash & (self.buckets.len() - 1)]
+    }
+}
+
+impl<G> TranspositionTable<G> for BoundedTranspositionTable<G>
+where
+    G: Eq + Hash,
+{
+    #[allow(clippy::missing_panics_doc)]
+    fn lookup_position(&self, position: &G) -> Option<CanonicalForm> {
+        let mut bucket = self.bucket(position).lock().unwrap();
+        let index = bucket
+            .iter()
+            .position(|entry| matches!(entry, Some((stored, _)) if stored == position))?;
+        bucket.swap(0, index);
+        let value = bucket[0].as_ref().map(|(_, value)| value.clone());
+        drop(bucket);
+        value
+    }
+
+    #[allow(clippy::missing_panics_doc)]
+    fn insert_position(&self, position: G, value: CanonicalForm) {
+        let mut bucket = self.bucket(&position).lock().unwrap();
+        if matches!(&bucket[0], Some((first, _)) if *first == position) {
+            bucket[0] = Some((position, value));
+            return;
+        }
+        bucket[1] = bucket[0].take();
+        bucket[0] = Some((position, value));
+    }
+
+    #[allow(clippy::missing_panics_doc)]
+    fn statistics(&self) -> Statistics {
+        let mut statistics = Statistics {
+            positions: self.len(),
+            ..Statistics::default()
+        };
+        let heap = self
+            .buckets
+            .iter()
+            .map(|bucket| {
+                let bucket = bucket.lock().unwrap();
+                statistics.count_values(bucket.iter().flatten().map(|(_, value)| value))
+            })
+            .sum::<usize>();
+        statistics.memory = self.buckets.len() * size_of::<Mutex<Bucket<G>>>() + heap;
+        statistics
+    }
+}
+
+/// Positions that have symmetries preserving their game value
+pub trait Symmetric {
+    /// Get a representative of all positions symmetric to this one. Symmetric positions must
+    /// have the same representative.
+    #[must_use]
+    fn canonical_symmetry(&self) -> Self;
+}
+
+/// Transposition table that stores positions under their canonical symmetry (see
+/// [`Symmetric`]), so positions symmetric to already evaluated ones are not evaluated again.
+pub struct SymmetricTranspositionTable<G, TT = ParallelTranspositionTable<G>> {
+    inner: TT,
+    _game: PhantomData<G>,
+}
+
+impl<G, TT> SymmetricTranspositionTable<G, TT> {
+    /// Wrap a transposition table
+    #[inline]
+    pub const fn new(inner: TT) -> Self {
+        Self {
+            inner,
+            _game: PhantomData,
+        }
+    }
+
+    /// Get the wrapped transposition table
+    #[inline]
+    pub const fn inner(&self) -> &TT {
+        &self.inner
+    }
+}
+
+impl<G, TT> Default for SymmetricTranspositionTable<G, TT>
+where
+    TT: Default,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(TT::default())
+    }
+}
+
+impl<G, TT> TranspositionTable<G> for SymmetricTranspositionTable<G, TT>
+where
+    G: Symmetric,
+    TT: TranspositionTable<G>,
+{
+    #[inline]
+    fn lookup_position(&self, position: &G) -> Option<CanonicalForm> {
+        self.inner.lookup_position(&position.canonical_symmetry())
+    }
+
+    #[inline]
+    fn insert_position(&self, position: G, value: CanonicalForm) {
+        self.inner
+            .insert_position(position.canonical_symmetry(), value);
+    }
+
+    #[inline]
+    fn claim_position(&self, position: &G) -> bool {
+        self.inner.claim_position(&position.canonical_symmetry())
+    }
+
+    #[inline]
+    fn release_position(&self, position: &G) {
+        self.inner.release_position(&position.canonical_symmetry());
+    }
+
+    #[inline]
+    fn wait_for_position(&self, position: &G) {
+        self.inner.wait_for_position(&position.canonical_symmetry());
+    }
+
+    #[inline]
+    fn statistics(&self) -> Statistics {
+        self.inner.statistics()
+    }
+}
+
+/// Transposition table that stops growing after storing a given number of positions, or after
+/// using a given amount of memory
+///
+/// Once the limit is reached new positions are not saved, so computations still finish with the
+/// correct result, only slower. To abort the computation instead, attach a [`Cancellation`] with
+/// [`Self::cancel_when_full`] and use a cancellable solver, like
+/// [`PartizanGame::canonical_form_cancellable`](crate::short::partizan::partizan_game::PartizanGame::canonical_form_cancellable).
+pub struct CappedTranspositionTable<G, TT = ParallelTranspositionTable<G>> {
+    inner: TT,
+    capacity: usize,
+    used: AtomicUsize,
+    count_bytes: bool,
+    cancellation: Option<Cancellation>,
+    _game: PhantomData<G>,
+}
+
+impl<G, TT> CappedTranspositionTable<G, TT> {
+    /// Wrap a transposition table, storing at most `capacity` positions
+    #[inline]
+    pub const fn new(inner: TT, capacity: usize) -> Self {
+        Self {
+            inner,
+            capacity,
+            used: AtomicUsize::new(0),
+            count_bytes: false,
+            cancellation: None,
+            _game: PhantomData,
+        }
+    }
+
+    /// Wrap a transposition table, storing positions until they use roughly `bytes` of memory.
+    ///
+    /// Every new position is charged for itself, bookkeeping of the table, and its value with
+    /// moves on the heap. Values shared by many positions are charged every time, so the
+    /// estimate errs on the side of using less memory. Heap memory owned by positions (e.g.
+    /// graphs) is not counted, so for such games [`Self::new`] with an explicit capacity should
+    /// be used.
+    #[inline]
+    pub const fn with_memory_budget(inner: TT, bytes: usize) -> Self {
+        let mut table = Self::new(inner, bytes);
+        table.count_bytes = true;
+        table
+    }
+
+    /// Trigger `cancellation` when the table becomes full
+    #[must_use]
+    pub fn cancel_when_full(mut self, cancellation: Cancellation) -> Self {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    /// Get the wrapped transposition table
+    #[inline]
+    pub const fn inner(&self) -> &TT {
+        &self.inner
+    }
+
+    /// Maximum number of stored positions, or bytes if created with
+    /// [`Self::with_memory_budget`]
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Check if table cannot store more positions
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.used.load(Ordering::Relaxed) >= self.capacity
+    }
+
+    /// Part of capacity used by a new position with given value
+    fn cost(&self, value: &CanonicalForm) -> usize {
+        if self.count_bytes {
+            // Position, index of its value, hash map overhead, and the value itself
+            std::mem::size_of::<G>()
+                + 2 * std::mem::size_of::<usize>()
+                + std::mem::size_of::<CanonicalForm>()
+                + value.heap_size()
+        } else {
+            1
+        }
+    }
+}
+
+impl<G, TT> TranspositionTable<G> for CappedTranspositionTable<G, TT>
+where
+    TT: TranspositionTable<G>,
+{
+    #[inline]
+    fn lookup_position(&self, position: &G) -> Option<CanonicalForm> {
+        self.inner.lookup_position(position)
+    }
+
+    #[inline]
+    fn insert_position(&self, position: G, value: CanonicalForm) {
+        // Replacing value of a stored position does not make the table grow
+        if self.inner.lookup_position(&position).is_some() {
+            self.inner.insert_position(position, value);
+            return;
+        }
+
+        let cost = self.cost(&value);
+        let reserved = self
+            .used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                used.checked_add(cost).filter(|&used| used <= self.capacity)
+            });
+        if reserved.is_ok() {
+            self.inner.insert_position(position, value);
+        } else if let Some(cancellation) = &self.cancellation {
+            cancellation.cancel();
+        }
+    }
+
+    #[inline]
+    fn claim_position(&self, position: &G) -> bool {
+        self.inner.claim_position(position)
+    }
+
+    #[inline]
+    fn release_position(&self, position: &G) {
+        self.inner.release_position(position);
+    }
+
+    #[inline]
+    fn wait_for_position(&self, position: &G) {
+        self.inner.wait_for_position(position);
+    }
+
+    #[inline]
+    fn statistics(&self) -> Statistics {
+        self.inner.statistics()
+    }
+}
+
+/// Transposition table counting lookups and insertions, reported by
+/// [`TranspositionTable::statistics`] together with statistics of the wrapped table.
+///
+/// Counters are shared between all threads, so they add some contention and should be used
+/// only when statistics are needed.
+pub struct CountingTranspositionTable<G, TT = ParallelTranspositionTable<G>> {
+    inner: TT,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    insertions: AtomicU64,
+    _game: PhantomData<G>,
+}
+
+impl<G, TT> CountingTranspositionTable<G, TT> {
+    /// Wrap a transposition table
+    #[inline]
+    pub const fn new(inner: TT) -> Self {
+        Self {
+            inner,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            insertions: AtomicU64::new(0),
+            _game: PhantomData,
+        }
+    }
+
+    /// Get the wrapped transposition table
+    #[inline]
+    pub const fn inner(&self) -> &TT {
+        &self.inner
+    }
+}
+
+impl<G, TT> Default for CountingTranspositionTable<G, TT>
+where
+    TT: Default,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(TT::default())
+    }
+}
+
+impl<G, TT> TranspositionTable<G> for CountingTranspositionTable<G, TT>
+where
+    TT: TranspositionTable<G>,
+{
+    #[inline]
+    fn lookup_position(&self, position: &G) -> Option<CanonicalForm> {
+        let value = self.inner.lookup_position(position);
+        let counter = if value.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+
+    #[inline]
+    fn insert_position(&self, position: G, value: CanonicalForm) {
+        self.insertions.fetch_add(1, Ordering::Relaxed);
+        self.inner.insert_position(position, value);
+    }
+
+    #[inline]
+    fn claim_position(&self, position: &G) -> bool {
+        self.inner.claim_position(position)
+    }
+
+    #[inline]
+    fn release_position(&self, position: &G) {
+        self.inner.release_position(position);
+    }
+
+    #[inline]
+    fn wait_for_position(&self, position: &G) {
+        self.inner.wait_for_position(position);
+    }
+
+    #[inline]
+    fn statistics(&self) -> Statistics {
+        Statistics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            insertions: self.insertions.load(Ordering::Relaxed),
+            ..self.inner.statistics()
+        }
+    }
 }
 
 /// Dummy transposition table that does not store anythning
@@ -110,3 +963,345 @@ impl<G> TranspositionTable<G> for NoTranspositionTable<G> {
     #[inline]
     fn insert_position(&self, _position: G, _value: CanonicalForm) {}
 }
+
+/// Kind of transposition table picked at runtime, e.g. from command line options
+///
+/// Parsed from and displayed as `none`, `unbounded` or `bounded:CAPACITY`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableKind {
+    /// [`NoTranspositionTable`], every position is evaluated from scratch
+    None,
+
+    /// [`ParallelTranspositionTable`]
+    Unbounded,
+
+    /// [`BoundedTranspositionTable`] storing at least given number of positions
+    Bounded(usize),
+}
+
+impl TableKind {
+    /// Create empty table of this kind
+    pub fn build<G>(self) -> Box<dyn TranspositionTable<G> + Send + Sync>
+    where
+        G: Eq + Hash + Clone + Send + Sync + 'static,
+    {
+        match self {
+            Self::None => Box::new(NoTranspositionTable::new()),
+            Self::Unbounded => Box::new(ParallelTranspositionTable::new()),
+            Self::Bounded(capacity) => Box::new(BoundedTranspositionTable::new(capacity)),
+        }
+    }
+}
+
+impl Display for TableKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Unbounded => write!(f, "unbounded"),
+            Self::Bounded(capacity) => write!(f, "bounded:{capacity}"),
+        }
+    }
+}
+
+impl FromStr for TableKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "none" => Ok(Self::None),
+            "unbounded" => Ok(Self::Unbounded),
+            other => other
+                .strip_prefix("bounded:")
+                .and_then(|capacity| capacity.parse().ok())
+                .map(Self::Bounded)
+                .ok_or_else(|| {
+                    Error::Syntax(format!(
+                        "invalid table '{other}', expected 'none', 'unbounded' or \
+                         'bounded:CAPACITY'"
+                    ))
+                }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        grid::small_bit_grid::SmallBitGrid,
+        short::partizan::{
+            cancellation::Cancelled, games::domineering::Domineering, partizan_game::PartizanGame,
+        },
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn sharded_table() {
+        let position = Domineering::new(SmallBitGrid::from_str("...|...|...").unwrap());
+        let transposition_table = ParallelTranspositionTable::with_shard_amount(64);
+        assert_eq!(
+            position.canonical_form(&transposition_table),
+            position.canonical_form(&ParallelTranspositionTable::new())
+        );
+        assert!(!transposition_table.is_empty());
+    }
+
+    #[test]
+    fn concurrent_evaluation_is_not_repeated() {
+        let position = Domineering::new(SmallBitGrid::from_str("...|...|...").unwrap());
+        let transposition_table = ParallelTranspositionTable::new();
+        let evaluations = AtomicUsize::new(0);
+
+        let values = std::thread::scope(|scope| {
+            let threads = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        transposition_table.get_or_insert_with(&position, || {
+                            evaluations.fetch_add(1, Ordering::Relaxed);
+                            std::thread::sleep(std::time::Duration::from_millis(50));
+                            position.canonical_form(&ParallelTranspositionTable::new())
+                        })
+                    })
+                })
+                .collect::<Vec<_>>();
+            threads
+                .into_iter()
+                .map(|thread| thread.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(evaluations.load(Ordering::Relaxed), 1);
+        assert!(values.windows(2).all(|pair| pair[0] == pair[1]));
+
+        let other = Domineering::new(SmallBitGrid::from_str("..|..").unwrap());
+        let Entry::Vacant(entry) = transposition_table.entry(other) else {
+            panic!("position should be vacant");
+        };
+        assert!(matches!(
+            transposition_table.entry(other),
+            Entry::InProgress
+        ));
+        drop(entry);
+        assert!(matches!(transposition_table.entry(other), Entry::Vacant(_)));
+    }
+
+    #[test]
+    fn retain_compacts_values() {
+        let position = Domineering::new(SmallBitGrid::from_str("...|...|...").unwrap());
+        let expected = position.canonical_form(&ParallelTranspositionTable::new());
+
+        let mut transposition_table = ParallelTranspositionTable::new();
+        position.canonical_form(&transposition_table);
+        let before = transposition_table.statistics();
+
+        let zero = CanonicalForm::new_integer(0);
+        let mut kept = Vec::new();
+        let removed = transposition_table.retain(|position, value| {
+            if *value == zero {
+                kept.push(*position);
+            }
+            *value == zero
+        });
+        assert!(removed > 0);
+        assert_eq!(removed + transposition_table.len(), before.positions);
+        assert_eq!(transposition_table.values.len(), 1);
+        assert!(transposition_table.statistics().memory < before.memory);
+
+        assert!(!kept.is_empty());
+        for position in &kept {
+            assert_eq!(
+                transposition_table.lookup_position(position),
+                Some(zero.clone())
+            );
+        }
+        assert_eq!(position.canonical_form(&transposition_table), expected);
+
+        let stored = transposition_table.len();
+        assert_eq!(transposition_table.retain(|_, _| false), stored);
+        assert!(transposition_table.is_empty());
+        assert_eq!(position.canonical_form(&transposition_table), expected);
+    }
+
+    #[test]
+    fn snapshot_is_independent_of_insertion_order() {
+        let positions = ["...|...|...", "..#|...", "....|.#.."]
+            .map(|grid| Domineering::new(SmallBitGrid::from_str(grid).unwrap()));
+
+        let forward = ParallelTranspositionTable::new();
+        for position in &positions {
+            position.canonical_form(&forward);
+        }
+        let backward = ParallelTranspositionTable::new();
+        for position in positions.iter().rev() {
+            position.canonical_form(&backward);
+        }
+
+        let snapshot = forward.snapshot();
+        assert_eq!(snapshot, backward.snapshot());
+        assert_eq!(snapshot.to_string(), backward.snapshot().to_string());
+        assert!(snapshot
+            .positions
+            .windows(2)
+            .all(|pair| pair[0].0 < pair[1].0));
+
+        let restored = ParallelTranspositionTable::from(snapshot.clone());
+        assert_eq!(restored.snapshot(), snapshot);
+        assert_eq!(
+            restored.snapshot_by_key(ToString::to_string),
+            forward.snapshot_by_key(ToString::to_string)
+        );
+    }
+
+    #[test]
+    fn bounded_table_evicts() {
+        let transposition_table = BoundedTranspositionTable::new(2);
+        assert_eq!(transposition_table.capacity(), 2);
+
+        for value in 0..10 {
+            transposition_table.insert_position(value, CanonicalForm::new_integer(value));
+        }
+        assert_eq!(transposition_table.len(), 2);
+        assert_eq!(
+            transposition_table.lookup_position(&9),
+            Some(CanonicalForm::new_integer(9))
+        );
+        assert_eq!(
+            transposition_table.lookup_position(&8),
+            Some(CanonicalForm::new_integer(8))
+        );
+        assert_eq!(transposition_table.lookup_position(&7), None);
+
+        // 8 was used most recently, so 9 is evicted
+        transposition_table.insert_position(10, CanonicalForm::new_integer(10));
+        assert_eq!(transposition_table.lookup_position(&9), None);
+        assert!(transposition_table.lookup_position(&8).is_some());
+
+        let position = Domineering::new(SmallBitGrid::from_str("...|...|...").unwrap());
+        assert_eq!(
+            position.canonical_form(&BoundedTranspositionTable::new(4)),
+            position.canonical_form(&ParallelTranspositionTable::new())
+        );
+    }
+
+    #[test]
+    fn capped_table_stops_growing() {
+        let position = Domineering::new(SmallBitGrid::from_str("...|...|...").unwrap());
+        let expected = position.canonical_form(&ParallelTranspositionTable::new());
+
+        let transposition_table =
+            CappedTranspositionTable::new(ParallelTranspositionTable::new(), 3);
+        assert_eq!(position.canonical_form(&transposition_table), expected);
+        assert!(transposition_table.is_full());
+        assert_eq!(transposition_table.inner().len(), 3);
+
+        let cancellation = Cancellation::new();
+        let transposition_table =
+            CappedTranspositionTable::new(ParallelTranspositionTable::new(), 3)
+                .cancel_when_full(cancellation.clone());
+        assert_eq!(
+            position.canonical_form_cancellable(&transposition_table, &cancellation),
+            Err(Cancelled)
+        );
+    }
+
+    #[test]
+    fn capped_table_counts_new_positions() {
+        let transposition_table =
+            CappedTranspositionTable::new(ParallelTranspositionTable::new(), 2);
+        for _ in 0..3 {
+            transposition_table.insert_position(0, CanonicalForm::new_integer(0));
+        }
+        assert!(!transposition_table.is_full());
+        transposition_table.insert_position(1, CanonicalForm::new_integer(1));
+        transposition_table.insert_position(2, CanonicalForm::new_integer(2));
+        assert!(transposition_table.is_full());
+        assert_eq!(transposition_table.inner().len(), 2);
+    }
+
+    #[test]
+    fn capped_table_memory_budget() {
+        let number = CanonicalForm::new_integer(1);
+        let switch = CanonicalForm::from_str("{2|-2}").unwrap();
+        let entry_size = std::mem::size_of::<u32>()
+            + 2 * std::mem::size_of::<usize>()
+            + std::mem::size_of::<CanonicalForm>();
+        assert!(switch.heap_size() > 0);
+
+        // Values with moves are charged for their heap memory too
+        let transposition_table = CappedTranspositionTable::with_memory_budget(
+            ParallelTranspositionTable::new(),
+            entry_size + switch.heap_size(),
+        );
+        transposition_table.insert_position(0_u32, switch);
+        assert!(transposition_table.is_full());
+        transposition_table.insert_position(1, number.clone());
+        assert_eq!(transposition_table.inner().len(), 1);
+
+        // Positions that are already stored are not charged again
+        let transposition_table = CappedTranspositionTable::with_memory_budget(
+            ParallelTranspositionTable::new(),
+            2 * entry_size,
+        );
+        transposition_table.insert_position(0_u32, number.clone());
+        transposition_table.insert_position(0, number.clone());
+        transposition_table.insert_position(1, number);
+        assert_eq!(transposition_table.inner().len(), 2);
+        assert!(transposition_table.is_full());
+    }
+
+    #[test]
+    fn table_picked_at_runtime() {
+        let position = Domineering::new(SmallBitGrid::from_str("...|...|...").unwrap());
+        let expected = position.canonical_form(&ParallelTranspositionTable::new());
+
+        let tables: Vec<Box<dyn TranspositionTable<Domineering> + Sync>> = vec![
+            Box::new(ParallelTranspositionTable::new()),
+            Box::new(BoundedTranspositionTable::new(16)),
+            Box::new(NoTranspositionTable::new()),
+        ];
+        for transposition_table in &tables {
+            assert_eq!(position.canonical_form(transposition_table), expected);
+        }
+
+        let shared = Arc::new(CountingTranspositionTable::new(
+            ParallelTranspositionTable::new(),
+        ));
+        assert_eq!(position.canonical_form(&shared), expected);
+        assert_eq!(position.canonical_form(&&*shared), expected);
+        assert!(shared.statistics().hits > 0);
+    }
+
+    #[test]
+    fn counting_table_statistics() {
+        let position = Domineering::new(SmallBitGrid::from_str("...|...|...").unwrap());
+        let transposition_table =
+            CountingTranspositionTable::new(ParallelTranspositionTable::new());
+        position.canonical_form(&transposition_table);
+
+        let first = transposition_table.statistics();
+        assert_eq!(first.positions, transposition_table.inner().len());
+        assert!(first.positions as u64 <= first.insertions);
+        assert!(first.hits > 0);
+        assert!(first.memory > 0);
+        assert_eq!(first.values(), transposition_table.inner().values.len());
+        assert!(first.nus_values > 0 && first.moves_values > 0);
+
+        // Everything is already known
+        position.canonical_form(&transposition_table);
+        let second = transposition_table.statistics();
+        assert_eq!(second.hits, first.hits + 1);
+        assert_eq!(second.misses, first.misses);
+        assert_eq!(second.positions, first.positions);
+    }
+
+    #[test]
+    fn table_kinds() {
+        let position = Domineering::new(SmallBitGrid::from_str("...|...|...").unwrap());
+        let expected = position.canonical_form(&ParallelTranspositionTable::new());
+        for input in ["none", "unbounded", "bounded:64"] {
+            let kind = TableKind::from_str(input).unwrap();
+            assert_eq!(kind.to_string(), input);
+            assert_eq!(position.canonical_form(&kind.build()), expected);
+        }
+        assert!(TableKind::from_str("bounded").is_err());
+    }
+}
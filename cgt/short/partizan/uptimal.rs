@@ -0,0 +1,378 @@
+//! Uptimal notation for infinitesimals, i.e. sums of `↑^n` (up-nth).
+//!
+//! `↑^1 = ↑` and `↑^(n+1) = {0 | ↓^(→n)*}`, where `↑^(→n) = ↑ + ↑^2 + ... + ↑^n`. Uptimal `x.d1d2...dn` stands for
+//! `x + d1.↑ + d2.↑^2 + ... + dn.↑^n`, optionally followed by a nimber. In text form it is written
+//! as `x^.d1d2...dn*k`, where digits outside of `0..=9` are put in square brackets, e.g.
+//! `^.1[-1]*`. Single `↑^n` can also be written as `^<n>` or `↑ⁿ`, and `↓^n` as `v<n>` or `↓ⁿ`.
+
+use crate::{
+    nom_utils::{impl_from_str_via_nom, lexeme},
+    numeric::{dyadic_rational_number::DyadicRationalNumber, nimber::Nimber},
+    short::partizan::canonical_form::{CanonicalForm, Moves, Nus},
+};
+use alloc::{vec, vec::Vec};
+use nom::{
+    branch::alt,
+    character::complete::{char, i64, one_of, satisfy, u32},
+    combinator::opt,
+    multi::many1,
+    sequence::delimited,
+};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+};
+
+/// Number plus a sum of `↑^n` multiples plus a nimber
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct Uptimal {
+    number: DyadicRationalNumber,
+    digits: Vec<i64>,
+    nimber: Nimber,
+}
+
+impl Uptimal {
+    /// Create new uptimal. `digits[i]` is the multiple of `↑^(i+1)`.
+    pub fn new(number: DyadicRationalNumber, mut digits: Vec<i64>, nimber: Nimber) -> Self {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        Self {
+            number,
+            digits,
+            nimber,
+        }
+    }
+
+    /// Get number part
+    #[inline]
+    pub const fn number(&self) -> DyadicRationalNumber {
+        self.number
+    }
+
+    /// Get multiples of consecutive `↑^n`, starting from `↑^1`
+    #[inline]
+    pub fn digits(&self) -> &[i64] {
+        &self.digits
+    }
+
+    /// Get nimber part
+    #[inline]
+    pub const fn nimber(&self) -> Nimber {
+        self.nimber
+    }
+
+    /// Construct `↑^n`
+    ///
+    /// # Panics
+    /// - `n` is zero
+    pub fn up_nth(n: u32) -> CanonicalForm {
+        assert!(n > 0, "There is no zeroth up");
+        let mut up_nth =
+            CanonicalForm::new_nus(Nus::new(DyadicRationalNumber::from(0), 1, Nimber::new(0)));
+        let mut up_arrow = up_nth.clone();
+        for _ in 1..n {
+            up_nth = CanonicalForm::new_from_moves(Moves {
+                left: vec![CanonicalForm::new_integer(0)],
+                right: vec![-&up_arrow + CanonicalForm::new_nimber(0.into(), Nimber::new(1))],
+            });
+            up_arrow += &up_nth;
+        }
+        up_nth
+    }
+
+    /// Convert uptimal to canonical form
+    pub fn to_canonical_form(&self) -> CanonicalForm {
+        let mut result = CanonicalForm::new_nimber(self.number, self.nimber);
+        for (idx, digit) in self.digits.iter().enumerate() {
+            let up_nth = Self::up_nth(idx as u32 + 1);
+            let term = if *digit < 0 { -up_nth } else { up_nth };
+            for _ in 0..digit.abs() {
+                result += &term;
+            }
+        }
+        result
+    }
+
+    /// Recognize if the game is an uptimal
+    pub fn from_canonical_form(game: &CanonicalForm) -> Option<Self> {
+        if let Some(nus) = game.to_nus() {
+            return Some(Self::new(
+                nus.number(),
+                vec![i64::from(nus.up_multiple())],
+                nus.nimber(),
+            ));
+        }
+
+        let number = game.left_stop();
+        if number != game.right_stop() {
+            return None;
+        }
+
+        let remainder = game - CanonicalForm::new_dyadic(number);
+        if !remainder.is_all_small() {
+            return None;
+        }
+
+        // `↑^n` is born on day `n + 1`
+        let max_order = remainder.birthday().saturating_sub(1);
+        let ups: Vec<CanonicalForm> = (1..=max_order).map(Self::up_nth).collect();
+        let digits = find_digits(&remainder, &ups, i64::from(max_order) + 1)?;
+
+        // Nimber part is whatever is left after removing all the ups
+        let without_nimber = Self::new(number, digits.clone(), Nimber::new(0));
+        let nimber = (game - without_nimber.to_canonical_form())
+            .to_nus()
+            .filter(|nus| nus.is_nimber())?
+            .nimber();
+        let uptimal = Self::new(number, digits, nimber);
+        (&uptimal.to_canonical_form() == game).then_some(uptimal)
+    }
+
+    /// Parse uptimal using `x^.d1d2...*k`, `x^<n>*k` or `x↑ⁿ*k` notation
+    #[allow(clippy::missing_errors_doc)]
+    pub fn parse(input: &str) -> nom::IResult<&str, Self> {
+        let (input, number) = opt(lexeme(DyadicRationalNumber::parse))(input)?;
+        let (input, arrows) = lexeme(alt((many1(one_of("^↑")), many1(one_of("v↓")))))(input)?;
+        let sign = if matches!(arrows[0], '^' | '↑') {
+            1
+        } else {
+            -1
+        };
+
+        let (input, digits) = if arrows.len() > 1 {
+            // Repeated arrows, e.g. `^^^`
+            (input, vec![sign * arrows.len() as i64])
+        } else {
+            alt((
+                |input| {
+                    let (input, order) =
+                        delimited(lexeme(char('<')), lexeme(u32), lexeme(char('>')))(input)?;
+                    if order == 0 {
+                        return Err(nom::Err::Error(nom::error::Error::new(
+                            input,
+                            nom::error::ErrorKind::Verify,
+                        )));
+                    }
+                    let mut digits = vec![0; order as usize];
+                    digits[order as usize - 1] = sign;
+                    Ok((input, digits))
+                },
+                |input| {
+                    // `↑ⁿ` with superscript digits, e.g. `↑³`
+                    let (input, digits) =
+                        lexeme(many1(satisfy(|c| superscript_digit(c).is_some())))(input)?;
+                    let order = digits
+                        .into_iter()
+                        .filter_map(superscript_digit)
+                        .try_fold(0u32, |acc, d| acc.checked_mul(10)?.checked_add(d))
+                        .filter(|order| *order > 0)
+                        .ok_or_else(|| {
+                            nom::Err::Error(nom::error::Error::new(
+                                input,
+                                nom::error::ErrorKind::Verify,
+                            ))
+                        })?;
+                    let mut digits = vec![0; order as usize];
+                    digits[order as usize - 1] = sign;
+                    Ok((input, digits))
+                },
+                |input| {
+                    let (input, _) = char('.')(input)?;
+                    let (input, digits) = many1(alt((
+                        |input| {
+                            satisfy(|c| c.is_ascii_digit())(input)
+                                .map(|(input, c)| (input, i64::from(c as u8 - b'0')))
+                        },
+                        delimited(char('['), i64, char(']')),
+                    )))(input)?;
+                    Ok((input, digits.into_iter().map(|d| sign * d).collect()))
+                },
+                |input| {
+                    let (input, multiple) = opt(lexeme(u32))(input)?;
+                    Ok((input, vec![sign * i64::from(multiple.unwrap_or(1))]))
+                },
+            ))(input)?
+        };
+
+        let (input, nimber) = match lexeme(char::<_, nom::error::Error<&str>>('*'))(input) {
+            Ok((input, _)) => {
+                lexeme(u32::<_, nom::error::Error<&str>>)(input).unwrap_or((input, 1))
+            }
+            Err(_) => (input, 0),
+        };
+
+        Ok((
+            input,
+            Self::new(
+                number.unwrap_or_else(|| DyadicRationalNumber::from(0)),
+                digits,
+                Nimber::new(nimber),
+            ),
+        ))
+    }
+}
+
+impl Display for Uptimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.digits.len() <= 1 {
+            let up_multiple = self.digits.first().copied().unwrap_or(0) as i32;
+            return Nus::new(self.number, up_multiple, self.nimber).fmt(f);
+        }
+
+        if self.number != DyadicRationalNumber::from(0) {
+            write!(f, "{}", self.number)?;
+        }
+
+        let (last, rest) = self.digits.split_last().expect("at least two digits");
+        if rest.iter().all(|d| *d == 0) && last.abs() == 1 {
+            write!(
+                f,
+                "{}<{}>",
+                if *last > 0 { '^' } else { 'v' },
+                self.digits.len()
+            )?;
+        } else {
+            // Print negative uptimals as `v.d1d2...`, like negative ups
+            let sign = if self.digits.iter().all(|d| *d <= 0) {
+                write!(f, "v.")?;
+                -1
+            } else {
+                write!(f, "^.")?;
+                1
+            };
+            for digit in self.digits.iter().map(|d| sign * d) {
+                if (0..=9).contains(&digit) {
+                    write!(f, "{}", digit)?;
+                } else {
+                    write!(f, "[{}]", digit)?;
+                }
+            }
+        }
+
+        match self.nimber.value() {
+            0 => Ok(()),
+            1 => write!(f, "*"),
+            n => write!(f, "*{}", n),
+        }
+    }
+}
+
+impl_from_str_via_nom!(Uptimal);
+
+/// Value of a superscript digit, e.g. `³`
+fn superscript_digit(c: char) -> Option<u32> {
+    "⁰¹²³⁴⁵⁶⁷⁸⁹".chars().position(|d| d == c).map(|d| d as u32)
+}
+
+/// Sign of the first non-zero digit of an uptimal. Game is compared with zero both as-is and
+/// with added star, to get rid of confusion of `↑*` with zero.
+fn sign(game: &CanonicalForm) -> Ordering {
+    let zero = CanonicalForm::new_integer(0);
+    let starred = game + CanonicalForm::new_nimber(0.into(), Nimber::new(1));
+    let positive = |g: &CanonicalForm| g != &zero && CanonicalForm::leq(&zero, g);
+    let negative = |g: &CanonicalForm| g != &zero && CanonicalForm::leq(g, &zero);
+    if positive(game) || positive(&starred) {
+        Ordering::Greater
+    } else if negative(game) || negative(&starred) {
+        Ordering::Less
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// Find digits of an infinitesimal uptimal, starting from `↑^1`
+fn find_digits(game: &CanonicalForm, ups: &[CanonicalForm], bound: i64) -> Option<Vec<i64>> {
+    // Multiples of `↑^n` get big fast, and games that are not uptimals (like `↑^{2 3}`) would
+    // never stop growing, so give up once the remainder gets much bigger than the game itself
+    let max_birthday = 2 * game.birthday() + 2;
+    let checked = |game: CanonicalForm| (game.birthday() <= max_birthday).then_some(game);
+
+    let mut remainder = game.clone();
+    let mut digits = Vec::new();
+
+    for up_nth in ups {
+        if remainder.is_nimber() {
+            break;
+        }
+
+        // Largest multiple of `↑^n` that leaves positive remainder. Digit is either that
+        // multiple or the next one, depending on the sign of the rest.
+        let mut multiple = 0;
+        let mut candidate = remainder.clone();
+        if sign(&remainder) == Ordering::Greater {
+            while multiple < bound {
+                let next = checked(&candidate - up_nth)?;
+                if sign(&next) != Ordering::Greater {
+                    break;
+                }
+                candidate = next;
+                multiple += 1;
+            }
+        } else {
+            multiple = -1;
+            candidate = checked(&candidate + up_nth)?;
+            while multiple > -bound && sign(&candidate) != Ordering::Greater {
+                candidate = checked(&candidate + up_nth)?;
+                multiple -= 1;
+            }
+        }
+
+        // Remainder is now either `↑^n + t` for non-positive `t` of higher order, or positive
+        // `t` of higher order. Doubling it and subtracting `↑^n` tells them apart.
+        if sign(&checked(&candidate + &candidate - up_nth)?) == Ordering::Greater {
+            candidate = checked(&candidate - up_nth)?;
+            multiple += 1;
+        }
+
+        remainder = candidate;
+        digits.push(multiple);
+    }
+
+    remainder.is_nimber().then_some(digits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn up_nth_works() {
+        assert_eq!(Uptimal::up_nth(1), CanonicalForm::from_str("^").unwrap());
+        assert_eq!(
+            Uptimal::up_nth(2),
+            <CanonicalForm as std::str::FromStr>::from_str("{0|v*}").unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_roundtrip() {
+        for input in [
+            "^<2>", "v<3>*", "^.11", "v.12*", "^.1[-1]*", "1/2^.02", "^2*",
+        ] {
+            assert_eq!(Uptimal::from_str(input).unwrap().to_string(), input);
+        }
+        assert_eq!(Uptimal::from_str("^^*").unwrap().to_string(), "^2*");
+        assert_eq!(Uptimal::from_str("↑<3>").unwrap().to_string(), "^<3>");
+        assert_eq!(Uptimal::from_str("↑³").unwrap().to_string(), "^<3>");
+        assert_eq!(Uptimal::from_str("1↓¹²*").unwrap().to_string(), "1v<12>*");
+        assert!(Uptimal::from_str("↑⁰").is_err());
+        assert_eq!(
+            Uptimal::from_str("^.2*").unwrap(),
+            Uptimal::from_str("^2*").unwrap()
+        );
+    }
+
+    #[test]
+    fn recognize_uptimals() {
+        for input in ["^<2>", "v<3>*", "^.11", "^.1[-1]*", "^.21", "3/4v.12*"] {
+            let uptimal = Uptimal::from_str(input).unwrap();
+            let game = uptimal.to_canonical_form();
+            assert_eq!(Uptimal::from_canonical_form(&game), Some(uptimal));
+        }
+
+        let switch = CanonicalForm::from_str("{1|-1}").unwrap();
+        assert_eq!(Uptimal::from_canonical_form(&switch), None);
+    }
+}
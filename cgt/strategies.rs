@@ -0,0 +1,185 @@
+//! [`proptest`] strategies generating positions and values
+//!
+//! Available with the `proptest` feature. Strategies are small by default, so games generated
+//! by them can be solved quickly, and property tests can run many cases, e.g.
+//!
+//! ```
+//! use cgt::{short::partizan::canonical_form::CanonicalForm, strategies};
+//! use proptest::{prop_assert_eq, proptest};
+//!
+//! proptest!(|(g in strategies::canonical_form(3))| {
+//!     prop_assert_eq!(&g + &(-&g), CanonicalForm::new_integer(0));
+//! });
+//! ```
+
+#[cfg(feature = "std")]
+use crate::{
+    graph::Graph,
+    short::partizan::games::{
+        domineering::{self, Domineering},
+        snort::{Snort, VertexColor, VertexKind},
+    },
+};
+use crate::{
+    grid::FiniteGrid,
+    numeric::{dyadic_rational_number::DyadicRationalNumber, nimber::Nimber},
+    short::partizan::canonical_form::{CanonicalForm, Moves, Nus},
+};
+#[cfg(feature = "std")]
+use proptest::prelude::{any, prop_oneof, Just};
+use proptest::{
+    arbitrary::Arbitrary,
+    collection::vec,
+    prelude::{BoxedStrategy, Strategy},
+};
+use std::fmt::Debug;
+
+/// Nimbers `*n` with `n <= max_value`
+pub fn nimber(max_value: u32) -> impl Strategy<Value = Nimber> {
+    (0..=max_value).prop_map(Nimber::new)
+}
+
+/// Dyadic rationals `n/2^k` with `|n| <= max_numerator` and `k <= max_exponent`
+pub fn dyadic_rational_number(
+    max_numerator: i64,
+    max_exponent: u32,
+) -> impl Strategy<Value = DyadicRationalNumber> {
+    (-max_numerator..=max_numerator, 0..=max_exponent)
+        .prop_map(|(numerator, exponent)| DyadicRationalNumber::new(numerator, exponent))
+}
+
+/// Number-up-star sums with small components
+pub fn nus() -> impl Strategy<Value = Nus> {
+    (dyadic_rational_number(16, 3), -4..=4i32, nimber(4))
+        .prop_map(|(number, up_multiple, nimber)| Nus::new(number, up_multiple, nimber))
+}
+
+/// Canonical forms built from at most `depth` levels of moves on top of [`nus`] values
+pub fn canonical_form(depth: u32) -> impl Strategy<Value = CanonicalForm> {
+    nus()
+        .prop_map(CanonicalForm::new_nus)
+        .prop_recursive(depth, 32, 3, |inner| {
+            (vec(inner.clone(), 0..3), vec(inner, 0..3))
+                .prop_map(|(left, right)| CanonicalForm::new_from_moves(Moves { left, right }))
+        })
+}
+
+/// Graphs with at most `max_vertices` vertices, without loops
+#[cfg(feature = "std")]
+pub fn graph<G>(max_vertices: usize) -> impl Strategy<Value = G>
+where
+    G: Graph + Debug,
+{
+    (0..=max_vertices).prop_flat_map(|size| {
+        vec(any::<bool>(), size * size).prop_map(move |edges| {
+            let mut graph = G::empty(size);
+            for u in graph.vertices() {
+                for v in graph.vertices() {
+                    if (u.index < v.index || (G::DIRECTED && u != v))
+                        && edges[size * u.index + v.index]
+                    {
+                        graph.connect(u, v, true);
+                    }
+                }
+            }
+            graph
+        })
+    })
+}
+
+/// Grids of size at least 1x1 and at most `max_width`x`max_height` with tiles from `tile`
+pub fn grid<G, T>(max_width: u8, max_height: u8, tile: T) -> impl Strategy<Value = G>
+where
+    G: FiniteGrid + Debug,
+    T: Strategy<Value = G::Item> + Clone,
+{
+    (1..=max_width, 1..=max_height).prop_flat_map(move |(width, height)| {
+        vec(tile.clone(), width as usize * height as usize).prop_filter_map(
+            "grid too large",
+            move |tiles| {
+                let mut tiles = tiles.into_iter();
+                let mut grid = G::filled(width, height, tiles.next()?)?;
+                for y in 0..height {
+                    for x in 0..width {
+                        if (x, y) != (0, 0) {
+                            grid.set(x, y, tiles.next()?);
+                        }
+                    }
+                }
+                Some(grid)
+            },
+        )
+    })
+}
+
+/// Domineering positions on the plane, with at most `max_width`x`max_height` tiles of which
+/// roughly one in four is taken
+#[cfg(feature = "std")]
+pub fn domineering<G>(max_width: u8, max_height: u8) -> impl Strategy<Value = Domineering<G>>
+where
+    G: FiniteGrid<Item = domineering::Tile> + Debug,
+{
+    let tile = prop_oneof![
+        3 => Just(domineering::Tile::Empty),
+        1 => Just(domineering::Tile::Taken),
+    ];
+    grid(max_width, max_height, tile).prop_map(Domineering::new)
+}
+
+/// Snort positions on graphs from [`graph`], with vertices being empty or tinted
+#[cfg(feature = "std")]
+pub fn snort<G>(max_vertices: usize) -> impl Strategy<Value = Snort<G>>
+where
+    G: Graph + Clone + Debug,
+{
+    let color = prop_oneof![
+        2 => Just(VertexColor::Empty),
+        1 => Just(VertexColor::TintLeft),
+        1 => Just(VertexColor::TintRight),
+    ];
+    graph::<G>(max_vertices).prop_flat_map(move |graph| {
+        vec(color.clone(), graph.size()).prop_map(move |colors| {
+            Snort::with_colors(
+                colors.into_iter().map(VertexKind::Single).collect(),
+                graph.clone(),
+            )
+            .expect("unreachable: graph has no loops nor taken vertices")
+        })
+    })
+}
+
+impl Arbitrary for Nimber {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        nimber(u16::MAX as u32).boxed()
+    }
+}
+
+impl Arbitrary for DyadicRationalNumber {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        dyadic_rational_number(i32::MAX as i64, 16).boxed()
+    }
+}
+
+impl Arbitrary for Nus {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        nus().boxed()
+    }
+}
+
+impl Arbitrary for CanonicalForm {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        canonical_form(3).boxed()
+    }
+}
@@ -0,0 +1,251 @@
+//! Zobrist hashing of game positions
+//!
+//! Hash of a position is the XOR of random keys of its parts, e.g. occupied tiles of a grid, so
+//! when a move changes only a few parts the hash of the resulting position can be computed from
+//! the hash of the previous one, without looking at the whole position. [`Zobrist`] wraps a game
+//! and keeps its hash next to it, so transposition tables and other hash maps use the stored hash
+//! instead of hashing the whole grid or adjacency matrix on every lookup.
+
+#[cfg(feature = "std")]
+use crate::short::partizan::{canonical_form::CanonicalForm, partizan_game::PartizanGame};
+use std::{
+    fmt::Display,
+    hash::{Hash, Hasher},
+};
+
+/// Index of key used for dimensions and other global properties of positions
+pub const GLOBAL_INDEX: u64 = u64::MAX;
+
+/// Get a pseudo-random key of `value` at `index`, e.g. a tile state at given tile.
+///
+/// Keys are deterministic, so hashes are stable between runs.
+#[inline]
+pub const fn zobrist_key(index: u64, value: u64) -> u64 {
+    splitmix64(splitmix64(index) ^ value)
+}
+
+#[inline]
+const fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Positions with Zobrist hash
+pub trait ZobristHash {
+    /// Compute hash of the whole position. Equal positions must have equal hashes.
+    fn zobrist_hash(&self) -> u64;
+
+    /// Get the hash of `other`, given that `hash` is the hash of `self`. Positions that can
+    /// update the hash incrementally, e.g. when `other` is an option of `self`, should override
+    /// this method. By default the hash of `other` is computed from scratch.
+    #[inline]
+    fn zobrist_hash_from(&self, hash: u64, other: &Self) -> u64 {
+        let _ = hash;
+        other.zobrist_hash()
+    }
+}
+
+/// Game position with its Zobrist hash
+///
+/// Hash is computed once when the position is created, and options get their hashes with
+/// [`ZobristHash::zobrist_hash_from`], so hashing a position for a transposition table lookup
+/// takes constant time.
+///
+/// # Examples
+///
+/// ```
+/// # // Games need the `std` feature
+/// # #[cfg(feature = "std")] {
+/// use cgt::{
+///     short::partizan::{
+///         games::domineering::Domineering, partizan_game::PartizanGame,
+///         transposition_table::ParallelTranspositionTable,
+///     },
+///     zobrist::Zobrist,
+/// };
+/// use std::str::FromStr;
+///
+/// let position: Domineering = Domineering::from_str("...|...|...").unwrap();
+/// assert_eq!(
+///     Zobrist::new(position).canonical_form(&ParallelTranspositionTable::new()),
+///     position.canonical_form(&ParallelTranspositionTable::new()),
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Zobrist<G> {
+    position: G,
+    hash: u64,
+}
+
+impl<G> Zobrist<G>
+where
+    G: ZobristHash,
+{
+    /// Compute hash of the position
+    #[inline]
+    pub fn new(position: G) -> Self {
+        Self {
+            hash: position.zobrist_hash(),
+            position,
+        }
+    }
+
+    /// Get the wrapped position
+    #[inline]
+    pub const fn position(&self) -> &G {
+        &self.position
+    }
+
+    /// Unwrap the position
+    #[inline]
+    pub fn into_inner(self) -> G {
+        self.position
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn option(&self, option: G) -> Self {
+        Self {
+            hash: self.position.zobrist_hash_from(self.hash, &option),
+            position: option,
+        }
+    }
+}
+
+impl<G> ZobristHash for Zobrist<G> {
+    #[inline]
+    fn zobrist_hash(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl<G> Hash for Zobrist<G> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+impl<G> PartialEq for Zobrist<G>
+where
+    G: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.position == other.position
+    }
+}
+
+impl<G> Eq for Zobrist<G> where G: Eq {}
+
+impl<G> Display for Zobrist<G>
+where
+    G: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.position.fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<G> PartizanGame for Zobrist<G>
+where
+    G: PartizanGame + ZobristHash,
+{
+    fn left_moves(&self) -> Vec<Self> {
+        self.position
+            .left_moves()
+            .into_iter()
+            .map(|option| self.option(option))
+            .collect()
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.position
+            .right_moves()
+            .into_iter()
+            .map(|option| self.option(option))
+            .collect()
+    }
+
+    fn left_moves_iter(&self) -> impl Iterator<Item = Self> + '_ {
+        self.position
+            .left_moves_iter()
+            .map(|option| self.option(option))
+    }
+
+    fn right_moves_iter(&self) -> impl Iterator<Item = Self> + '_ {
+        self.position
+            .right_moves_iter()
+            .map(|option| self.option(option))
+    }
+
+    fn decompositions(&self) -> Vec<Self> {
+        self.position
+            .decompositions()
+            .into_iter()
+            .map(|component| self.option(component))
+            .collect()
+    }
+
+    fn reductions(&self) -> Option<CanonicalForm> {
+        self.position.reductions()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::adjacency_matrix::undirected::UndirectedGraph,
+        grid::{small_bit_grid::SmallBitGrid, Grid},
+        short::partizan::{
+            games::{domineering::Domineering, snort::Snort},
+            transposition_table::ParallelTranspositionTable,
+        },
+    };
+    use std::{num::NonZeroU32, str::FromStr};
+
+    #[test]
+    fn incremental_hash_matches_full_hash() {
+        let position: Zobrist<Domineering> =
+            Zobrist::new(Domineering::from_str("....|.#..|....").unwrap());
+        for option in position.left_moves().iter().chain(&position.right_moves()) {
+            assert_eq!(option.zobrist_hash(), option.position().zobrist_hash());
+        }
+
+        let position: Zobrist<Snort<UndirectedGraph>> =
+            Zobrist::new(Snort::new_three_caterpillar(NonZeroU32::new(3).unwrap()));
+        for option in position.left_moves().iter().chain(&position.right_moves()) {
+            assert_eq!(option.zobrist_hash(), option.position().zobrist_hash());
+        }
+
+        let grid = SmallBitGrid::<bool>::from_str("..#|.#.").unwrap();
+        let mut moved = grid;
+        moved.set(0, 0, true);
+        assert_eq!(
+            grid.zobrist_hash_from(grid.zobrist_hash(), &moved),
+            moved.zobrist_hash()
+        );
+        assert_ne!(grid.zobrist_hash(), moved.zobrist_hash());
+    }
+
+    #[test]
+    fn same_canonical_form() {
+        let position: Domineering = Domineering::from_str("....|.#..|....").unwrap();
+        assert_eq!(
+            Zobrist::new(position).canonical_form(&ParallelTranspositionTable::new()),
+            position.canonical_form(&ParallelTranspositionTable::new())
+        );
+
+        let position: Snort<UndirectedGraph> =
+            Snort::new_three_caterpillar(NonZeroU32::new(3).unwrap());
+        assert_eq!(
+            Zobrist::new(position.clone()).canonical_form(&ParallelTranspositionTable::new()),
+            position.canonical_form(&ParallelTranspositionTable::new())
+        );
+    }
+}
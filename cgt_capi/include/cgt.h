@@ -0,0 +1,107 @@
+/*
+ * C interface of the cgt library, see cgt_capi/README.md
+ *
+ * Positions and values are opaque handles owned by the caller and freed with
+ * cgt_position_free and cgt_value_free. Strings returned by the library are
+ * freed with cgt_string_free. Fallible functions return CGT_OK and write their
+ * result to the last argument, or return an error code and leave it untouched.
+ * Panics inside the library are reported as CGT_ERROR_PANIC, or as NULL by
+ * functions returning a pointer.
+ */
+
+#ifndef CGT_H
+#define CGT_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Version of this interface, compare with cgt_version() */
+#define CGT_CAPI_VERSION 1
+
+typedef enum cgt_status {
+    CGT_OK = 0,
+    /* Required pointer argument was null */
+    CGT_ERROR_NULL_POINTER = 1,
+    /* Input string was not valid UTF-8 */
+    CGT_ERROR_INVALID_UTF8 = 2,
+    /* Input was not a position in the game:payload format */
+    CGT_ERROR_INVALID_POSITION = 3,
+    /* Input was not a value, e.g. {1|-1}, ^* or 3/2 */
+    CGT_ERROR_INVALID_VALUE = 4,
+    /* Library panicked, e.g. a number overflowed. Indicates a bug or a limit */
+    CGT_ERROR_PANIC = 5
+} cgt_status;
+
+typedef enum cgt_ordering {
+    CGT_LESS = -1,
+    CGT_EQUAL = 0,
+    CGT_GREATER = 1,
+    /* Neither value is greater or equal, e.g. * and 0 */
+    CGT_CONFUSED = 2
+} cgt_ordering;
+
+/* Position of any game */
+typedef struct cgt_position cgt_position;
+
+/* Canonical form of a short partizan game */
+typedef struct cgt_value cgt_value;
+
+/* Version of the linked library */
+uint32_t cgt_version(void);
+
+/*
+ * Message of the last failure on the calling thread. Valid until the next
+ * failing call on the same thread, and must not be freed
+ */
+const char *cgt_last_error_message(void);
+
+/* Free string returned by the library. Does nothing on NULL */
+void cgt_string_free(char *string);
+
+/* Parse position in the game:payload format, e.g. "domineering:..|.." */
+cgt_status cgt_position_parse(const char *input, cgt_position **out);
+
+/* Free position. Does nothing on NULL */
+void cgt_position_free(cgt_position *position);
+
+/* Print position in the game:payload format */
+cgt_status cgt_position_to_string(const cgt_position *position, char **out);
+
+/*
+ * Compute canonical form of the position. Every call starts with an empty
+ * transposition table
+ */
+cgt_status cgt_position_canonical_form(const cgt_position *position, cgt_value **out);
+
+/* Parse value, e.g. "{1|-1}", "^*" or "3/2" */
+cgt_status cgt_value_parse(const char *input, cgt_value **out);
+
+/* Value of an integer, NULL on panic */
+cgt_value *cgt_value_integer(int64_t value);
+
+/* Free value. Does nothing on NULL */
+void cgt_value_free(cgt_value *value);
+
+/* Print shortest form of the value, e.g. "{1|-1}" or "^*" */
+cgt_status cgt_value_to_string(const cgt_value *value, char **out);
+
+/* Print value in LaTeX */
+cgt_status cgt_value_to_latex(const cgt_value *value, char **out);
+
+/* Print temperature of the value as an exact string, e.g. "3/2" */
+cgt_status cgt_value_temperature(const cgt_value *value, char **out);
+
+/* Compare values */
+cgt_status cgt_value_compare(const cgt_value *lhs, const cgt_value *rhs, cgt_ordering *out);
+
+/* Sum of values, e.g. of components of a position */
+cgt_status cgt_value_add(const cgt_value *lhs, const cgt_value *rhs, cgt_value **out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CGT_H */
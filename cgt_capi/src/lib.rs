@@ -0,0 +1,554 @@
+//! C bindings of the `cgt` library for embedding in game GUIs and other non-Rust programs
+//!
+//! The interface is declared in `include/cgt.h`, which must be kept in sync with this file.
+//! Positions and values are opaque handles owned by the caller. Fallible functions return a
+//! [`Status`] and write their result to an out pointer, with the error message available from
+//! [`cgt_last_error_message`].
+//!
+//! Panics never unwind into the caller. They are reported as [`Status::Panic`], or as a null
+//! result by functions that do not return a status. This needs the library to be built with
+//! unwinding, e.g. with the `release-capi` profile, as the `release` profile aborts on panic.
+
+use cgt::short::partizan::{
+    canonical_form::CanonicalForm,
+    position::{parse_position, AnyPosition},
+};
+use std::{
+    any::Any,
+    cell::RefCell,
+    cmp,
+    ffi::{c_char, CStr, CString},
+    panic::{self, AssertUnwindSafe},
+    str::FromStr,
+};
+
+/// Version of the interface in `include/cgt.h`, bumped on every incompatible change
+pub const CGT_CAPI_VERSION: u32 = 1;
+
+/// Result of a fallible function, `cgt_status` in C
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    InvalidPosition = 3,
+    InvalidValue = 4,
+    Panic = 5,
+}
+
+/// Result of comparing values, `cgt_ordering` in C
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ordering {
+    Less = -1,
+    Equal = 0,
+    Greater = 1,
+    Confused = 2,
+}
+
+/// Position of any game, `cgt_position` in C
+pub struct Position {
+    inner: AnyPosition,
+}
+
+/// Canonical form of a short partizan game, `cgt_value` in C
+pub struct Value {
+    inner: CanonicalForm,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+struct Failure {
+    status: Status,
+    message: String,
+}
+
+impl Failure {
+    fn new(status: Status, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+fn set_last_error(message: &str) {
+    let message =
+        CString::new(message.replace('\0', " ")).expect("unreachable: nul bytes were replaced");
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic payload");
+    format!("Internal error: {message}")
+}
+
+/// Run `f`, returning `on_panic` instead of unwinding into the caller, and store the panic
+/// message for [`cgt_last_error_message`]
+fn guard<T>(on_panic: T, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        set_last_error(&panic_message(payload.as_ref()));
+        on_panic
+    })
+}
+
+/// Run `f` and store the message of its failure or panic for [`cgt_last_error_message`]
+fn report(f: impl FnOnce() -> Result<(), Failure>) -> Status {
+    guard(Status::Panic, || match f() {
+        Ok(()) => Status::Ok,
+        Err(failure) => {
+            set_last_error(&failure.message);
+            failure.status
+        }
+    })
+}
+
+unsafe fn borrow<'a, T>(handle: *const T, name: &str) -> Result<&'a T, Failure> {
+    handle
+        .as_ref()
+        .ok_or_else(|| Failure::new(Status::NullPointer, format!("'{name}' is null")))
+}
+
+unsafe fn read_str<'a>(input: *const c_char, name: &str) -> Result<&'a str, Failure> {
+    if input.is_null() {
+        return Err(Failure::new(
+            Status::NullPointer,
+            format!("'{name}' is null"),
+        ));
+    }
+    CStr::from_ptr(input)
+        .to_str()
+        .map_err(|err| Failure::new(Status::InvalidUtf8, format!("'{name}' is not UTF-8: {err}")))
+}
+
+/// Check out pointer before computing the result, so nothing is allocated when the result
+/// cannot be returned
+fn check_out<T>(out: *mut T) -> Result<(), Failure> {
+    if out.is_null() {
+        return Err(Failure::new(Status::NullPointer, "'out' is null"));
+    }
+    Ok(())
+}
+
+/// Write result to out pointer that passed [`check_out`]
+unsafe fn write<T>(out: *mut T, value: T) -> Result<(), Failure> {
+    debug_assert!(!out.is_null(), "'out' was not checked");
+    out.write(value);
+    Ok(())
+}
+
+unsafe fn write_string(out: *mut *mut c_char, value: String) -> Result<(), Failure> {
+    let value =
+        CString::new(value).expect("unreachable: printed positions and values have no nul bytes");
+    write(out, value.into_raw())
+}
+
+/// Version of the interface, compare with `CGT_CAPI_VERSION` from the header
+#[no_mangle]
+pub extern "C" fn cgt_version() -> u32 {
+    CGT_CAPI_VERSION
+}
+
+/// Message of the last failure on the calling thread. Valid until the next failing call on the
+/// same thread, and must not be freed
+#[no_mangle]
+pub extern "C" fn cgt_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ptr())
+}
+
+/// Free string returned by the library. Does nothing on null
+///
+/// # Safety
+///
+/// `string` must be null or returned by this library and not freed before
+#[no_mangle]
+pub unsafe extern "C" fn cgt_string_free(string: *mut c_char) {
+    guard((), || {
+        if !string.is_null() {
+            drop(CString::from_raw(string));
+        }
+    });
+}
+
+/// Parse position in the `game:payload` format, e.g. `domineering:..|..`
+///
+/// # Safety
+///
+/// `input` must be a nul-terminated string and `out` must be valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn cgt_position_parse(
+    input: *const c_char,
+    out: *mut *mut Position,
+) -> Status {
+    report(|| {
+        check_out(out)?;
+        let input = read_str(input, "input")?;
+        let inner = parse_position(input).map_err(|err| {
+            Failure::new(
+                Status::InvalidPosition,
+                format!("Could not parse position '{input}': {err}"),
+            )
+        })?;
+        write(out, Box::into_raw(Box::new(Position { inner })))
+    })
+}
+
+/// Free position. Does nothing on null
+///
+/// # Safety
+///
+/// `position` must be null or returned by this library and not freed before
+#[no_mangle]
+pub unsafe extern "C" fn cgt_position_free(position: *mut Position) {
+    guard((), || {
+        if !position.is_null() {
+            drop(Box::from_raw(position));
+        }
+    });
+}
+
+/// Print position in the `game:payload` format, to be freed with [`cgt_string_free`]
+///
+/// # Safety
+///
+/// `position` must be a live position and `out` must be valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn cgt_position_to_string(
+    position: *const Position,
+    out: *mut *mut c_char,
+) -> Status {
+    report(|| {
+        check_out(out)?;
+        let position = borrow(position, "position")?;
+        write_string(out, position.inner.to_string())
+    })
+}
+
+/// Compute canonical form of the position. Every call starts with an empty transposition table
+///
+/// # Safety
+///
+/// `position` must be a live position and `out` must be valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn cgt_position_canonical_form(
+    position: *const Position,
+    out: *mut *mut Value,
+) -> Status {
+    report(|| {
+        check_out(out)?;
+        let position = borrow(position, "position")?;
+        let inner = position.inner.canonical_form();
+        write(out, Box::into_raw(Box::new(Value { inner })))
+    })
+}
+
+/// Parse value, e.g. `{1|-1}`, `^*` or `3/2`
+///
+/// # Safety
+///
+/// `input` must be a nul-terminated string and `out` must be valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn cgt_value_parse(input: *const c_char, out: *mut *mut Value) -> Status {
+    report(|| {
+        check_out(out)?;
+        let input = read_str(input, "input")?;
+        let inner = CanonicalForm::from_str(input).map_err(|err| {
+            Failure::new(
+                Status::InvalidValue,
+                format!("Could not parse value '{input}': {err}"),
+            )
+        })?;
+        write(out, Box::into_raw(Box::new(Value { inner })))
+    })
+}
+
+/// Value of an integer, to be freed with [`cgt_value_free`]. Null on panic
+#[no_mangle]
+pub extern "C" fn cgt_value_integer(value: i64) -> *mut Value {
+    guard(std::ptr::null_mut(), || {
+        Box::into_raw(Box::new(Value {
+            inner: CanonicalForm::new_integer(value),
+        }))
+    })
+}
+
+/// Free value. Does nothing on null
+///
+/// # Safety
+///
+/// `value` must be null or returned by this library and not freed before
+#[no_mangle]
+pub unsafe extern "C" fn cgt_value_free(value: *mut Value) {
+    guard((), || {
+        if !value.is_null() {
+            drop(Box::from_raw(value));
+        }
+    });
+}
+
+/// Print shortest form of the value, e.g. `{1|-1}` or `^*`, to be freed with
+/// [`cgt_string_free`]
+///
+/// # Safety
+///
+/// `value` must be a live value and `out` must be valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn cgt_value_to_string(value: *const Value, out: *mut *mut c_char) -> Status {
+    report(|| {
+        check_out(out)?;
+        let value = borrow(value, "value")?;
+        write_string(out, value.inner.to_string())
+    })
+}
+
+/// Print value in LaTeX, to be freed with [`cgt_string_free`]
+///
+/// # Safety
+///
+/// `value` must be a live value and `out` must be valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn cgt_value_to_latex(value: *const Value, out: *mut *mut c_char) -> Status {
+    report(|| {
+        check_out(out)?;
+        let value = borrow(value, "value")?;
+        write_string(out, value.inner.to_latex())
+    })
+}
+
+/// Print temperature of the value as an exact string, e.g. `3/2`, to be freed with
+/// [`cgt_string_free`]
+///
+/// # Safety
+///
+/// `value` must be a live value and `out` must be valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn cgt_value_temperature(
+    value: *const Value,
+    out: *mut *mut c_char,
+) -> Status {
+    report(|| {
+        check_out(out)?;
+        let value = borrow(value, "value")?;
+        write_string(out, value.inner.temperature().to_string())
+    })
+}
+
+/// Compare values, with [`Ordering::Confused`] if neither is greater or equal
+///
+/// # Safety
+///
+/// `lhs` and `rhs` must be live values and `out` must be valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn cgt_value_compare(
+    lhs: *const Value,
+    rhs: *const Value,
+    out: *mut Ordering,
+) -> Status {
+    report(|| {
+        check_out(out)?;
+        let lhs = borrow(lhs, "lhs")?;
+        let rhs = borrow(rhs, "rhs")?;
+        let ordering = match CanonicalForm::compare(&lhs.inner, &rhs.inner) {
+            Some(cmp::Ordering::Less) => Ordering::Less,
+            Some(cmp::Ordering::Equal) => Ordering::Equal,
+            Some(cmp::Ordering::Greater) => Ordering::Greater,
+            None => Ordering::Confused,
+        };
+        write(out, ordering)
+    })
+}
+
+/// Sum of values, e.g. of components of a position
+///
+/// # Safety
+///
+/// `lhs` and `rhs` must be live values and `out` must be valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn cgt_value_add(
+    lhs: *const Value,
+    rhs: *const Value,
+    out: *mut *mut Value,
+) -> Status {
+    report(|| {
+        check_out(out)?;
+        let lhs = borrow(lhs, "lhs")?;
+        let rhs = borrow(rhs, "rhs")?;
+        let inner = &lhs.inner + &rhs.inner;
+        write(out, Box::into_raw(Box::new(Value { inner })))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    fn last_error() -> String {
+        unsafe { CStr::from_ptr(cgt_last_error_message()) }
+            .to_str()
+            .unwrap()
+            .to_owned()
+    }
+
+    /// Take ownership of string returned by the library
+    unsafe fn take_string(string: *mut c_char) -> String {
+        let result = CStr::from_ptr(string).to_str().unwrap().to_owned();
+        cgt_string_free(string);
+        result
+    }
+
+    unsafe fn parse_value(input: &str) -> *mut Value {
+        let input = CString::new(input).unwrap();
+        let mut value = ptr::null_mut();
+        assert_eq!(cgt_value_parse(input.as_ptr(), &mut value), Status::Ok);
+        value
+    }
+
+    #[test]
+    fn position_round_trip() {
+        unsafe {
+            let input = CString::new("domineering:..|..").unwrap();
+            let mut position = ptr::null_mut();
+            assert_eq!(
+                cgt_position_parse(input.as_ptr(), &mut position),
+                Status::Ok
+            );
+
+            let mut string = ptr::null_mut();
+            assert_eq!(cgt_position_to_string(position, &mut string), Status::Ok);
+            assert_eq!(take_string(string), "domineering:..|..");
+
+            let mut value = ptr::null_mut();
+            assert_eq!(
+                cgt_position_canonical_form(position, &mut value),
+                Status::Ok
+            );
+            assert_eq!(cgt_value_to_string(value, &mut string), Status::Ok);
+            assert_eq!(take_string(string), "{1|-1}");
+
+            cgt_value_free(value);
+            cgt_position_free(position);
+        }
+    }
+
+    #[test]
+    fn value_round_trip() {
+        unsafe {
+            let switch = parse_value("{1|-1}");
+            let mut string = ptr::null_mut();
+            assert_eq!(cgt_value_to_latex(switch, &mut string), Status::Ok);
+            assert_eq!(take_string(string), r"\{1 \,|\, -1\}");
+            assert_eq!(cgt_value_temperature(switch, &mut string), Status::Ok);
+            assert_eq!(take_string(string), "1");
+
+            let one = cgt_value_integer(1);
+            let mut sum = ptr::null_mut();
+            assert_eq!(cgt_value_add(switch, one, &mut sum), Status::Ok);
+            assert_eq!(cgt_value_to_string(sum, &mut string), Status::Ok);
+            assert_eq!(take_string(string), "{2|0}");
+
+            let mut ordering = Ordering::Equal;
+            assert_eq!(cgt_value_compare(one, sum, &mut ordering), Status::Ok);
+            assert_eq!(ordering, Ordering::Confused);
+            assert_eq!(cgt_value_compare(sum, switch, &mut ordering), Status::Ok);
+            assert_eq!(ordering, Ordering::Greater);
+
+            for value in [switch, one, sum] {
+                cgt_value_free(value);
+            }
+        }
+    }
+
+    #[test]
+    fn null_pointers() {
+        unsafe {
+            let mut value = ptr::null_mut();
+            assert_eq!(
+                cgt_value_parse(ptr::null(), &mut value),
+                Status::NullPointer
+            );
+            assert_eq!(last_error(), "'input' is null");
+            assert!(value.is_null());
+
+            let input = CString::new("domineering:..").unwrap();
+            assert_eq!(
+                cgt_position_parse(input.as_ptr(), ptr::null_mut()),
+                Status::NullPointer
+            );
+            assert_eq!(last_error(), "'out' is null");
+
+            let mut string = ptr::null_mut();
+            assert_eq!(
+                cgt_position_to_string(ptr::null(), &mut string),
+                Status::NullPointer
+            );
+            assert_eq!(last_error(), "'position' is null");
+            assert!(string.is_null());
+
+            let one = cgt_value_integer(1);
+            assert_eq!(
+                cgt_value_add(one, ptr::null(), &mut value),
+                Status::NullPointer
+            );
+            assert_eq!(last_error(), "'rhs' is null");
+            assert_eq!(
+                cgt_value_to_string(one, ptr::null_mut()),
+                Status::NullPointer
+            );
+            cgt_value_free(one);
+
+            // Freeing null is allowed
+            cgt_value_free(ptr::null_mut());
+            cgt_position_free(ptr::null_mut());
+            cgt_string_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn invalid_input() {
+        unsafe {
+            let input = c"domineering:\xff";
+            let mut position = ptr::null_mut();
+            assert_eq!(
+                cgt_position_parse(input.as_ptr(), &mut position),
+                Status::InvalidUtf8
+            );
+            assert!(last_error().starts_with("'input' is not UTF-8"));
+
+            let input = CString::new("chess:..").unwrap();
+            assert_eq!(
+                cgt_position_parse(input.as_ptr(), &mut position),
+                Status::InvalidPosition
+            );
+            assert!(position.is_null());
+
+            let input = CString::new("{1|").unwrap();
+            let mut value = ptr::null_mut();
+            assert_eq!(
+                cgt_value_parse(input.as_ptr(), &mut value),
+                Status::InvalidValue
+            );
+            assert!(last_error().starts_with("Could not parse value '{1|'"));
+            assert!(value.is_null());
+        }
+    }
+
+    #[test]
+    fn panics_are_reported() {
+        assert_eq!(report(|| panic!("boom")), Status::Panic);
+        assert_eq!(last_error(), "Internal error: boom");
+
+        let number = 42;
+        assert_eq!(report(|| panic!("boom {number}")), Status::Panic);
+        assert_eq!(last_error(), "Internal error: boom 42");
+
+        assert!(guard(ptr::null_mut::<Value>(), || panic!("boom")).is_null());
+    }
+}
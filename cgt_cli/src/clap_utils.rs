@@ -2,6 +2,8 @@ macro_rules! mk_subcommand {
     ($($variant:ident => $module:ident),* $(,)?) => {
         $(mod $module;)*
 
+        // Parsed once per run, so sizes of arguments of subcommands do not matter
+        #[allow(clippy::large_enum_variant)]
         #[derive(::clap::Subcommand, Debug)]
         pub enum Command {
             $($variant($module::Args),)*
@@ -5,4 +5,18 @@ crate::clap_utils::mk_subcommand! {
     WindUp => wind_up,
     CanonicalForm => canonical_form,
     Amazons => amazons,
+    Search => search,
+    Eval => eval,
+    Logs => logs,
+    Db => db,
+    Cgsuite => cgsuite,
+    Convert => convert,
+    Export => export,
+    Play => play,
+    Bench => bench,
+    Conjecture => conjecture,
+    Graph => graph,
+    Tree => tree,
+    Pipe => pipe,
+    Family => family,
 }
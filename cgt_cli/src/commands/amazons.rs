@@ -1,3 +1,4 @@
 crate::clap_utils::mk_subcommand! {
     Evaluate => evaluate,
+    Render => render,
 }
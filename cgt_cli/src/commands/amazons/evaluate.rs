@@ -1,29 +1,59 @@
 use anyhow::{Context, Result};
-use cgt::short::partizan::{
-    games::amazons::Amazons, partizan_game::PartizanGame,
-    transposition_table::ParallelTranspositionTable,
+use cgt::{
+    numeric::dyadic_rational_number::DyadicRationalNumber,
+    short::partizan::{
+        games::amazons::{Amazons, Tile},
+        partizan_game::PartizanGame,
+        transposition_table::{
+            CountingTranspositionTable, ParallelTranspositionTable, TranspositionTable,
+        },
+    },
 };
 use clap::{self, Parser};
 use std::str::FromStr;
 
-/// Evaluate a single Amazons position
+#[derive(serde::Serialize)]
+struct EvaluationResult {
+    position: String,
+    canonical_form: String,
+    temperature: DyadicRationalNumber,
+    left_amazons: usize,
+    right_amazons: usize,
+}
+
+/// Evaluate a single Amazons position. Writes details to stderr and a JSON result to stdout
 #[derive(Debug, Clone, Parser)]
 pub struct Args {
     /// Amazons position to evalueate (e.g. '.x.|o#.|..#')
     #[arg(long)]
     position: String,
+
+    /// Print transposition table statistics
+    #[arg(long, default_value_t = false)]
+    stats: bool,
 }
 
 pub fn run(args: Args) -> Result<()> {
-    let pos: Amazons = Amazons::from_str(&args.position)
-        .ok()
-        .context("Could not parse the position")?;
+    let pos: Amazons =
+        Amazons::from_str(&args.position).context("Could not parse the position")?;
     eprintln!("Game: {}", pos);
 
-    let tt = ParallelTranspositionTable::new();
+    let tt = CountingTranspositionTable::new(ParallelTranspositionTable::new());
     let cf = pos.canonical_form(&tt);
     eprintln!("Canonical Form: {}", cf);
     eprintln!("Temperature: {}", cf.temperature());
+    if args.stats {
+        eprintln!("Transposition table: {}", tt.statistics());
+    }
+
+    let result = EvaluationResult {
+        position: pos.to_string(),
+        canonical_form: cf.to_string(),
+        temperature: cf.temperature(),
+        left_amazons: pos.amazons_count(Tile::Left),
+        right_amazons: pos.amazons_count(Tile::Right),
+    };
+    println!("{}", serde_json::ser::to_string(&result)?);
 
     Ok(())
 }
@@ -0,0 +1,56 @@
+use crate::io::FileOrStdout;
+use anyhow::{Context, Result};
+use cgt::{drawing::svg::Svg, short::partizan::games::amazons::Amazons};
+use clap::{Parser, ValueEnum};
+use std::{io::Write, str::FromStr};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    Svg,
+    Tikz,
+
+    /// Graphviz board with pinned tile positions, render with `neato`
+    Dot,
+}
+
+/// Draw Amazons position
+#[derive(Parser, Debug)]
+pub struct Args {
+    /// Amazons position to draw (e.g. '.x.|o#.|..#')
+    #[arg(long)]
+    position: String,
+
+    /// Format of the image
+    #[arg(long, value_enum, default_value_t = Format::Svg)]
+    format: Format,
+
+    /// Scale of TikZ pictures
+    #[arg(long, default_value_t = 1.0)]
+    tikz_scale: f32,
+
+    /// Output file with the image. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let position: Amazons = Amazons::from_str(&args.position).context("Could not parse position")?;
+
+    let image = match args.format {
+        Format::Svg => {
+            let mut buf = String::new();
+            position.to_svg(&mut buf).context("Could not render SVG")?;
+            buf
+        }
+        Format::Tikz => position.to_tikz_with_scale(args.tikz_scale),
+        Format::Dot => position.to_graphviz(),
+    };
+
+    let mut output = args
+        .out_file
+        .create()
+        .context("Could not create output file")?;
+    writeln!(output, "{image}")?;
+
+    Ok(())
+}
@@ -0,0 +1,202 @@
+use crate::io::FileOrStdout;
+use anyhow::{Context, Result};
+use cgt::short::partizan::{
+    position::{parse_position, AnyPosition},
+    transposition_table::TableKind,
+};
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+use std::{
+    io::Write,
+    num::NonZeroUsize,
+    str::FromStr,
+    thread::available_parallelism,
+    time::{Duration, Instant},
+};
+
+/// Positions evaluated when none are given, small enough to finish without a transposition
+/// table
+const SUITE: &[&str] = &[
+    "domineering:....|....|...#",
+    "snort:........;0-1,0-4,0-7,1-2,2-3,3-4,4-5,5-6,6-7",
+    "toads-and-frogs:TT..FF",
+];
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    /// Aligned table
+    Text,
+
+    /// JSON object per configuration, one per line
+    Json,
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Evaluate positions under different transposition tables and thread counts, and compare times
+pub struct Args {
+    /// Positions in the `game:payload` format. Use a suite of standard positions if none are given
+    positions: Vec<String>,
+
+    /// Transposition tables to compare: `none`, `unbounded` or `bounded:CAPACITY`
+    #[arg(long, value_delimiter = ',', default_value = "none,unbounded,bounded:4096")]
+    tables: Vec<String>,
+
+    /// Numbers of threads to compare. Defaults to 1 and the number of available cores
+    #[arg(long, value_delimiter = ',')]
+    threads: Vec<NonZeroUsize>,
+
+    /// Number of evaluations of every position in every configuration
+    #[arg(long, default_value_t = NonZeroUsize::new(3).unwrap())]
+    iterations: NonZeroUsize,
+
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Output file. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+}
+
+#[derive(Debug, Serialize)]
+struct Measurement {
+    position: String,
+    table: String,
+    threads: usize,
+
+    /// Fastest evaluation, in milliseconds
+    best_ms: f64,
+
+    /// Mean evaluation time, in milliseconds
+    mean_ms: f64,
+
+    /// Fastest evaluation relative to the fastest configuration of the position
+    relative: f64,
+
+    hit_rate: Option<f64>,
+
+    /// Positions stored in the table after evaluation
+    stored_positions: usize,
+}
+
+fn measure(position: &AnyPosition, table: TableKind, iterations: NonZeroUsize) -> Measurement {
+    let mut times = Vec::with_capacity(iterations.get());
+    let mut statistics = None;
+    for _ in 0..iterations.get() {
+        let start = Instant::now();
+        let (_, table_statistics) = position.canonical_form_with_table(table);
+        times.push(start.elapsed());
+        statistics = Some(table_statistics);
+    }
+    let statistics = statistics.expect("unreachable: there is at least one iteration");
+    let best = times.iter().min().copied().unwrap_or_default();
+    let mean = times.iter().sum::<Duration>() / times.len() as u32;
+
+    Measurement {
+        position: position.to_string(),
+        table: table.to_string(),
+        threads: rayon::current_num_threads(),
+        best_ms: best.as_secs_f64() * 1000.0,
+        mean_ms: mean.as_secs_f64() * 1000.0,
+        relative: 1.0,
+        hit_rate: statistics.hit_rate(),
+        stored_positions: statistics.positions,
+    }
+}
+
+fn write_text(output: &mut impl Write, measurements: &[Measurement]) -> Result<()> {
+    let position_width = measurements
+        .iter()
+        .map(|measurement| measurement.position.len())
+        .chain(["position".len()])
+        .max()
+        .unwrap_or_default();
+    let table_width = measurements
+        .iter()
+        .map(|measurement| measurement.table.len())
+        .chain(["table".len()])
+        .max()
+        .unwrap_or_default();
+
+    writeln!(
+        output,
+        "{:position_width$}  {:table_width$}  {:>7}  {:>12}  {:>12}  {:>8}  {:>8}  {:>10}",
+        "position", "table", "threads", "best (ms)", "mean (ms)", "relative", "hit rate", "stored"
+    )?;
+    for measurement in measurements {
+        let hit_rate = measurement
+            .hit_rate
+            .map_or_else(|| "-".to_owned(), |rate| format!("{:.1}%", rate * 100.0));
+        writeln!(
+            output,
+            "{:position_width$}  {:table_width$}  {:>7}  {:>12.3}  {:>12.3}  {:>7.2}x  {:>8}  {:>10}",
+            measurement.position,
+            measurement.table,
+            measurement.threads,
+            measurement.best_ms,
+            measurement.mean_ms,
+            measurement.relative,
+            hit_rate,
+            measurement.stored_positions,
+        )?;
+    }
+    Ok(())
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let positions = if args.positions.is_empty() {
+        SUITE.iter().map(|&position| position.to_owned()).collect()
+    } else {
+        args.positions.clone()
+    }
+    .iter()
+    .map(|position| {
+        parse_position(position).with_context(|| format!("Invalid position '{position}'"))
+    })
+    .collect::<Result<Vec<_>>>()?;
+    let tables = args
+        .tables
+        .iter()
+        .map(|table| TableKind::from_str(table))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut threads = args.threads.clone();
+    if threads.is_empty() {
+        threads.push(NonZeroUsize::MIN);
+        threads.push(available_parallelism().unwrap_or(NonZeroUsize::MIN));
+        threads.dedup();
+    }
+
+    let mut measurements = Vec::new();
+    for position in &positions {
+        let first = measurements.len();
+        for &thread_count in &threads {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(thread_count.get())
+                .build()
+                .context("Could not create thread pool")?;
+            for &table in &tables {
+                measurements.push(pool.install(|| measure(position, table, args.iterations)));
+            }
+        }
+
+        let fastest = measurements[first..]
+            .iter()
+            .map(|measurement| measurement.best_ms)
+            .fold(f64::INFINITY, f64::min);
+        for measurement in &mut measurements[first..] {
+            measurement.relative = measurement.best_ms / fastest;
+        }
+    }
+
+    let mut output = args.out_file.create()?;
+    match args.format {
+        Format::Text => write_text(&mut output, &measurements)?,
+        Format::Json => {
+            for measurement in &measurements {
+                writeln!(output, "{}", serde_json::ser::to_string(measurement)?)?;
+            }
+        }
+    }
+    output.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,4 @@
+crate::clap_utils::mk_subcommand! {
+    Export => export,
+    Import => import,
+}
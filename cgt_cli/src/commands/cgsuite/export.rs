@@ -0,0 +1,47 @@
+use crate::io::FileOrStdout;
+use anyhow::{anyhow, Result};
+use cgt::short::partizan::{cgsuite, position::parse_position};
+use clap::Parser;
+use std::io::Write;
+
+#[derive(Parser, Debug, Clone)]
+/// Write positions and their values as a CGSuite script
+///
+/// For every position the script defines a variable with its full game tree and checks that
+/// CGSuite computes the same canonical form, e.g. `g1 := {{{|}|}|{|{|}}}; g1 == {1|-1}`
+pub struct Args {
+    /// Positions in the `game:payload` format
+    #[arg(required = true)]
+    positions: Vec<String>,
+
+    /// Define variables with canonical forms instead of game trees, which is feasible for larger
+    /// positions
+    #[arg(long)]
+    values_only: bool,
+
+    /// Output file with the script. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let mut output = args.out_file.create()?;
+
+    for (idx, input) in args.positions.iter().enumerate() {
+        let position = parse_position(input)
+            .map_err(|err| anyhow!("Could not parse position '{input}': {err}"))?;
+        let value = cgsuite::to_cgsuite(&position.canonical_form());
+        let variable = format!("g{}", idx + 1);
+
+        writeln!(output, "// {position}")?;
+        if args.values_only {
+            writeln!(output, "{variable} := {value};")?;
+        } else {
+            writeln!(output, "{variable} := {};", position.to_cgsuite())?;
+            writeln!(output, "{variable} == {value};")?;
+        }
+    }
+    output.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anyhow::{anyhow, Result};
+use cgt::short::partizan::cgsuite;
+use clap::Parser;
+use std::io::{stdin, BufRead};
+
+#[derive(Parser, Debug, Clone)]
+/// Convert values printed by CGSuite, e.g. `{2||1|0}` or `^^*`, to canonical forms
+pub struct Args {
+    /// Values in the CGSuite notation. Read values from stdin, one per line, if none are given
+    #[arg(allow_hyphen_values = true)]
+    values: Vec<String>,
+}
+
+fn convert(value: &str) -> Result<()> {
+    let game = cgsuite::from_cgsuite(value.trim())
+        .map_err(|err| anyhow!("Could not parse value '{value}': {err}"))?;
+    println!("{game}");
+    Ok(())
+}
+
+pub fn run(args: Args) -> Result<()> {
+    if !args.values.is_empty() {
+        return args.values.iter().try_for_each(|value| convert(value));
+    }
+
+    for line in stdin().lock().lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            convert(&line)?;
+        }
+    }
+    Ok(())
+}
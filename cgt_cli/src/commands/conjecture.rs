@@ -0,0 +1,266 @@
+use crate::{
+    commands::{
+        logs,
+        search::fitness::Features,
+    },
+    io::{FileOrStdin, FileOrStdout},
+    progress,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use cgt::{
+    graph::{adjacency_matrix::undirected::UndirectedGraph, graph6, Graph},
+    numeric::dyadic_rational_number::DyadicRationalNumber,
+    short::partizan::{
+        batch::evaluate_all_with_progress,
+        canonical_form::CanonicalForm,
+        games::snort::Snort,
+        position::{parse_position, NamedGame},
+        transposition_table::ParallelTranspositionTable,
+    },
+};
+use clap::Parser;
+use rhai::{Dynamic, Engine, AST};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    num::NonZeroUsize,
+    path::PathBuf,
+    str::FromStr,
+};
+
+#[derive(Parser, Debug, Clone)]
+/// Check a claim on every position of a stream and report counterexamples
+///
+/// The claim is a [Rhai](https://rhai.rs) expression returning a boolean, with the same variables
+/// as fitness scripts of `cgt-cli search`, e.g. `temperature <= degree / 2.0`. Input lines are
+/// records written by `cgt-cli snort census`, positions in the `game:payload` format, or graph6
+/// graphs played as Snort.
+pub struct Args {
+    /// Claim expected to hold for every position
+    claim: String,
+
+    /// Input file, one record, position or graph per line. Use '-' for stdin
+    #[arg(long, default_value = "-")]
+    in_file: FileOrStdin,
+
+    /// Output file with newline-separated JSON counterexamples
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+
+    /// Number of lines read and evaluated in parallel at once
+    #[arg(long, default_value_t = NonZeroUsize::new(1024).unwrap())]
+    batch_size: NonZeroUsize,
+
+    /// Stop after finding this many counterexamples
+    #[arg(long)]
+    max_counterexamples: Option<NonZeroUsize>,
+
+    /// Directory to write Graphviz drawings of Snort counterexamples to, as `line-N.dot`
+    #[arg(long)]
+    render_dir: Option<PathBuf>,
+}
+
+/// Census record, other fields are recomputed from the canonical form
+#[derive(Deserialize)]
+struct CensusRecord {
+    graph6: String,
+    canonical_form: String,
+}
+
+/// Position read from one input line
+struct Candidate {
+    line: usize,
+    position: String,
+    snort: Option<Snort>,
+    canonical_form: Option<CanonicalForm>,
+}
+
+#[derive(Serialize)]
+struct Counterexample {
+    line: usize,
+    position: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    graph6: Option<String>,
+    canonical_form: String,
+    temperature: DyadicRationalNumber,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vertices: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    degree: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    drawing: Option<PathBuf>,
+}
+
+struct Claim {
+    engine: Engine,
+    ast: AST,
+}
+
+impl Claim {
+    fn new(source: &str) -> Result<Self> {
+        let engine = Engine::new();
+        let ast = engine
+            .compile_expression(source)
+            .map_err(|err| anyhow!("Could not compile claim: {err}"))?;
+        Ok(Self { engine, ast })
+    }
+
+    fn holds(&self, features: &Features) -> Result<bool> {
+        let value = self
+            .engine
+            .eval_ast_with_scope::<Dynamic>(&mut features.scope(), &self.ast)
+            .map_err(|err| anyhow!("Claim failed: {err}"))?;
+        value
+            .clone()
+            .try_cast::<bool>()
+            .ok_or_else(|| anyhow!("Claim returned {}, expected a boolean", value.type_name()))
+    }
+}
+
+fn snort_position(snort: &Snort) -> String {
+    format!("{}:{}", <Snort>::NAME, snort)
+}
+
+fn parse_line(line_number: usize, line: &str) -> Result<Candidate> {
+    let invalid = || format!("Invalid input at line {line_number}: '{line}'");
+    if line.starts_with('{') {
+        let record = serde_json::from_str::<CensusRecord>(line).with_context(invalid)?;
+        let graph = graph6::from_graph6::<UndirectedGraph>(&record.graph6)
+            .ok_or_else(|| anyhow!(invalid()))?;
+        let canonical_form = CanonicalForm::from_str(&record.canonical_form)
+            .map_err(|err| anyhow!("{}: {err}", invalid()))?;
+        let snort = Snort::new(graph);
+        Ok(Candidate {
+            line: line_number,
+            position: snort_position(&snort),
+            snort: Some(snort),
+            canonical_form: Some(canonical_form),
+        })
+    } else if line.contains(':') {
+        let position = parse_position(line).with_context(invalid)?;
+        let snort = if position.game() == <Snort>::NAME {
+            Some(logs::parse_snort(line)?)
+        } else {
+            None
+        };
+        // Snort positions are evaluated in batches with a shared transposition table
+        let canonical_form = snort.is_none().then(|| position.canonical_form());
+        Ok(Candidate {
+            line: line_number,
+            position: position.to_string(),
+            snort,
+            canonical_form,
+        })
+    } else {
+        let graph = graph6::from_graph6::<UndirectedGraph>(line).ok_or_else(|| anyhow!(invalid()))?;
+        let snort = Snort::new(graph);
+        Ok(Candidate {
+            line: line_number,
+            position: snort_position(&snort),
+            snort: Some(snort),
+            canonical_form: None,
+        })
+    }
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let claim = Claim::new(&args.claim)?;
+    let input = BufReader::new(args.in_file.open().context("Could not open input file")?);
+    let mut output = args.out_file.create().context("Could not open output file")?;
+    if let Some(render_dir) = &args.render_dir {
+        fs::create_dir_all(render_dir).context("Could not create render directory")?;
+    }
+    let transposition_table = ParallelTranspositionTable::new();
+    let progress = progress::spinner("positions");
+
+    let mut checked = 0;
+    let mut found = 0;
+    let mut lines = input.lines().enumerate();
+    'batches: loop {
+        let mut batch = Vec::with_capacity(args.batch_size.get());
+        let mut read = 0;
+        for (line_number, line) in lines.by_ref().take(args.batch_size.get()) {
+            read += 1;
+            let line = line.context("Could not read input")?;
+            let line = line.trim();
+            if !line.is_empty() {
+                batch.push(parse_line(line_number + 1, line)?);
+            }
+        }
+        if read == 0 {
+            break;
+        }
+
+        let missing = batch
+            .iter()
+            .filter(|candidate| candidate.canonical_form.is_none())
+            .filter_map(|candidate| candidate.snort.clone())
+            .collect::<Vec<_>>();
+        let mut evaluations =
+            evaluate_all_with_progress(&missing, &transposition_table, |_| {}).into_iter();
+
+        for candidate in batch {
+            let canonical_form = candidate.canonical_form.unwrap_or_else(|| {
+                evaluations
+                    .next()
+                    .expect("unreachable: every missing position is evaluated")
+                    .canonical_form
+            });
+            let features = Features {
+                canonical_form,
+                vertices: candidate.snort.as_ref().map(|snort| snort.graph.size()),
+                degree: candidate.snort.as_ref().map(Snort::degree),
+            };
+            checked += 1;
+            progress.inc(1);
+            if claim
+                .holds(&features)
+                .with_context(|| format!("Could not check line {}", candidate.line))?
+            {
+                continue;
+            }
+
+            let drawing = match (&args.render_dir, &candidate.snort) {
+                (Some(render_dir), Some(snort)) => {
+                    let path = render_dir.join(format!("line-{}.dot", candidate.line));
+                    fs::write(&path, snort.to_graphviz())
+                        .with_context(|| format!("Could not write '{}'", path.display()))?;
+                    Some(path)
+                }
+                _ => None,
+            };
+            let counterexample = Counterexample {
+                line: candidate.line,
+                position: candidate.position,
+                graph6: candidate
+                    .snort
+                    .as_ref()
+                    .map(|snort| graph6::to_graph6(&snort.graph)),
+                temperature: features.canonical_form.temperature(),
+                canonical_form: features.canonical_form.to_string(),
+                vertices: features.vertices,
+                degree: features.degree,
+                drawing,
+            };
+            writeln!(output, "{}", serde_json::ser::to_string(&counterexample)?)?;
+            found += 1;
+            if args
+                .max_counterexamples
+                .is_some_and(|max| found >= max.get())
+            {
+                break 'batches;
+            }
+        }
+        output.flush()?;
+    }
+    output.flush()?;
+    progress.finish_and_clear();
+
+    eprintln!("Checked {checked} positions, found {found} counterexamples");
+    if checked == 0 {
+        bail!("No positions to check");
+    }
+
+    Ok(())
+}
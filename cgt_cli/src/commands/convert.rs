@@ -0,0 +1,168 @@
+use crate::{
+    commands::logs,
+    io::{FileOrStdin, FileOrStdout},
+};
+use anyhow::{anyhow, bail, Context, Result};
+use cgt::{
+    graph::{adjacency_matrix::undirected::UndirectedGraph, graph6, Graph},
+    short::partizan::{games::snort::Snort, position::NamedGame},
+};
+use clap::{Parser, ValueEnum};
+use std::io::{Read, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// nauty's graph6, one graph per line. Input may also be sparse6
+    Graph6,
+
+    /// nauty's sparse6, one graph per line
+    Sparse6,
+
+    /// Rows of `0` and `1`, graphs separated by empty lines
+    Matrix,
+
+    /// Graphviz DOT with vertex colors, single graph
+    Dot,
+
+    /// GraphML with vertex colors, single graph
+    Graphml,
+
+    /// Snort position in the `snort:payload` format, one per line
+    Position,
+}
+
+impl Format {
+    pub fn name(self) -> String {
+        self.to_possible_value()
+            .expect("unreachable: no variant is skipped")
+            .get_name()
+            .to_owned()
+    }
+
+    /// Whether the format keeps vertex colors and clusters of Snort positions
+    const fn has_colors(self) -> bool {
+        matches!(self, Self::Dot | Self::Graphml | Self::Position)
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Convert graphs and Snort positions between formats. Graph-only formats (graph6, sparse6 and
+/// matrix) read as positions with all vertices empty
+pub struct Args {
+    /// Format of the input
+    #[arg(long, value_enum)]
+    from: Format,
+
+    /// Format of the output
+    #[arg(long, value_enum)]
+    to: Format,
+
+    /// Drop vertex colors and clusters when converting to a graph-only format, instead of failing
+    #[arg(long, default_value_t = false)]
+    drop_colors: bool,
+
+    /// Input file. Use '-' for stdin
+    #[arg(long, default_value = "-")]
+    in_file: FileOrStdin,
+
+    /// Output file. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+}
+
+fn parse_matrix(input: &str) -> Result<UndirectedGraph> {
+    let rows = input
+        .lines()
+        .map(|row| {
+            row.chars()
+                .filter(|c| !c.is_whitespace())
+                .map(|c| match c {
+                    '0' => Ok(false),
+                    '1' => Ok(true),
+                    _ => Err(anyhow!("Invalid adjacency matrix entry '{c}'")),
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let size = rows.len();
+    if rows.iter().any(|row| row.len() != size) {
+        bail!("Adjacency matrix is not square");
+    }
+    if (0..size).any(|u| (0..u).any(|v| rows[u][v] != rows[v][u])) {
+        bail!("Adjacency matrix is not symmetric");
+    }
+    let matrix = rows.concat();
+    UndirectedGraph::from_flat_matrix(size, &matrix)
+        .map_err(|_| anyhow!("Adjacency matrix is not square"))
+}
+
+/// Split input into records of the format
+fn records(format: Format, input: &str) -> Vec<&str> {
+    match format {
+        Format::Graph6 | Format::Sparse6 | Format::Position => input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect(),
+        Format::Matrix => input
+            .split("\n\n")
+            .map(str::trim)
+            .filter(|block| !block.is_empty())
+            .collect(),
+        Format::Dot | Format::Graphml => vec![input],
+    }
+}
+
+fn parse(format: Format, record: &str) -> Result<Snort> {
+    let invalid = || anyhow!("Invalid {} input '{record}'", format.name());
+    match format {
+        Format::Graph6 | Format::Sparse6 => {
+            graph6::from_nauty(record).map(Snort::new).ok_or_else(invalid)
+        }
+        Format::Matrix => parse_matrix(record).map(Snort::new),
+        Format::Dot => Snort::from_graphviz(record).ok_or_else(invalid),
+        Format::Graphml => Snort::from_graphml(record).ok_or_else(invalid),
+        Format::Position => logs::parse_snort(record),
+    }
+}
+
+/// Write position in given format, followed by a newline
+pub fn write(output: &mut impl Write, format: Format, position: &Snort) -> Result<()> {
+    match format {
+        Format::Graph6 => writeln!(output, "{}", graph6::to_graph6(&position.graph))?,
+        Format::Sparse6 => writeln!(output, "{}", graph6::to_sparse6(&position.graph))?,
+        Format::Matrix => writeln!(output, "{}", position.graph)?,
+        Format::Dot => writeln!(output, "{}", position.to_graphviz())?,
+        Format::Graphml => writeln!(output, "{}", position.to_graphml())?,
+        Format::Position => writeln!(output, "{}:{}", <Snort>::NAME, position)?,
+    }
+    Ok(())
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let mut input = String::new();
+    args.in_file
+        .open()
+        .context("Could not open input file")?
+        .read_to_string(&mut input)
+        .context("Could not read input file")?;
+    let mut output = args.out_file.create().context("Could not create output file")?;
+
+    for (idx, record) in records(args.from, &input).into_iter().enumerate() {
+        let position = parse(args.from, record)
+            .with_context(|| format!("Could not convert record {}", idx + 1))?;
+        let uncolored = Snort::new(position.graph.clone());
+        if !args.to.has_colors() && !args.drop_colors && position != uncolored {
+            bail!(
+                "Record {} has colored vertices or clusters, which {} cannot store. Use \
+                 --drop-colors to convert anyway",
+                idx + 1,
+                args.to.name()
+            );
+        }
+        write(&mut output, args.to, &position)?;
+    }
+    output.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,4 @@
+crate::clap_utils::mk_subcommand! {
+    Query => query,
+    Stats => stats,
+}
@@ -0,0 +1,61 @@
+use crate::database::{Database, Entry};
+use anyhow::{anyhow, Result};
+use cgt::{
+    numeric::dyadic_rational_number::DyadicRationalNumber,
+    short::partizan::position::parse_position,
+};
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug, Clone)]
+/// Look up values of positions in the database
+pub struct Args {
+    /// Path to the database file
+    #[arg(long)]
+    db: PathBuf,
+
+    /// Compute and store values of positions missing from the database
+    #[arg(long)]
+    compute: bool,
+
+    /// Positions in the `game:payload` format
+    #[arg(required = true)]
+    positions: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+struct QueryResult {
+    position: String,
+    canonical_form: Option<String>,
+    temperature: Option<DyadicRationalNumber>,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let mut database = Database::open(&args.db)?;
+
+    for input in &args.positions {
+        let position = parse_position(input)
+            .map_err(|err| anyhow!("Could not parse position '{input}': {err}"))?;
+        // Positions are stored in their normalized form, so different spellings of the same
+        // position share entries
+        let key = position.to_string();
+
+        let mut entry = database.get(&key)?;
+        if entry.is_none() && args.compute {
+            let computed = Entry::new(position.canonical_form());
+            database.insert_all([(key.as_str(), &computed)])?;
+            entry = Some(computed);
+        }
+
+        let result = QueryResult {
+            position: key,
+            canonical_form: entry
+                .as_ref()
+                .map(|entry| entry.canonical_form.to_string()),
+            temperature: entry.map(|entry| entry.temperature),
+        };
+        println!("{}", serde_json::ser::to_string(&result)?);
+    }
+
+    Ok(())
+}
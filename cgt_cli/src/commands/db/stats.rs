@@ -0,0 +1,29 @@
+use crate::database::Database;
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug, Clone)]
+/// Print number of stored positions of each game
+pub struct Args {
+    /// Path to the database file
+    #[arg(long)]
+    db: PathBuf,
+}
+
+#[derive(serde::Serialize)]
+struct GameStats {
+    game: String,
+    positions: usize,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let database = Database::open(&args.db)?;
+    for (game, positions) in database.count_by_game()? {
+        println!(
+            "{}",
+            serde_json::ser::to_string(&GameStats { game, positions })?
+        );
+    }
+    Ok(())
+}
@@ -5,4 +5,7 @@ crate::clap_utils::mk_subcommand! {
     GeneticSearch => genetic_search,
     Evaluate => evaluate,
     LatexTable => latex_table,
+    RectangleTable => rectangle_table,
+    Rectangles => rectangles,
+    Render => render,
 }
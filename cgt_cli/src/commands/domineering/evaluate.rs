@@ -4,7 +4,9 @@ use cgt::{
     drawing::svg::Svg,
     short::partizan::{
         games::domineering::Domineering, partizan_game::PartizanGame,
-        transposition_table::ParallelTranspositionTable,
+        transposition_table::{
+            CountingTranspositionTable, ParallelTranspositionTable, TranspositionTable,
+        },
     },
 };
 use clap::Parser;
@@ -23,16 +25,20 @@ pub struct Args {
     /// SVG render output path
     #[arg(long, default_value = None)]
     output_svg: Option<FileOrStdout>,
+
+    /// Print transposition table statistics
+    #[arg(long, default_value_t = false)]
+    stats: bool,
 }
 
 pub fn run(args: Args) -> Result<()> {
     let position: Domineering =
-        Domineering::from_str(&args.position).expect("Could not parse position");
+        Domineering::from_str(&args.position).context("Could not parse position")?;
 
     if let Some(ref svg_fp) = args.output_svg {
         let mut w = BufWriter::new(
             svg_fp
-                .open()
+                .create()
                 .context(format!("Could not create file '{}'", svg_fp))?,
         );
         let mut buf = String::new();
@@ -42,10 +48,13 @@ pub fn run(args: Args) -> Result<()> {
             .context(format!("Could not write to file '{}'", svg_fp))?;
     }
 
-    let tt = ParallelTranspositionTable::new();
+    let tt = CountingTranspositionTable::new(ParallelTranspositionTable::new());
     let canonical_form = position.canonical_form(&tt);
     println!("Canonical Form: {}", canonical_form);
     println!("Temperature: {}", canonical_form.temperature());
+    if args.stats {
+        println!("Transposition table: {}", tt.statistics());
+    }
 
     Ok(())
 }
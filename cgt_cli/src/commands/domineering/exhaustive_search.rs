@@ -3,9 +3,12 @@ use cgt::{
     grid::{small_bit_grid::SmallBitGrid, FiniteGrid},
     numeric::dyadic_rational_number::DyadicRationalNumber,
     short::partizan::{
-        games::domineering,
+        games::domineering::{self, Domineering},
         partizan_game::PartizanGame,
-        transposition_table::{NoTranspositionTable, ParallelTranspositionTable},
+        transposition_table::{
+            CappedTranspositionTable, CountingTranspositionTable, NoTranspositionTable,
+            ParallelTranspositionTable, TranspositionTable,
+        },
     },
 };
 use clap::{Parser, ValueEnum};
@@ -79,6 +82,14 @@ pub struct Args {
     /// Don't use transposition table of game positions and canonical forms
     #[arg(long, default_value_t = false)]
     no_transposition_table: bool,
+
+    /// Stop caching positions after transposition table reaches that many megabytes
+    #[arg(long, default_value = None)]
+    transposition_table_memory_limit: Option<usize>,
+
+    /// Print transposition table statistics after the search
+    #[arg(long, default_value_t = false)]
+    stats: bool,
 }
 
 struct ProgressTracker {
@@ -132,12 +143,6 @@ pub fn run(args: Args) -> Result<()> {
         );
     }
 
-    let transposition_table = if args.no_transposition_table {
-        None
-    } else {
-        Some(ParallelTranspositionTable::new())
-    };
-
     let output_file =
         File::create(&args.output_path).with_context(|| "Could not open output file")?;
     let progress_tracker = Arc::new(ProgressTracker::new(args, output_file));
@@ -150,6 +155,42 @@ pub fn run(args: Args) -> Result<()> {
         None
     };
 
+    if progress_tracker.args.no_transposition_table {
+        search(&progress_tracker, last_id, &NoTranspositionTable::new());
+    } else {
+        let transposition_table =
+            if let Some(megabytes) = progress_tracker.args.transposition_table_memory_limit {
+                CappedTranspositionTable::with_memory_budget(
+                    ParallelTranspositionTable::new(),
+                    megabytes * 1024 * 1024,
+                )
+            } else {
+                CappedTranspositionTable::new(ParallelTranspositionTable::new(), usize::MAX)
+            };
+
+        if progress_tracker.args.stats {
+            let transposition_table = CountingTranspositionTable::new(transposition_table);
+            search(&progress_tracker, last_id, &transposition_table);
+            eprintln!(
+                "Transposition table: {}",
+                transposition_table.statistics()
+            );
+        } else {
+            search(&progress_tracker, last_id, &transposition_table);
+        }
+    }
+
+    if let Some(pid) = progress_pid {
+        pid.join().unwrap()
+    }
+
+    Ok(())
+}
+
+fn search<TT>(progress_tracker: &ProgressTracker, last_id: u64, transposition_table: &TT)
+where
+    TT: TranspositionTable<Domineering> + Sync,
+{
     (progress_tracker.args.start_id..last_id)
         .into_par_iter()
         .for_each(|i| {
@@ -190,14 +231,7 @@ pub fn run(args: Args) -> Result<()> {
 
             let thermograph = match progress_tracker.args.thermograph_method {
                 ThermographMethod::CanonicalForm => {
-                    let canonical_form = {
-                        if let Some(ref transposition_table) = transposition_table {
-                            grid.canonical_form(transposition_table)
-                        } else {
-                            grid.canonical_form(&NoTranspositionTable::new())
-                        }
-                    };
-                    canonical_form.thermograph()
+                    grid.canonical_form(transposition_table).thermograph()
                 }
                 ThermographMethod::Direct => grid.thermograph_direct(),
             };
@@ -225,11 +259,6 @@ pub fn run(args: Args) -> Result<()> {
                 }
             }
         });
-    if let Some(pid) = progress_pid {
-        pid.join().unwrap()
-    }
-
-    Ok(())
 }
 
 /// Zero pad `to_pad` to the length of `max_size`
@@ -59,7 +59,7 @@ struct DomineeringHighTemperature {
 }
 
 impl Algorithm<Domineering, DyadicRationalNumber> for DomineeringHighTemperature {
-    fn mutate(&self, object: &mut Domineering, rng: &mut rand::rngs::ThreadRng) {
+    fn mutate(&self, object: &mut Domineering, rng: &mut rand::rngs::StdRng) {
         for y in 0..object.grid().height() {
             for x in 0..object.grid().width() {
                 if rng.gen::<f32>() <= self.mutation_rate {
@@ -78,7 +78,7 @@ impl Algorithm<Domineering, DyadicRationalNumber> for DomineeringHighTemperature
         &self,
         lhs: &Domineering,
         rhs: &Domineering,
-        rng: &mut rand::rngs::ThreadRng,
+        rng: &mut rand::rngs::StdRng,
     ) -> Domineering {
         let mid_point = rng.gen_range(0..(lhs.grid().height() * lhs.grid().width()));
 
@@ -112,7 +112,7 @@ impl Algorithm<Domineering, DyadicRationalNumber> for DomineeringHighTemperature
         }
     }
 
-    fn random(&self, rng: &mut rand::rngs::ThreadRng) -> Domineering {
+    fn random(&self, rng: &mut rand::rngs::StdRng) -> Domineering {
         let mut new =
             Domineering::new(SmallBitGrid::empty(self.grid_width, self.grid_height).unwrap());
 
@@ -138,9 +138,8 @@ pub fn run(args: Args) -> Result<()> {
     };
 
     let specimen = if let Some(seed_input) = args.seed {
-        let pos: Domineering = Domineering::from_str(&seed_input)
-            .ok()
-            .context("Could not parse seed position")?;
+        let pos: Domineering =
+            Domineering::from_str(&seed_input).context("Could not parse seed position")?;
 
         if pos.grid().width() != args.width {
             bail!(
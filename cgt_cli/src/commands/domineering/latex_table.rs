@@ -20,9 +20,7 @@ impl DomineeringEntry {
             temperature: Rational::from_str(&result.temperature)
                 .ok()
                 .context("Invalid temperature")?,
-            grid: domineering::Domineering::from_str(&result.grid)
-                .ok()
-                .context("Invalid grid")?,
+            grid: domineering::Domineering::from_str(&result.grid).context("Invalid grid")?,
         })
     }
 }
@@ -85,11 +83,11 @@ pub fn run(args: Args) -> Result<()> {
         let mut input_without_rotations = input.iter().cloned().map(Some).collect::<Vec<_>>();
         for (idx, entry) in input.iter().enumerate() {
             let grid = *entry.grid.grid();
-            let rot_90deg = grid.rotate();
-            let rot_180deg = rot_90deg.rotate();
-            let rot_270deg = rot_180deg.rotate();
-            let vertical_flip = grid.vertical_flip();
-            let horizontal_flip = grid.horizontal_flip();
+            let rot_90deg = grid.rotate90();
+            let rot_180deg = rot_90deg.rotate90();
+            let rot_270deg = rot_180deg.rotate90();
+            let vertical_flip = grid.flip_vertical();
+            let horizontal_flip = grid.flip_horizontal();
             let equivalent_grids = [
                 rot_90deg,
                 rot_180deg,
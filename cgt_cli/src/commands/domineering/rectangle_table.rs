@@ -0,0 +1,49 @@
+use crate::io::FileOrStdout;
+use anyhow::{Context, Result};
+use cgt::short::partizan::{
+    games::domineering_table::RectangleTable, transposition_table::ParallelTranspositionTable,
+};
+use clap::{Parser, ValueEnum};
+use std::io::{BufWriter, Write};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Packed binary format, as bundled in the library
+    #[default]
+    Packed,
+
+    /// One rectangle per line as `width height canonical_form`
+    Text,
+}
+
+/// Compute canonical forms of empty domineering rectangles, e.g. to rebuild the bundled table
+/// at `cgt/short/partizan/games/domineering_table.bin`
+#[derive(Parser, Debug)]
+pub struct Args {
+    /// Largest side length of rectangles in the table
+    #[arg(long, default_value_t = 5)]
+    max_size: u8,
+
+    /// Format of the table
+    #[arg(long, value_enum, default_value_t)]
+    format: Format,
+
+    /// Output file with the table. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let table = RectangleTable::generate(args.max_size, &ParallelTranspositionTable::new());
+    let mut output = BufWriter::new(
+        args.out_file
+            .create()
+            .context("Could not create output file")?,
+    );
+    match args.format {
+        Format::Packed => output.write_all(&table.to_packed()),
+        Format::Text => write!(output, "{table}"),
+    }
+    .context("Could not write the table")?;
+    Ok(())
+}
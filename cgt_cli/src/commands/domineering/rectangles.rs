@@ -0,0 +1,73 @@
+use crate::io::FileOrStdout;
+use anyhow::{Context, Result};
+use cgt::{
+    numeric::dyadic_rational_number::DyadicRationalNumber,
+    short::partizan::{
+        games::domineering::Domineering, partizan_game::PartizanGame,
+        transposition_table::ParallelTranspositionTable,
+    },
+};
+use clap::Parser;
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use std::io::{BufWriter, Write};
+
+/// Evaluate all empty domineering rectangles up to given size
+#[derive(Parser, Debug)]
+pub struct Args {
+    /// Largest width of rectangles
+    #[arg(long)]
+    max_width: u8,
+
+    /// Largest height of rectangles
+    #[arg(long)]
+    max_height: u8,
+
+    /// Output file with newline-separated JSON results. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+}
+
+#[derive(serde::Serialize)]
+struct RectangleResult {
+    width: u8,
+    height: u8,
+    canonical_form: String,
+    temperature: DyadicRationalNumber,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let rectangles = (1..=args.max_height)
+        .flat_map(|height| (1..=args.max_width).map(move |width| (width, height)))
+        .map(|(width, height)| {
+            Domineering::with_holes(width, height, &[])
+                .map(|position| (width, height, position))
+                .with_context(|| format!("Rectangle {width}x{height} is too large"))
+        })
+        .collect::<Result<Vec<(u8, u8, Domineering)>>>()?;
+
+    let transposition_table = ParallelTranspositionTable::new();
+    let results = rectangles
+        .into_par_iter()
+        .map(|(width, height, position)| {
+            let canonical_form = position.canonical_form(&transposition_table);
+            RectangleResult {
+                width,
+                height,
+                temperature: canonical_form.temperature(),
+                canonical_form: canonical_form.to_string(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut output = BufWriter::new(
+        args.out_file
+            .create()
+            .context("Could not create output file")?,
+    );
+    for result in results {
+        writeln!(output, "{}", serde_json::ser::to_string(&result)?)?;
+    }
+    output.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,356 @@
+use anyhow::{anyhow, bail, Context, Result};
+use cgt::{
+    numeric::{dyadic_rational_number::DyadicRationalNumber, rational::Rational},
+    short::partizan::{canonical_form::CanonicalForm, position::parse_position},
+};
+use clap::Parser;
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt::{self, Display},
+    io::{stdin, stdout, BufRead, IsTerminal, Write},
+    str::FromStr,
+};
+
+/// Evaluate expressions on games, interactively if no expressions are given
+///
+/// Values are written in brace notation (e.g. `{2|-1}`, `^*`, `3/2`) or as positions in the
+/// `game:payload` format in double quotes (e.g. `"domineering:..|.."`). Supported are sums
+/// (`G + H`), differences (`G - H`), negation (`-G`), comparisons (`==`, `<=`, `>=`, `<`, `>`,
+/// `<>` for confused), variables (`g = {1|-1}`) and functions `temperature`, `mean`,
+/// `leftstop`, `rightstop`, `thermograph` and `outcome`.
+#[derive(Parser, Debug, Clone)]
+pub struct Args {
+    /// Expressions to evaluate, in order. Read expressions from stdin if none are given
+    #[arg(allow_hyphen_values = true)]
+    expressions: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+enum Value {
+    Game(CanonicalForm),
+    Bool(bool),
+    Text(String),
+}
+
+impl Value {
+    fn into_game(self) -> Result<CanonicalForm> {
+        match self {
+            Self::Game(game) => Ok(game),
+            Self::Bool(_) | Self::Text(_) => bail!("Expected a game, got '{self}'"),
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Game(game) => write!(f, "{game}"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Equal,
+    LessOrEqual,
+    GreaterOrEqual,
+    Less,
+    Greater,
+    Confused,
+}
+
+impl Comparison {
+    // Longer operators first, so `<=` is not parsed as `<`
+    const OPERATORS: [(&'static str, Self); 6] = [
+        ("==", Self::Equal),
+        ("<=", Self::LessOrEqual),
+        (">=", Self::GreaterOrEqual),
+        ("<>", Self::Confused),
+        ("<", Self::Less),
+        (">", Self::Greater),
+    ];
+
+    fn holds(self, lhs: &CanonicalForm, rhs: &CanonicalForm) -> bool {
+        let ordering = CanonicalForm::compare(lhs, rhs);
+        match self {
+            Self::Equal => ordering == Some(Ordering::Equal),
+            Self::LessOrEqual => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+            Self::GreaterOrEqual => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+            Self::Less => ordering == Some(Ordering::Less),
+            Self::Greater => ordering == Some(Ordering::Greater),
+            Self::Confused => ordering.is_none(),
+        }
+    }
+}
+
+/// Environment of the REPL with values of variables
+#[derive(Debug, Default)]
+struct Environment {
+    variables: HashMap<String, CanonicalForm>,
+}
+
+impl Environment {
+    /// Evaluate a line, either an assignment `name = expression` or an expression
+    fn eval_line(&mut self, line: &str) -> Result<Option<Value>> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(None);
+        }
+
+        if let Some((name, expression)) = split_assignment(line) {
+            let value = self.eval(expression)?.into_game()?;
+            self.variables.insert(name.to_owned(), value.clone());
+            return Ok(Some(Value::Game(value)));
+        }
+
+        self.eval(line).map(Some)
+    }
+
+    fn eval(&self, input: &str) -> Result<Value> {
+        let mut expression = Expression {
+            input,
+            environment: self,
+        };
+        let value = expression.comparison()?;
+        expression.skip_whitespace();
+        if !expression.input.is_empty() {
+            bail!("Unexpected input: '{}'", expression.input);
+        }
+        Ok(value)
+    }
+}
+
+/// Split `name = expression`, not confusing it with `==`, `<=` or `>=`
+fn split_assignment(line: &str) -> Option<(&str, &str)> {
+    let (name, expression) = line.split_once('=')?;
+    let name = name.trim();
+    let is_identifier = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    (is_identifier && !expression.starts_with('=')).then_some((name, expression))
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Recursive descent parser evaluating expressions as it goes
+///
+/// ```text
+/// comparison = sum (operator sum)?
+/// sum = unary (('+' | '-') unary)*
+/// unary = '-' unary | atom
+/// atom = '(' comparison ')' | function '(' comparison ')' | '"' position '"' | game | variable
+/// ```
+struct Expression<'a> {
+    input: &'a str,
+    environment: &'a Environment,
+}
+
+impl Expression<'_> {
+    fn skip_whitespace(&mut self) {
+        self.input = self.input.trim_start();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if let Some(rest) = self.input.strip_prefix(token) {
+            self.input = rest;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<()> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            bail!("Expected '{token}' at '{}'", self.input)
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Value> {
+        let lhs = self.sum()?;
+        self.skip_whitespace();
+        let Some(&(operator, comparison)) = Comparison::OPERATORS
+            .iter()
+            .find(|(operator, _)| self.input.starts_with(operator))
+        else {
+            return Ok(lhs);
+        };
+        self.input = &self.input[operator.len()..];
+        let rhs = self.sum()?;
+        Ok(Value::Bool(
+            comparison.holds(&lhs.into_game()?, &rhs.into_game()?),
+        ))
+    }
+
+    fn sum(&mut self) -> Result<Value> {
+        let mut value = self.unary()?;
+        loop {
+            let rhs = if self.eat("+") {
+                self.unary()?.into_game()?
+            } else if self.eat("-") {
+                negative(&self.unary()?.into_game()?)?
+            } else {
+                return Ok(value);
+            };
+            let lhs = value.into_game()?;
+            value = Value::Game(
+                CanonicalForm::checked_sum(&lhs, &rhs)
+                    .with_context(|| format!("Sum of '{lhs}' and '{rhs}' overflowed"))?,
+            );
+        }
+    }
+
+    fn unary(&mut self) -> Result<Value> {
+        if self.eat("-") {
+            return Ok(Value::Game(negative(&self.unary()?.into_game()?)?));
+        }
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Result<Value> {
+        self.skip_whitespace();
+
+        if self.eat("(") {
+            let value = self.comparison()?;
+            self.expect(")")?;
+            return Ok(value);
+        }
+
+        if self.eat("\"") {
+            let (position, rest) = self
+                .input
+                .split_once('"')
+                .context("Unterminated position string")?;
+            self.input = rest;
+            let position = parse_position(position)
+                .map_err(|err| anyhow!("Could not parse position '{position}': {err}"))?;
+            return Ok(Value::Game(position.canonical_form()));
+        }
+
+        // Games like `v` or `v*` look like identifiers, so they are tried first, unless followed
+        // by more identifier characters
+        if let Ok((rest, game)) = CanonicalForm::parse(self.input) {
+            if !rest.starts_with(is_identifier_char) {
+                self.input = rest;
+                return Ok(Value::Game(game));
+            }
+        }
+
+        // Identifiers cannot start with a digit, so this is a number that is not a valid game
+        if self.input.starts_with(|c: char| c.is_ascii_digit()) {
+            let length = self
+                .input
+                .find(|c: char| !c.is_ascii_digit() && c != '/')
+                .unwrap_or(self.input.len());
+            let number = &self.input[..length];
+            match Rational::from_str(number) {
+                Ok(rational) if DyadicRationalNumber::from_rational(rational).is_none() => {
+                    bail!("'{number}' is not dyadic, its denominator must be a power of two")
+                }
+                Ok(_) => bail!("Unexpected input: '{}'", self.input),
+                Err(_) => bail!("Could not parse number '{number}'"),
+            }
+        }
+
+        let length = self
+            .input
+            .find(|c| !is_identifier_char(c))
+            .unwrap_or(self.input.len());
+        if self.input.is_empty() {
+            bail!("Unexpected end of input");
+        }
+        if length == 0 {
+            bail!("Unexpected input: '{}'", self.input);
+        }
+        let (identifier, rest) = self.input.split_at(length);
+        self.input = rest;
+
+        if self.eat("(") {
+            let argument = self.comparison()?.into_game()?;
+            self.expect(")")?;
+            return Self::call(identifier, &argument);
+        }
+
+        self.environment
+            .variables
+            .get(identifier)
+            .cloned()
+            .map(Value::Game)
+            .with_context(|| format!("Unknown variable '{identifier}'"))
+    }
+
+    fn call(function: &str, game: &CanonicalForm) -> Result<Value> {
+        if matches!(function, "temperature" | "mean" | "thermograph")
+            && game.checked_thermograph().is_none()
+        {
+            bail!("Thermograph of '{game}' overflowed");
+        }
+
+        let value = match function {
+            "temperature" => Value::Game(CanonicalForm::new_dyadic(game.temperature())),
+            "mean" => Value::Game(CanonicalForm::new_dyadic(game.mean())),
+            "leftstop" => Value::Game(CanonicalForm::new_dyadic(game.left_stop())),
+            "rightstop" => Value::Game(CanonicalForm::new_dyadic(game.right_stop())),
+            "thermograph" => Value::Text(game.thermograph().to_string()),
+            "outcome" => {
+                let outcome = match game.partial_cmp(&0) {
+                    Some(Ordering::Greater) => "L",
+                    Some(Ordering::Less) => "R",
+                    Some(Ordering::Equal) => "P",
+                    None => "N",
+                };
+                Value::Text(outcome.to_owned())
+            }
+            _ => bail!("Unknown function '{function}'"),
+        };
+        Ok(value)
+    }
+}
+
+fn negative(game: &CanonicalForm) -> Result<CanonicalForm> {
+    game.checked_negative()
+        .with_context(|| format!("Negative of '{game}' overflowed"))
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let mut environment = Environment::default();
+    let mut output = stdout();
+
+    if !args.expressions.is_empty() {
+        for expression in &args.expressions {
+            if let Some(value) = environment.eval_line(expression)? {
+                writeln!(output, "{value}")?;
+            }
+        }
+        return Ok(());
+    }
+
+    let interactive = stdin().is_terminal();
+    let mut lines = stdin().lock().lines();
+    loop {
+        if interactive {
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+        let Some(line) = lines.next() else {
+            break;
+        };
+        match environment.eval_line(&line?) {
+            Ok(Some(value)) => writeln!(output, "{value}")?,
+            Ok(None) => {}
+            Err(err) if interactive => eprintln!("Error: {err}"),
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,257 @@
+use crate::{
+    commands::logs::{self, csv_field},
+    encoding,
+    io::{FileOrStdin, FileOrStdout},
+};
+use anyhow::{anyhow, bail, Context, Result};
+use cgt::{
+    graph::{adjacency_matrix::undirected::UndirectedGraph, graph6, Graph},
+    numeric::rational::Rational,
+};
+use clap::{Parser, ValueEnum};
+use parquet::{
+    data_type::{ByteArray, ByteArrayType, DataType, DoubleType, Int64Type},
+    file::{
+        properties::WriterProperties,
+        writer::{SerializedFileWriter, SerializedRowGroupWriter},
+    },
+    schema::parser::parse_message_type,
+};
+use serde_json::Value;
+use std::{io::Write, str::FromStr, sync::Arc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Comma-separated values with a header
+    Csv,
+
+    /// Apache Parquet file with a single row group
+    Parquet,
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Flatten results of searches, censuses and logs into a table for pandas, R or spreadsheets
+///
+/// Every record with a `position` or `graph6` field becomes a row, and members of Pareto fronts
+/// in logs become a row each. Columns are `id` (number of the row), `position`, `graph6`,
+/// `vertices`, `degree`, `temperature`, `value` (canonical form) and `fitness`, empty when
+/// a record does not have them. Vertices and degree are computed from Snort positions and
+/// graphs when not recorded. Temperatures and fitness are written as floats.
+pub struct Args {
+    /// Input file with records in any encoding, e.g. output of `cgt-cli snort census`,
+    /// `cgt-cli search` or a search log. Use '-' for stdin
+    #[arg(long, default_value = "-")]
+    in_file: FileOrStdin,
+
+    /// Output file. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+
+    /// Format of the output
+    #[arg(long, value_enum, default_value_t = Format::Csv)]
+    format: Format,
+}
+
+const PARQUET_SCHEMA: &str = "
+message result {
+    required int64 id;
+    optional binary position (UTF8);
+    optional binary graph6 (UTF8);
+    optional int64 vertices;
+    optional int64 degree;
+    optional double temperature;
+    optional binary value (UTF8);
+    optional double fitness;
+}
+";
+
+struct Row {
+    id: i64,
+    position: Option<String>,
+    graph6: Option<String>,
+    vertices: Option<i64>,
+    degree: Option<i64>,
+    temperature: Option<f64>,
+    value: Option<String>,
+    fitness: Option<f64>,
+}
+
+fn string(record: &Value, field: &str) -> Option<String> {
+    record.get(field)?.as_str().map(str::to_owned)
+}
+
+fn integer(record: &Value, field: &str) -> Option<i64> {
+    record.get(field)?.as_i64()
+}
+
+/// Number written as a JSON number or an exact string, e.g. `"3/2"` or `"-∞"`
+fn number(record: &Value, field: &str) -> Result<Option<f64>> {
+    match record.get(field) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Number(number)) => Ok(number.as_f64()),
+        Some(Value::String(number)) => Rational::from_str(number)
+            .map(|number| Some(f64::from(number)))
+            .map_err(|err| anyhow!("Invalid {field} '{number}': {err}")),
+        Some(other) => bail!("Invalid {field} '{other}'"),
+    }
+}
+
+/// Number of vertices and degree of the underlying graph, if the record is a graph or a Snort
+/// position
+fn graph_shape(position: Option<&str>, graph6: Option<&str>) -> Result<Option<(usize, usize)>> {
+    if let Some(position) = position {
+        if position.starts_with("snort:") {
+            let position = logs::parse_snort(position)?;
+            return Ok(Some((position.graph.size(), position.degree())));
+        }
+    }
+    if let Some(graph6) = graph6 {
+        let graph = graph6::from_graph6::<UndirectedGraph>(graph6)
+            .with_context(|| format!("Invalid graph6 '{graph6}'"))?;
+        return Ok(Some((graph.size(), graph.degree())));
+    }
+    Ok(None)
+}
+
+fn row(id: i64, record: &Value) -> Result<Row> {
+    let position = string(record, "position");
+    let graph6 = string(record, "graph6");
+    let mut vertices = integer(record, "vertices");
+    let mut degree = integer(record, "degree");
+    if vertices.is_none() || degree.is_none() {
+        if let Some((size, max_degree)) = graph_shape(position.as_deref(), graph6.as_deref())? {
+            vertices = vertices.or(Some(size as i64));
+            degree = degree.or(Some(max_degree as i64));
+        }
+    }
+    Ok(Row {
+        id,
+        position,
+        graph6,
+        vertices,
+        degree,
+        temperature: number(record, "temperature")?,
+        value: string(record, "canonical_form"),
+        fitness: match number(record, "fitness")? {
+            Some(fitness) => Some(fitness),
+            None => number(record, "score")?,
+        },
+    })
+}
+
+/// Records of positions in `record`, i.e. itself or members of a Pareto front
+fn positions(record: &Value) -> Vec<&Value> {
+    match record.get("kind").and_then(Value::as_str) {
+        Some("pareto_front") => record
+            .get("front")
+            .and_then(Value::as_array)
+            .map(|front| front.iter().collect())
+            .unwrap_or_default(),
+        _ if record.get("position").is_some() || record.get("graph6").is_some() => vec![record],
+        _ => Vec::new(),
+    }
+}
+
+fn write_csv(output: &mut impl Write, rows: &[Row]) -> Result<()> {
+    fn field<T: ToString>(value: &Option<T>) -> String {
+        value
+            .as_ref()
+            .map_or_else(String::new, |value| csv_field(&value.to_string()))
+    }
+
+    writeln!(
+        output,
+        "id,position,graph6,vertices,degree,temperature,value,fitness"
+    )?;
+    for row in rows {
+        writeln!(
+            output,
+            "{},{},{},{},{},{},{},{}",
+            row.id,
+            field(&row.position),
+            field(&row.graph6),
+            field(&row.vertices),
+            field(&row.degree),
+            field(&row.temperature),
+            field(&row.value),
+            field(&row.fitness),
+        )?;
+    }
+    Ok(())
+}
+
+/// Write next column of optional values
+fn write_column<T, W>(
+    row_group: &mut SerializedRowGroupWriter<'_, W>,
+    values: impl Iterator<Item = Option<T::T>>,
+) -> Result<()>
+where
+    T: DataType,
+    W: Write + Send,
+{
+    let mut column = row_group
+        .next_column()?
+        .context("unreachable: schema has all columns")?;
+    let mut present = Vec::new();
+    let mut levels = Vec::new();
+    for value in values {
+        levels.push(i16::from(value.is_some()));
+        present.extend(value);
+    }
+    column.typed::<T>().write_batch(&present, Some(&levels), None)?;
+    column.close()?;
+    Ok(())
+}
+
+fn write_parquet(output: impl Write + Send, rows: &[Row]) -> Result<()> {
+    let utf8 = |value: &Option<String>| value.as_deref().map(ByteArray::from);
+
+    let schema = Arc::new(parse_message_type(PARQUET_SCHEMA)?);
+    let properties = Arc::new(WriterProperties::builder().build());
+    let mut writer = SerializedFileWriter::new(output, schema, properties)?;
+    let mut row_group = writer.next_row_group()?;
+
+    let mut ids = row_group
+        .next_column()?
+        .context("unreachable: schema has all columns")?;
+    let values = rows.iter().map(|row| row.id).collect::<Vec<_>>();
+    ids.typed::<Int64Type>().write_batch(&values, None, None)?;
+    ids.close()?;
+
+    write_column::<ByteArrayType, _>(&mut row_group, rows.iter().map(|row| utf8(&row.position)))?;
+    write_column::<ByteArrayType, _>(&mut row_group, rows.iter().map(|row| utf8(&row.graph6)))?;
+    write_column::<Int64Type, _>(&mut row_group, rows.iter().map(|row| row.vertices))?;
+    write_column::<Int64Type, _>(&mut row_group, rows.iter().map(|row| row.degree))?;
+    write_column::<DoubleType, _>(&mut row_group, rows.iter().map(|row| row.temperature))?;
+    write_column::<ByteArrayType, _>(&mut row_group, rows.iter().map(|row| utf8(&row.value)))?;
+    write_column::<DoubleType, _>(&mut row_group, rows.iter().map(|row| row.fitness))?;
+
+    row_group.close()?;
+    writer.close()?;
+    Ok(())
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let input = args.in_file.open().context("Could not open input file")?;
+    let mut records = 0;
+    let mut rows = Vec::new();
+    for (idx, record) in encoding::read_values(input)?.enumerate() {
+        let record = record?;
+        records += 1;
+        for position in positions(&record) {
+            let id = rows.len() as i64 + 1;
+            rows.push(row(id, position).with_context(|| format!("Invalid record {}", idx + 1))?);
+        }
+    }
+
+    let mut output = args.out_file.create().context("Could not create output file")?;
+    match args.format {
+        Format::Csv => write_csv(&mut output, &rows)?,
+        Format::Parquet => write_parquet(&mut output, &rows)?,
+    }
+    output.flush()?;
+
+    eprintln!("Exported {} rows from {records} records", rows.len());
+
+    Ok(())
+}
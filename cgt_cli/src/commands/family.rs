@@ -0,0 +1,6 @@
+mod dsl;
+
+crate::clap_utils::mk_subcommand! {
+    List => list,
+    Search => search,
+}
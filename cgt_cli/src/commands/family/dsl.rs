@@ -0,0 +1,325 @@
+//! Definitions of position families given by integer parameters
+//!
+//! Every definition is a single line with the family name, parameters with their inclusive
+//! (`lo..=hi`) or exclusive (`lo..hi`) ranges, and a [Rhai](https://rhai.rs) expression building
+//! the position from the parameters. Comment lines directly above a definition describe it.
+//!
+//! ```text
+//! # Path of three vertices with a, b and c leaves
+//! caterpillar(a = 0..=6, b = 0..=6, c = 0..=6) = caterpillar([a, b, c])
+//! ```
+//!
+//! Expressions can use graph constructors `path(n)`, `cycle(n)`, `star(leaves)`, `complete(n)`,
+//! `complete_bipartite(a, b)`, `grid(width, height)`, `caterpillar([legs])` and
+//! `spider([legs])`, disjoint union of graphs `g + h` and `connect(g, u, v)` adding an edge.
+//! Graphs are played as Snort. `domineering(width, height)` is an empty Domineering board, and
+//! any string in the `game:payload` format is a position of that game.
+
+use anyhow::{anyhow, bail, Context, Result};
+use cgt::{
+    graph::{adjacency_matrix::undirected::UndirectedGraph, generators, Graph, VertexIndex},
+    short::partizan::{
+        games::snort::Snort,
+        position::{parse_position, AnyPosition},
+    },
+};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, ImmutableString, Scope, AST};
+use std::{
+    fmt::{self, Display},
+    fs,
+    path::Path,
+};
+
+/// Families available without a family file
+pub const BUILTIN: &str = "\
+# Path of three vertices with a, b and c leaves, the caterpillar C(a, b, c)
+caterpillar(a = 0..=6, b = 0..=6, c = 0..=6) = caterpillar([a, b, c])
+# Generalized star, a center with paths of a, b and c vertices
+spider(a = 1..=5, b = 1..=5, c = 1..=5) = spider([a, b, c])
+# Grid graph
+grid(width = 1..=4, height = 1..=3) = grid(width, height)
+# Complete bipartite graph K(a, b)
+complete-bipartite(a = 1..=6, b = 1..=6) = complete_bipartite(a, b)
+# Empty Domineering board
+domineering(width = 1..=5, height = 1..=5) = domineering(width, height)
+";
+
+/// Parameter of a family with its inclusive range
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameter {
+    pub name: String,
+    pub min: i64,
+    pub max: i64,
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {}..={}", self.name, self.min, self.max)
+    }
+}
+
+/// Position of a family with given parameters
+pub struct Instance {
+    pub position: AnyPosition,
+
+    /// Graph of positions given as graphs, i.e. Snort positions
+    pub graph: Option<UndirectedGraph>,
+}
+
+/// Family of positions, see the [module documentation](self)
+pub struct Family {
+    pub name: String,
+    pub description: String,
+    pub parameters: Vec<Parameter>,
+    body: String,
+    engine: Engine,
+    ast: AST,
+}
+
+impl Display for Family {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.description.lines() {
+            writeln!(f, "# {line}")?;
+        }
+        write!(f, "{}(", self.name)?;
+        for (idx, parameter) in self.parameters.iter().enumerate() {
+            if idx != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{parameter}")?;
+        }
+        write!(f, ") = {}", self.body)
+    }
+}
+
+impl Family {
+    /// Build the position with parameter values in order of [`Self::parameters`]
+    pub fn instantiate(&self, values: &[i64]) -> Result<Instance> {
+        let mut scope = Scope::new();
+        for (parameter, value) in self.parameters.iter().zip(values) {
+            scope.push_constant(parameter.name.as_str(), *value);
+        }
+        let result = self
+            .engine
+            .eval_ast_with_scope::<Dynamic>(&mut scope, &self.ast)
+            .map_err(|err| anyhow!("Family '{}' failed: {err}", self.name))?;
+
+        if let Some(graph) = result.clone().try_cast::<UndirectedGraph>() {
+            Ok(Instance {
+                position: AnyPosition::new(Snort::new(graph.clone())),
+                graph: Some(graph),
+            })
+        } else if let Some(position) = result.clone().try_cast::<ImmutableString>() {
+            Ok(Instance {
+                position: parse_position(&position)
+                    .with_context(|| format!("Invalid position '{position}'"))?,
+                graph: None,
+            })
+        } else {
+            bail!(
+                "Family '{}' returned {}, expected a graph or a position",
+                self.name,
+                result.type_name()
+            )
+        }
+    }
+}
+
+/// Built-in families followed by families defined in `file`, which replace built-in families of
+/// the same name
+pub fn load(file: Option<&Path>) -> Result<Vec<Family>> {
+    let mut families = parse(BUILTIN).expect("unreachable: built-in families are valid");
+    if let Some(file) = file {
+        let source = fs::read_to_string(file)
+            .with_context(|| format!("Could not read family file '{}'", file.display()))?;
+        for family in parse(&source)
+            .with_context(|| format!("Could not parse family file '{}'", file.display()))?
+        {
+            families.retain(|builtin| builtin.name != family.name);
+            families.push(family);
+        }
+    }
+    Ok(families)
+}
+
+/// Parse family definitions, skipping empty lines
+pub fn parse(source: &str) -> Result<Vec<Family>> {
+    let mut families = Vec::new();
+    let mut description = Vec::new();
+    for (idx, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            description.clear();
+        } else if let Some(comment) = line.strip_prefix('#') {
+            description.push(comment.trim());
+        } else {
+            let family = parse_definition(line, description.join("\n"))
+                .with_context(|| format!("Invalid family definition at line {}", idx + 1))?;
+            families.push(family);
+            description.clear();
+        }
+    }
+    Ok(families)
+}
+
+fn parse_definition(line: &str, description: String) -> Result<Family> {
+    let (name, rest) = line.split_once('(').context("Expected '(' after family name")?;
+    let name = name.trim();
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        bail!("Invalid family name '{name}'");
+    }
+
+    let (parameters, body) = rest.split_once(')').context("Expected ')' after parameters")?;
+    let parameters = parameters
+        .split(',')
+        .map(str::trim)
+        .filter(|parameter| !parameter.is_empty())
+        .map(parse_parameter)
+        .collect::<Result<Vec<_>>>()?;
+    if parameters.is_empty() {
+        bail!("Family '{name}' has no parameters");
+    }
+    for (idx, parameter) in parameters.iter().enumerate() {
+        if parameters[..idx].iter().any(|other| other.name == parameter.name) {
+            bail!("Parameter '{}' is defined twice", parameter.name);
+        }
+    }
+
+    let body = body
+        .trim()
+        .strip_prefix('=')
+        .context("Expected '=' before family expression")?
+        .trim();
+    let engine = engine();
+    let ast = engine
+        .compile(body)
+        .map_err(|err| anyhow!("Could not compile family '{name}': {err}"))?;
+
+    Ok(Family {
+        name: name.to_owned(),
+        description,
+        parameters,
+        body: body.to_owned(),
+        engine,
+        ast,
+    })
+}
+
+fn parse_parameter(parameter: &str) -> Result<Parameter> {
+    let (name, range) = parameter
+        .split_once('=')
+        .with_context(|| format!("Expected 'name = range' in parameter '{parameter}'"))?;
+    let name = name.trim();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        bail!("Invalid parameter name '{name}'");
+    }
+
+    let range = range.trim();
+    let (min, max, inclusive) = if let Some((min, max)) = range.split_once("..=") {
+        (min, max, true)
+    } else if let Some((min, max)) = range.split_once("..") {
+        (min, max, false)
+    } else {
+        bail!("Expected range 'lo..=hi' or 'lo..hi' of parameter '{name}'");
+    };
+    let bound = |bound: &str| {
+        bound
+            .trim()
+            .parse::<i64>()
+            .with_context(|| format!("Invalid bound '{}' of parameter '{name}'", bound.trim()))
+    };
+    let min = bound(min)?;
+    let max = if inclusive { bound(max)? } else { bound(max)? - 1 };
+    if min > max {
+        bail!("Range of parameter '{name}' is empty");
+    }
+
+    Ok(Parameter {
+        name: name.to_owned(),
+        min,
+        max,
+    })
+}
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+fn count(value: i64) -> ScriptResult<usize> {
+    usize::try_from(value).map_err(|_| format!("Expected non-negative number, got {value}").into())
+}
+
+fn counts(values: &Array) -> ScriptResult<Vec<usize>> {
+    values
+        .iter()
+        .map(|value| {
+            value
+                .as_int()
+                .map_err(|typ| format!("Expected integer, got {typ}").into())
+                .and_then(count)
+        })
+        .collect()
+}
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.register_type_with_name::<UndirectedGraph>("Graph");
+    engine.register_fn("path", |n: i64| -> ScriptResult<UndirectedGraph> {
+        Ok(generators::path(count(n)?))
+    });
+    engine.register_fn("cycle", |n: i64| -> ScriptResult<UndirectedGraph> {
+        Ok(generators::cycle(count(n)?))
+    });
+    engine.register_fn("star", |leaves: i64| -> ScriptResult<UndirectedGraph> {
+        Ok(generators::star(count(leaves)?))
+    });
+    engine.register_fn("complete", |n: i64| -> ScriptResult<UndirectedGraph> {
+        Ok(generators::complete(count(n)?))
+    });
+    engine.register_fn(
+        "complete_bipartite",
+        |a: i64, b: i64| -> ScriptResult<UndirectedGraph> {
+            Ok(generators::complete_bipartite(count(a)?, count(b)?))
+        },
+    );
+    engine.register_fn(
+        "grid",
+        |width: i64, height: i64| -> ScriptResult<UndirectedGraph> {
+            Ok(generators::grid(count(width)?, count(height)?))
+        },
+    );
+    engine.register_fn("caterpillar", |legs: Array| -> ScriptResult<UndirectedGraph> {
+        Ok(generators::caterpillar(&counts(&legs)?))
+    });
+    engine.register_fn("spider", |legs: Array| -> ScriptResult<UndirectedGraph> {
+        Ok(generators::spider(&counts(&legs)?))
+    });
+    engine.register_fn("+", |lhs: UndirectedGraph, rhs: UndirectedGraph| lhs + rhs);
+    engine.register_fn(
+        "connect",
+        |mut graph: UndirectedGraph, u: i64, v: i64| -> ScriptResult<UndirectedGraph> {
+            let (u, v) = (count(u)?, count(v)?);
+            if u >= graph.size() || v >= graph.size() || u == v {
+                return Err(format!(
+                    "Cannot connect {u} and {v} in graph of {} vertices",
+                    graph.size()
+                )
+                .into());
+            }
+            graph.connect(VertexIndex { index: u }, VertexIndex { index: v }, true);
+            Ok(graph)
+        },
+    );
+    engine.register_fn(
+        "domineering",
+        |width: i64, height: i64| -> ScriptResult<String> {
+            let rows = vec![".".repeat(count(width)?); count(height)?];
+            Ok(format!("domineering:{}", rows.join("|")))
+        },
+    );
+    engine
+}
@@ -0,0 +1,22 @@
+use crate::commands::family::dsl;
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug, Clone)]
+/// Print definitions of built-in families and families defined in a file
+pub struct Args {
+    /// File with family definitions, one per line
+    #[arg(long)]
+    family_file: Option<PathBuf>,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    for (idx, family) in dsl::load(args.family_file.as_deref())?.iter().enumerate() {
+        if idx != 0 {
+            println!();
+        }
+        println!("{family}");
+    }
+    Ok(())
+}
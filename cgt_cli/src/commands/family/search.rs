@@ -0,0 +1,345 @@
+use crate::{
+    commands::{
+        family::dsl::{self, Family, Instance},
+        logs::{Log, Record},
+        search::fitness::{Features, FitnessScript},
+    },
+    io::{FileOrStderr, FileOrStdout},
+    progress,
+};
+use anyhow::{bail, Context, Result};
+use cgt::{
+    genetic_algorithm::{Algorithm, GeneticAlgorithm},
+    graph::Graph,
+    numeric::rational::Rational,
+    short::partizan::{
+        cancellation::Cancellation,
+        canonical_form::CanonicalForm,
+    },
+};
+use clap::Parser;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    io::Write,
+    num::NonZeroUsize,
+    path::PathBuf,
+    sync::Mutex,
+    time::Duration,
+};
+
+#[derive(Parser, Debug, Clone)]
+/// Search a family of positions given by integer parameters for positions with high temperature
+///
+/// The genetic algorithm mutates and crosses parameter vectors instead of positions, so every
+/// specimen stays in the family. See `cgt-cli family list` for the built-in families.
+pub struct Args {
+    /// Name of the family to search
+    #[arg(long)]
+    family: String,
+
+    /// File with family definitions, one per line. Families defined there replace built-in
+    /// families of the same name
+    #[arg(long)]
+    family_file: Option<PathBuf>,
+
+    /// Number of parameter vectors in a generation
+    #[arg(long, default_value_t = NonZeroUsize::new(32).unwrap())]
+    generation_size: NonZeroUsize,
+
+    /// Probability that a parameter is changed in a new specimen
+    #[arg(long, default_value_t = 0.3)]
+    mutation_rate: f32,
+
+    /// Stop after running that many generations. Run forever otherwise
+    #[arg(long, default_value = None)]
+    generation_limit: Option<usize>,
+
+    /// Score positions with a Rhai script instead of their temperature, with the same variables
+    /// as fitness scripts of `cgt-cli search`
+    #[arg(long, conflicts_with = "fitness_file")]
+    fitness: Option<String>,
+
+    /// Read the fitness script from that file
+    #[arg(long)]
+    fitness_file: Option<PathBuf>,
+
+    /// Output positions with temperature, or fitness if given a fitness script, at least that
+    /// value
+    #[arg(long, default_value_t = Rational::from(0))]
+    temperature_threshold: Rational,
+
+    /// Give up on positions that take longer than that many milliseconds to evaluate, scoring
+    /// them as lowest possible
+    #[arg(long, default_value = None)]
+    evaluation_timeout_ms: Option<u64>,
+
+    /// Seed of the random number generator. Drawn at random if not given, and always written to
+    /// diagnostics
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Path to output file with found positions
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+
+    /// Path to output progress of the search
+    #[arg(long, default_value = "-")]
+    diagnostics: FileOrStderr,
+}
+
+/// Position of a parameter vector and its score. Positions that could not be built have none
+#[derive(Clone)]
+struct Evaluation {
+    position: Option<String>,
+    score: Rational,
+}
+
+/// Search algorithm over parameter vectors of a family. Parameter spaces are small, so every
+/// vector is evaluated once
+struct FamilySearch {
+    family: Family,
+    mutation_rate: f32,
+    fitness: Option<FitnessScript>,
+    evaluation_timeout: Option<Duration>,
+    evaluations: Mutex<HashMap<Vec<i64>, Evaluation>>,
+}
+
+impl FamilySearch {
+    fn canonical_form(&self, instance: &Instance) -> Option<CanonicalForm> {
+        match self.evaluation_timeout {
+            None => Some(instance.position.canonical_form()),
+            Some(timeout) => instance
+                .position
+                .canonical_form_cancellable(&Cancellation::with_timeout(timeout))
+                .ok(),
+        }
+    }
+
+    fn evaluate(&self, values: &[i64]) -> Evaluation {
+        let Ok(instance) = self.family.instantiate(values) else {
+            return Evaluation {
+                position: None,
+                score: Rational::NegativeInfinity,
+            };
+        };
+        let score = self
+            .canonical_form(&instance)
+            .map_or(Rational::NegativeInfinity, |canonical_form| {
+                match &self.fitness {
+                    None => canonical_form.temperature().to_rational(),
+                    Some(fitness) => fitness
+                        .eval(&features(&instance, canonical_form))
+                        .unwrap_or_else(|err| {
+                            panic!("Could not score '{}': {err}", instance.position)
+                        }),
+                }
+            });
+        Evaluation {
+            position: Some(instance.position.to_string()),
+            score,
+        }
+    }
+
+    fn evaluation(&self, values: &[i64]) -> Evaluation {
+        self.evaluations.lock().unwrap()[values].clone()
+    }
+}
+
+fn features(instance: &Instance, canonical_form: CanonicalForm) -> Features {
+    Features {
+        canonical_form,
+        vertices: instance.graph.as_ref().map(Graph::size),
+        degree: instance.graph.as_ref().map(Graph::degree),
+    }
+}
+
+impl Algorithm<Vec<i64>, Rational> for FamilySearch {
+    fn mutate(&self, values: &mut Vec<i64>, rng: &mut StdRng) {
+        for (value, parameter) in values.iter_mut().zip(&self.family.parameters) {
+            if rng.gen::<f32>() < self.mutation_rate {
+                // Small steps explore neighbouring family members, jumps escape local optima
+                *value = if rng.gen_bool(0.5) {
+                    let step = if rng.gen_bool(0.5) { 1 } else { -1 };
+                    (*value + step).clamp(parameter.min, parameter.max)
+                } else {
+                    rng.gen_range(parameter.min..=parameter.max)
+                };
+            }
+        }
+    }
+
+    fn cross(&self, lhs: &Vec<i64>, rhs: &Vec<i64>, rng: &mut StdRng) -> Vec<i64> {
+        lhs.iter()
+            .zip(rhs)
+            .map(|(&lhs, &rhs)| if rng.gen_bool(0.5) { lhs } else { rhs })
+            .collect()
+    }
+
+    fn lowest_score(&self) -> Rational {
+        Rational::NegativeInfinity
+    }
+
+    fn score(&self, values: &Vec<i64>) -> Rational {
+        self.score_all(std::slice::from_ref(values))[0]
+    }
+
+    fn score_all(&self, objects: &[Vec<i64>]) -> Vec<Rational> {
+        let missing = {
+            let evaluations = self.evaluations.lock().unwrap();
+            objects
+                .iter()
+                .filter(|values| !evaluations.contains_key(*values))
+                .cloned()
+                .collect::<HashSet<_>>()
+        };
+        let evaluated = missing
+            .into_par_iter()
+            .map(|values| {
+                let evaluation = self.evaluate(&values);
+                (values, evaluation)
+            })
+            .collect::<Vec<_>>();
+
+        let mut evaluations = self.evaluations.lock().unwrap();
+        evaluations.extend(evaluated);
+        objects
+            .iter()
+            .map(|values| evaluations[values].score)
+            .collect()
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Vec<i64> {
+        self.family
+            .parameters
+            .iter()
+            .map(|parameter| rng.gen_range(parameter.min..=parameter.max))
+            .collect()
+    }
+}
+
+/// Compile fitness script and run it once on `example`, so errors are reported before the search
+/// starts
+fn load_fitness(args: &Args, example: &Instance) -> Result<Option<FitnessScript>> {
+    let source = match (&args.fitness, &args.fitness_file) {
+        (Some(source), _) => source.clone(),
+        (None, Some(path)) => fs::read_to_string(path)
+            .with_context(|| format!("Could not read fitness script '{}'", path.display()))?,
+        (None, None) => return Ok(None),
+    };
+    let fitness = FitnessScript::new(&source)?;
+    fitness.eval(&features(example, CanonicalForm::new_integer(0)))?;
+    Ok(Some(fitness))
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let Some(family) = dsl::load(args.family_file.as_deref())?
+        .into_iter()
+        .find(|family| family.name == args.family)
+    else {
+        bail!("Unknown family '{}'", args.family);
+    };
+    // Report errors in the definition before the search starts
+    let lowest = family
+        .parameters
+        .iter()
+        .map(|parameter| parameter.min)
+        .collect::<Vec<_>>();
+    let example = family
+        .instantiate(&lowest)
+        .context("Could not build the family member with lowest parameters")?;
+
+    let seed = args.seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let alg = FamilySearch {
+        fitness: load_fitness(&args, &example)?,
+        family,
+        mutation_rate: args.mutation_rate,
+        evaluation_timeout: args.evaluation_timeout_ms.map(Duration::from_millis),
+        evaluations: Mutex::new(HashMap::new()),
+    };
+    let scripted = alg.fitness.is_some();
+    let label = |score: &Rational| {
+        if scripted {
+            (None, Some(*score))
+        } else {
+            (Some(*score), None)
+        }
+    };
+    let mut alg =
+        GeneticAlgorithm::with_specimen_seeded(Vec::new(), args.generation_size, alg, rng.gen());
+
+    let mut output = args.out_file.create()?;
+    let mut diagnostics = args.diagnostics.create()?;
+    writeln!(
+        diagnostics,
+        "{}",
+        serde_json::ser::to_string(&Log::new(Record::run(seed, None)))?
+    )?;
+    let mut found = HashSet::new();
+
+    let generations = args.generation_limit.map_or_else(
+        || progress::spinner("generations"),
+        |limit| progress::bar(limit as u64, "generations"),
+    );
+
+    while args
+        .generation_limit
+        .is_none_or(|limit| alg.generation() < limit)
+    {
+        alg.step_generation();
+
+        let algorithm = alg.algorithm();
+        let parameters = |values: &[i64]| {
+            algorithm
+                .family
+                .parameters
+                .iter()
+                .zip(values)
+                .map(|(parameter, value)| (parameter.name.clone(), *value))
+                .collect::<BTreeMap<_, _>>()
+        };
+        for specimen in alg.specimen() {
+            if specimen.score >= args.temperature_threshold
+                && found.insert(specimen.object.clone())
+            {
+                let Some(position) = algorithm.evaluation(&specimen.object).position else {
+                    continue;
+                };
+                let (temperature, fitness) = label(&specimen.score);
+                let result = Log::new(Record::FamilyFound {
+                    position,
+                    parameters: parameters(&specimen.object),
+                    temperature,
+                    fitness,
+                });
+                writeln!(output, "{}", serde_json::ser::to_string(&result)?)?;
+            }
+        }
+        output.flush()?;
+
+        let best = alg.highest_score();
+        let (top_temperature, top_fitness) = label(&best.score);
+        let progress = Log::new(Record::FamilyProgress {
+            generation: alg.generation(),
+            top_temperature,
+            top_fitness,
+            parameters: parameters(&best.object),
+            position: algorithm.evaluation(&best.object).position,
+        });
+        // Diagnostics go to stderr by default, where they would break the progress bar
+        generations.suspend(|| -> Result<()> {
+            writeln!(diagnostics, "{}", serde_json::ser::to_string(&progress)?)?;
+            diagnostics.flush()?;
+            Ok(())
+        })?;
+        generations.set_position(alg.generation() as u64);
+    }
+
+    generations.finish();
+
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use crate::{
+    commands::convert::{self, Format},
+    io::FileOrStdout,
+};
+use anyhow::{bail, Context, Result};
+use cgt::{
+    graph::{adjacency_matrix::undirected::UndirectedGraph, generators},
+    short::partizan::games::snort::Snort,
+};
+use clap::{Parser, ValueEnum};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{io::Write, num::NonZeroUsize};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Family {
+    /// Paths on `--vertices` vertices
+    Path,
+
+    /// Cycles on `--vertices` vertices
+    Cycle,
+
+    /// Stars on `--vertices` vertices, i.e. a center and the remaining vertices as leaves
+    Star,
+
+    /// Complete graphs on `--vertices` vertices
+    Complete,
+
+    /// Complete bipartite graph with parts of `--left` and `--right` vertices
+    CompleteBipartite,
+
+    /// Grid of `--width` by `--height` vertices
+    Grid,
+
+    /// Path with `--legs` leaves attached to its vertices
+    Caterpillar,
+
+    /// Erdős–Rényi graphs on `--vertices` vertices, every edge present with `--edge-probability`
+    Gnp,
+
+    /// Uniformly random labeled trees on `--vertices` vertices
+    Tree,
+
+    /// Random graphs on `--vertices` vertices with degrees at most `--max-degree`
+    BoundedDegree,
+}
+
+impl Family {
+    const fn is_random(self) -> bool {
+        matches!(self, Self::Gnp | Self::Tree | Self::BoundedDegree)
+    }
+
+    const fn is_sized(self) -> bool {
+        matches!(
+            self,
+            Self::Path
+                | Self::Cycle
+                | Self::Star
+                | Self::Complete
+                | Self::Gnp
+                | Self::Tree
+                | Self::BoundedDegree
+        )
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Generate graphs of common families, e.g. as input of `cgt-cli snort census`
+pub struct Args {
+    #[arg(long, value_enum)]
+    family: Family,
+
+    /// Comma-separated numbers of vertices, one or more graphs are generated for each
+    #[arg(long, value_delimiter = ',')]
+    vertices: Vec<usize>,
+
+    /// Width of `grid`
+    #[arg(long)]
+    width: Option<usize>,
+
+    /// Height of `grid`
+    #[arg(long)]
+    height: Option<usize>,
+
+    /// Size of the first part of `complete-bipartite`
+    #[arg(long)]
+    left: Option<usize>,
+
+    /// Size of the second part of `complete-bipartite`
+    #[arg(long)]
+    right: Option<usize>,
+
+    /// Comma-separated numbers of leaves of spine vertices of `caterpillar`
+    #[arg(long, value_delimiter = ',')]
+    legs: Vec<usize>,
+
+    /// Probability that an edge is present, used by `gnp` and `bounded-degree`
+    #[arg(long, default_value_t = 0.3)]
+    edge_probability: f64,
+
+    /// Highest vertex degree, used by `bounded-degree`
+    #[arg(long, default_value_t = 3)]
+    max_degree: usize,
+
+    /// Number of random graphs generated for each number of vertices
+    #[arg(long, default_value_t = NonZeroUsize::MIN)]
+    count: NonZeroUsize,
+
+    /// Seed of the random number generator, for reproducible graphs
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Format of the output
+    #[arg(long, value_enum, default_value_t = Format::Graph6)]
+    format: Format,
+
+    /// Output file. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+}
+
+fn generate(args: &Args, rng: &mut StdRng) -> Result<Vec<UndirectedGraph>> {
+    let sized = |f: &mut dyn FnMut(usize) -> UndirectedGraph| {
+        let repeat = if args.family.is_random() {
+            args.count.get()
+        } else {
+            1
+        };
+        args.vertices
+            .iter()
+            .flat_map(|&size| (0..repeat).map(move |_| size))
+            .map(f)
+            .collect::<Vec<_>>()
+    };
+
+    let graphs = match args.family {
+        Family::Path => sized(&mut generators::path),
+        Family::Cycle => sized(&mut generators::cycle),
+        Family::Star => sized(&mut |size: usize| generators::star(size.saturating_sub(1))),
+        Family::Complete => sized(&mut generators::complete),
+        Family::Gnp => {
+            sized(&mut |size| generators::random_gnp(size, args.edge_probability, rng))
+        }
+        Family::Tree => sized(&mut |size| generators::random_tree(size, rng)),
+        Family::BoundedDegree => sized(&mut |size| {
+            generators::random_bounded_degree(size, args.max_degree, args.edge_probability, rng)
+        }),
+        Family::CompleteBipartite => {
+            let (Some(left), Some(right)) = (args.left, args.right) else {
+                bail!("complete-bipartite requires --left and --right");
+            };
+            vec![generators::complete_bipartite(left, right)]
+        }
+        Family::Grid => {
+            let (Some(width), Some(height)) = (args.width, args.height) else {
+                bail!("grid requires --width and --height");
+            };
+            vec![generators::grid(width, height)]
+        }
+        Family::Caterpillar => {
+            if args.legs.is_empty() {
+                bail!("caterpillar requires --legs");
+            }
+            vec![generators::caterpillar(&args.legs)]
+        }
+    };
+    Ok(graphs)
+}
+
+pub fn run(args: Args) -> Result<()> {
+    if args.family.is_sized() && args.vertices.is_empty() {
+        let family = args
+            .family
+            .to_possible_value()
+            .expect("unreachable: no variant is skipped");
+        bail!("{} requires --vertices", family.get_name());
+    }
+    if !(0.0..=1.0).contains(&args.edge_probability) {
+        bail!("Edge probability must be between 0 and 1");
+    }
+
+    let mut rng = args
+        .seed
+        .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+    let graphs = generate(&args, &mut rng)?;
+    if matches!(args.format, Format::Dot | Format::Graphml) && graphs.len() > 1 {
+        bail!(
+            "{} holds a single graph, but {} were generated",
+            args.format.name(),
+            graphs.len()
+        );
+    }
+
+    let mut output = args.out_file.create().context("Could not create output file")?;
+    for graph in graphs {
+        convert::write(&mut output, args.format, &Snort::new(graph))?;
+    }
+    output.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,337 @@
+//! Records written by searches, one JSON object per line, or in another
+//! [encoding](crate::encoding)
+//!
+//! Every record has a `version` field with [`LOG_VERSION`] and a `kind` field, one of `"run"`,
+//! `"generation"`, `"high_fitness"`, `"pareto_front"`, `"family_found"` or `"family_progress"`.
+//! Records written before the versioning was introduced (version 1) have neither and can be
+//! upgraded with `cgt-cli logs migrate`.
+//!
+//! ```text
+//! {"version":2,"kind":"run","seed":42,"cgt_version":"0.5.1","arguments":["cgt-cli","snort",...]}
+//! {"version":2,"kind":"generation","generation":1,"top_score":"1/2","temperature":"3/2"}
+//! {"version":2,"kind":"high_fitness","position":"snort:...;0-1,1-2","graph6":"Bg","score":"1/2",
+//!  "canonical_form":"{2|-2}","temperature":"2","degree":2}
+//! {"version":2,"kind":"pareto_front","generation":1,"front":[{"position":"snort:...;0-1,1-2",
+//!  "graph6":"Bg","temperature":"2","degree":2}]}
+//! {"version":2,"kind":"family_found","position":"snort:...","parameters":{"n":3},
+//!  "temperature":"2"}
+//! {"version":2,"kind":"family_progress","generation":1,"top_temperature":"2",
+//!  "parameters":{"n":3},"position":"snort:..."}
+//! ```
+
+use crate::encoding;
+use anyhow::{bail, Context, Result};
+use cgt::{
+    genetic::{
+        islands::Migration,
+        snort::{Crossover, Mutation},
+        Scored, Selection,
+    },
+    graph::graph6,
+    numeric::{dyadic_rational_number::DyadicRationalNumber, rational::Rational},
+    short::partizan::{games::snort::Snort, position::NamedGame},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, io::Read, num::NonZeroUsize, str::FromStr};
+
+crate::clap_utils::mk_subcommand! {
+    Convert => convert,
+    Migrate => migrate,
+    Verify => verify,
+    Stats => stats,
+    Dedupe => dedupe,
+}
+
+/// Version of the log schema written by this version of `cgt-cli`
+pub const LOG_VERSION: u32 = 2;
+
+/// Versioned log record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Log {
+    /// Schema version, always [`LOG_VERSION`] for records written by this version of `cgt-cli`
+    pub version: u32,
+
+    #[serde(flatten)]
+    pub record: Record,
+}
+
+impl Log {
+    pub const fn new(record: Record) -> Self {
+        Self {
+            version: LOG_VERSION,
+            record,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Record {
+    /// Parameters of the run, written before any other record so the run can be repeated
+    Run {
+        /// Seed of the random number generator
+        seed: u64,
+
+        /// Version of `cgt-cli` that wrote the log
+        cgt_version: String,
+
+        /// Command line arguments, including the program name
+        arguments: Vec<String>,
+
+        /// Operators of genetic searches, missing in logs of other searches and of earlier
+        /// versions
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        operators: Option<Operators>,
+    },
+
+    /// Summary of a finished generation
+    Generation {
+        generation: usize,
+        top_score: Rational,
+        temperature: DyadicRationalNumber,
+    },
+
+    /// Position with high score
+    HighFitness {
+        /// Position in the `game:payload` format
+        position: String,
+
+        /// Underlying graph of the position in graph6 format
+        graph6: String,
+
+        score: Rational,
+        canonical_form: String,
+        temperature: DyadicRationalNumber,
+        degree: usize,
+    },
+
+    /// Positions not dominated by any position found so far in both high temperature and low
+    /// degree, written when the front changes
+    ParetoFront {
+        generation: usize,
+        front: Vec<FrontMember>,
+    },
+
+    /// Member of a family found by `cgt-cli family search` with score above the threshold
+    FamilyFound {
+        /// Position in the `game:payload` format
+        position: String,
+
+        /// Values of the family parameters the position was built with
+        parameters: BTreeMap<String, i64>,
+
+        /// Temperature, when searching without a fitness script
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        temperature: Option<Rational>,
+
+        /// Fitness, when searching with a fitness script
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        fitness: Option<Rational>,
+    },
+
+    /// Best family member after a finished generation of `cgt-cli family search`
+    FamilyProgress {
+        generation: usize,
+
+        /// Temperature, when searching without a fitness script
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        top_temperature: Option<Rational>,
+
+        /// Fitness, when searching with a fitness script
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        top_fitness: Option<Rational>,
+
+        /// Values of the family parameters of the best member
+        parameters: BTreeMap<String, i64>,
+
+        /// Best member in the `game:payload` format, missing if it could not be built
+        position: Option<String>,
+    },
+}
+
+/// Operators of a genetic search, recorded in [`Record::Run`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Operators {
+    pub selection: Selection,
+
+    /// Mutation operators, for searches configuring them separately
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mutation: Option<Mutation>,
+
+    /// Crossover scheme, for searches with more than one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crossover: Option<Crossover>,
+
+    /// Number of islands, for searches running more than one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub islands: Option<NonZeroUsize>,
+
+    /// Migration between islands, for searches running more than one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub migration: Option<Migration>,
+}
+
+impl Operators {
+    pub const fn new(selection: Selection) -> Self {
+        Self {
+            selection,
+            mutation: None,
+            crossover: None,
+            islands: None,
+            migration: None,
+        }
+    }
+}
+
+/// Position on the Pareto front of [`Record::ParetoFront`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrontMember {
+    /// Position in the `game:payload` format
+    pub position: String,
+
+    /// Underlying graph of the position in graph6 format
+    pub graph6: String,
+
+    pub temperature: Rational,
+    pub degree: usize,
+}
+
+impl FrontMember {
+    pub fn new(position: &Snort, temperature: Rational) -> Self {
+        Self {
+            position: format!("{}:{}", <Snort>::NAME, position),
+            graph6: graph6::to_graph6(&position.graph),
+            temperature,
+            degree: position.degree(),
+        }
+    }
+}
+
+impl Record {
+    /// Parameters of the current process, for runs using random number generator seeded with
+    /// `seed` and, for genetic searches, given operators
+    pub fn run(seed: u64, operators: Option<Operators>) -> Self {
+        Self::Run {
+            seed,
+            cgt_version: env!("CARGO_PKG_VERSION").to_owned(),
+            arguments: std::env::args().collect(),
+            operators,
+        }
+    }
+
+    pub fn high_fitness(
+        position: &Scored<Snort, Rational>,
+        canonical_form: String,
+        temperature: DyadicRationalNumber,
+    ) -> Self {
+        Self::HighFitness {
+            position: format!("{}:{}", <Snort>::NAME, position.object),
+            graph6: graph6::to_graph6(&position.object.graph),
+            score: position.score,
+            canonical_form,
+            temperature,
+            degree: position.object.degree(),
+        }
+    }
+}
+
+/// Position recorded in a log together with its logged properties
+pub struct LoggedPosition {
+    /// Number of the record, starting from 1
+    pub record: usize,
+
+    /// Position in the `game:payload` format
+    pub position: String,
+
+    pub graph6: String,
+
+    /// Only recorded in [`Record::HighFitness`]
+    pub canonical_form: Option<String>,
+
+    pub temperature: Rational,
+    pub degree: usize,
+}
+
+/// Positions of [`Record::HighFitness`] and [`Record::ParetoFront`] records, in order of records
+pub fn logged_positions(records: &[Record]) -> Vec<LoggedPosition> {
+    let mut positions = Vec::new();
+    for (idx, record) in records.iter().enumerate() {
+        match record {
+            Record::Run { .. }
+            | Record::Generation { .. }
+            | Record::FamilyFound { .. }
+            | Record::FamilyProgress { .. } => {}
+            Record::HighFitness {
+                position,
+                graph6,
+                canonical_form,
+                temperature,
+                degree,
+                ..
+            } => positions.push(LoggedPosition {
+                record: idx + 1,
+                position: position.clone(),
+                graph6: graph6.clone(),
+                canonical_form: Some(canonical_form.clone()),
+                temperature: temperature.to_rational(),
+                degree: *degree,
+            }),
+            Record::ParetoFront { front, .. } => {
+                positions.extend(front.iter().map(|member| LoggedPosition {
+                    record: idx + 1,
+                    position: member.position.clone(),
+                    graph6: member.graph6.clone(),
+                    canonical_form: None,
+                    temperature: member.temperature,
+                    degree: member.degree,
+                }));
+            }
+        }
+    }
+    positions
+}
+
+/// Parse position in the `snort:payload` format, as written in [`Record::HighFitness`]
+pub fn parse_snort(position: &str) -> Result<Snort> {
+    let Some(payload) = position
+        .strip_prefix(<Snort>::NAME)
+        .and_then(|payload| payload.strip_prefix(':'))
+    else {
+        bail!("Position '{position}' is not a position of {}", <Snort>::NAME);
+    };
+    Snort::from_str(payload).with_context(|| format!("Could not parse position '{position}'"))
+}
+
+/// Quote field if it contains characters special in CSV, e.g. commas in edge lists
+pub fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Read log records in any [encoding](crate::encoding), failing on records of other schema
+/// versions
+pub fn read_logs(input: impl Read + 'static) -> Result<Vec<Log>> {
+    encoding::read_values(input)?
+        .enumerate()
+        .map(|(idx, value)| {
+            let value = value?;
+            match value.get("version").and_then(serde_json::Value::as_u64) {
+                Some(version) if version == u64::from(LOG_VERSION) => {
+                    serde_json::from_value(value)
+                        .with_context(|| format!("Could not decode log record {}", idx + 1))
+                }
+                Some(version) => bail!(
+                    "Log record {} has unsupported version {version}, expected {LOG_VERSION}",
+                    idx + 1
+                ),
+                None => bail!(
+                    "Log record {} has no version, upgrade the log with `cgt-cli logs migrate`",
+                    idx + 1
+                ),
+            }
+        })
+        .collect()
+}
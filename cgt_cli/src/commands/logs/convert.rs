@@ -0,0 +1,41 @@
+use crate::{
+    commands::logs::read_logs,
+    encoding::{Format, Writer},
+    io::{FileOrStdin, FileOrStdout},
+};
+use anyhow::{Context, Result};
+use clap::Parser;
+
+#[derive(Parser, Debug, Clone)]
+/// Convert log records between encodings, e.g. to shrink JSON logs of long runs
+pub struct Args {
+    /// Input file with log records in any encoding. Use '-' for stdin
+    #[arg(long, default_value = "-")]
+    in_file: FileOrStdin,
+
+    /// Output file with converted records. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+
+    /// Encoding of the output
+    #[arg(long, value_enum, default_value_t = Format::Cbor)]
+    format: Format,
+
+    /// Compress the output with gzip
+    #[arg(long, default_value_t = false)]
+    compress: bool,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let input = args.in_file.open().context("Could not open input file")?;
+    let logs = read_logs(input)?;
+
+    let output = args.out_file.create().context("Could not create output file")?;
+    let mut output = Writer::new(output, args.format, args.compress)?;
+    for log in &logs {
+        output.write(log)?;
+    }
+    output.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use crate::{
+    commands::logs::{parse_snort, read_logs, Log, Record},
+    io::{FileOrStdin, FileOrStdout},
+};
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    io::{BufReader, Write},
+};
+
+#[derive(Parser, Debug, Clone)]
+/// Remove log records of positions isomorphic to other logged positions, keeping the one with
+/// the best fitness. Isomorphisms must preserve vertex colors
+///
+/// Generation records are kept, and duplicates within every Pareto front are removed.
+pub struct Args {
+    /// Input file with log records in any encoding. Use '-' for stdin
+    #[arg(long, default_value = "-")]
+    in_file: FileOrStdin,
+
+    /// Output file with deduplicated records. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+}
+
+/// Canonically labeled positions, computed once for every position
+#[derive(Default)]
+struct Classes {
+    canonical: HashMap<String, String>,
+}
+
+impl Classes {
+    fn of(&mut self, position: &str) -> Result<String> {
+        if let Some(class) = self.canonical.get(position) {
+            return Ok(class.clone());
+        }
+        let class = parse_snort(position)?.canonically_labeled().to_string();
+        self.canonical.insert(position.to_owned(), class.clone());
+        Ok(class)
+    }
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let input = BufReader::new(args.in_file.open().context("Could not open input file")?);
+    let logs = read_logs(input)?;
+    let mut classes = Classes::default();
+
+    // Record with the best score of every class, the first one on ties
+    let mut best = HashMap::new();
+    let mut high_fitness = 0;
+    for (idx, log) in logs.iter().enumerate() {
+        if let Record::HighFitness {
+            position, score, ..
+        } = &log.record
+        {
+            high_fitness += 1;
+            let class = classes
+                .of(position)
+                .with_context(|| format!("Invalid position in record {}", idx + 1))?;
+            match best.entry(class) {
+                Entry::Vacant(entry) => {
+                    entry.insert((idx, *score));
+                }
+                Entry::Occupied(mut entry) => {
+                    if *score > entry.get().1 {
+                        entry.insert((idx, *score));
+                    }
+                }
+            }
+        }
+    }
+    let kept = best.values().map(|&(idx, _)| idx).collect::<HashSet<_>>();
+
+    let mut output = args.out_file.create().context("Could not create output file")?;
+    let mut removed_members = 0;
+    for (idx, log) in logs.into_iter().enumerate() {
+        let record = match log.record {
+            Record::HighFitness { .. } if !kept.contains(&idx) => continue,
+            Record::ParetoFront { generation, front } => {
+                let mut seen = HashSet::new();
+                let mut deduplicated = Vec::with_capacity(front.len());
+                for member in front {
+                    let class = classes
+                        .of(&member.position)
+                        .with_context(|| format!("Invalid position in record {}", idx + 1))?;
+                    if seen.insert(class) {
+                        deduplicated.push(member);
+                    } else {
+                        removed_members += 1;
+                    }
+                }
+                Record::ParetoFront {
+                    generation,
+                    front: deduplicated,
+                }
+            }
+            record => record,
+        };
+        writeln!(output, "{}", serde_json::ser::to_string(&Log::new(record))?)?;
+    }
+    output.flush()?;
+
+    eprintln!(
+        "Kept {} of {high_fitness} high fitness records, removed {removed_members} duplicate \
+         Pareto front members",
+        kept.len()
+    );
+
+    Ok(())
+}
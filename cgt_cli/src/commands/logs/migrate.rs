@@ -0,0 +1,87 @@
+use crate::{
+    commands::logs::{Log, Record, LOG_VERSION},
+    io::{FileOrStdin, FileOrStdout},
+};
+use anyhow::{bail, Context, Result};
+use cgt::{
+    genetic_algorithm::Scored,
+    numeric::{dyadic_rational_number::DyadicRationalNumber, rational::Rational},
+    short::partizan::games::snort::Snort,
+};
+use clap::Parser;
+use serde::Deserialize;
+use std::io::{BufReader, BufWriter, Write};
+
+#[derive(Parser, Debug, Clone)]
+/// Upgrade log records written by older versions of `cgt-cli` to the current schema
+pub struct Args {
+    /// Input file with newline-separated JSON log records. Use '-' for stdin
+    #[arg(long, default_value = "-")]
+    in_file: FileOrStdin,
+
+    /// Output file with upgraded records. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+}
+
+/// Records written before versioning was introduced
+#[derive(Deserialize)]
+enum LogV1 {
+    Generation {
+        generation: usize,
+        top_score: Rational,
+        temperature: DyadicRationalNumber,
+    },
+    HighFitness {
+        position: Scored<Snort, Rational>,
+        canonical_form: String,
+        temperature: DyadicRationalNumber,
+    },
+}
+
+impl From<LogV1> for Log {
+    fn from(log: LogV1) -> Self {
+        let record = match log {
+            LogV1::Generation {
+                generation,
+                top_score,
+                temperature,
+            } => Record::Generation {
+                generation,
+                top_score,
+                temperature,
+            },
+            LogV1::HighFitness {
+                position,
+                canonical_form,
+                temperature,
+            } => Record::high_fitness(&position, canonical_form, temperature),
+        };
+        Self::new(record)
+    }
+}
+
+fn migrate(value: serde_json::Value) -> Result<Log> {
+    match value.get("version").and_then(serde_json::Value::as_u64) {
+        None => Ok(serde_json::from_value::<LogV1>(value)?.into()),
+        Some(version) if version == u64::from(LOG_VERSION) => Ok(serde_json::from_value(value)?),
+        Some(version) => bail!("Unsupported log version {version}, latest is {LOG_VERSION}"),
+    }
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let input = BufReader::new(args.in_file.open().context("Could not open input file")?);
+    let mut output = BufWriter::new(args.out_file.create().context("Could not create output file")?);
+
+    for (idx, value) in serde_json::de::Deserializer::from_reader(input)
+        .into_iter::<serde_json::Value>()
+        .enumerate()
+    {
+        let value = value.context("Could not decode input")?;
+        let log = migrate(value).with_context(|| format!("Could not migrate record {}", idx + 1))?;
+        writeln!(output, "{}", serde_json::ser::to_string(&log)?)?;
+    }
+    output.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use crate::{
+    commands::logs::{csv_field, logged_positions, parse_snort, read_logs, LoggedPosition},
+    io::{FileOrStdin, FileOrStdout},
+};
+use anyhow::{bail, Context, Result};
+use cgt::{graph::Graph, numeric::rational::Rational};
+use clap::{Parser, ValueEnum};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io::{BufReader, Write},
+};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Report {
+    /// Number of positions in temperature bins of `--bin-width`
+    Histogram,
+
+    /// Hottest position for every number of vertices
+    BestPerVertices,
+
+    /// Degree, temperature and number of vertices of every position
+    Scatter,
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Aggregate positions recorded in search logs into CSV reports. Positions found several times
+/// are counted once
+pub struct Args {
+    /// Report to write
+    #[arg(long, value_enum)]
+    report: Report,
+
+    /// Width of temperature bins of the histogram
+    #[arg(long, default_value = "1/2")]
+    bin_width: Rational,
+
+    /// Input file with log records in any encoding. Use '-' for stdin
+    #[arg(long, default_value = "-")]
+    in_file: FileOrStdin,
+
+    /// Output CSV file. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+}
+
+/// Index of the bin containing `value`, rounding down
+fn bin(value: Rational, width: Rational) -> Result<i64> {
+    let quotient = value / width;
+    let Some(mut idx) = quotient.try_round() else {
+        bail!("Temperature {value} does not fit in a bin");
+    };
+    if Rational::from(idx) > quotient {
+        idx -= 1;
+    }
+    Ok(idx)
+}
+
+fn histogram(
+    output: &mut impl Write,
+    positions: &[(LoggedPosition, usize)],
+    width: Rational,
+) -> Result<()> {
+    let mut bins = BTreeMap::new();
+    for (position, _) in positions {
+        *bins.entry(bin(position.temperature, width)?).or_insert(0) += 1;
+    }
+
+    writeln!(output, "bin_start,bin_end,count")?;
+    if let (Some((&first, _)), Some((&last, _))) = (bins.first_key_value(), bins.last_key_value())
+    {
+        for idx in first..=last {
+            let start = Rational::from(idx) * width;
+            let count = bins.get(&idx).copied().unwrap_or(0);
+            writeln!(output, "{},{},{count}", start, start + width)?;
+        }
+    }
+    Ok(())
+}
+
+fn best_per_vertices(output: &mut impl Write, positions: &[(LoggedPosition, usize)]) -> Result<()> {
+    let mut best = BTreeMap::<usize, &LoggedPosition>::new();
+    for (position, vertices) in positions {
+        let entry = best.entry(*vertices).or_insert(position);
+        if position.temperature > entry.temperature {
+            *entry = position;
+        }
+    }
+
+    writeln!(output, "vertices,temperature,degree,canonical_form,position")?;
+    for (vertices, position) in best {
+        writeln!(
+            output,
+            "{vertices},{},{},{},{}",
+            position.temperature,
+            position.degree,
+            csv_field(position.canonical_form.as_deref().unwrap_or_default()),
+            csv_field(&position.position)
+        )?;
+    }
+    Ok(())
+}
+
+fn scatter(output: &mut impl Write, positions: &[(LoggedPosition, usize)]) -> Result<()> {
+    writeln!(output, "degree,temperature,vertices,position")?;
+    for (position, vertices) in positions {
+        writeln!(
+            output,
+            "{},{},{vertices},{}",
+            position.degree,
+            position.temperature,
+            csv_field(&position.position)
+        )?;
+    }
+    Ok(())
+}
+
+pub fn run(args: Args) -> Result<()> {
+    if args.bin_width <= Rational::from(0) {
+        bail!("Bin width must be positive");
+    }
+
+    let input = BufReader::new(args.in_file.open().context("Could not open input file")?);
+    let records = read_logs(input)?
+        .into_iter()
+        .map(|log| log.record)
+        .collect::<Vec<_>>();
+
+    // Prefer high fitness records that include canonical forms over Pareto front members
+    let mut logged = logged_positions(&records);
+    logged.sort_by_key(|position| position.canonical_form.is_none());
+    let mut seen = HashSet::new();
+    let mut positions = Vec::new();
+    for position in logged {
+        if seen.insert(position.position.clone()) {
+            let vertices = parse_snort(&position.position)
+                .with_context(|| format!("Invalid position in record {}", position.record))?
+                .graph
+                .size();
+            positions.push((position, vertices));
+        }
+    }
+    positions.sort_by_key(|(position, _)| position.record);
+
+    let mut output = args.out_file.create().context("Could not create output file")?;
+    match args.report {
+        Report::Histogram => histogram(&mut output, &positions, args.bin_width)?,
+        Report::BestPerVertices => best_per_vertices(&mut output, &positions)?,
+        Report::Scatter => scatter(&mut output, &positions)?,
+    }
+    output.flush()?;
+
+    eprintln!(
+        "Aggregated {} unique positions from {} records",
+        positions.len(),
+        records.len()
+    );
+
+    Ok(())
+}
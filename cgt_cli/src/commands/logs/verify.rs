@@ -0,0 +1,139 @@
+use crate::{
+    commands::logs::{logged_positions, parse_snort, read_logs, LoggedPosition},
+    io::{FileOrStdin, FileOrStdout},
+    progress,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use cgt::{
+    graph::graph6,
+    short::partizan::{
+        batch::evaluate_all_with_progress, canonical_form::CanonicalForm, games::snort::Snort,
+        transposition_table::ParallelTranspositionTable,
+    },
+};
+use clap::Parser;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    io::{BufReader, Write},
+    str::FromStr,
+};
+
+#[derive(Parser, Debug, Clone)]
+/// Re-evaluate positions recorded in a log and check the stored values, temperatures and degrees.
+/// Fails if any mismatch is found
+pub struct Args {
+    /// Input file with log records in any encoding. Use '-' for stdin
+    #[arg(long, default_value = "-")]
+    in_file: FileOrStdin,
+
+    /// Output file with mismatches, one JSON object per line. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+}
+
+/// Logged field that does not match the recomputed one
+#[derive(Debug, Serialize)]
+struct Mismatch {
+    /// Number of the record, starting from 1
+    record: usize,
+    position: String,
+    field: &'static str,
+    logged: String,
+    computed: String,
+}
+
+fn check(
+    claim: &LoggedPosition,
+    position: &Snort,
+    canonical_form: &CanonicalForm,
+    mismatches: &mut Vec<Mismatch>,
+) -> Result<()> {
+    let mut compare = |field, logged: &str, computed: String| {
+        if logged != computed {
+            mismatches.push(Mismatch {
+                record: claim.record,
+                position: claim.position.clone(),
+                field,
+                logged: logged.to_owned(),
+                computed,
+            });
+        }
+    };
+
+    compare("graph6", &claim.graph6, graph6::to_graph6(&position.graph));
+    compare(
+        "temperature",
+        &claim.temperature.to_string(),
+        canonical_form.temperature().to_rational().to_string(),
+    );
+    compare("degree", &claim.degree.to_string(), position.degree().to_string());
+    if let Some(logged) = &claim.canonical_form {
+        // Compare values rather than strings, so older notations are accepted
+        let logged_value = CanonicalForm::from_str(logged).map_err(|err| {
+            anyhow!(
+                "Record {}: invalid canonical form '{logged}': {err}",
+                claim.record
+            )
+        })?;
+        if &logged_value != canonical_form {
+            compare("canonical_form", logged, canonical_form.to_string());
+        }
+    }
+    Ok(())
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let input = BufReader::new(args.in_file.open().context("Could not open input file")?);
+    let records = read_logs(input)?
+        .into_iter()
+        .map(|log| log.record)
+        .collect::<Vec<_>>();
+    let claims = logged_positions(&records);
+
+    let mut unique = HashMap::new();
+    let mut positions = Vec::new();
+    for claim in &claims {
+        if !unique.contains_key(&claim.position) {
+            let position = parse_snort(&claim.position)
+                .with_context(|| format!("Could not verify record {}", claim.record))?;
+            unique.insert(claim.position.clone(), positions.len());
+            positions.push(position);
+        }
+    }
+
+    let transposition_table = ParallelTranspositionTable::new();
+    let progress = progress::bar(positions.len() as u64, "positions");
+    let evaluations =
+        evaluate_all_with_progress(&positions, &transposition_table, |_| progress.inc(1));
+    progress.finish_and_clear();
+
+    let mut mismatches = Vec::new();
+    for claim in &claims {
+        let idx = unique[&claim.position];
+        check(
+            claim,
+            &positions[idx],
+            &evaluations[idx].canonical_form,
+            &mut mismatches,
+        )?;
+    }
+
+    let mut output = args.out_file.create().context("Could not create output file")?;
+    for mismatch in &mismatches {
+        writeln!(output, "{}", serde_json::ser::to_string(mismatch)?)?;
+    }
+    output.flush()?;
+
+    eprintln!(
+        "Checked {} positions ({} unique) in {} records",
+        claims.len(),
+        positions.len(),
+        records.len()
+    );
+    if !mismatches.is_empty() {
+        bail!("Verification failed, mismatches: {}", mismatches.len());
+    }
+
+    Ok(())
+}
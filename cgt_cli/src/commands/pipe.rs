@@ -0,0 +1,87 @@
+use crate::io::{FileOrStdin, FileOrStdout};
+use anyhow::{Context, Result};
+use cgt::{numeric::dyadic_rational_number::DyadicRationalNumber, short::partizan::position::parse_position};
+use clap::Parser;
+use serde::Serialize;
+use std::{
+    io::{BufRead, BufReader, Write},
+    time::Instant,
+};
+
+#[derive(Parser, Debug, Clone)]
+/// Evaluate positions line by line, for use in shell pipelines and from other programs
+///
+/// Every non-empty input line is a position in the `game:payload` format, and every one of them
+/// gets a JSON object on the output line, written as soon as it is evaluated. Invalid positions
+/// get an object with an `error` field instead, unless `--fail-fast` is given.
+pub struct Args {
+    /// Input file with one position per line. Use '-' for stdin
+    #[arg(long, default_value = "-")]
+    in_file: FileOrStdin,
+
+    /// Output file with one JSON object per line. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+
+    /// Stop at the first invalid position instead of reporting it and continuing
+    #[arg(long, default_value_t = false)]
+    fail_fast: bool,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Output {
+    Evaluation {
+        line: usize,
+        position: String,
+        canonical_form: String,
+        temperature: DyadicRationalNumber,
+        elapsed_ms: f64,
+    },
+    Error {
+        line: usize,
+        input: String,
+        error: String,
+    },
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let input = BufReader::new(args.in_file.open().context("Could not open input file")?);
+    let mut output = args.out_file.create().context("Could not create output file")?;
+
+    for (idx, line) in input.lines().enumerate() {
+        let line = line.context("Could not read input")?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let result = match parse_position(line) {
+            Ok(position) => {
+                let start = Instant::now();
+                let canonical_form = position.canonical_form();
+                let elapsed = start.elapsed();
+                Output::Evaluation {
+                    line: idx + 1,
+                    position: position.to_string(),
+                    temperature: canonical_form.temperature(),
+                    canonical_form: canonical_form.to_string(),
+                    elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+                }
+            }
+            Err(err) if args.fail_fast => {
+                return Err(err).with_context(|| format!("Invalid position at line {}", idx + 1));
+            }
+            Err(err) => Output::Error {
+                line: idx + 1,
+                input: line.to_owned(),
+                error: err.to_string(),
+            },
+        };
+        writeln!(output, "{}", serde_json::ser::to_string(&result)?)?;
+        // Consumers wait for every line, so it cannot sit in a buffer
+        output.flush()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use anyhow::{bail, Context, Result};
+use cgt::short::partizan::position::{parse_position, AnyPosition};
+use clap::{Parser, ValueEnum};
+use std::{
+    fmt::{self, Display},
+    io::{stdin, stdout, BufRead, IsTerminal, Write},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Player {
+    Left,
+    Right,
+}
+
+impl Player {
+    const fn opponent(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+
+    fn moves(self, position: &AnyPosition) -> Vec<AnyPosition> {
+        match self {
+            Self::Left => position.left_moves(),
+            Self::Right => position.right_moves(),
+        }
+    }
+
+    fn best_move(self, position: &AnyPosition) -> Option<AnyPosition> {
+        match self {
+            Self::Left => position.best_left_move(),
+            Self::Right => position.best_right_move(),
+        }
+    }
+}
+
+impl Display for Player {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Left => write!(f, "Left"),
+            Self::Right => write!(f, "Right"),
+        }
+    }
+}
+
+/// Play a position against the engine, which answers with its best move
+///
+/// The human picks moves by their number in the listed options. The player who cannot move loses.
+#[derive(Parser, Debug, Clone)]
+pub struct Args {
+    /// Starting position in the `game:payload` format, e.g. `domineering:...|...|...`
+    position: String,
+
+    /// Side played by the human
+    #[arg(long, value_enum, default_value_t = Player::Left)]
+    human: Player,
+
+    /// Side that moves first
+    #[arg(long, value_enum, default_value_t = Player::Left)]
+    first: Player,
+}
+
+/// Parse choice of a move, numbered from 1
+fn parse_choice(line: &str, options: usize) -> Result<usize> {
+    let choice = line
+        .trim()
+        .parse::<usize>()
+        .with_context(|| format!("Invalid move '{}', expected a number", line.trim()))?;
+    if choice == 0 || choice > options {
+        bail!("Invalid move {choice}, expected a number from 1 to {options}");
+    }
+    Ok(choice - 1)
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let mut position = parse_position(&args.position)?;
+    let mut output = stdout();
+    let interactive = stdin().is_terminal();
+    let mut lines = stdin().lock().lines();
+    let mut to_move = args.first;
+
+    loop {
+        writeln!(output, "Position: {position}")?;
+        writeln!(output, "Value: {}", position.canonical_form())?;
+
+        let mut options = to_move.moves(&position);
+        if options.is_empty() {
+            let winner = to_move.opponent();
+            let who = if winner == args.human { "you" } else { "engine" };
+            writeln!(output, "{to_move} has no moves, {winner} ({who}) wins")?;
+            break;
+        }
+
+        if to_move == args.human {
+            writeln!(output, "Your moves as {to_move}:")?;
+            for (idx, option) in options.iter().enumerate() {
+                writeln!(output, "{:>4}: {option}", idx + 1)?;
+            }
+            let choice = loop {
+                if interactive {
+                    write!(output, "> ")?;
+                    output.flush()?;
+                }
+                let Some(line) = lines.next() else {
+                    bail!("Input ended before the game was finished");
+                };
+                match parse_choice(&line?, options.len()) {
+                    Ok(choice) => break choice,
+                    Err(err) if interactive => eprintln!("Error: {err}"),
+                    Err(err) => return Err(err),
+                }
+            };
+            position = options.swap_remove(choice);
+        } else {
+            position = to_move
+                .best_move(&position)
+                .expect("unreachable: player has moves");
+            writeln!(output, "Engine moves as {to_move}")?;
+        }
+
+        to_move = to_move.opponent();
+    }
+    output.flush()?;
+
+    Ok(())
+}
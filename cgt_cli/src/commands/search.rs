@@ -0,0 +1,1107 @@
+use crate::{
+    commands::{
+        logs::{Log, Operators, Record},
+        search::fitness::{Features, FitnessScript},
+    },
+    database::{Database, Entry},
+    io::{FileOrStderr, FileOrStdout},
+    progress,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use cgt::{
+    genetic::{
+        Algorithm, Checkpoint, Chromosome, GeneticAlgorithm, ParentSelection, Scored, Selection,
+    },
+    graph::Graph,
+    local_search::{Cooling, HillClimbing, SimulatedAnnealing},
+    numeric::{dyadic_rational_number::DyadicRationalNumber, rational::Rational},
+    pareto::{self, ParetoFront},
+    short::partizan::{
+        cancellation::Cancellation,
+        canonical_form::CanonicalForm,
+        games::{
+            amazons::Amazons, domineering::Domineering, fission::Fission, ski_jumps::SkiJumps,
+            snort::Snort, toads_and_frogs::ToadsAndFrogs,
+        },
+        position::NamedGame,
+        random_position::{GraphParameters, GridParameters, RandomPosition},
+        transposition_table::ParallelTranspositionTable,
+    },
+};
+use clap::{self, Parser, ValueEnum};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rayon::prelude::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs::{self, File},
+    hash::Hash,
+    io::{BufRead, BufReader, BufWriter, Write},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+pub mod fitness;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Game {
+    Amazons,
+    Domineering,
+    Fission,
+    SkiJumps,
+    Snort,
+    ToadsAndFrogs,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+enum Mode {
+    /// Genetic algorithm on a population of positions
+    #[default]
+    Genetic,
+
+    /// Simulated annealing of a single position
+    Annealing,
+
+    /// Steepest-ascent hill climbing with random restarts
+    HillClimbing,
+}
+
+/// Choice of parents of new positions in genetic searches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Parents {
+    /// Any position, with equal probability
+    Uniform,
+
+    /// Best of `--tournament-size` random positions
+    Tournament,
+
+    /// Positions with probability proportional to their rank
+    Roulette,
+}
+
+/// Selection of genetic searches, shared by `search` and `snort genetic`
+#[derive(clap::Args, Debug, Clone)]
+pub struct SelectionArgs {
+    /// Choice of parents of new positions in the genetic search
+    #[arg(long, value_enum, default_value_t = Parents::Uniform)]
+    selection: Parents,
+
+    /// Number of positions competing for each parent in the tournament selection
+    #[arg(long, default_value_t = NonZeroUsize::new(2).unwrap())]
+    tournament_size: NonZeroUsize,
+
+    /// Fraction of best positions copied to the next generation unchanged, between 0 and 1
+    #[arg(long, default_value_t = 0.5)]
+    elitism: f64,
+}
+
+impl SelectionArgs {
+    pub fn selection(&self) -> Result<Selection> {
+        if !(0.0..=1.0).contains(&self.elitism) {
+            bail!("--elitism must be between 0 and 1, got {}", self.elitism);
+        }
+        let parents = match self.selection {
+            Parents::Uniform => ParentSelection::Uniform,
+            Parents::Tournament => ParentSelection::Tournament {
+                size: self.tournament_size,
+            },
+            Parents::Roulette => ParentSelection::Roulette,
+        };
+        Ok(Selection {
+            elitism: self.elitism,
+            parents,
+        })
+    }
+}
+
+/// Objective of multi-objective searches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Objective {
+    Temperature,
+    Mean,
+    LeftStop,
+    RightStop,
+
+    /// Number of vertices, in games played on graphs
+    Vertices,
+
+    /// Highest vertex degree, in games played on graphs
+    Degree,
+}
+
+impl Objective {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Temperature => "temperature",
+            Self::Mean => "mean",
+            Self::LeftStop => "left_stop",
+            Self::RightStop => "right_stop",
+            Self::Vertices => "vertices",
+            Self::Degree => "degree",
+        }
+    }
+
+    /// Value of the objective, or `None` if it is not defined for the game
+    fn value<G>(self, position: &G, canonical_form: &CanonicalForm) -> Option<Rational>
+    where
+        G: Mutate,
+    {
+        let count = |count: usize| Rational::from(count as i64);
+        match self {
+            Self::Temperature => Some(canonical_form.temperature().to_rational()),
+            Self::Mean => Some(canonical_form.mean().to_rational()),
+            Self::LeftStop => Some(canonical_form.left_stop().to_rational()),
+            Self::RightStop => Some(canonical_form.right_stop().to_rational()),
+            Self::Vertices => position.vertices().map(count),
+            Self::Degree => position.degree().map(count),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Goal {
+    objective: Objective,
+    maximize: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Search positions of a game to find positions with high temperature
+pub struct Args {
+    /// Game to search
+    #[arg(long, value_enum)]
+    game: Game,
+
+    /// Search algorithm
+    #[arg(long, value_enum, default_value_t = Mode::Genetic)]
+    mode: Mode,
+
+    /// Number of positions in a generation, required by the genetic search
+    #[arg(long)]
+    generation_size: Option<NonZeroUsize>,
+
+    #[arg(long)]
+    mutation_rate: f32,
+
+    #[command(flatten)]
+    selection: SelectionArgs,
+
+    /// Initial temperature of the annealing schedule, in units of score
+    #[arg(long, default_value_t = 1.0)]
+    annealing_temperature: f64,
+
+    /// Factor the annealing temperature is multiplied by after each step
+    #[arg(long, default_value_t = 0.995)]
+    cooling_rate: f64,
+
+    /// Number of mutations of the current position scored in each step of hill climbing
+    #[arg(long, default_value_t = NonZeroUsize::new(16).unwrap())]
+    neighbours: NonZeroUsize,
+
+    /// Stop after running that many generations (or steps of local searches), counting
+    /// generations run before resuming. Run forever otherwise
+    #[arg(long, default_value = None)]
+    generation_limit: Option<usize>,
+
+    /// Periodically save population, random number generator state and generation counter to
+    /// that file. Supported only by the genetic search
+    #[arg(long, default_value = None)]
+    checkpoint_file: Option<PathBuf>,
+
+    /// Save checkpoint every that many generations
+    #[arg(long, default_value_t = NonZeroUsize::new(10).unwrap())]
+    checkpoint_interval: NonZeroUsize,
+
+    /// Resume the search from the checkpoint file instead of starting with a new population
+    #[arg(long, requires = "checkpoint_file")]
+    resume: bool,
+
+    /// Width of random grids
+    #[arg(long, default_value_t = 4)]
+    width: u8,
+
+    /// Height of random grids
+    #[arg(long, default_value_t = 4)]
+    height: u8,
+
+    /// Number of pieces of each player in random positions, e.g. amazons or skiers
+    #[arg(long, default_value_t = 1)]
+    pieces: u8,
+
+    /// Probability that a tile without pieces is filled in random grids
+    #[arg(long, default_value_t = 0.3)]
+    density: f64,
+
+    /// Number of vertices of random graphs
+    #[arg(long, default_value_t = 8)]
+    vertices: usize,
+
+    /// Probability that an edge is present in random graphs
+    #[arg(long, default_value_t = 0.3)]
+    edge_probability: f64,
+
+    /// Initial position in the `game:payload` format, can be passed many times. Rest of the first
+    /// generation is random. Local searches start from the first seed
+    #[arg(long)]
+    seed: Vec<String>,
+
+    /// Read initial positions from that file, one position in the `game:payload` format per line.
+    /// Empty lines and lines starting with '#' are skipped
+    #[arg(long)]
+    seed_file: Option<PathBuf>,
+
+    /// Seed of the random number generator. Runs with the same seed and arguments find the same
+    /// positions. Drawn at random if not given, and always written to diagnostics
+    #[arg(long)]
+    rng_seed: Option<u64>,
+
+    /// Score positions with a Rhai script instead of their temperature, e.g.
+    /// `temperature - degree / 4.0`. See the README for the available variables
+    #[arg(long, conflicts_with = "fitness_file")]
+    fitness: Option<String>,
+
+    /// Read the fitness script from that file
+    #[arg(long)]
+    fitness_file: Option<PathBuf>,
+
+    /// Maximize that objective, can be passed many times. Together with `--minimize` runs a
+    /// multi-objective search that tracks the Pareto front of found positions
+    #[arg(long, value_enum, conflicts_with_all = ["fitness", "fitness_file"])]
+    maximize: Vec<Objective>,
+
+    /// Minimize that objective, can be passed many times
+    #[arg(long, value_enum, conflicts_with_all = ["fitness", "fitness_file"])]
+    minimize: Vec<Objective>,
+
+    /// Overwrite that file with the current Pareto front, one position per line, whenever the
+    /// front changes
+    #[arg(long)]
+    front_file: Option<PathBuf>,
+
+    /// Output positions with temperature, or fitness if given a fitness script, at least that
+    /// value. Multi-objective searches output positions that join the Pareto front instead
+    #[arg(long, default_value_t = Rational::from(0))]
+    temperature_threshold: Rational,
+
+    /// Give up on positions that take longer than that many milliseconds to evaluate, scoring
+    /// them as lowest possible
+    #[arg(long, default_value = None)]
+    evaluation_timeout_ms: Option<u64>,
+
+    /// Database of already computed positions. Positions found there are not evaluated again,
+    /// and new results are added to it
+    #[arg(long)]
+    db: Option<PathBuf>,
+
+    /// Path to output file with found positions
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+
+    /// Path to output progress of the search
+    #[arg(long, default_value = "-")]
+    diagnostics: FileOrStderr,
+}
+
+/// Coarse class of the value: `number`, `nus` (number, ups and a nimber), `tepid` (not a nus but
+/// temperature zero) or `hot`
+pub fn value_class(canonical_form: &CanonicalForm) -> &'static str {
+    if canonical_form.to_number().is_some() {
+        "number"
+    } else if canonical_form.to_nus().is_some() {
+        "nus"
+    } else if canonical_form.temperature() > DyadicRationalNumber::from(0) {
+        "hot"
+    } else {
+        "tepid"
+    }
+}
+
+/// Games that can be searched with genetic algorithm
+pub trait Mutate:
+    NamedGame
+    + RandomPosition
+    + Chromosome
+    + Clone
+    + Hash
+    + Eq
+    + Send
+    + Sync
+    + Serialize
+    + DeserializeOwned
+{
+    /// Parameters of random positions of the first generation
+    fn parameters(args: &Args) -> Self::Parameters;
+
+    /// Number of vertices, for games played on graphs
+    fn vertices(&self) -> Option<usize> {
+        None
+    }
+
+    /// Highest vertex degree, for games played on graphs
+    fn degree(&self) -> Option<usize> {
+        None
+    }
+}
+
+const fn grid_parameters(args: &Args) -> GridParameters {
+    GridParameters {
+        width: args.width,
+        height: args.height,
+        pieces: args.pieces,
+        density: args.density,
+    }
+}
+
+impl Mutate for Domineering {
+    fn parameters(args: &Args) -> Self::Parameters {
+        grid_parameters(args)
+    }
+}
+
+impl Mutate for Amazons {
+    fn parameters(args: &Args) -> Self::Parameters {
+        grid_parameters(args)
+    }
+}
+
+impl Mutate for Fission {
+    fn parameters(args: &Args) -> Self::Parameters {
+        grid_parameters(args)
+    }
+}
+
+impl Mutate for SkiJumps {
+    fn parameters(args: &Args) -> Self::Parameters {
+        grid_parameters(args)
+    }
+}
+
+impl Mutate for ToadsAndFrogs {
+    fn parameters(args: &Args) -> Self::Parameters {
+        grid_parameters(args)
+    }
+}
+
+impl Mutate for Snort {
+    fn parameters(args: &Args) -> Self::Parameters {
+        GraphParameters {
+            vertices: args.vertices,
+            edge_probability: args.edge_probability,
+        }
+    }
+
+    fn vertices(&self) -> Option<usize> {
+        Some(self.graph.size())
+    }
+
+    fn degree(&self) -> Option<usize> {
+        Some(Snort::degree(self))
+    }
+}
+
+struct HighTemperature<G>
+where
+    G: Mutate,
+{
+    transposition_table: ParallelTranspositionTable<G>,
+    parameters: G::Parameters,
+    mutation_rate: f32,
+    evaluation_timeout: Option<Duration>,
+    database: Option<Mutex<Database>>,
+    fitness: Option<FitnessScript>,
+    goals: Vec<Goal>,
+    front: Mutex<ParetoFront<G, Rational>>,
+}
+
+impl<G> HighTemperature<G>
+where
+    G: Mutate,
+    G::Parameters: Sync,
+{
+    /// Canonical form of the position, or `None` if it is not worth scoring
+    fn canonical_form(&self, position: &G) -> Option<CanonicalForm> {
+        // Sums of positions are not interesting, their temperature is one of the components
+        if position.decompositions().len() != 1 {
+            return None;
+        }
+
+        match self.evaluation_timeout {
+            Some(timeout) => position
+                .canonical_form_cancellable(
+                    &self.transposition_table,
+                    &Cancellation::with_timeout(timeout),
+                )
+                .ok(),
+            None => Some(position.canonical_form(&self.transposition_table)),
+        }
+    }
+
+    /// Canonical forms of positions worth scoring, taken from the database if given
+    fn canonical_forms(&self, positions: &[G]) -> Vec<Option<CanonicalForm>> {
+        let Some(database) = &self.database else {
+            return positions
+                .par_iter()
+                .map(|position| self.canonical_form(position))
+                .collect();
+        };
+
+        let keys = positions
+            .iter()
+            .map(|position| format!("{}:{}", G::NAME, position))
+            .collect::<Vec<_>>();
+        let cached = database
+            .lock()
+            .unwrap()
+            .get_all(keys.iter().map(String::as_str))
+            .expect("Could not read database");
+        let computed = positions
+            .par_iter()
+            .zip(&cached)
+            .map(|(position, cached)| match cached {
+                Some(_) => None,
+                None => self.canonical_form(position).map(Entry::new),
+            })
+            .collect::<Vec<_>>();
+        database
+            .lock()
+            .unwrap()
+            .insert_all(
+                keys.iter()
+                    .zip(&computed)
+                    .filter_map(|(key, entry)| Some((key.as_str(), entry.as_ref()?))),
+            )
+            .expect("Could not write database");
+
+        cached
+            .into_iter()
+            .zip(computed)
+            .map(|(cached, computed)| Some(cached.or(computed)?.canonical_form))
+            .collect()
+    }
+
+    /// Values of the objectives of multi-objective searches, negated if minimized
+    fn objectives(&self, position: &G, canonical_form: &CanonicalForm) -> Vec<Rational> {
+        self.goals
+            .iter()
+            .map(|goal| {
+                let value = goal
+                    .objective
+                    .value(position, canonical_form)
+                    .expect("unreachable: objectives are checked before the search");
+                if goal.maximize {
+                    value
+                } else {
+                    value.checked_neg().expect("objective overflowed")
+                }
+            })
+            .collect()
+    }
+
+    /// Score positions by the number of positions, in the Pareto front and among `positions`,
+    /// that dominate them, and add them to the front
+    fn rank(&self, positions: &[G], canonical_forms: Vec<Option<CanonicalForm>>) -> Vec<Rational> {
+        let objectives = positions
+            .iter()
+            .zip(canonical_forms)
+            .map(|(position, canonical_form)| {
+                canonical_form.map(|canonical_form| self.objectives(position, &canonical_form))
+            })
+            .collect::<Vec<_>>();
+
+        let mut front = self.front.lock().unwrap();
+        let scores = objectives
+            .iter()
+            .map(|objectives_of| {
+                objectives_of
+                    .as_ref()
+                    .map_or(Rational::NegativeInfinity, |objectives_of| {
+                        let dominating = front.dominated_by(objectives_of)
+                            + objectives
+                                .iter()
+                                .flatten()
+                                .filter(|other| pareto::dominates(other, objectives_of))
+                                .count();
+                        Rational::from(-(dominating as i64))
+                    })
+            })
+            .collect();
+        for (position, objectives) in positions.iter().zip(objectives) {
+            if let Some(objectives) = objectives {
+                front.insert(position.clone(), objectives);
+            }
+        }
+        scores
+    }
+
+    /// Temperature of the position, or the value of the fitness script if given
+    fn score_canonical_form(&self, position: &G, canonical_form: CanonicalForm) -> Rational {
+        match &self.fitness {
+            None => canonical_form.temperature().to_rational(),
+            Some(fitness) => fitness
+                .eval(&Features {
+                    canonical_form,
+                    vertices: position.vertices(),
+                    degree: position.degree(),
+                })
+                .unwrap_or_else(|err| panic!("Could not score '{}:{position}': {err}", G::NAME)),
+        }
+    }
+}
+
+impl<G> Algorithm<G, Rational> for HighTemperature<G>
+where
+    G: Mutate,
+    G::Parameters: Sync,
+{
+    fn mutate(&self, position: &mut G, rng: &mut StdRng) {
+        position.mutate(self.mutation_rate, rng);
+    }
+
+    fn cross(&self, lhs: &G, rhs: &G, rng: &mut StdRng) -> G {
+        lhs.crossover(rhs, rng)
+    }
+
+    fn lowest_score(&self) -> Rational {
+        Rational::NegativeInfinity
+    }
+
+    fn score(&self, position: &G) -> Rational {
+        if !self.goals.is_empty() || self.database.is_some() {
+            return self.score_all(std::slice::from_ref(position))[0];
+        }
+
+        self.canonical_form(position)
+            .map_or(Rational::NegativeInfinity, |canonical_form| {
+                self.score_canonical_form(position, canonical_form)
+            })
+    }
+
+    fn score_all(&self, positions: &[G]) -> Vec<Rational> {
+        let canonical_forms = self.canonical_forms(positions);
+        if !self.goals.is_empty() {
+            return self.rank(positions, canonical_forms);
+        }
+
+        positions
+            .par_iter()
+            .zip(canonical_forms)
+            .map(|(position, canonical_form)| {
+                canonical_form.map_or(Rational::NegativeInfinity, |canonical_form| {
+                    self.score_canonical_form(position, canonical_form)
+                })
+            })
+            .collect()
+    }
+
+    fn random(&self, rng: &mut StdRng) -> G {
+        G::random_position(&self.parameters, rng)
+            .expect("unreachable: parameters are checked before the search")
+    }
+}
+
+/// Found position with its temperature, or fitness if searching with a fitness script
+#[derive(Serialize)]
+struct Found {
+    position: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fitness: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    objectives: Option<BTreeMap<&'static str, Rational>>,
+}
+
+#[derive(Serialize)]
+struct Progress {
+    generation: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_temperature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_fitness: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    front_size: Option<usize>,
+    position: String,
+}
+
+/// Compile fitness script and run it once on `example`, so errors are reported before the search
+/// starts
+fn load_fitness<G>(args: &Args, example: &G) -> Result<Option<FitnessScript>>
+where
+    G: Mutate,
+{
+    let source = match (&args.fitness, &args.fitness_file) {
+        (Some(source), _) => source.clone(),
+        (None, Some(path)) => fs::read_to_string(path)
+            .with_context(|| format!("Could not read fitness script '{}'", path.display()))?,
+        (None, None) => return Ok(None),
+    };
+    let fitness = FitnessScript::new(&source)?;
+    fitness.eval(&Features {
+        canonical_form: CanonicalForm::new_integer(0),
+        vertices: example.vertices(),
+        degree: example.degree(),
+    })?;
+    Ok(Some(fitness))
+}
+
+fn parse_seed<G>(seed: &str) -> Result<G>
+where
+    G: Mutate,
+{
+    let Some(payload) = seed
+        .strip_prefix(G::NAME)
+        .and_then(|payload| payload.strip_prefix(':'))
+    else {
+        bail!("Seed '{seed}' is not a position of {}", G::NAME);
+    };
+    G::from_str(payload).map_err(|err| {
+        let err: cgt::error::Error = err.into();
+        anyhow!("Could not parse seed '{seed}': {err}")
+    })
+}
+
+/// Read positions from lines of a file, skipping empty lines and '#' comments
+fn load_seeds<G>(path: &Path) -> Result<Vec<G>>
+where
+    G: Mutate,
+{
+    let file = File::open(path)
+        .with_context(|| format!("Could not open seed file '{}'", path.display()))?;
+    let mut seeds = Vec::new();
+    for (idx, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.with_context(|| format!("Could not read '{}'", path.display()))?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        seeds.push(
+            parse_seed(line)
+                .with_context(|| format!("Invalid seed at line {} of '{}'", idx + 1, path.display()))?,
+        );
+    }
+    Ok(seeds)
+}
+
+fn load_checkpoint<G>(path: &Path) -> Result<Checkpoint<G, Rational>>
+where
+    G: Mutate,
+{
+    let file = File::open(path)
+        .with_context(|| format!("Could not open checkpoint file '{}'", path.display()))?;
+    serde_json::from_reader(BufReader::new(file))
+        .with_context(|| format!("Could not read checkpoint file '{}'", path.display()))
+}
+
+/// Write checkpoint to a temporary file first, so interrupted write does not corrupt the
+/// previous checkpoint
+fn save_checkpoint<G>(path: &Path, checkpoint: &Checkpoint<G, Rational>) -> Result<()>
+where
+    G: Mutate,
+{
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    let temporary = PathBuf::from(temporary);
+
+    let mut writer = BufWriter::new(File::create(&temporary).with_context(|| {
+        format!("Could not create checkpoint file '{}'", temporary.display())
+    })?);
+    serde_json::to_writer(&mut writer, checkpoint)?;
+    writer.into_inner()?.sync_all()?;
+    fs::rename(&temporary, path)
+        .with_context(|| format!("Could not save checkpoint file '{}'", path.display()))?;
+    Ok(())
+}
+
+/// Search algorithm scoring positions with [`HighTemperature`]
+trait Driver<G>
+where
+    G: Mutate,
+{
+    /// Run one generation, or one step of a local search
+    fn step(&mut self);
+
+    /// Number of finished generations or steps
+    fn steps(&self) -> usize;
+
+    /// Positions reached in the last step
+    fn scored(&self) -> &[Scored<G, Rational>];
+
+    fn highest_score(&self) -> &Scored<G, Rational>;
+
+    fn algorithm(&self) -> &HighTemperature<G>;
+
+    /// State to save, if the driver can be resumed
+    fn checkpoint(&self) -> Option<Checkpoint<G, Rational>> {
+        None
+    }
+}
+
+impl<G> Driver<G> for GeneticAlgorithm<HighTemperature<G>, G, Rational>
+where
+    G: Mutate,
+    G::Parameters: Sync,
+{
+    fn step(&mut self) {
+        self.step_generation();
+    }
+
+    fn steps(&self) -> usize {
+        self.generation()
+    }
+
+    fn scored(&self) -> &[Scored<G, Rational>] {
+        self.specimen()
+    }
+
+    fn highest_score(&self) -> &Scored<G, Rational> {
+        self.highest_score()
+    }
+
+    fn algorithm(&self) -> &HighTemperature<G> {
+        self.algorithm()
+    }
+
+    fn checkpoint(&self) -> Option<Checkpoint<G, Rational>> {
+        Some(self.checkpoint())
+    }
+}
+
+impl<G> Driver<G> for SimulatedAnnealing<HighTemperature<G>, G, Rational>
+where
+    G: Mutate,
+    G::Parameters: Sync,
+{
+    fn step(&mut self) {
+        self.step();
+    }
+
+    fn steps(&self) -> usize {
+        self.steps()
+    }
+
+    fn scored(&self) -> &[Scored<G, Rational>] {
+        std::slice::from_ref(self.current())
+    }
+
+    fn highest_score(&self) -> &Scored<G, Rational> {
+        self.highest_score()
+    }
+
+    fn algorithm(&self) -> &HighTemperature<G> {
+        self.algorithm()
+    }
+}
+
+impl<G> Driver<G> for HillClimbing<HighTemperature<G>, G, Rational>
+where
+    G: Mutate,
+    G::Parameters: Sync,
+{
+    fn step(&mut self) {
+        self.step();
+    }
+
+    fn steps(&self) -> usize {
+        self.steps()
+    }
+
+    fn scored(&self) -> &[Scored<G, Rational>] {
+        std::slice::from_ref(self.current())
+    }
+
+    fn highest_score(&self) -> &Scored<G, Rational> {
+        self.highest_score()
+    }
+
+    fn algorithm(&self) -> &HighTemperature<G> {
+        self.algorithm()
+    }
+}
+
+/// Objectives of multi-objective searches, checking that they are defined for `example`
+fn load_goals<G>(args: &Args, example: &G) -> Result<Vec<Goal>>
+where
+    G: Mutate,
+{
+    let goals = args
+        .maximize
+        .iter()
+        .map(|&objective| Goal {
+            objective,
+            maximize: true,
+        })
+        .chain(args.minimize.iter().map(|&objective| Goal {
+            objective,
+            maximize: false,
+        }))
+        .collect::<Vec<_>>();
+    for goal in &goals {
+        if goal
+            .objective
+            .value(example, &CanonicalForm::new_integer(0))
+            .is_none()
+        {
+            bail!(
+                "Objective '{}' is not defined for {}",
+                goal.objective.name(),
+                G::NAME
+            );
+        }
+    }
+    Ok(goals)
+}
+
+/// Member of the Pareto front with values of objectives, un-negating minimized objectives
+fn front_member<G>(goals: &[Goal], member: &Scored<G, Vec<Rational>>) -> Found
+where
+    G: Mutate,
+{
+    let objectives = goals
+        .iter()
+        .zip(&member.score)
+        .map(|(goal, &value)| {
+            let value = if goal.maximize {
+                value
+            } else {
+                value.checked_neg().expect("objective overflowed")
+            };
+            (goal.objective.name(), value)
+        })
+        .collect();
+    Found {
+        position: format!("{}:{}", G::NAME, member.object),
+        temperature: None,
+        fitness: None,
+        objectives: Some(objectives),
+    }
+}
+
+fn save_front<G>(path: &Path, goals: &[Goal], front: &ParetoFront<G, Rational>) -> Result<()>
+where
+    G: Mutate,
+{
+    let mut writer = BufWriter::new(
+        File::create(path)
+            .with_context(|| format!("Could not create front file '{}'", path.display()))?,
+    );
+    for member in front.members() {
+        writeln!(
+            writer,
+            "{}",
+            serde_json::ser::to_string(&front_member(goals, member))?
+        )?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn search<G>(args: &Args) -> Result<()>
+where
+    G: Mutate,
+    G::Parameters: Sync,
+{
+    let rng_seed = args.rng_seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(rng_seed);
+    let selection = args.selection.selection()?;
+
+    let parameters = G::parameters(args);
+    let Some(example) = G::random_position(&parameters, &mut rng) else {
+        bail!("No {} position matches the given parameters", G::NAME);
+    };
+
+    let alg = HighTemperature {
+        transposition_table: ParallelTranspositionTable::new(),
+        parameters,
+        mutation_rate: args.mutation_rate,
+        evaluation_timeout: args.evaluation_timeout_ms.map(Duration::from_millis),
+        database: args
+            .db
+            .as_deref()
+            .map(Database::open)
+            .transpose()?
+            .map(Mutex::new),
+        fitness: load_fitness(args, &example)?,
+        goals: load_goals(args, &example)?,
+        front: Mutex::new(ParetoFront::new()),
+    };
+    let multi_objective = !alg.goals.is_empty();
+    // Scores are temperatures unless searching with a fitness script
+    let label = |score: &Rational| {
+        if args.fitness.is_some() || args.fitness_file.is_some() {
+            (None, Some(score.to_string()))
+        } else {
+            (Some(score.to_string()), None)
+        }
+    };
+    let mut specimen = args
+        .seed
+        .iter()
+        .map(|seed| parse_seed::<G>(seed))
+        .collect::<Result<Vec<_>>>()?;
+    if let Some(seed_file) = &args.seed_file {
+        specimen.extend(load_seeds::<G>(seed_file)?);
+    }
+    let mut alg: Box<dyn Driver<G>> = match args.mode {
+        Mode::Genetic => {
+            let Some(generation_size) = args.generation_size else {
+                bail!("Genetic search requires --generation-size");
+            };
+            let mut genetic = match &args.checkpoint_file {
+                Some(checkpoint_file) if args.resume => {
+                    GeneticAlgorithm::from_checkpoint(load_checkpoint(checkpoint_file)?, alg)
+                }
+                _ => GeneticAlgorithm::with_specimen_seeded(
+                    specimen,
+                    generation_size,
+                    alg,
+                    rng.gen(),
+                ),
+            };
+            genetic.set_selection(selection);
+            Box::new(genetic)
+        }
+        Mode::Annealing | Mode::HillClimbing if args.checkpoint_file.is_some() => {
+            bail!("Checkpoints are supported only by the genetic search");
+        }
+        Mode::Annealing => {
+            let cooling = Cooling {
+                initial_temperature: args.annealing_temperature,
+                rate: args.cooling_rate,
+            };
+            let object = if specimen.is_empty() {
+                alg.random(&mut rng)
+            } else {
+                specimen.swap_remove(0)
+            };
+            Box::new(SimulatedAnnealing::with_object_seeded(
+                object,
+                cooling,
+                alg,
+                rng.gen(),
+            ))
+        }
+        Mode::HillClimbing => {
+            let object = if specimen.is_empty() {
+                alg.random(&mut rng)
+            } else {
+                specimen.swap_remove(0)
+            };
+            Box::new(HillClimbing::with_object_seeded(
+                object,
+                args.neighbours,
+                alg,
+                rng.gen(),
+            ))
+        }
+    };
+
+    let mut output = args.out_file.create()?;
+    let mut diagnostics = args.diagnostics.create()?;
+    // Resumed searches continue with the generator state saved in the checkpoint
+    writeln!(
+        diagnostics,
+        "{}",
+        serde_json::ser::to_string(&Log::new(Record::run(
+            rng_seed,
+            (args.mode == Mode::Genetic).then(|| Operators::new(selection)),
+        )))?
+    )?;
+    let mut found = HashSet::new();
+
+    let unit = if args.mode == Mode::Genetic {
+        "generations"
+    } else {
+        "steps"
+    };
+    let generations = args.generation_limit.map_or_else(
+        || progress::spinner(unit),
+        |limit| progress::bar(limit as u64, unit),
+    );
+    generations.set_position(alg.steps() as u64);
+
+    while args.generation_limit.is_none_or(|limit| alg.steps() < limit) {
+        alg.step();
+
+        let algorithm = alg.algorithm();
+        let front = algorithm.front.lock().unwrap();
+        if multi_objective {
+            let mut changed = false;
+            for member in front.members() {
+                if found.insert(member.object.clone()) {
+                    changed = true;
+                    let result = front_member(&algorithm.goals, member);
+                    writeln!(output, "{}", serde_json::ser::to_string(&result)?)?;
+                }
+            }
+            if let (Some(front_file), true) = (&args.front_file, changed) {
+                save_front(front_file, &algorithm.goals, &front)?;
+            }
+        } else {
+            for specimen in alg.scored() {
+                if specimen.score >= args.temperature_threshold
+                    && found.insert(specimen.object.clone())
+                {
+                    let (temperature, fitness) = label(&specimen.score);
+                    let result = Found {
+                        position: format!("{}:{}", G::NAME, specimen.object),
+                        temperature,
+                        fitness,
+                        objectives: None,
+                    };
+                    writeln!(output, "{}", serde_json::ser::to_string(&result)?)?;
+                }
+            }
+        }
+        output.flush()?;
+
+        let best = alg.highest_score();
+        let (top_temperature, top_fitness) = if multi_objective {
+            (None, None)
+        } else {
+            label(&best.score)
+        };
+        let progress = Progress {
+            generation: alg.steps(),
+            top_temperature,
+            top_fitness,
+            front_size: multi_objective.then(|| front.len()),
+            position: format!("{}:{}", G::NAME, best.object),
+        };
+        drop(front);
+        // Diagnostics go to stderr by default, where they would break the progress bar
+        generations.suspend(|| -> Result<()> {
+            writeln!(diagnostics, "{}", serde_json::ser::to_string(&progress)?)?;
+            diagnostics.flush()?;
+            Ok(())
+        })?;
+        generations.set_position(alg.steps() as u64);
+
+        if let Some(checkpoint_file) = &args.checkpoint_file {
+            if alg.steps() % args.checkpoint_interval.get() == 0 {
+                if let Some(checkpoint) = alg.checkpoint() {
+                    save_checkpoint(checkpoint_file, &checkpoint)?;
+                }
+            }
+        }
+    }
+
+    generations.finish();
+
+    if let (Some(checkpoint_file), Some(checkpoint)) = (&args.checkpoint_file, alg.checkpoint()) {
+        save_checkpoint(checkpoint_file, &checkpoint)?;
+    }
+
+    Ok(())
+}
+
+pub fn run(args: Args) -> Result<()> {
+    match args.game {
+        Game::Amazons => search::<Amazons>(&args),
+        Game::Domineering => search::<Domineering>(&args),
+        Game::Fission => search::<Fission>(&args),
+        Game::SkiJumps => search::<SkiJumps>(&args),
+        Game::Snort => search::<Snort>(&args),
+        Game::ToadsAndFrogs => search::<ToadsAndFrogs>(&args),
+    }
+}
@@ -0,0 +1,88 @@
+use crate::commands::search::value_class;
+use anyhow::{anyhow, Result};
+use cgt::{
+    numeric::{dyadic_rational_number::DyadicRationalNumber, rational::Rational},
+    short::partizan::canonical_form::CanonicalForm,
+};
+use num_rational::Rational64;
+use rhai::{Dynamic, Engine, Scope, AST};
+
+/// Properties of a position available to fitness scripts
+pub struct Features {
+    pub canonical_form: CanonicalForm,
+
+    /// Number of vertices of positions played on graphs
+    pub vertices: Option<usize>,
+
+    /// Highest vertex degree of positions played on graphs
+    pub degree: Option<usize>,
+}
+
+impl Features {
+    /// Variables seen by scripts, see [`FitnessScript`]
+    pub fn scope(&self) -> Scope<'static> {
+        let canonical_form = &self.canonical_form;
+        let optional = |value: Option<usize>| value.map_or(Dynamic::UNIT, |v| (v as i64).into());
+
+        let mut scope = Scope::new();
+        scope.push_constant("temperature", to_float(canonical_form.temperature()));
+        scope.push_constant("mean", to_float(canonical_form.mean()));
+        scope.push_constant("left_stop", to_float(canonical_form.left_stop()));
+        scope.push_constant("right_stop", to_float(canonical_form.right_stop()));
+        scope.push_constant("class", value_class(canonical_form));
+        scope.push_constant("vertices", optional(self.vertices));
+        scope.push_constant("degree", optional(self.degree));
+        scope
+    }
+}
+
+/// Fitness function given as a [Rhai](https://rhai.rs) script
+///
+/// Scripts see variables `temperature`, `mean`, `left_stop`, `right_stop` (floats), `class`
+/// (string, see [`value_class`]), and `vertices` and `degree` (integers in games played on
+/// graphs, unit otherwise). Value of the last expression, an integer or a float, is the fitness.
+pub struct FitnessScript {
+    engine: Engine,
+    ast: AST,
+}
+
+impl FitnessScript {
+    pub fn new(source: &str) -> Result<Self> {
+        let engine = Engine::new();
+        let ast = engine
+            .compile(source)
+            .map_err(|err| anyhow!("Could not compile fitness script: {err}"))?;
+        Ok(Self { engine, ast })
+    }
+
+    pub fn eval(&self, features: &Features) -> Result<Rational> {
+        let mut scope = features.scope();
+        let fitness = self
+            .engine
+            .eval_ast_with_scope::<Dynamic>(&mut scope, &self.ast)
+            .map_err(|err| anyhow!("Fitness script failed: {err}"))?;
+        if let Some(fitness) = fitness.clone().try_cast::<i64>() {
+            Ok(Rational::from(fitness))
+        } else if let Some(fitness) = fitness.clone().try_cast::<f64>() {
+            Ok(float_to_rational(fitness))
+        } else {
+            Err(anyhow!(
+                "Fitness script returned {}, expected a number",
+                fitness.type_name()
+            ))
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn to_float(value: DyadicRationalNumber) -> f64 {
+    value.numerator() as f64 / f64::from(value.denominator_exponent()).exp2()
+}
+
+fn float_to_rational(value: f64) -> Rational {
+    if value == f64::INFINITY {
+        Rational::PositiveInfinity
+    } else {
+        Rational64::approximate_float(value).map_or(Rational::NegativeInfinity, Rational::Value)
+    }
+}
@@ -1,8 +1,11 @@
 mod common;
 
 crate::clap_utils::mk_subcommand! {
+    Census => census,
     Genetic => genetic,
     Latex => latex,
     Graph => graph,
     ThreeCaterpillar => three_caterpillar,
+    Sample => sample,
+    Eval => eval,
 }
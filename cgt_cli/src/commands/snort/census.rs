@@ -0,0 +1,161 @@
+use crate::{
+    database::{Database, Entry},
+    io::{FileOrStdin, FileOrStdout},
+    progress,
+};
+use anyhow::{bail, Context, Result};
+use cgt::{
+    graph::{adjacency_matrix::undirected::UndirectedGraph, graph6, Graph},
+    numeric::dyadic_rational_number::DyadicRationalNumber,
+    short::partizan::{
+        batch::evaluate_all_with_progress, games::snort::Snort, position::NamedGame,
+        transposition_table::ParallelTranspositionTable,
+    },
+};
+use clap::Parser;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Write},
+    num::NonZeroUsize,
+    path::PathBuf,
+};
+
+#[derive(Parser, Debug, Clone)]
+/// Evaluate Snort positions on every graph of a stream of graph6 graphs, e.g. output of nauty's
+/// `geng`
+pub struct Args {
+    /// Input file with one graph6 graph per line. Use '-' for stdin
+    #[arg(long, default_value = "-")]
+    in_file: FileOrStdin,
+
+    /// Output file with newline-separated JSON results, in the order of input graphs
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+
+    /// Number of graphs read and evaluated in parallel at once
+    #[arg(long, default_value_t = NonZeroUsize::new(1024).unwrap())]
+    batch_size: NonZeroUsize,
+
+    /// Skip graphs that are not connected
+    #[arg(long)]
+    connected_only: bool,
+
+    /// Database of already computed positions. Positions found there are not evaluated again,
+    /// and new results are added to it
+    #[arg(long)]
+    db: Option<PathBuf>,
+}
+
+#[derive(serde::Serialize)]
+struct CensusResult {
+    graph6: String,
+    canonical_form: String,
+    temperature: DyadicRationalNumber,
+    degree: usize,
+}
+
+struct Graph6Position {
+    graph6: String,
+    position: Snort,
+    key: String,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let input = BufReader::new(args.in_file.open().context("Could not open input file")?);
+    let mut output = args.out_file.create().context("Could not open output file")?;
+    let transposition_table = ParallelTranspositionTable::new();
+    let mut database = args.db.as_deref().map(Database::open).transpose()?;
+
+    // Length of stdin is not known up front, so only input files get an ETA
+    let progress = match &args.in_file {
+        FileOrStdin::FilePath(path) => {
+            let lines = BufReader::new(File::open(path).context("Could not open input file")?)
+                .lines()
+                .count();
+            progress::bar(lines as u64, "graphs")
+        }
+        FileOrStdin::FileOrStdin => progress::spinner("graphs"),
+    };
+
+    let mut lines = input.lines().enumerate();
+    loop {
+        let mut batch = Vec::with_capacity(args.batch_size.get());
+        let mut read = 0;
+        for (line_number, line) in lines.by_ref().take(args.batch_size.get()) {
+            read += 1;
+            let line = line.context("Could not read input")?;
+            let line = line.trim();
+            if line.is_empty() {
+                progress.inc(1);
+                continue;
+            }
+
+            let Some(graph) = graph6::from_graph6::<UndirectedGraph>(line) else {
+                bail!("Invalid graph6 graph at line {}: '{line}'", line_number + 1);
+            };
+            if args.connected_only && !graph.is_connected() {
+                progress.inc(1);
+                continue;
+            }
+            let position = Snort::new(graph);
+            batch.push(Graph6Position {
+                graph6: line.to_owned(),
+                key: format!("{}:{}", <Snort>::NAME, position),
+                position,
+            });
+        }
+        if read == 0 {
+            break;
+        }
+
+        let cached = match &database {
+            Some(database) => database.get_all(batch.iter().map(|entry| entry.key.as_str()))?,
+            None => vec![None; batch.len()],
+        };
+        let missing = batch
+            .iter()
+            .zip(&cached)
+            .filter(|(_, cached)| cached.is_none())
+            .map(|(entry, _)| entry.position.clone())
+            .collect::<Vec<_>>();
+        progress.inc((batch.len() - missing.len()) as u64);
+        let mut evaluations =
+            evaluate_all_with_progress(&missing, &transposition_table, |_| progress.inc(1))
+                .into_iter();
+        let entries = cached
+            .into_iter()
+            .map(|cached| {
+                cached.unwrap_or_else(|| {
+                    Entry::new(
+                        evaluations
+                            .next()
+                            .expect("unreachable: every missing position is evaluated")
+                            .canonical_form,
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        if let Some(database) = &mut database {
+            database.insert_all(
+                batch
+                    .iter()
+                    .map(|entry| entry.key.as_str())
+                    .zip(&entries),
+            )?;
+        }
+
+        for (entry, values) in batch.into_iter().zip(entries) {
+            let result = CensusResult {
+                degree: entry.position.degree(),
+                graph6: entry.graph6,
+                canonical_form: values.canonical_form.to_string(),
+                temperature: values.temperature,
+            };
+            writeln!(output, "{}", serde_json::ser::to_string(&result)?)?;
+        }
+        output.flush()?;
+    }
+    progress.finish();
+
+    Ok(())
+}
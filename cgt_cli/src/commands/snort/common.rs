@@ -1,7 +1,6 @@
 use anyhow::{bail, Context, Result};
 use cgt::{
-    genetic_algorithm::Scored,
-    graph::{adjacency_matrix::undirected::UndirectedGraph, Graph},
+    graph::{adjacency_matrix::undirected::UndirectedGraph, Graph, VertexIndex},
     numeric::{dyadic_rational_number::DyadicRationalNumber, rational::Rational},
     short::partizan::{
         canonical_form::CanonicalForm, games::snort::Snort, partizan_game::PartizanGame,
@@ -15,21 +14,6 @@ use std::{
     time,
 };
 
-#[derive(serde::Serialize, serde::Deserialize)]
-pub enum Log {
-    Generation {
-        generation: usize,
-        top_score: Rational,
-        temperature: DyadicRationalNumber,
-    },
-    HighFitness {
-        position: Scored<Snort, Rational>,
-        canonical_form: String,
-        temperature: DyadicRationalNumber,
-        degree: usize,
-    },
-}
-
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct EvaluationResult {
     pub position: Snort,
@@ -64,6 +48,29 @@ impl FromStr for Edge {
     }
 }
 
+/// Graph with vertices up to the highest index in the edges
+pub fn graph_from_edges(edges: &[Edge]) -> UndirectedGraph {
+    let graph_size = edges
+        .iter()
+        .map(|edge| edge.from.max(edge.to))
+        .max()
+        .unwrap_or(0);
+    let edges = edges
+        .iter()
+        .map(|edge| {
+            (
+                VertexIndex {
+                    index: edge.from as usize,
+                },
+                VertexIndex {
+                    index: edge.to as usize,
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+    UndirectedGraph::from_edges((graph_size + 1) as usize, &edges)
+}
+
 fn dump_edges(w: &mut impl Write, graph: &UndirectedGraph) -> io::Result<()> {
     let mut first = true;
 
@@ -153,7 +160,7 @@ pub fn analyze_position(position: Snort, with_graphviz: bool) -> Result<()> {
     Ok(())
 }
 
-fn render_snort(position: &Snort, filename: &str, format: &str, engine: &str) -> Result<()> {
+pub fn render_snort(position: &Snort, filename: &str, format: &str, engine: &str) -> Result<()> {
     let mut graphviz_proc = Command::new(engine)
         .stdin(Stdio::piped())
         .arg(format!("-T{}", format))
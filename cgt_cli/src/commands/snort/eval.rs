@@ -0,0 +1,185 @@
+use crate::{
+    commands::snort::common::{graph_from_edges, render_snort, Edge},
+    io::FileOrStdin,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use cgt::{
+    graph::{graph6, Graph},
+    numeric::dyadic_rational_number::DyadicRationalNumber,
+    short::partizan::{
+        games::snort::{Snort, VertexColor},
+        partizan_game::PartizanGame,
+        position::NamedGame,
+        transposition_table::ParallelTranspositionTable,
+    },
+};
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+use std::{fs, io::Read, path::PathBuf, str::FromStr};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    /// Comma-separated edges `u-v`, vertices numbered from 0 up to the highest index
+    Edges,
+
+    /// nauty's graph6 or sparse6
+    Graph6,
+
+    /// Graphviz DOT with vertex colors, as written by `cgt-cli convert`
+    Dot,
+
+    /// Snort position in the `snort:payload` format
+    Position,
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Evaluate a single Snort position and print its canonical form, temperature, stops and
+/// components. Writes details to stderr and a JSON result to stdout
+pub struct Args {
+    /// Graph or position to evaluate. Read from `--in-file` if not given
+    graph: Option<String>,
+
+    /// Format of the graph
+    #[arg(long, value_enum, default_value_t = Format::Edges)]
+    format: Format,
+
+    /// Input file with the graph, used if the graph is not given. Use '-' for stdin
+    #[arg(long, default_value = "-")]
+    in_file: FileOrStdin,
+
+    /// Comma-separated list of vertices that are tinted blue/left
+    #[arg(long, value_delimiter = ',')]
+    tinted_left: Vec<usize>,
+
+    /// Comma-separated list of vertices that are tinted red/right
+    #[arg(long, value_delimiter = ',')]
+    tinted_right: Vec<usize>,
+
+    /// Write an image of the position. `.dot` files are written directly, other extensions
+    /// (e.g. `.png` or `.svg`) are rendered with Graphviz `fdp`
+    #[arg(long)]
+    render: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+struct Component {
+    position: String,
+    canonical_form: String,
+}
+
+#[derive(Serialize)]
+struct EvaluationResult {
+    position: String,
+    graph6: String,
+    canonical_form: String,
+    temperature: DyadicRationalNumber,
+    left_stop: DyadicRationalNumber,
+    right_stop: DyadicRationalNumber,
+    components: Vec<Component>,
+}
+
+fn parse(format: Format, input: &str) -> Result<Snort> {
+    let input = input.trim();
+    let invalid = || anyhow!("Invalid graph '{input}'");
+    match format {
+        Format::Edges => {
+            let edges = input
+                .split(',')
+                .map(str::trim)
+                .filter(|edge| !edge.is_empty())
+                .map(|edge| {
+                    Edge::from_str(edge).map_err(|err| anyhow!("Invalid edge '{edge}': {err}"))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Snort::new(graph_from_edges(&edges)))
+        }
+        Format::Graph6 => graph6::from_nauty(input).map(Snort::new).ok_or_else(invalid),
+        Format::Dot => Snort::from_graphviz(input).ok_or_else(invalid),
+        Format::Position => {
+            let payload = input
+                .strip_prefix(<Snort>::NAME)
+                .and_then(|payload| payload.strip_prefix(':'))
+                .unwrap_or(input);
+            Snort::from_str(payload).with_context(invalid)
+        }
+    }
+}
+
+fn tint(position: &mut Snort, vertices: &[usize], color: VertexColor) -> Result<()> {
+    for &v in vertices {
+        let Some(vertex) = position.vertices.inner.get_mut(v) else {
+            bail!(
+                "Cannot tint vertex {v}, graph has {} vertices",
+                position.graph.size()
+            );
+        };
+        *vertex.color_mut() = color;
+    }
+    Ok(())
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let input = match &args.graph {
+        Some(graph) => graph.clone(),
+        None => {
+            let mut input = String::new();
+            args.in_file
+                .open()
+                .context("Could not open input file")?
+                .read_to_string(&mut input)
+                .context("Could not read input file")?;
+            input
+        }
+    };
+    let mut position = parse(args.format, &input)?;
+    tint(&mut position, &args.tinted_left, VertexColor::TintLeft)?;
+    tint(&mut position, &args.tinted_right, VertexColor::TintRight)?;
+
+    if let Some(render) = &args.render {
+        if render.extension().is_some_and(|extension| extension == "dot") {
+            fs::write(render, position.to_graphviz())
+                .with_context(|| format!("Could not write '{}'", render.display()))?;
+        } else {
+            let format = render
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .context("Image file needs an extension, e.g. '.png'")?;
+            let filename = render.to_str().context("Image path is not valid UTF-8")?;
+            render_snort(&position, filename, format, "fdp")?;
+        }
+    }
+
+    let transposition_table = ParallelTranspositionTable::new();
+    let canonical_form = position.canonical_form(&transposition_table);
+    let components = position
+        .decompositions()
+        .into_iter()
+        .map(|component| Component {
+            canonical_form: component.canonical_form(&transposition_table).to_string(),
+            position: format!("{}:{}", <Snort>::NAME, component),
+        })
+        .collect::<Vec<_>>();
+    let result = EvaluationResult {
+        position: format!("{}:{}", <Snort>::NAME, position),
+        graph6: graph6::to_graph6(&position.graph),
+        temperature: canonical_form.temperature(),
+        left_stop: canonical_form.left_stop(),
+        right_stop: canonical_form.right_stop(),
+        canonical_form: canonical_form.to_string(),
+        components,
+    };
+
+    eprintln!("Position: {}", result.position);
+    eprintln!("Canonical Form: {}", result.canonical_form);
+    eprintln!("Temperature: {}", result.temperature);
+    eprintln!("Stops: {} {}", result.left_stop, result.right_stop);
+    for component in &result.components {
+        eprintln!(
+            "Component: {} = {}",
+            component.position, component.canonical_form
+        );
+    }
+    println!("{}", serde_json::ser::to_string(&result)?);
+
+    Ok(())
+}
@@ -1,37 +1,147 @@
-use crate::{commands::snort::common::Log, io::FileOrStderr};
-use anyhow::{Context, Result};
+use crate::{
+    commands::{
+        logs::{parse_snort, FrontMember, Log, Operators, Record},
+        search::SelectionArgs,
+    },
+    encoding::{self, Format, Writer},
+    io::FileOrStderr,
+};
+use anyhow::{bail, Context, Result};
 use cgt::{
-    genetic_algorithm::{Algorithm, GeneticAlgorithm, Scored},
-    graph::{adjacency_matrix::undirected, Graph, VertexIndex},
-    numeric::rational::Rational,
-    short::partizan::{
-        games::snort::{Snort, VertexColor, VertexKind},
-        partizan_game::PartizanGame,
-        transposition_table::ParallelTranspositionTable,
+    genetic::{
+        islands::{IslandModel, Migration},
+        snort::{Crossover, Mutation, TemperatureDegreeSearch},
+        Scored,
     },
+    graph::{adjacency_matrix::undirected, classes::GraphClass, Graph, VertexIndex},
+    numeric::rational::Rational,
+    pareto::ParetoFront,
+    short::partizan::{games::snort::Snort, partizan_game::PartizanGame},
+    zobrist::Zobrist,
 };
-use clap::{self, Parser};
-use rand::{seq::SliceRandom, Rng};
+use clap::{self, Parser, ValueEnum};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::{
-    cmp::min,
     fs::File,
-    io::{BufReader, BufWriter, Write},
+    io::{BufRead, BufReader, BufWriter},
     num::NonZeroUsize,
+    path::{Path, PathBuf},
+    time::Duration,
 };
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Class {
+    Tree,
+    Planar,
+    Bipartite,
+    Connected,
+}
+
+impl From<Class> for GraphClass {
+    fn from(class: Class) -> Self {
+        match class {
+            Class::Tree => Self::Tree,
+            Class::Planar => Self::Planar,
+            Class::Bipartite => Self::Bipartite,
+            Class::Connected => Self::Connected,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum MutationOperator {
+    /// Remove a random vertex
+    VertexRemove,
+
+    /// Add a vertex connected to a random vertex
+    VertexAdd,
+
+    /// Toggle edges
+    EdgeFlip,
+
+    /// Change colors of vertices
+    ColorFlip,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CrossoverScheme {
+    /// First vertices from the smaller graph and the rest from the larger one
+    Prefix,
+
+    /// Every edge and color from a random parent
+    Uniform,
+}
+
+impl From<CrossoverScheme> for Crossover {
+    fn from(scheme: CrossoverScheme) -> Self {
+        match scheme {
+            CrossoverScheme::Prefix => Self::Prefix,
+            CrossoverScheme::Uniform => Self::Uniform,
+        }
+    }
+}
+
 #[derive(Parser, Debug, Clone)]
 /// Run genetic algorithm on Snort game to find positions with high difference between temperature and degree
 pub struct Args {
+    /// Number of positions on every island
     #[arg(long)]
     generation_size: NonZeroUsize,
 
+    /// Number of populations evolving separately on their own threads
+    #[arg(long, default_value = "1")]
+    islands: NonZeroUsize,
+
+    /// Number of generations between migrations of best positions from every island to the
+    /// next one
+    #[arg(long, default_value = "10")]
+    migration_interval: NonZeroUsize,
+
+    /// Number of best positions migrating from every island, replacing the worst positions of
+    /// the next one
+    #[arg(long, default_value_t = 2)]
+    migrants: usize,
+
     /// Do not generate graphs with more that that vertices
     #[arg(long)]
     max_graph_vertices: usize,
 
+    /// Probability of applying each mutation operator, unless overridden by its own rate
     #[arg(long)]
     mutation_rate: f32,
 
+    /// Comma-separated mutation operators to apply, all by default
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "vertex-remove,vertex-add,edge-flip,color-flip"
+    )]
+    mutation_operators: Vec<MutationOperator>,
+
+    /// Probability of removing a random vertex
+    #[arg(long)]
+    vertex_remove_rate: Option<f32>,
+
+    /// Probability of adding a vertex
+    #[arg(long)]
+    vertex_add_rate: Option<f32>,
+
+    /// Probability of toggling every edge
+    #[arg(long)]
+    edge_flip_rate: Option<f32>,
+
+    /// Probability of changing color of every vertex
+    #[arg(long)]
+    color_flip_rate: Option<f32>,
+
+    /// Crossover scheme of parents of new positions
+    #[arg(long, value_enum, default_value_t = CrossoverScheme::Prefix)]
+    crossover: CrossoverScheme,
+
+    #[command(flatten)]
+    selection: SelectionArgs,
+
     /// Stop after running that many generations. Run forever otherwise
     #[arg(long, default_value = None)]
     generation_limit: Option<usize>,
@@ -40,6 +150,16 @@ pub struct Args {
     #[arg(long, default_value = None)]
     snapshot_load_file: Option<String>,
 
+    /// Path to file with initial positions in the `snort:payload` format, one per line, used
+    /// instead of the built-in ones. Empty lines and lines starting with '#' are skipped
+    #[arg(long, conflicts_with = "snapshot_load_file")]
+    population_file: Option<PathBuf>,
+
+    /// Seed of the random number generator. Runs with the same seed and arguments log the same
+    /// positions. Drawn at random if not given, and always logged in the `run` record
+    #[arg(long)]
+    seed: Option<u64>,
+
     /// Path to save snapshot file
     #[arg(long)]
     snapshot_save_file: String,
@@ -48,6 +168,14 @@ pub struct Args {
     #[arg(long)]
     out_file: FileOrStderr,
 
+    /// Encoding of logs and snapshots. Snapshots to load are read in any encoding
+    #[arg(long, value_enum, default_value_t = Format::Json)]
+    log_format: Format,
+
+    /// Compress logs and snapshots with gzip
+    #[arg(long, default_value_t = false)]
+    compress: bool,
+
     /// Clean up transpositon table after that many generations
     #[arg(long, default_value_t = 50)]
     cleanup_interval: usize,
@@ -55,147 +183,26 @@ pub struct Args {
     /// Save if score is above that value
     #[arg(long, default_value_t = Rational::from(0))]
     save_eq_or_above: Rational,
-}
-
-struct SnortTemperatureDegreeDifference {
-    transposition_table: ParallelTranspositionTable<Snort>,
-    max_graph_vertices: usize,
-    mutation_rate: f32,
-}
-
-impl SnortTemperatureDegreeDifference {
-    fn mutate_with_rate(
-        &self,
-        position: &mut Snort,
-        rng: &mut rand::rngs::ThreadRng,
-        mutation_rate: f32,
-    ) {
-        // Mutate vertices
-        if position.graph.size() > 1 {
-            let mutation_roll: f32 = rng.gen();
-            if mutation_roll < mutation_rate {
-                let to_remove = VertexIndex {
-                    index: rng.gen_range(0..position.graph.size()),
-                };
-                position.graph.remove_vertex(to_remove);
-                position.vertices.inner.remove(to_remove.index);
-            }
-        }
-        // TODO: Check for max size
-        // if position.graph.size()
-        let mutation_roll: f32 = rng.gen();
-        if mutation_roll < mutation_rate {
-            position.graph.add_vertex();
-            position
-                .vertices
-                .inner
-                .push(VertexKind::Single(VertexColor::Empty));
-            let another_vertex = VertexIndex {
-                index: rng.gen_range(0..position.graph.size() - 1),
-            };
-            position.graph.connect(
-                VertexIndex {
-                    index: position.graph.size() - 1,
-                },
-                another_vertex,
-                true,
-            );
-        }
-
-        // Mutate edges
-        for v in position.graph.vertices() {
-            for u in position.graph.vertices() {
-                if v == u {
-                    continue;
-                }
-
-                let mutation_roll: f32 = rng.gen();
-                if mutation_roll < mutation_rate {
-                    position
-                        .graph
-                        .connect(v, u, !position.graph.are_adjacent(v, u));
-                }
-            }
-        }
-
-        // Mutate colors
-        let available_colors = vec![
-            VertexColor::Empty,
-            VertexColor::TintLeft,
-            VertexColor::TintRight,
-        ];
-        for index in 0..position.vertices.inner.len() {
-            let mutation_roll: f32 = rng.gen();
-            if mutation_roll < mutation_rate {
-                position.vertices[VertexIndex { index }] =
-                    VertexKind::Single(*available_colors.choose(rng).unwrap());
-            }
-        }
-    }
-}
-
-impl Algorithm<Snort, Rational> for SnortTemperatureDegreeDifference {
-    fn mutate(&self, position: &mut Snort, rng: &mut rand::rngs::ThreadRng) {
-        self.mutate_with_rate(position, rng, self.mutation_rate);
-    }
 
-    fn cross(&self, lhs: &Snort, rhs: &Snort, _rng: &mut rand::rngs::ThreadRng) -> Snort {
-        let mut rng = rand::thread_rng();
+    /// Compute temperature from the thermograph of the whole game tree, without constructing
+    /// canonical forms. Positions are not split into components and nothing is cached between
+    /// them, so it is faster only for positions with small game trees
+    #[arg(long, default_value_t = false)]
+    temperature_only: bool,
 
-        let mut positions = [lhs, rhs];
-        positions.sort_by_key(|pos| pos.graph.size());
-        let [smaller, larger] = positions;
-
-        let new_size = rng.gen_range(1..=larger.graph.size());
-        let mut new_graph = undirected::UndirectedGraph::empty(new_size);
-
-        for v in 0..(min(new_size, smaller.graph.size())) {
-            for u in 0..(min(new_size, smaller.graph.size())) {
-                let v = VertexIndex { index: v };
-                let u = VertexIndex { index: u };
-                new_graph.connect(v, u, smaller.graph.are_adjacent(v, u));
-            }
-        }
-        for v in (min(new_size, smaller.graph.size()))..(min(new_size, larger.graph.size())) {
-            for u in (min(new_size, smaller.graph.size()))..(min(new_size, larger.graph.size())) {
-                let v = VertexIndex { index: v };
-                let u = VertexIndex { index: u };
-                new_graph.connect(v, u, larger.graph.are_adjacent(v, u));
-            }
-        }
-
-        let mut colors = smaller.vertices.inner[0..(min(new_size, smaller.graph.size()))].to_vec();
-        colors.extend(
-            &larger.vertices.inner
-                [(min(new_size, smaller.graph.size()))..(min(new_size, larger.graph.size()))],
-        );
-
-        Snort::with_colors(colors, new_graph).unwrap()
-    }
-
-    fn lowest_score(&self) -> Rational {
-        Rational::NegativeInfinity
-    }
-
-    fn score(&self, position: &Snort) -> Rational {
-        let degree_sum = position.graph.degrees().sum::<usize>();
-        if position.vertices.inner.is_empty() || degree_sum == 0 || !position.graph.is_connected() {
-            return Rational::NegativeInfinity;
-        }
+    /// Give up on positions that take longer than that many milliseconds to evaluate, scoring
+    /// them as lowest possible
+    #[arg(long, default_value = None)]
+    evaluation_timeout_ms: Option<u64>,
 
-        let game = position.canonical_form(&self.transposition_table);
-        let temp = game.temperature();
-        let degree = position.degree();
-        temp.to_rational() - Rational::from(degree as i64)
-    }
+    /// Comma-separated classes of graphs to search in. Mutated and crossed positions are
+    /// repaired to belong to all of them
+    #[arg(long, value_enum, value_delimiter = ',')]
+    graph_class: Vec<Class>,
 
-    fn random(&self, rng: &mut rand::rngs::ThreadRng) -> Snort {
-        let graph_size = rng.gen_range(1..=self.max_graph_vertices);
-        let graph = undirected::UndirectedGraph::empty(graph_size);
-        let mut position = Snort::new(graph);
-        self.mutate_with_rate(&mut position, rng, 1.0);
-        position
-    }
+    /// Search only graphs without vertices of degree above that value
+    #[arg(long)]
+    max_degree: Option<usize>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -260,25 +267,110 @@ fn seed_positions() -> Vec<Snort> {
     vec![pos_1, pos_2]
 }
 
+/// Rates of selected mutation operators, with the common rate unless given their own
+fn mutation(args: &Args) -> Mutation {
+    let rate = |operator, own_rate: Option<f32>| {
+        if args.mutation_operators.contains(&operator) {
+            own_rate.unwrap_or(args.mutation_rate)
+        } else {
+            0.0
+        }
+    };
+    Mutation {
+        vertex_remove: rate(MutationOperator::VertexRemove, args.vertex_remove_rate),
+        vertex_add: rate(MutationOperator::VertexAdd, args.vertex_add_rate),
+        edge_flip: rate(MutationOperator::EdgeFlip, args.edge_flip_rate),
+        color_flip: rate(MutationOperator::ColorFlip, args.color_flip_rate),
+    }
+}
+
+/// Read positions from lines of a file, skipping empty lines and '#' comments
+fn load_population(path: &Path) -> Result<Vec<Snort>> {
+    let file = File::open(path)
+        .with_context(|| format!("Could not open population file '{}'", path.display()))?;
+    let mut population = Vec::new();
+    for (idx, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.with_context(|| format!("Could not read '{}'", path.display()))?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        population.push(parse_snort(line).with_context(|| {
+            format!("Invalid position at line {} of '{}'", idx + 1, path.display())
+        })?);
+    }
+    Ok(population)
+}
+
 pub fn run(args: Args) -> Result<()> {
-    let alg = SnortTemperatureDegreeDifference {
-        transposition_table: ParallelTranspositionTable::new(),
-        max_graph_vertices: args.max_graph_vertices,
-        mutation_rate: args.mutation_rate,
+    let mut graph_classes = args
+        .graph_class
+        .iter()
+        .copied()
+        .map(GraphClass::from)
+        .collect::<Vec<_>>();
+    graph_classes.extend(args.max_degree.map(GraphClass::MaxDegree));
+
+    let mut alg = TemperatureDegreeSearch::new(args.max_graph_vertices, args.mutation_rate);
+    alg.fitness.temperature_only = args.temperature_only;
+    alg.fitness.evaluation_timeout = args.evaluation_timeout_ms.map(Duration::from_millis);
+    alg.graph_classes = graph_classes;
+    alg.mutation = mutation(&args);
+    alg.crossover = Crossover::from(args.crossover);
+    let migration = Migration {
+        interval: args.migration_interval,
+        migrants: args.migrants,
+    };
+    let many_islands = args.islands.get() > 1;
+    let operators = Operators {
+        selection: args.selection.selection()?,
+        mutation: Some(alg.mutation),
+        crossover: Some(alg.crossover),
+        islands: many_islands.then_some(args.islands),
+        migration: many_islands.then_some(migration),
     };
 
-    let specimen = if let Some(snapshot_file) = args.snapshot_load_file.clone() {
-        let f = BufReader::new(File::open(snapshot_file).context("Could not open snapshot file")?);
-        let snapshot: Snapshot =
-            serde_json::de::from_reader(f).context("Could not parse snapshot file")?;
+    let mut specimen: Vec<Snort> = if let Some(snapshot_file) = args.snapshot_load_file.clone() {
+        let f = File::open(snapshot_file).context("Could not open snapshot file")?;
+        let Some(snapshot) = encoding::read::<Snapshot>(f)
+            .context("Could not parse snapshot file")?
+            .pop()
+        else {
+            bail!("Snapshot file is empty");
+        };
         snapshot.specimen.into_iter().map(|s| s.object).collect()
+    } else if let Some(population_file) = &args.population_file {
+        load_population(population_file)?
     } else {
         seed_positions()
     };
+    let seed = args.seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(seed);
+    // Seeds and snapshots may come from searches in other classes
+    for position in &mut specimen {
+        alg.repair(position, &mut rng);
+    }
 
-    let mut alg = GeneticAlgorithm::with_specimen(specimen, args.generation_size, alg);
-
-    let mut log_writer = args.out_file.create().unwrap();
+    let mut alg = IslandModel::with_specimen_seeded(
+        specimen,
+        args.islands,
+        args.generation_size,
+        migration,
+        alg,
+        rng.gen(),
+    );
+    alg.set_selection(operators.selection);
+
+    let mut log_writer = Writer::new(
+        args.out_file.create().context("Could not create log file")?,
+        args.log_format,
+        args.compress,
+    )?;
+    log_writer.write(&Log::new(Record::run(seed, Some(operators))))?;
+    log_writer.flush()?;
+
+    // Temperature and negated degree of positions
+    let mut front = ParetoFront::new();
 
     loop {
         if args
@@ -292,46 +384,67 @@ pub fn run(args: Args) -> Result<()> {
 
         // TODO: Save interval
         {
-            let mut output = BufWriter::new(
-                File::create(&args.snapshot_save_file)
-                    .context("Could not create/open output file")?,
-            );
-            writeln!(
-                output,
-                "{}",
-                serde_json::ser::to_string(&Snapshot {
-                    specimen: alg.specimen().to_vec()
-                })
-                .unwrap()
-            )
-            .unwrap();
+            let mut output = Writer::new(
+                BufWriter::new(
+                    File::create(&args.snapshot_save_file)
+                        .context("Could not create/open output file")?,
+                ),
+                args.log_format,
+                args.compress,
+            )?;
+            output.write(&Snapshot {
+                specimen: alg.specimen().cloned().collect(),
+            })?;
         }
 
         let best = alg.highest_score();
-        let best_cf = best
-            .object
-            .canonical_form(&alg.algorithm().transposition_table);
+        let best_cf = Zobrist::new(best.object.clone())
+            .canonical_form(alg.algorithm().fitness.transposition_table());
         let best_temp = best_cf.temperature();
 
         {
-            let log = Log::Generation {
+            let log = Log::new(Record::Generation {
                 generation: alg.generation(),
                 top_score: best.score,
                 temperature: best_temp,
-            };
-            writeln!(log_writer, "{}", serde_json::ser::to_string(&log).unwrap()).unwrap();
-            log_writer.flush().unwrap();
+            });
+            log_writer.write(&log)?;
+            log_writer.flush()?;
         }
 
         {
-            let log = Log::HighFitness {
-                position: best.clone(),
-                canonical_form: best_cf.to_string(),
-                temperature: best_temp,
-                degree: best.object.degree(),
-            };
-            writeln!(log_writer, "{}", serde_json::ser::to_string(&log).unwrap()).unwrap();
-            log_writer.flush().unwrap();
+            let log = Log::new(Record::high_fitness(
+                best,
+                best_cf.to_string(),
+                best_temp,
+            ));
+            log_writer.write(&log)?;
+            log_writer.flush()?;
+        }
+
+        {
+            let mut changed = false;
+            for specimen in alg.specimen() {
+                if specimen.score.is_infinite() {
+                    continue;
+                }
+                let degree = Rational::from(specimen.object.degree() as i64);
+                let temperature = specimen.score + degree;
+                changed |= front.insert(specimen.object.clone(), vec![temperature, -degree]);
+            }
+
+            if changed {
+                let log = Log::new(Record::ParetoFront {
+                    generation: alg.generation(),
+                    front: front
+                        .members()
+                        .iter()
+                        .map(|member| FrontMember::new(&member.object, member.score[0]))
+                        .collect(),
+                });
+                log_writer.write(&log)?;
+                log_writer.flush()?;
+            }
         }
     }
 
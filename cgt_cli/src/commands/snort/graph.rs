@@ -1,14 +1,19 @@
-use crate::commands::snort::common::{analyze_position, Edge};
-use anyhow::Result;
+use crate::commands::snort::common::{analyze_position, graph_from_edges, Edge};
+use anyhow::{Context, Result};
 use cgt::{
-    graph::{adjacency_matrix::undirected::UndirectedGraph, Graph, VertexIndex},
+    graph::Graph,
     short::partizan::games::snort::{Snort, VertexColor, VertexKind},
 };
 use clap::Parser;
+use std::str::FromStr;
 
 #[derive(Parser, Debug, Clone)]
 /// Evaluate a graph of Snort position
 pub struct Args {
+    #[arg(long, default_value = None, conflicts_with_all = ["edges", "tinted_left", "tinted_right"])]
+    /// Position in the text format, i.e. vertex colors followed by edges (e.g. '.L.<3>;0-1,1-2')
+    position: Option<String>,
+
     #[arg(long, value_delimiter = ',')]
     /// Comma-separated list of edges in the graph in the form `<from>-<to>` (e.g. '0-1,1-2').
     ///
@@ -26,41 +31,37 @@ pub struct Args {
     #[arg(long)]
     /// Do not generate a graphviz graph of the position and immediate children.
     no_graphviz: bool,
+
+    #[arg(long)]
+    /// Merge vertices with the same color and neighbors into clusters before the analysis.
+    compress_clusters: bool,
 }
 
 pub fn run(args: Args) -> Result<()> {
-    let graph_size = args
-        .edges
-        .iter()
-        .map(|edge| edge.from.max(edge.to))
-        .max()
-        .unwrap_or(0);
-    let edges = args
-        .edges
-        .iter()
-        .map(|edge| {
-            (
-                VertexIndex {
-                    index: edge.from as usize,
-                },
-                VertexIndex {
-                    index: edge.to as usize,
-                },
-            )
-        })
-        .collect::<Vec<_>>();
-    let graph = UndirectedGraph::from_edges((graph_size + 1) as usize, &edges);
+    if let Some(position) = &args.position {
+        let position = Snort::from_str(position).context("Could not parse the position")?;
+        return analyze(position, &args);
+    }
+
+    let graph = graph_from_edges(&args.edges);
 
     let mut vertices = vec![VertexKind::Single(VertexColor::Empty); graph.size()];
-    for v in args.tinted_left {
+    for &v in &args.tinted_left {
         vertices[v as usize] = VertexKind::Single(VertexColor::TintLeft);
     }
-    for v in args.tinted_right {
+    for &v in &args.tinted_right {
         vertices[v as usize] = VertexKind::Single(VertexColor::TintRight);
     }
 
-    let position = Snort::with_colors(vertices, graph).unwrap();
-    analyze_position(position, !args.no_graphviz)?;
+    let position = Snort::with_colors(vertices, graph)?;
+    analyze(position, &args)
+}
 
-    Ok(())
+fn analyze(position: Snort, args: &Args) -> Result<()> {
+    let position = if args.compress_clusters {
+        position.compress_clusters()
+    } else {
+        position
+    };
+    analyze_position(position, !args.no_graphviz)
 }
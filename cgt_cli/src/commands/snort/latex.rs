@@ -1,13 +1,25 @@
 use crate::{
-    commands::snort::common::Log,
+    commands::logs::{self, Record},
     io::{FileOrStdin, FileOrStdout},
+    progress,
 };
-use anyhow::{bail, Context, Result};
-use cgt::numeric::rational::Rational;
-use clap::Parser;
+use anyhow::{anyhow, bail, Context, Result};
+use cgt::{
+    drawing::svg::Svg,
+    genetic_algorithm::Scored,
+    numeric::{dyadic_rational_number::DyadicRationalNumber, rational::Rational},
+    graph::Graph,
+    short::partizan::{canonical_form::CanonicalForm, games::snort::Snort, position::NamedGame},
+};
+use clap::{Parser, ValueEnum};
+use indicatif::ParallelProgressIterator;
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use std::{
+    fmt::Write as _,
+    fs,
     io::{BufReader, BufWriter, Write},
+    path::PathBuf,
+    str::FromStr,
     process::{Command, Stdio},
     sync::{
         atomic::{AtomicUsize, Ordering},
@@ -15,10 +27,25 @@ use std::{
     },
 };
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Renderer {
+    /// Draw SVG images without external tools
+    Builtin,
+
+    /// Draw images with `graphviz`, which needs to be installed
+    Graphviz,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SortBy {
+    Fitness,
+    Temperature,
+    Degree,
+    Vertices,
+}
+
 #[derive(Parser, Debug, Clone)]
 /// Convert a log file (usually obtained from a genetic algorithm search) to a LaTeX table with images.
-///
-/// Requires `graphviz` to be installed.
 pub struct Args {
     #[arg(long)]
     /// Input file with logs
@@ -28,7 +55,11 @@ pub struct Args {
     /// Output file with LaTeX table
     out_file: FileOrStdout,
 
-    /// See <https://graphviz.org/docs/outputs/>
+    /// Tool used to draw images of positions
+    #[arg(long, value_enum, default_value_t = Renderer::Builtin)]
+    renderer: Renderer,
+
+    /// See <https://graphviz.org/docs/outputs/>. Built-in renderer supports only `svg`
     #[arg(long, default_value = "svg")]
     image_format: String,
 
@@ -40,7 +71,8 @@ pub struct Args {
     #[arg(long, default_value_t = 2)]
     columns: usize,
 
-    /// Width of each individual image, height will be scaled to preserve ratio
+    /// Width of each individual image and of the column with images, height will be scaled to
+    /// preserve ratio
     #[arg(long, default_value = "4cm")]
     image_width: String,
 
@@ -51,29 +83,173 @@ pub struct Args {
     /// Fitness lower bound (inclusive)
     #[arg(long, default_value = None)]
     fitness_lower_bound: Option<Rational>,
+
+    /// Draw positions inline as TikZ pictures instead of including image files
+    #[arg(long, default_value_t = false)]
+    tikz: bool,
+
+    /// Scale of TikZ pictures
+    #[arg(long, default_value_t = 0.5)]
+    tikz_scale: f32,
+
+    /// Add a column with canonical forms of positions
+    #[arg(long, default_value_t = false)]
+    canonical_form: bool,
+
+    /// Width of the column with canonical forms
+    #[arg(long, default_value = "3cm")]
+    canonical_form_width: String,
+
+    /// Order of positions in the table, from the highest value
+    #[arg(long, value_enum, default_value_t = SortBy::Fitness)]
+    sort_by: SortBy,
+
+    /// Sort from the lowest value instead
+    #[arg(long, default_value_t = false)]
+    ascending: bool,
+
+    /// Template of the table replacing the default layout, see [`Template`]
+    #[arg(long, conflicts_with = "columns")]
+    template: Option<PathBuf>,
+}
+
+/// Position from the log with its image
+struct Entry {
+    position: Scored<Snort, Rational>,
+    graph6: String,
+    canonical_form: CanonicalForm,
+    temperature: DyadicRationalNumber,
+    degree: usize,
+    image: String,
+}
+
+/// User template of the table
+///
+/// Template is split into sections by lines `%% cgt:header`, `%% cgt:row` and `%% cgt:footer`.
+/// Row section is repeated for every position, with placeholders `{{image}}`, `{{position}}`,
+/// `{{graph6}}`, `{{canonical_form}}`, `{{value}}` (canonical form in LaTeX), `{{temperature}}`,
+/// `{{degree}}`, `{{vertices}}`, `{{fitness}}` and `{{index}}` replaced with values of the
+/// position. Header and footer can use `{{count}}`, the
+/// number of positions. Text before the first section is ignored.
+struct Template {
+    header: String,
+    row: String,
+    footer: String,
+}
+
+impl Template {
+    fn parse(input: &str) -> Result<Self> {
+        let mut sections = [None::<String>, None, None];
+        let mut current = None;
+        for line in input.lines() {
+            let marker = match line.trim() {
+                "%% cgt:header" => Some(0),
+                "%% cgt:row" => Some(1),
+                "%% cgt:footer" => Some(2),
+                _ => None,
+            };
+            if let Some(marker) = marker {
+                if sections[marker].is_some() {
+                    bail!("Template has more than one '{}' section", line.trim());
+                }
+                sections[marker] = Some(String::new());
+                current = Some(marker);
+            } else if let Some(current) = current {
+                let section = sections[current].as_mut().unwrap();
+                section.push_str(line);
+                section.push('\n');
+            }
+        }
+
+        let [header, row, footer] = sections;
+        let Some(row) = row else {
+            bail!("Template has no '%% cgt:row' section");
+        };
+        Ok(Self {
+            header: header.unwrap_or_default(),
+            row,
+            footer: footer.unwrap_or_default(),
+        })
+    }
+
+    fn fill(section: &str, values: &[(&str, String)]) -> String {
+        values
+            .iter()
+            .fold(section.to_owned(), |section, (name, value)| {
+                section.replace(&format!("{{{{{name}}}}}"), value)
+            })
+    }
+
+    fn write(&self, output: &mut impl Write, entries: &[Entry]) -> Result<()> {
+        let count = [("count", entries.len().to_string())];
+        write!(output, "{}", Self::fill(&self.header, &count))?;
+        for (index, entry) in entries.iter().enumerate() {
+            let values = [
+                ("image", entry.image.clone()),
+                (
+                    "position",
+                    format!("{}:{}", <Snort>::NAME, entry.position.object),
+                ),
+                ("graph6", entry.graph6.clone()),
+                ("canonical_form", entry.canonical_form.to_string()),
+                ("value", entry.canonical_form.to_latex()),
+                ("temperature", entry.temperature.to_string()),
+                ("degree", entry.degree.to_string()),
+                ("vertices", entry.position.object.graph.size().to_string()),
+                ("fitness", entry.position.score.to_string()),
+                ("index", (index + 1).to_string()),
+            ];
+            write!(output, "{}", Self::fill(&self.row, &values))?;
+        }
+        write!(output, "{}", Self::fill(&self.footer, &count))?;
+        Ok(())
+    }
 }
 
 pub fn run(args: Args) -> Result<()> {
+    if !args.tikz && matches!(args.renderer, Renderer::Builtin) && args.image_format != "svg" {
+        bail!("Built-in renderer supports only svg images");
+    }
+
+    let template = args
+        .template
+        .as_ref()
+        .map(|path| {
+            let template = fs::read_to_string(path)
+                .with_context(|| format!("Could not read template '{}'", path.display()))?;
+            Template::parse(&template)
+                .with_context(|| format!("Invalid template '{}'", path.display()))
+        })
+        .transpose()?;
+
     let input = BufReader::new(args.in_file.open().context("Could not open input file")?);
-    let mut output = BufWriter::new(args.out_file.open().context("Could not open output file")?);
+    let mut output = BufWriter::new(args.out_file.create().context("Could not create output file")?);
 
-    let input: Result<Vec<Log>> = serde_json::de::Deserializer::from_reader(input)
-        .into_iter()
-        .map(|log| log.context("Could not decode input"))
-        .collect();
-    let input = input?;
+    let input = logs::read_logs(input)?;
 
     let entries = Mutex::new(Vec::new());
     let i = AtomicUsize::new(0);
 
-    input.into_par_iter().try_for_each(|log| -> Result<()> {
-        if let Log::HighFitness {
+    let records = progress::bar(input.len() as u64, "records");
+    let input = input.into_par_iter().progress_with(records.clone());
+
+    input.try_for_each(|log| -> Result<()> {
+        if let Record::HighFitness {
             position,
+            graph6,
+            score,
+            canonical_form,
             temperature,
             degree,
-            ..
-        } = log
+        } = log.record
         {
+            let canonical_form = CanonicalForm::from_str(&canonical_form).map_err(|err| {
+                anyhow!("Invalid canonical form '{canonical_form}' of '{position}': {err}")
+            })?;
+            let position = Scored {
+                object: logs::parse_snort(&position)?,
+                score,
+            };
             if args
                 .fitness_lower_bound
                 .map_or(false, |fitness_lower_bound| {
@@ -83,45 +259,67 @@ pub fn run(args: Args) -> Result<()> {
                 return Ok(());
             }
 
-            // Start engine with output file and no input
-            let out_image_name = format!(
-                "{}{}.{}",
-                &args.image_file_prefix,
-                i.fetch_add(1, Ordering::SeqCst),
-                &args.image_format
-            );
-            eprintln!("Generating {}", out_image_name);
-
-            let mut graphviz_proc = Command::new(&args.graphviz_engine)
-                .stdin(Stdio::piped())
-                .arg(format!("-T{}", &args.image_format))
-                .arg(format!("-o{}", &out_image_name))
-                .spawn()
-                .context("Could not spawn graphviz")?;
-
-            // Pipe dot to the running engine via stdin
-            graphviz_proc
-                .stdin
-                .take()
-                .context("Could not open graphviz stdin")?
-                .write_all(position.object.to_graphviz().as_bytes())
-                .context("Could not write to graphviz stdin")?;
-
-            // Await result and check for errors
-            if !graphviz_proc
-                .wait()
-                .context("Could not wait for graphviz")?
-                .success()
-            {
-                bail!("Graphviz failed");
+            let image = if args.tikz {
+                position.object.to_tikz_with_scale(args.tikz_scale)
+            } else {
+                let out_image_name = format!(
+                    "{}{}.{}",
+                    &args.image_file_prefix,
+                    i.fetch_add(1, Ordering::SeqCst),
+                    &args.image_format
+                );
+
+                match args.renderer {
+                    Renderer::Builtin => {
+                        let mut svg = String::new();
+                        position.object.to_svg(&mut svg)?;
+                        fs::write(&out_image_name, svg).context("Could not write image")?;
+                    }
+                    Renderer::Graphviz => {
+                        render_graphviz(&position.object, &out_image_name, &args)?;
+                    }
+                }
+                format!(
+                    "\\includegraphics[width={}]{{{}}}",
+                    args.image_width, out_image_name
+                )
             };
 
             let mut es = entries.lock().unwrap();
-            es.push((position, temperature, degree, out_image_name));
+            es.push(Entry {
+                position,
+                graph6,
+                canonical_form,
+                temperature,
+                degree,
+                image,
+            });
         }
         Ok(())
     })?;
-    let mut entries = entries.lock().unwrap();
+    records.finish();
+    let mut entries = entries.into_inner().unwrap();
+
+    entries.sort_by(|lhs, rhs| match args.sort_by {
+        SortBy::Fitness => lhs.position.score.cmp(&rhs.position.score),
+        SortBy::Temperature => lhs.temperature.cmp(&rhs.temperature),
+        SortBy::Degree => lhs.degree.cmp(&rhs.degree),
+        SortBy::Vertices => lhs
+            .position
+            .object
+            .graph
+            .size()
+            .cmp(&rhs.position.object.graph.size()),
+    });
+    if !args.ascending {
+        entries.reverse();
+    }
+
+    if let Some(template) = template {
+        template.write(&mut output, &entries)?;
+        output.flush()?;
+        return Ok(());
+    }
 
     // preamble
     writeln!(output, "{{")?;
@@ -132,18 +330,18 @@ pub fn run(args: Args) -> Result<()> {
     let degree_column_width = "0.75cm";
     let fitness_column_width = "1.25cm";
 
-    // table start
-    write!(
-        output,
-        "\\begin{{longtabu}}{{m{{{}}} m{{{}}} m{{{}}} m{{{}}}",
+    let mut column_spec = format!(
+        "m{{{}}} m{{{}}} m{{{}}} m{{{}}}",
         args.image_width, temperature_column_width, degree_column_width, fitness_column_width
-    )?;
+    );
+    if args.canonical_form {
+        write!(column_spec, " m{{{}}}", args.canonical_form_width)?;
+    }
+
+    // table start
+    write!(output, "\\begin{{longtabu}}{{{column_spec}")?;
     for _ in 1..args.columns {
-        write!(
-            output,
-            "|m{{{}}} m{{{}}} m{{{}}} m{{{}}}",
-            args.image_width, temperature_column_width, degree_column_width, fitness_column_width
-        )?;
+        write!(output, "|{column_spec}")?;
     }
     write!(output, "}} \n\\hline ")?;
 
@@ -153,15 +351,13 @@ pub fn run(args: Args) -> Result<()> {
             write!(output, "& ")?;
         }
         write!(output, "Position & Temp. & Degree & Fitness")?;
+        if args.canonical_form {
+            write!(output, " & Value")?;
+        }
     }
     writeln!(output, "\\\\ \\hline \\endhead")?;
 
     // entries
-
-    // sort by fitness
-    entries.sort_by_key(|entry| entry.0.score);
-    entries.reverse();
-
     let mut entries = entries.iter().peekable();
     while entries.peek().is_some() {
         for idx in 0..args.columns {
@@ -171,9 +367,12 @@ pub fn run(args: Args) -> Result<()> {
                 }
                 write!(
                     output,
-                    "\\includegraphics[width={}]{{{}}} & ${}$ & ${}$ & ${}$ ",
-                    args.image_width, entry.3, entry.1, entry.2, entry.0.score
+                    "{} & ${}$ & ${}$ & ${}$ ",
+                    entry.image, entry.temperature, entry.degree, entry.position.score
                 )?;
+                if args.canonical_form {
+                    write!(output, "& ${}$ ", entry.canonical_form.to_latex())?;
+                }
             };
         }
         writeln!(output, "\\\\")?;
@@ -184,3 +383,31 @@ pub fn run(args: Args) -> Result<()> {
 
     Ok(())
 }
+
+fn render_graphviz(position: &Snort, out_image_name: &str, args: &Args) -> Result<()> {
+    let mut graphviz_proc = Command::new(&args.graphviz_engine)
+        .stdin(Stdio::piped())
+        .arg(format!("-T{}", &args.image_format))
+        .arg(format!("-o{}", out_image_name))
+        .spawn()
+        .context("Could not spawn graphviz")?;
+
+    // Pipe dot to the running engine via stdin
+    graphviz_proc
+        .stdin
+        .take()
+        .context("Could not open graphviz stdin")?
+        .write_all(position.to_graphviz().as_bytes())
+        .context("Could not write to graphviz stdin")?;
+
+    // Await result and check for errors
+    if !graphviz_proc
+        .wait()
+        .context("Could not wait for graphviz")?
+        .success()
+    {
+        bail!("Graphviz failed");
+    };
+
+    Ok(())
+}
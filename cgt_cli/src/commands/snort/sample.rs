@@ -0,0 +1,257 @@
+use crate::{commands::search::value_class, io::FileOrStdout, progress};
+use anyhow::{bail, Result};
+use cgt::{
+    graph::{
+        adjacency_matrix::undirected::UndirectedGraph,
+        generators::{random_bounded_degree, random_gnp, random_tree},
+    },
+    numeric::dyadic_rational_number::DyadicRationalNumber,
+    short::partizan::{
+        batch::evaluate_all_with_progress, games::snort::Snort,
+        transposition_table::ParallelTranspositionTable,
+    },
+};
+use clap::{Parser, ValueEnum};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Write,
+    num::NonZeroUsize,
+};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Model {
+    /// Erdős–Rényi graphs, every edge present with `--edge-probability`
+    Gnp,
+
+    /// Uniformly random labeled trees
+    Tree,
+
+    /// Graphs with vertex degrees at most `--max-degree`, every edge present with
+    /// `--edge-probability` if it fits
+    BoundedDegree,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    /// Single JSON object
+    Json,
+
+    /// Human readable summary with histogram bars
+    Text,
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Evaluate Snort on random graphs and report the distribution of their temperatures and values
+pub struct Args {
+    /// Model of random graphs
+    #[arg(long, value_enum, default_value_t = Model::Gnp)]
+    model: Model,
+
+    /// Number of vertices of sampled graphs
+    #[arg(long)]
+    vertices: usize,
+
+    /// Number of sampled graphs
+    #[arg(long, default_value_t = NonZeroUsize::new(1000).unwrap())]
+    samples: NonZeroUsize,
+
+    /// Probability that an edge is present, used by `gnp` and `bounded-degree` models
+    #[arg(long, default_value_t = 0.3)]
+    edge_probability: f64,
+
+    /// Highest vertex degree, used by `bounded-degree` model
+    #[arg(long, default_value_t = 3)]
+    max_degree: usize,
+
+    /// Seed of the random number generator, for reproducible samples
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Number of most common values to report
+    #[arg(long, default_value_t = 10)]
+    top_values: usize,
+
+    #[arg(long, value_enum, default_value_t = Format::Json)]
+    format: Format,
+
+    /// Output file. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+}
+
+#[derive(Serialize)]
+struct Statistics {
+    min: DyadicRationalNumber,
+    max: DyadicRationalNumber,
+    median: DyadicRationalNumber,
+    mean: f64,
+    std_dev: f64,
+}
+
+#[derive(Serialize)]
+struct Bucket {
+    temperature: DyadicRationalNumber,
+    count: usize,
+}
+
+#[derive(Serialize)]
+struct Value {
+    canonical_form: String,
+    count: usize,
+}
+
+#[derive(Serialize)]
+struct Summary {
+    samples: usize,
+    temperature: Statistics,
+    histogram: Vec<Bucket>,
+    classes: BTreeMap<&'static str, usize>,
+    values: Vec<Value>,
+}
+
+fn to_float(value: DyadicRationalNumber) -> f64 {
+    value.numerator() as f64 / f64::from(value.denominator_exponent()).exp2()
+}
+
+fn sample_graph(args: &Args, rng: &mut StdRng) -> UndirectedGraph {
+    match args.model {
+        Model::Gnp => random_gnp(args.vertices, args.edge_probability, rng),
+        Model::Tree => random_tree(args.vertices, rng),
+        Model::BoundedDegree => {
+            random_bounded_degree(args.vertices, args.max_degree, args.edge_probability, rng)
+        }
+    }
+}
+
+fn write_text(output: &mut impl Write, summary: &Summary) -> Result<()> {
+    const BAR_WIDTH: usize = 50;
+
+    let statistics = &summary.temperature;
+    writeln!(output, "samples: {}", summary.samples)?;
+    writeln!(
+        output,
+        "temperature: min {}, max {}, median {}, mean {:.4}, std dev {:.4}",
+        statistics.min, statistics.max, statistics.median, statistics.mean, statistics.std_dev
+    )?;
+
+    writeln!(output, "\nhistogram:")?;
+    let highest = summary
+        .histogram
+        .iter()
+        .map(|bucket| bucket.count)
+        .max()
+        .unwrap_or(0);
+    for bucket in &summary.histogram {
+        let width = bucket.count * BAR_WIDTH / highest.max(1);
+        writeln!(
+            output,
+            "{:>8} {:>8} {}",
+            bucket.temperature.to_string(),
+            bucket.count,
+            "#".repeat(width.max(1))
+        )?;
+    }
+
+    writeln!(output, "\nclasses:")?;
+    for (class, count) in &summary.classes {
+        writeln!(output, "{class:>8} {count:>8}")?;
+    }
+
+    writeln!(output, "\nmost common values:")?;
+    for value in &summary.values {
+        writeln!(output, "{:>8} {}", value.count, value.canonical_form)?;
+    }
+    Ok(())
+}
+
+pub fn run(args: Args) -> Result<()> {
+    if !(0.0..=1.0).contains(&args.edge_probability) {
+        bail!("Edge probability must be between 0 and 1");
+    }
+
+    let mut rng = args
+        .seed
+        .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+    let positions = (0..args.samples.get())
+        .map(|_| Snort::new(sample_graph(&args, &mut rng)))
+        .collect::<Vec<_>>();
+
+    let transposition_table = ParallelTranspositionTable::new();
+    let progress = progress::bar(positions.len() as u64, "positions");
+    let evaluations =
+        evaluate_all_with_progress(&positions, &transposition_table, |_| progress.inc(1));
+    progress.finish_and_clear();
+
+    let mut temperatures = evaluations
+        .iter()
+        .map(|evaluation| evaluation.canonical_form.temperature())
+        .collect::<Vec<_>>();
+    temperatures.sort();
+
+    let mean = temperatures.iter().copied().map(to_float).sum::<f64>() / temperatures.len() as f64;
+    let variance = temperatures
+        .iter()
+        .map(|&temperature| (to_float(temperature) - mean).powi(2))
+        .sum::<f64>()
+        / temperatures.len() as f64;
+
+    let mut histogram = Vec::<Bucket>::new();
+    for &temperature in &temperatures {
+        match histogram.last_mut() {
+            Some(bucket) if bucket.temperature == temperature => bucket.count += 1,
+            _ => histogram.push(Bucket {
+                temperature,
+                count: 1,
+            }),
+        }
+    }
+
+    let mut classes = BTreeMap::new();
+    let mut values = HashMap::<String, usize>::new();
+    for evaluation in &evaluations {
+        *classes
+            .entry(value_class(&evaluation.canonical_form))
+            .or_default() += 1;
+        *values
+            .entry(evaluation.canonical_form.to_string())
+            .or_default() += 1;
+    }
+    let mut values = values
+        .into_iter()
+        .map(|(canonical_form, count)| Value {
+            canonical_form,
+            count,
+        })
+        .collect::<Vec<_>>();
+    values.sort_by(|lhs, rhs| {
+        rhs.count
+            .cmp(&lhs.count)
+            .then_with(|| lhs.canonical_form.cmp(&rhs.canonical_form))
+    });
+    values.truncate(args.top_values);
+
+    let summary = Summary {
+        samples: temperatures.len(),
+        temperature: Statistics {
+            min: temperatures[0],
+            max: temperatures[temperatures.len() - 1],
+            median: temperatures[temperatures.len() / 2],
+            mean,
+            std_dev: variance.sqrt(),
+        },
+        histogram,
+        classes,
+        values,
+    };
+
+    let mut output = args.out_file.create()?;
+    match args.format {
+        Format::Json => writeln!(output, "{}", serde_json::ser::to_string(&summary)?)?,
+        Format::Text => write_text(&mut output, &summary)?,
+    }
+    output.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,239 @@
+use crate::io::FileOrStdout;
+use anyhow::{Context, Result};
+use cgt::short::partizan::{
+    canonical_form::{CanonicalForm, Moves, OptionKind},
+    position::{parse_position, AnyPosition},
+};
+use clap::{Parser, ValueEnum};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Write as _,
+    io::Write,
+};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    /// Graphviz directed graph, render with `dot`
+    Dot,
+
+    /// TikZ picture with nodes placed in layers by depth
+    Tikz,
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Draw the game tree of a position with canonical values at nodes
+///
+/// Left options are drawn in blue and Right options in red. Dominated options are dashed and
+/// reversible options are bold. Positions reachable in several ways are drawn once, so the
+/// result is a DAG unless `--unfold` is given.
+pub struct Args {
+    /// Position in the `game:payload` format, e.g. `domineering:..|..`
+    position: String,
+
+    /// Number of moves to expand from the root position
+    #[arg(long, default_value_t = 2)]
+    depth: usize,
+
+    /// Draw repeated positions as separate nodes
+    #[arg(long, default_value_t = false)]
+    unfold: bool,
+
+    /// Format of the image
+    #[arg(long, value_enum, default_value_t = Format::Dot)]
+    format: Format,
+
+    /// Scale of TikZ pictures
+    #[arg(long, default_value_t = 1.0)]
+    tikz_scale: f32,
+
+    /// Output file with the image. Use '-' for stdout
+    #[arg(long, default_value = "-")]
+    out_file: FileOrStdout,
+}
+
+struct Node {
+    position: AnyPosition,
+    value: CanonicalForm,
+    depth: usize,
+}
+
+struct Edge {
+    from: usize,
+    to: usize,
+    left: bool,
+    kind: OptionKind,
+}
+
+#[derive(Default)]
+struct Tree {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    seen: HashMap<String, usize>,
+}
+
+impl Tree {
+    fn node(&mut self, position: AnyPosition, depth: usize, unfold: bool) -> (usize, bool) {
+        let key = position.to_string();
+        if !unfold {
+            if let Some(&idx) = self.seen.get(&key) {
+                return (idx, false);
+            }
+        }
+        let idx = self.nodes.len();
+        self.nodes.push(Node {
+            value: position.canonical_form(),
+            position,
+            depth,
+        });
+        self.seen.insert(key, idx);
+        (idx, true)
+    }
+
+    fn build(root: AnyPosition, max_depth: usize, unfold: bool) -> Self {
+        let mut tree = Self::default();
+        let (root, _) = tree.node(root, 0, unfold);
+        let mut queue = VecDeque::from([root]);
+        while let Some(from) = queue.pop_front() {
+            let depth = tree.nodes[from].depth;
+            if depth >= max_depth {
+                continue;
+            }
+
+            let position = tree.nodes[from].position.clone();
+            let mut children = |options: Vec<AnyPosition>| {
+                options
+                    .into_iter()
+                    .map(|option| {
+                        let (to, new) = tree.node(option, depth + 1, unfold);
+                        if new {
+                            queue.push_back(to);
+                        }
+                        to
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let left = children(position.left_moves());
+            let right = children(position.right_moves());
+
+            let moves = Moves {
+                left: left.iter().map(|&to| tree.nodes[to].value.clone()).collect(),
+                right: right.iter().map(|&to| tree.nodes[to].value.clone()).collect(),
+            };
+            let left_edges = left.into_iter().zip(moves.left_option_kinds()).map(|(to, kind)| {
+                Edge {
+                    from,
+                    to,
+                    left: true,
+                    kind,
+                }
+            });
+            let right_edges = right
+                .into_iter()
+                .zip(moves.right_option_kinds())
+                .map(|(to, kind)| Edge {
+                    from,
+                    to,
+                    left: false,
+                    kind,
+                });
+            tree.edges.extend(left_edges.chain(right_edges));
+        }
+        tree
+    }
+
+    fn to_graphviz(&self) -> String {
+        let escape = |s: String| s.replace('\\', "\\\\").replace('"', "\\\"");
+
+        let mut buf = String::new();
+        write!(buf, "digraph G {{node [shape=box];").unwrap();
+        for (idx, node) in self.nodes.iter().enumerate() {
+            write!(
+                buf,
+                "n{idx} [label=\"{}\\n{}\"];",
+                escape(node.position.to_string()),
+                escape(node.value.to_string())
+            )
+            .unwrap();
+        }
+        for edge in &self.edges {
+            let color = if edge.left { "blue" } else { "red" };
+            let style = match edge.kind {
+                OptionKind::Canonical => "solid",
+                OptionKind::Dominated => "dashed",
+                OptionKind::Reversible => "bold",
+            };
+            write!(
+                buf,
+                "n{} -> n{} [color={color}, style={style}];",
+                edge.from, edge.to
+            )
+            .unwrap();
+        }
+        write!(buf, "}}").unwrap();
+        buf
+    }
+
+    fn to_tikz(&self, scale: f32) -> String {
+        let mut layers = HashMap::<usize, usize>::new();
+        let mut columns = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let column = layers.entry(node.depth).or_default();
+            columns.push(*column);
+            *column += 1;
+        }
+
+        let mut buf = String::new();
+        write!(
+            buf,
+            "\\begin{{tikzpicture}}[scale={scale}, every node/.style={{draw, rounded corners}}] "
+        )
+        .unwrap();
+        for (idx, node) in self.nodes.iter().enumerate() {
+            #[allow(clippy::cast_precision_loss)]
+            let x = 2.0 * (columns[idx] as f32 - (layers[&node.depth] - 1) as f32 / 2.0);
+            #[allow(clippy::cast_precision_loss)]
+            let y = -1.5 * node.depth as f32;
+            write!(
+                buf,
+                "\\node (n{idx}) at ({x}, {y}) {{${}$}}; ",
+                node.value.to_latex()
+            )
+            .unwrap();
+        }
+        for edge in &self.edges {
+            let color = if edge.left { "blue" } else { "red" };
+            let style = match edge.kind {
+                OptionKind::Canonical => "",
+                OptionKind::Dominated => ", dashed",
+                OptionKind::Reversible => ", very thick",
+            };
+            write!(
+                buf,
+                "\\draw[->, {color}{style}] (n{}) -- (n{}); ",
+                edge.from, edge.to
+            )
+            .unwrap();
+        }
+        write!(buf, "\\end{{tikzpicture}}").unwrap();
+        buf
+    }
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let position = parse_position(&args.position)
+        .with_context(|| format!("Invalid position '{}'", args.position))?;
+    let tree = Tree::build(position, args.depth, args.unfold);
+
+    let image = match args.format {
+        Format::Dot => tree.to_graphviz(),
+        Format::Tikz => tree.to_tikz(args.tikz_scale),
+    };
+
+    let mut output = args
+        .out_file
+        .create()
+        .context("Could not create output file")?;
+    writeln!(output, "{image}")?;
+
+    Ok(())
+}
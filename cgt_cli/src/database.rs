@@ -0,0 +1,119 @@
+//! SQLite store of computed values of positions, shared between runs of different commands
+
+use anyhow::{anyhow, Context, Result};
+use cgt::{
+    numeric::dyadic_rational_number::DyadicRationalNumber,
+    short::partizan::canonical_form::CanonicalForm,
+};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::{path::Path, str::FromStr};
+
+/// Computed values of a position
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub canonical_form: CanonicalForm,
+    pub temperature: DyadicRationalNumber,
+}
+
+impl Entry {
+    pub fn new(canonical_form: CanonicalForm) -> Self {
+        Self {
+            temperature: canonical_form.temperature(),
+            canonical_form,
+        }
+    }
+}
+
+/// Store of positions in the `game:payload` format and their values
+pub struct Database {
+    connection: Connection,
+}
+
+impl Database {
+    /// Open database file, creating it if it does not exist
+    pub fn open(path: &Path) -> Result<Self> {
+        let connection = Connection::open(path)
+            .with_context(|| format!("Could not open database '{}'", path.display()))?;
+        connection
+            .execute_batch(
+                "PRAGMA journal_mode = WAL;
+                 CREATE TABLE IF NOT EXISTS positions (
+                     position TEXT PRIMARY KEY NOT NULL,
+                     canonical_form TEXT NOT NULL,
+                     temperature TEXT NOT NULL
+                 );",
+            )
+            .context("Could not initialize database")?;
+        Ok(Self { connection })
+    }
+
+    /// Look up values of a position
+    pub fn get(&self, position: &str) -> Result<Option<Entry>> {
+        let row = self
+            .connection
+            .prepare_cached(
+                "SELECT canonical_form, temperature FROM positions WHERE position = ?1",
+            )?
+            .query_row(params![position], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .optional()?;
+
+        row.map(|(canonical_form, temperature)| {
+            Ok(Entry {
+                canonical_form: CanonicalForm::from_str(&canonical_form).map_err(|err| {
+                    anyhow!("Invalid canonical form '{canonical_form}' of '{position}': {err}")
+                })?,
+                temperature: DyadicRationalNumber::from_str(&temperature).map_err(|err| {
+                    anyhow!("Invalid temperature '{temperature}' of '{position}': {err}")
+                })?,
+            })
+        })
+        .transpose()
+    }
+
+    /// Look up values of many positions at once
+    pub fn get_all<'a>(
+        &self,
+        positions: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Vec<Option<Entry>>> {
+        positions
+            .into_iter()
+            .map(|position| self.get(position))
+            .collect()
+    }
+
+    /// Store values of positions in a single transaction. Positions already in the database are
+    /// left unchanged.
+    pub fn insert_all<'a>(
+        &mut self,
+        entries: impl IntoIterator<Item = (&'a str, &'a Entry)>,
+    ) -> Result<()> {
+        let transaction = self.connection.transaction()?;
+        {
+            let mut insert = transaction.prepare_cached(
+                "INSERT OR IGNORE INTO positions (position, canonical_form, temperature)
+                 VALUES (?1, ?2, ?3)",
+            )?;
+            for (position, entry) in entries {
+                insert.execute(params![
+                    position,
+                    entry.canonical_form.to_string(),
+                    entry.temperature.to_string()
+                ])?;
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Number of stored positions of each game, ordered by game name
+    pub fn count_by_game(&self) -> Result<Vec<(String, usize)>> {
+        let mut statement = self.connection.prepare(
+            "SELECT substr(position, 1, instr(position, ':') - 1) AS game, count(*)
+             FROM positions GROUP BY game ORDER BY game",
+        )?;
+        let rows = statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+}
@@ -0,0 +1,146 @@
+//! Encodings of records written by long running commands, like logs and snapshots of searches
+//!
+//! Records are either JSON objects, one per line, or a sequence of CBOR values after the CBOR
+//! magic number (self-described CBOR tag `d9 d9 f7`), which is smaller and faster to decode.
+//! Both can be compressed with gzip, shrinking logs of long runs about ten times. Readers detect
+//! the encoding and compression, so every command reading records accepts all of them.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use flate2::{read::MultiGzDecoder, write::GzEncoder, Compression};
+use serde::{de::DeserializeOwned, Serialize};
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+/// Self-described CBOR tag, starting files of CBOR records
+const CBOR_MAGIC: [u8; 3] = [0xd9, 0xd9, 0xf7];
+
+/// Magic number of gzip streams
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// JSON objects, one per line
+    Json,
+
+    /// Sequence of CBOR values
+    Cbor,
+}
+
+/// Writer of records in one of the [`Format`]s, optionally compressed
+pub struct Writer {
+    format: Format,
+    output: Box<dyn Write>,
+}
+
+impl Writer {
+    pub fn new(output: impl Write + 'static, format: Format, compress: bool) -> Result<Self> {
+        let mut output: Box<dyn Write> = if compress {
+            Box::new(GzEncoder::new(output, Compression::default()))
+        } else {
+            Box::new(output)
+        };
+        if format == Format::Cbor {
+            output.write_all(&CBOR_MAGIC)?;
+        }
+        Ok(Self { format, output })
+    }
+
+    pub fn write<T>(&mut self, record: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        match self.format {
+            Format::Json => {
+                serde_json::to_writer(&mut self.output, record)?;
+                self.output.write_all(b"\n")?;
+            }
+            Format::Cbor => ciborium::into_writer(record, &mut self.output)?,
+        }
+        Ok(())
+    }
+
+    /// Flush written records, so they can be read while the output is still being written.
+    /// Compressed output is complete only after the writer is dropped, but records flushed
+    /// before the process was killed can still be read
+    pub fn flush(&mut self) -> Result<()> {
+        self.output.flush()?;
+        Ok(())
+    }
+}
+
+/// Compressed stream that ends without the gzip trailer, e.g. of a killed run, is read until the
+/// last flushed record
+struct Truncated<R>(R);
+
+impl<R> Read for Truncated<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.0.read(buf) {
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(0),
+            result => result,
+        }
+    }
+}
+
+/// Read records in any [`Format`], compressed or not, as JSON values
+pub fn read_values(
+    input: impl Read + 'static,
+) -> Result<Box<dyn Iterator<Item = Result<serde_json::Value>>>> {
+    let mut input = BufReader::new(input);
+    if input.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        let decompressed = Truncated(MultiGzDecoder::new(input));
+        return read_decompressed(BufReader::new(decompressed));
+    }
+    read_decompressed(input)
+}
+
+fn read_decompressed(
+    mut input: BufReader<impl Read + 'static>,
+) -> Result<Box<dyn Iterator<Item = Result<serde_json::Value>>>> {
+    if !input.fill_buf()?.starts_with(&CBOR_MAGIC) {
+        let mut values =
+            serde_json::de::Deserializer::from_reader(input).into_iter::<serde_json::Value>();
+        return Ok(Box::new(std::iter::from_fn(move || {
+            match values.next()? {
+                Err(err) if err.is_eof() => incomplete(),
+                value => Some(value.context("Could not decode JSON record")),
+            }
+        })));
+    }
+
+    input.consume(CBOR_MAGIC.len());
+    Ok(Box::new(std::iter::from_fn(move || {
+        match input.fill_buf() {
+            Ok([]) => None,
+            Ok(_) => match ciborium::from_reader::<serde_json::Value, _>(&mut input) {
+                Err(ciborium::de::Error::Io(err)) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                    incomplete()
+                }
+                value => Some(value.context("Could not decode CBOR record")),
+            },
+            Err(err) => Some(Err(err).context("Could not read input")),
+        }
+    })))
+}
+
+/// Skip the last record cut short, e.g. by killing the process writing it
+fn incomplete<T>() -> Option<T> {
+    eprintln!("Warning: Skipping incomplete last record");
+    None
+}
+
+/// Read records in any [`Format`], compressed or not
+pub fn read<T>(input: impl Read + 'static) -> Result<Vec<T>>
+where
+    T: DeserializeOwned,
+{
+    read_values(input)?
+        .enumerate()
+        .map(|(idx, value)| {
+            serde_json::from_value(value?)
+                .with_context(|| format!("Could not decode record {}", idx + 1))
+        })
+        .collect()
+}
@@ -4,7 +4,10 @@ use clap::Parser;
 
 pub(crate) mod clap_utils;
 mod commands;
+mod database;
+mod encoding;
 mod io;
+mod progress;
 
 #[cfg(not(windows))]
 #[global_allocator]
@@ -0,0 +1,23 @@
+//! Progress bars of long computations, drawn on stderr only when it is a terminal
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// Progress bar of `len` steps with count, rate and estimated time left
+pub fn bar(len: u64, unit: &'static str) -> ProgressBar {
+    let style = ProgressStyle::with_template(
+        "{elapsed_precise} [{wide_bar}] {human_pos}/{human_len} {msg} ({per_sec}, ETA {eta})",
+    )
+    .expect("unreachable: template is valid")
+    .progress_chars("=> ");
+    ProgressBar::new(len).with_style(style).with_message(unit)
+}
+
+/// Progress spinner with count and rate, for computations of unknown length
+pub fn spinner(unit: &'static str) -> ProgressBar {
+    let style =
+        ProgressStyle::with_template("{elapsed_precise} {spinner} {human_pos} {msg} ({per_sec})")
+            .expect("unreachable: template is valid");
+    ProgressBar::new_spinner()
+        .with_style(style)
+        .with_message(unit)
+}
@@ -3,7 +3,7 @@ use std::fmt::Write;
 use cgt::{
     grid::{BitTile, FiniteGrid, Grid},
     numeric::{rational::Rational, v2f::V2f},
-    short::partizan::{thermograph::Thermograph, trajectory::Trajectory},
+    short::partizan::{position::NamedGame, thermograph::Thermograph, trajectory::Trajectory},
 };
 use imgui::{DrawListMut, ImColor32, StyleColor};
 
@@ -22,6 +22,29 @@ pub const DOMINEERING_TILE_GAP: f32 = 4.0;
 pub const DOMINEERING_EMPTY_COLOR: ImColor32 = ImColor32::from_rgb(0xcc, 0xcc, 0xcc);
 pub const DOMINEERING_FILLED_COLOR: ImColor32 = ImColor32::from_rgb(0x44, 0x44, 0x44);
 
+pub const INPUT_ERROR_COLOR: ImColor32 = ImColor32::from_rgb(0xdd, 0x00, 0x00);
+
+/// Position in the `game:payload` format, as used by `cgt-cli`
+pub fn position_text<G>(game: &G) -> String
+where
+    G: NamedGame,
+{
+    format!("{}:{}", G::NAME, game)
+}
+
+/// Parse position copied from [`position_text`] or `cgt-cli`, the game name prefix is optional
+pub fn parse_position_text<G>(text: &str) -> Option<G>
+where
+    G: NamedGame,
+{
+    let text = text.trim();
+    let payload = text
+        .strip_prefix(G::NAME)
+        .and_then(|payload| payload.strip_prefix(':'))
+        .unwrap_or(text);
+    G::from_str(payload).ok()
+}
+
 fn fade(mut color: [f32; 4], alpha: f32) -> [f32; 4] {
     let alpha = alpha.clamp(0.0, 1.0);
     color[3] *= alpha;
@@ -1,5 +1,5 @@
 use cgt::short::partizan::canonical_form::CanonicalForm;
-use imgui::{Condition, ImColor32};
+use imgui::Condition;
 use std::str::FromStr;
 
 use crate::{
@@ -70,10 +70,7 @@ impl IsCgtWindow for TitledWindow<CanonicalFormWindow> {
                 }
 
                 if self.content.input_error {
-                    ui.text_colored(
-                        ImColor32::from_rgb(0xdd, 0x00, 0x00).to_rgba_f32s(),
-                        "Invalid input",
-                    );
+                    ui.text_colored(widgets::INPUT_ERROR_COLOR.to_rgba_f32s(), "Invalid input");
                 }
 
                 widgets::game_details!(self, ui, draw_list);
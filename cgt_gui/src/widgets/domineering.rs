@@ -1,4 +1,5 @@
 use cgt::{
+    drawing::svg::Svg,
     grid::{small_bit_grid::SmallBitGrid, FiniteGrid, Grid},
     short::partizan::games::domineering::Domineering,
 };
@@ -16,6 +17,7 @@ pub struct DomineeringWindow {
     game: Domineering,
     details_options: DetailOptions,
     pub details: Option<Details>,
+    paste_error: bool,
 }
 
 impl DomineeringWindow {
@@ -24,6 +26,7 @@ impl DomineeringWindow {
             game: Domineering::from_str(".#.##|...##|#....|#...#|###..").unwrap(),
             details_options: DetailOptions::new(),
             details: None,
+            paste_error: false,
         }
     }
 }
@@ -72,9 +75,41 @@ impl IsCgtWindow for TitledWindow<DomineeringWindow> {
                         if ui.menu_item("Dump Tikz") {
                             println!("{}", self.content.game.to_latex());
                         };
+                        if ui.menu_item("Dump SVG") {
+                            let mut buf = String::new();
+                            if self.content.game.to_svg(&mut buf).is_ok() {
+                                println!("{buf}");
+                            }
+                        };
+                        if ui.menu_item("Copy Position") {
+                            ui.set_clipboard_text(widgets::position_text(&self.content.game));
+                        };
+                    }
+
+                    if let Some(_new_menu) = ui.begin_menu("Open") {
+                        if ui.menu_item("Paste Position") {
+                            match ui
+                                .clipboard_text()
+                                .and_then(|text| widgets::parse_position_text(&text))
+                            {
+                                Some(game) => {
+                                    self.content.game = game;
+                                    self.content.paste_error = false;
+                                    is_dirty = true;
+                                }
+                                None => self.content.paste_error = true,
+                            }
+                        };
                     }
                 }
 
+                if self.content.paste_error {
+                    ui.text_colored(
+                        widgets::INPUT_ERROR_COLOR.to_rgba_f32s(),
+                        "Clipboard does not contain a Domineering position",
+                    );
+                }
+
                 ui.columns(2, "Columns", true);
 
                 widgets::grid_size_selector(ui, &mut new_width, &mut new_height);
@@ -1,4 +1,5 @@
 use cgt::{
+    drawing::svg::Svg,
     graph::{
         adjacency_matrix::undirected::UndirectedGraph, layout::SpringEmbedder, Graph, VertexIndex,
     },
@@ -49,6 +50,7 @@ pub struct SnortWindow {
     edge_creates_vertex: bool,
     details_options: DetailOptions,
     details: Option<Details>,
+    paste_error: bool,
 }
 
 impl SnortWindow {
@@ -85,6 +87,7 @@ impl SnortWindow {
             edge_creates_vertex: true,
             details_options: DetailOptions::new(),
             details: None,
+            paste_error: false,
         }
     }
 
@@ -174,6 +177,50 @@ impl IsCgtWindow for TitledWindow<SnortWindow> {
                             }
                         }
                     }
+
+                    if let Some(_new_menu) = ui.begin_menu("Save") {
+                        if ui.menu_item("Dump Tikz") {
+                            println!("{}", self.content.game.to_tikz());
+                        };
+                        if ui.menu_item("Dump Graphviz") {
+                            println!("{}", self.content.game.to_graphviz());
+                        };
+                        if ui.menu_item("Dump SVG") {
+                            let mut buf = String::new();
+                            if self.content.game.to_svg(&mut buf).is_ok() {
+                                println!("{buf}");
+                            }
+                        };
+                        if ui.menu_item("Copy Position") {
+                            ui.set_clipboard_text(widgets::position_text(&self.content.game));
+                        };
+                    }
+
+                    if let Some(_new_menu) = ui.begin_menu("Open") {
+                        if ui.menu_item("Paste Position") {
+                            match ui
+                                .clipboard_text()
+                                .and_then(|text| widgets::parse_position_text(&text))
+                            {
+                                Some(game) => {
+                                    self.content.game = game;
+                                    // Vertices are drawn below, so they need positions right away
+                                    self.content.reposition_circle();
+                                    self.content.paste_error = false;
+                                    should_reposition = true;
+                                    is_dirty = true;
+                                }
+                                None => self.content.paste_error = true,
+                            }
+                        };
+                    }
+                }
+
+                if self.content.paste_error {
+                    ui.text_colored(
+                        widgets::INPUT_ERROR_COLOR.to_rgba_f32s(),
+                        "Clipboard does not contain a Snort position",
+                    );
                 }
 
                 ui.columns(2, "columns", true);
@@ -186,7 +233,7 @@ impl IsCgtWindow for TitledWindow<SnortWindow> {
                     |i| Cow::Borrowed(i),
                 );
                 ui.same_line();
-                should_reposition = ui.button("Reposition");
+                should_reposition |= ui.button("Reposition");
 
                 ui.combo(
                     "Edit Mode",
@@ -40,10 +40,9 @@ macro_rules! impl_py_partizan_game {
         impl $py_game {
             #[new]
             fn py_new(position: &str) -> PyResult<Self> {
-                let inner = $game::from_str(position)
-                    .or(Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                        "Parse error",
-                    )))?;
+                let inner = $game::from_str(position).map_err(|err| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Parse error: {err}"))
+                })?;
                 Ok(Self::from(inner))
             }
 
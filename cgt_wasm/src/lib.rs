@@ -0,0 +1,18 @@
+//! WebAssembly bindings of the `cgt` library for browser-based explorers
+//!
+//! Positions are created from strings in the `game:payload` format and evaluated to values.
+//! Dyadic rationals, like temperatures and stops, are returned as exact strings, e.g. `"3/2"`.
+
+use cgt::short::partizan::position::GAMES;
+use wasm_bindgen::prelude::*;
+
+mod position;
+mod value;
+
+pub use crate::{position::Position, value::Value};
+
+/// Names of games accepted by [`Position::new`]
+#[wasm_bindgen]
+pub fn games() -> Vec<String> {
+    GAMES.iter().map(|game| game.name.to_owned()).collect()
+}
@@ -0,0 +1,142 @@
+use crate::value::Value;
+use cgt::{
+    drawing::svg::Svg,
+    error::Error,
+    short::partizan::{
+        games::{
+            amazons::Amazons,
+            domineering::Domineering,
+            fission::Fission,
+            ski_jumps::SkiJumps,
+            snort::{DirectedSnort, Snort},
+            toads_and_frogs::ToadsAndFrogs,
+        },
+        position::{parse_position, AnyPosition, NamedGame},
+    },
+};
+use wasm_bindgen::prelude::*;
+
+/// Draw position of a game from its payload
+type Draw = fn(&str) -> Result<String, Error>;
+
+/// Games that can be drawn
+const DRAWABLE: &[(&str, Draw)] = &[
+    (Amazons::NAME, svg::<Amazons>),
+    (DirectedSnort::NAME, svg::<DirectedSnort>),
+    (Domineering::NAME, svg::<Domineering>),
+    (Fission::NAME, svg::<Fission>),
+    (SkiJumps::NAME, svg::<SkiJumps>),
+    (<Snort>::NAME, svg::<Snort>),
+    (ToadsAndFrogs::NAME, svg::<ToadsAndFrogs>),
+];
+
+fn svg<G>(payload: &str) -> Result<String, Error>
+where
+    G: NamedGame + Svg,
+{
+    let position = G::from_str(payload).map_err(Into::into)?;
+    let mut buf = String::new();
+    position
+        .to_svg(&mut buf)
+        .expect("Write to String should not fail");
+    Ok(buf)
+}
+
+/// Position of any game, in the `game:payload` format, e.g. `domineering:..|..`
+#[wasm_bindgen]
+pub struct Position {
+    inner: AnyPosition,
+}
+
+impl From<AnyPosition> for Position {
+    fn from(inner: AnyPosition) -> Self {
+        Self { inner }
+    }
+}
+
+#[wasm_bindgen]
+impl Position {
+    /// Parse position in the `game:payload` format
+    #[wasm_bindgen(constructor)]
+    pub fn new(position: &str) -> Result<Position, JsError> {
+        Ok(Self::from(parse_position(position)?))
+    }
+
+    /// Name of the game of the position
+    #[wasm_bindgen(getter)]
+    pub fn game(&self) -> String {
+        self.inner.game().to_owned()
+    }
+
+    /// Position in the `game:payload` format
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_string_js(&self) -> String {
+        self.inner.to_string()
+    }
+
+    /// Positions reachable with a move of Left
+    #[wasm_bindgen(js_name = leftMoves)]
+    pub fn left_moves(&self) -> Vec<Position> {
+        self.inner
+            .left_moves()
+            .into_iter()
+            .map(Self::from)
+            .collect()
+    }
+
+    /// Positions reachable with a move of Right
+    #[wasm_bindgen(js_name = rightMoves)]
+    pub fn right_moves(&self) -> Vec<Position> {
+        self.inner
+            .right_moves()
+            .into_iter()
+            .map(Self::from)
+            .collect()
+    }
+
+    /// Independent components of the position, whose sum is the position
+    pub fn decompositions(&self) -> Vec<Position> {
+        self.inner
+            .decompositions()
+            .into_iter()
+            .map(Self::from)
+            .collect()
+    }
+
+    /// Best move of Left, if Left can move
+    #[wasm_bindgen(js_name = bestLeftMove)]
+    pub fn best_left_move(&self) -> Option<Position> {
+        self.inner.best_left_move().map(Self::from)
+    }
+
+    /// Best move of Right, if Right can move
+    #[wasm_bindgen(js_name = bestRightMove)]
+    pub fn best_right_move(&self) -> Option<Position> {
+        self.inner.best_right_move().map(Self::from)
+    }
+
+    /// Canonical form of the position
+    pub fn evaluate(&self) -> Value {
+        Value::from(self.inner.canonical_form())
+    }
+
+    /// Outcome class of the position: `L`, `R`, `N` or `P`
+    pub fn outcome(&self) -> String {
+        self.inner.outcome().to_string()
+    }
+
+    /// Drawing of the position as an SVG image
+    #[wasm_bindgen(js_name = toSvg)]
+    pub fn to_svg(&self) -> Result<String, JsError> {
+        let game = self.inner.game();
+        let (_, draw) = DRAWABLE
+            .iter()
+            .find(|(name, _)| *name == game)
+            .ok_or_else(|| JsError::new(&format!("Drawing {game} positions is not supported")))?;
+        let position = self.inner.to_string();
+        let (_, payload) = position
+            .split_once(':')
+            .expect("unreachable: positions are displayed with game name");
+        Ok(draw(payload)?)
+    }
+}
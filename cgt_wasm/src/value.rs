@@ -0,0 +1,142 @@
+use cgt::{drawing::svg::Svg, short::partizan::canonical_form::CanonicalForm};
+use std::{cmp::Ordering, str::FromStr};
+use wasm_bindgen::prelude::*;
+
+/// Canonical form of a short partizan game, e.g. `{1|-1}` or `^*`
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct Value {
+    inner: CanonicalForm,
+}
+
+impl From<CanonicalForm> for Value {
+    fn from(inner: CanonicalForm) -> Self {
+        Self { inner }
+    }
+}
+
+#[wasm_bindgen]
+impl Value {
+    /// Parse value, e.g. `{1|-1}`, `^*` or `3/2`
+    #[wasm_bindgen(constructor)]
+    pub fn new(value: &str) -> Result<Value, JsError> {
+        CanonicalForm::from_str(value)
+            .map(Self::from)
+            .map_err(|err| JsError::new(&format!("Could not parse value '{value}': {err}")))
+    }
+
+    /// Value of an integer
+    pub fn integer(value: i32) -> Value {
+        Self::from(CanonicalForm::new_integer(i64::from(value)))
+    }
+
+    /// Shortest form of the value, e.g. `{1|-1}` or `^*`
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_string_js(&self) -> String {
+        self.inner.to_string()
+    }
+
+    /// Value in LaTeX, e.g. for rendering with KaTeX or MathJax
+    #[wasm_bindgen(js_name = toLatex)]
+    pub fn to_latex(&self) -> String {
+        self.inner.to_latex()
+    }
+
+    pub fn temperature(&self) -> String {
+        self.inner.temperature().to_string()
+    }
+
+    pub fn mean(&self) -> String {
+        self.inner.mean().to_string()
+    }
+
+    #[wasm_bindgen(js_name = leftStop)]
+    pub fn left_stop(&self) -> String {
+        self.inner.left_stop().to_string()
+    }
+
+    #[wasm_bindgen(js_name = rightStop)]
+    pub fn right_stop(&self) -> String {
+        self.inner.right_stop().to_string()
+    }
+
+    pub fn birthday(&self) -> u32 {
+        self.inner.birthday()
+    }
+
+    #[wasm_bindgen(js_name = isNumber)]
+    pub fn is_number(&self) -> bool {
+        self.inner.is_number()
+    }
+
+    #[wasm_bindgen(js_name = isNimber)]
+    pub fn is_nimber(&self) -> bool {
+        self.inner.is_nimber()
+    }
+
+    #[wasm_bindgen(js_name = isInfinitesimal)]
+    pub fn is_infinitesimal(&self) -> bool {
+        self.inner.is_infinitesimal()
+    }
+
+    /// Left options of the canonical form
+    #[wasm_bindgen(js_name = leftOptions)]
+    pub fn left_options(&self) -> Vec<Value> {
+        self.inner
+            .to_moves()
+            .left
+            .into_iter()
+            .map(Self::from)
+            .collect()
+    }
+
+    /// Right options of the canonical form
+    #[wasm_bindgen(js_name = rightOptions)]
+    pub fn right_options(&self) -> Vec<Value> {
+        self.inner
+            .to_moves()
+            .right
+            .into_iter()
+            .map(Self::from)
+            .collect()
+    }
+
+    /// Sum of values
+    pub fn add(&self, other: &Value) -> Value {
+        Self::from(&self.inner + &other.inner)
+    }
+
+    /// Difference of values
+    pub fn sub(&self, other: &Value) -> Value {
+        Self::from(&self.inner - &other.inner)
+    }
+
+    pub fn neg(&self) -> Value {
+        Self::from(-&self.inner)
+    }
+
+    pub fn equals(&self, other: &Value) -> bool {
+        self.inner == other.inner
+    }
+
+    /// `-1`, `0` or `1` if the value is less than, equal to or greater than `other`, and
+    /// `undefined` if they are confused
+    pub fn compare(&self, other: &Value) -> Option<i32> {
+        CanonicalForm::compare(&self.inner, &other.inner).map(|ordering| match ordering {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        })
+    }
+
+    /// Thermograph of the value as an SVG image
+    #[wasm_bindgen(js_name = thermographSvg)]
+    pub fn thermograph_svg(&self) -> String {
+        let mut buf = String::new();
+        self.inner
+            .thermograph()
+            .to_svg(&mut buf)
+            .expect("Write to String should not fail");
+        buf
+    }
+}
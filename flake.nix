@@ -107,6 +107,23 @@
           };
         };
 
+        checks = {
+          cgt-domineering-table = (mkCgtTools {inherit pkgs;}).overrideAttrs (_: {
+            name = "cgt-domineering-table";
+            cargoBuildFlags = ["-p cgt"];
+            cargoTestFlags = ["-p cgt" "--features domineering_table"];
+            buildInputs = [];
+            doCheck = true;
+          });
+          cgt-bigint = (mkCgtTools {inherit pkgs;}).overrideAttrs (_: {
+            name = "cgt-bigint";
+            cargoBuildFlags = ["-p cgt"];
+            cargoTestFlags = ["-p cgt" "--features bigint"];
+            buildInputs = [];
+            doCheck = true;
+          });
+        };
+
         packages = {
           cgt-tools-x86_64-windows = mkCgtTools {
             pkgs = pkgs.pkgsCross.mingwW64;